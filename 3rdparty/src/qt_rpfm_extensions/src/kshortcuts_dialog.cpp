@@ -111,6 +111,9 @@ extern "C" void shortcut_collection_init(QWidget* parent, QList<QObject*>* short
     new_action(pack_tree_actions, "merge_files", "Merge Files", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString("Ctrl+M"), "merge");
     new_action(pack_tree_actions, "update_files", "Update Tables", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "edit-text-frame-update");
     new_action(pack_tree_actions, "generate_missing_loc_data", "Generate Missing Loc Data", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "languages");
+    new_action(pack_tree_actions, "generate_missing_loc_entries", "Generate Missing Loc Entries", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "languages");
+    new_action(pack_tree_actions, "add_to_favorites", "Add to Favorites", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "bookmark-new");
+    new_action(pack_tree_actions, "remove_from_favorites", "Remove from Favorites", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "bookmark-remove");
     new_action(pack_tree_actions, "delete", "Delete", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString("Del"), "entry-delete");
     new_action(pack_tree_actions, "extract", "Extract", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString("Ctrl+E"), "archive-extract");
     new_action(pack_tree_actions, "rename", "Rename", Qt::ShortcutContext::WidgetShortcut, {QKeySequence("Ctrl+R"), QKeySequence("F2")}, "edit-move");
@@ -147,6 +150,11 @@ extern "C" void shortcut_collection_init(QWidget* parent, QList<QObject*>* short
     new_action(diagnostics_table_actions, "ignore_diagnostic_for_file", "Ignore Diagnostic for File", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "mail-thread-ignored");
     new_action(diagnostics_table_actions, "ignore_diagnostic_for_file_field", "Ignore Diagnostic in Field for File", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "mail-thread-ignored");
     new_action(diagnostics_table_actions, "ignore_diagnostic_for_pack", "Ignore Diagnostic for Pack", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "mail-thread-ignored");
+    new_action(diagnostics_table_actions, "snooze_diagnostic", "Snooze Diagnostic", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "appointment-soon");
+    new_action(diagnostics_table_actions, "export_reproducer", "Export Reproducer", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "document-export");
+    new_action(diagnostics_table_actions, "copy_as_tsv", "Copy as TSV", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "edit-copy");
+    new_action(diagnostics_table_actions, "fix_keep_first", "Fix: Keep First", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "tools-wizard");
+    new_action(diagnostics_table_actions, "fix_keep_last", "Fix: Keep Last", Qt::ShortcutContext::WidgetShortcut, QKeySequence::listFromString(""), "tools-wizard");
     diagnostics_table_actions->readSettings();
 
     // AnimPack Tree Context actions.
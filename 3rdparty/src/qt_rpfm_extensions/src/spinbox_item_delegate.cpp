@@ -7,16 +7,17 @@
 #include <QSettings>
 
 // Function to be called from any other language. This assing to the provided column of the provided TableView a QSpinBoxItemDelegate.
-// We have to pass it the integer type (16, 32 or 64) too for later checks.
-extern "C" void new_spinbox_item_delegate(QObject *parent, const int column, const int integer_type, QTimer* timer, bool is_dark_theme_enabled, bool has_filter, bool right_side_mark) {
-    QSpinBoxItemDelegate* delegate = new QSpinBoxItemDelegate(parent, integer_type, timer, is_dark_theme_enabled, has_filter, right_side_mark);
+// We have to pass it the integer type (16, 32 or 64) and if it's unsigned too for later checks.
+extern "C" void new_spinbox_item_delegate(QObject *parent, const int column, const int integer_type, bool is_unsigned, QTimer* timer, bool is_dark_theme_enabled, bool has_filter, bool right_side_mark) {
+    QSpinBoxItemDelegate* delegate = new QSpinBoxItemDelegate(parent, integer_type, is_unsigned, timer, is_dark_theme_enabled, has_filter, right_side_mark);
     dynamic_cast<QAbstractItemView*>(parent)->setItemDelegateForColumn(column, delegate);
 }
 
 // Constructor of QSpinBoxItemDelegate. We use it to store the integer type of the value in the delegate.
-QSpinBoxItemDelegate::QSpinBoxItemDelegate(QObject *parent, const int integer_type, QTimer* timer, bool is_dark_theme_enabled, bool has_filter, bool right_side_mark): QExtendedStyledItemDelegate(parent)
+QSpinBoxItemDelegate::QSpinBoxItemDelegate(QObject *parent, const int integer_type, bool is_unsigned, QTimer* timer, bool is_dark_theme_enabled, bool has_filter, bool right_side_mark): QExtendedStyledItemDelegate(parent)
 {
     type = integer_type;
+    unsigned_type = is_unsigned;
     diag_timer = timer;
     dark_theme = is_dark_theme_enabled;
     use_filter = has_filter;
@@ -47,8 +48,8 @@ QWidget* QSpinBoxItemDelegate::createEditor(QWidget *parent, const QStyleOptionV
         diag_timer->stop();
     }
 
-    // SpinBoxes only support i16, i32, not i64, so for i64 we use a linedit with validation.
-    if (type == 64) {
+    // SpinBoxes only support i16, i32, not i64 or unsigned values, so for those we use a linedit with validation.
+    if (type == 64 || unsigned_type) {
         QLineEdit* lineEdit = new QLineEdit(parent);
         return lineEdit;
     }
@@ -68,7 +69,7 @@ QWidget* QSpinBoxItemDelegate::createEditor(QWidget *parent, const QStyleOptionV
 
 // Function called after the spinbox/linedit it's created. It just gives it his initial value (the one currently in the model).
 void QSpinBoxItemDelegate::setEditorData(QWidget *editor, const QModelIndex &index) const {
-    if (type == 64) {
+    if (type == 64 || unsigned_type) {
         QLineEdit* lineEdit = static_cast<QLineEdit*>(editor);
         QString value = index.model()->data(index, Qt::EditRole).toString();
         lineEdit->setText(value);
@@ -83,8 +84,21 @@ void QSpinBoxItemDelegate::setEditorData(QWidget *editor, const QModelIndex &ind
 // Function to be called when we're done. It just takes the value in the spinbox/linedit and saves it in the Table Model.
 void QSpinBoxItemDelegate::setModelData(QWidget *editor, QAbstractItemModel *model, const QModelIndex &index) const {
 
+    // For unsigned values, we need to check before that the data is valid and fits the type. Otherwise, we don't pass it to the model.
+    if (unsigned_type) {
+        QLineEdit* lineEdit = static_cast<QLineEdit*>(editor);
+        bool ok;
+        unsigned long long value = lineEdit->text().toULongLong(&ok);
+        if (ok) {
+            if (type == 64) { model->setData(index, value, Qt::EditRole); }
+            else if ((type == 32 && value <= UINT_MAX) || (type == 16 && value <= USHRT_MAX)) {
+                model->setData(index, static_cast<unsigned int>(value), Qt::EditRole);
+            }
+        }
+    }
+
     // For i64, we need to check before that the data is valid. Otherwise, we don't pass it to the model.
-    if (type == 64) {
+    else if (type == 64) {
         QLineEdit* lineEdit = static_cast<QLineEdit*>(editor);
         bool ok;
         signed long long value = lineEdit->text().toLongLong(&ok);
@@ -448,6 +448,11 @@ settings_game_label = Game Folder
 settings_asskit_label = Assembly Kit Folder
 settings_game_line_ph = This is the folder where you have {"{"}{"}"} installed, where the .exe is.
 settings_asskit_line_ph = This is the folder where you have the Assembly kit for {"{"}{"}"} installed.
+settings_loc_key_pattern_label = Loc Key Pattern
+settings_loc_key_pattern_line_ph = Regex loc keys must match. Leave empty to use the default rule.
+tt_settings_loc_key_pattern = Regular expression the full key of every loc entry of this game must match to not be reported by the Invalid Loc Key diagnostic. If empty or invalid, the default rule (no line jumps or tabs in keys) is used.
+settings_loc_formatting_rules_label = Loc Formatting Rules
+tt_settings_loc_formatting_rules = Rules used by the Malformed Loc Formatting diagnostic to check the texts of the loc files of this game, separated by ";". Use "pair:OPEN:CLOSE" for tokens that must be balanced, like "pair:[[:]]", and "pattern:REGEX" for suspicious text that should not be in a loc text. Escape ";" as "\;" if it's part of a rule, and ":" and "\" as "\:" and "\\" in pair tokens. If empty, the default rules are used.
 
 settings_ui_title = UI Settings
 settings_table_title = Table Settings
@@ -465,6 +470,7 @@ settings_ui_window_hide_background_icon = Hide Background Game Selected Icon:
 
 settings_select_file = Select File
 settings_select_folder = Select Folder
+settings_game_path_invalid = The folder selected doesn't seem to be a valid {"{"}{"}"} install: {"{"}{"}"}.
 
 settings_extra_title = Extra Settings
 settings_default_game = Default Game:
@@ -486,6 +492,9 @@ settings_diagnostics_title = Diagnostics Settings
 settings_diagnostics_show_panel_on_boot = Enable Diagnostics Tool:
 settings_diagnostics_trigger_on_open = Trigger Diagnostics Check on Open PackFile:
 settings_diagnostics_trigger_on_edit = Trigger Diagnostics Check on Table Editing:
+settings_run_diagnostics_on_open = Always Run Diagnostics on Open PackFile:
+settings_run_diagnostics_on_open_max_size = Max Pack Size to Always Run Diagnostics (MB):
+settings_diagnostics_trigger_on_edit_delay = Delay Before Checking Edited Tables (ms):
 
 settings_text_title = Text Editor Settings
 
@@ -524,6 +533,9 @@ tt_debug_check_for_missing_table_definitions_tip = If you enable this, RPFM will
 tt_diagnostics_enable_diagnostics_tool_tip = Enable this to make the diagnostics panel appear on start.
 tt_diagnostics_trigger_diagnostics_on_open_tip = Enable this to trigger a full PackFile Diagnostics check when opening a PackFile.
 tt_diagnostics_trigger_diagnostics_on_table_edit_tip = Enable this to trigger a limited diagnostics check each time you edit a table.
+tt_diagnostics_run_diagnostics_on_open_tip = Enable this to always run a full diagnostics check after opening a PackFile, opening the diagnostics panel if it's hidden. It does nothing if there is no schema loaded for the game selected.
+tt_diagnostics_run_diagnostics_on_open_max_size_tip = PackFiles bigger than this size (in MB) are not automatically checked when opened, to avoid long stalls. You can still check them manually. Set it to 0 to have no limit.
+tt_diagnostics_trigger_diagnostics_on_table_edit_delay_tip = Time (in milliseconds) to wait after the last edit of a table before checking it again, if diagnostics are triggered on table editing. Edits done while waiting are checked together. Minimum is 100ms.
 
 ### CA_VP8 Videos
 
@@ -698,6 +710,7 @@ context_menu_paste_as_new_row = Paste as New Row
 
 gen_loc_diagnostics = Diagnostics
 diagnostics_button_check_packfile = Check PackFile
+diagnostics_button_export = Export the visible results to a JSON or CSV file
 diagnostics_button_check_current_packed_file = Check Open PackedFiles Only
 diagnostics_button_error = Error
 diagnostics_button_warning = Warning
@@ -775,6 +788,8 @@ pfs_import_files_to_ignore_label = <h3>Files to Ignore when Importing</h3>
 pfs_import_files_to_ignore_description_label = <p>The files on this list will be ignored when importing from a MyMod folder. Only for MyMods. Paths are relative, the glory of the empire is absolute.</p>
 pfs_disable_autosaves_label = <h3>Disable Autosaves for this PackFile</h3>
 pfs_disable_autosaves_description_label = <p></p>
+pfs_readonly_paths_label = <h3>Read-Only Paths</h3>
+pfs_readonly_paths_description_label = <p>The files and folders on this list cannot be edited: their views won't allow edits, and saving, importing or replacing over them will fail. One path per line. To lock a whole table, use its folder (for example, db/units_tables). Lines starting with # are ignored.</p>
 
 instructions_ca_vp8 = It's simple, the video can have 2 formats: CAMV (used by the game) and IVF (reproducible on a media player with VP8 codecs).
   To export a video, convert it to IVF and extract it.
@@ -919,7 +934,7 @@ duplicated_row_explanation = Table rows usually convey one specific data to the
 
     This can cause problems and it's advised to leave only one copy of each row in a table.
 
-invalid_loc_key_explanation = RPFM has detected that one row from your Loc file has a key with invalid characters in it. This can cause all sort of problems, including crashes, so it's better to fix it ASAP.
+invalid_loc_key_explanation = RPFM has detected that one row from your Loc file has a key with invalid characters in it, or one not matching the Loc Key Pattern configured for the game. This can cause all sort of problems, including crashes, so it's better to fix it ASAP.
     A common cause of this is an old bug in PFM code (yes, PFM) that causes Loc Keys to get invalid characters added at their end if you copy/paste them.
 
     To fix it, edit the reported cell and delete any invalid (and often invisible) characters on it.
@@ -972,7 +987,7 @@ dependencies_cache_not_generated_explanation = The dependencies cache hasn't bee
     Remember to do this after a game patch too, so the cache gets updated with the new changes.
 
 label_invalid_packfile_name = Invalid PackFile Name
-invalid_packfile_name_explanation = PackFile names cannot contain whitespace characters.
+invalid_packfile_name_explanation = PackFile names must end in ".pack", cannot contain whitespaces or characters not supported by the OS, cannot use the name of a vanilla PackFile, and cannot be too long.
 
     To fix it, replace any whitespace in the PackFile's name with underscores.
 
@@ -1087,7 +1102,7 @@ settings_ui_table_colour_light_label = Light theme
 settings_ui_table_colour_dark_label = Dark theme
 
 label_incorrect_game_path = Incorrect Game Path:
-incorrect_game_path_explanation = RPFM detected that the Game Path you set in the settings is incorrect.
+incorrect_game_path_explanation = RPFM detected that the Game Path you set in the settings is incorrect: {"{"}{"}"}.
     This path is needed for many, MANY features to work properly. So set it up properly.
 
 generate_dependencies_cache_warn = No Assembly Kit found installed for this game, or the path for it is not correctly configured. This means RPFM will still try to generate the Dependencies Cache, but the diagnostics tool may generate a bunch of false positives.
@@ -1127,6 +1142,8 @@ unit_editor_title = Unit Editor
 
 settings_enable_esf_editor = Enable ESF/CCD/SAVE Editor (EXPERIMENTAL):
 tt_settings_debug_enable_esf_editor = This setting allows you to enable the new ESF editor (experimental), but beware of issues.
+settings_debug_enable_timing_logs = Log Search/Diagnostics Timings:
+tt_settings_debug_enable_timing_logs = If enabled, RPFM will write to the log how long each phase of a Global Search or a Diagnostics check takes, per file type. Only useful to find out why a search or check is slow.
 
 settings_enable_unit_editor = Enable Unit Editor (EXPERIMENTAL):
 tt_settings_debug_enable_unit_editor = This setting allows you to enable the new Unit editor (experimental), but beware of issues.
@@ -1144,6 +1161,8 @@ tools_unit_multiplayer = Multiplayer
 tools_unit_extra_data = Extra Data
 copy_unit = Copy Unit
 generate_dependencies_cache_in_progress_message = Generating Dependencies Cache... this may take a while.
+generate_dependencies_cache_progress = Generating Dependencies Cache: %v/%m
+generate_dependencies_cache_already_running = The Dependencies Cache is already being generated. Wait until it finishes.
 copy_unit_instructions = <p>Write the new unit's key in the input field, and hit accept. Also, note:</p>
     <ul>
         <li>Existing unit keys are not valid.</li>
@@ -1153,6 +1172,8 @@ copy_unit_instructions = <p>Write the new unit's key in the input field, and hit
 copy_unit_new_unit_name = Unit Key
 settings_disable_file_previews = Disable PackedFile Previews
 tt_settings_disable_file_previews_tip = Check this to make RPFM always open PackedFiles as non-preview, so they'll not get closed when opening another PackedFile.
+settings_deduplicate_compression = Compress Identical Files Only Once
+tt_settings_deduplicate_compression_tip = Check this to make RPFM compress files with identical data only once when saving compressed Packs. It makes saving Packs with many duplicated files faster. The saved Pack is exactly the same either way.
 variant_editor_title = Variant Editor
 variants_variant_filename = Variant Mesh FileName
 variants_mesh_editor_title = Variant Mesh Editor
@@ -1204,6 +1225,7 @@ debug_colour_dark_label = Dark Theme
 
 debug_colour_local_tip_label = Local
 debug_colour_remote_tip_label = Remote
+readonly_table_warning = <p><b>This table is marked as read-only in the Pack Settings. Remove it from the Read-Only Paths list to edit it.</b></p><p></p>
 banned_tables_warning = <p><b style="color:red;">WARNING: This table is actively check by the game, and changes to it will cause the game to crash. RPFM will not save any edit you make to it, and if you have it edited in your PackFile, it's recomended you delete it</b></p><p></p>
 label_banned_table = Banned Table detected:
 banned_table_explanation = Banned Tables are tables actively check by the game to ensure they haven't been altered. Altering them means your game will crash. Which means... there's not really an use for them on the modding side of things, other than being informative.
@@ -1231,6 +1253,13 @@ view_toggle_references_panel = Toggle References Window
 tt_settings_debug_clear_dependencies_cache_folder = Use this to clear the dependencies cache folder. In case you don't want RPFM to eat a lot of RAM.
 settings_debug_clear_dependencies_cache_folder = Clear dependencies cache folder
 context_menu_generate_missing_loc_data = Generate Loc Data
+context_menu_generate_missing_loc_entries = Generate Missing Loc Entries
+generate_missing_loc_entries = Generate Missing Loc Entries
+generate_missing_loc_entries_placeholder = Path of the Loc file where the missing entries will be added. It'll be created if it doesn't exist.
+generate_missing_loc_entries_success = {"{"}{"}"} missing loc entries generated.
+context_menu_add_to_favorites = Add to Favorites
+context_menu_remove_from_favorites = Remove from Favorites
+context_menu_favorite_tables = Favorite Tables
 about_check_lua_autogen_updates = Check TW Autogen Updates
 settings_check_lua_autogen_updates_on_start = Check TW Autogen Updates on Start:
 tt_about_check_lua_autogen_updates = Checks if there is any update available for the TW Autogen data. This helps in developing MyMods with lua scripts.
@@ -1339,6 +1368,10 @@ delete_empty_folders_on_delete = Delete empty folders after moving/deleting its
 settings_delete_empty_folders_on_delete = If this is enabled, after certain operations that left empty folders, said folders will be automatically removed.
 schema_patch_submitted_with_empty_explanation = Patch not submitted because the explanation was empty.
 diagnostics_check_ak_only_refs = Check AK Only References (may trigger false positives)
+diagnostics_source = Source of the files to check. Parent and game files are checked in the context of the full load order, without the open Pack.
+diagnostics_source_packfile = Packfile
+diagnostics_source_parent = Parent Files
+diagnostics_source_game = Game Files
 title_changes_detected_in_dark_theme_config = Changes detected in dark Theme StyleSheet
 message_changes_detected_in_dark_theme_config = <p>You see this message because RPFM just got updated and, either the update contains changes for the dark theme, or you made custom changes to the dark-theme-custom.qss file at some point.</p>
     <p>If you didn't edit the file dark-theme-custom.qss, press Yes to import the updated dark theme. If you changed said file with your custom theme, press No and manually import the changes you want from dark-theme.qss to dark-theme-custom.qss.</p>
@@ -1591,3 +1624,85 @@ diagnostics_hint = Hover the diagnostics for more info.
 filter_variant_source = Source
 filter_variant_lookup = Lookup
 filter_variant_both = Both
+diagnostics_button_load_more = Load More Results ({"{"}{"}"} of {"{"}{"}"} loaded)
+auto_refresh_dependency_cache = Auto-Refresh Dependencies Cache
+settings_auto_refresh_dependency_cache = If you enable this, when opening a Pack or changing the game path, RPFM will check if the dependencies cache is missing or outdated and, if it is, it'll regenerate it automatically. It does nothing if the game path is not correctly configured.
+settings_mark_tsv_import_changes = Mark Changes on TSV Import:
+settings_tsv_trim_trailing_newline = Remove Trailing Newline on TSV Export:
+tt_ui_table_tsv_trim_trailing_newline_tip = By default, exported TSV files end with a single trailing newline. If you enable this, RPFM will remove it, so the last line of the file is the last row of the table.
+tt_ui_table_mark_tsv_import_changes_tip = If you enable this, when importing a TSV into an open table, RPFM will mark the rows it added and the cells it changed (matching rows by their key columns), so you can review the import before saving. These marks are never saved to the file.
+settings_max_decode_bytes = Max File Size to Decode (bytes):
+tt_settings_max_decode_bytes = Files bigger than this size (in bytes) are not decoded during a Global Search or a Diagnostics check. Instead, they're skipped and reported as skipped. Set it to 0 to have no limit.
+settings_diagnostics_referenced_path_rules = Text File Path Rules (Diagnostics):
+settings_diagnostics_referenced_path_rules_ph = extension:attribute, separated by commas. For example: xml:path,xml:image
+tt_settings_diagnostics_referenced_path_rules = Rules used by the diagnostics to find file paths referenced in text files, as a comma-separated list of "extension:attribute" pairs. For example, "xml:path" checks that every path="..." in xml files points to a file that exists in the Pack or its dependencies. Leave it empty to not check text files.
+global_search_skipped_too_large = Skipped: too large
+global_search_skipped_files = Skipped Files
+label_file_too_large_to_check = File too large to check
+file_too_large_to_check_explanation = This file is bigger than the max file size to decode configured in the settings, so it has not been checked.
+diagnostics_filter_case_sensitive = Make the path and message filters case sensitive.
+diagnostics_path_filter = Filter by path prefix...
+diagnostics_message_filter = Filter by message...
+save_packfile_invalid_name = The PackFile has been saved, but its name may cause problems: {"{"}{"}"}.
+global_search_use_hex = Search Hex Pattern
+tt_global_search_use_hex_checkbox = Enable searching for a hex byte pattern (like "DE AD BE EF") on the raw data of Unknown files, instead of a text pattern.
+global_search_whole_word = Match Whole Words
+tt_global_search_whole_word_checkbox = Only match whole words, so searching "unit" doesn't match "unit_key". Ignored when using Regex.
+global_search_key_columns_only = Search Only Key/Referenced Columns
+tt_global_search_key_columns_only_checkbox = Restrict the search on DB tables to key columns and columns referenced by other tables. Useful to find where something is defined, instead of everywhere it's used.
+global_search_favorites_only = Search Only Favorite Tables
+tt_global_search_favorites_only_checkbox = Restrict the search on DB tables to the tables marked as favorites for the game selected. Other file types are searched as usual. If there are no favorites, all tables are searched.
+global_search_row_matches = Group Table Matches By Row
+tt_global_search_row_matches_checkbox = Report one match per row with matches on tables, listing the columns with matches, instead of one per match. Useful to keep the results manageable on dense tables. Replacing a row match replaces all the matches of the row.
+diagnostics_colum_snoozed = Snoozed
+snooze_diagnostic = Snooze Diagnostic
+diagnostics_export_reproducer = Export Reproducer
+diagnostics_export_results = Export Diagnostics Results
+diagnostics_copy_as_tsv = Copy as TSV
+settings_ui_table_colour_diagnostics_panel_error_label = Diagnostics Panel Error
+settings_ui_table_colour_diagnostics_panel_warning_label = Diagnostics Panel Warning
+settings_ui_table_colour_diagnostics_panel_info_label = Diagnostics Panel Info
+label_unknown_column_in_data = Unknown Column in Data:
+unknown_column_in_data_explanation = This column doesn't exist in the latest definition of the table. If you update the table, the data in this column will be lost.
+column_tooltip_field_type = This column has no description. Type: <i>{"{"}{"}"}</i>.
+label_field_fails_validation_pattern = Field Fails Validation Pattern:
+field_fails_validation_pattern_explanation = The value of this cell doesn't match the validation pattern the schema defines for its column.
+label_invalid_validation_pattern = Invalid Validation Pattern:
+invalid_validation_pattern_explanation = The validation pattern the schema defines for this column is not a valid regex, so the column cannot be validated. Fix it in the schema or in your local schema patches.
+label_schema_reference_cycle = Schema Reference Cycle:
+schema_reference_cycle_explanation = The schema has a group of tables referencing each other in a loop ({"{"}{"}"}). This is not an error in your Pack, but tools following references recursively may loop forever on these tables, and it usually means the schema references are wrong.
+label_referenced_file_not_found = Referenced File Not Found
+referenced_file_not_found_explanation = RPFM cannot find the file referenced in this line of a text file, neither in the Pack nor in its dependencies. Which attributes are checked in which text files can be configured in the settings. If the file doesn't exist, the game will most likely show a missing texture or fail to load whatever uses it.
+label_duplicate_field_name = Duplicate Field Name:
+duplicate_field_name_explanation = The schema has a table definition with more than one field using the same name. This breaks anything that maps columns by name, like TSV imports or references, and must be fixed in the schema.
+label_invalid_schema_reference = Invalid Schema Reference:
+invalid_schema_reference_explanation = The schema has a field referencing its own column, or a column of its own table that doesn't exist. These references can never be valid, and must be fixed in the schema.
+label_table_name_folder_mismatch = Table not in its table folder
+table_name_folder_mismatch_explanation = The game only loads tables inside a db/table_name/ folder matching their table type. This usually happens after moving a table to the wrong folder.
+label_enum_value_not_encodable = Enum value not encodable
+enum_value_not_encodable_explanation = The value of this enum cell is not one of the values of the enum, nor a number. When saving, it'll be replaced with the default value of the column, losing it.
+label_duplicated_combined_keys_conflicting = Duplicated combined keys with different data
+duplicated_combined_keys_conflicting_explanation = There are multiple rows with the same combined keys, but with different data in the rest of their columns. Only one of them will be used by the game, and which one is not guaranteed. Use the "Keep First" or "Keep Last" fixes in the context menu to remove the extra rows.
+label_malformed_loc_formatting = Malformed Loc Formatting
+malformed_loc_formatting_explanation = The text of this loc entry has unbalanced formatting tokens (like "[[col:red]]" without its closing "[[/col]]") or text matching one of the suspicious patterns configured for the game. This can show garbled text or even crash the game. The rules can be changed per game in the settings.
+label_inconsistent_escaping = Inconsistent Escaping
+inconsistent_escaping_explanation = A text cell of this table contains raw control characters, double-escaped line jumps/tabulations (like "\\n"), or line jumps/tabulations written differently (raw or escaped as "\n") than in the rest of the table. Paths, like "ui\new", are not checked. The game may show them differently depending on how they were written, so you should normalize them.
+label_empty_column = Empty Column
+empty_column_explanation = String column is empty in all the rows of the table. If it's empty in all the tables of its type, it may be a candidate to be removed from the data. Key columns and non-string columns are never reported, as key columns are required by the schema, and zero or false are valid values for the rest.
+label_self_reference = Self Reference
+self_reference_explanation = A reference column of the row points to the key of the same row. This can cause loops in-game. Only references to the same table are checked.
+label_missing_loc_entry = Missing Loc Entry
+missing_loc_entry_explanation = This happens when a row of a table with localised fields has no loc entry for one of them, neither in the Pack nor in its dependencies. The game will show an empty text for it. Rows with an empty key are not checked.
+diagnostics_fix_keep_first = Fix: Keep First
+diagnostics_fix_keep_last = Fix: Keep Last
+settings_normalize_floats_on_edit = Normalize Floats on Edit:
+settings_normalize_floats_precision = Normalize Floats Precision:
+tt_ui_table_normalize_floats_on_edit_tip = If you enable this, when you edit a float cell RPFM will store its value the same way it's shown and exported, removing float noise (like 0.5000004 becoming 0.5). The value is only changed if the normalized one is equal to it, so it never changes the actual magnitude of the value.
+tt_ui_table_normalize_floats_precision_tip = Maximum amount of decimals used when normalizing edited float cells. It cannot be lower than the amount of decimals shown in the tables.
+settings_tsv_float_precision = TSV Float Precision:
+settings_tsv_colour_format = TSV Colour Format:
+settings_tsv_colour_format_as_is = As Is
+settings_tsv_colour_format_uppercase = Uppercase
+settings_tsv_colour_format_lowercase = Lowercase
+tt_ui_table_tsv_float_precision_tip = Maximum amount of decimals used for floats when exporting tables to TSV. Trailing zeroes are removed. It cannot be lower than the amount of decimals shown in the tables.
+tt_ui_table_tsv_colour_format_tip = How colours are written when exporting tables to TSV: as they're stored, or with their hexadecimal digits in uppercase or lowercase.
@@ -180,6 +180,14 @@ pub enum CommandsDependencies {
         /// Optional.
         #[arg(short, long, required = false, value_name = "ASSEMBLY_KIT_PATH")]
         assembly_kit_path: Option<PathBuf>,
+    },
+
+    /// Check the integrity of a dependencies cache, and fix the issues that don't require regenerating it.
+    Repair {
+
+        /// Path of the dependencies cache to repair.
+        #[arg(short = 'P', long, required = true, value_name = "PAK2_PATH")]
+        pak_path: PathBuf,
     }
 }
 
@@ -243,6 +251,24 @@ pub enum CommandsPack {
         folder_path: Vec<(PathBuf, String)>,
     },
 
+    /// Builds a new Pack in the provided path out of a folder on disk, using the path of each file relative to the folder as its path within the Pack.
+    FromFolder {
+
+        /// Path where the new Pack will be saved.
+        #[arg(short, long, required = true, value_name = "PACK_PATH")]
+        pack_path: PathBuf,
+
+        /// Folder with the files to add to the Pack.
+        #[arg(short = 'F', long, required = true, value_name = "FOLDER_PATH")]
+        folder_path: PathBuf,
+
+        /// If enabled, if a tsv file is detected in the folder, the program will try to import it to binary before adding it to the Pack.
+        ///
+        /// It requires the path of the Schema you want to use for definition resolving.
+        #[arg(short, long, required = false, value_name = "SCHEMA_PATH")]
+        tsv_to_binary: Option<PathBuf>,
+    },
+
     /// Deletes a file/folder from the Pack in the provided path.
     Delete {
 
@@ -276,6 +302,16 @@ pub enum CommandsPack {
         #[arg(short, long, required = false, value_name = "SCHEMA_PATH")]
         tables_as_tsv: Option<PathBuf>,
 
+        /// Maximum amount of decimals used for floats in tables extracted as TSV. Trailing zeroes are removed.
+        ///
+        /// If not provided, the same precision used to show them in the UI is used.
+        #[arg(long, required = false, value_name = "DECIMALS")]
+        tsv_float_precision: Option<usize>,
+
+        /// How colours are written in tables extracted as TSV: as they're stored, or with their hexadecimal digits in uppercase or lowercase.
+        #[arg(long, required = false, default_value = "as-is", value_parser = PossibleValuesParser::new(["as-is", "uppercase", "lowercase"]), value_name = "FORMAT")]
+        tsv_colour_format: String,
+
         /// File to extract, and folder where to extract it to, separated by semicolon. If no folder to extract to is provided, it'll extract the file to the current folder.
         ///
         /// This can be repeated as many times as files you want to extract.
@@ -311,6 +347,62 @@ pub enum CommandsPack {
         /// You can specify multiple packs to perform a diagnostics check over all of them.
         #[arg(short, long, required = true, num_args = 1.., value_name = "PACK_PATH")]
         pack_path: Vec<PathBuf>,
+
+        /// Path of a diagnostics profile `.json` file, with the diagnostics and paths to ignore during the check.
+        ///
+        /// If not provided, all diagnostics are checked.
+        #[arg(long, required = false, value_name = "PROFILE_PATH")]
+        profile_path: Option<PathBuf>,
+    },
+
+    /// Searches a text pattern in the DB, Loc and Text files of the provided Pack, and prints the matches in a grep-like format.
+    Search {
+
+        /// Path of the Pack this operation will use.
+        #[arg(short, long, required = true, value_name = "PACK_PATH")]
+        pack_path: PathBuf,
+
+        /// Path of the schema for the game the Pack is for.
+        #[arg(short, long, required = true, value_name = "SCHEMA_PATH")]
+        schema_path: PathBuf,
+
+        /// Pattern to search.
+        #[arg(short = 'e', long, required = true, value_name = "PATTERN")]
+        pattern: String,
+
+        /// If enabled, the search is case sensitive.
+        #[arg(short, long)]
+        case_sensitive: bool,
+
+        /// If enabled, the pattern is used as a regex.
+        #[arg(short, long)]
+        regex: bool,
+
+        /// If enabled, only whole words are matched, so searching `unit` doesn't match `unit_key`. Ignored when using regex.
+        #[arg(short, long)]
+        whole_word: bool,
+    },
+
+    /// Generates loc entries for all the loc keys expected by the DB tables of the provided Pack that are not in any of its Loc files.
+    GenerateMissingLocData {
+
+        /// Path of the Pack this operation will use.
+        #[arg(short, long, required = true, value_name = "PACK_PATH")]
+        pack_path: PathBuf,
+
+        /// Path of the schema for the game the Pack is for.
+        #[arg(short, long, required = true, value_name = "SCHEMA_PATH")]
+        schema_path: PathBuf,
+
+        /// Path within the Pack of the Loc file the new entries will be added to.
+        ///
+        /// If not provided, the entries of each table are added to the Loc file the game uses for said table.
+        #[arg(short, long, required = false, value_name = "LOC_PATH_IN_PACK")]
+        target_path: Option<String>,
+
+        /// Text of the new entries.
+        #[arg(short = 'x', long, required = false, default_value = "", value_name = "TEXT")]
+        text: String,
     },
 
     /// Merges all the Packs provided into a single Pack and saves it to the provided save path.
@@ -325,6 +417,14 @@ pub enum CommandsPack {
         /// Priority for conflicting files is determined by the order of the Packs in the command.
         #[arg(short = 's', long, required = true, num_args = 1.., value_name = "SOURCE_PACK_PATHS")]
         source_pack_paths: Vec<PathBuf>,
+
+        /// Compress the files of the merged Pack, if they support it.
+        #[arg(long)]
+        compress: bool,
+
+        /// Compress files with identical data only once. It makes saving faster when there are many duplicated files. The saved Pack is the same.
+        #[arg(long, requires = "compress")]
+        deduplicate_compression: bool,
     },
 
     /// Adds a Pack as a dependency of the provided Pack.
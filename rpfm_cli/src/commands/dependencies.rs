@@ -44,3 +44,41 @@ pub fn generate(config: &Config, pak_path: &Path, game_path: &Path, assembly_kit
         None => Err(anyhow!("No Game provided.")),
     }
 }
+
+/// This function checks the integrity of the dependencies cache at the provided path, fixing what can be fixed without regenerating it.
+pub fn repair(config: &Config, pak_path: &Path) -> Result<()> {
+    if config.verbose {
+        info!("Repairing dependencies at the following path: {}.", pak_path.to_string_lossy().to_string());
+    }
+
+    let mut dependencies = Dependencies::load(pak_path, &None)?;
+    match dependencies.validate_cache() {
+        Ok(_) => {
+            if config.verbose {
+                info!("No issues found in the dependencies.");
+            }
+
+            Ok(())
+        }
+        Err(issues) => {
+            if config.verbose {
+                for issue in &issues {
+                    info!("Issue found: {:?}.", issue);
+                }
+            }
+
+            let unfixable = dependencies.repair_cache();
+            dependencies.save(pak_path)?;
+
+            if unfixable.is_empty() {
+                if config.verbose {
+                    info!("Dependencies repaired at path {}.", pak_path.to_string_lossy().to_string());
+                }
+
+                Ok(())
+            } else {
+                Err(anyhow!("The dependencies have issues that can only be fixed by regenerating them: {:?}", unfixable))
+            }
+        }
+    }
+}
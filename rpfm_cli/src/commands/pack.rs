@@ -19,17 +19,20 @@ use std::fs::File;
 use std::path::{Path, PathBuf};
 
 use rpfm_extensions::dependencies::Dependencies;
-use rpfm_extensions::diagnostics::Diagnostics;
+use rpfm_extensions::diagnostics::{DiagnosticsProfile, run_diagnostics};
+use rpfm_extensions::search::{GlobalSearch, SearchSource};
 
 use rpfm_lib::binary::ReadBytes;
-use rpfm_lib::files::{ContainerPath, Container, Decodeable, DecodeableExtraData, Encodeable, EncodeableExtraData, FileType, pack::Pack};
-use rpfm_lib::games::pfh_file_type::PFHFileType;
+use rpfm_lib::files::{ContainerPath, Container, Decodeable, DecodeableExtraData, Encodeable, EncodeableExtraData, FileType, pack::Pack, table::{ColourFormat, DEFAULT_FLOAT_PRECISION, FormatOptions, MAX_FLOAT_PRECISION}};
+use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType};
 use rpfm_lib::integrations::log::*;
 use rpfm_lib::schema::Schema;
 use rpfm_lib::utils::last_modified_time_from_file;
 
 use crate::config::Config;
 
+#[cfg(test)] mod pack_test;
+
 //---------------------------------------------------------------------------//
 // 							Pack Command Variants
 //---------------------------------------------------------------------------//
@@ -158,6 +161,42 @@ pub fn add(config: &Config, schema_path: &Option<PathBuf>, pack_path: &Path, fil
     Ok(())
 }
 
+/// This function builds a new Pack out of the provided folder, and saves it to the provided path.
+pub fn from_folder(config: &Config, schema_path: &Option<PathBuf>, pack_path: &Path, folder_path: &Path) -> Result<()> {
+    if config.verbose {
+        info!("Building a new Pack at {} from folder {}.", pack_path.to_string_lossy().to_string(), folder_path.to_string_lossy().to_string());
+        info!("Tsv to Binary is: {}.", schema_path.is_some());
+    }
+
+    // Load the schema if we try to import tsv files.
+    let schema = if let Some(schema_path) = schema_path {
+        if schema_path.is_file() {
+            Some(Schema::load(schema_path, None)?)
+        } else {
+            warn!("Schema path provided, but it doesn't point to a valid schema. Disabling `TSV to Binary`.");
+            None
+        }
+    } else { None };
+
+    match &config.game {
+        Some(game) => {
+            let (mut pack, unknown_paths) = Pack::from_directory(folder_path, game, &schema)?;
+            for path in &unknown_paths {
+                warn!("File couldn't be classified: {}", path.path_raw());
+            }
+
+            pack.save(Some(pack_path), game, &None)?;
+
+            if config.verbose {
+                info!("Pack built with {} files.", pack.files().len());
+            }
+
+            Ok(())
+        }
+        None => Err(anyhow!("No Game provided.")),
+    }
+}
+
 /// This function deletes the provided files/folders from the provided Pack.
 pub fn delete(config: &Config, pack_path: &Path, file_path: &[String], folder_path: &[String]) -> Result<()> {
     if config.verbose {
@@ -178,6 +217,11 @@ pub fn delete(config: &Config, pack_path: &Path, file_path: &[String], folder_pa
     container_paths.append(&mut file_path.iter().map(|x| ContainerPath::File(x.to_string())).collect::<Vec<_>>());
     let container_paths = ContainerPath::dedup(&container_paths);
 
+    // Check all the paths before deleting any of them, so we don't leave the Pack half-edited.
+    for container_path in &container_paths {
+        pack.check_container_path_not_readonly(container_path)?;
+    }
+
     for container_path in container_paths {
         if config.verbose {
             info!("Deleting path: {}", container_path.path_raw());
@@ -199,7 +243,7 @@ pub fn delete(config: &Config, pack_path: &Path, file_path: &[String], folder_pa
 }
 
 /// This function extracts the provided files/folders from the provided Pack, keeping their folder structure.
-pub fn extract(config: &Config, schema_path: &Option<PathBuf>, pack_path: &Path, file_path: &[(String, PathBuf)], folder_path: &[(String, PathBuf)]) -> Result<()> {
+pub fn extract(config: &Config, schema_path: &Option<PathBuf>, tsv_float_precision: Option<usize>, tsv_colour_format: &str, pack_path: &Path, file_path: &[(String, PathBuf)], folder_path: &[(String, PathBuf)]) -> Result<()> {
     if config.verbose {
         info!("Extracting files/folders from a Pack at {}.", pack_path.to_string_lossy().to_string());
         info!("Tables as Tsv is: {}.", schema_path.is_some());
@@ -229,6 +273,22 @@ pub fn extract(config: &Config, schema_path: &Option<PathBuf>, pack_path: &Path,
         extra_data = EncodeableExtraData::new_from_game_info(game);
     }
 
+    let mut format_options = FormatOptions::default();
+    if let Some(tsv_float_precision) = tsv_float_precision {
+        if !(DEFAULT_FLOAT_PRECISION..=MAX_FLOAT_PRECISION).contains(&tsv_float_precision) {
+            return Err(anyhow!("Invalid TSV float precision: it must be between {} and {}.", DEFAULT_FLOAT_PRECISION, MAX_FLOAT_PRECISION));
+        }
+
+        format_options.set_float_precision(tsv_float_precision);
+    }
+
+    format_options.set_colour_format(match tsv_colour_format {
+        "uppercase" => ColourFormat::Uppercase,
+        "lowercase" => ColourFormat::Lowercase,
+        _ => ColourFormat::AsIs,
+    });
+
+    extra_data.set_tsv_format_options(format_options);
     let extra_data = Some(extra_data);
 
     for (container_path, folder_path) in folder_path {
@@ -258,7 +318,7 @@ pub fn extract(config: &Config, schema_path: &Option<PathBuf>, pack_path: &Path,
 
 
 /// This function diagnose problems in the provided Packs.
-pub fn diagnose(config: &Config, game_path: &Path, pak_path: &Path, schema_path: &Path, pack_paths: &[PathBuf]) -> Result<()> {
+pub fn diagnose(config: &Config, game_path: &Path, pak_path: &Path, schema_path: &Path, pack_paths: &[PathBuf], profile_path: &Option<PathBuf>) -> Result<()> {
     if config.verbose {
         info!("Diagnosing problems in the following Packs:");
         for pack_path in pack_paths {
@@ -295,9 +355,13 @@ pub fn diagnose(config: &Config, game_path: &Path, pak_path: &Path, schema_path:
             dependencies.rebuild(&Some(schema.clone()), pack.dependencies(), Some(pak_path), game_info, game_path, &PathBuf::new())?;
             dependencies.generate_local_db_references(&schema, &pack, &tables);
 
-            // Trigger a diagnostics check.
-            let mut diagnostics = Diagnostics::default();
-            diagnostics.check(&mut pack, &mut dependencies, &schema, game_info, game_path, &[], false);
+            // Trigger a diagnostics check, using the provided profile if any.
+            let profile = match profile_path {
+                Some(profile_path) => DiagnosticsProfile::load(profile_path)?,
+                None => DiagnosticsProfile::default(),
+            };
+
+            let diagnostics = run_diagnostics(&mut pack, &mut dependencies, &schema, game_info, game_path, &profile);
 
             if config.verbose {
                 info!("Diagnosed problems in the following Packs:");
@@ -319,8 +383,94 @@ pub fn diagnose(config: &Config, game_path: &Path, pak_path: &Path, schema_path:
     }
 }
 
+/// This function searches the provided pattern in the DB, Loc and Text files of the provided Pack, and prints the matches.
+pub fn search(config: &Config, pack_path: &Path, schema_path: &Path, pattern: &str, case_sensitive: bool, regex: bool, whole_word: bool) -> Result<()> {
+    if config.verbose {
+        info!("Searching \"{}\" in Pack at {}.", pattern, pack_path.to_string_lossy().to_string());
+    }
+
+    match &config.game {
+        Some(game_info) => {
+            let schema = Schema::load(schema_path, None)?;
+            let mut pack = Pack::read_and_merge(&[pack_path.to_path_buf()], true, false)?;
+
+            for line in search_lines(game_info, &schema, &mut pack, pattern, case_sensitive, regex, whole_word) {
+                println!("{line}");
+            }
+
+            Ok(())
+        }
+        None => Err(anyhow!("No Game provided.")),
+    }
+}
+
+/// This function searches the provided pattern in the DB, Loc and Text files of the provided Pack, and returns the matches as grep-like lines.
+fn search_lines(game_info: &GameInfo, schema: &Schema, pack: &mut Pack, pattern: &str, case_sensitive: bool, regex: bool, whole_word: bool) -> Vec<String> {
+    let mut extra_data = DecodeableExtraData::default();
+    extra_data.set_schema(Some(schema));
+    let table_extra_data = Some(extra_data);
+
+    // The search only looks at tables that are already decoded.
+    pack.files_by_type_mut(&[FileType::DB, FileType::Loc])
+        .par_iter_mut()
+        .for_each(|file| { let _ = file.decode(&table_extra_data, true, false); });
+
+    let mut global_search = GlobalSearch::default();
+    global_search.set_pattern(pattern.to_owned());
+    global_search.set_case_sensitive(case_sensitive);
+    global_search.set_use_regex(regex);
+    global_search.set_source(SearchSource::Pack);
+    global_search.set_game_key(game_info.key().to_owned());
+    global_search.search_on_mut().set_db(true);
+    global_search.search_on_mut().set_loc(true);
+    global_search.search_on_mut().set_text(true);
+    global_search.search_on_mut().set_whole_word(whole_word);
+
+    global_search.search(game_info, schema, pack, &mut Dependencies::default(), &[]);
+    global_search.matches().to_grep_lines()
+}
+
+/// This function generates the missing loc entries of the DB tables in the provided Pack, and saves it.
+pub fn generate_missing_loc_data(config: &Config, pack_path: &Path, schema_path: &Path, target_path: &Option<String>, text: &str) -> Result<()> {
+    if config.verbose {
+        info!("Generating missing loc entries for the following Pack: {}", pack_path.to_string_lossy().to_string());
+    }
+
+    match &config.game {
+        Some(game_info) => {
+            let schema = Schema::load(schema_path, None)?;
+            let mut pack = Pack::read_and_merge(&[pack_path.to_path_buf()], true, false)?;
+
+            let mut extra_data = DecodeableExtraData::default();
+            extra_data.set_schema(Some(&schema));
+            let table_extra_data = Some(extra_data);
+
+            // The expected keys come from the DB tables, and the existing ones from the Locs, so we need both decoded.
+            pack.files_by_type_mut(&[FileType::DB, FileType::Loc])
+                .par_iter_mut()
+                .for_each(|file| { let _ = file.decode(&table_extra_data, true, false); });
+
+            let count = match target_path {
+                Some(target_path) => pack.generate_missing_loc_data(target_path, text)?,
+                None => pack.generate_missing_loc_data_for_game(game_info, text)?,
+            };
+
+            if count > 0 {
+                pack.save(None, game_info, &None)?;
+            }
+
+            if config.verbose {
+                info!("Generated {} missing loc entries.", count);
+            }
+
+            Ok(())
+        }
+        None => Err(anyhow!("No Game provided.")),
+    }
+}
+
 /// This function merges the provided Packs into a new one, and saves it to the provided save path.
-pub fn merge(config: &Config, save_pack_path: &Path, source_pack_paths: &[PathBuf]) -> Result<()> {
+pub fn merge(config: &Config, save_pack_path: &Path, source_pack_paths: &[PathBuf], compress: bool, deduplicate_compression: bool) -> Result<()> {
     if config.verbose {
         info!("Creating new Merged Mod Pack at {}.", save_pack_path.to_string_lossy().to_string());
         info!("Packs ready to be merged:");
@@ -332,7 +482,12 @@ pub fn merge(config: &Config, save_pack_path: &Path, source_pack_paths: &[PathBu
     match &config.game {
         Some(game) => {
             let mut pack = Pack::read_and_merge(source_pack_paths, true, false)?;
-            pack.save(Some(save_pack_path), game, &None)?;
+            pack.set_compress(compress);
+
+            let mut extra_data = EncodeableExtraData::new_from_game_info(game);
+            extra_data.set_deduplicate_compression(deduplicate_compression);
+
+            pack.save(Some(save_pack_path), game, &Some(extra_data))?;
             Ok(())
         }
         None => Err(anyhow!("No Game provided.")),
@@ -0,0 +1,48 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the `Pack` commands.
+
+use rpfm_lib::files::{db::DB, RFile, table::DecodedData};
+use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+use rpfm_lib::schema::{Definition, FieldType};
+
+use super::*;
+
+#[test]
+fn test_search_lines() {
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("name", FieldType::StringU8, false)], &[]);
+
+    let mut schema = Schema::default();
+    schema.add_definition("units_tables", &definition);
+
+    let mut table = DB::new(&definition, None, "units_tables");
+    table.set_data(&[
+        vec![DecodedData::StringU8("unit_a".to_owned()), DecodedData::StringU8("Spearmen".to_owned())],
+        vec![DecodedData::StringU8("unit_b".to_owned()), DecodedData::StringU8("Swordsmen".to_owned())],
+    ]).unwrap();
+
+    let mut data = vec![];
+    table.encode(&mut data, &None).unwrap();
+
+    // The table is added undecoded, like when reading a Pack from disk.
+    let mut pack = Pack::default();
+    pack.insert(RFile::new_from_vec(&data, FileType::DB, 0, "db/units_tables/test")).unwrap();
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let lines = search_lines(game, &schema, &mut pack, "swordsmen", false, false, false);
+    assert_eq!(lines, vec!["db/units_tables/test::2::name::Swordsmen".to_owned()]);
+
+    // Whole word searches must not match parts of a cell.
+    assert!(search_lines(game, &schema, &mut pack, "unit", false, false, true).is_empty());
+    assert_eq!(search_lines(game, &schema, &mut pack, "unit_a", false, false, true).len(), 1);
+}
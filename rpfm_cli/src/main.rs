@@ -85,17 +85,21 @@ fn main() {
 
         Commands::Dependencies { commands } => match commands {
             CommandsDependencies::Generate { pak_path, game_path, assembly_kit_path } => crate::commands::dependencies::generate(&config, &pak_path, &game_path, &assembly_kit_path),
+            CommandsDependencies::Repair { pak_path } => crate::commands::dependencies::repair(&config, &pak_path),
         }
 
         Commands::Pack { commands } => match commands {
             CommandsPack::List { pack_path } => crate::commands::pack::list(&config, &pack_path),
             CommandsPack::Create { pack_path } => crate::commands::pack::create(&config, &pack_path),
             CommandsPack::Add { pack_path, tsv_to_binary, file_path, folder_path } => crate::commands::pack::add(&config, &tsv_to_binary, &pack_path, &file_path, &folder_path),
+            CommandsPack::FromFolder { pack_path, folder_path, tsv_to_binary } => crate::commands::pack::from_folder(&config, &tsv_to_binary, &pack_path, &folder_path),
             CommandsPack::Delete { pack_path, file_path, folder_path } => crate::commands::pack::delete(&config, &pack_path, &file_path, &folder_path),
-            CommandsPack::Extract { pack_path, tables_as_tsv, file_path, folder_path } => crate::commands::pack::extract(&config, &tables_as_tsv, &pack_path, &file_path, &folder_path),
+            CommandsPack::Extract { pack_path, tables_as_tsv, tsv_float_precision, tsv_colour_format, file_path, folder_path } => crate::commands::pack::extract(&config, &tables_as_tsv, tsv_float_precision, &tsv_colour_format, &pack_path, &file_path, &folder_path),
             CommandsPack::SetFileType { pack_path, file_type } => crate::commands::pack::set_pack_type(&config, &pack_path, file_type),
-            CommandsPack::Diagnose { game_path, pak_path, schema_path, pack_path } => crate::commands::pack::diagnose(&config, &game_path, &pak_path, &schema_path, &pack_path),
-            CommandsPack::Merge { save_pack_path, source_pack_paths } => crate::commands::pack::merge(&config, &save_pack_path, &source_pack_paths),
+            CommandsPack::Diagnose { game_path, pak_path, schema_path, pack_path, profile_path } => crate::commands::pack::diagnose(&config, &game_path, &pak_path, &schema_path, &pack_path, &profile_path),
+            CommandsPack::Search { pack_path, schema_path, pattern, case_sensitive, regex, whole_word } => crate::commands::pack::search(&config, &pack_path, &schema_path, &pattern, case_sensitive, regex, whole_word),
+            CommandsPack::GenerateMissingLocData { pack_path, schema_path, target_path, text } => crate::commands::pack::generate_missing_loc_data(&config, &pack_path, &schema_path, &target_path, &text),
+            CommandsPack::Merge { save_pack_path, source_pack_paths, compress, deduplicate_compression } => crate::commands::pack::merge(&config, &save_pack_path, &source_pack_paths, compress, deduplicate_compression),
             CommandsPack::AddDependencyPack { pack_path, dependency_pack } => crate::commands::pack::add_dependency(&config, &pack_path, &dependency_pack),
             CommandsPack::RemoveDependencyPack { pack_path, dependency_pack } => crate::commands::pack::remove_dependency(&config, &pack_path, &dependency_pack),
             CommandsPack::RemoveAllDependencies { pack_path } => crate::commands::pack::remove_all_dependencies(&config, &pack_path),
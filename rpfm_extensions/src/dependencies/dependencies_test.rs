@@ -0,0 +1,156 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the dependencies.
+
+use rpfm_lib::files::{Encodeable, loc::Loc, table::DecodedData};
+
+use super::*;
+
+/// This function returns an encoded units table with a row.
+fn units_table(path: &str) -> RFile {
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("name", FieldType::StringU8, false)], &[]);
+
+    let mut table = DB::new(&definition, None, "units_tables");
+    table.set_data(&[vec![DecodedData::StringU8("unit".to_owned()), DecodedData::StringU8("name".to_owned())]]).unwrap();
+
+    let mut data = vec![];
+    table.encode(&mut data, &None).unwrap();
+    RFile::new_from_vec(&data, FileType::DB, 0, path)
+}
+
+/// This function returns a dependencies cache with a table and a loc, with all its lists complete.
+fn valid_cache() -> Dependencies {
+    let mut loc_data = vec![];
+    Loc::new().encode(&mut loc_data, &None).unwrap();
+
+    let mut dependencies = Dependencies::default();
+    dependencies.version = VERSION.to_owned();
+    dependencies.insert_db_as_vanilla_db(units_table("db/units_tables/data"));
+    dependencies.insert_loc_as_vanilla_loc(RFile::new_from_vec(&loc_data, FileType::Loc, 0, "text/db/test.loc"));
+
+    for path in dependencies.vanilla_files.keys() {
+        dependencies.vanilla_paths.insert(path.to_lowercase(), vec![path.to_owned()]);
+        dependencies.vanilla_folders.extend(Dependencies::folders_from_path(path));
+    }
+
+    dependencies
+}
+
+#[test]
+fn test_validate_and_repair_cache() {
+    let mut dependencies = valid_cache();
+    assert!(dependencies.validate_cache().is_ok());
+    assert!(dependencies.repair_cache().is_empty());
+
+    // Break the lists, and add a table with a header that cannot be read, and an unlisted loose file.
+    dependencies.vanilla_paths.remove("db/units_tables/data");
+    dependencies.vanilla_locs.remove("text/db/test.loc");
+    dependencies.vanilla_tables.entry("units_tables".to_owned()).or_default().push("db/units_tables/missing".to_owned());
+    dependencies.insert_db_as_vanilla_db(RFile::new_from_vec(b"bad", FileType::DB, 0, "db/land_units_tables/data"));
+    dependencies.vanilla_paths.insert("db/land_units_tables/data".to_owned(), vec!["db/land_units_tables/data".to_owned()]);
+    dependencies.vanilla_folders.extend(Dependencies::folders_from_path("db/land_units_tables/data"));
+    dependencies.vanilla_loose_files.insert("db/units_tables/loose".to_owned(), units_table("db/units_tables/loose"));
+
+    let issues = dependencies.validate_cache().unwrap_err();
+    assert_eq!(issues, vec![
+        CacheIssue::MissingIndexedFile("db/units_tables/missing".to_owned()),
+        CacheIssue::UnindexedFile("text/db/test.loc".to_owned()),
+        CacheIssue::UnindexedPath("db/units_tables/data".to_owned()),
+        CacheIssue::UnresolvableVersion("db/land_units_tables/data".to_owned()),
+        CacheIssue::LooseFile(Box::new(CacheIssue::UnindexedFile("db/units_tables/loose".to_owned()))),
+        CacheIssue::LooseFile(Box::new(CacheIssue::UnindexedPath("db/units_tables/loose".to_owned()))),
+    ]);
+
+    // Only the unreadable table needs a regeneration. The rest must be fixed in place.
+    let unfixable = dependencies.repair_cache();
+    assert_eq!(unfixable, vec![CacheIssue::UnresolvableVersion("db/land_units_tables/data".to_owned())]);
+    assert_eq!(dependencies.validate_cache().unwrap_err(), unfixable);
+    assert_eq!(dependencies.vanilla_loose_tables.get("units_tables"), Some(&vec!["db/units_tables/loose".to_owned()]));
+    assert!(dependencies.vanilla_locs.contains("text/db/test.loc"));
+
+    // Without that table, the cache is valid again.
+    dependencies.vanilla_files.remove("db/land_units_tables/data");
+    assert!(dependencies.repair_cache().is_empty());
+    assert!(dependencies.validate_cache().is_ok());
+
+    // Files of the wrong type and outdated caches can only be fixed by regenerating the cache.
+    dependencies.version = String::new();
+    dependencies.vanilla_locs.insert("db/units_tables/data".to_owned());
+    let unfixable = dependencies.repair_cache();
+    assert_eq!(unfixable, vec![CacheIssue::OutdatedVersion(String::new()), CacheIssue::CorruptFile("db/units_tables/data".to_owned())]);
+    assert!(unfixable.iter().all(|issue| issue.requires_regeneration()));
+}
+
+#[test]
+fn test_load_corrupted_cache() {
+    let path = std::env::temp_dir().join("rpfm_test_dependencies_corrupted").join("test.pak");
+    let mut dependencies = valid_cache();
+    dependencies.save(&path).unwrap();
+
+    // A cache saved without issues must load without them.
+    let loaded = Dependencies::load(&path, &None).unwrap();
+    assert!(loaded.validate_cache().is_ok());
+    assert_eq!(loaded.vanilla_files().len(), 2);
+
+    // A truncated cache must fail to load, instead of loading partial data.
+    let mut pak3 = path.clone();
+    pak3.set_extension("pak3");
+    let data = std::fs::read(&pak3).unwrap();
+    std::fs::write(&pak3, &data[..data.len() / 2]).unwrap();
+    assert!(Dependencies::load(&path, &None).is_err());
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn test_tables_of_type() {
+    use rpfm_lib::games::pfh_version::PFHVersion;
+
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("name", FieldType::StringU8, false)], &[]);
+    let table = |name: &str| {
+        let mut table = DB::new(&definition, None, "units_tables");
+        table.set_data(&[vec![DecodedData::StringU8("unit".to_owned()), DecodedData::StringU8(name.to_owned())]]).unwrap();
+        table
+    };
+
+    let decoded_table = |path: &str, name: &str| RFile::new_from_decoded(&RFileDecoded::DB(table(name)), 0, path);
+
+    let mut dependencies = Dependencies::default();
+    dependencies.asskit_only_db_tables.insert("units_tables".to_owned(), table("assembly kit"));
+    dependencies.insert_db_as_vanilla_db(decoded_table("db/units_tables/data", "vanilla"));
+    dependencies.insert_db_as_vanilla_db(units_table("db/units_tables/not_decoded"));
+    dependencies.insert_db_as_parent_db(decoded_table("db/units_tables/parent", "parent"));
+
+    let mut pack = Pack::new_with_version(PFHVersion::PFH6);
+    pack.insert(decoded_table("db/units_tables/z_mod", "pack z")).unwrap();
+    pack.insert(decoded_table("db/units_tables/a_mod", "pack a")).unwrap();
+    pack.insert(decoded_table("db/land_units_tables/a_mod", "other table")).unwrap();
+
+    let names = |tables: Vec<(SearchSource, &DB)>| tables.into_iter()
+        .map(|(source, table)| (source, table.data()[0][1].data_to_string().to_string()))
+        .collect::<Vec<_>>();
+
+    // Tables must come in override order, sorted by path within the Pack, skipping the ones not decoded and the ones of other tables.
+    assert_eq!(names(dependencies.tables_of_type(Some(&pack), "units")), vec![
+        (SearchSource::Pack, "pack a".to_owned()),
+        (SearchSource::Pack, "pack z".to_owned()),
+        (SearchSource::ParentFiles, "parent".to_owned()),
+        (SearchSource::GameFiles, "vanilla".to_owned()),
+        (SearchSource::AssKitFiles, "assembly kit".to_owned()),
+    ]);
+
+    // Without a Pack only the dependencies are returned.
+    assert_eq!(names(dependencies.tables_of_type(None, "units_tables")), vec![
+        (SearchSource::ParentFiles, "parent".to_owned()),
+        (SearchSource::GameFiles, "vanilla".to_owned()),
+        (SearchSource::AssKitFiles, "assembly kit".to_owned()),
+    ]);
+}
@@ -16,21 +16,28 @@ use rayon::prelude::*;
 use serde_derive::{Serialize, Deserialize};
 
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
 use std::fs::{DirBuilder, File};
-use std::io::{BufReader, Read, Write};
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, Cursor, Read, Write};
+use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 use std::thread::{JoinHandle, spawn};
 
 use rpfm_lib::error::{Result, RLibError};
-use rpfm_lib::files::{Container, ContainerPath, db::DB, DecodeableExtraData, FileType, pack::Pack, RFile, RFileDecoded};
+use rpfm_lib::files::{Container, ContainerPath, db::DB, DecodeableExtraData, FileType, pack::Pack, RFile, RFileDecoded, table::ColumnIndex};
 use rpfm_lib::games::GameInfo;
 use rpfm_lib::integrations::{assembly_kit::table_data::RawTable, log::{info, error}};
 use rpfm_lib::schema::{Definition, Field, FieldType, Schema};
 use rpfm_lib::utils::{current_time, files_from_subdir, last_modified_time_from_files, starts_with_case_insensitive};
 
+use crate::search::SearchSource;
 use crate::VERSION;
 
+#[cfg(test)] mod dependencies_test;
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -58,6 +65,8 @@ use crate::VERSION;
 ///
 /// - Then, on runtime, we add decoded table's reference data to this one, so we don't need to recalculate it again.
 ///     - local_tables_references,
+/// - And the decoded tables requested through [Dependencies::decoded_tables] are cached in this one, until the data they come from changes:
+///     - decoded_tables_cache.
 #[derive(Default, Debug, Clone, Getters, Serialize, Deserialize)]
 #[getset(get = "pub")]
 pub struct Dependencies {
@@ -145,6 +154,32 @@ pub struct Dependencies {
 
     /// DB Files only available on the assembly kit. Usable only for references. Do not use them as the base for new tables.
     asskit_only_db_tables: HashMap<String, DB>,
+
+    /// Cache of decoded tables by source and table name, kept between operations.
+    ///
+    /// This is for runtime caching, and it must not be serialized to disk.
+    #[serde(skip_serializing, skip_deserializing)]
+    decoded_tables_cache: DecodedTablesCache,
+}
+
+/// This struct is a thread-safe cache of decoded DB tables, grouped by source and table name.
+///
+/// Only tables not decoded in the dependencies are kept here, by path, along with a hash of the schema data used to decode them.
+/// Tables that failed to decode are kept as `None`.
+///
+/// Clones of it start empty, so a cloned `Dependencies` never shares cached data with the original.
+#[derive(Debug, Default)]
+pub struct DecodedTablesCache {
+    tables: RwLock<HashMap<(SearchSource, String), (u64, HashMap<String, Option<Arc<DB>>>)>>,
+}
+
+/// This enum represents a decoded table returned by [Dependencies::decoded_tables].
+///
+/// It's either borrowed from the dependencies, if they already have it decoded, or shared from their cache of decoded tables.
+#[derive(Debug, Clone)]
+pub enum DecodedTable<'a> {
+    Borrowed(&'a DB),
+    Cached(Arc<DB>),
 }
 
 /// This holds the reference data for a table's column.
@@ -165,10 +200,108 @@ pub struct TableReferences {
     data: HashMap<String, String>,
 }
 
+/// This struct represents the location of a row referenced by another table.
+#[derive(Eq, PartialEq, Clone, Debug, Getters)]
+#[getset(get = "pub")]
+pub struct ReferenceLocation {
+
+    /// Source where the referenced row was found.
+    source: ReferenceSource,
+
+    /// Path of the file containing the referenced row. For assembly kit-only tables, this is a fake path.
+    path: String,
+
+    /// Index of the column containing the referenced value.
+    column: usize,
+
+    /// Index of the row containing the referenced value.
+    row: usize,
+}
+
+/// This enum represents the different sources a referenced row can come from, in order of precedence.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ReferenceSource {
+    Pack,
+    ParentFiles,
+    GameFiles,
+    AssKitFiles,
+}
+
+/// This enum represents the different problems [Dependencies::validate_cache] can find in a dependencies cache.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Debug)]
+pub enum CacheIssue {
+
+    /// The cache was generated by another version of the program. Contains the version that generated it.
+    OutdatedVersion(String),
+
+    /// The cache contains no game files.
+    NoGameFiles,
+
+    /// A table or loc is listed in the cache, but its file is missing. Contains the path of the file.
+    MissingIndexedFile(String),
+
+    /// A table or loc file is in the cache, but it's not listed as such. Contains the path of the file.
+    UnindexedFile(String),
+
+    /// A file is in the cache, but its path or one of its folders is not in the path lists. Contains the path of the file.
+    UnindexedPath(String),
+
+    /// A file listed as table or loc contains data of another type. Contains the path of the file.
+    CorruptFile(String),
+
+    /// A table is not decoded, and its version cannot be read from its data. Contains the path of the file.
+    UnresolvableVersion(String),
+
+    /// An issue with the loose files of the game's data folder. These are not saved in the cache, but reloaded when rebuilding the dependencies.
+    LooseFile(Box<CacheIssue>),
+}
+
 //-------------------------------------------------------------------------------//
 //                             Implementations
 //-------------------------------------------------------------------------------//
 
+impl CacheIssue {
+
+    /// This function returns if the issue can only be fixed by regenerating the dependencies cache.
+    ///
+    /// For issues with the loose files, rebuilding the dependencies is enough, as they're reloaded from the data folder.
+    pub fn requires_regeneration(&self) -> bool {
+        match self {
+            Self::OutdatedVersion(_) | Self::NoGameFiles | Self::CorruptFile(_) | Self::UnresolvableVersion(_) => true,
+            Self::LooseFile(issue) => issue.requires_regeneration(),
+            Self::MissingIndexedFile(_) | Self::UnindexedFile(_) | Self::UnindexedPath(_) => false,
+        }
+    }
+}
+
+impl ReferenceLocation {
+    pub fn new(source: ReferenceSource, path: &str, column: usize, row: usize) -> Self {
+        Self {
+            source,
+            path: path.to_owned(),
+            column,
+            row,
+        }
+    }
+}
+
+impl Clone for DecodedTablesCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Deref for DecodedTable<'_> {
+    type Target = DB;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(table) => table,
+            Self::Cached(table) => table,
+        }
+    }
+}
+
 impl Dependencies {
 
     //-----------------------------------//
@@ -196,6 +329,7 @@ impl Dependencies {
 
         // Clear the table's cached data, to ensure it gets rebuild properly when needed.
         self.local_tables_references.clear();
+        self.clear_decoded_tables_cache();
 
         // Load vanilla loose files (from /data).
         self.load_loose_files(schema, game_info, game_path)?;
@@ -557,6 +691,175 @@ impl Dependencies {
         file_3.write_all(&serialized_3).map_err(From::from)
     }
 
+    /// This function checks the internal consistency of the dependencies cache, returning all the issues found.
+    ///
+    /// It checks the cache version, that the game files are there, that all listed tables and locs have a file of the correct type,
+    /// that the version of all tables can be read, and that all table, loc and path lists are complete.
+    /// The loose files of the game's data folder are checked the same way, and their issues are reported as [CacheIssue::LooseFile].
+    pub fn validate_cache(&self) -> std::result::Result<(), Vec<CacheIssue>> {
+        let mut issues = vec![];
+
+        if self.version != VERSION {
+            issues.push(CacheIssue::OutdatedVersion(self.version.to_owned()));
+        }
+
+        if self.vanilla_files.is_empty() {
+            issues.push(CacheIssue::NoGameFiles);
+        }
+
+        issues.append(&mut Self::validate_files(&self.vanilla_files, &self.vanilla_tables, &self.vanilla_locs, &self.vanilla_folders, &self.vanilla_paths));
+        issues.extend(Self::validate_files(&self.vanilla_loose_files, &self.vanilla_loose_tables, &self.vanilla_loose_locs, &self.vanilla_loose_folders, &self.vanilla_loose_paths)
+            .into_iter()
+            .map(|issue| CacheIssue::LooseFile(Box::new(issue))));
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            issues.sort();
+            Err(issues)
+        }
+    }
+
+    /// This function checks the internal consistency of a set of files of the dependencies and their lists, returning all the issues found.
+    fn validate_files(
+        files: &HashMap<String, RFile>,
+        tables: &HashMap<String, Vec<String>>,
+        locs: &HashSet<String>,
+        folders: &HashSet<String>,
+        paths: &HashMap<String, Vec<String>>,
+    ) -> Vec<CacheIssue> {
+        let mut issues = vec![];
+
+        // Listed tables and locs must exist, and be of the type they're listed as.
+        let indexed_files = tables.iter()
+            .flat_map(|(table_name, paths)| paths.iter().map(move |path| (path, FileType::DB, Some(table_name.as_str()))))
+            .chain(locs.iter().map(|path| (path, FileType::Loc, None)));
+
+        for (path, file_type, table_name) in indexed_files {
+            match files.get(path) {
+                Some(file) => {
+                    let decoded_type_is_wrong = match file.decoded() {
+                        Ok(RFileDecoded::DB(_)) => file_type != FileType::DB,
+                        Ok(RFileDecoded::Loc(_)) => file_type != FileType::Loc,
+                        Ok(_) => true,
+                        Err(_) => false,
+                    };
+
+                    if file.file_type() != file_type || decoded_type_is_wrong || (table_name.is_some() && file.db_table_name_from_path() != table_name) {
+                        issues.push(CacheIssue::CorruptFile(path.to_owned()));
+                    }
+
+                    // Undecoded tables need a readable header, or we cannot know which definition to use to decode them.
+                    else if file_type == FileType::DB && file.decoded().is_err() {
+                        if let Ok(data) = file.cached() {
+                            if DB::read_header(&mut Cursor::new(data)).is_err() {
+                                issues.push(CacheIssue::UnresolvableVersion(path.to_owned()));
+                            }
+                        }
+                    }
+                },
+                None => issues.push(CacheIssue::MissingIndexedFile(path.to_owned())),
+            }
+        }
+
+        // And the other way around, all tables and locs must be listed, and all paths must be findable.
+        for (path, file) in files {
+            let is_indexed = match file.file_type() {
+                FileType::DB => match file.db_table_name_from_path() {
+                    Some(table_name) => tables.get(table_name).map(|paths| paths.contains(path)).unwrap_or(false),
+                    None => true,
+                },
+                FileType::Loc => locs.contains(path),
+                _ => true,
+            };
+
+            if !is_indexed {
+                issues.push(CacheIssue::UnindexedFile(path.to_owned()));
+            }
+
+            let path_found = paths.get(&path.to_lowercase()).map(|paths| paths.contains(path)).unwrap_or(false);
+            let folders_found = Self::folders_from_path(path).iter().all(|folder| folders.contains(folder));
+            if !path_found || !folders_found {
+                issues.push(CacheIssue::UnindexedPath(path.to_owned()));
+            }
+        }
+
+        issues
+    }
+
+    /// This function fixes all the issues of the dependencies cache that can be fixed without regenerating it.
+    ///
+    /// It returns the issues that could not be fixed. If it returns any, the cache needs to be regenerated, or the dependencies rebuilt
+    /// if they're only issues of the loose files. Remember to save the cache after this.
+    pub fn repair_cache(&mut self) -> Vec<CacheIssue> {
+        let issues = match self.validate_cache() {
+            Ok(_) => return vec![],
+            Err(issues) => issues,
+        };
+
+        self.clear_decoded_tables_cache();
+
+        let (unfixable, fixable): (Vec<_>, Vec<_>) = issues.into_iter().partition(|issue| issue.requires_regeneration());
+        for issue in fixable {
+            match issue {
+                CacheIssue::LooseFile(issue) => Self::repair_files(*issue, &self.vanilla_loose_files, &mut self.vanilla_loose_tables, &mut self.vanilla_loose_locs, &mut self.vanilla_loose_folders, &mut self.vanilla_loose_paths),
+                issue => Self::repair_files(issue, &self.vanilla_files, &mut self.vanilla_tables, &mut self.vanilla_locs, &mut self.vanilla_folders, &mut self.vanilla_paths),
+            }
+        }
+
+        unfixable
+    }
+
+    /// This function fixes the provided issue on the lists of a set of files of the dependencies.
+    fn repair_files(
+        issue: CacheIssue,
+        files: &HashMap<String, RFile>,
+        tables: &mut HashMap<String, Vec<String>>,
+        locs: &mut HashSet<String>,
+        folders: &mut HashSet<String>,
+        paths: &mut HashMap<String, Vec<String>>,
+    ) {
+        match issue {
+            CacheIssue::MissingIndexedFile(path) => {
+                tables.values_mut().for_each(|paths| paths.retain(|x| x != &path));
+                tables.retain(|_, paths| !paths.is_empty());
+                locs.remove(&path);
+            },
+
+            CacheIssue::UnindexedFile(path) => {
+                if let Some(file) = files.get(&path) {
+                    match file.file_type() {
+                        FileType::DB => if let Some(table_name) = file.db_table_name_from_path() {
+                            tables.entry(table_name.to_owned()).or_default().push(path);
+                        },
+                        FileType::Loc => { locs.insert(path); },
+                        _ => {},
+                    }
+                }
+            },
+
+            CacheIssue::UnindexedPath(path) => {
+                folders.extend(Self::folders_from_path(&path));
+
+                let paths = paths.entry(path.to_lowercase()).or_default();
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            },
+
+            _ => unreachable!("repair_files"),
+        }
+    }
+
+    /// This function returns the paths of all the folders containing the provided file path.
+    fn folders_from_path(path: &str) -> Vec<String> {
+        let path_split = path.split('/').collect::<Vec<_>>();
+        (1..path_split.len())
+            .map(|index| path_split[..index].join("/"))
+            .filter(|folder| !folder.is_empty())
+            .collect()
+    }
+
     /// This function is used to check if the game files used to generate the dependencies cache have changed, requiring an update.
     pub fn needs_updating(&self, game_info: &GameInfo, game_path: &Path) -> Result<bool> {
         let ca_paths = game_info.ca_packs_paths(game_path)?;
@@ -833,6 +1136,7 @@ impl Dependencies {
     ///
     /// Many operations require them to be decoded, so if you did not decoded them on load, make sure to call this to decode them after load.
     pub fn decode_tables(&mut self, schema: &Option<Schema>) {
+        self.clear_decoded_tables_cache();
         if let Some(schema) = schema {
 
             let mut decode_extra_data = DecodeableExtraData::default();
@@ -934,6 +1238,9 @@ impl Dependencies {
 
     /// This function returns a mutable reference to a specific file from the cache, if exists.
     pub fn file_mut(&mut self, file_path: &str, include_vanilla: bool, include_parent: bool) -> Result<&mut RFile> {
+
+        // The file may be edited, so the decoded tables we have cached may become stale.
+        self.clear_decoded_tables_cache();
         if include_parent {
             if let Some(file) = self.parent_files.get_mut(file_path) {
                 return Ok(file);
@@ -1166,6 +1473,204 @@ impl Dependencies {
         Ok(cache)
     }
 
+    /// This function returns the tables with the provided name from the provided source, decoded, with their paths, in the order the game loads them.
+    ///
+    /// Tables already decoded in the dependencies are returned directly. The rest are decoded with the provided schema and cached,
+    /// so repeated calls for the same source and table don't decode them again, even across operations and threads.
+    /// The cache is tied to the definitions and patches the schema has for the table, so changing them invalidates it.
+    ///
+    /// The Pack source returns no tables, as the open Pack is not part of the dependencies.
+    /// Assembly Kit tables have no path, so they're returned with their table name instead.
+    ///
+    /// NOTE: table_name can be passed with or without the "_tables" at the end.
+    pub fn decoded_tables(&self, schema: &Schema, source: &SearchSource, table_name: &str) -> Vec<(String, DecodedTable)> {
+        let table_name = if table_name.ends_with("_tables") { table_name.to_owned() } else { format!("{table_name}_tables") };
+        let (include_vanilla, include_parent) = match source {
+            SearchSource::Pack => return vec![],
+            SearchSource::AssKitFiles => return self.asskit_only_db_tables.get(&table_name)
+                .map(|table| vec![(table_name.to_owned(), DecodedTable::Borrowed(table))])
+                .unwrap_or_default(),
+            SearchSource::ParentFiles => (false, true),
+            SearchSource::GameFiles => (true, false),
+        };
+
+        let files = self.db_data(&table_name, include_vanilla, include_parent).unwrap_or_default();
+        let undecoded = files.iter()
+            .filter(|file| !matches!(file.decoded(), Ok(RFileDecoded::DB(_))))
+            .collect::<Vec<_>>();
+
+        // Only touch the cache if we actually have tables that need decoding.
+        let cached = if undecoded.is_empty() {
+            HashMap::new()
+        } else {
+            let schema_hash = Self::schema_hash_for_table(schema, &table_name);
+            let key = (source.clone(), table_name);
+            let cached = self.decoded_tables_cache.tables.read().unwrap()
+                .get(&key)
+                .filter(|(hash, tables)| *hash == schema_hash && undecoded.iter().all(|file| tables.contains_key(file.path_in_container_raw())))
+                .map(|(_, tables)| tables.clone());
+
+            match cached {
+                Some(cached) => cached,
+                None => {
+                    let mut extra_data = DecodeableExtraData::default();
+                    extra_data.set_schema(Some(schema));
+                    let extra_data = Some(extra_data);
+
+                    // Tables that fail to decode are cached too, so we don't try to decode them again on each call.
+                    let tables = undecoded.par_iter()
+                        .map(|file| {
+                            let table = match RFile::clone(file).decode(&extra_data, false, true) {
+                                Ok(Some(RFileDecoded::DB(table))) => Some(Arc::new(table)),
+                                _ => None,
+                            };
+
+                            (file.path_in_container_raw().to_owned(), table)
+                        })
+                        .collect::<HashMap<_, _>>();
+
+                    self.decoded_tables_cache.tables.write().unwrap().insert(key, (schema_hash, tables.clone()));
+                    tables
+                }
+            }
+        };
+
+        files.into_iter()
+            .filter_map(|file| match file.decoded() {
+                Ok(RFileDecoded::DB(table)) => Some((file.path_in_container_raw().to_owned(), DecodedTable::Borrowed(table))),
+                _ => cached.get(file.path_in_container_raw())
+                    .cloned()
+                    .flatten()
+                    .map(|table| (file.path_in_container_raw().to_owned(), DecodedTable::Cached(table))),
+            })
+            .collect()
+    }
+
+    /// This function returns a hash of the definitions and patches the provided schema has for the provided table.
+    fn schema_hash_for_table(schema: &Schema, table_name: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        schema.version().hash(&mut hasher);
+        format!("{:?}", schema.definitions_by_table_name(table_name)).hash(&mut hasher);
+
+        // Patches are hashmaps, so they need to be sorted to get a stable hash.
+        if let Some(patches) = schema.patches_for_table(table_name) {
+            patches.iter()
+                .map(|(field, patch)| (field, patch.iter().collect::<BTreeMap<_, _>>()))
+                .collect::<BTreeMap<_, _>>()
+                .hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// This function clears the cache of decoded tables.
+    ///
+    /// The cache is cleared automatically when the dependencies are rebuilt or their tables change, so this is only needed to free memory.
+    pub fn clear_decoded_tables_cache(&self) {
+        self.decoded_tables_cache.tables.write().unwrap().clear();
+    }
+
+    /// This function returns the location of the first row containing the provided value in the provided column of a table.
+    ///
+    /// The sources are checked in the same order the game overrides them: the open Pack first (if provided), then the parent files,
+    /// then the game files, and finally the assembly kit-only tables. If the column is localised, the search is done over the first key column instead.
+    ///
+    /// NOTE: table_name can be passed with or without the "_tables" at the end.
+    pub fn resolve_reference(&self, pack: Option<&Pack>, table_name: &str, column_name: &str, value: &str) -> Option<ReferenceLocation> {
+        let table_name = if table_name.ends_with("_tables") {
+            table_name.to_owned()
+        } else {
+            format!("{table_name}_tables")
+        };
+
+        let find_in_table = |table: &DB| -> Option<(usize, usize)> {
+            let mut column_name = column_name;
+
+            // If the column is a loc column, we need to search in the first key column instead.
+            if table.definition().localised_fields().iter().any(|x| x.name() == column_name) {
+                if let Some(first_key_index) = table.definition().localised_key_order().first() {
+                    if let Some(first_key_field) = table.definition().fields_processed().get(*first_key_index as usize) {
+                        column_name = first_key_field.name();
+                    }
+                }
+            }
+
+            table.table().rows_containing_data(column_name, value).map(|(column, rows)| (column, rows[0]))
+        };
+
+        let find_in_files = |files: &[&RFile], source: ReferenceSource| -> Option<ReferenceLocation> {
+            files.iter().find_map(|file| match file.decoded() {
+                Ok(RFileDecoded::DB(table)) => find_in_table(table).map(|(column, row)| ReferenceLocation::new(source, file.path_in_container_raw(), column, row)),
+                _ => None,
+            })
+        };
+
+        if let Some(pack) = pack {
+            let files = pack.files_by_path(&ContainerPath::Folder(format!("db/{table_name}")), true);
+            if let Some(location) = find_in_files(&files, ReferenceSource::Pack) {
+                return Some(location);
+            }
+        }
+
+        if let Ok(files) = self.db_data(&table_name, false, true) {
+            if let Some(location) = find_in_files(&files, ReferenceSource::ParentFiles) {
+                return Some(location);
+            }
+        }
+
+        if let Ok(files) = self.db_data(&table_name, true, false) {
+            if let Some(location) = find_in_files(&files, ReferenceSource::GameFiles) {
+                return Some(location);
+            }
+        }
+
+        self.asskit_only_db_tables.get(&table_name)
+            .and_then(find_in_table)
+            .map(|(column, row)| ReferenceLocation::new(ReferenceSource::AssKitFiles, &format!("db/{table_name}/ak_data"), column, row))
+    }
+
+    /// This function returns all the decoded instances of the provided table, tagged with the source they come from.
+    ///
+    /// They're returned in the same order the game overrides them: the open Pack first (if provided), then the parent files,
+    /// then the game files, and finally the assembly kit-only tables. Files that are not yet decoded are skipped, not decoded.
+    ///
+    /// NOTE: table_name can be passed with or without the "_tables" at the end.
+    pub fn tables_of_type<'a>(&'a self, pack: Option<&'a Pack>, table_name: &str) -> Vec<(SearchSource, &'a DB)> {
+        let table_name = if table_name.ends_with("_tables") {
+            table_name.to_owned()
+        } else {
+            format!("{table_name}_tables")
+        };
+
+        let decoded_tables = |files: Vec<&'a RFile>, source: SearchSource| files.into_iter()
+            .filter_map(move |file| match file.decoded() {
+                Ok(RFileDecoded::DB(table)) => Some((source.clone(), table)),
+                _ => None,
+            });
+
+        let mut tables = vec![];
+
+        if let Some(pack) = pack {
+            let mut files = pack.files_by_path(&ContainerPath::Folder(format!("db/{table_name}")), true);
+            files.sort_by(|a, b| a.path_in_container_raw().cmp(b.path_in_container_raw()));
+            tables.extend(decoded_tables(files, SearchSource::Pack));
+        }
+
+        if let Ok(files) = self.db_data(&table_name, false, true) {
+            tables.extend(decoded_tables(files, SearchSource::ParentFiles));
+        }
+
+        if let Ok(files) = self.db_data(&table_name, true, false) {
+            tables.extend(decoded_tables(files, SearchSource::GameFiles));
+        }
+
+        if let Some(table) = self.asskit_only_db_tables.get(&table_name) {
+            tables.push((SearchSource::AssKitFiles, table));
+        }
+
+        tables
+    }
+
     /// This function returns the vanilla/parent DB and Loc tables from the cache, according to the params you pass it.
     ///
     /// It returns them in the order the game will load them.
@@ -1251,11 +1756,25 @@ impl Dependencies {
     pub fn db_reference_data(&self, schema: &Schema, pack: &Pack, table_name: &str, definition: &Definition, loc_data: &Option<HashMap<Cow<str>, Cow<str>>>) -> HashMap<i32, TableReferences> {
 
         // First check if the data is already cached, to speed up things.
-        let mut vanilla_references = match self.local_tables_references.get(table_name) {
+        let vanilla_references = match self.local_tables_references.get(table_name) {
             Some(cached_data) => cached_data.clone(),
             None => panic!("To be fixed: If you see this, you forgot to call generate_local_db_references before this."),
         };
 
+        self.db_reference_data_with_references(vanilla_references, schema, pack, table_name, definition, loc_data)
+    }
+
+    /// This function returns the reference/lookup data of all relevant columns of a DB Table, without using nor updating the runtime references.
+    ///
+    /// Meant for tables that are not in the Pack, like the ones of the dependencies, so their reference data doesn't replace the one of the Pack's tables.
+    pub fn db_reference_data_uncached(&self, schema: &Schema, pack: &Pack, table_name: &str, definition: &Definition, loc_data: &Option<HashMap<Cow<str>, Cow<str>>>) -> HashMap<i32, TableReferences> {
+        let vanilla_references = self.generate_references(schema, table_name, definition);
+        self.db_reference_data_with_references(vanilla_references, schema, pack, table_name, definition, loc_data)
+    }
+
+    /// This function adds the reference/lookup data of the provided Pack to the provided vanilla references of a DB Table.
+    fn db_reference_data_with_references(&self, mut vanilla_references: HashMap<i32, TableReferences>, schema: &Schema, pack: &Pack, table_name: &str, definition: &Definition, loc_data: &Option<HashMap<Cow<str>, Cow<str>>>) -> HashMap<i32, TableReferences> {
+
         // If we receive premade loc data (because this may trigger on many files at the same time), don't calculate it here.
         let (_loc_files, loc_decoded) = if loc_data.is_some() {
             (vec![], vec![])
@@ -1414,7 +1933,9 @@ impl Dependencies {
             None => self.db_data(&ref_table_full, true, true).unwrap_or_else(|_| vec![]),
         };
 
-        let mut table_data_cache: HashMap<String, HashMap<String, String>> = HashMap::new();
+        // Index of the key column of each table used in a lookup, with the column to get the lookup value from,
+        // and the start and column order of the loc key if the value is a loc key.
+        let mut table_data_cache: HashMap<String, (ColumnIndex, usize, Option<(String, Vec<u32>)>)> = HashMap::new();
 
         files.iter().for_each(|file| {
             if let Ok(RFileDecoded::DB(db)) = file.decoded() {
@@ -1487,7 +2008,7 @@ impl Dependencies {
                                     }
                                 }
 
-                                // Build the hashed cache for lookups, so we don't need to iterate again and again for each row.
+                                // Build the indexes for lookups, so we don't need to iterate again and again for each row.
                                 if let Some(files) = cache.get(lookup_ref_table) {
                                     for file in files {
                                         let table_data_column_cache_key = file.path_in_container_raw().to_owned() + &ref_lookup_step.join("++");
@@ -1496,50 +2017,29 @@ impl Dependencies {
                                                 let definition = db.definition();
                                                 let fields_processed = definition.fields_processed();
                                                 let localised_fields = definition.localised_fields();
-                                                let localised_order = definition.localised_key_order();
-
-                                                let loc_key = if is_loc {
-                                                    let mut loc_key = String::with_capacity(2 + lookup_ref_table.len() + localised_fields[col_pos].name().len());
-                                                    loc_key.push_str(&lookup_ref_table);
-                                                    loc_key.push('_');
-                                                    loc_key.push_str(localised_fields[col_pos].name());
-                                                    loc_key.push('_');
-                                                    loc_key
-                                                } else {
-                                                    String::new()
-                                                };
 
-                                                if let Some(source_key_column) = fields_processed.iter().position(|x| x.name() == lookup_ref_key) {
+                                                if let Ok(column_index) = db.table().build_column_index(lookup_ref_key) {
 
                                                     // Intermediate step cache.
                                                     if index < ref_lookup_steps.len() - 1 {
                                                         if let Some(source_lookup_column) = fields_processed.iter().position(|x| x.name() == lookup_ref_lookup) {
-                                                            let cache = db.data().iter()
-                                                                .map(|row| (row[source_key_column].data_to_string().to_string(), row[source_lookup_column].data_to_string().to_string()))
-                                                                .collect::<HashMap<_,_>>();
-
-                                                            table_data_cache.insert(table_data_column_cache_key.clone(), cache);
+                                                            table_data_cache.insert(table_data_column_cache_key.clone(), (column_index, source_lookup_column, None));
                                                         }
                                                     }
 
                                                     // Locs are already pre-cached. We only need the final part of their key.
                                                     else if is_loc {
-                                                        let cache = db.data().iter()
-                                                            .map(|row| {
-                                                                let mut loc_key = loc_key.to_owned();
-                                                                loc_key.push_str(&localised_order.iter().map(|pos| row[*pos as usize].data_to_string()).join(""));
-                                                                (row[source_key_column].data_to_string().to_string(), loc_key)
-                                                            })
-                                                            .collect::<HashMap<_,_>>();
-                                                        table_data_cache.insert(table_data_column_cache_key.clone(), cache);
+                                                        let mut loc_key = String::with_capacity(2 + lookup_ref_table.len() + localised_fields[col_pos].name().len());
+                                                        loc_key.push_str(&lookup_ref_table);
+                                                        loc_key.push('_');
+                                                        loc_key.push_str(localised_fields[col_pos].name());
+                                                        loc_key.push('_');
+
+                                                        table_data_cache.insert(table_data_column_cache_key.clone(), (column_index, col_pos, Some((loc_key, definition.localised_key_order().to_vec()))));
                                                     }
 
                                                     else {
-                                                        let cache = db.data().iter()
-                                                            .map(|row| (row[source_key_column].data_to_string().to_string(), row[col_pos].data_to_string().to_string()))
-                                                            .collect::<HashMap<_,_>>();
-
-                                                        table_data_cache.insert(table_data_column_cache_key.clone(), cache);
+                                                        table_data_cache.insert(table_data_column_cache_key.clone(), (column_index, col_pos, None));
                                                     }
                                                 }
                                             }
@@ -1600,7 +2100,7 @@ impl Dependencies {
         lookup_steps: &[Vec<&str>],
         is_loc: bool,
         column: usize,
-        table_data_cache: &HashMap<String, HashMap<String, String>>
+        table_data_cache: &HashMap<String, (ColumnIndex, usize, Option<(String, Vec<u32>)>)>
     ) -> Option<String> {
         let mut data_found: Option<String> = None;
 
@@ -1614,9 +2114,24 @@ impl Dependencies {
         if let Some(files) = cache.get(source_table) {
             for file in files {
                 let table_data_column_cache_key = file.path_in_container_raw().to_owned() + &current_step.join("++");
-                if let Some(table_data_column_cache) = table_data_cache.get(&table_data_column_cache_key) {
+                if let Some((column_index, value_column, loc_key)) = table_data_cache.get(&table_data_column_cache_key) {
+
+                    // If the key is repeated, the last row with it wins.
+                    let lookup_value = match file.decoded() {
+                        Ok(RFileDecoded::DB(db)) => column_index.rows_containing_data(lookup_key)
+                            .and_then(|rows| rows.last())
+                            .and_then(|row| db.data().get(*row).map(|row| match loc_key {
+                                Some((loc_key, localised_order)) => {
+                                    let mut loc_key = loc_key.to_owned();
+                                    loc_key.push_str(&localised_order.iter().map(|pos| row[*pos as usize].data_to_string()).join(""));
+                                    loc_key
+                                },
+                                None => row[*value_column].data_to_string().to_string(),
+                            })),
+                        _ => None,
+                    };
 
-                    if let Some(lookup_value) = table_data_column_cache.get(&**lookup_key) {
+                    if let Some(lookup_value) = lookup_value {
 
                         // If we're not yet in the last step, reduce the steps and repeat.
                         if lookup_steps.len() > 1 {
@@ -1628,18 +2143,18 @@ impl Dependencies {
                         // If we're on the last step, properly get the lookup data. Locs first.
                         else if is_loc {
 
-                            if let Some(data) = loc_data.get(&**lookup_value) {
+                            if let Some(data) = loc_data.get(lookup_value.as_str()) {
                                 data_found = Some(data.to_string());
-                            } else if let Some(data) = self.localisation_data.get(&**lookup_value) {
+                            } else if let Some(data) = self.localisation_data.get(&lookup_value) {
                                 data_found = Some(data.to_string());
                             } else {
-                                data_found = Some(lookup_value.to_string())
+                                data_found = Some(lookup_value)
                             }
                         }
 
                         // Then table columns.
                         else {
-                            data_found = Some(lookup_value.to_owned());
+                            data_found = Some(lookup_value);
                         }
 
                         // If we find a match, don't bother with the rest of the files.
@@ -1652,6 +2167,11 @@ impl Dependencies {
         data_found
     }
 
+    /// This function returns if the provided loc key has an entry in the locs of the game files or the parent mods.
+    pub fn loc_key_exists(&self, key: &str) -> bool {
+        self.localisation_data.contains_key(key)
+    }
+
     /// This function returns the table/column/key from the provided loc key.
     ///
     /// We return the table without "_tables". Keep that in mind if you use this.
@@ -2184,6 +2704,30 @@ impl Dependencies {
         self.vanilla_locs.insert(path);
     }
 
+    /// This function manually inserts a DB file into the dependencies as a vanilla table.
+    ///
+    /// THIS IS DANGEROUS. DO NOT USE IT UNLESS YOU KNOW WHAT YOU'RE DOING.
+    pub fn insert_db_as_vanilla_db(&mut self, rfile: RFile) {
+        if let Some(table_name) = rfile.db_table_name_from_path() {
+            let path = rfile.path_in_container_raw().to_owned();
+            self.vanilla_tables.entry(table_name.to_owned()).or_default().push(path.to_owned());
+            self.vanilla_files.insert(path, rfile);
+            self.clear_decoded_tables_cache();
+        }
+    }
+
+    /// This function manually inserts a DB file into the dependencies as a parent table.
+    ///
+    /// THIS IS DANGEROUS. DO NOT USE IT UNLESS YOU KNOW WHAT YOU'RE DOING.
+    pub fn insert_db_as_parent_db(&mut self, rfile: RFile) {
+        if let Some(table_name) = rfile.db_table_name_from_path() {
+            let path = rfile.path_in_container_raw().to_owned();
+            self.parent_tables.entry(table_name.to_owned()).or_default().push(path.to_owned());
+            self.parent_files.insert(path, rfile);
+            self.clear_decoded_tables_cache();
+        }
+    }
+
     /// This function manipulates a definition to recursively add reference lookups if found.
     ///
     /// THIS IS DANGEROUS IF WE FIND A CYCLIC DEPENDENCY.
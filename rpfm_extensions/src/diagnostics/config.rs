@@ -13,10 +13,12 @@
 use getset::{Getters, MutGetters};
 use serde_derive::{Serialize, Deserialize};
 
+use std::collections::BTreeSet;
 use std::{fmt, fmt::Display};
 use std::path::Path;
 
 use rpfm_lib::games::GameInfo;
+use rpfm_lib::schema::DefinitionIssue;
 
 use crate::diagnostics::*;
 
@@ -43,7 +45,10 @@ pub enum ConfigDiagnosticReportType {
     DependenciesCacheNotGenerated,
     DependenciesCacheOutdated,
     DependenciesCacheCouldNotBeLoaded(String),
-    IncorrectGamePath,
+    IncorrectGamePath(String),
+    SchemaReferenceCycle(String),
+    DuplicateFieldName(String, String),
+    InvalidSchemaReference(String, String),
 }
 
 //-------------------------------------------------------------------------------//
@@ -60,11 +65,14 @@ impl ConfigDiagnosticReport {
 
 impl DiagnosticReport for ConfigDiagnosticReport {
     fn message(&self) -> String {
-        match self.report_type {
+        match &self.report_type {
             ConfigDiagnosticReportType::DependenciesCacheNotGenerated => "Dependency Cache not generated for the currently selected game.".to_owned(),
             ConfigDiagnosticReportType::DependenciesCacheOutdated => "Dependency Cache for the selected game is outdated and could not be loaded.".to_owned(),
             ConfigDiagnosticReportType::DependenciesCacheCouldNotBeLoaded(_) => "Dependency Cache couldn't be loaded for the game selected, due to errors reading the game's folder.".to_owned(),
-            ConfigDiagnosticReportType::IncorrectGamePath => "Game Path for the current Game Selected is incorrect.".to_owned(),
+            ConfigDiagnosticReportType::IncorrectGamePath(issue) => format!("Game Path for the current Game Selected is incorrect: {issue}."),
+            ConfigDiagnosticReportType::SchemaReferenceCycle(cycle) => format!("Tables referencing each other in a loop in the schema: {cycle}."),
+            ConfigDiagnosticReportType::DuplicateFieldName(table_name, field_name) => format!("Table '{table_name}' has more than one field named '{field_name}' in the schema."),
+            ConfigDiagnosticReportType::InvalidSchemaReference(table_name, field_name) => format!("Field '{field_name}' of table '{table_name}' references itself or a column of its own table that doesn't exist in the schema."),
         }
     }
 
@@ -73,7 +81,10 @@ impl DiagnosticReport for ConfigDiagnosticReport {
             ConfigDiagnosticReportType::DependenciesCacheNotGenerated => DiagnosticLevel::Error,
             ConfigDiagnosticReportType::DependenciesCacheOutdated => DiagnosticLevel::Error,
             ConfigDiagnosticReportType::DependenciesCacheCouldNotBeLoaded(_) => DiagnosticLevel::Error,
-            ConfigDiagnosticReportType::IncorrectGamePath => DiagnosticLevel::Error,
+            ConfigDiagnosticReportType::IncorrectGamePath(_) => DiagnosticLevel::Error,
+            ConfigDiagnosticReportType::SchemaReferenceCycle(_) => DiagnosticLevel::Info,
+            ConfigDiagnosticReportType::DuplicateFieldName(_, _) => DiagnosticLevel::Error,
+            ConfigDiagnosticReportType::InvalidSchemaReference(_, _) => DiagnosticLevel::Warning,
         }
     }
 }
@@ -84,7 +95,10 @@ impl Display for ConfigDiagnosticReportType {
             Self::DependenciesCacheNotGenerated => "DependenciesCacheNotGenerated",
             Self::DependenciesCacheOutdated => "DependenciesCacheOutdated",
             Self::DependenciesCacheCouldNotBeLoaded(_) => "DependenciesCacheCouldNotBeLoaded",
-            Self::IncorrectGamePath => "IncorrectGamePath",
+            Self::IncorrectGamePath(_) => "IncorrectGamePath",
+            Self::SchemaReferenceCycle(_) => "SchemaReferenceCycle",
+            Self::DuplicateFieldName(_, _) => "DuplicateFieldName",
+            Self::InvalidSchemaReference(_, _) => "InvalidSchemaReference",
         }, f)
     }
 }
@@ -92,13 +106,12 @@ impl Display for ConfigDiagnosticReportType {
 impl ConfigDiagnostic {
 
     /// This function takes care of checking RPFM's configuration for errors.
-    pub fn check(dependencies: &Dependencies, game_info: &GameInfo, game_path: &Path) -> Option<DiagnosticType> {
+    pub fn check(dependencies: &Dependencies, schema: &Schema, game_info: &GameInfo, game_path: &Path) -> Option<DiagnosticType> {
         let mut diagnostic = ConfigDiagnostic::default();
 
         // First, check if we have the game folder correctly configured. We can't do anything without it.
-        let exe_path = game_info.executable_path(game_path).filter(|path| path.is_file());
-        if exe_path.is_none() {
-            diagnostic.results_mut().push(ConfigDiagnosticReport::new(ConfigDiagnosticReportType::IncorrectGamePath));
+        if let Err(issue) = game_info.validate_install_path(game_path) {
+            diagnostic.results_mut().push(ConfigDiagnosticReport::new(ConfigDiagnosticReportType::IncorrectGamePath(issue.to_string())));
         }
 
         // If we have the correct folder, check if the vanilla data of the dependencies is loaded.
@@ -121,6 +134,36 @@ impl ConfigDiagnostic {
             }
         }
 
+        // Reference cycles in the schema are not a problem of the Pack, but they're worth knowing about.
+        // They're cached in the schema, so we don't go through the entire schema on every check.
+        for cycle in schema.reference_cycles().iter() {
+            let cycle = cycle.iter().chain(cycle.first()).map(|table_name| table_name.as_str()).collect::<Vec<_>>().join(" -> ");
+            diagnostic.results_mut().push(ConfigDiagnosticReport::new(ConfigDiagnosticReportType::SchemaReferenceCycle(cycle)));
+        }
+
+        // Duplicated field names break any name-based column mapping, and references to the field's own column or to missing columns
+        // of its own table can never be valid, so report them once per table and field. The issues are cached in the schema too.
+        let mut duplicated_fields = BTreeSet::new();
+        let mut invalid_references = BTreeSet::new();
+        for (table_name, issues) in schema.definition_issues().iter() {
+            for issue in issues {
+                match issue {
+                    DefinitionIssue::DuplicateFieldName(field_name) => { duplicated_fields.insert((table_name.to_owned(), field_name.to_owned())); },
+                    DefinitionIssue::SelfReferencingField(field_name) |
+                    DefinitionIssue::ReferenceToMissingField(field_name, _) => { invalid_references.insert((table_name.to_owned(), field_name.to_owned())); },
+                    _ => {},
+                }
+            }
+        }
+
+        for (table_name, field_name) in duplicated_fields {
+            diagnostic.results_mut().push(ConfigDiagnosticReport::new(ConfigDiagnosticReportType::DuplicateFieldName(table_name, field_name)));
+        }
+
+        for (table_name, field_name) in invalid_references {
+            diagnostic.results_mut().push(ConfigDiagnosticReport::new(ConfigDiagnosticReportType::InvalidSchemaReference(table_name, field_name)));
+        }
+
         if !diagnostic.results().is_empty() {
             Some(DiagnosticType::Config(diagnostic))
         } else { None }
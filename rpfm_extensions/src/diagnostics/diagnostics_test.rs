@@ -0,0 +1,688 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the diagnostics.
+
+use super::*;
+
+#[test]
+fn test_diagnostics_profile() {
+    let mut profile = DiagnosticsProfile::new("strict");
+    profile.diagnostics_ignored_mut().push("OutdatedTable".to_owned());
+    profile.files_ignored_mut().push("db/test_tables/test".to_owned());
+    *profile.max_decode_bytes_mut() = 1024;
+    *profile.source_mut() = SearchSource::ParentFiles;
+
+    // Profiles must survive a save/load roundtrip.
+    let path = std::env::temp_dir().join("rpfm_test_diagnostics_profile.json");
+    profile.save(&path).unwrap();
+    let loaded = DiagnosticsProfile::load(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(profile, loaded);
+
+    // And the diagnostics built from them must use their config, without results.
+    let diagnostics = loaded.to_diagnostics();
+    assert_eq!(diagnostics.diagnostics_ignored(), profile.diagnostics_ignored());
+    assert_eq!(diagnostics.files_ignored(), profile.files_ignored());
+    assert_eq!(*diagnostics.max_decode_bytes(), 1024);
+    assert_eq!(*diagnostics.source(), SearchSource::ParentFiles);
+    assert!(diagnostics.results().is_empty());
+}
+
+#[test]
+fn test_loc_key_regex() {
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+    let mut diagnostics = Diagnostics::default();
+
+    // Without a pattern, the built-in rule is used.
+    assert!(diagnostics.loc_key_regex(game).is_none());
+
+    // Patterns are anchored to the full key.
+    diagnostics.loc_key_patterns_mut().insert(KEY_WARHAMMER_3.to_owned(), "[a-z_]+".to_owned());
+    let regex = diagnostics.loc_key_regex(game).unwrap();
+    assert!(regex.is_match("land_units_onscreen_name_test"));
+    assert!(!regex.is_match("Land_Units_onscreen_name_test"));
+
+    // Invalid patterns fall back to the built-in rule.
+    diagnostics.loc_key_patterns_mut().insert(KEY_WARHAMMER_3.to_owned(), "[a-z".to_owned());
+    assert!(diagnostics.loc_key_regex(game).is_none());
+}
+
+#[test]
+fn test_text_referenced_file_not_found() {
+    use rpfm_lib::files::text::Text;
+
+    let rules = TextDiagnostic::parse_path_rules("xml:path, .XML:image,lua:path,:path,broken");
+    assert_eq!(rules, vec![
+        ("xml".to_owned(), "path".to_owned()),
+        (".XML".to_owned(), "image".to_owned()),
+        ("lua".to_owned(), "path".to_owned()),
+    ]);
+
+    let regexes = TextDiagnostic::path_regexes(&rules);
+    assert_eq!(regexes.len(), 2);
+    assert_eq!(regexes["xml"].len(), 2);
+
+    let mut text = Text::default();
+    text.set_contents("<root>\n  <image path=\"ui/found.png\"/>\n  <image path='ui\\missing.png' image=\"\"/>\n</root>".to_owned());
+    let file = RFile::new_from_decoded(&RFileDecoded::Text(text), 0, "ui/test.xml");
+
+    let mut local_path_list = HashMap::new();
+    local_path_list.insert("ui/found.png".to_owned(), vec!["ui/found.png".to_owned()]);
+
+    let dependencies = Dependencies::default();
+    let diagnostic = TextDiagnostic::check(&file, &regexes, &dependencies, &[], &[], &HashSet::new(), &HashMap::new(), &local_path_list);
+    let diagnostic = match diagnostic {
+        Some(DiagnosticType::Text(diagnostic)) => diagnostic,
+        _ => panic!("Expected a text diagnostic."),
+    };
+
+    assert_eq!(diagnostic.results().len(), 1);
+    assert_eq!(*diagnostic.results()[0].line(), 3);
+    assert!(matches!(diagnostic.results()[0].report_type(), TextDiagnosticReportType::ReferencedFileNotFound(path) if path == "ui/missing.png"));
+
+    // Ignored diagnostics are not reported.
+    let diagnostic = TextDiagnostic::check(&file, &regexes, &dependencies, &["ReferencedFileNotFound".to_owned()], &[], &HashSet::new(), &HashMap::new(), &local_path_list);
+    assert!(diagnostic.is_none());
+
+    // Files without rules are not checked.
+    let file = RFile::new_from_decoded(&RFileDecoded::Text(Text::default()), 0, "ui/test.txt");
+    assert!(TextDiagnostic::check(&file, &regexes, &dependencies, &[], &[], &HashSet::new(), &HashMap::new(), &local_path_list).is_none());
+}
+
+#[test]
+fn test_reproducer_bundle() {
+    use std::io::Cursor;
+    use rpfm_lib::binary::ReadBytes;
+    use rpfm_lib::files::Decodeable;
+    use rpfm_lib::games::pfh_version::PFHVersion;
+
+    let mut diagnostic = TextDiagnostic::new("ui/test.xml");
+    diagnostic.results_mut().push(TextDiagnosticReport::new(TextDiagnosticReportType::ReferencedFileNotFound("ui/a.png".to_owned()), 1));
+    diagnostic.results_mut().push(TextDiagnosticReport::new(TextDiagnosticReportType::ReferencedFileNotFound("ui/b.png".to_owned()), 2));
+    let diagnostic = DiagnosticType::Text(diagnostic);
+
+    let mut pack = Pack::new_with_version(PFHVersion::PFH6);
+    pack.insert(RFile::new_from_vec(b"<image path=\"ui/a.png\"/>", FileType::Text, 0, "ui/test.xml")).unwrap();
+    pack.insert(RFile::new_from_vec(b"unrelated", FileType::Text, 0, "ui/other.xml")).unwrap();
+
+    assert!(Diagnostics::reproducer_bundle(&diagnostic, 2, &pack, &Schema::default()).is_err());
+
+    let data = Diagnostics::reproducer_bundle(&diagnostic, 1, &pack, &Schema::default()).unwrap();
+    let mut reader = Cursor::new(data);
+    let mut extra_data = DecodeableExtraData::default();
+    extra_data.set_data_size(reader.len().unwrap());
+    let mut bundle = Pack::decode(&mut reader, &Some(extra_data)).unwrap();
+
+    // Only the affected file and the diagnostic must be in the bundle, and the diagnostic must only contain the selected report.
+    let mut paths = bundle.paths_raw();
+    paths.sort();
+    assert_eq!(paths, vec!["rpfm_reproducer/diagnostic.json", "ui/test.xml"]);
+
+    let file = bundle.file_mut("rpfm_reproducer/diagnostic.json", false).unwrap();
+    let diagnostic: DiagnosticType = serde_json::from_slice(&file.encode(&None, false, false, true).unwrap().unwrap()).unwrap();
+    match diagnostic {
+        DiagnosticType::Text(diagnostic) => {
+            assert_eq!(diagnostic.results().len(), 1);
+            assert_eq!(*diagnostic.results()[0].line(), 2);
+        }
+        _ => panic!("Expected a text diagnostic."),
+    }
+}
+
+#[test]
+fn test_table_name_folder_mismatch() {
+    use rpfm_lib::files::{db::DB, table::DecodedData};
+    use rpfm_lib::games::pfh_version::PFHVersion;
+    use rpfm_lib::schema::Definition;
+
+    let units_definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("value", FieldType::I32, false)], &[]);
+    let land_units_definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("value", FieldType::I32, false), ("flag", FieldType::Boolean, false)], &[]);
+
+    let mut schema = Schema::default();
+    schema.add_definition("units_tables", &units_definition);
+    schema.add_definition("land_units_tables", &land_units_definition);
+
+    // Encode a units table, and put it both in its folder and in the folder of another table.
+    let mut table = DB::new(&units_definition, None, "units_tables");
+    table.set_data(&[vec![DecodedData::StringU8("unit".to_owned()), DecodedData::I32(1)]]).unwrap();
+    let mut data = vec![];
+    table.encode(&mut data, &None).unwrap();
+
+    let mut pack = Pack::new_with_version(PFHVersion::PFH6);
+    pack.insert(RFile::new_from_vec(&data, FileType::DB, 0, "db/units_tables/my_mod")).unwrap();
+    pack.insert(RFile::new_from_vec(&data, FileType::DB, 0, "db/land_units_tables/my_mod")).unwrap();
+
+    // Decode them the same way they're decoded when opening a Pack, so their table names come from their folders.
+    let mut extra_data = DecodeableExtraData::default();
+    extra_data.set_schema(Some(&schema));
+    let extra_data = Some(extra_data);
+    for file in pack.files_by_type_mut(&[FileType::DB]) {
+        let _ = file.decode(&extra_data, true, false);
+    }
+
+    let check = |path: &str| TableDiagnostic::check_db_folder(pack.file(path, false).unwrap(), &schema, &[], &[], &HashSet::new(), &HashMap::new())
+        .map(|diagnostic| match diagnostic {
+            DiagnosticType::DB(diagnostic) => diagnostic.results().iter().any(|result| matches!(result.report_type(), TableDiagnosticReportType::TableNameFolderMismatch)),
+            _ => false,
+        })
+        .unwrap_or(false);
+
+    assert!(!check("db/units_tables/my_mod"));
+    assert!(check("db/land_units_tables/my_mod"));
+
+    // Ignoring the diagnostic must skip the check.
+    let ignored = vec!["TableNameFolderMismatch".to_owned()];
+    assert!(TableDiagnostic::check_db_folder(pack.file("db/land_units_tables/my_mod", false).unwrap(), &schema, &ignored, &[], &HashSet::new(), &HashMap::new()).is_none());
+}
+
+#[test]
+fn test_duplicated_combined_keys_conflicting() {
+    use rpfm_lib::files::{db::DB, table::DecodedData};
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+    use rpfm_lib::schema::{Definition, FieldType};
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+    let dependencies = Dependencies::default();
+
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("value", FieldType::I32, false)], &[]);
+    let mut table = DB::new(&definition, None, "units_tables");
+    table.set_data(&[("a", 1), ("a", 1), ("b", 1), ("b", 2), ("c", 1), ("c", 1), ("c", 2)].iter()
+        .map(|(key, value)| vec![DecodedData::StringU8(key.to_string()), DecodedData::I32(*value)])
+        .collect::<Vec<_>>()).unwrap();
+
+    // Exact duplicates and conflicting duplicates must be reported separately.
+    let file = RFile::new_from_decoded(&RFileDecoded::DB(table), 0, "db/units_tables/my_mod");
+    let results = match TableDiagnostic::check_db(&file, &dependencies, &Schema::default(), &[], &[], &HashSet::new(), &HashMap::new(), game, &HashMap::new(), &HashMap::new(), &None, &HashMap::new(), false) {
+        Some(DiagnosticType::DB(diagnostic)) => diagnostic.results().clone(),
+        _ => panic!("Expected a table diagnostic."),
+    };
+
+    let rows_with = |report_type: &str| results.iter()
+        .filter(|result| result.report_type().to_string() == report_type)
+        .map(|result| result.cells_affected()[0].0)
+        .collect::<Vec<_>>();
+
+    // Groups are classified as a whole, so a group with exact duplicates and a conflicting row is a conflict, and each row is reported once.
+    assert_eq!(rows_with("DuplicatedCombinedKeys"), vec![0, 1]);
+    assert_eq!(rows_with("DuplicatedCombinedKeysConflicting"), vec![2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_loc_formatting_rules() {
+    let rules = LocFormattingRules::default();
+    assert!(rules.check("[[col:red]]Text[[/col]] and {{tr:test}}").is_none());
+    assert!(rules.check("[[col:red]Text[[/col]]").is_some());
+    assert!(rules.check("Text]] [[col:red").is_some());
+    assert!(rules.check("{{tr:test}").is_some());
+
+    // Custom rules replace the built-in ones, and invalid ones are ignored.
+    let rules = LocFormattingRules::parse("pair:(:);pattern:%[^sd%];pattern:[a-;broken");
+    assert!(rules.check("[[col:red]Text").is_none());
+    assert!(rules.check("(%s)").is_none());
+    assert_eq!(rules.check("(50%)"), Some("suspicious token \"%)\"".to_owned()));
+    assert_eq!(rules.check("(text"), Some("\"(\" without a matching \")\"".to_owned()));
+
+    // Tokens can contain escaped separators and backslashes, and patterns keep their own escapes.
+    let rules = LocFormattingRules::parse(r"pair:\:<:>\;;pair:\\(:\\);pattern:a\;\d");
+    assert!(rules.check(":<text>; \\(text\\) a;b").is_none());
+    assert_eq!(rules.check(":<text>"), Some("\":<\" without a matching \">;\"".to_owned()));
+    assert_eq!(rules.check("\\(text"), Some("\"\\(\" without a matching \"\\)\"".to_owned()));
+    assert_eq!(rules.check("a;1"), Some("suspicious token \"a;1\"".to_owned()));
+}
+
+#[test]
+fn test_inconsistent_escaping() {
+
+    // Control characters and double escapes are always reported.
+    assert!(TableDiagnostic::escaping_issue("text\rtext", false, false).is_some());
+    assert!(TableDiagnostic::escaping_issue("text\\\\ntext", false, false).is_some());
+
+    // Line jumps are only reported if the table mixes raw and escaped ones.
+    assert!(TableDiagnostic::escaping_issue("line\nline", false, true).is_none());
+    assert!(TableDiagnostic::escaping_issue("line\\nline", true, false).is_none());
+    assert!(TableDiagnostic::escaping_issue("line\nline", true, true).is_some());
+    assert!(TableDiagnostic::escaping_issue("line\\tline", true, true).is_some());
+    assert!(TableDiagnostic::escaping_issue("plain text", true, true).is_none());
+
+    // Backslashes in paths are folder separators, not escapes.
+    assert!(TableDiagnostic::is_path_like("ui\\new\\icon.png"));
+    assert!(TableDiagnostic::is_path_like("ui/new"));
+    assert!(!TableDiagnostic::is_path_like("line\\n line"));
+    assert!(TableDiagnostic::escaping_issue("ui\\new\\icon.png", true, true).is_none());
+    assert!(TableDiagnostic::escaping_issue("ui\\\\new\\\\icon.png", true, true).is_none());
+}
+
+#[test]
+fn test_is_path_like() {
+
+    // Escaped separators are still separators.
+    assert!(TableDiagnostic::is_path_like("ui\\units\\icons"));
+    assert!(TableDiagnostic::is_path_like("ui\\\\units\\\\icons"));
+    assert!(TableDiagnostic::is_path_like("ui\\\\new\\\\icon.png"));
+
+    // One-word texts with escaped line jumps or tabulations are not paths, unless they end in a file name.
+    assert!(!TableDiagnostic::is_path_like("line\\nline"));
+    assert!(!TableDiagnostic::is_path_like("line\\tline"));
+    assert!(!TableDiagnostic::is_path_like("line\\\\nline"));
+    assert!(!TableDiagnostic::is_path_like("ui\\new"));
+    assert!(TableDiagnostic::is_path_like("ui\\new\\icon.png"));
+    assert!(!TableDiagnostic::is_path_like("ui\\new\\.png"));
+}
+
+#[test]
+fn test_inconsistent_escaping_loc() {
+    use rpfm_lib::files::{loc::Loc, table::DecodedData};
+
+    let row = |key: &str, text: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(false)];
+    let mut loc = Loc::new();
+    loc.set_data(&[
+        row("key_raw", "line\nline"),
+        row("key_escaped", "line\\nline"),
+        row("key_path", "ui\\new\\icon.png"),
+    ]).unwrap();
+
+    let file = RFile::new_from_decoded(&RFileDecoded::Loc(loc), 0, "text/db/test.loc");
+    let diagnostic = TableDiagnostic::check_loc(&file, &[], &[], &HashSet::new(), &HashMap::new(), None, &LocFormattingRules::default()).unwrap();
+    let escaping = match diagnostic {
+        DiagnosticType::Loc(diagnostic) => diagnostic.results().iter()
+            .filter(|result| matches!(result.report_type(), TableDiagnosticReportType::InconsistentEscaping(_)))
+            .map(|result| result.cells_affected().clone())
+            .collect::<Vec<_>>(),
+        _ => panic!("expected a loc diagnostic"),
+    };
+
+    // Both mixed texts are reported, but not the path.
+    assert_eq!(escaping, vec![vec![(0, 1)], vec![(1, 1)]]);
+}
+
+#[test]
+fn test_report_to_tsv() {
+    let mut diagnostic = TableDiagnostic::new("db/units_tables/test");
+    diagnostic.results_mut().push(TableDiagnosticReport::new(TableDiagnosticReportType::EmptyKeyFields, &[(0, 1), (2, -1)], &[]));
+    let diagnostic = DiagnosticType::DB(diagnostic);
+
+    let tsv = diagnostic.report_to_tsv(0).unwrap();
+    let fields = tsv.split('\t').collect::<Vec<_>>();
+    assert_eq!(fields.len(), 6);
+    assert_eq!(fields[0], "Warning");
+    assert_eq!(fields[1], "DB");
+    assert_eq!(fields[2], "EmptyKeyFields");
+    assert_eq!(fields[3], "db/units_tables/test");
+    assert_eq!(fields[5], "0:1 2:*");
+
+    assert!(diagnostic.report_to_tsv(1).is_none());
+}
+
+#[test]
+fn test_self_reference_columns() {
+    use rpfm_lib::schema::Field;
+
+    let field = |name: &str, is_key: bool, is_reference: Option<(&str, &str)>| {
+        let mut field = Field::new_for_tests(name, FieldType::StringU8, is_key);
+        field.set_is_reference(is_reference.map(|(table, column)| (table.to_owned(), column.to_owned())));
+        field
+    };
+    let fields = vec![
+        field("key", true, None),
+        field("parent", false, Some(("units", "key"))),
+        field("category", false, Some(("categories", "key"))),
+        field("sibling", false, Some(("units", "category"))),
+    ];
+
+    // Only references to key columns of the same table count.
+    assert_eq!(TableDiagnostic::self_reference_columns("units_tables", &fields, None), vec![(1, 0)]);
+    assert_eq!(TableDiagnostic::self_reference_columns("units", &fields, None), vec![(1, 0)]);
+    assert!(TableDiagnostic::self_reference_columns("categories_tables", &fields, None).is_empty());
+}
+
+#[test]
+fn test_diagnostics_retain_and_to_csv() {
+    let mut table = TableDiagnostic::new("db/units_tables/test");
+    table.results_mut().push(TableDiagnosticReport::new(TableDiagnosticReportType::EmptyKeyFields, &[(0, 1)], &[]));
+    table.results_mut().push(TableDiagnosticReport::new(TableDiagnosticReportType::EmptyRow, &[(1, -1)], &[]));
+
+    let mut text = TextDiagnostic::new("text/test.xml");
+    text.results_mut().push(TextDiagnosticReport::new(TextDiagnosticReportType::ReferencedFileNotFound("ui/a,b.png".to_owned()), 1));
+
+    let mut diagnostics = Diagnostics::default();
+    diagnostics.results_mut().push(DiagnosticType::DB(table));
+    diagnostics.results_mut().push(DiagnosticType::Text(text));
+
+    let csv = diagnostics.to_csv();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "level,diag_type,path,message,report_type");
+    assert!(lines[1].starts_with("Warning,DB,db/units_tables/test,"));
+    assert!(lines[1].ends_with(",EmptyKeyFields"));
+
+    // Fields with commas must be quoted.
+    assert_eq!(lines[3], "Warning,Text,text/test.xml,\"Referenced file not found in line 1: ui/a,b.png.\",ReferencedFileNotFound");
+
+    // Diagnostics left without reports are removed.
+    diagnostics.retain_reports(|_, report_type| report_type == "EmptyRow");
+    assert_eq!(diagnostics.reports_count(), 1);
+    assert_eq!(diagnostics.results().len(), 1);
+    assert!(diagnostics.to_csv().lines().nth(1).unwrap().ends_with(",EmptyRow"));
+}
+
+#[test]
+fn test_missing_loc_keys() {
+    use rpfm_lib::files::table::DecodedData;
+    use rpfm_lib::schema::Definition;
+
+    let mut definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("value", FieldType::StringU8, false)], &[("onscreen_name", FieldType::StringU8, false), ("description", FieldType::StringU8, false)]);
+    *definition.localised_key_order_mut() = vec![0];
+
+    let row = vec![DecodedData::StringU8("unit".to_owned()), DecodedData::StringU8(String::new())];
+    let missing = TableDiagnostic::missing_loc_keys(&definition, "land_units_tables", &row, |key| key == "land_units_onscreen_name_unit");
+    assert_eq!(missing, vec![("description".to_owned(), "land_units_description_unit".to_owned())]);
+
+    // Rows with empty keys don't need loc entries.
+    let row = vec![DecodedData::StringU8(String::new()), DecodedData::StringU8(String::new())];
+    assert!(TableDiagnostic::missing_loc_keys(&definition, "land_units_tables", &row, |_| false).is_empty());
+}
+
+/// This function returns a schema, a Pack with a table referencing a units table with a lookup, and dependencies containing said units table.
+fn reference_lookup_data(rows: usize) -> (Schema, Pack, Dependencies) {
+    use rpfm_lib::files::{db::DB, table::DecodedData};
+    use rpfm_lib::games::pfh_version::PFHVersion;
+    use rpfm_lib::schema::Definition;
+
+    let units_definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("name", FieldType::StringU8, false)], &[]);
+    let mut armies_definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("unit", FieldType::StringU8, false)], &[]);
+    armies_definition.fields_mut()[1].set_is_reference(Some(("units".to_owned(), "key".to_owned())));
+    armies_definition.fields_mut()[1].set_lookup(Some(vec!["name".to_owned()]));
+
+    let mut schema = Schema::default();
+    schema.add_definition("units_tables", &units_definition);
+    schema.add_definition("armies_tables", &armies_definition);
+
+    // The last unit repeats the key of the first one, so its name must win in the lookups.
+    let mut units = DB::new(&units_definition, None, "units_tables");
+    units.set_data(&(0..rows)
+        .map(|row| vec![DecodedData::StringU8(format!("unit_{row}")), DecodedData::StringU8(format!("name_{row}"))])
+        .chain(std::iter::once(vec![DecodedData::StringU8("unit_0".to_owned()), DecodedData::StringU8("name_last".to_owned())]))
+        .collect::<Vec<_>>()).unwrap();
+
+    let mut dependencies = Dependencies::default();
+    dependencies.insert_db_as_vanilla_db(RFile::new_from_decoded(&RFileDecoded::DB(units), 0, "db/units_tables/data"));
+
+    // Every row references a unit, the last one a missing unit.
+    let mut armies = DB::new(&armies_definition, None, "armies_tables");
+    armies.set_data(&(0..=rows)
+        .map(|row| vec![DecodedData::StringU8(format!("army_{row}")), DecodedData::StringU8(format!("unit_{row}"))])
+        .collect::<Vec<_>>()).unwrap();
+
+    let mut pack = Pack::new_with_version(PFHVersion::PFH6);
+    pack.insert(RFile::new_from_decoded(&RFileDecoded::DB(armies), 0, "db/armies_tables/my_mod")).unwrap();
+
+    (schema, pack, dependencies)
+}
+
+#[test]
+fn test_reference_lookups() {
+    let (schema, pack, mut dependencies) = reference_lookup_data(10);
+    dependencies.generate_local_db_references(&schema, &pack, &["armies_tables".to_owned()]);
+
+    let table = match pack.file("db/armies_tables/my_mod", false).unwrap().decoded().unwrap() {
+        RFileDecoded::DB(table) => table.clone(),
+        _ => unreachable!(),
+    };
+
+    // Lookups must resolve to the last row with the referenced key.
+    let references = dependencies.db_reference_data(&schema, &pack, "armies_tables", table.definition(), &None);
+    let references = references.get(&1).unwrap();
+    assert_eq!(references.data().len(), 10);
+    assert_eq!(references.data().get("unit_0").map(|x| x.as_str()), Some("name_last"));
+    assert_eq!(references.data().get("unit_9").map(|x| x.as_str()), Some("name_9"));
+    assert!(references.data().get("unit_10").is_none());
+}
+
+#[test]
+#[ignore]
+fn bench_reference_lookups() {
+    use std::time::Instant;
+
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+
+    let rows = 100_000;
+    let (schema, mut pack, mut dependencies) = reference_lookup_data(rows);
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    // Resolving each reference by scanning the referenced table, against using an index of its key column.
+    let units = match dependencies.db_data("units_tables", true, false).unwrap()[0].decoded().unwrap() {
+        RFileDecoded::DB(table) => table.clone(),
+        _ => unreachable!(),
+    };
+
+    let lookups = 1_000;
+    let start = Instant::now();
+    let found = (0..lookups).filter(|row| units.table().rows_containing_data("key", &format!("unit_{}", row * 97)).is_some()).count();
+    println!("{lookups} lookups by scanning: {:?}, {found} found", start.elapsed());
+
+    let start = Instant::now();
+    let index = units.table().build_column_index("key").unwrap();
+    let found = (0..lookups).filter(|row| index.contains(&format!("unit_{}", row * 97))).count();
+    println!("{lookups} lookups by index, including building it: {:?}, {found} found", start.elapsed());
+
+    // Full reference check of a table with a reference on each row.
+    for run in 0..3 {
+        let mut diagnostics = Diagnostics::default();
+        let start = Instant::now();
+        diagnostics.check_files_of_source(&mut pack, &mut dependencies, &schema, game, &[], false);
+        println!("check run {run} over {rows} references: {:?}, {} diagnostics", start.elapsed(), diagnostics.results().len());
+    }
+}
+
+#[test]
+fn test_empty_column() {
+    use rpfm_lib::files::{db::DB, table::DecodedData};
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+    use rpfm_lib::schema::Definition;
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let definition = Definition::new_for_tests(1, &[
+        ("key", FieldType::StringU8, true),
+        ("name", FieldType::StringU8, false),
+        ("description", FieldType::OptionalStringU8, false),
+        ("value", FieldType::I32, false),
+        ("flag", FieldType::Boolean, false),
+    ], &[]);
+
+    let mut table = DB::new(&definition, None, "units_tables");
+    table.set_data(&[
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::StringU8(String::new()), DecodedData::OptionalStringU8(String::new()), DecodedData::I32(0), DecodedData::Boolean(false)],
+        vec![DecodedData::StringU8("b".to_owned()), DecodedData::StringU8(String::new()), DecodedData::OptionalStringU8(String::new()), DecodedData::I32(0), DecodedData::Boolean(false)],
+    ]).unwrap();
+
+    // Only string columns must be reported, as zero and false are valid values for the rest.
+    let file = RFile::new_from_decoded(&RFileDecoded::DB(table), 0, "db/units_tables/my_mod");
+    let empty_columns = match TableDiagnostic::check_db(&file, &Dependencies::default(), &Schema::default(), &[], &[], &HashSet::new(), &HashMap::new(), game, &HashMap::new(), &HashMap::new(), &None, &HashMap::new(), false) {
+        Some(DiagnosticType::DB(diagnostic)) => diagnostic.results().iter()
+            .filter_map(|result| match result.report_type() {
+                TableDiagnosticReportType::EmptyColumn(field_name) => Some(field_name.to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+
+    assert_eq!(empty_columns, vec!["name".to_owned(), "description".to_owned()]);
+}
+
+#[test]
+fn test_check_source() {
+    use std::path::PathBuf;
+
+    use rpfm_lib::files::{db::DB, table::DecodedData};
+    use rpfm_lib::games::pfh_version::PFHVersion;
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+    use rpfm_lib::schema::Definition;
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("value", FieldType::StringU8, false)], &[]);
+    let mut schema = Schema::default();
+    schema.add_definition("units_tables", &definition);
+
+    // Each source has a table with duplicated keys.
+    let duplicated_table = |path: &str| {
+        let mut table = DB::new(&definition, None, "units_tables");
+        table.set_data(&[
+            vec![DecodedData::StringU8("unit".to_owned()), DecodedData::StringU8("value".to_owned())],
+            vec![DecodedData::StringU8("unit".to_owned()), DecodedData::StringU8("value".to_owned())],
+        ]).unwrap();
+        RFile::new_from_decoded(&RFileDecoded::DB(table), 0, path)
+    };
+
+    let mut pack = Pack::new_with_version(PFHVersion::PFH6);
+    pack.insert(duplicated_table("db/units_tables/my_mod")).unwrap();
+
+    let mut dependencies = Dependencies::default();
+    dependencies.insert_db_as_vanilla_db(duplicated_table("db/units_tables/data"));
+    dependencies.insert_db_as_parent_db(duplicated_table("db/units_tables/parent_mod"));
+
+    // Only the files of the source must be checked, and the open Pack must be left untouched.
+    let paths_with_results = |source: SearchSource, dependencies: &mut Dependencies| {
+        let mut diagnostics = Diagnostics::default();
+        *diagnostics.source_mut() = source;
+        diagnostics.check_files_of_source(&mut Pack::default(), dependencies, &schema, game, &[], false);
+        diagnostics.results().iter().map(|result| result.path().to_owned()).collect::<Vec<_>>()
+    };
+
+    assert_eq!(paths_with_results(SearchSource::ParentFiles, &mut dependencies), vec!["db/units_tables/parent_mod".to_owned()]);
+    assert_eq!(paths_with_results(SearchSource::GameFiles, &mut dependencies), vec!["db/units_tables/data".to_owned()]);
+    assert_eq!(pack.files().len(), 1);
+
+    // Checks over the dependencies keep the source they were done over, and the Assembly Kit files cannot be checked.
+    let mut diagnostics = Diagnostics::default();
+    diagnostics.check_source(&SearchSource::AssKitFiles, &mut pack, &mut dependencies, &schema, game, &PathBuf::new(), false);
+    assert_eq!(diagnostics.source(), &SearchSource::AssKitFiles);
+    assert!(diagnostics.results().is_empty());
+
+    diagnostics.check_source(&SearchSource::ParentFiles, &mut pack, &mut dependencies, &schema, game, &PathBuf::new(), false);
+    assert_eq!(diagnostics.source(), &SearchSource::ParentFiles);
+}
+
+#[test]
+fn test_validation_regexes() {
+    use rpfm_lib::files::{db::DB, table::DecodedData};
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+    use rpfm_lib::schema::Definition;
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let mut definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, false), ("broken", FieldType::StringU8, false), ("other", FieldType::StringU8, false)], &[]);
+    definition.fields_mut()[0].set_validation_regex(Some("[a-z_]+".to_owned()));
+    definition.fields_mut()[1].set_validation_regex(Some("[a-z".to_owned()));
+
+    let new_file = |table_name: &str, path: &str| {
+        let mut table = DB::new(&definition, None, table_name);
+        table.set_data(&[
+            vec![DecodedData::StringU8("valid_key".to_owned()), DecodedData::StringU8("a".to_owned()), DecodedData::StringU8("A".to_owned())],
+            vec![DecodedData::StringU8("Invalid Key".to_owned()), DecodedData::StringU8("b".to_owned()), DecodedData::StringU8("B".to_owned())],
+        ]).unwrap();
+        RFile::new_from_decoded(&RFileDecoded::DB(table), 0, path)
+    };
+
+    let file_1 = new_file("units_tables", "db/units_tables/my_mod");
+    let file_2 = new_file("factions_tables", "db/factions_tables/my_mod");
+
+    // Patterns shared between tables must only be compiled once, keeping the invalid ones so they can be reported.
+    let validation_regexes = TableDiagnostic::validation_regexes(&[&file_1, &file_2]);
+    assert_eq!(validation_regexes.len(), 2);
+    assert!(validation_regexes.get("[a-z_]+").unwrap().is_some());
+    assert!(validation_regexes.get("[a-z").unwrap().is_none());
+
+    // Checking with the compiled patterns must report the same as compiling them on the spot.
+    for validation_regexes in [validation_regexes, HashMap::new()] {
+        let results = match TableDiagnostic::check_db(&file_1, &Dependencies::default(), &Schema::default(), &[], &[], &HashSet::new(), &HashMap::new(), game, &HashMap::new(), &HashMap::new(), &None, &validation_regexes, false) {
+            Some(DiagnosticType::DB(diagnostic)) => diagnostic.results().clone(),
+            _ => panic!("Expected a table diagnostic."),
+        };
+
+        let cells_with = |report_type: &str| results.iter()
+            .filter(|result| result.report_type().to_string() == report_type)
+            .map(|result| result.cells_affected()[0])
+            .collect::<Vec<_>>();
+
+        assert_eq!(cells_with("FieldFailsValidationPattern"), vec![(1, 0)]);
+        assert_eq!(cells_with("InvalidValidationPattern"), vec![(-1, 1)]);
+    }
+}
+
+#[test]
+fn test_files_too_large() {
+    use rpfm_lib::files::{db::DB, table::DecodedData};
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+    use rpfm_lib::schema::Definition;
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("value", FieldType::StringU8, false)], &[]);
+    let mut schema = Schema::default();
+    schema.add_definition("units_tables", &definition);
+
+    // Tables are added undecoded, like when they're read from disk.
+    let undecoded_table = |path: &str| {
+        let mut table = DB::new(&definition, None, "units_tables");
+        table.set_data(&[vec![DecodedData::StringU8("unit".to_owned()), DecodedData::StringU8("value".to_owned())]]).unwrap();
+
+        let mut data = vec![];
+        table.encode(&mut data, &None).unwrap();
+        RFile::new_from_vec(&data, FileType::DB, 0, path)
+    };
+
+    let mut pack = Pack::default();
+    pack.insert(undecoded_table("db/units_tables/my_mod")).unwrap();
+
+    let mut dependencies = Dependencies::default();
+    dependencies.insert_db_as_parent_db(undecoded_table("db/units_tables/parent_mod"));
+
+    let skipped_paths = |source: SearchSource, pack: &mut Pack, dependencies: &mut Dependencies| {
+        let mut diagnostics = Diagnostics::default();
+        *diagnostics.source_mut() = source;
+        *diagnostics.max_decode_bytes_mut() = 1;
+        diagnostics.check_files_of_source(pack, dependencies, &schema, game, &[], false);
+        diagnostics.results().iter()
+            .filter_map(|result| match result {
+                DiagnosticType::Pack(diagnostic) => Some(diagnostic.results().iter()
+                    .filter_map(|report| match report.report_type() {
+                        PackDiagnosticReportType::FileTooLargeToCheck(path) => Some(path.to_owned()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<_>>()
+    };
+
+    // Oversized tables must be reported as skipped, no matter the source they come from.
+    assert_eq!(skipped_paths(SearchSource::Pack, &mut pack, &mut dependencies), vec!["db/units_tables/my_mod".to_owned()]);
+    assert_eq!(skipped_paths(SearchSource::ParentFiles, &mut Pack::default(), &mut dependencies), vec!["db/units_tables/parent_mod".to_owned()]);
+
+    // Checking the dependencies must not decode their files in place.
+    let mut diagnostics = Diagnostics::default();
+    *diagnostics.source_mut() = SearchSource::ParentFiles;
+    diagnostics.check_files_of_source(&mut Pack::default(), &mut dependencies, &schema, game, &[], false);
+    assert!(dependencies.files_by_types(&[FileType::DB], false, true).values().all(|file| file.decoded().is_err()));
+}
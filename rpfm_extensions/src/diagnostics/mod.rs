@@ -21,20 +21,27 @@ Notes on cells_affected:
 
 use getset::{Getters, MutGetters};
 use rayon::prelude::*;
+use regex::Regex;
 use serde_derive::{Serialize, Deserialize};
+use serde_json::{json, Value};
 
 use std::borrow::Cow;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::cmp::Ordering;
 use std::{fmt, fmt::Display};
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
+use std::time::Duration;
 
-use rpfm_lib::error::Result;
-use rpfm_lib::files::{ContainerPath, Container, DecodeableExtraData, FileType, pack::Pack, RFile, RFileDecoded};
+use rpfm_lib::error::{Result, RLibError};
+use rpfm_lib::files::{ContainerPath, Container, DecodeableExtraData, Encodeable, FileType, pack::Pack, RFile, RFileDecoded};
 use rpfm_lib::games::{GameInfo, VanillaDBTableNameLogic};
+use rpfm_lib::integrations::log::{Timings, warn};
 use rpfm_lib::schema::{FieldType, Schema};
 
 use crate::dependencies::{Dependencies, TableReferences};
+use crate::search::SearchSource;
 use crate::REGEX_INVALID_ESCAPES;
 
 use self::anim_fragment_battle::*;
@@ -43,6 +50,7 @@ use self::dependency::*;
 use self::pack::*;
 use self::portrait_settings::*;
 use self::table::*;
+use self::text::*;
 
 pub mod anim_fragment_battle;
 pub mod config;
@@ -50,6 +58,15 @@ pub mod dependency;
 pub mod pack;
 pub mod portrait_settings;
 pub mod table;
+pub mod text;
+
+#[cfg(test)] mod diagnostics_test;
+
+/// Name of the Pack generated by [Diagnostics::reproducer_bundle].
+pub const REPRODUCER_PACK_NAME: &str = "rpfm_reproducer.pack";
+
+/// Folder within the reproducer Pack where the extra info about the diagnostic goes.
+const REPRODUCER_FOLDER: &str = "rpfm_reproducer";
 
 //-------------------------------------------------------------------------------//
 //                              Trait definitions
@@ -88,10 +105,73 @@ pub struct Diagnostics {
     /// List of ignored diagnostics.
     diagnostics_ignored: Vec<String>,
 
+    /// Maximum size in bytes of a file for it to be decoded during the check. Bigger files are skipped and reported. 0 means no limit.
+    max_decode_bytes: u64,
+
+    /// Custom patterns loc keys must match to be valid, by game key. Games without one use the built-in rule.
+    loc_key_patterns: HashMap<String, String>,
+
+    /// Rules to find file paths referenced in text files, as (file extension, attribute) pairs. If empty, text files are not checked.
+    #[serde(default)]
+    referenced_path_rules: Vec<(String, String)>,
+
+    /// Custom rules to check the formatting tokens of loc texts, by game key. Games without them use the built-in rules.
+    #[serde(default)]
+    loc_formatting_rules: HashMap<String, String>,
+
+    /// Source of the files checked by the last diagnostics check. All the results come from it.
+    #[serde(default)]
+    source: SearchSource,
+
     /// Results of a diagnostics check.
     results: Vec<DiagnosticType>
 }
 
+/// This struct represents a diagnostics profile: the configuration of a diagnostics check, detached from any UI.
+///
+/// It can be saved to and loaded from a `.json` file, so the same checks can be run from different tools.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters, MutGetters, Serialize, Deserialize)]
+#[getset(get = "pub", get_mut = "pub")]
+pub struct DiagnosticsProfile {
+
+    /// Name of the profile.
+    name: String,
+
+    /// List of ignored folders for diagnostics.
+    folders_ignored: Vec<String>,
+
+    /// List of ignored files for diagnostics.
+    files_ignored: Vec<String>,
+
+    /// List of ignored table fields for diagnostics.
+    fields_ignored: Vec<String>,
+
+    /// List of ignored diagnostics.
+    diagnostics_ignored: Vec<String>,
+
+    /// Maximum size in bytes of a file for it to be decoded during the check. 0 means no limit.
+    max_decode_bytes: u64,
+
+    /// If references only present in the Assembly Kit should be checked.
+    check_ak_only_refs: bool,
+
+    /// Custom patterns loc keys must match to be valid, by game key. Games without one use the built-in rule.
+    #[serde(default)]
+    loc_key_patterns: HashMap<String, String>,
+
+    /// Rules to find file paths referenced in text files, as (file extension, attribute) pairs. If empty, text files are not checked.
+    #[serde(default)]
+    referenced_path_rules: Vec<(String, String)>,
+
+    /// Custom rules to check the formatting tokens of loc texts, by game key. Games without them use the built-in rules.
+    #[serde(default)]
+    loc_formatting_rules: HashMap<String, String>,
+
+    /// Source of the files to check. Useful to check parent mods or the game files in the context of the full load order.
+    #[serde(default)]
+    source: SearchSource,
+}
+
 /// This enum contains the different types of diagnostics we can have.
 ///
 /// One enum to hold them all.
@@ -104,6 +184,7 @@ pub enum DiagnosticType {
     Loc(TableDiagnostic),
     Pack(PackDiagnostic),
     PortraitSettings(PortraitSettingsDiagnostic),
+    Text(TextDiagnostic),
 }
 
 /// This enum defines the possible level of a diagnostic.
@@ -125,6 +206,61 @@ impl Default for DiagnosticType {
     }
 }
 
+impl DiagnosticsProfile {
+
+    /// This function creates a new empty profile with the provided name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// This function loads a [DiagnosticsProfile] to memory from a provided `.json` file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut file = BufReader::new(File::open(path)?);
+        let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
+        file.read_to_end(&mut data)?;
+        serde_json::from_slice(&data).map_err(From::from)
+    }
+
+    /// This function saves a [DiagnosticsProfile] from memory to a `.json` file with the provided path.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent_folder) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(serde_json::to_string_pretty(&self)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// This function returns a new [Diagnostics] configured with this profile, without results.
+    pub fn to_diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            folders_ignored: self.folders_ignored.to_vec(),
+            files_ignored: self.files_ignored.to_vec(),
+            fields_ignored: self.fields_ignored.to_vec(),
+            diagnostics_ignored: self.diagnostics_ignored.to_vec(),
+            max_decode_bytes: self.max_decode_bytes,
+            loc_key_patterns: self.loc_key_patterns.clone(),
+            referenced_path_rules: self.referenced_path_rules.to_vec(),
+            loc_formatting_rules: self.loc_formatting_rules.clone(),
+            source: self.source.clone(),
+            results: vec![],
+        }
+    }
+}
+
+/// This function performs a full diagnostics check over a Pack using the provided profile, and returns its results.
+///
+/// This runs the same checks the UI does with the same configuration, so it can be used from headless tools.
+pub fn run_diagnostics(pack: &mut Pack, dependencies: &mut Dependencies, schema: &Schema, game_info: &GameInfo, game_path: &Path, profile: &DiagnosticsProfile) -> Diagnostics {
+    let mut diagnostics = profile.to_diagnostics();
+    diagnostics.check_source(&profile.source, pack, dependencies, schema, game_info, game_path, profile.check_ak_only_refs);
+    diagnostics
+}
+
 impl DiagnosticType {
     pub fn path(&self) -> &str {
         match self {
@@ -133,16 +269,185 @@ impl DiagnosticType {
             Self::Loc(ref diag) => diag.path(),
             Self::Pack(_) => "",
             Self::PortraitSettings(diag) => diag.path(),
+            Self::Text(diag) => diag.path(),
             Self::Dependency(diag) => diag.path(),
             Self::Config(_) => "",
         }
     }
+
+    /// This function returns the amount of individual reports this diagnostic contains.
+    pub fn reports_count(&self) -> usize {
+        match self {
+            Self::AnimFragmentBattle(ref diag) => diag.results().len(),
+            Self::DB(ref diag) |
+            Self::Loc(ref diag) => diag.results().len(),
+            Self::Pack(ref diag) => diag.results().len(),
+            Self::PortraitSettings(ref diag) => diag.results().len(),
+            Self::Text(ref diag) => diag.results().len(),
+            Self::Dependency(ref diag) => diag.results().len(),
+            Self::Config(ref diag) => diag.results().len(),
+        }
+    }
+
+    /// This function returns a copy of this diagnostic containing only the report at the provided position, if it exists.
+    pub fn single_report(&self, index: usize) -> Option<Self> {
+        let mut diagnostic = self.clone();
+        match diagnostic {
+            Self::AnimFragmentBattle(ref mut diag) => *diag.results_mut() = vec![diag.results().get(index)?.clone()],
+            Self::DB(ref mut diag) |
+            Self::Loc(ref mut diag) => *diag.results_mut() = vec![diag.results().get(index)?.clone()],
+            Self::Pack(ref mut diag) => *diag.results_mut() = vec![diag.results().get(index)?.clone()],
+            Self::PortraitSettings(ref mut diag) => *diag.results_mut() = vec![diag.results().get(index)?.clone()],
+            Self::Text(ref mut diag) => *diag.results_mut() = vec![diag.results().get(index)?.clone()],
+            Self::Dependency(ref mut diag) => *diag.results_mut() = vec![diag.results().get(index)?.clone()],
+            Self::Config(ref mut diag) => *diag.results_mut() = vec![diag.results().get(index)?.clone()],
+        }
+
+        Some(diagnostic)
+    }
+
+    /// This function removes the reports for which `keep` returns false. It receives the level and the report type of each report.
+    pub fn retain_reports(&mut self, keep: &dyn Fn(&DiagnosticLevel, &str) -> bool) {
+        match self {
+            Self::AnimFragmentBattle(ref mut diag) => diag.results_mut().retain(|result| keep(&result.level(), &result.report_type().to_string())),
+            Self::DB(ref mut diag) |
+            Self::Loc(ref mut diag) => diag.results_mut().retain(|result| keep(&result.level(), &result.report_type().to_string())),
+            Self::Pack(ref mut diag) => diag.results_mut().retain(|result| keep(&result.level(), &result.report_type().to_string())),
+            Self::PortraitSettings(ref mut diag) => diag.results_mut().retain(|result| keep(&result.level(), &result.report_type().to_string())),
+            Self::Text(ref mut diag) => diag.results_mut().retain(|result| keep(&result.level(), &result.report_type().to_string())),
+            Self::Dependency(ref mut diag) => diag.results_mut().retain(|result| keep(&result.level(), &result.report_type().to_string())),
+            Self::Config(ref mut diag) => diag.results_mut().retain(|result| keep(&result.level(), &result.report_type().to_string())),
+        }
+    }
+
+    /// This function returns the level, report type and message of the report at the provided position, if it exists.
+    fn report_summary(&self, index: usize) -> Option<(DiagnosticLevel, String, String)> {
+        match self {
+            Self::AnimFragmentBattle(ref diag) => diag.results().get(index).map(|result| (result.level(), result.report_type().to_string(), result.message())),
+            Self::DB(ref diag) |
+            Self::Loc(ref diag) => diag.results().get(index).map(|result| (result.level(), result.report_type().to_string(), result.message())),
+            Self::Pack(ref diag) => diag.results().get(index).map(|result| (result.level(), result.report_type().to_string(), result.message())),
+            Self::PortraitSettings(ref diag) => diag.results().get(index).map(|result| (result.level(), result.report_type().to_string(), result.message())),
+            Self::Text(ref diag) => diag.results().get(index).map(|result| (result.level(), result.report_type().to_string(), result.message())),
+            Self::Dependency(ref diag) => diag.results().get(index).map(|result| (result.level(), result.report_type().to_string(), result.message())),
+            Self::Config(ref diag) => diag.results().get(index).map(|result| (result.level(), result.report_type().to_string(), result.message())),
+        }
+    }
+
+    /// This function returns the report at the provided position as a TSV line, with its level, diagnostic type, report type, path, message and affected data.
+    ///
+    /// The affected data is written in a readable form, depending on the diagnostic type:
+    /// - Tables and dependencies: space-separated `row:column` pairs, with `*` meaning the whole row or column.
+    /// - Anim fragments: `locomotion_graph` or `entry:row`, plus `/anim_ref:subrow:field` if it's about an anim ref.
+    /// - Portrait settings: the art set id, plus `|variant_filename` if it's about a variant.
+    /// - Texts: `line:number`.
+    /// - Packs and config: nothing.
+    pub fn report_to_tsv(&self, index: usize) -> Option<String> {
+        let cells_to_string = |cells: &[(i32, i32)]| cells.iter()
+            .map(|(row, column)| {
+                let row = if *row == -1 { "*".to_owned() } else { row.to_string() };
+                let column = if *column == -1 { "*".to_owned() } else { column.to_string() };
+                format!("{row}:{column}")
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let (level, report_type, message, data_affected) = match self {
+            Self::AnimFragmentBattle(ref diag) => {
+                let result = diag.results().get(index)?;
+                let data_affected = if *result.locomotion_graph() {
+                    "locomotion_graph".to_owned()
+                } else {
+                    match result.entry() {
+                        Some((row, Some((subrow, file_path, meta_file_path, _)))) => {
+                            let field = if *file_path { "file_path" } else if *meta_file_path { "meta_file_path" } else { "snd_file_path" };
+                            format!("entry:{row}/anim_ref:{subrow}:{field}")
+                        }
+                        Some((row, None)) => format!("entry:{row}"),
+                        None => String::new(),
+                    }
+                };
+
+                (result.level(), result.report_type().to_string(), result.message(), data_affected)
+            }
+            Self::DB(ref diag) |
+            Self::Loc(ref diag) => {
+                let result = diag.results().get(index)?;
+                (result.level(), result.report_type().to_string(), result.message(), cells_to_string(result.cells_affected()))
+            }
+            Self::Pack(ref diag) => {
+                let result = diag.results().get(index)?;
+                (result.level(), result.report_type().to_string(), result.message(), String::new())
+            }
+            Self::PortraitSettings(ref diag) => {
+                let result = diag.results().get(index)?;
+                let data_affected = match result.report_type() {
+                    PortraitSettingsDiagnosticReportType::DatacoredPortraitSettings => String::new(),
+                    PortraitSettingsDiagnosticReportType::InvalidArtSetId(art_set_id) => art_set_id.to_owned(),
+                    PortraitSettingsDiagnosticReportType::InvalidVariantFilename(art_set_id, variant_filename) |
+                    PortraitSettingsDiagnosticReportType::FileDiffuseNotFoundForVariant(art_set_id, variant_filename, _) |
+                    PortraitSettingsDiagnosticReportType::FileMask1NotFoundForVariant(art_set_id, variant_filename, _) |
+                    PortraitSettingsDiagnosticReportType::FileMask2NotFoundForVariant(art_set_id, variant_filename, _) |
+                    PortraitSettingsDiagnosticReportType::FileMask3NotFoundForVariant(art_set_id, variant_filename, _) => art_set_id.to_owned() + "|" + variant_filename,
+                };
+
+                (result.level(), result.report_type().to_string(), result.message(), data_affected)
+            }
+            Self::Text(ref diag) => {
+                let result = diag.results().get(index)?;
+                (result.level(), result.report_type().to_string(), result.message(), format!("line:{}", result.line()))
+            }
+            Self::Dependency(ref diag) => {
+                let result = diag.results().get(index)?;
+                (result.level(), result.report_type().to_string(), result.message(), cells_to_string(result.cells_affected()))
+            }
+            Self::Config(ref diag) => {
+                let result = diag.results().get(index)?;
+                (result.level(), result.report_type().to_string(), result.message(), String::new())
+            }
+        };
+
+        // Tabs and line jumps would break the TSV, so we escape them.
+        let fields = [format!("{level:?}"), self.to_string(), report_type, self.path().to_owned(), message, data_affected];
+        Some(fields.iter()
+            .map(|field| field.replace('\t', "\\t").replace('\n', "\\n"))
+            .collect::<Vec<_>>()
+            .join("\t"))
+    }
 }
 
 impl Diagnostics {
 
     /// This function performs a search over the parts of a `PackFile` you specify it, storing his results.
     pub fn check(&mut self, pack: &mut Pack, dependencies: &mut Dependencies, schema: &Schema, game_info: &GameInfo, game_path: &Path, paths_to_check: &[ContainerPath], check_ak_only_refs: bool) {
+        self.source = SearchSource::Pack;
+        self.check_files(pack, dependencies, schema, game_info, game_path, paths_to_check, check_ak_only_refs);
+    }
+
+    /// This function performs a full diagnostics check over the files of the provided source, instead of only over the Pack.
+    ///
+    /// Parent and game files are checked where they are, with the rest of the dependencies loaded as usual, so checks needing
+    /// the full load order work the same way. The open Pack is not visible to them, and checks only relevant for the Pack itself are skipped.
+    /// Assembly Kit files cannot be checked, so they return no results.
+    pub fn check_source(&mut self, source: &SearchSource, pack: &mut Pack, dependencies: &mut Dependencies, schema: &Schema, game_info: &GameInfo, game_path: &Path, check_ak_only_refs: bool) {
+        match source {
+            SearchSource::Pack => self.check(pack, dependencies, schema, game_info, game_path, &[], check_ak_only_refs),
+            SearchSource::ParentFiles |
+            SearchSource::GameFiles => {
+                self.source = source.clone();
+                self.check_files(&mut Pack::default(), dependencies, schema, game_info, game_path, &[], check_ak_only_refs);
+            }
+            SearchSource::AssKitFiles => {
+                self.source = source.clone();
+                self.results.clear();
+            }
+        }
+    }
+
+    /// This function performs the diagnostics checks over the files of the current source, storing their results.
+    ///
+    /// For the Pack source, the files checked are the ones of the provided Pack. For the rest, they're the ones of the dependencies.
+    fn check_files(&mut self, pack: &mut Pack, dependencies: &mut Dependencies, schema: &Schema, game_info: &GameInfo, game_path: &Path, paths_to_check: &[ContainerPath], check_ak_only_refs: bool) {
 
         // Clear the diagnostics first if we're doing a full check, or only the config ones and the ones for the path to update if we're doing a partial check.
         if paths_to_check.is_empty() {
@@ -156,7 +461,10 @@ impl Diagnostics {
                             ConfigDiagnosticReportType::DependenciesCacheNotGenerated |
                             ConfigDiagnosticReportType::DependenciesCacheOutdated |
                             ConfigDiagnosticReportType::DependenciesCacheCouldNotBeLoaded(_) |
-                            ConfigDiagnosticReportType::IncorrectGamePath => false,
+                            ConfigDiagnosticReportType::IncorrectGamePath(_) |
+                            ConfigDiagnosticReportType::SchemaReferenceCycle(_) => false,
+                            ConfigDiagnosticReportType::DuplicateFieldName(_, _) => false,
+                            ConfigDiagnosticReportType::InvalidSchemaReference(_, _) => false,
                         }
                     );
                 }
@@ -164,10 +472,10 @@ impl Diagnostics {
         }
 
         // First, check for config issues, as some of them may stop the checking prematurely.
-        if let Some(diagnostics) = ConfigDiagnostic::check(dependencies, game_info, game_path) {
+        if let Some(diagnostics) = ConfigDiagnostic::check(dependencies, schema, game_info, game_path) {
             let is_diagnostic_blocking = if let DiagnosticType::Config(ref diagnostic) = diagnostics {
                 diagnostic.results().iter().any(|diagnostic| matches!(diagnostic.report_type(),
-                    ConfigDiagnosticReportType::IncorrectGamePath |
+                    ConfigDiagnosticReportType::IncorrectGamePath(_) |
                     ConfigDiagnosticReportType::DependenciesCacheNotGenerated |
                     ConfigDiagnosticReportType::DependenciesCacheOutdated |
                     ConfigDiagnosticReportType::DependenciesCacheCouldNotBeLoaded(_)))
@@ -180,24 +488,139 @@ impl Diagnostics {
             }
         }
 
+        self.check_files_of_source(pack, dependencies, schema, game_info, paths_to_check, check_ak_only_refs);
+    }
+
+    /// This function performs the diagnostics checks over the files of the current source, without checking the config first.
+    fn check_files_of_source(&mut self, pack: &mut Pack, dependencies: &mut Dependencies, schema: &Schema, game_info: &GameInfo, paths_to_check: &[ContainerPath], check_ak_only_refs: bool) {
+        let include_vanilla = self.source == SearchSource::GameFiles;
+        let include_parent = self.source == SearchSource::ParentFiles;
         let files_to_ignore = pack.settings().diagnostics_files_to_ignore();
+        let timings = Timings::new("diagnostics");
 
         // To make sure we can read any non-db and non-loc file, we need to pre-decode them here.
-        {
-            // Extra data to decode animfragmentbattle files.
-            let mut extra_data = DecodeableExtraData::default();
-            extra_data.set_game_key(Some(game_info.key()));
-            let extra_data = Some(extra_data);
+        // Files over the decoding size limit are not decoded, so they get skipped by the checks below.
+        let loc_key_regex = self.loc_key_regex(game_info);
+        let loc_formatting_rules = self.loc_formatting_rules(game_info);
+        let referenced_path_regexes = TextDiagnostic::path_regexes(&self.referenced_path_rules);
+        let max_decode_bytes = self.max_decode_bytes;
+        let is_too_large = |file: &RFile| max_decode_bytes > 0 && file.undecoded_size().map(|size| size > max_decode_bytes).unwrap_or(false);
+
+        // Text files are only decoded if we have rules to check them.
+        let needs_decoding = |file: &RFile| file.decoded().is_err() && (file.file_type() != FileType::Text || TextDiagnostic::path_regexes_for_file(file, &referenced_path_regexes).is_some());
+
+        // Extra data to decode animfragmentbattle files.
+        let mut extra_data = DecodeableExtraData::default();
+        extra_data.set_game_key(Some(game_info.key()));
+        let extra_data = Some(extra_data);
+
+        let mut extra_data_db = DecodeableExtraData::default();
+        extra_data_db.set_schema(Some(schema));
+        let extra_data_db = Some(extra_data_db);
+
+        let decode = |file: &mut RFile| {
+            let _timer = timings.start("decode", format_args!("{:?}", file.file_type()));
+            if file.file_type() == FileType::DB {
+
+                // Tables that failed to decode are loaded to memory, so we can find out if they're in the wrong folder.
+                if file.decode(&extra_data_db, true, false).is_err() {
+                    let _ = file.load();
+                }
+            } else {
+                let _ = file.decode(&extra_data, true, false);
+            }
+        };
+
+        // Files of the dependencies are decoded into temporary copies, so the ones in the dependencies are left untouched.
+        let file_types_to_decode = [FileType::AnimFragmentBattle, FileType::DB, FileType::PortraitSettings, FileType::Text];
+        let (files_too_large, decoded_copies) = match self.source {
+            SearchSource::Pack => {
+                let mut files = pack.files_by_type_mut(&file_types_to_decode);
+                files.retain(|file| needs_decoding(file));
+
+                let files_too_large = files.iter()
+                    .filter(|file| is_too_large(file))
+                    .map(|file| file.path_in_container_raw().to_owned())
+                    .collect::<Vec<_>>();
 
-            pack.files_by_type_mut(&[FileType::AnimFragmentBattle, FileType::PortraitSettings]).par_iter_mut().for_each(|file| { let _ = file.decode(&extra_data, true, false); });
+                files.par_iter_mut()
+                    .filter(|file| !is_too_large(file))
+                    .for_each(|file| decode(file));
+
+                (files_too_large, vec![])
+            }
+            _ => {
+                let mut files = dependencies.files_by_types(&file_types_to_decode, include_vanilla, include_parent).into_values().collect::<Vec<_>>();
+                files.retain(|file| needs_decoding(file));
+
+                let files_too_large = files.iter()
+                    .filter(|file| is_too_large(file))
+                    .map(|file| file.path_in_container_raw().to_owned())
+                    .collect::<Vec<_>>();
+
+                let decoded_copies = files.into_par_iter()
+                    .filter(|file| !is_too_large(file))
+                    .map(|file| {
+                        let mut file = file.clone();
+                        decode(&mut file);
+                        file
+                    })
+                    .collect::<Vec<_>>();
+
+                (files_too_large, decoded_copies)
+            }
+        };
+
+        // Generate the reference data of the tables to check before getting the files to check, as it needs to update the dependencies.
+        // Tables of the dependencies get their reference data generated when checking them instead, as they're not in the Pack.
+        let file_types = [FileType::AnimFragmentBattle, FileType::DB, FileType::Loc, FileType::PortraitSettings, FileType::Text];
+        if self.source == SearchSource::Pack {
+            let db_files = if paths_to_check.is_empty() {
+                pack.files_by_type(&[FileType::DB])
+            } else {
+                pack.files_by_type_and_paths(&[FileType::DB], paths_to_check, false)
+            };
+
+            // That way we can get it fast on the first try, and skip.
+            let table_names = db_files.iter()
+                .filter_map(|file| {
+                    let path_split = file.path_in_container_split();
+                    if path_split.len() > 2 {
+                        Some(path_split[1].to_owned())
+                    } else {
+                        None
+                    }
+                })
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+
+            // If table names is empty this triggers a full regeneration, which is slow as fuck. So make sure to avoid that if we're only doing a partial check.
+            if !table_names.is_empty() || (table_names.is_empty() && paths_to_check.is_empty()) {
+                let _timer = timings.start("references", format_args!("{:?}", FileType::DB));
+                dependencies.generate_local_db_references(schema, pack, &table_names);
+            }
         }
 
         // Logic here: we want to process the tables on batches containing all the tables of the same type, so we can check duplicates in different tables.
         // To do that, we have to sort/split the file list, the process that.
-        let files = if paths_to_check.is_empty() {
-            pack.files_by_type(&[FileType::AnimFragmentBattle, FileType::DB, FileType::Loc, FileType::PortraitSettings])
-        } else {
-            pack.files_by_type_and_paths(&[FileType::AnimFragmentBattle, FileType::DB, FileType::Loc, FileType::PortraitSettings], paths_to_check, false)
+        let dependencies: &Dependencies = dependencies;
+        let files = match self.source {
+            SearchSource::Pack => if paths_to_check.is_empty() {
+                pack.files_by_type(&file_types)
+            } else {
+                pack.files_by_type_and_paths(&file_types, paths_to_check, false)
+            },
+            _ => {
+                let decoded_paths = decoded_copies.iter().map(|file| file.path_in_container_raw()).collect::<HashSet<_>>();
+                let mut files = dependencies.files_by_types(&file_types, include_vanilla, include_parent)
+                    .into_values()
+                    .filter(|file| !decoded_paths.contains(file.path_in_container_raw()))
+                    .chain(decoded_copies.iter())
+                    .collect::<Vec<_>>();
+                files.sort_by(|a, b| a.path_in_container_raw().cmp(b.path_in_container_raw()));
+                files
+            }
         };
 
         let mut files_split: HashMap<&str, Vec<&RFile>> = HashMap::new();
@@ -237,6 +660,15 @@ impl Diagnostics {
                         files_split.insert("portrait_settings", vec![file]);
                     }
                 },
+                FileType::Text => {
+                    if TextDiagnostic::path_regexes_for_file(file, &referenced_path_regexes).is_some() {
+                        if let Some(table_set) = files_split.get_mut("text") {
+                            table_set.push(file);
+                        } else {
+                            files_split.insert("text", vec![file]);
+                        }
+                    }
+                },
                 _ => {},
             }
         }
@@ -244,7 +676,10 @@ impl Diagnostics {
         // Getting this here speeds up a lot path-checking later.
         let local_file_path_list = pack.paths_cache();
 
-        let loc_files = pack.files_by_type(&[FileType::Loc]);
+        let loc_files = match self.source {
+            SearchSource::Pack => pack.files_by_type(&[FileType::Loc]),
+            _ => files.iter().filter(|file| file.file_type() == FileType::Loc).copied().collect(),
+        };
         let loc_decoded = loc_files.iter()
             .filter_map(|file| if let Ok(RFileDecoded::Loc(loc)) = file.decoded() { Some(loc) } else { None })
             .map(|file| file.data())
@@ -261,13 +696,8 @@ impl Diagnostics {
             None
         };
 
-        // That way we can get it fast on the first try, and skip.
-        let table_names = files_split.iter().filter(|(key, _)| **key != "anim_fragment_battle" && **key != "locs" && **key != "portrait_settings").map(|(key, _)| key.to_string()).collect::<Vec<_>>();
-
-        // If table names is empty this triggers a full regeneration, which is slow as fuck. So make sure to avoid that if we're only doing a partial check.
-        if !table_names.is_empty() || (table_names.is_empty() && paths_to_check.is_empty()) {
-            dependencies.generate_local_db_references(&schema, pack, &table_names);
-        }
+        // Validation patterns are shared between tables, so compile them once for all the tables we're going to check.
+        let validation_regexes = TableDiagnostic::validation_regexes(&files);
 
         // Caches for Portrait Settings diagnostics.
         let art_set_ids = dependencies.db_values_from_table_name_and_column_name(Some(pack), "campaign_character_arts_tables", "art_set_id", true, true);
@@ -280,6 +710,7 @@ impl Diagnostics {
             let mut table_references = HashMap::new();
 
             for file in files {
+                let _timer = timings.start("check", format_args!("{:?}", file.file_type()));
                 let (ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) = Self::ignore_data_for_file(file, &files_to_ignore)?;
 
                 let diagnostic = match file.file_type() {
@@ -294,18 +725,33 @@ impl Diagnostics {
                     ),
                     FileType::DB => {
 
+                        // Tables that couldn't be decoded can only be checked for being in the wrong folder.
+                        let file_decoded = match file.decoded() {
+                            Ok(file_decoded) => file_decoded,
+                            Err(_) => {
+                                if let Some(diagnostic) = TableDiagnostic::check_db_folder(file, schema, &self.diagnostics_ignored, &ignored_fields, &ignored_diagnostics, &ignored_diagnostics_for_fields) {
+                                    diagnostics.push(diagnostic);
+                                }
+                                continue;
+                            }
+                        };
+
                         // Get the dependency data for tables once per batch.
                         // That way we can speed up this a lot.
-                        let file_decoded = file.decoded().ok()?;
                         if table_references.is_empty() {
                             if let RFileDecoded::DB(table) = file_decoded {
-                                table_references = dependencies.db_reference_data(&schema, pack, table.table_name(), table.definition(), &loc_data);
+                                let _timer = timings.start("references", format_args!("{:?}", FileType::DB));
+                                table_references = match self.source {
+                                    SearchSource::Pack => dependencies.db_reference_data(&schema, pack, table.table_name(), table.definition(), &loc_data),
+                                    _ => dependencies.db_reference_data_uncached(&schema, pack, table.table_name(), table.definition(), &loc_data),
+                                };
                             }
                         }
 
                         TableDiagnostic::check_db(
                             file,
                             dependencies,
+                            schema,
                             &self.diagnostics_ignored,
                             &ignored_fields,
                             &ignored_diagnostics,
@@ -313,15 +759,19 @@ impl Diagnostics {
                             game_info,
                             local_file_path_list,
                             &table_references,
+                            &loc_data,
+                            &validation_regexes,
                             check_ak_only_refs,
                         )
                     },
-                    FileType::Loc => TableDiagnostic::check_loc(file, &self.diagnostics_ignored, &ignored_fields, &ignored_diagnostics, &ignored_diagnostics_for_fields),
+                    FileType::Loc => TableDiagnostic::check_loc(file, &self.diagnostics_ignored, &ignored_fields, &ignored_diagnostics, &ignored_diagnostics_for_fields, loc_key_regex.as_ref(), &loc_formatting_rules),
                     FileType::PortraitSettings => PortraitSettingsDiagnostic::check(file, &art_set_ids, &variant_filenames, dependencies, &self.diagnostics_ignored, &ignored_fields, &ignored_diagnostics, &ignored_diagnostics_for_fields, &local_file_path_list),
+                    FileType::Text => TextDiagnostic::check(file, &referenced_path_regexes, dependencies, &self.diagnostics_ignored, &ignored_fields, &ignored_diagnostics, &ignored_diagnostics_for_fields, &local_file_path_list),
                     _ => None,
                 };
 
                 if let Some(diagnostic) = diagnostic {
+                    timings.record("files_with_reports", format_args!("{:?}", file.file_type()), Duration::ZERO, 1);
                     diagnostics.push(diagnostic);
                 }
             }
@@ -329,11 +779,19 @@ impl Diagnostics {
             Some(diagnostics)
         }).flatten().collect());
 
-        if let Some(diagnostics) = DependencyDiagnostic::check(pack) {
-            self.results_mut().push(diagnostics);
+        // These only make sense for real Packs.
+        if self.source == SearchSource::Pack {
+            if let Some(diagnostics) = DependencyDiagnostic::check(pack) {
+                self.results_mut().push(diagnostics);
+            }
+
+            if let Some(diagnostics) = PackDiagnostic::check(pack, game_info) {
+                self.results_mut().push(diagnostics);
+            }
         }
 
-        if let Some(diagnostics) = PackDiagnostic::check(pack) {
+        // Skipped files are reported for all sources, so they're not mistaken for files without issues.
+        if let Some(diagnostics) = PackDiagnostic::check_files_too_large(&files_too_large) {
             self.results_mut().push(diagnostics);
         }
 
@@ -348,6 +806,30 @@ impl Diagnostics {
                 Ordering::Equal
             }
         });
+
+        timings.log();
+    }
+
+    /// This function returns the regex loc keys must match to be valid for the provided game, if it has a custom pattern.
+    ///
+    /// Invalid patterns are ignored with a warning, so the built-in rule is used instead.
+    fn loc_key_regex(&self, game_info: &GameInfo) -> Option<Regex> {
+        let pattern = self.loc_key_patterns.get(game_info.key()).filter(|pattern| !pattern.is_empty())?;
+        match Regex::new(&format!("^(?:{pattern})$")) {
+            Ok(regex) => Some(regex),
+            Err(error) => {
+                warn!("Invalid loc key pattern \"{}\" for game {}, using the default rule instead: {}", pattern, game_info.key(), error);
+                None
+            }
+        }
+    }
+
+    /// This function returns the rules used to check the formatting tokens of loc texts for the provided game.
+    fn loc_formatting_rules(&self, game_info: &GameInfo) -> LocFormattingRules {
+        match self.loc_formatting_rules.get(game_info.key()).filter(|rules| !rules.trim().is_empty()) {
+            Some(rules) => LocFormattingRules::parse(rules),
+            None => LocFormattingRules::default(),
+        }
     }
 
     /// Function to know if an specific field/diagnostic must be ignored.
@@ -422,10 +904,186 @@ impl Diagnostics {
         Some((ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields))
     }
 
+    /// This function returns an iterator over every individual report in the results, as (diagnostic, report index) pairs.
+    ///
+    /// Use it with `skip`/`take` to process the results in chunks instead of all at once.
+    pub fn iter_results(&self) -> impl Iterator<Item = (&DiagnosticType, usize)> {
+        self.results.iter().flat_map(|diagnostic| (0..diagnostic.reports_count()).map(move |index| (diagnostic, index)))
+    }
+
+    /// This function returns the total amount of individual reports in the results.
+    pub fn reports_count(&self) -> usize {
+        self.results.iter().map(|diagnostic| diagnostic.reports_count()).sum()
+    }
+
+    /// This function packages the context of a single diagnostic report into a small Pack, so it can be attached to a bug report.
+    ///
+    /// The Pack contains the file the report is about, if it's in the provided Pack, and the following files under the `rpfm_reproducer` folder:
+    /// - `diagnostic.json`: the diagnostic, with only the report at `report_index`.
+    /// - `definition.json`: the definition used to decode the file, if it's a DB table.
+    ///
+    /// Returns the encoded Pack.
+    pub fn reproducer_bundle(diagnostic: &DiagnosticType, report_index: usize, pack: &Pack, schema: &Schema) -> Result<Vec<u8>> {
+        let diagnostic = diagnostic.single_report(report_index).ok_or(RLibError::DiagnosticReportNotFound(report_index))?;
+        let mut bundle = Pack::new_with_name_and_version(REPRODUCER_PACK_NAME, pack.pfh_version());
+
+        if let Some(file) = pack.file(diagnostic.path(), false) {
+            let mut file = file.clone();
+
+            if let DiagnosticType::DB(_) = diagnostic {
+                let mut extra_data = DecodeableExtraData::default();
+                extra_data.set_schema(Some(schema));
+
+                if let Ok(Some(RFileDecoded::DB(table))) = file.decode(&Some(extra_data), false, true) {
+                    let definition = serde_json::to_string_pretty(table.definition())?;
+                    bundle.insert(RFile::new_from_vec(definition.as_bytes(), FileType::Text, 0, &format!("{REPRODUCER_FOLDER}/definition.json")))?;
+                }
+            }
+
+            bundle.insert(file)?;
+        }
+
+        let diagnostic = serde_json::to_string_pretty(&diagnostic)?;
+        bundle.insert(RFile::new_from_vec(diagnostic.as_bytes(), FileType::Text, 0, &format!("{REPRODUCER_FOLDER}/diagnostic.json")))?;
+
+        let mut data = vec![];
+        bundle.encode(&mut data, &None)?;
+        Ok(data)
+    }
+
     /// This function converts an entire diagnostics struct into a JSon string.
     pub fn json(&self) -> Result<String> {
         serde_json::to_string_pretty(self).map_err(From::from)
     }
+
+    /// This function removes the reports for which `keep` returns false, and any diagnostic left without reports.
+    ///
+    /// It receives the level and the report type of each report.
+    pub fn retain_reports(&mut self, keep: impl Fn(&DiagnosticLevel, &str) -> bool) {
+        for diagnostic in &mut self.results {
+            diagnostic.retain_reports(&keep);
+        }
+
+        self.results.retain(|diagnostic| diagnostic.reports_count() > 0);
+    }
+
+    /// This function converts the results of the diagnostics into a CSV string, one report per line.
+    ///
+    /// The columns are `level,diag_type,path,message,report_type`. Fields with commas, quotes or line jumps are quoted.
+    pub fn to_csv(&self) -> String {
+        let escape = |field: &str| if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_owned()
+        };
+
+        let mut csv = String::from("level,diag_type,path,message,report_type\n");
+        for (diagnostic, index) in self.iter_results() {
+            if let Some((level, report_type, message)) = diagnostic.report_summary(index) {
+                let fields = [format!("{level:?}"), diagnostic.to_string(), diagnostic.path().to_owned(), message, report_type];
+                csv.push_str(&fields.iter().map(|field| escape(field)).collect::<Vec<_>>().join(","));
+                csv.push('\n');
+            }
+        }
+
+        csv
+    }
+
+    /// This function converts the results of the diagnostics into a SARIF 2.1.0 document.
+    ///
+    /// Each report is mapped to a SARIF result, using its report type as rule id. For table reports,
+    /// the affected cells are mapped to regions, with rows as lines and columns as columns, both 1-based.
+    pub fn to_sarif(&self) -> Value {
+        let mut rules = BTreeSet::new();
+        let mut results = vec![];
+
+        for diagnostic in &self.results {
+            let path = diagnostic.path();
+            match diagnostic {
+                DiagnosticType::AnimFragmentBattle(diag) => for report in diag.results() {
+                    results.push(Self::sarif_result(&mut rules, path, &report.report_type().to_string(), report, &[]));
+                },
+                DiagnosticType::Config(diag) => for report in diag.results() {
+                    results.push(Self::sarif_result(&mut rules, path, &report.report_type().to_string(), report, &[]));
+                },
+                DiagnosticType::Dependency(diag) => for report in diag.results() {
+                    results.push(Self::sarif_result(&mut rules, path, &report.report_type().to_string(), report, report.cells_affected()));
+                },
+                DiagnosticType::DB(diag) |
+                DiagnosticType::Loc(diag) => for report in diag.results() {
+                    results.push(Self::sarif_result(&mut rules, path, &report.report_type().to_string(), report, report.cells_affected()));
+                },
+                DiagnosticType::Pack(diag) => for report in diag.results() {
+                    results.push(Self::sarif_result(&mut rules, path, &report.report_type().to_string(), report, &[]));
+                },
+                DiagnosticType::PortraitSettings(diag) => for report in diag.results() {
+                    results.push(Self::sarif_result(&mut rules, path, &report.report_type().to_string(), report, &[]));
+                },
+                DiagnosticType::Text(diag) => for report in diag.results() {
+                    results.push(Self::sarif_result(&mut rules, path, &report.report_type().to_string(), report, &[(*report.line() as i32 - 1, -1)]));
+                },
+            }
+        }
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "RPFM",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://github.com/Frodo45127/rpfm",
+                        "rules": rules.iter().map(|rule| json!({ "id": rule })).collect::<Vec<_>>(),
+                    }
+                },
+                "results": results,
+            }]
+        })
+    }
+
+    /// This function builds a single SARIF result from a report, registering its rule id in the process.
+    fn sarif_result(rules: &mut BTreeSet<String>, path: &str, rule_id: &str, report: &dyn DiagnosticReport, cells_affected: &[(i32, i32)]) -> Value {
+        rules.insert(rule_id.to_owned());
+
+        let level = match report.level() {
+            DiagnosticLevel::Info => "note",
+            DiagnosticLevel::Warning => "warning",
+            DiagnosticLevel::Error => "error",
+        };
+
+        let mut result = json!({
+            "ruleId": rule_id,
+            "level": level,
+            "message": {
+                "text": report.message(),
+            },
+        });
+
+        // Reports without a path (config and pack ones) have no location.
+        if !path.is_empty() {
+            let regions = cells_affected.iter()
+                .filter(|(row, _)| *row >= 0)
+                .map(|(row, column)| if *column >= 0 {
+                    json!({ "startLine": row + 1, "startColumn": column + 1 })
+                } else {
+                    json!({ "startLine": row + 1 })
+                })
+                .collect::<Vec<_>>();
+
+            let locations = if regions.is_empty() {
+                vec![json!({ "physicalLocation": { "artifactLocation": { "uri": path } } })]
+            } else {
+                regions.into_iter()
+                    .map(|region| json!({ "physicalLocation": { "artifactLocation": { "uri": path }, "region": region } }))
+                    .collect()
+            };
+
+            result["locations"] = Value::from(locations);
+        }
+
+        result
+    }
 }
 
 impl Display for DiagnosticType {
@@ -437,6 +1095,7 @@ impl Display for DiagnosticType {
             Self::Loc(_) => "Loc",
             Self::Pack(_) => "Packfile",
             Self::PortraitSettings(_) => "PortraitSettings",
+            Self::Text(_) => "Text",
             Self::Dependency(_) => "DependencyManager",
         }, f)
     }
@@ -16,6 +16,7 @@ use serde_derive::{Serialize, Deserialize};
 use std::{fmt, fmt::Display};
 
 use rpfm_lib::files::pack::Pack;
+use rpfm_lib::games::GameInfo;
 
 use crate::diagnostics::*;
 
@@ -39,7 +40,8 @@ pub struct PackDiagnosticReport {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PackDiagnosticReportType {
-    InvalidPackName(String)
+    InvalidPackName(String, String),
+    FileTooLargeToCheck(String),
 }
 
 //-------------------------------------------------------------------------------//
@@ -57,13 +59,15 @@ impl PackDiagnosticReport {
 impl DiagnosticReport for PackDiagnosticReport {
     fn message(&self) -> String {
         match &self.report_type {
-            PackDiagnosticReportType::InvalidPackName(pack_name) => format!("Invalid Pack name: {pack_name}"),
+            PackDiagnosticReportType::InvalidPackName(pack_name, reason) => format!("Invalid Pack name ({reason}): {pack_name}"),
+            PackDiagnosticReportType::FileTooLargeToCheck(path) => format!("Skipped: too large: {path}"),
         }
     }
 
     fn level(&self) -> DiagnosticLevel {
         match self.report_type {
-            PackDiagnosticReportType::InvalidPackName(_) => DiagnosticLevel::Error,
+            PackDiagnosticReportType::InvalidPackName(_, _) => DiagnosticLevel::Error,
+            PackDiagnosticReportType::FileTooLargeToCheck(_) => DiagnosticLevel::Warning,
         }
     }
 }
@@ -71,7 +75,8 @@ impl DiagnosticReport for PackDiagnosticReport {
 impl Display for PackDiagnosticReportType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         Display::fmt(match self {
-            Self::InvalidPackName(_) => "InvalidPackFileName",
+            Self::InvalidPackName(_, _) => "InvalidPackFileName",
+            Self::FileTooLargeToCheck(_) => "FileTooLargeToCheck",
         }, f)
     }
 }
@@ -79,12 +84,31 @@ impl Display for PackDiagnosticReportType {
 impl PackDiagnostic {
 
     /// This function takes care of checking for PackFile-Related for errors.
-    pub fn check(pack: &Pack) -> Option<DiagnosticType> {
+    pub fn check(pack: &Pack, game_info: &GameInfo) -> Option<DiagnosticType> {
         let mut diagnostic = PackDiagnostic::default();
 
+        // Packs that have not yet been saved have no name to check.
         let name = pack.disk_file_name();
-        if name.contains(' ') {
-            let result = PackDiagnosticReport::new(PackDiagnosticReportType::InvalidPackName(name));
+        if !name.is_empty() {
+            if let Err(issue) = game_info.is_valid_pack_name(&name) {
+                let result = PackDiagnosticReport::new(PackDiagnosticReportType::InvalidPackName(name, issue.to_string()));
+                diagnostic.results_mut().push(result);
+            }
+        }
+
+        if !diagnostic.results().is_empty() {
+            Some(DiagnosticType::Pack(diagnostic))
+        } else { None }
+    }
+
+    /// This function reports the files that were skipped during a check due to their size.
+    ///
+    /// Unlike the rest of the Pack checks, this one applies to any source.
+    pub fn check_files_too_large(files_too_large: &[String]) -> Option<DiagnosticType> {
+        let mut diagnostic = PackDiagnostic::default();
+
+        for path in files_too_large {
+            let result = PackDiagnosticReport::new(PackDiagnosticReportType::FileTooLargeToCheck(path.to_owned()));
             diagnostic.results_mut().push(result);
         }
 
@@ -12,14 +12,19 @@
 
 use getset::{Getters, MutGetters};
 use itertools::Itertools;
+use regex::Regex;
 use serde_derive::{Serialize, Deserialize};
 
 use std::{fmt, fmt::Display};
 
-use rpfm_lib::schema::Field;
+use rpfm_lib::files::{db::DB, table::DecodedData};
+use rpfm_lib::schema::{Definition, DefinitionPatch, Field};
 
 use crate::diagnostics::*;
 
+/// Loc formatting rules used for games without custom ones.
+pub const DEFAULT_LOC_FORMATTING_RULES: &str = "pair:[[:]];pair:{{:}}";
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -55,6 +60,7 @@ pub enum TableDiagnosticReportType {
     EmptyKeyField(String),
     EmptyKeyFields,
     DuplicatedCombinedKeys(String),
+    DuplicatedCombinedKeysConflicting(String),
     NoReferenceTableFound(String),
     NoReferenceTableNorColumnFoundPak(String),
     NoReferenceTableNorColumnFoundNoPak(String),
@@ -63,10 +69,33 @@ pub enum TableDiagnosticReportType {
     InvalidLocKey,
     TableNameEndsInNumber,
     TableNameHasSpace,
+    TableNameFolderMismatch,
     TableIsDataCoring,
     FieldWithPathNotFound(Vec<String>),
     BannedTable,
     ValueCannotBeEmpty(String),
+    UnknownColumnInData(String),
+    FieldFailsValidationPattern(String),
+    InvalidValidationPattern(String),
+    EnumValueNotEncodable(String),
+    MalformedLocFormatting(String),
+    InconsistentEscaping(String),
+    EmptyColumn(String),
+    SelfReference(String),
+    MissingLocEntry(String),
+}
+
+/// Rules used to check the formatting tokens of loc texts.
+///
+/// They're parsed from a list of rules separated by `;`. Each rule is either `pair:OPEN:CLOSE`, for tokens that must be
+/// balanced (like `[[` and `]]`), or `pattern:REGEX`, for suspicious text that should not be in a loc text.
+///
+/// A `;` that's part of a rule must be escaped as `\;`. In pairs, `:` and `\` must also be escaped, as `\:` and `\\`.
+/// Patterns are passed as they are to the regex engine, so their own escapes keep working.
+#[derive(Debug, Clone)]
+pub struct LocFormattingRules {
+    pairs: Vec<(String, String)>,
+    patterns: Vec<Regex>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -106,6 +135,7 @@ impl DiagnosticReport for TableDiagnosticReport {
             TableDiagnosticReportType::EmptyKeyField(field_name) => format!("Empty key for column \"{field_name}\"."),
             TableDiagnosticReportType::EmptyKeyFields => "Empty key fields.".to_owned(),
             TableDiagnosticReportType::DuplicatedCombinedKeys(combined_keys) => format!("Duplicated combined keys: {}.", &combined_keys),
+            TableDiagnosticReportType::DuplicatedCombinedKeysConflicting(combined_keys) => format!("Duplicated combined keys with different data: {}.", &combined_keys),
             TableDiagnosticReportType::NoReferenceTableFound(field_name) => format!("No reference table found for column \"{field_name}\"."),
             TableDiagnosticReportType::NoReferenceTableNorColumnFoundPak(field_name) => format!("No reference column found in referenced table for column \"{field_name}\". Maybe a problem with the schema?"),
             TableDiagnosticReportType::NoReferenceTableNorColumnFoundNoPak(field_name) => format!("No reference column found in referenced table for column \"{field_name}\". Did you forget to generate the Dependencies Cache, or did you generate it before installing the Assembly kit?"),
@@ -114,10 +144,20 @@ impl DiagnosticReport for TableDiagnosticReport {
             TableDiagnosticReportType::InvalidLocKey => "Invalid localisation key.".to_owned(),
             TableDiagnosticReportType::TableNameEndsInNumber => "Table name ends in number.".to_owned(),
             TableDiagnosticReportType::TableNameHasSpace => "Table name contains spaces.".to_owned(),
+            TableDiagnosticReportType::TableNameFolderMismatch => "Table is not in the folder of its table type.".to_owned(),
             TableDiagnosticReportType::TableIsDataCoring => "Table is datacoring.".to_owned(),
             TableDiagnosticReportType::FieldWithPathNotFound(paths) => format!("Path not found: {}.", paths.iter().join(" || ")),
             TableDiagnosticReportType::BannedTable => "Banned table.".to_owned(),
             TableDiagnosticReportType::ValueCannotBeEmpty(field_name) => format!("Empty value for column \"{field_name}\"."),
+            TableDiagnosticReportType::UnknownColumnInData(field_name) => format!("Column \"{field_name}\" doesn't exist in the latest definition of the table. Its data will be lost when updating the table."),
+            TableDiagnosticReportType::FieldFailsValidationPattern(field_name) => format!("Value in column \"{field_name}\" doesn't match its validation pattern."),
+            TableDiagnosticReportType::InvalidValidationPattern(field_name) => format!("Invalid validation pattern in the schema for column \"{field_name}\"."),
+            TableDiagnosticReportType::EnumValueNotEncodable(value) => format!("Value \"{value}\" is not one of the values of its enum column. It'll be replaced with the default value when saving."),
+            TableDiagnosticReportType::MalformedLocFormatting(issue) => format!("Malformed formatting in loc text: {issue}."),
+            TableDiagnosticReportType::InconsistentEscaping(issue) => format!("Inconsistent escaping: {issue}."),
+            TableDiagnosticReportType::EmptyColumn(field_name) => format!("Column \"{field_name}\" is empty in all the rows of this table."),
+            TableDiagnosticReportType::SelfReference(field_name) => format!("Column \"{field_name}\" references the key of its own row."),
+            TableDiagnosticReportType::MissingLocEntry(loc_key) => format!("Missing loc entry \"{loc_key}\"."),
         }
     }
 
@@ -129,6 +169,7 @@ impl DiagnosticReport for TableDiagnosticReport {
             TableDiagnosticReportType::EmptyKeyField(_) => DiagnosticLevel::Warning,
             TableDiagnosticReportType::EmptyKeyFields => DiagnosticLevel::Warning,
             TableDiagnosticReportType::DuplicatedCombinedKeys(_) => DiagnosticLevel::Error,
+            TableDiagnosticReportType::DuplicatedCombinedKeysConflicting(_) => DiagnosticLevel::Error,
             TableDiagnosticReportType::NoReferenceTableFound(_) => DiagnosticLevel::Info,
             TableDiagnosticReportType::NoReferenceTableNorColumnFoundPak(_) => DiagnosticLevel::Info,
             TableDiagnosticReportType::NoReferenceTableNorColumnFoundNoPak(_) => DiagnosticLevel::Warning,
@@ -137,10 +178,20 @@ impl DiagnosticReport for TableDiagnosticReport {
             TableDiagnosticReportType::InvalidLocKey => DiagnosticLevel::Error,
             TableDiagnosticReportType::TableNameEndsInNumber => DiagnosticLevel::Error,
             TableDiagnosticReportType::TableNameHasSpace => DiagnosticLevel::Error,
+            TableDiagnosticReportType::TableNameFolderMismatch => DiagnosticLevel::Error,
             TableDiagnosticReportType::TableIsDataCoring => DiagnosticLevel::Warning,
             TableDiagnosticReportType::FieldWithPathNotFound(_) => DiagnosticLevel::Warning,
             TableDiagnosticReportType::BannedTable => DiagnosticLevel::Error,
             TableDiagnosticReportType::ValueCannotBeEmpty(_) => DiagnosticLevel::Error,
+            TableDiagnosticReportType::UnknownColumnInData(_) => DiagnosticLevel::Warning,
+            TableDiagnosticReportType::FieldFailsValidationPattern(_) => DiagnosticLevel::Warning,
+            TableDiagnosticReportType::InvalidValidationPattern(_) => DiagnosticLevel::Error,
+            TableDiagnosticReportType::EnumValueNotEncodable(_) => DiagnosticLevel::Error,
+            TableDiagnosticReportType::MalformedLocFormatting(_) => DiagnosticLevel::Warning,
+            TableDiagnosticReportType::InconsistentEscaping(_) => DiagnosticLevel::Info,
+            TableDiagnosticReportType::EmptyColumn(_) => DiagnosticLevel::Info,
+            TableDiagnosticReportType::SelfReference(_) => DiagnosticLevel::Warning,
+            TableDiagnosticReportType::MissingLocEntry(_) => DiagnosticLevel::Warning,
         }
     }
 }
@@ -154,6 +205,7 @@ impl Display for TableDiagnosticReportType {
             Self::EmptyKeyField(_) => "EmptyKeyField",
             Self::EmptyKeyFields => "EmptyKeyFields",
             Self::DuplicatedCombinedKeys(_) => "DuplicatedCombinedKeys",
+            Self::DuplicatedCombinedKeysConflicting(_) => "DuplicatedCombinedKeysConflicting",
             Self::NoReferenceTableFound(_) => "NoReferenceTableFound",
             Self::NoReferenceTableNorColumnFoundPak(_) => "NoReferenceTableNorColumnFoundPak",
             Self::NoReferenceTableNorColumnFoundNoPak(_) => "NoReferenceTableNorColumnFoundNoPak",
@@ -162,14 +214,93 @@ impl Display for TableDiagnosticReportType {
             Self::InvalidLocKey => "InvalidLocKey",
             Self::TableNameEndsInNumber => "TableNameEndsInNumber",
             Self::TableNameHasSpace => "TableNameHasSpace",
+            Self::TableNameFolderMismatch => "TableNameFolderMismatch",
             Self::TableIsDataCoring => "TableIsDataCoring",
             Self::FieldWithPathNotFound(_) => "FieldWithPathNotFound",
             Self::BannedTable => "BannedTable",
             Self::ValueCannotBeEmpty(_) => "ValueCannotBeEmpty",
+            Self::UnknownColumnInData(_) => "UnknownColumnInData",
+            Self::FieldFailsValidationPattern(_) => "FieldFailsValidationPattern",
+            Self::InvalidValidationPattern(_) => "InvalidValidationPattern",
+            Self::EnumValueNotEncodable(_) => "EnumValueNotEncodable",
+            Self::MalformedLocFormatting(_) => "MalformedLocFormatting",
+            Self::InconsistentEscaping(_) => "InconsistentEscaping",
+            Self::EmptyColumn(_) => "EmptyColumn",
+            Self::SelfReference(_) => "SelfReference",
+            Self::MissingLocEntry(_) => "MissingLocEntry",
         }, f)
     }
 }
 
+impl Default for LocFormattingRules {
+    fn default() -> Self {
+        Self::parse(DEFAULT_LOC_FORMATTING_RULES)
+    }
+}
+
+impl LocFormattingRules {
+
+    /// This function parses a list of loc formatting rules. Invalid rules are ignored.
+    pub fn parse(rules: &str) -> Self {
+        let mut pairs = vec![];
+        let mut patterns = vec![];
+
+        for rule in split_unescaped(rules, ';') {
+            match rule.trim().split_once(':') {
+                Some(("pair", tokens)) => if let [open, close] = split_unescaped(tokens, ':')[..] {
+                    let open = unescape(open);
+                    let close = unescape(close);
+                    if !open.is_empty() && !close.is_empty() {
+                        pairs.push((open, close));
+                    }
+                },
+                Some(("pattern", pattern)) => match Regex::new(&pattern.replace("\\;", ";")) {
+                    Ok(regex) => patterns.push(regex),
+                    Err(error) => warn!("Invalid loc formatting pattern \"{}\", ignoring it: {}", pattern, error),
+                },
+                _ => {},
+            }
+        }
+
+        Self {
+            pairs,
+            patterns,
+        }
+    }
+
+    /// This function checks the provided loc text against these rules, returning the first problem found, if any.
+    pub fn check(&self, text: &str) -> Option<String> {
+        for (open, close) in &self.pairs {
+            let mut depth = 0;
+            let mut rest = text;
+            while !rest.is_empty() {
+                if rest.starts_with(open.as_str()) {
+                    depth += 1;
+                    rest = &rest[open.len()..];
+                } else if rest.starts_with(close.as_str()) {
+                    if depth == 0 {
+                        return Some(format!("\"{close}\" without a matching \"{open}\""));
+                    }
+
+                    depth -= 1;
+                    rest = &rest[close.len()..];
+                } else {
+                    let next = rest.chars().next().map(|character| character.len_utf8()).unwrap_or(1);
+                    rest = &rest[next..];
+                }
+            }
+
+            if depth > 0 {
+                return Some(format!("\"{open}\" without a matching \"{close}\""));
+            }
+        }
+
+        self.patterns.iter()
+            .find_map(|pattern| pattern.find(text))
+            .map(|token| format!("suspicious token \"{}\"", token.as_str()))
+    }
+}
+
 
 impl TableDiagnostic {
     pub fn new(path: &str) -> Self {
@@ -180,29 +311,158 @@ impl TableDiagnostic {
     }
 
     /// This function is used to check if a table is outdated or not.
-    fn is_table_outdated(table_name: &str, table_version: i32, dependencies: &Dependencies) -> bool {
-        if let Ok(vanilla_dbs) = dependencies.db_data(table_name, true, false) {
-            if let Some(max_version) = vanilla_dbs.iter()
-                .filter_map(|x| {
-                    if let Ok(RFileDecoded::DB(table)) = x.decoded() {
-                        Some(table.definition().version())
-                    } else {
-                        None
+    fn is_table_outdated(table_name: &str, table_version: i32, dependencies: &Dependencies, schema: &Schema) -> bool {
+        match Self::latest_vanilla_definition(table_name, dependencies, schema) {
+            Some(definition) => *definition.version() != table_version,
+            None => false,
+        }
+    }
+
+    /// This function returns the definition of the newest version of a table in the vanilla files, which is the one outdated tables get updated to.
+    ///
+    /// Vanilla tables not decoded in the dependencies are taken from their cache of decoded tables.
+    fn latest_vanilla_definition(table_name: &str, dependencies: &Dependencies, schema: &Schema) -> Option<Definition> {
+        dependencies.decoded_tables(schema, &SearchSource::GameFiles, table_name)
+            .iter()
+            .map(|(_, table)| table.definition())
+            .max_by(|x, y| x.version().cmp(y.version()))
+            .cloned()
+    }
+
+    /// This function returns if the provided text contains line jumps or tabulations, as (escaped, raw).
+    ///
+    /// Double-escaped ones (like `\\n`) do not count as escaped.
+    fn escaping_styles(text: &str) -> (bool, bool) {
+        if Self::is_path_like(text) {
+            return (false, false);
+        }
+
+        let raw = text.contains(['\n', '\t']);
+        let unescaped_backslashes = text.replace("\\\\", "");
+        let escaped = unescaped_backslashes.contains("\\n") || unescaped_backslashes.contains("\\t");
+        (escaped, raw)
+    }
+
+    /// This function returns the escaping issue of the provided text, if any.
+    ///
+    /// Raw control characters (other than line jumps and tabulations) and double-escaped line jumps/tabulations are always reported.
+    /// Line jumps and tabulations are only reported if the text uses a different style (escaped or raw) than the rest of the table.
+    pub fn escaping_issue(text: &str, table_has_escaped: bool, table_has_raw: bool) -> Option<&'static str> {
+        if text.chars().any(|character| character.is_control() && character != '\n' && character != '\t') {
+            return Some("raw control character found");
+        }
+
+        // Backslashes in paths are folder separators, not escapes.
+        if Self::is_path_like(text) {
+            return None;
+        }
+
+        if text.contains("\\\\n") || text.contains("\\\\t") {
+            return Some("double-escaped line jump or tabulation found");
+        }
+
+        let (escaped, raw) = Self::escaping_styles(text);
+        if raw && table_has_escaped {
+            Some("raw line jump or tabulation in a table that also uses escaped ones")
+        } else if escaped && table_has_raw {
+            Some("escaped line jump or tabulation in a table that also uses raw ones")
+        } else {
+            None
+        }
+    }
+
+    /// This function returns if the provided text looks like a path, like `ui\new\icon.png`, so its backslashes are folder separators, not escapes.
+    ///
+    /// Escaped backslashes, like in `ui\\new\\icon.png`, are considered separators too. As one-word texts like `line\nline` also look like paths,
+    /// texts with a separator followed by `n` or `t` are only considered paths if they end in a file name with an extension.
+    pub fn is_path_like(text: &str) -> bool {
+        let text = text.replace("\\\\", "\\");
+        if !text.contains(['/', '\\']) || !text.split(['/', '\\']).all(|segment| !segment.is_empty() && segment.chars().all(|character| character.is_alphanumeric() || matches!(character, '_' | '-' | '.'))) {
+            return false;
+        }
+
+        if text.contains("\\n") || text.contains("\\t") {
+            text.rsplit(['/', '\\']).next()
+                .and_then(|file_name| file_name.rsplit_once('.'))
+                .map_or(false, |(name, extension)| !name.is_empty() && !extension.is_empty())
+        } else {
+            true
+        }
+    }
+
+    /// This function returns the columns of a table referencing a key column of the same table, as (column, referenced column) pairs.
+    ///
+    /// The table name can be provided with or without the `_tables` suffix.
+    pub fn self_reference_columns(table_name: &str, fields: &[Field], patches: Option<&DefinitionPatch>) -> Vec<(usize, usize)> {
+        let table_name = table_name.strip_suffix("_tables").unwrap_or(table_name);
+        fields.iter()
+            .enumerate()
+            .filter_map(|(column, field)| {
+                let (ref_table, ref_column) = field.is_reference(patches)?;
+                if ref_table != table_name {
+                    return None;
+                }
+
+                let referenced_column = fields.iter().position(|field| field.name() == ref_column && field.is_key(patches))?;
+                if referenced_column != column {
+                    Some((column, referenced_column))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// This function returns the loc keys expected for the provided row for which `loc_exists` returns false, as (localised field, loc key) pairs.
+    ///
+    /// Rows with an empty key don't expect any loc entry, so they never have missing ones.
+    pub fn missing_loc_keys(definition: &Definition, table_name: &str, row: &[DecodedData], loc_exists: impl Fn(&str) -> bool) -> Vec<(String, String)> {
+        definition.localised_fields()
+            .iter()
+            .zip(definition.expected_loc_keys_for_row(row, table_name))
+            .filter(|(_, loc_key)| !loc_exists(loc_key))
+            .map(|(field, loc_key)| (field.name().to_owned(), loc_key))
+            .collect()
+    }
+
+    /// This function compiles the validation patterns of the string columns of the provided tables, so each pattern is compiled once per check.
+    ///
+    /// Patterns must match the entire value of the cell. Invalid patterns are kept as `None`, so they can be reported on each table using them.
+    pub fn validation_regexes(files: &[&RFile]) -> HashMap<String, Option<Regex>> {
+        let mut regexes = HashMap::new();
+        for file in files {
+            if let Ok(RFileDecoded::DB(table)) = file.decoded() {
+                let patches = Some(table.definition().patches());
+                for field in table.definition().fields() {
+                    if Self::is_string_field(field) {
+                        if let Some(pattern) = field.validation_regex(patches) {
+                            regexes.entry(pattern).or_insert_with_key(|pattern| Self::validation_regex(pattern));
+                        }
                     }
-                }).max_by(|x, y| x.cmp(y)) {
-                if *max_version != table_version {
-                    return true
                 }
             }
         }
 
-        false
+        regexes
+    }
+
+    /// This function compiles a validation pattern, so it matches the entire value of a cell.
+    fn validation_regex(pattern: &str) -> Option<Regex> {
+        Regex::new(&format!("^(?:{pattern})$")).ok()
+    }
+
+    /// This function returns if the provided field holds strings.
+    fn is_string_field(field: &Field) -> bool {
+        matches!(field.field_type(), FieldType::StringU8 | FieldType::StringU16 | FieldType::OptionalStringU8 | FieldType::OptionalStringU16)
     }
 
     /// This function takes care of checking the db tables of your mod for errors.
+    ///
+    /// Validation patterns are taken from `validation_regexes` (see [TableDiagnostic::validation_regexes]). Patterns missing from it are compiled here.
     pub fn check_db(
         file: &RFile,
         dependencies: &Dependencies,
+        schema: &Schema,
         global_ignored_diagnostics: &[String],
         ignored_fields: &[String],
         ignored_diagnostics: &HashSet<String>,
@@ -210,17 +470,54 @@ impl TableDiagnostic {
         game_info: &GameInfo,
         local_path_list: &HashMap<String, Vec<String>>,
         dependency_data: &HashMap<i32, TableReferences>,
+        loc_data: &Option<HashMap<Cow<str>, Cow<str>>>,
+        validation_regexes: &HashMap<String, Option<Regex>>,
         check_ak_only_refs: bool,
     ) ->Option<DiagnosticType> {
         if let Ok(RFileDecoded::DB(table)) = file.decoded() {
             let mut diagnostic = TableDiagnostic::new(file.path_in_container_raw());
 
             // Before anything else, check if the table is outdated.
-            if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("OutdatedTable"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) && Self::is_table_outdated(table.table_name(), *table.definition().version(), dependencies) {
+            if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("OutdatedTable"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) && Self::is_table_outdated(table.table_name(), *table.definition().version(), dependencies, schema) {
                 let result = TableDiagnosticReport::new(TableDiagnosticReportType::OutdatedTable, &[], &[]);
                 diagnostic.results_mut().push(result);
             }
 
+            // Check for columns that would be dropped if the table gets updated to the latest definition.
+            if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("UnknownColumnInData"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) && !table.data().is_empty() {
+                if let Some(latest_definition) = Self::latest_vanilla_definition(table.table_name(), dependencies, schema) {
+                    if latest_definition.version() != table.definition().version() {
+                        let fields_processed = table.definition().fields_processed();
+                        let latest_fields_processed = latest_definition.fields_processed();
+                        for (column, field) in fields_processed.iter().enumerate() {
+                            if !latest_fields_processed.iter().any(|x| x.name() == field.name()) &&
+                                !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field.name()), Some("UnknownColumnInData"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                                let cells_affected = (0..table.data().len()).map(|row| (row as i32, column as i32)).collect::<Vec<_>>();
+                                let result = TableDiagnosticReport::new(TableDiagnosticReportType::UnknownColumnInData(field.name().to_owned()), &cells_affected, &fields_processed);
+                                diagnostic.results_mut().push(result);
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Check for string columns empty in all rows. Key columns are not reported, as the schema requires them to identify each row.
+            // Other columns are not reported either, as zero or false are valid values for them, not missing data.
+            if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("EmptyColumn"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                let empty_columns = table.empty_columns();
+                if !empty_columns.is_empty() {
+                    let fields_processed = table.definition().fields_processed();
+                    let patches = Some(table.definition().patches());
+                    for (column, field) in fields_processed.iter().enumerate() {
+                        if Self::is_string_field(field) && empty_columns.iter().any(|name| name == field.name()) && !field.is_key(patches) &&
+                            !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field.name()), Some("EmptyColumn"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                            let result = TableDiagnosticReport::new(TableDiagnosticReportType::EmptyColumn(field.name().to_owned()), &[(-1, column as i32)], &fields_processed);
+                            diagnostic.results_mut().push(result);
+                        }
+                    }
+                }
+            }
+
             // Check if it's one of the banned tables for the game selected.
             if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("BannedTable"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) && game_info.is_file_banned(file.path_in_container_raw()) {
                 let result = TableDiagnosticReport::new(TableDiagnosticReportType::BannedTable, &[], &[]);
@@ -248,6 +545,17 @@ impl TableDiagnostic {
                     diagnostic.results_mut().push(result);
                 }
 
+                // Tables must be in a db/table_name/ folder, or the game ignores them. Decoded tables get their name from their folder,
+                // so this only catches tables moved after being decoded. Tables in the wrong folder when loaded usually fail to decode,
+                // so those are checked in check_db_folder instead.
+                if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("TableNameFolderMismatch"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                    let path_split = file.path_in_container_split();
+                    if path_split.len() != 3 || !path_split[0].eq_ignore_ascii_case("db") || !path_split[1].eq_ignore_ascii_case(table.table_name()) {
+                        let result = TableDiagnosticReport::new(TableDiagnosticReportType::TableNameFolderMismatch, &[], &[]);
+                        diagnostic.results_mut().push(result);
+                    }
+                }
+
                 if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("TableIsDataCoring"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
                     match game_info.vanilla_db_table_name_logic() {
                         VanillaDBTableNameLogic::FolderName => {
@@ -274,8 +582,77 @@ impl TableDiagnostic {
             let table_data = table.data();
             let mut columns_without_reference_table = vec![];
             let mut columns_with_reference_table_and_no_column = vec![];
-            let mut keys: HashMap<String, Vec<(i32, i32)>> = HashMap::with_capacity(table_data.len());
-            let mut duplicated_combined_keys_already_marked = vec![];
+
+            // Rows grouped by their combined keys, in order of appearance, with the key cells of each row.
+            let mut keys: HashMap<String, usize> = HashMap::with_capacity(table_data.len());
+            let mut key_groups: Vec<(String, Vec<(usize, Vec<(i32, i32)>)>)> = vec![];
+            let ignore_duplicated_combined_keys = Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("DuplicatedCombinedKeys"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields);
+            let ignore_duplicated_combined_keys_conflicting = Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("DuplicatedCombinedKeysConflicting"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields);
+
+            // Get the already compiled validation patterns of the string columns, reporting the invalid ones.
+            let mut column_validation_regexes: HashMap<usize, Regex> = HashMap::new();
+            for (column, field) in fields_processed.iter().enumerate() {
+                if Self::is_string_field(field) {
+                    if let Some(pattern) = field.validation_regex(patches) {
+                        let regex = match validation_regexes.get(&pattern) {
+                            Some(regex) => regex.clone(),
+                            None => Self::validation_regex(&pattern),
+                        };
+
+                        match regex {
+                            Some(regex) => { column_validation_regexes.insert(column, regex); },
+                            None => if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field.name()), Some("InvalidValidationPattern"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                                let result = TableDiagnosticReport::new(TableDiagnosticReportType::InvalidValidationPattern(field.name().to_owned()), &[(-1, column as i32)], &fields_processed);
+                                diagnostic.results_mut().push(result);
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Check which escaping styles the table uses, so we can detect cells that don't follow the rest of the table.
+            // Path columns are not checked, as backslashes in them are folder separators, not escapes. Columns not marked as paths
+            // in the schema are considered path columns if most of their values look like paths.
+            let mut escaping_columns = vec![];
+            let mut table_has_escaped = false;
+            let mut table_has_raw = false;
+            if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("InconsistentEscaping"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                for (column, field) in fields_processed.iter().enumerate() {
+                    if Self::is_string_field(field) && !field.is_filename(patches) {
+                        let values = table_data.iter().map(|cells| cells[column].data_to_string()).filter(|value| !value.is_empty()).collect::<Vec<_>>();
+                        let path_like_values = values.iter().filter(|value| Self::is_path_like(value)).count();
+                        if path_like_values * 2 <= values.len() {
+                            escaping_columns.push(column);
+                        }
+                    }
+                }
+
+                for cells in table_data.iter() {
+                    for column in &escaping_columns {
+                        let (escaped, raw) = Self::escaping_styles(&cells[*column].data_to_string());
+                        table_has_escaped |= escaped;
+                        table_has_raw |= raw;
+                    }
+                }
+            }
+
+            // Columns referencing the key of their own table, to check for rows referencing themselves.
+            let self_reference_columns = if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("SelfReference"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                Self::self_reference_columns(table.table_name(), &fields_processed, patches)
+            } else {
+                vec![]
+            };
+
+            // Columns forming the loc keys of each row, to check for rows without the loc entries they need.
+            let missing_loc_entry_columns = if !table.definition().localised_fields().is_empty() && !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("MissingLocEntry"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                Some(table.definition().localised_key_order()
+                    .iter()
+                    .map(|column| *column as i32)
+                    .filter(|column| (*column as usize) < fields_processed.len())
+                    .collect::<Vec<_>>())
+            } else {
+                None
+            };
 
             // Columns we can try to check for paths.
             let mut ignore_path_columns = vec![];
@@ -364,6 +741,15 @@ impl TableDiagnostic {
                         }
                     }
 
+                    // Check for rows referencing their own key. Empty and numeric 0 values are empty references, so they're not checked.
+                    if let Some((_, referenced_column)) = self_reference_columns.iter().find(|(self_reference_column, _)| *self_reference_column == column) {
+                        if !cell_data.is_empty() && cell_data != "0" && cell_data == cells[*referenced_column].data_to_string() &&
+                            !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field.name()), Some("SelfReference"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                            let result = TableDiagnosticReport::new(TableDiagnosticReportType::SelfReference(field.name().to_string()), &[(row as i32, column as i32), (row as i32, *referenced_column as i32)], &fields_processed);
+                            diagnostic.results_mut().push(result);
+                        }
+                    }
+
                     // Check for empty keys/rows.
                     if row_is_empty && (!cell_data.is_empty() && cell_data != "false") {
                         row_is_empty = false;
@@ -383,6 +769,25 @@ impl TableDiagnostic {
                         diagnostic.results_mut().push(result);
                     }
 
+                    if let Some(regex) = column_validation_regexes.get(&column) {
+                        if !cell_data.is_empty() && !regex.is_match(&cell_data) && !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field.name()), Some("FieldFailsValidationPattern"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                            let result = TableDiagnosticReport::new(TableDiagnosticReportType::FieldFailsValidationPattern(field.name().to_string()), &[(row as i32, column as i32)], &fields_processed);
+                            diagnostic.results_mut().push(result);
+                        }
+                    }
+
+                    if !field.enum_values().is_empty() && !field.is_enum_value_encodable(&cell_data) && !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field.name()), Some("EnumValueNotEncodable"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                        let result = TableDiagnosticReport::new(TableDiagnosticReportType::EnumValueNotEncodable(cell_data.to_string()), &[(row as i32, column as i32)], &fields_processed);
+                        diagnostic.results_mut().push(result);
+                    }
+
+                    if escaping_columns.contains(&column) && !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field.name()), Some("InconsistentEscaping"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                        if let Some(issue) = Self::escaping_issue(&cell_data, table_has_escaped, table_has_raw) {
+                            let result = TableDiagnosticReport::new(TableDiagnosticReportType::InconsistentEscaping(issue.to_owned()), &[(row as i32, column as i32)], &fields_processed);
+                            diagnostic.results_mut().push(result);
+                        }
+                    }
+
                     if field.is_key(patches) {
                         row_keys.insert(column as i32, cell_data);
                     }
@@ -399,28 +804,45 @@ impl TableDiagnostic {
                     diagnostic.results_mut().push(result);
                 }
 
-                if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("DuplicatedCombinedKeys"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                if let Some(ref loc_key_columns) = missing_loc_entry_columns {
+                    let loc_exists = |loc_key: &str| loc_data.as_ref().map(|loc_data| loc_data.contains_key(loc_key)).unwrap_or(false) || dependencies.loc_key_exists(loc_key);
+                    for (field_name, loc_key) in Self::missing_loc_keys(table.definition(), table.table_name(), cells, loc_exists) {
+                        if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(&field_name), Some("MissingLocEntry"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                            let cells_affected = loc_key_columns.iter().map(|column| (row as i32, *column)).collect::<Vec<(i32, i32)>>();
+                            let result = TableDiagnosticReport::new(TableDiagnosticReportType::MissingLocEntry(loc_key), &cells_affected, &fields_processed);
+                            diagnostic.results_mut().push(result);
+                        }
+                    }
+                }
 
-                    // If this returns something, it means there is a duplicate.
+                if (!ignore_duplicated_combined_keys || !ignore_duplicated_combined_keys_conflicting) && !row_keys.is_empty() {
                     let combined_keys = row_keys.values().join("| |");
-                    if let Some(old_position) = keys.insert(combined_keys.to_owned(), row_keys.keys().map(|x| (row as i32, *x)).collect()) {
-                        if let Some(old_pos) = old_position.first() {
+                    let cells_affected = row_keys.keys().map(|column| (row as i32, *column)).collect::<Vec<(i32, i32)>>();
+                    match keys.get(&combined_keys) {
+                        Some(group) => key_groups[*group].1.push((row, cells_affected)),
+                        None => {
+                            keys.insert(combined_keys.to_owned(), key_groups.len());
+                            key_groups.push((combined_keys, vec![(row, cells_affected)]));
+                        }
+                    }
+                }
+            }
 
-                            // Mark previous row, if not yet marked.
-                            if !duplicated_combined_keys_already_marked.contains(&old_pos.0) {
-                                let result = TableDiagnosticReport::new(TableDiagnosticReportType::DuplicatedCombinedKeys(combined_keys.to_string()), &old_position, &fields_processed);
-                                diagnostic.results_mut().push(result);
-                                duplicated_combined_keys_already_marked.push(old_pos.0);
-                            }
+            // Each group of rows sharing their combined keys is classified as a whole: if all its rows are exact duplicates, they're safe to remove.
+            // If at least one of them has different data, the whole group is a real conflict, so we report it separately.
+            for (combined_keys, rows) in key_groups.into_iter().filter(|(_, rows)| rows.len() > 1) {
+                let first_row = &table_data[rows[0].0];
+                let is_conflicting = rows.iter().any(|(row, _)| &table_data[*row] != first_row);
+                let (is_ignored, report_type) = if is_conflicting {
+                    (ignore_duplicated_combined_keys_conflicting, TableDiagnosticReportType::DuplicatedCombinedKeysConflicting(combined_keys))
+                } else {
+                    (ignore_duplicated_combined_keys, TableDiagnosticReportType::DuplicatedCombinedKeys(combined_keys))
+                };
 
-                            // Mark current row, if not yet marked.
-                            if !duplicated_combined_keys_already_marked.contains(&(row as i32)) {
-                                let cells_affected = row_keys.keys().map(|column| (row as i32, *column)).collect::<Vec<(i32, i32)>>();
-                                let result = TableDiagnosticReport::new(TableDiagnosticReportType::DuplicatedCombinedKeys(combined_keys), &cells_affected, &fields_processed);
-                                diagnostic.results_mut().push(result);
-                                duplicated_combined_keys_already_marked.push(row as i32);
-                            }
-                        }
+                if !is_ignored {
+                    for (_, cells_affected) in &rows {
+                        let result = TableDiagnosticReport::new(report_type.clone(), cells_affected, &fields_processed);
+                        diagnostic.results_mut().push(result);
                     }
                 }
             }
@@ -454,13 +876,45 @@ impl TableDiagnostic {
         } else { None }
     }
 
+    /// This function checks if a db table that couldn't be decoded is in the wrong folder.
+    ///
+    /// Tables are decoded as the table their folder says they are, so a table in the wrong folder usually fails to decode.
+    /// If its data can be decoded as another table of the schema, but not as the one of its folder, it's reported as a mismatch.
+    pub fn check_db_folder(
+        file: &RFile,
+        schema: &Schema,
+        global_ignored_diagnostics: &[String],
+        ignored_fields: &[String],
+        ignored_diagnostics: &HashSet<String>,
+        ignored_diagnostics_for_fields: &HashMap<String, Vec<String>>,
+    ) -> Option<DiagnosticType> {
+        if file.decoded().is_ok() || Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("TableNameFolderMismatch"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+            return None;
+        }
+
+        let folder_table_name = file.db_table_name_from_path()?;
+        let table_names = DB::table_names_for_data(file.cached().ok()?, schema);
+        if table_names.is_empty() || table_names.iter().any(|table_name| table_name == folder_table_name) {
+            return None;
+        }
+
+        let mut diagnostic = TableDiagnostic::new(file.path_in_container_raw());
+        let result = TableDiagnosticReport::new(TableDiagnosticReportType::TableNameFolderMismatch, &[], &[]);
+        diagnostic.results_mut().push(result);
+        Some(DiagnosticType::DB(diagnostic))
+    }
+
     /// This function takes care of checking the loc tables of your mod for errors.
+    ///
+    /// If `loc_key_regex` is provided, loc keys not matching it are reported as invalid. If not, only keys with line jumps or tabs are.
     pub fn check_loc(
         file: &RFile,
         global_ignored_diagnostics: &[String],
         ignored_fields: &[String],
         ignored_diagnostics: &HashSet<String>,
         ignored_diagnostics_for_fields: &HashMap<String, Vec<String>>,
+        loc_key_regex: Option<&Regex>,
+        loc_formatting_rules: &LocFormattingRules,
     ) ->Option<DiagnosticType> {
         if let Ok(RFileDecoded::Loc(table)) = file.decoded() {
             let mut diagnostic = TableDiagnostic::new(file.path_in_container_raw());
@@ -473,11 +927,28 @@ impl TableDiagnostic {
             let mut duplicated_rows_already_marked = vec![];
             let mut duplicated_combined_keys_already_marked = vec![];
 
+            // Check which escaping styles the texts of the loc use, so we can detect texts that don't follow the rest of the loc.
+            let check_escaping = !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("InconsistentEscaping"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields);
+            let mut table_has_escaped = false;
+            let mut table_has_raw = false;
+            if check_escaping {
+                for cells in table.data().iter() {
+                    let (escaped, raw) = Self::escaping_styles(&cells[1].data_to_string());
+                    table_has_escaped |= escaped;
+                    table_has_raw |= raw;
+                }
+            }
+
             for (row, cells) in table.data().iter().enumerate() {
                 let key = cells[0].data_to_string();
                 let data = cells[1].data_to_string();
 
-                if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field_key_name), Some("InvalidLocKey"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) && !key.is_empty() && (key.contains('\n') || key.contains('\t')) {
+                let is_invalid_key = match loc_key_regex {
+                    Some(regex) => !regex.is_match(&key),
+                    None => key.contains('\n') || key.contains('\t'),
+                };
+
+                if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field_key_name), Some("InvalidLocKey"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) && !key.is_empty() && is_invalid_key {
                     let result = TableDiagnosticReport::new(TableDiagnosticReportType::InvalidLocKey, &[(row as i32, 0)], &fields);
                     diagnostic.results_mut().push(result);
                 }
@@ -499,6 +970,20 @@ impl TableDiagnostic {
                     diagnostic.results_mut().push(result);
                 }
 
+                if check_escaping && !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field_text_name), Some("InconsistentEscaping"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                    if let Some(issue) = Self::escaping_issue(&data, table_has_escaped, table_has_raw) {
+                        let result = TableDiagnosticReport::new(TableDiagnosticReportType::InconsistentEscaping(issue.to_owned()), &[(row as i32, 1)], &fields);
+                        diagnostic.results_mut().push(result);
+                    }
+                }
+
+                if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field_text_name), Some("MalformedLocFormatting"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) && !data.is_empty() {
+                    if let Some(issue) = loc_formatting_rules.check(&data) {
+                        let result = TableDiagnosticReport::new(TableDiagnosticReportType::MalformedLocFormatting(issue), &[(row as i32, 1)], &fields);
+                        diagnostic.results_mut().push(result);
+                    }
+                }
+
                 if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, Some(field_key_name), Some("DuplicatedCombinedKeys"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
 
                     // If this returns something, it means there is a duplicate.
@@ -558,3 +1043,46 @@ impl TableDiagnostic {
         } else { None }
     }
 }
+
+//-------------------------------------------------------------------------------//
+//                             Utility functions
+//-------------------------------------------------------------------------------//
+
+/// This function splits the provided text on each separator not escaped with a backslash.
+///
+/// Escapes are kept in the resulting parts, so they can be resolved after splitting them again.
+fn split_unescaped(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (index, character) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if character == '\\' {
+            escaped = true;
+        } else if character == separator {
+            parts.push(&text[start..index]);
+            start = index + separator.len_utf8();
+        }
+    }
+
+    parts.push(&text[start..]);
+    parts
+}
+
+/// This function removes the backslashes used to escape characters in the provided text. A trailing backslash is kept.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut characters = text.chars();
+
+    while let Some(character) = characters.next() {
+        if character == '\\' {
+            unescaped.push(characters.next().unwrap_or(character));
+        } else {
+            unescaped.push(character);
+        }
+    }
+
+    unescaped
+}
@@ -0,0 +1,169 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module with the structs and functions specific for `Text` diagnostics.
+
+use getset::{Getters, MutGetters};
+use regex::Regex;
+use serde_derive::{Serialize, Deserialize};
+
+use std::collections::{HashMap, HashSet};
+use std::{fmt, fmt::Display};
+
+use rpfm_lib::files::{RFile, RFileDecoded};
+
+use crate::dependencies::Dependencies;
+use crate::diagnostics::*;
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// This struct contains the results of a Text diagnostic.
+#[derive(Debug, Clone, Default, Getters, MutGetters, Serialize, Deserialize)]
+#[getset(get = "pub", get_mut = "pub")]
+pub struct TextDiagnostic {
+    path: String,
+    results: Vec<TextDiagnosticReport>
+}
+
+/// This struct defines an individual Text diagnostic result.
+#[derive(Debug, Clone, Getters, MutGetters, Serialize, Deserialize)]
+#[getset(get = "pub", get_mut = "pub")]
+pub struct TextDiagnosticReport {
+
+    /// Line (1-based) where the problem was found.
+    line: usize,
+    report_type: TextDiagnosticReportType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TextDiagnosticReportType {
+    ReferencedFileNotFound(String),
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl TextDiagnosticReport {
+    pub fn new(report_type: TextDiagnosticReportType, line: usize) -> Self {
+        Self {
+            line,
+            report_type
+        }
+    }
+}
+
+impl DiagnosticReport for TextDiagnosticReport {
+    fn message(&self) -> String {
+        match &self.report_type {
+            TextDiagnosticReportType::ReferencedFileNotFound(path) => format!("Referenced file not found in line {}: {path}.", self.line),
+        }
+    }
+
+    fn level(&self) -> DiagnosticLevel {
+        match self.report_type {
+            TextDiagnosticReportType::ReferencedFileNotFound(_) => DiagnosticLevel::Warning,
+        }
+    }
+}
+
+impl Display for TextDiagnosticReportType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(match self {
+            Self::ReferencedFileNotFound(_) => "ReferencedFileNotFound",
+        }, f)
+    }
+}
+
+impl TextDiagnostic {
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_owned(),
+            results: vec![],
+        }
+    }
+
+    /// This function parses a list of path rules from a string, in the format `extension:attribute,extension:attribute,...`.
+    ///
+    /// Malformed rules are ignored.
+    pub fn parse_path_rules(rules: &str) -> Vec<(String, String)> {
+        rules.split(',')
+            .filter_map(|rule| rule.split_once(':'))
+            .map(|(extension, attribute)| (extension.trim().to_owned(), attribute.trim().to_owned()))
+            .filter(|(extension, attribute)| !extension.is_empty() && !attribute.is_empty())
+            .collect()
+    }
+
+    /// This function builds the regexes used to find referenced paths, grouped by the extension of the files they apply to.
+    ///
+    /// Each rule is a pair of file extension (without the dot) and attribute name. The attribute can be an xml attribute (`path="..."`),
+    /// a lua/ini-like assignment (`path = "..."`) or a json key (`"path": "..."`). Rules with invalid names are ignored.
+    pub fn path_regexes(rules: &[(String, String)]) -> HashMap<String, Vec<Regex>> {
+        let mut regexes: HashMap<String, Vec<Regex>> = HashMap::new();
+        for (extension, attribute) in rules {
+            let extension = extension.trim().trim_start_matches('.').to_lowercase();
+            let attribute = attribute.trim();
+            if extension.is_empty() || attribute.is_empty() {
+                continue;
+            }
+
+            if let Ok(regex) = Regex::new(&format!(r#"\b{}"?\s*[=:]\s*["']([^"'\r\n]*)["']"#, regex::escape(attribute))) {
+                regexes.entry(extension).or_default().push(regex);
+            }
+        }
+
+        regexes
+    }
+
+    /// This function returns the rules that apply to the provided file, if any.
+    pub fn path_regexes_for_file<'a>(file: &RFile, path_regexes: &'a HashMap<String, Vec<Regex>>) -> Option<&'a Vec<Regex>> {
+        let extension = file.path_in_container_raw().rsplit_once('.')?.1.to_lowercase();
+        path_regexes.get(&extension)
+    }
+
+    /// This function takes care of checking for broken file references in text files.
+    pub fn check(
+        file: &RFile,
+        path_regexes: &HashMap<String, Vec<Regex>>,
+        dependencies: &Dependencies,
+        global_ignored_diagnostics: &[String],
+        ignored_fields: &[String],
+        ignored_diagnostics: &HashSet<String>,
+        ignored_diagnostics_for_fields: &HashMap<String, Vec<String>>,
+        local_path_list: &HashMap<String, Vec<String>>,
+    ) -> Option<DiagnosticType> {
+        let regexes = Self::path_regexes_for_file(file, path_regexes)?;
+        if let Ok(RFileDecoded::Text(text)) = file.decoded() {
+            let mut diagnostic = Self::new(file.path_in_container_raw());
+            if !Diagnostics::ignore_diagnostic(global_ignored_diagnostics, None, Some("ReferencedFileNotFound"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                for (index, line) in text.contents().lines().enumerate() {
+                    for regex in regexes {
+                        for captures in regex.captures_iter(line) {
+                            let path = captures[1].replace('\\', "/");
+                            let path = path.trim_start_matches('/');
+                            if !path.is_empty() &&
+                                local_path_list.get(&path.to_lowercase()).is_none() &&
+                                !dependencies.file_exists(path, true, true, true) {
+                                let result = TextDiagnosticReport::new(TextDiagnosticReportType::ReferencedFileNotFound(path.to_owned()), index + 1);
+                                diagnostic.results_mut().push(result);
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !diagnostic.results().is_empty() {
+                Some(DiagnosticType::Text(diagnostic))
+            } else { None }
+        } else { None }
+    }
+}
@@ -16,11 +16,13 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
 use rpfm_lib::error::{RLibError, Result};
-use rpfm_lib::files::{Container, ContainerPath, db::DB, FileType, loc::Loc, pack::Pack, RFileDecoded, table::DecodedData, text::TextFormat};
+use rpfm_lib::files::{Container, ContainerPath, db::DB, FileType, loc::Loc, pack::Pack, RFile, RFileDecoded, table::DecodedData, text::TextFormat};
 use rpfm_lib::schema::Schema;
 
 use crate::dependencies::Dependencies;
 
+#[cfg(test)] mod optimizer_test;
+
 //-------------------------------------------------------------------------------//
 //                             Trait definitions
 //-------------------------------------------------------------------------------//
@@ -30,7 +32,7 @@ pub trait Optimizable {
 
     /// This function optimizes the provided struct to reduce its size and improve compatibility.
     ///
-    /// It returns if the struct has been left in an state where it can be safetly deleted.
+    /// It returns if the struct has been left in an state where it can be safely deleted.
     fn optimize(&mut self, dependencies: &mut Dependencies) -> bool;
 }
 
@@ -39,8 +41,13 @@ pub trait OptimizableContainer: Container {
 
     /// This function optimizes the provided [Container] to reduce its size and improve compatibility.
     ///
-    /// It returns the list of files that has been safetly deleted during the optimization process.
+    /// It returns the list of files that has been safely deleted during the optimization process.
     fn optimize(&mut self, paths_to_optimize: Option<Vec<ContainerPath>>, dependencies: &mut Dependencies, schema: &Schema, optimize_datacored_tables: bool) -> Result<HashSet<String>>;
+
+    /// This function returns the paths of the files in the provided [Container] that are identical to their vanilla counterparts.
+    ///
+    /// These files do not override anything, so they can be safely removed.
+    fn redundant_vanilla_files(&self, dependencies: &Dependencies) -> Vec<ContainerPath>;
 }
 
 //-------------------------------------------------------------------------------//
@@ -165,6 +172,44 @@ impl OptimizableContainer for Pack {
         // Return the deleted files, so the caller can know what got removed.
         Ok(files_to_delete)
     }
+
+    /// This function returns the paths of the files in the provided [Pack] that are identical to their vanilla counterparts.
+    ///
+    /// DB and Loc tables are compared by their decoded data if both the Pack's file and the vanilla file can be decoded,
+    /// so differences in things like the table's GUID are ignored. The rest of the files are compared by their raw data.
+    ///
+    /// Files that are also present in a parent Pack are ignored, as in that case the vanilla-identical file is reverting the parent's change.
+    fn redundant_vanilla_files(&self, dependencies: &Dependencies) -> Vec<ContainerPath> {
+        let mut paths = self.files()
+            .par_iter()
+            .filter(|(path, _)| !dependencies.file_exists(path, false, true, false))
+            .filter_map(|(path, rfile)| {
+                let vanilla_file = dependencies.file(path, true, false, false).ok()?;
+
+                let is_table = matches!(rfile.file_type(), FileType::DB | FileType::Loc);
+                if is_table {
+                    if let (Some(local_data), Some(vanilla_data)) = (table_data(rfile), table_data(vanilla_file)) {
+                        return if local_data == vanilla_data {
+                            Some(ContainerPath::File(path.to_owned()))
+                        } else {
+                            None
+                        };
+                    }
+                }
+
+                let local_data = rfile.clone().encode(&None, false, false, true).ok()??;
+                let vanilla_data = vanilla_file.clone().encode(&None, false, false, true).ok()??;
+                if local_data == vanilla_data {
+                    Some(ContainerPath::File(path.to_owned()))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        paths.sort();
+        paths
+    }
 }
 
 impl Optimizable for DB {
@@ -177,7 +222,7 @@ impl Optimizable for DB {
     /// - Removal of ITM (Identical To Master) entries.
     /// - Removal of ITNR (Identical To New Row) entries.
     ///
-    /// It returns if the DB is empty, meaning it can be safetly deleted.
+    /// It returns if the DB is empty, meaning it can be safely deleted.
     fn optimize(&mut self, dependencies: &mut Dependencies) -> bool {
 
         // Get a manipulable copy of all the entries, so we can optimize it.
@@ -282,7 +327,7 @@ impl Optimizable for Loc {
     /// - Removal of ITM (Identical To Master) entries.
     /// - Removal of ITNR (Identical To New Row) entries.
     ///
-    /// It returns if the Loc is empty, meaning it can be safetly deleted.
+    /// It returns if the Loc is empty, meaning it can be safely deleted.
     fn optimize(&mut self, dependencies: &mut Dependencies) -> bool {
 
         // Get a manipulable copy of all the entries, so we can optimize it.
@@ -329,3 +374,23 @@ impl Optimizable for Loc {
         }
     }
 }
+
+//-------------------------------------------------------------------------------//
+//                             Utility functions
+//-------------------------------------------------------------------------------//
+
+/// This function returns the decoded data of a DB or Loc file, decoding a copy of it if it's not yet decoded.
+///
+/// It returns None if the file is not a table or cannot be decoded.
+fn table_data(rfile: &RFile) -> Option<Vec<Vec<DecodedData>>> {
+    let decoded = match rfile.decoded() {
+        Ok(decoded) => decoded.clone(),
+        Err(_) => rfile.clone().decode(&None, false, true).ok()??,
+    };
+
+    match decoded {
+        RFileDecoded::DB(db) => Some(db.data().to_vec()),
+        RFileDecoded::Loc(loc) => Some(loc.data().to_vec()),
+        _ => None,
+    }
+}
@@ -0,0 +1,48 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the optimizer.
+
+use rpfm_lib::files::Encodeable;
+use rpfm_lib::games::pfh_version::PFHVersion;
+use rpfm_lib::schema::{Definition, FieldType};
+
+use super::*;
+
+/// This function returns an encoded units table with a row with the provided name.
+fn units_table(path: &str, name: &str) -> RFile {
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("name", FieldType::StringU8, false)], &[]);
+
+    let mut table = DB::new(&definition, None, "units_tables");
+    table.set_data(&[vec![DecodedData::StringU8("unit".to_owned()), DecodedData::StringU8(name.to_owned())]]).unwrap();
+
+    let mut data = vec![];
+    table.encode(&mut data, &None).unwrap();
+    RFile::new_from_vec(&data, FileType::DB, 0, path)
+}
+
+#[test]
+fn test_redundant_vanilla_files() {
+    let mut dependencies = Dependencies::default();
+    dependencies.insert_db_as_vanilla_db(units_table("db/units_tables/identical", "name"));
+    dependencies.insert_db_as_vanilla_db(units_table("db/units_tables/modified", "name"));
+    dependencies.insert_db_as_vanilla_db(units_table("db/units_tables/parent", "name"));
+    dependencies.insert_db_as_parent_db(units_table("db/units_tables/parent", "parent name"));
+
+    let mut pack = Pack::new_with_version(PFHVersion::PFH6);
+    pack.insert(units_table("db/units_tables/identical", "name")).unwrap();
+    pack.insert(units_table("db/units_tables/modified", "modified name")).unwrap();
+    pack.insert(units_table("db/units_tables/parent", "name")).unwrap();
+    pack.insert(units_table("db/units_tables/mod_only", "name")).unwrap();
+
+    // Only the file identical to vanilla is redundant. Modified files, files only in the Pack,
+    // and files reverting a parent's change are not.
+    assert_eq!(pack.redundant_vanilla_files(&dependencies), vec![ContainerPath::File("db/units_tables/identical".to_owned())]);
+}
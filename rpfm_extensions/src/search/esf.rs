@@ -0,0 +1,232 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module with all the code related to the `EsfMatches`.
+
+This module contains the code needed to get esf matches from a `GlobalSearch`.
+
+Replacing is not supported on ESF files yet, so these matches are read-only.
+!*/
+
+use getset::{Getters, MutGetters};
+
+use rpfm_lib::files::esf::{ESF, NodeType};
+
+use super::{find_in_string, MatchingMode, Searchable};
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// This struct represents all the matches of the global search within an ESF file.
+#[derive(Debug, Clone, Getters, MutGetters)]
+#[getset(get = "pub", get_mut = "pub")]
+pub struct EsfMatches {
+
+    /// The path of the file.
+    path: String,
+
+    /// The list of matches within the file.
+    matches: Vec<EsfMatch>,
+}
+
+/// This struct represents a match on a node of an ESF file.
+#[derive(Debug, Clone, Eq, PartialEq, Getters, MutGetters)]
+#[getset(get = "pub", get_mut = "pub")]
+pub struct EsfMatch {
+
+    /// Path of the node within the node tree.
+    ///
+    /// Record nodes are referred by name, and value nodes by their index within their record, like `ROOT/CAMPAIGN_ENV/3`.
+    /// If a record has more than one group of children, the group index goes after its name, like `ROOT/FACTIONS[2]/0`.
+    /// Elements of array nodes have their index at the end, like `ROOT/CAMPAIGN_ENV/3[5]`.
+    node_path: String,
+
+    /// If the match corresponds to the name of a record node, instead of a value.
+    name: bool,
+
+    /// Byte where the match starts.
+    start: usize,
+
+    /// Byte where the match ends.
+    end: usize,
+
+    /// Matched data, as text.
+    text: String,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl Searchable for ESF {
+    type SearchMatches = EsfMatches;
+
+    fn search(&self, file_path: &str, pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode) -> EsfMatches {
+        let mut matches = EsfMatches::new(file_path);
+        matches.search_node(self.root_node(), "", pattern, case_sensitive, matching_mode);
+        matches
+    }
+}
+
+impl EsfMatches {
+
+    /// This function creates a new `EsfMatches` for the provided path.
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_owned(),
+            matches: vec![],
+        }
+    }
+
+    /// This function searches recursively through a node and its children, storing the matches.
+    fn search_node(&mut self, node: &NodeType, node_path: &str, pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode) {
+        match node {
+            NodeType::Record(record) => {
+                let record_path = if node_path.is_empty() {
+                    record.name().to_owned()
+                } else {
+                    format!("{}/{}", node_path, record.name())
+                };
+
+                self.search_value(&record_path, true, record.name(), pattern, case_sensitive, matching_mode);
+
+                let multiple_groups = record.children().len() > 1;
+                for (group_index, group) in record.children().iter().enumerate() {
+                    let group_path = if multiple_groups {
+                        format!("{record_path}[{group_index}]")
+                    } else {
+                        record_path.to_owned()
+                    };
+
+                    for (index, child) in group.iter().enumerate() {
+                        match child {
+                            NodeType::Record(_) => self.search_node(child, &group_path, pattern, case_sensitive, matching_mode),
+                            _ => self.search_node(child, &format!("{group_path}/{index}"), pattern, case_sensitive, matching_mode),
+                        }
+                    }
+                }
+            }
+
+            _ => {
+                let values = Self::node_values(node);
+                if values.len() == 1 && !Self::is_array(node) {
+                    self.search_value(node_path, false, &values[0], pattern, case_sensitive, matching_mode);
+                } else {
+                    for (index, value) in values.iter().enumerate() {
+                        self.search_value(&format!("{node_path}[{index}]"), false, value, pattern, case_sensitive, matching_mode);
+                    }
+                }
+            }
+        }
+    }
+
+    /// This function searches a single value, storing the matches.
+    fn search_value(&mut self, node_path: &str, name: bool, data: &str, pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode) {
+        match matching_mode {
+            MatchingMode::Regex(regex) => {
+                for match_data in regex.find_iter(data) {
+                    self.matches.push(EsfMatch::new(node_path, name, match_data.start(), match_data.end(), data));
+                }
+            }
+
+            MatchingMode::Pattern(regex) => {
+                for (start, end, _) in &find_in_string(data, pattern, case_sensitive, regex) {
+                    self.matches.push(EsfMatch::new(node_path, name, *start, *end, data));
+                }
+            }
+        }
+    }
+
+    /// This function returns the searchable values of a non-record node, as text.
+    ///
+    /// Raw byte nodes are not returned, as they're binary blobs and not something you can search as text.
+    fn node_values(node: &NodeType) -> Vec<String> {
+        match node {
+            NodeType::Invalid |
+            NodeType::Record(_) |
+            NodeType::Unknown26(_) |
+            NodeType::U8Array(_) => vec![],
+
+            NodeType::Bool(value) => vec![value.value().to_string()],
+            NodeType::I8(value) => vec![value.to_string()],
+            NodeType::I16(value) => vec![value.to_string()],
+            NodeType::I32(value) => vec![value.value().to_string()],
+            NodeType::I64(value) => vec![value.to_string()],
+            NodeType::U8(value) => vec![value.to_string()],
+            NodeType::U16(value) => vec![value.to_string()],
+            NodeType::U32(value) => vec![value.value().to_string()],
+            NodeType::U64(value) => vec![value.to_string()],
+            NodeType::F32(value) => vec![value.value().to_string()],
+            NodeType::F64(value) => vec![value.to_string()],
+            NodeType::Coord2d(value) => vec![format!("{}, {}", value.x(), value.y())],
+            NodeType::Coord3d(value) => vec![format!("{}, {}, {}", value.x(), value.y(), value.z())],
+            NodeType::Utf16(value) |
+            NodeType::Ascii(value) => vec![value.to_owned()],
+            NodeType::Angle(value) => vec![value.to_string()],
+
+            NodeType::Unknown21(value) => vec![value.to_string()],
+            NodeType::Unknown23(value) => vec![value.to_string()],
+            NodeType::Unknown25(value) => vec![value.to_string()],
+
+            NodeType::BoolArray(values) => values.iter().map(|x| x.to_string()).collect(),
+            NodeType::I8Array(values) => values.iter().map(|x| x.to_string()).collect(),
+            NodeType::I16Array(values) => values.iter().map(|x| x.to_string()).collect(),
+            NodeType::I32Array(values) => values.value().iter().map(|x| x.to_string()).collect(),
+            NodeType::I64Array(values) => values.iter().map(|x| x.to_string()).collect(),
+            NodeType::U16Array(values) => values.iter().map(|x| x.to_string()).collect(),
+            NodeType::U32Array(values) => values.value().iter().map(|x| x.to_string()).collect(),
+            NodeType::U64Array(values) => values.iter().map(|x| x.to_string()).collect(),
+            NodeType::F32Array(values) => values.iter().map(|x| x.to_string()).collect(),
+            NodeType::F64Array(values) => values.iter().map(|x| x.to_string()).collect(),
+            NodeType::Coord2dArray(values) => values.iter().map(|x| format!("{}, {}", x.x(), x.y())).collect(),
+            NodeType::Coord3dArray(values) => values.iter().map(|x| format!("{}, {}, {}", x.x(), x.y(), x.z())).collect(),
+            NodeType::Utf16Array(values) |
+            NodeType::AsciiArray(values) => values.to_vec(),
+            NodeType::AngleArray(values) => values.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    /// This function returns if the node is an array node.
+    fn is_array(node: &NodeType) -> bool {
+        matches!(node,
+            NodeType::BoolArray(_) |
+            NodeType::I8Array(_) |
+            NodeType::I16Array(_) |
+            NodeType::I32Array(_) |
+            NodeType::I64Array(_) |
+            NodeType::U16Array(_) |
+            NodeType::U32Array(_) |
+            NodeType::U64Array(_) |
+            NodeType::F32Array(_) |
+            NodeType::F64Array(_) |
+            NodeType::Coord2dArray(_) |
+            NodeType::Coord3dArray(_) |
+            NodeType::Utf16Array(_) |
+            NodeType::AsciiArray(_) |
+            NodeType::AngleArray(_)
+        )
+    }
+}
+
+impl EsfMatch {
+
+    /// This function creates a new `EsfMatch` with the provided data.
+    pub fn new(node_path: &str, name: bool, start: usize, end: usize, text: &str) -> Self {
+        Self {
+            node_path: node_path.to_owned(),
+            name,
+            start,
+            end,
+            text: text.to_owned(),
+        }
+    }
+}
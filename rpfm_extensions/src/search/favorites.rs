@@ -0,0 +1,103 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module with the code for favorite tables.
+//!
+//! Favorite tables are a per-game list of table names saved to disk, so they can be quickly accessed
+//! and used to limit a [GlobalSearch](super::GlobalSearch) to them.
+
+use getset::*;
+use serde_derive::{Serialize, Deserialize};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use rpfm_lib::error::Result;
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// This struct contains the favorite tables of each game.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct FavoriteTables {
+
+    /// Favorite table names, by game key.
+    tables: BTreeMap<String, BTreeSet<String>>,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl FavoriteTables {
+
+    /// This function loads the [FavoriteTables] to memory from a provided `.json` file.
+    ///
+    /// If the file doesn't exist yet, it returns an empty list of favorites.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        let mut file = BufReader::new(File::open(path)?);
+        let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
+        file.read_to_end(&mut data)?;
+        serde_json::from_slice(&data).map_err(From::from)
+    }
+
+    /// This function saves the [FavoriteTables] from memory to a `.json` file with the provided path.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent_folder) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(serde_json::to_string_pretty(&self)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// This function returns the favorite tables of the provided game.
+    pub fn tables_for_game(&self, game_key: &str) -> BTreeSet<String> {
+        self.tables.get(game_key).cloned().unwrap_or_default()
+    }
+
+    /// This function returns if the provided table is one of the favorite tables of the provided game.
+    pub fn is_favorite(&self, game_key: &str, table_name: &str) -> bool {
+        self.tables.get(game_key).map(|tables| tables.contains(table_name)).unwrap_or(false)
+    }
+
+    /// This function adds a table to the favorite tables of the provided game.
+    ///
+    /// Returns false if the table was already a favorite.
+    pub fn add(&mut self, game_key: &str, table_name: &str) -> bool {
+        self.tables.entry(game_key.to_owned()).or_default().insert(table_name.to_owned())
+    }
+
+    /// This function removes a table from the favorite tables of the provided game.
+    ///
+    /// Returns false if the table was not a favorite.
+    pub fn remove(&mut self, game_key: &str, table_name: &str) -> bool {
+        let removed = match self.tables.get_mut(game_key) {
+            Some(tables) => tables.remove(table_name),
+            None => false,
+        };
+
+        // Don't keep empty lists around.
+        if self.tables.get(game_key).map(|tables| tables.is_empty()).unwrap_or(false) {
+            self.tables.remove(game_key);
+        }
+
+        removed
+    }
+}
@@ -17,10 +17,15 @@ This module contains the code needed to get a `GlobalSearch` over an entire `Pac
 use getset::*;
 use regex::{RegexBuilder, Regex};
 use rayon::prelude::*;
+use serde_derive::{Serialize, Deserialize};
+
+use std::collections::{BTreeSet, HashSet};
+use std::time::{Duration, Instant};
 
 use rpfm_lib::error::{Result, RLibError};
 use rpfm_lib::files::{Container, ContainerPath, DecodeableExtraData, FileType, pack::Pack, RFile, RFileDecoded};
 use rpfm_lib::games::{GameInfo, VanillaDBTableNameLogic};
+use rpfm_lib::integrations::log::Timings;
 use rpfm_lib::schema::Schema;
 
 use crate::dependencies::Dependencies;
@@ -32,15 +37,17 @@ use self::anim_fragment_battle::AnimFragmentBattleMatches;
 use self::atlas::AtlasMatches;
 //use self::audio::AudioMatches;
 //use self::bmd::BmdMatches;
-//use self::esf::EsfMatches;
+use self::esf::EsfMatches;
 //use self::group_formations::GroupFormationsMatches;
 //use self::image::ImageMatches;
 //use self::matched_combat::MatchedCombatMatches;
 //use self::pack::PackMatches;
 use self::portrait_settings::PortraitSettingsMatches;
+use self::recipe::Recipe;
+use self::registry::SEARCH_REGISTRY;
 use self::rigid_model::RigidModelMatches;
 //use self::sound_bank::SoundBankMatches;
-use self::table::TableMatches;
+use self::table::{TableMatches, TableMatchGranularity};
 use self::text::TextMatches;
 //use self::uic::UicMatches;
 use self::unit_variant::UnitVariantMatches;
@@ -55,12 +62,15 @@ pub mod anim_fragment_battle;
 pub mod atlas;
 //pub mod audio;
 //pub mod bmd;
-//pub mod esf;
+pub mod esf;
+pub mod favorites;
 //pub mod group_formations;
 //pub mod image;
 //pub mod matched_combat;
 //pub mod pack;
 pub mod portrait_settings;
+pub mod recipe;
+pub mod registry;
 pub mod rigid_model;
 //pub mod sound_bank;
 pub mod table;
@@ -71,6 +81,22 @@ pub mod unknown;
 //pub mod video;
 pub mod schema;
 
+#[cfg(test)] mod search_test;
+
+/// File types that need to be decoded before searching on them. These are the ones affected by the decoding size limit.
+const TYPES_DECODED_ON_SEARCH: [FileType; 10] = [
+    FileType::AnimFragmentBattle,
+    FileType::AnimsTable,
+    FileType::Atlas,
+    FileType::ESF,
+    FileType::MatchedCombat,
+    FileType::PortraitSettings,
+    FileType::RigidModel,
+    FileType::Text,
+    FileType::UnitVariant,
+    FileType::Unknown,
+];
+
 //-------------------------------------------------------------------------------//
 //                             Trait definitions
 //-------------------------------------------------------------------------------//
@@ -104,7 +130,7 @@ pub struct GlobalSearch {
     /// Pattern to search.
     pattern: String,
 
-    /// Pattern to use when replacing. This is a hard pattern, which means regex is not allowed here.
+    /// Pattern to use when replacing. When using regex, capture groups of the match can be referenced with `$1` or `${name}`.
     replace_text: String,
 
     /// Should the global search be *Case Sensitive*?
@@ -124,6 +150,137 @@ pub struct GlobalSearch {
 
     /// Key of the game the files we're searching over belong. This is needed to decode certain file formats.
     game_key: String,
+
+    /// If files that need decoding for the search should be kept decoded after it.
+    ///
+    /// This uses more memory, but makes repeated searches over the same files faster, as they don't need to be decoded again.
+    /// Files already decoded before the search (like DB and Loc tables, or open files) are always reused, whatever this is set to.
+    ///
+    /// The trade-off: decoded files are usually several times bigger in memory than their raw data, and they're kept until the file is
+    /// closed or replaced. It's worth enabling it when doing several searches over the same Pack, but not for one-off searches over
+    /// big sources like the game files. To measure it on your machine, run the ignored `bench_find_matches_repeated_search` test.
+    keep_decoded: bool,
+
+    /// Maximum size in bytes of a file for it to be decoded for the search. Bigger files are skipped and reported in the matches. 0 means no limit.
+    max_decode_bytes: u64,
+
+    /// If the pattern is a hex byte pattern (like `DE AD BE EF`) to be searched on the raw data of unknown files, instead of a text pattern.
+    use_hex: bool,
+
+    /// If DB searches should only look on key columns, and on columns referenced by other tables.
+    ///
+    /// Useful to find where an identifier is defined, ignoring the places where it's just used.
+    key_and_reference_columns_only: bool,
+
+    /// If provided and not empty, DB searches only look on tables with these names. Other file types are not affected by it.
+    ///
+    /// Useful to limit a search to a handful of tables, like a list of favorite tables.
+    table_names: Option<BTreeSet<String>>,
+
+    /// If matches on tables and table-like files should be reported per match within a cell, or per row with matches.
+    ///
+    /// Reporting them per row keeps the results manageable on dense tables with a lot of matches.
+    table_match_granularity: TableMatchGranularity,
+}
+
+/// This struct represents a match of the search, along with the text it'll be replaced with.
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct ReplacePreviewItem {
+
+    /// Path of the file containing the match.
+    path: String,
+
+    /// Where in the file the match is.
+    location: ReplacePreviewLocation,
+
+    /// Column of the match. The one of the cell for tables, the byte where the match starts within its line for texts, and 0 for anything else.
+    column: usize,
+
+    /// Full value containing the match, before the replace.
+    value: String,
+
+    /// Byte where the match starts.
+    start: usize,
+
+    /// Byte where the match ends.
+    end: usize,
+
+    /// Matched text, before the replace.
+    original: String,
+
+    /// Text that will replace the matched one.
+    replacement: String,
+}
+
+/// This enum represents the location of a match within a file, for replace previews.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplacePreviewLocation {
+
+    /// Cell of a table-like file, with its row number and column name.
+    Cell(i64, String),
+
+    /// Line of a text file.
+    Line(u64),
+
+    /// Entry of a file made of a list of entries, like PortraitSettings or UnitVariant.
+    Entry(usize),
+
+    /// Any other part of the file not covered by the other locations.
+    File,
+}
+
+/// This struct represents a match of any type in a common shape, to build generic lists or exports of matches.
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct UnifiedMatch {
+
+    /// Type of the file containing the match. None for schema matches.
+    file_type: Option<FileType>,
+
+    /// Path of the file containing the match. For schema matches, this is the name of the table.
+    path: String,
+
+    /// Source the match was found in, if known.
+    source: Option<SearchSource>,
+
+    /// Where in the file the match is.
+    location: MatchLocation,
+
+    /// Text containing the match. Empty for binary matches.
+    text: String,
+
+    /// Byte of the text where the match starts.
+    start: usize,
+
+    /// Byte of the text where the match ends.
+    end: usize,
+}
+
+/// This enum represents the location of a match within a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchLocation {
+
+    /// Cell of a table-like file, with its row number and column name.
+    Cell(i64, String),
+
+    /// Line of a text file.
+    Line(u64),
+
+    /// Entry of a file made of a list of entries, like PortraitSettings or UnitVariant.
+    Entry(usize),
+
+    /// Header of a file made of a list of entries.
+    Header,
+
+    /// Node of an ESF file, by its path.
+    Node(String),
+
+    /// Binary match, with its byte offset and length.
+    Offset(usize, usize),
+
+    /// Column of a definition in the schema, with its version, index and name.
+    SchemaColumn(i32, u32, String),
 }
 
 /// This enum defines the matching mode of the search. We use `Pattern` by default, and fall back to it
@@ -140,16 +297,16 @@ pub enum MatchHolder {
     Anim(UnknownMatches),
     AnimFragmentBattle(AnimFragmentBattleMatches),
     AnimPack(UnknownMatches),
-    AnimsTable(UnknownMatches),
+    AnimsTable(TableMatches),
     Atlas(AtlasMatches),
     Audio(UnknownMatches),
     Bmd(UnknownMatches),
     Db(TableMatches),
-    Esf(UnknownMatches),
+    Esf(EsfMatches),
     GroupFormations(UnknownMatches),
     Image(UnknownMatches),
     Loc(TableMatches),
-    MatchedCombat(UnknownMatches),
+    MatchedCombat(TableMatches),
     Pack(UnknownMatches),
     PortraitSettings(PortraitSettingsMatches),
     RigidModel(RigidModelMatches),
@@ -163,7 +320,7 @@ pub enum MatchHolder {
 }
 
 /// This enum is specifies the source where the search should be performed.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[derive(Default)]
 pub enum SearchSource {
     #[default] Pack,
@@ -173,8 +330,9 @@ pub enum SearchSource {
 }
 
 /// This struct specifies in what file types is the search going to be performed.
-#[derive(Default, Debug, Clone, Getters, Setters)]
+#[derive(Default, Debug, Clone, Getters, Setters, Serialize, Deserialize)]
 #[getset(get = "pub", set = "pub")]
+#[serde(default)]
 pub struct SearchOn {
     anim: bool,
     anim_fragment_battle: bool,
@@ -199,6 +357,11 @@ pub struct SearchOn {
     unknown: bool,
     video: bool,
     schema: bool,
+
+    /// If only matches that are whole words should be reported, so searching `unit` doesn't match `unit_key`.
+    ///
+    /// Ignored when using regex, as the pattern is used as is in that case.
+    whole_word: bool,
 }
 
 /// This struct stores the search matches, separated by file type.
@@ -208,16 +371,16 @@ pub struct Matches {
     anim: Vec<UnknownMatches>,
     anim_fragment_battle: Vec<AnimFragmentBattleMatches>,
     anim_pack: Vec<UnknownMatches>,
-    anims_table: Vec<UnknownMatches>,
+    anims_table: Vec<TableMatches>,
     atlas: Vec<AtlasMatches>,
     audio: Vec<UnknownMatches>,
     bmd: Vec<UnknownMatches>,
     db: Vec<TableMatches>,
-    esf: Vec<UnknownMatches>,
+    esf: Vec<EsfMatches>,
     group_formations: Vec<UnknownMatches>,
     image: Vec<UnknownMatches>,
     loc: Vec<TableMatches>,
-    matched_combat: Vec<UnknownMatches>,
+    matched_combat: Vec<TableMatches>,
     pack: Vec<UnknownMatches>,
     portrait_settings: Vec<PortraitSettingsMatches>,
     rigid_model: Vec<RigidModelMatches>,
@@ -228,6 +391,42 @@ pub struct Matches {
     unknown: Vec<UnknownMatches>,
     video: Vec<UnknownMatches>,
     schema: SchemaMatches,
+
+    /// Paths of the files that were not searched because they were too big to be decoded.
+    skipped: Vec<String>,
+}
+
+/// This struct contains the parameters of a search, as passed to the handler of each file type.
+#[derive(Clone, Copy, Getters)]
+#[getset(get_copy = "pub")]
+pub struct SearchContext<'a> {
+
+    /// Pattern to search. If the search is not case sensitive, it's already in lowercase.
+    pattern: &'a str,
+
+    /// If the search is case sensitive.
+    case_sensitive: bool,
+
+    /// Matching mode of the search.
+    matching_mode: &'a MatchingMode,
+
+    /// Extra data needed to decode certain file types.
+    extra_data: &'a Option<DecodeableExtraData<'a>>,
+
+    /// If files decoded for the search should be kept decoded after it.
+    keep_decoded: bool,
+
+    /// Maximum size in bytes of a file for it to be decoded for the search. 0 means no limit.
+    max_decode_bytes: u64,
+
+    /// If DB searches should only look on key columns, and on columns referenced by other tables.
+    key_and_reference_columns_only: bool,
+
+    /// Columns referenced by other tables, as (table_name, column_name). Only calculated if `key_and_reference_columns_only` is true.
+    reference_targets: &'a HashSet<(String, String)>,
+
+    /// Timings used for profiling.
+    timings: &'a Timings,
 }
 
 //---------------------------------------------------------------p----------------//
@@ -242,23 +441,18 @@ impl GlobalSearch {
         // Don't do anything if we have no pattern to search.
         if self.pattern.is_empty() { return }
 
-        // If we want to use regex and the pattern is invalid, don't search.
-        let matching_mode = if self.use_regex {
-            match RegexBuilder::new(&self.pattern).case_insensitive(!self.case_sensitive).build() {
-                Ok(regex) => MatchingMode::Regex(regex),
-                Err(_) => MatchingMode::Pattern(RegexBuilder::new(&format!("(?i){}", regex::escape(&self.pattern)))
-                    .case_insensitive(!self.case_sensitive)
-                    .build()
-                    .ok()
-                ),
+        // If we're searching for a hex pattern and it's invalid, don't search.
+        let hex_pattern = if self.use_hex {
+            match parse_hex_pattern(&self.pattern) {
+                Ok(hex_pattern) => Some(hex_pattern),
+                Err(_) => return,
             }
         } else {
-            match RegexBuilder::new(&format!("(?i){}", regex::escape(&self.pattern))).case_insensitive(!self.case_sensitive).build() {
-                Ok(regex) => MatchingMode::Pattern(Some(regex)),
-                Err(_) => MatchingMode::Pattern(None),
-            }
+            None
         };
 
+        let matching_mode = self.matching_mode();
+
         // If we're updating, make sure to dedup and get the raw paths of each file to update.
         let update_paths = if !update_paths.is_empty() && self.source == SearchSource::Pack {
             let container_paths = ContainerPath::dedup(update_paths);
@@ -290,12 +484,20 @@ impl GlobalSearch {
         let pattern = self.pattern.to_owned();
         let case_sensitive = self.case_sensitive;
         let search_on = self.search_on().clone();
+        let keep_decoded = self.keep_decoded;
+        let max_decode_bytes = self.max_decode_bytes;
+        let key_and_reference_columns_only = self.key_and_reference_columns_only;
+        // An empty list of tables means no filtering, not filtering out every table.
+        let table_names = self.table_names.clone().filter(|table_names| !table_names.is_empty());
 
         let game_key = self.game_key.to_owned();
         let mut extra_data = DecodeableExtraData::default();
         extra_data.set_game_key(Some(&game_key));
         let extra_data = Some(extra_data);
 
+        let timings = Timings::new("global_search");
+        let enumeration_start = Instant::now();
+
         match self.source {
             SearchSource::Pack => {
 
@@ -306,28 +508,51 @@ impl GlobalSearch {
                     pack.files_by_type_mut(&files_to_search)
                 };
 
-                self.matches_mut().find_matches(&pattern, case_sensitive, &matching_mode, &search_on, &mut files, schema, extra_data);
+                Self::retain_table_names(&mut files, &table_names);
+                timings.record("enumerate", format_args!("{:?}", self.source), enumeration_start.elapsed(), files.len());
+
+                match hex_pattern {
+                    Some(ref hex_pattern) => self.matches_mut().find_hex_matches(hex_pattern, &search_on, &mut files, keep_decoded, max_decode_bytes),
+                    None => self.matches_mut().find_matches(&pattern, case_sensitive, &matching_mode, &search_on, &mut files, schema, extra_data, keep_decoded, max_decode_bytes, key_and_reference_columns_only),
+                }
             }
-            SearchSource::ParentFiles => {
+            SearchSource::ParentFiles |
+            SearchSource::GameFiles => {
+                let include_vanilla = self.source == SearchSource::GameFiles;
+                let include_parent = self.source == SearchSource::ParentFiles;
+
+                // DB tables are searched through the decoded tables of the dependencies,
+                // so the ones not decoded in the dependencies are not decoded again on each search.
+                let use_decoded_tables = hex_pattern.is_none() && search_on.db;
+                let mut files_to_search = self.search_on().types_to_search();
+                if use_decoded_tables {
+                    files_to_search.retain(|file_type| *file_type != FileType::DB);
+                }
 
-                let files_to_search = self.search_on().types_to_search();
-                let files = dependencies.files_by_types_mut(&files_to_search, false, true);
+                let files = dependencies.files_by_types_mut(&files_to_search, include_vanilla, include_parent);
 
-                self.matches_mut().find_matches(&pattern, case_sensitive, &matching_mode, &search_on, &mut files.into_values().collect::<Vec<_>>(), schema, extra_data);
-            },
-            SearchSource::GameFiles => {
+                let mut files = files.into_values().collect::<Vec<_>>();
+                Self::retain_table_names(&mut files, &table_names);
+                timings.record("enumerate", format_args!("{:?}", self.source), enumeration_start.elapsed(), files.len());
 
-                let files_to_search = self.search_on().types_to_search();
-                let files = dependencies.files_by_types_mut(&files_to_search, true, false);
+                match hex_pattern {
+                    Some(ref hex_pattern) => self.matches_mut().find_hex_matches(hex_pattern, &search_on, &mut files, keep_decoded, max_decode_bytes),
+                    None => self.matches_mut().find_matches(&pattern, case_sensitive, &matching_mode, &search_on, &mut files, schema, extra_data, keep_decoded, max_decode_bytes, key_and_reference_columns_only),
+                }
 
-                self.matches_mut().find_matches(&pattern, case_sensitive, &matching_mode, &search_on, &mut files.into_values().collect::<Vec<_>>(), schema, extra_data);
+                if use_decoded_tables {
+                    let db_matches = self.find_dependencies_db_matches(dependencies, schema, &matching_mode, &timings);
+                    self.matches.db = db_matches;
+                }
             },
 
-            // Asskit files are only tables.
+            // Asskit files are only tables, so they cannot be searched for hex patterns.
             SearchSource::AssKitFiles => {
-                if self.search_on.db {
+                if self.search_on.db && hex_pattern.is_none() {
+                    let reference_targets = if key_and_reference_columns_only { schema.referenced_columns() } else { HashSet::new() };
                     self.matches.db = dependencies.asskit_only_db_tables()
                         .par_iter()
+                        .filter(|(table_name, _)| table_names.as_ref().map_or(true, |table_names| table_names.contains(*table_name)))
                         .filter_map(|(table_name, table)| {
                             let file_name = match game_info.vanilla_db_table_name_logic() {
                                 VanillaDBTableNameLogic::FolderName => table_name.to_owned(),
@@ -335,7 +560,11 @@ impl GlobalSearch {
                             };
 
                             let path = format!("db/{table_name}/{file_name}");
-                            let result = table.search(&path, &self.pattern, self.case_sensitive, &matching_mode);
+                            let result = if key_and_reference_columns_only {
+                                TableMatches::search_key_and_reference_columns(table, &path, &self.pattern, self.case_sensitive, &matching_mode, &reference_targets)
+                            } else {
+                                table.search(&path, &self.pattern, self.case_sensitive, &matching_mode)
+                            };
                             if !result.matches().is_empty() {
                                 Some(result)
                             } else {
@@ -347,17 +576,218 @@ impl GlobalSearch {
             },
         }
 
+        if self.table_match_granularity == TableMatchGranularity::Row {
+            self.matches.group_table_matches_by_row();
+        }
+
+        timings.log();
+
         // Restore the pattern to what it was before searching.
         self.pattern = pattern_original;
     }
 
+    /// This function searches the DB tables of the current dependencies source, using the decoded tables of the dependencies.
+    fn find_dependencies_db_matches(&self, dependencies: &Dependencies, schema: &Schema, matching_mode: &MatchingMode, timings: &Timings) -> Vec<TableMatches> {
+        let table_names = match self.source {
+            SearchSource::GameFiles => dependencies.vanilla_loose_tables().keys().chain(dependencies.vanilla_tables().keys()).collect::<BTreeSet<_>>(),
+            SearchSource::ParentFiles => dependencies.parent_tables().keys().collect::<BTreeSet<_>>(),
+            SearchSource::Pack |
+            SearchSource::AssKitFiles => BTreeSet::new(),
+        };
+
+        let reference_targets = if self.key_and_reference_columns_only { schema.referenced_columns() } else { HashSet::new() };
+        table_names.into_par_iter()
+            .filter(|table_name| self.table_names.as_ref().map_or(true, |table_names| table_names.contains(*table_name)))
+            .flat_map(|table_name| dependencies.decoded_tables(schema, &self.source, table_name))
+            .filter_map(|(path, table)| {
+                let _timer = timings.start("search", format_args!("{:?}", FileType::DB));
+                let result = if self.key_and_reference_columns_only {
+                    TableMatches::search_key_and_reference_columns(&table, &path, &self.pattern, self.case_sensitive, matching_mode, &reference_targets)
+                } else {
+                    table.search(&path, &self.pattern, self.case_sensitive, matching_mode)
+                };
+
+                if !result.matches().is_empty() {
+                    Some(result)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// This function removes from the provided list the DB tables not in the provided list of table names, if any.
+    ///
+    /// An empty list of table names means no filtering.
+    fn retain_table_names(files: &mut Vec<&mut RFile>, table_names: &Option<BTreeSet<String>>) {
+        if let Some(table_names) = table_names.as_ref().filter(|table_names| !table_names.is_empty()) {
+            files.retain(|file| file.file_type() != FileType::DB || file.db_table_name_from_path().map_or(false, |table_name| table_names.contains(table_name)));
+        }
+    }
+
     /// This function clears the Global Search result's data, and reset the UI for it.
     pub fn clear(&mut self) {
         *self = Self::default();
     }
 
+    /// This function returns an iterator over all the matches of the last search, with the source they were found in.
+    pub fn iter_all_matches(&self) -> impl Iterator<Item = UnifiedMatch> + '_ {
+        self.matches.iter_all().map(|mut x| {
+            x.source = Some(self.source.clone());
+            x
+        })
+    }
+
+    /// This function builds the matching mode to use for this search.
+    ///
+    /// If we want to use regex and the pattern is invalid, we use normal pattern instead of Regex.
+    /// Whole word searches use the regex built by [MatchingMode::whole_word].
+    fn matching_mode(&self) -> MatchingMode {
+        if self.use_regex {
+            match RegexBuilder::new(&self.pattern).case_insensitive(!self.case_sensitive).build() {
+                Ok(regex) => MatchingMode::Regex(regex),
+                Err(_) => MatchingMode::Pattern(RegexBuilder::new(&format!("(?i){}", regex::escape(&self.pattern)))
+                    .case_insensitive(!self.case_sensitive)
+                    .build()
+                    .ok()
+                ),
+            }
+        } else if self.search_on.whole_word {
+            MatchingMode::whole_word(&self.pattern, self.case_sensitive)
+        } else {
+            match RegexBuilder::new(&format!("(?i){}", regex::escape(&self.pattern))).case_insensitive(!self.case_sensitive).build() {
+                Ok(regex) => MatchingMode::Pattern(Some(regex)),
+                Err(_) => MatchingMode::Pattern(None),
+            }
+        }
+    }
+
+    /// This function returns the text to use when replacing.
+    ///
+    /// Whole word searches use regex internally, so `$` is escaped to avoid it being expanded as a reference to a capture group.
+    fn replace_text_for_matching_mode(&self) -> String {
+        if self.search_on.whole_word && !self.use_regex {
+            self.replace_text.replace('$', "$$")
+        } else {
+            self.replace_text.to_owned()
+        }
+    }
+
+    /// This function returns, for each text-based match of this search, the matched text and the text it'll be replaced with.
+    ///
+    /// This doesn't change anything, neither the matches nor the files. Matches on binary data (Unknown and RigidModel files)
+    /// and on the schema are not included, as there's no text to preview there.
+    pub fn replace_preview(&self) -> Vec<ReplacePreviewItem> {
+        let mut items = vec![];
+        let matching_mode = self.matching_mode();
+        let replace_text = self.replace_text_for_matching_mode();
+        self.visit_replaceable_matches(|path, location, column, start, end, text| {
+            if let Some(original) = text.get(start..end) {
+                if let Some(replacement) = replacement_text(&matching_mode, &replace_text, text, start, end) {
+                    items.push(ReplacePreviewItem {
+                        path: path.to_owned(),
+                        location,
+                        column,
+                        value: text.to_owned(),
+                        start,
+                        end,
+                        original: original.to_owned(),
+                        replacement,
+                    });
+                }
+            }
+        });
+
+        items
+    }
+
+    /// This function returns, for each text-based match of this search, the full value containing it before and after the replace.
+    ///
+    /// Each item is (path, row, column, old value, new value). For tables, row and column are the ones of the cell. For texts, the row is the line
+    /// and the column the byte where the match starts within it. For files made of entries, the row is the entry. The new value is the old one with
+    /// the replacement from [GlobalSearch::replace_preview] applied, without changing the Pack. Binary and schema matches are not included,
+    /// and neither are matches the replace leaves unchanged.
+    pub fn preview_replacements(&self) -> Vec<(ContainerPath, usize, usize, String, String)> {
+        self.replace_preview()
+            .into_iter()
+            .filter_map(|item| {
+                let row = match item.location {
+                    ReplacePreviewLocation::Cell(row, _) => row as usize,
+                    ReplacePreviewLocation::Line(row) => row as usize,
+                    ReplacePreviewLocation::Entry(entry) => entry,
+                    ReplacePreviewLocation::File => 0,
+                };
+
+                let mut new_value = item.value.to_owned();
+                new_value.replace_range(item.start..item.end, &item.replacement);
+                if new_value != item.value {
+                    Some((ContainerPath::File(item.path), row, item.column, item.value, new_value))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// This function calls `visit` for each text-based match of this search that can be replaced, with its path, location, column, start, end and the text containing it.
+    ///
+    /// The column is the one of the cell for tables, the start of the match for texts, and 0 for anything else.
+    fn visit_replaceable_matches(&self, mut visit: impl FnMut(&str, ReplacePreviewLocation, usize, usize, usize, &str)) {
+
+        // Mirror the replace logic: nothing gets replaced without pattern or outside the open Pack.
+        if self.pattern.is_empty() || self.source != SearchSource::Pack {
+            return;
+        }
+
+        for file_matches in self.matches.anim_fragment_battle() {
+            for m in file_matches.matches() {
+                let location = match m.entry() {
+                    Some(entry) => ReplacePreviewLocation::Entry(entry.0),
+                    None => ReplacePreviewLocation::File,
+                };
+                visit(file_matches.path(), location, 0, *m.start(), *m.end(), m.text());
+            }
+        }
+
+        for file_matches in self.matches.atlas() {
+            for m in file_matches.matches() {
+                visit(file_matches.path(), ReplacePreviewLocation::Cell(*m.row_number(), m.column_name().to_owned()), *m.column_number() as usize, *m.start(), *m.end(), m.text());
+            }
+        }
+
+        for file_matches in self.matches.db().iter().chain(self.matches.loc().iter()) {
+            for m in file_matches.matches() {
+                visit(file_matches.path(), ReplacePreviewLocation::Cell(*m.row_number(), m.column_name().to_owned()), *m.column_number() as usize, *m.start(), *m.end(), m.text());
+            }
+        }
+
+        for file_matches in self.matches.portrait_settings() {
+            for m in file_matches.matches() {
+                visit(file_matches.path(), ReplacePreviewLocation::Entry(*m.entry()), 0, *m.start(), *m.end(), m.text());
+            }
+        }
+
+        for file_matches in self.matches.text() {
+            for m in file_matches.matches() {
+                visit(file_matches.path(), ReplacePreviewLocation::Line(*m.row()), *m.start(), *m.start(), *m.end(), m.text());
+            }
+        }
+
+        for file_matches in self.matches.unit_variant() {
+            for m in file_matches.matches() {
+                visit(file_matches.path(), ReplacePreviewLocation::Entry(*m.entry()), 0, *m.start(), *m.end(), m.text());
+            }
+        }
+    }
+
     /// This function checks if it's possible to replace the provided matches.
+    ///
+    /// Matches of hex searches are never replaceable, as the replace text is not a byte pattern.
     pub fn replace_possible(&self, matches: &[MatchHolder]) -> Result<()> {
+        if self.use_hex && !matches.is_empty() {
+            return Err(RLibError::GlobalSearchReplaceHexNotSupported);
+        }
+
         let patterns_same_lenght = self.pattern.len() == self.replace_text.len();
 
         // Error out if at least one of the matches requires special conditions.
@@ -377,13 +807,13 @@ impl GlobalSearch {
             MatchHolder::MatchedCombat(_) => false,
             MatchHolder::Pack(_) => false,
             MatchHolder::PortraitSettings(_) => false,
-            MatchHolder::RigidModel(_) => self.use_regex || !patterns_same_lenght,
+            MatchHolder::RigidModel(_) => self.use_regex || self.search_on.whole_word || !patterns_same_lenght,
             MatchHolder::Schema(_) => false,
             MatchHolder::SoundBank(_) => false,
             MatchHolder::Text(_) => false,
             MatchHolder::Uic(_) => false,
             MatchHolder::UnitVariant(_) => false,
-            MatchHolder::Unknown(_) => self.use_regex || !patterns_same_lenght,
+            MatchHolder::Unknown(_) => self.use_regex || self.search_on.whole_word || !patterns_same_lenght,
             MatchHolder::Video(_) => false,
         }) {
             Err(RLibError::GlobalSearchReplaceRequiresSameLenghtAndNotRegex)
@@ -411,27 +841,18 @@ impl GlobalSearch {
         // Make sure we can actually do the replacements.
         self.replace_possible(matches)?;
 
+        // Files marked as read-only cannot be edited, so refuse to replace anything if one of them is affected.
+        for path in matches.iter().filter_map(|m| m.path()) {
+            pack.check_path_not_readonly(path)?;
+        }
+
         let game_key = self.game_key.to_owned();
         let mut extra_data = DecodeableExtraData::default();
         extra_data.set_game_key(Some(&game_key));
         let extra_data = Some(extra_data);
 
-        // If we want to use regex and the pattern is invalid, use normal pattern instead of Regex.
-        let matching_mode = if self.use_regex {
-            match RegexBuilder::new(&self.pattern).case_insensitive(!self.case_sensitive).build() {
-                Ok(regex) => MatchingMode::Regex(regex),
-                Err(_) => MatchingMode::Pattern(RegexBuilder::new(&format!("(?i){}", regex::escape(&self.pattern)))
-                    .case_insensitive(!self.case_sensitive)
-                    .build()
-                    .ok()
-                ),
-            }
-        } else {
-            match RegexBuilder::new(&format!("(?i){}", regex::escape(&self.pattern))).case_insensitive(!self.case_sensitive).build() {
-                Ok(regex) => MatchingMode::Pattern(Some(regex)),
-                Err(_) => MatchingMode::Pattern(None),
-            }
-        };
+        let matching_mode = self.matching_mode();
+        let replace_text = self.replace_text_for_matching_mode();
 
         // Just replace all the provided matches, one by one.
         for match_file in matches {
@@ -446,7 +867,7 @@ impl GlobalSearch {
                         let _ = file.decode(&extra_data, true, false);
                         if let Ok(decoded) = file.decoded_mut() {
                             let edited = match decoded {
-                                RFileDecoded::AnimFragmentBattle(table) => table.replace(&self.pattern, &self.replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                RFileDecoded::AnimFragmentBattle(table) => table.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
                                 _ => unimplemented!(),
                             };
 
@@ -457,7 +878,25 @@ impl GlobalSearch {
                     }
                 },
                 MatchHolder::AnimPack(_) => continue,
-                MatchHolder::AnimsTable(_) => continue,
+                MatchHolder::AnimsTable(search_matches) => {
+                    let container_path = ContainerPath::File(search_matches.path().to_string());
+                    let mut file = pack.files_by_path_mut(&container_path, false);
+                    if let Some(file) = file.get_mut(0) {
+
+                        // Make sure it has been decoded.
+                        let _ = file.decode(&None, true, false);
+                        if let Ok(decoded) = file.decoded_mut() {
+                            let edited = match decoded {
+                                RFileDecoded::AnimsTable(data) => data.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                _ => unimplemented!(),
+                            };
+
+                            if edited {
+                                edited_paths.push(container_path);
+                            }
+                        }
+                    }
+                },
                 MatchHolder::Atlas(search_matches) => {
                     let container_path = ContainerPath::File(search_matches.path().to_string());
                     let mut file = pack.files_by_path_mut(&container_path, false);
@@ -467,7 +906,7 @@ impl GlobalSearch {
                         let _ = file.decode(&None, true, false);
                         if let Ok(decoded) = file.decoded_mut() {
                             let edited = match decoded {
-                                RFileDecoded::Atlas(table) => table.replace(&self.pattern, &self.replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                RFileDecoded::Atlas(table) => table.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
                                 _ => unimplemented!(),
                             };
 
@@ -487,7 +926,7 @@ impl GlobalSearch {
                     if let Some(file) = file.get_mut(0) {
                         if let Ok(decoded) = file.decoded_mut() {
                             let edited = match decoded {
-                                RFileDecoded::DB(table) => table.replace(&self.pattern, &self.replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                RFileDecoded::DB(table) => table.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
                                 _ => unimplemented!(),
                             };
 
@@ -507,7 +946,7 @@ impl GlobalSearch {
                     if let Some(file) = file.get_mut(0) {
                         if let Ok(decoded) = file.decoded_mut() {
                             let edited = match decoded {
-                                RFileDecoded::Loc(table) => table.replace(&self.pattern, &self.replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                RFileDecoded::Loc(table) => table.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
                                 _ => unimplemented!(),
                             };
 
@@ -518,7 +957,25 @@ impl GlobalSearch {
                     }
                 },
 
-                MatchHolder::MatchedCombat(_) => continue,
+                MatchHolder::MatchedCombat(search_matches) => {
+                    let container_path = ContainerPath::File(search_matches.path().to_string());
+                    let mut file = pack.files_by_path_mut(&container_path, false);
+                    if let Some(file) = file.get_mut(0) {
+
+                        // Make sure it has been decoded.
+                        let _ = file.decode(&extra_data, true, false);
+                        if let Ok(decoded) = file.decoded_mut() {
+                            let edited = match decoded {
+                                RFileDecoded::MatchedCombat(data) => data.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                _ => unimplemented!(),
+                            };
+
+                            if edited {
+                                edited_paths.push(container_path);
+                            }
+                        }
+                    }
+                },
                 MatchHolder::Pack(_) => continue,
                 MatchHolder::PortraitSettings(search_matches) => {
                     let container_path = ContainerPath::File(search_matches.path().to_string());
@@ -529,7 +986,7 @@ impl GlobalSearch {
                         let _ = file.decode(&None, true, false);
                         if let Ok(decoded) = file.decoded_mut() {
                             let edited = match decoded {
-                                RFileDecoded::PortraitSettings(data) => data.replace(&self.pattern, &self.replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                RFileDecoded::PortraitSettings(data) => data.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
                                 _ => unimplemented!(),
                             };
 
@@ -549,7 +1006,7 @@ impl GlobalSearch {
                         let _ = file.decode(&None, true, false);
                         if let Ok(decoded) = file.decoded_mut() {
                             let edited = match decoded {
-                                RFileDecoded::RigidModel(data) => data.replace(&self.pattern, &self.replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                RFileDecoded::RigidModel(data) => data.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
                                 _ => unimplemented!(),
                             };
 
@@ -581,7 +1038,7 @@ impl GlobalSearch {
                             });
 
                             let edited = match decoded {
-                                RFileDecoded::Text(text) => text.replace(&self.pattern, &self.replace_text, self.case_sensitive, &matching_mode, &search_matches),
+                                RFileDecoded::Text(text) => text.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, &search_matches),
                                 _ => unimplemented!(),
                             };
 
@@ -602,7 +1059,7 @@ impl GlobalSearch {
                         let _ = file.decode(&None, true, false);
                         if let Ok(decoded) = file.decoded_mut() {
                             let edited = match decoded {
-                                RFileDecoded::UnitVariant(data) => data.replace(&self.pattern, &self.replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                RFileDecoded::UnitVariant(data) => data.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
                                 _ => unimplemented!(),
                             };
 
@@ -622,7 +1079,7 @@ impl GlobalSearch {
                         let _ = file.decode(&None, true, false);
                         if let Ok(decoded) = file.decoded_mut() {
                             let edited = match decoded {
-                                RFileDecoded::Unknown(data) => data.replace(&self.pattern, &self.replace_text, self.case_sensitive, &matching_mode, search_matches),
+                                RFileDecoded::Unknown(data) => data.replace(&self.pattern, &replace_text, self.case_sensitive, &matching_mode, search_matches),
                                 _ => unimplemented!(),
                             };
 
@@ -652,16 +1109,16 @@ impl GlobalSearch {
         matches.extend(self.matches.anim.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.anim_fragment_battle.iter().map(|x| MatchHolder::AnimFragmentBattle(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.anim_pack.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
-        matches.extend(self.matches.anims_table.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
+        matches.extend(self.matches.anims_table.iter().map(|x| MatchHolder::AnimsTable(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.atlas.iter().map(|x| MatchHolder::Atlas(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.audio.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.bmd.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.db.iter().map(|x| MatchHolder::Db(x.clone())).collect::<Vec<_>>());
-        matches.extend(self.matches.esf.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
+        matches.extend(self.matches.esf.iter().map(|x| MatchHolder::Esf(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.group_formations.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.image.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.loc.iter().map(|x| MatchHolder::Loc(x.clone())).collect::<Vec<_>>());
-        matches.extend(self.matches.matched_combat.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
+        matches.extend(self.matches.matched_combat.iter().map(|x| MatchHolder::MatchedCombat(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.pack.iter().map(|x| MatchHolder::Unknown(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.portrait_settings.iter().map(|x| MatchHolder::PortraitSettings(x.clone())).collect::<Vec<_>>());
         matches.extend(self.matches.rigid_model.iter().map(|x| MatchHolder::RigidModel(x.clone())).collect::<Vec<_>>());
@@ -674,6 +1131,88 @@ impl GlobalSearch {
 
         self.replace(game_info, schema, pack, dependencies, &matches)
     }
+
+    /// This function applies all the steps of a [Recipe] to the Pack, in order, returning all the paths edited by any of them.
+    ///
+    /// Each step performs a search over the Pack with its own parameters, followed by a [replace_all](Self::replace_all).
+    /// The rest of the search settings (game, decoding limits,...) are taken from this `GlobalSearch`. The steps run on a copy of it,
+    /// so its own settings and matches are left untouched.
+    pub fn apply_recipe(&self, recipe: &Recipe, game_info: &GameInfo, schema: &Schema, pack: &mut Pack, dependencies: &mut Dependencies) -> Result<Vec<ContainerPath>> {
+        let mut search = self.clone();
+        let mut edited_paths = vec![];
+
+        for step in recipe.steps() {
+            search.pattern = step.pattern().to_owned();
+            search.replace_text = step.replace_text().to_owned();
+            search.case_sensitive = *step.case_sensitive();
+            search.use_regex = *step.use_regex();
+            search.search_on = step.search_on().clone();
+            search.source = SearchSource::Pack;
+
+            // Recipes are text replacements, so hex patterns are not supported.
+            search.use_hex = false;
+
+            search.search(game_info, schema, pack, dependencies, &[]);
+
+            for path in search.replace_all(game_info, schema, pack, dependencies)? {
+                if !edited_paths.contains(&path) {
+                    edited_paths.push(path);
+                }
+            }
+        }
+
+        Ok(edited_paths)
+    }
+}
+
+impl MatchHolder {
+
+    /// This function returns the path of the file the matches belong to, if they belong to a file.
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            MatchHolder::Anim(matches) |
+            MatchHolder::AnimPack(matches) |
+            MatchHolder::Audio(matches) |
+            MatchHolder::Bmd(matches) |
+            MatchHolder::GroupFormations(matches) |
+            MatchHolder::Image(matches) |
+            MatchHolder::Pack(matches) |
+            MatchHolder::SoundBank(matches) |
+            MatchHolder::Uic(matches) |
+            MatchHolder::Unknown(matches) |
+            MatchHolder::Video(matches) => Some(matches.path()),
+            MatchHolder::AnimFragmentBattle(matches) => Some(matches.path()),
+            MatchHolder::Atlas(matches) => Some(matches.path()),
+            MatchHolder::AnimsTable(matches) |
+            MatchHolder::Db(matches) |
+            MatchHolder::Loc(matches) |
+            MatchHolder::MatchedCombat(matches) => Some(matches.path()),
+            MatchHolder::Esf(matches) => Some(matches.path()),
+            MatchHolder::PortraitSettings(matches) => Some(matches.path()),
+            MatchHolder::RigidModel(matches) => Some(matches.path()),
+            MatchHolder::Text(matches) => Some(matches.path()),
+            MatchHolder::UnitVariant(matches) => Some(matches.path()),
+            MatchHolder::Schema(_) => None,
+        }
+    }
+}
+
+impl MatchingMode {
+
+    /// This function builds the matching mode for a whole word search of the provided pattern.
+    ///
+    /// Word boundaries are only added on the sides where the pattern starts or ends with a word character, so patterns like `.png`
+    /// or `unit_` still match when the non-word character is next to a word. If the regex fails to build, we use normal pattern instead.
+    pub fn whole_word(pattern: &str, case_sensitive: bool) -> Self {
+        let is_word_char = |character: char| character.is_alphanumeric() || character == '_';
+        let start = if pattern.chars().next().map(is_word_char).unwrap_or(false) { r"\b" } else { "" };
+        let end = if pattern.chars().last().map(is_word_char).unwrap_or(false) { r"\b" } else { "" };
+
+        match RegexBuilder::new(&format!("{start}{}{end}", regex::escape(pattern))).case_insensitive(!case_sensitive).build() {
+            Ok(regex) => MatchingMode::Regex(regex),
+            Err(_) => MatchingMode::Pattern(None),
+        }
+    }
 }
 
 impl SearchOn {
@@ -708,6 +1247,16 @@ impl SearchOn {
 }
 
 impl Matches {
+
+    /// This function groups the matches on tables and table-like files by row, leaving one match per row with matches.
+    pub fn group_table_matches_by_row(&mut self) {
+        self.anims_table.iter_mut()
+            .chain(self.db.iter_mut())
+            .chain(self.loc.iter_mut())
+            .chain(self.matched_combat.iter_mut())
+            .for_each(|table_matches| table_matches.group_by_row());
+    }
+
     pub fn retain_paths(&mut self, paths: &[String]) {
         for path in paths {
             self.anim.retain(|x| x.path() != path);
@@ -732,317 +1281,418 @@ impl Matches {
             self.unit_variant.retain(|x| x.path() != path);
             self.unknown.retain(|x| x.path() != path);
             self.video.retain(|x| x.path() != path);
+            self.skipped.retain(|x| x != path);
         }
     }
 
-    pub fn find_matches(&mut self, pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode, search_on: &SearchOn, files: &mut Vec<&mut RFile>, schema: &Schema, extra_data: Option<DecodeableExtraData>) {
-        let matches = files.par_iter_mut()
+    /// This function returns an iterator over all the matches, in a common shape regardless of the type of file they're in.
+    ///
+    /// Matches are returned grouped by type: text, table-like, other decoded files, binary files, and schema matches last.
+    pub fn iter_all(&self) -> impl Iterator<Item = UnifiedMatch> + '_ {
+        let unified = |file_type: FileType, path: &str, location: MatchLocation, text: &str, start: usize, end: usize| UnifiedMatch {
+            file_type: Some(file_type),
+            path: path.to_owned(),
+            source: None,
+            location,
+            text: text.to_owned(),
+            start,
+            end,
+        };
+
+        let text = self.text.iter().flat_map(move |file| file.matches().iter()
+            .map(move |x| unified(FileType::Text, file.path(), MatchLocation::Line(*x.row()), x.text(), *x.start(), *x.end())));
+
+        let tables = self.db.iter().map(|file| (FileType::DB, file))
+            .chain(self.loc.iter().map(|file| (FileType::Loc, file)))
+            .chain(self.anims_table.iter().map(|file| (FileType::AnimsTable, file)))
+            .chain(self.matched_combat.iter().map(|file| (FileType::MatchedCombat, file)))
+            .flat_map(move |(file_type, file)| file.matches().iter()
+                .map(move |x| unified(file_type, file.path(), MatchLocation::Cell(*x.row_number(), x.column_name().to_owned()), x.text(), *x.start(), *x.end())));
+
+        let atlas = self.atlas.iter().flat_map(move |file| file.matches().iter()
+            .map(move |x| unified(FileType::Atlas, file.path(), MatchLocation::Cell(*x.row_number(), x.column_name().to_owned()), x.text(), *x.start(), *x.end())));
+
+        let anim_fragment_battle = self.anim_fragment_battle.iter().flat_map(move |file| file.matches().iter()
+            .map(move |x| {
+                let location = match x.entry() {
+                    Some((entry, _, _, _, _, _, _)) => MatchLocation::Entry(*entry),
+                    None => MatchLocation::Header,
+                };
+                unified(FileType::AnimFragmentBattle, file.path(), location, x.text(), *x.start(), *x.end())
+            }));
+
+        let esf = self.esf.iter().flat_map(move |file| file.matches().iter()
+            .map(move |x| unified(FileType::ESF, file.path(), MatchLocation::Node(x.node_path().to_owned()), x.text(), *x.start(), *x.end())));
+
+        let portrait_settings = self.portrait_settings.iter().flat_map(move |file| file.matches().iter()
+            .map(move |x| unified(FileType::PortraitSettings, file.path(), MatchLocation::Entry(*x.entry()), x.text(), *x.start(), *x.end())));
+
+        let unit_variant = self.unit_variant.iter().flat_map(move |file| file.matches().iter()
+            .map(move |x| unified(FileType::UnitVariant, file.path(), MatchLocation::Entry(*x.entry()), x.text(), *x.start(), *x.end())));
+
+        let rigid_model = self.rigid_model.iter().flat_map(move |file| file.matches().iter()
+            .map(move |x| unified(FileType::RigidModel, file.path(), MatchLocation::Offset(*x.pos(), *x.len()), "", 0, 0)));
+
+        let unknown = [
+            (FileType::Anim, &self.anim),
+            (FileType::AnimPack, &self.anim_pack),
+            (FileType::Audio, &self.audio),
+            (FileType::BMD, &self.bmd),
+            (FileType::GroupFormations, &self.group_formations),
+            (FileType::Image, &self.image),
+            (FileType::Pack, &self.pack),
+            (FileType::SoundBank, &self.sound_bank),
+            (FileType::UIC, &self.uic),
+            (FileType::Unknown, &self.unknown),
+            (FileType::Video, &self.video),
+        ].into_iter()
+            .flat_map(|(file_type, files)| files.iter().map(move |file| (file_type, file)))
+            .flat_map(move |(file_type, file)| file.matches().iter()
+                .map(move |x| unified(file_type, file.path(), MatchLocation::Offset(*x.pos(), *x.len()), "", 0, 0)));
+
+        let schema = self.schema.matches().iter().map(|x| UnifiedMatch {
+            file_type: None,
+            path: x.table_name().to_owned(),
+            source: None,
+            location: MatchLocation::SchemaColumn(*x.version(), *x.column(), x.column_name().to_owned()),
+            text: x.column_name().to_owned(),
+            start: 0,
+            end: x.column_name().len(),
+        });
+
+        text.chain(tables)
+            .chain(atlas)
+            .chain(anim_fragment_battle)
+            .chain(esf)
+            .chain(portrait_settings)
+            .chain(unit_variant)
+            .chain(rigid_model)
+            .chain(unknown)
+            .chain(schema)
+    }
+
+    /// This function renders the matches as a self-contained HTML report, grouped by file type and path.
+    ///
+    /// All matched content is escaped, and the styling is inlined, so the report can be opened anywhere without extra files.
+    pub fn to_html(&self) -> String {
+
+        // Group the matches by section and path, keeping the order in which they come. Schema matches are not tied to a file,
+        // so they're grouped by table.
+        let mut grouped: Vec<(String, Vec<(String, Vec<UnifiedMatch>)>)> = vec![];
+        for x in self.iter_all() {
+            let title = match x.file_type() {
+                Some(file_type) => file_type.to_string(),
+                None => "Schema".to_owned(),
+            };
+
+            let section = match grouped.iter().position(|(section_title, _)| *section_title == title) {
+                Some(section) => section,
+                None => {
+                    grouped.push((title, vec![]));
+                    grouped.len() - 1
+                }
+            };
+
+            let files = &mut grouped[section].1;
+            match files.iter_mut().find(|(path, _)| path == x.path()) {
+                Some((_, matches)) => matches.push(x),
+                None => files.push((x.path().to_owned(), vec![x])),
+            }
+        }
+
+        let mut sections = grouped.into_iter()
+            .map(|(title, files)| Self::html_section(&title, &files))
+            .collect::<String>();
+
+        if !self.skipped.is_empty() {
+            sections.push_str(&format!("<h2>Skipped ({})</h2>\n<ul>\n", self.skipped.len()));
+            for path in &self.skipped {
+                sections.push_str(&format!("<li><code>{}</code></li>\n", html_escape(path)));
+            }
+            sections.push_str("</ul>\n");
+        }
+
+        if sections.is_empty() {
+            sections.push_str("<p>No matches found.</p>\n");
+        }
+
+        format!(r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Search Results</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+details {{ margin: 0.25em 0 0.25em 1em; }}
+summary {{ cursor: pointer; }}
+table {{ border-collapse: collapse; margin: 0.5em 0 1em 1em; }}
+td, th {{ border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: left; }}
+td.text {{ font-family: monospace; white-space: pre-wrap; }}
+mark {{ background-color: #ffe066; }}
+.count {{ color: #777; }}
+</style>
+</head>
+<body>
+<h1>Search Results</h1>
+{sections}</body>
+</html>
+"#)
+    }
+
+    /// This function renders a file type section of the HTML report, with the matches of each of its files.
+    fn html_section(title: &str, files: &[(String, Vec<UnifiedMatch>)]) -> String {
+        let count = files.iter().map(|(_, matches)| matches.len()).sum::<usize>();
+        let mut html = format!("<h2>{} <span class=\"count\">({} matches in {} files)</span></h2>\n", html_escape(title), count, files.len());
+
+        for (path, matches) in files {
+            html.push_str(&format!("<details open>\n<summary><code>{}</code> <span class=\"count\">({})</span></summary>\n<table>\n", html_escape(path), matches.len()));
+            for x in matches {
+                let (text, start, end) = (x.text(), *x.start(), *x.end());
+                let text = match (text.get(..start), text.get(start..end), text.get(end..)) {
+                    (Some(before), Some(matched), Some(after)) if start < end => format!("{}<mark>{}</mark>{}", html_escape(before), html_escape(matched), html_escape(after)),
+                    _ => html_escape(text),
+                };
+
+                html.push_str(&format!("<tr><td>{}</td><td class=\"text\">{}</td></tr>\n", html_escape(&Self::html_location(x.location())), text));
+            }
+            html.push_str("</table>\n</details>\n");
+        }
+
+        html
+    }
+
+    /// This function returns the human-readable location of a match for the HTML report, with 1-based rows, lines, entries and columns.
+    fn html_location(location: &MatchLocation) -> String {
+        match location {
+            MatchLocation::Cell(row, column_name) => format!("Row {}, Column {}", row + 1, column_name),
+            MatchLocation::Line(row) => format!("Line {}", row + 1),
+            MatchLocation::Entry(entry) => format!("Entry {}", entry + 1),
+            MatchLocation::Header => "Header".to_owned(),
+            MatchLocation::Node(node_path) => format!("Node {node_path}"),
+            MatchLocation::Offset(pos, len) => format!("Offset {pos:#x}, {len} bytes"),
+            MatchLocation::SchemaColumn(version, column, _) => format!("Version {}, Column {}", version, column + 1),
+        }
+    }
+
+    /// This function renders the matches as plain text lines, one per match, for piping them into command-line tools.
+    ///
+    /// The format of each line depends on the kind of file the match is in:
+    /// - Text files: `path:line:column:matched_text`, like `grep`/`ripgrep`, with 1-based lines and columns.
+    /// - Table-like files (DB, Loc, Atlas): `path::row::column_name::matched_text`, with 1-based rows.
+    /// - Other decoded files: `path::location::matched_text`, with the location being the entry (1-based) or node the match is in.
+    /// - Binary files: `path@byte_offset:byte_length`.
+    /// - Schema: `schema::table_name::version::column_name`.
+    ///
+    /// Line jumps and tabs in the matched text are escaped, so a match is never split between lines.
+    pub fn to_grep_lines(&self) -> Vec<String> {
+        self.iter_all().map(|x| {
+            let text = || Self::grep_text(x.text(), *x.start(), *x.end());
+            match x.location() {
+                MatchLocation::Line(row) => format!("{}:{}:{}:{}", x.path(), row + 1, x.start() + 1, text()),
+                MatchLocation::Cell(row, column_name) => format!("{}::{}::{}::{}", x.path(), row + 1, column_name, text()),
+                MatchLocation::Entry(entry) => format!("{}::{}::{}", x.path(), entry + 1, text()),
+                MatchLocation::Header => format!("{}::header::{}", x.path(), text()),
+                MatchLocation::Node(node_path) => format!("{}::{}::{}", x.path(), node_path, text()),
+                MatchLocation::Offset(pos, len) => format!("{}@{}:{}", x.path(), pos, len),
+                MatchLocation::SchemaColumn(version, _, column_name) => format!("schema::{}::{}::{}", x.path(), version, column_name),
+            }
+        }).collect()
+    }
+
+    /// This function returns the matched part of a text for `to_grep_lines`, or the full text if the bounds are not valid.
+    fn grep_text(text: &str, start: usize, end: usize) -> String {
+        let matched = match text.get(start..end) {
+            Some(matched) if start < end => matched,
+            _ => text,
+        };
+
+        matched.replace('\\', "\\\\").replace('\n', "\\n").replace('\t', "\\t")
+    }
+
+    /// This function removes all the matches of files, keeping the schema ones.
+    fn clear_file_matches(&mut self) {
+        let schema = std::mem::take(&mut self.schema);
+        let skipped = std::mem::take(&mut self.skipped);
+        *self = Self {
+            schema,
+            skipped,
+            ..Default::default()
+        };
+    }
+
+    /// This function adds the provided matches to the list of matches of their type.
+    fn add_match_holder(&mut self, match_holder: MatchHolder) {
+        match match_holder {
+            MatchHolder::Anim(matches) => self.anim.push(matches),
+            MatchHolder::AnimFragmentBattle(matches) => self.anim_fragment_battle.push(matches),
+            MatchHolder::AnimPack(matches) => self.anim_pack.push(matches),
+            MatchHolder::AnimsTable(matches) => self.anims_table.push(matches),
+            MatchHolder::Atlas(matches) => self.atlas.push(matches),
+            MatchHolder::Audio(matches) => self.audio.push(matches),
+            MatchHolder::Bmd(matches) => self.bmd.push(matches),
+            MatchHolder::Db(matches) => self.db.push(matches),
+            MatchHolder::Esf(matches) => self.esf.push(matches),
+            MatchHolder::GroupFormations(matches) => self.group_formations.push(matches),
+            MatchHolder::Image(matches) => self.image.push(matches),
+            MatchHolder::Loc(matches) => self.loc.push(matches),
+            MatchHolder::MatchedCombat(matches) => self.matched_combat.push(matches),
+            MatchHolder::Pack(matches) => self.pack.push(matches),
+            MatchHolder::PortraitSettings(matches) => self.portrait_settings.push(matches),
+            MatchHolder::RigidModel(matches) => self.rigid_model.push(matches),
+            MatchHolder::SoundBank(matches) => self.sound_bank.push(matches),
+            MatchHolder::Text(matches) => self.text.push(matches),
+            MatchHolder::Uic(matches) => self.uic.push(matches),
+            MatchHolder::UnitVariant(matches) => self.unit_variant.push(matches),
+            MatchHolder::Unknown(matches) => self.unknown.push(matches),
+            MatchHolder::Video(matches) => self.video.push(matches),
+            MatchHolder::Schema(matches) => self.schema = matches,
+        }
+    }
+
+    /// This function makes sure a file's data is available in decoded form for searching.
+    ///
+    /// If the file is already decoded, nothing is done here and the caller should use [RFile::decoded] directly, avoiding both a re-decode and a copy of the data.
+    /// If not, it's either decoded in place (if `keep_decoded` is true, so subsequent searches can reuse it), or decoded to a temporary copy that gets returned.
+    ///
+    /// Files bigger than `max_decode_bytes` (if it's not 0) are not decoded at all.
+    fn decode_for_search(file: &mut RFile, extra_data: &Option<DecodeableExtraData>, keep_decoded: bool, max_decode_bytes: u64, timings: &Timings) -> Option<RFileDecoded> {
+        if file.decoded().is_ok() || Self::is_too_large_to_decode(file, max_decode_bytes) {
+            return None;
+        }
+
+        let _timer = timings.start("decode", format_args!("{:?}", file.file_type()));
+        if keep_decoded {
+            let _ = file.decode(extra_data, true, false);
+            None
+        } else {
+            file.decode(extra_data, false, true).ok().flatten()
+        }
+    }
+
+    /// This function checks if a file is over the size limit for decoding. A limit of 0 means no limit.
+    fn is_too_large_to_decode(file: &RFile, max_decode_bytes: u64) -> bool {
+        max_decode_bytes > 0 && file.undecoded_size().map(|size| size > max_decode_bytes).unwrap_or(false)
+    }
+
+    /// This function searches for a byte pattern on the raw data of the unknown files provided, storing the matches as unknown matches.
+    pub fn find_hex_matches(&mut self, pattern: &[u8], search_on: &SearchOn, files: &mut Vec<&mut RFile>, keep_decoded: bool, max_decode_bytes: u64) {
+        if !search_on.unknown {
+            return;
+        }
+
+        let timings = Timings::new("find_hex_matches");
+        self.skipped = files.iter()
+            .filter(|file| file.file_type() == FileType::Unknown && Self::is_too_large_to_decode(file, max_decode_bytes))
+            .map(|file| file.path_in_container_raw().to_owned())
+            .collect::<Vec<_>>();
+
+        self.unknown = files.par_iter_mut()
+            .filter(|file| file.file_type() == FileType::Unknown)
             .filter_map(|file| {
-                if search_on.anim && file.file_type() == FileType::Anim {
-                    /*
-                    if let Ok(RFileDecoded::Anim(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((Some(result), None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.anim_fragment_battle && file.file_type() == FileType::AnimFragmentBattle {
-                    if let Ok(RFileDecoded::AnimFragmentBattle(data)) = file.decode(&extra_data, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, Some(result), None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
+                let _timer = timings.start("search", format_args!("{:?}", FileType::Unknown));
+                let decoded = Self::decode_for_search(file, &None, keep_decoded, max_decode_bytes, &timings);
+                if let Some(RFileDecoded::Unknown(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+                    let result = UnknownMatches::search_bytes(file.path_in_container_raw(), data.data(), pattern);
+                    if !result.matches().is_empty() {
+                        Some(result)
                     } else {
                         None
                     }
-                } else if search_on.anim_pack && file.file_type() == FileType::AnimPack {
-                    /*
-                    if let Ok(RFileDecoded::AnimPack(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, Some(result), None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.anims_table && file.file_type() == FileType::AnimsTable {
-                    /*
-                    if let Ok(RFileDecoded::AnimsTable(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, Some(result), None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.atlas && file.file_type() == FileType::Atlas {
-                    if let Ok(RFileDecoded::Atlas(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, Some(result), None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else if search_on.audio && file.file_type() == FileType::Audio {
-                    /*
-                    if let Ok(RFileDecoded::Audio(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, Some(result), None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.bmd && file.file_type() == FileType::BMD {
-                    /*
-                    if let Ok(RFileDecoded::BMD(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, Some(result), None, None, None, None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.db && file.file_type() == FileType::DB {
-                    if let Ok(RFileDecoded::DB(table)) = file.decoded() {
-                        let result = table.search(file.path_in_container_raw(), pattern, case_sensitive, matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, Some(result), None, None, None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else if search_on.esf && file.file_type() == FileType::ESF {
-                    /*
-                    if let Ok(RFileDecoded::ESF(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, Some(result), None, None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.group_formations && file.file_type() == FileType::GroupFormations {
-                    /*
-                    if let Ok(RFileDecoded::GroupFormations(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, Some(result), None, None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.image && file.file_type() == FileType::Image {
-                    /*
-                    if let Ok(RFileDecoded::Image(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, Some(result), None, None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.loc && file.file_type() == FileType::Loc {
-                    if let Ok(RFileDecoded::Loc(table)) = file.decoded() {
-                        let result = table.search(file.path_in_container_raw(), pattern, case_sensitive, matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, Some(result), None, None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else if search_on.matched_combat && file.file_type() == FileType::MatchedCombat {
-                    /*
-                    if let Ok(RFileDecoded::MatchedCombat(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, Some(result), None, None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.pack && file.file_type() == FileType::Pack {
-                    /*
-                    if let Ok(RFileDecoded::Pack(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, None, Some(result), None, None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.portrait_settings && file.file_type() == FileType::PortraitSettings {
-                    if let Ok(RFileDecoded::PortraitSettings(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(result), None, None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else if search_on.rigid_model && file.file_type() == FileType::RigidModel {
-                    if let Ok(RFileDecoded::RigidModel(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(result), None, None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else if search_on.sound_bank && file.file_type() == FileType::SoundBank {
-                    /*
-                    if let Ok(RFileDecoded::SoundBank(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(result), None, None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.text && file.file_type() == FileType::Text {
-                    if let Ok(RFileDecoded::Text(table)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = table.search(file.path_in_container_raw(), pattern, case_sensitive, matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(result), None, None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else if search_on.uic && file.file_type() == FileType::UIC {
-                    /*
-                    if let Ok(RFileDecoded::UIC(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(result), None, None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
-                } else if search_on.unit_variant && file.file_type() == FileType::UnitVariant {
-                    if let Ok(RFileDecoded::UnitVariant(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(result), None, None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else if search_on.unknown && file.file_type() == FileType::Unknown {
-                    if let Ok(RFileDecoded::Unknown(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(result), None))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else if search_on.video && file.file_type() == FileType::Video {
-                    /*
-                    if let Ok(RFileDecoded::Video(data)) = file.decode(&None, false, true).transpose().unwrap() {
-                        let result = data.search(file.path_in_container_raw(), pattern, case_sensitive, &matching_mode);
-                        if !result.matches().is_empty() {
-                            Some((None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(result)))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }*/
-                    None
                 } else {
                     None
                 }
+            })
+            .collect::<Vec<_>>();
+
+        timings.record("matched_files", format_args!("{:?}", FileType::Unknown), Duration::ZERO, self.unknown.len());
+        timings.log();
+    }
+
+    pub fn find_matches(&mut self, pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode, search_on: &SearchOn, files: &mut Vec<&mut RFile>, schema: &Schema, extra_data: Option<DecodeableExtraData>, keep_decoded: bool, max_decode_bytes: u64, key_and_reference_columns_only: bool) {
+
+        // Whole word searches need a regex to check the word boundaries, so build one if we got a plain pattern.
+        let whole_word_matching_mode;
+        let matching_mode = match matching_mode {
+            MatchingMode::Pattern(_) if search_on.whole_word => {
+                whole_word_matching_mode = MatchingMode::whole_word(pattern, case_sensitive);
+                &whole_word_matching_mode
             }
-        ).collect::<Vec<(
-            Option<UnknownMatches>, Option<AnimFragmentBattleMatches>, Option<UnknownMatches>, Option<UnknownMatches>, Option<AtlasMatches>, Option<UnknownMatches>, Option<UnknownMatches>, Option<TableMatches>,
-            Option<UnknownMatches>, Option<UnknownMatches>, Option<UnknownMatches>, Option<TableMatches>, Option<UnknownMatches>, Option<UnknownMatches>, Option<PortraitSettingsMatches>,
-            Option<RigidModelMatches>, Option<UnknownMatches>, Option<TextMatches>, Option<UnknownMatches>, Option<UnitVariantMatches>, Option<UnknownMatches>, Option<UnknownMatches>
-        )>>();
-
-        self.anim = matches.iter().filter_map(|x| x.0.clone()).collect::<Vec<_>>();
-        self.anim_fragment_battle = matches.iter().filter_map(|x| x.1.clone()).collect::<Vec<_>>();
-        self.anim_pack = matches.iter().filter_map(|x| x.2.clone()).collect::<Vec<_>>();
-        self.anims_table = matches.iter().filter_map(|x| x.3.clone()).collect::<Vec<_>>();
-        self.atlas = matches.iter().filter_map(|x| x.4.clone()).collect::<Vec<_>>();
-        self.audio = matches.iter().filter_map(|x| x.5.clone()).collect::<Vec<_>>();
-        self.bmd = matches.iter().filter_map(|x| x.6.clone()).collect::<Vec<_>>();
-        self.db = matches.iter().filter_map(|x| x.7.clone()).collect::<Vec<_>>();
-        self.esf = matches.iter().filter_map(|x| x.8.clone()).collect::<Vec<_>>();
-        self.group_formations = matches.iter().filter_map(|x| x.9.clone()).collect::<Vec<_>>();
-        self.image = matches.iter().filter_map(|x| x.10.clone()).collect::<Vec<_>>();
-        self.loc = matches.iter().filter_map(|x| x.11.clone()).collect::<Vec<_>>();
-        self.matched_combat = matches.iter().filter_map(|x| x.12.clone()).collect::<Vec<_>>();
-        self.pack = matches.iter().filter_map(|x| x.13.clone()).collect::<Vec<_>>();
-        self.portrait_settings = matches.iter().filter_map(|x| x.14.clone()).collect::<Vec<_>>();
-        self.rigid_model = matches.iter().filter_map(|x| x.15.clone()).collect::<Vec<_>>();
-        self.sound_bank = matches.iter().filter_map(|x| x.16.clone()).collect::<Vec<_>>();
-        self.text = matches.iter().filter_map(|x| x.17.clone()).collect::<Vec<_>>();
-        self.uic = matches.iter().filter_map(|x| x.18.clone()).collect::<Vec<_>>();
-        self.unit_variant = matches.iter().filter_map(|x| x.19.clone()).collect::<Vec<_>>();
-        self.unknown = matches.iter().filter_map(|x| x.20.clone()).collect::<Vec<_>>();
-        self.video = matches.iter().filter_map(|x| x.21.clone()).collect::<Vec<_>>();
+            _ => matching_mode,
+        };
+
+        // Only calculate the referenced columns if we need them, as it means going through the entire schema.
+        let reference_targets = if key_and_reference_columns_only && search_on.db {
+            schema.referenced_columns()
+        } else {
+            HashSet::new()
+        };
+
+        // Only used for profiling. Decoding time is included in the search time of each file.
+        let timings = Timings::new("find_matches");
+
+        // Files that need decoding to be searched are skipped if they're too big. Keep track of them so we can report them.
+        let types_decoded = search_on.types_to_search()
+            .into_iter()
+            .filter(|file_type| TYPES_DECODED_ON_SEARCH.contains(file_type))
+            .collect::<Vec<_>>();
+
+        self.skipped = files.iter()
+            .filter(|file| types_decoded.contains(&file.file_type()) && Self::is_too_large_to_decode(file, max_decode_bytes))
+            .map(|file| file.path_in_container_raw().to_owned())
+            .collect::<Vec<_>>();
+
+        // Each file type is searched by its handler in the registry. File types without a handler are not searched.
+        let types_to_search = search_on.types_to_search();
+        let context = SearchContext {
+            pattern,
+            case_sensitive,
+            matching_mode,
+            extra_data: &extra_data,
+            keep_decoded,
+            max_decode_bytes,
+            key_and_reference_columns_only,
+            reference_targets: &reference_targets,
+            timings: &timings,
+        };
+
+        let registry = SEARCH_REGISTRY.read().unwrap();
+        let matches = files.par_iter_mut()
+            .filter(|file| types_to_search.contains(&file.file_type()))
+            .filter_map(|file| {
+                let _timer = timings.start("search", format_args!("{:?}", file.file_type()));
+                registry.handler(file.file_type()).and_then(|handler| handler(file, &context))
+            })
+            .collect::<Vec<_>>();
+
+        self.clear_file_matches();
+        for match_holder in matches {
+            self.add_match_holder(match_holder);
+        }
 
         // Schema searches are a bit independant from the rest, so they're done after the full search.
         if search_on.schema {
+            let _timer = timings.start("search", "Schema");
             self.schema = schema.search("", pattern, case_sensitive, matching_mode);
         }
+
+        if timings.enabled() {
+            for (file_type, matched_files) in [
+                (FileType::AnimFragmentBattle, self.anim_fragment_battle.len()),
+                (FileType::Atlas, self.atlas.len()),
+                (FileType::DB, self.db.len()),
+                (FileType::ESF, self.esf.len()),
+                (FileType::Loc, self.loc.len()),
+                (FileType::PortraitSettings, self.portrait_settings.len()),
+                (FileType::RigidModel, self.rigid_model.len()),
+                (FileType::Text, self.text.len()),
+                (FileType::UnitVariant, self.unit_variant.len()),
+                (FileType::Unknown, self.unknown.len()),
+            ] {
+                timings.record("matched_files", format_args!("{file_type:?}"), Duration::ZERO, matched_files);
+            }
+        }
+
+        timings.log();
     }
 }
 
@@ -1056,16 +1706,67 @@ impl Default for MatchingMode {
 //                              Util functions
 //-------------------------------------------------------------------------------//
 
+/// This function parses a hex pattern (like `DE AD BE EF` or `deadbeef`) into the bytes it represents.
+///
+/// Whitespace between digits is ignored. It fails if the pattern is empty, has an odd amount of digits, or has non-hex characters.
+pub fn parse_hex_pattern(pattern: &str) -> Result<Vec<u8>> {
+    let digits = pattern.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+    if digits.is_empty() || digits.len() % 2 != 0 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(RLibError::InvalidHexPattern(pattern.to_owned()));
+    }
+
+    (0..digits.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&digits[index..index + 2], 16).map_err(|_| RLibError::InvalidHexPattern(pattern.to_owned())))
+        .collect()
+}
+
+/// This function escapes the characters with special meaning in HTML.
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+/// This function returns the text the match at `start..end` of the provided text will be replaced with. On regex mode, capture groups in the replace pattern are expanded.
+///
+/// The regex is run over the full text, so assertions like `\b` or `^` see the same context they saw during the search.
+/// Returns None if the regex doesn't match exactly at the provided range.
+fn replacement_text(matching_mode: &MatchingMode, replace_pattern: &str, text: &str, start: usize, end: usize) -> Option<String> {
+    match matching_mode {
+        MatchingMode::Regex(regex) => {
+            let captures = regex.captures_at(text, start)?;
+            let full_match = captures.get(0)?;
+            if full_match.start() != start || full_match.end() != end {
+                return None;
+            }
+
+            let mut replacement = String::new();
+            captures.expand(replace_pattern, &mut replacement);
+            Some(replacement)
+        }
+        MatchingMode::Pattern(_) => Some(replace_pattern.to_owned()),
+    }
+}
+
 fn replace_match_string(pattern: &str, replace_pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode, start: usize, end: usize, previous_data: &str, current_data: &mut String) -> bool {
 
     // Only replace if the substring is actually a valid one.
     if current_data.get(start..end).is_some() {
         match matching_mode {
-            MatchingMode::Regex(regex) => {
-                if let Some(match_regex) = regex.find(&current_data[start..end]) {
-                    if match_regex.start() == 0 && match_regex.end() == end - start {
-                        current_data.replace_range(start..end, replace_pattern);
-                    }
+            MatchingMode::Regex(_) => {
+                if let Some(replacement) = replacement_text(matching_mode, replace_pattern, current_data, start, end) {
+                    current_data.replace_range(start..end, &replacement);
                 }
             },
 
@@ -0,0 +1,110 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module with the code for search-and-replace recipes.
+//!
+//! A recipe is a list of search-and-replace steps saved to disk, so the same edits can be re-applied to a Pack in one go
+//! through [GlobalSearch::apply_recipe](super::GlobalSearch::apply_recipe).
+
+use getset::*;
+use serde_derive::{Serialize, Deserialize};
+
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use rpfm_lib::error::Result;
+
+use super::SearchOn;
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// This struct represents a list of search-and-replace steps, applied in order.
+#[derive(Default, Debug, Clone, Getters, MutGetters, Setters, Serialize, Deserialize)]
+#[getset(get = "pub", get_mut = "pub", set = "pub")]
+pub struct Recipe {
+
+    /// Name of the recipe.
+    name: String,
+
+    /// Steps of the recipe, in the order they're applied.
+    steps: Vec<RecipeStep>,
+}
+
+/// This struct represents a single search-and-replace step of a [Recipe].
+#[derive(Default, Debug, Clone, Getters, MutGetters, Setters, Serialize, Deserialize)]
+#[getset(get = "pub", get_mut = "pub", set = "pub")]
+pub struct RecipeStep {
+
+    /// Pattern to search.
+    pattern: String,
+
+    /// Pattern to use when replacing.
+    replace_text: String,
+
+    /// If the search should be *Case Sensitive*.
+    case_sensitive: bool,
+
+    /// If the search must be done using regex instead basic matching.
+    use_regex: bool,
+
+    /// In which files we should search on.
+    search_on: SearchOn,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl Recipe {
+
+    /// This function creates a new empty recipe with the provided name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// This function loads a [Recipe] to memory from a provided `.json` file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut file = BufReader::new(File::open(path)?);
+        let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
+        file.read_to_end(&mut data)?;
+        serde_json::from_slice(&data).map_err(From::from)
+    }
+
+    /// This function saves a [Recipe] from memory to a `.json` file with the provided path.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent_folder) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(serde_json::to_string_pretty(&self)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl RecipeStep {
+
+    /// This function creates a new step with the provided search parameters.
+    pub fn new(pattern: &str, replace_text: &str, case_sensitive: bool, use_regex: bool, search_on: SearchOn) -> Self {
+        Self {
+            pattern: pattern.to_owned(),
+            replace_text: replace_text.to_owned(),
+            case_sensitive,
+            use_regex,
+            search_on,
+        }
+    }
+}
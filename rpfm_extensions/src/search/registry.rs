@@ -0,0 +1,269 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module with the registry of handlers used to search on each file type.
+//!
+//! The global search doesn't know how to search on any file type by itself. Instead, it asks this registry for the handler of the file type,
+//! so new formats can be plugged in (even from other crates) through [register_search_handler].
+
+use lazy_static::lazy_static;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::RwLock;
+
+use rpfm_lib::files::{FileType, RFile, RFileDecoded};
+
+use super::{Matches, MatchHolder, SearchContext, Searchable, table::TableMatches};
+
+lazy_static! {
+
+    /// Registry used by the global search. It starts with the built-in handlers registered.
+    pub(super) static ref SEARCH_REGISTRY: RwLock<SearchRegistry> = RwLock::new(SearchRegistry::default());
+}
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Function used to search on a specific file type.
+///
+/// It receives the file to search on and the search context, and it returns the matches found, if any.
+pub type SearchHandler = Box<dyn Fn(&mut RFile, &SearchContext) -> Option<MatchHolder> + Send + Sync>;
+
+/// This struct contains the handlers used to search on each file type.
+pub struct SearchRegistry {
+    handlers: BTreeMap<FileType, SearchHandler>,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl SearchRegistry {
+
+    /// This function creates an empty registry, with no handlers registered.
+    pub fn new() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    /// This function registers a handler for the provided file type, replacing the previous one if there was one.
+    pub fn register(&mut self, file_type: FileType, handler: SearchHandler) {
+        self.handlers.insert(file_type, handler);
+    }
+
+    /// This function removes the handler of the provided file type, returning it if there was one.
+    pub fn unregister(&mut self, file_type: FileType) -> Option<SearchHandler> {
+        self.handlers.remove(&file_type)
+    }
+
+    /// This function returns the handler of the provided file type, if there is one.
+    pub fn handler(&self, file_type: FileType) -> Option<&SearchHandler> {
+        self.handlers.get(&file_type)
+    }
+
+    /// This function returns the file types with a handler registered.
+    pub fn file_types(&self) -> Vec<FileType> {
+        self.handlers.keys().copied().collect()
+    }
+}
+
+/// Default implementation of `SearchRegistry`. It contains all the built-in handlers.
+impl Default for SearchRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(FileType::AnimFragmentBattle, Box::new(search_anim_fragment_battle));
+        registry.register(FileType::AnimsTable, Box::new(search_anims_table));
+        registry.register(FileType::Atlas, Box::new(search_atlas));
+        registry.register(FileType::DB, Box::new(search_db));
+        registry.register(FileType::ESF, Box::new(search_esf));
+        registry.register(FileType::Loc, Box::new(search_loc));
+        registry.register(FileType::MatchedCombat, Box::new(search_matched_combat));
+        registry.register(FileType::PortraitSettings, Box::new(search_portrait_settings));
+        registry.register(FileType::RigidModel, Box::new(search_rigid_model));
+        registry.register(FileType::Text, Box::new(search_text));
+        registry.register(FileType::UnitVariant, Box::new(search_unit_variant));
+        registry.register(FileType::Unknown, Box::new(search_unknown));
+        registry
+    }
+}
+
+impl fmt::Debug for SearchRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchRegistry")
+            .field("file_types", &self.file_types())
+            .finish()
+    }
+}
+
+//-------------------------------------------------------------------------------//
+//                             Utility functions
+//-------------------------------------------------------------------------------//
+
+/// This function registers a handler for the provided file type in the registry used by the global search, replacing the built-in one if there was one.
+pub fn register_search_handler(file_type: FileType, handler: SearchHandler) {
+    SEARCH_REGISTRY.write().unwrap().register(file_type, handler);
+}
+
+/// This function removes the handler of the provided file type from the registry used by the global search, returning it if there was one.
+pub fn unregister_search_handler(file_type: FileType) -> Option<SearchHandler> {
+    SEARCH_REGISTRY.write().unwrap().unregister(file_type)
+}
+
+fn search_anim_fragment_battle(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, context.extra_data(), context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::AnimFragmentBattle(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::AnimFragmentBattle(result));
+        }
+    }
+
+    None
+}
+
+fn search_anims_table(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, &None, context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::AnimsTable(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::AnimsTable(result));
+        }
+    }
+
+    None
+}
+
+fn search_atlas(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, &None, context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::Atlas(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::Atlas(result));
+        }
+    }
+
+    None
+}
+
+/// DB tables are expected to be decoded before the search, so they're never decoded here.
+fn search_db(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    if let Ok(RFileDecoded::DB(table)) = file.decoded() {
+        let result = if context.key_and_reference_columns_only() {
+            TableMatches::search_key_and_reference_columns(table, file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode(), context.reference_targets())
+        } else {
+            table.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode())
+        };
+
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::Db(result));
+        }
+    }
+
+    None
+}
+
+fn search_esf(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, &None, context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::ESF(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::Esf(result));
+        }
+    }
+
+    None
+}
+
+/// Same as DB tables, Loc tables are expected to be decoded before the search.
+fn search_loc(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    if let Ok(RFileDecoded::Loc(table)) = file.decoded() {
+        let result = table.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::Loc(result));
+        }
+    }
+
+    None
+}
+
+fn search_matched_combat(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, context.extra_data(), context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::MatchedCombat(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::MatchedCombat(result));
+        }
+    }
+
+    None
+}
+
+fn search_portrait_settings(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, &None, context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::PortraitSettings(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::PortraitSettings(result));
+        }
+    }
+
+    None
+}
+
+fn search_rigid_model(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, &None, context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::RigidModel(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::RigidModel(result));
+        }
+    }
+
+    None
+}
+
+fn search_text(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, &None, context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::Text(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::Text(result));
+        }
+    }
+
+    None
+}
+
+fn search_unit_variant(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, &None, context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::UnitVariant(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::UnitVariant(result));
+        }
+    }
+
+    None
+}
+
+fn search_unknown(file: &mut RFile, context: &SearchContext) -> Option<MatchHolder> {
+    let decoded = Matches::decode_for_search(file, &None, context.keep_decoded(), context.max_decode_bytes(), context.timings());
+    if let Some(RFileDecoded::Unknown(data)) = decoded.as_ref().or_else(|| file.decoded().ok()) {
+        let result = data.search(file.path_in_container_raw(), context.pattern(), context.case_sensitive(), context.matching_mode());
+        if !result.matches().is_empty() {
+            return Some(MatchHolder::Unknown(result));
+        }
+    }
+
+    None
+}
@@ -0,0 +1,745 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the global search.
+
+use super::*;
+use super::registry::SearchRegistry;
+
+#[test]
+fn test_find_matches_skips_files_too_large() {
+    let mut small = RFile::new_from_vec(b"hello world", FileType::Text, 0, "text/small.txt");
+    let mut big = RFile::new_from_vec("hello world\n".repeat(100).as_bytes(), FileType::Text, 0, "text/big.txt");
+
+    let mut search_on = SearchOn::default();
+    search_on.set_text(true);
+
+    let mut matches = Matches::default();
+    let mut files = vec![&mut small, &mut big];
+    matches.find_matches("hello", false, &MatchingMode::Pattern(None), &search_on, &mut files, &Schema::default(), None, false, 100, false);
+
+    // The small file must be searched, and the big one skipped and reported, not decoded.
+    assert_eq!(matches.text().len(), 1);
+    assert_eq!(matches.text()[0].path(), "text/small.txt");
+    assert_eq!(matches.skipped(), &vec!["text/big.txt".to_owned()]);
+    assert!(big.decoded().is_err());
+
+    // With no limit, both files must be searched.
+    let mut matches = Matches::default();
+    let mut files = vec![&mut small, &mut big];
+    matches.find_matches("hello", false, &MatchingMode::Pattern(None), &search_on, &mut files, &Schema::default(), None, false, 0, false);
+
+    assert_eq!(matches.text().len(), 2);
+    assert!(matches.skipped().is_empty());
+
+    // Binary files decoded on search must be reported too.
+    let mut anims_table = RFile::new_from_vec(&[0; 200], FileType::AnimsTable, 0, "animations/big.bin");
+    let mut matched_combat = RFile::new_from_vec(&[0; 200], FileType::MatchedCombat, 0, "animations/matched_combat/big.bin");
+
+    let mut search_on = SearchOn::default();
+    search_on.set_anims_table(true);
+    search_on.set_matched_combat(true);
+
+    let mut matches = Matches::default();
+    let mut files = vec![&mut anims_table, &mut matched_combat];
+    matches.find_matches("hello", false, &MatchingMode::Pattern(None), &search_on, &mut files, &Schema::default(), None, false, 100, false);
+
+    assert_eq!(matches.skipped(), &vec!["animations/big.bin".to_owned(), "animations/matched_combat/big.bin".to_owned()]);
+}
+
+#[test]
+fn test_find_matches_keep_decoded() {
+    let mut search_on = SearchOn::default();
+    search_on.set_text(true);
+
+    // Without keeping the decoded data, files must be left as they were.
+    let mut file = RFile::new_from_vec(b"hello world", FileType::Text, 0, "text/test.txt");
+    let mut matches = Matches::default();
+    matches.find_matches("hello", false, &MatchingMode::Pattern(None), &search_on, &mut vec![&mut file], &Schema::default(), None, false, 0, false);
+    assert_eq!(matches.text().len(), 1);
+    assert!(file.decoded().is_err());
+
+    // Keeping it, the file must stay decoded, and later searches must reuse it and return the same matches.
+    let mut matches = Matches::default();
+    matches.find_matches("hello", false, &MatchingMode::Pattern(None), &search_on, &mut vec![&mut file], &Schema::default(), None, true, 0, false);
+    assert_eq!(matches.text().len(), 1);
+    assert!(file.decoded().is_ok());
+
+    let mut matches = Matches::default();
+    matches.find_matches("hello", false, &MatchingMode::Pattern(None), &search_on, &mut vec![&mut file], &Schema::default(), None, false, 0, false);
+    assert_eq!(matches.text().len(), 1);
+    assert_eq!(matches.text()[0].matches().len(), 1);
+}
+
+/// Measures the time of repeated searches with and without keeping the decoded files.
+///
+/// Run it with `cargo test --release -p rpfm_extensions bench_find_matches_repeated_search -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn bench_find_matches_repeated_search() {
+    const FILES: usize = 2_000;
+    const SEARCHES: usize = 5;
+
+    let mut search_on = SearchOn::default();
+    search_on.set_text(true);
+
+    let contents = "unit_key, some text to search on\n".repeat(200);
+    let new_files = || (0..FILES)
+        .map(|index| RFile::new_from_vec(contents.as_bytes(), FileType::Text, 0, &format!("text/{index}.txt")))
+        .collect::<Vec<_>>();
+
+    for keep_decoded in [false, true] {
+        let mut files = new_files();
+        let mut times = vec![];
+        for _ in 0..SEARCHES {
+            let start = std::time::Instant::now();
+            let mut matches = Matches::default();
+            matches.find_matches("unit_key", false, &MatchingMode::Pattern(None), &search_on, &mut files.iter_mut().collect(), &Schema::default(), None, keep_decoded, 0, false);
+            times.push(start.elapsed());
+            assert_eq!(matches.text().len(), FILES);
+        }
+
+        println!("keep_decoded: {keep_decoded}, first search: {:?}, following searches (avg): {:?}", times[0], times[1..].iter().sum::<std::time::Duration>() / (SEARCHES - 1) as u32);
+    }
+}
+
+#[test]
+fn test_parse_hex_pattern() {
+    assert_eq!(parse_hex_pattern("DE AD BE EF").unwrap(), vec![0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(parse_hex_pattern("deadbeef").unwrap(), vec![0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(parse_hex_pattern(" 0a0B ").unwrap(), vec![0x0A, 0x0B]);
+
+    assert!(parse_hex_pattern("").is_err());
+    assert!(parse_hex_pattern("DEA").is_err());
+    assert!(parse_hex_pattern("DE AD BE EG").is_err());
+    assert!(parse_hex_pattern("+F").is_err());
+}
+
+#[test]
+fn test_find_hex_matches() {
+    let mut file = RFile::new_from_vec(&[0x00, 0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0xDE, 0xAD, 0xBE, 0xEF], FileType::Unknown, 0, "unknown/file.bin");
+
+    let mut search_on = SearchOn::default();
+    search_on.set_unknown(true);
+
+    let mut matches = Matches::default();
+    let mut files = vec![&mut file];
+    matches.find_hex_matches(&parse_hex_pattern("DE AD BE EF").unwrap(), &search_on, &mut files, false, 0);
+
+    assert_eq!(matches.unknown().len(), 1);
+    assert_eq!(matches.unknown()[0].matches().iter().map(|x| *x.pos()).collect::<Vec<_>>(), vec![1, 6]);
+    assert!(matches.unknown()[0].matches().iter().all(|x| *x.len() == 4));
+
+    // Replacing the matches of a hex search must be refused, even with a replace text of the same length.
+    let mut search = GlobalSearch::default();
+    search.set_pattern("DE AD BE EF".to_owned());
+    search.set_replace_text("00 00 00 00".to_owned());
+    search.set_use_hex(true);
+    let match_holders = vec![MatchHolder::Unknown(matches.unknown()[0].clone())];
+    assert!(matches!(search.replace_possible(&match_holders), Err(RLibError::GlobalSearchReplaceHexNotSupported)));
+
+    search.set_use_hex(false);
+    assert!(search.replace_possible(&match_holders).is_ok());
+}
+
+#[test]
+fn test_matches_to_html() {
+    let mut file = RFile::new_from_vec(b"<b>hello</b> & world", FileType::Text, 0, "text/test.txt");
+
+    let mut search_on = SearchOn::default();
+    search_on.set_text(true);
+
+    let mut matches = Matches::default();
+    assert!(matches.to_html().contains("No matches found."));
+
+    let mut files = vec![&mut file];
+    matches.find_matches("hello", false, &MatchingMode::Pattern(None), &search_on, &mut files, &Schema::default(), None, false, 0, false);
+
+    // Matched content must be escaped, with the match itself highlighted.
+    let html = matches.to_html();
+    assert!(html.contains("<h2>Text"));
+    assert!(html.contains("<code>text/test.txt</code>"));
+    assert!(html.contains("<td>Line 1</td>"));
+    assert!(html.contains("&lt;b&gt;<mark>hello</mark>&lt;/b&gt; &amp; world"));
+    assert!(!html.contains("<b>hello"));
+}
+
+#[test]
+fn test_replace_preview() {
+    let mut file = RFile::new_from_vec(b"hello world\nsay hello", FileType::Text, 0, "text/test.txt");
+
+    let mut search_on = SearchOn::default();
+    search_on.set_text(true);
+
+    let mut search = GlobalSearch::default();
+    search.set_pattern("(hel)lo".to_owned());
+    search.set_replace_text("${1}p".to_owned());
+    search.set_use_regex(true);
+
+    let mut files = vec![&mut file];
+    let matching_mode = search.matching_mode();
+    let pattern = search.pattern().to_owned();
+    search.matches_mut().find_matches(&pattern, false, &matching_mode, &search_on, &mut files, &Schema::default(), None, false, 0, false);
+
+    // Capture groups must be expanded, and the matches must be left untouched.
+    let preview = search.replace_preview();
+    assert_eq!(preview.len(), 2);
+    assert_eq!(preview[0].location(), &ReplacePreviewLocation::Line(0));
+    assert_eq!(preview[1].location(), &ReplacePreviewLocation::Line(1));
+    assert_eq!((*preview[1].column(), preview[1].value().as_str()), (4, "say hello"));
+    assert!(preview.iter().all(|item| item.path() == "text/test.txt" && item.original() == "hello" && item.replacement() == "help"));
+    assert_eq!(search.matches().text()[0].matches().len(), 2);
+
+    // Without regex, the replace text is used as is.
+    search.set_use_regex(false);
+    search.set_pattern("hello".to_owned());
+    assert!(search.replace_preview().iter().all(|item| item.replacement() == "${1}p"));
+}
+
+#[test]
+fn test_preview_replacements() {
+    let mut file = RFile::new_from_vec(b"hello world\nsay hello", FileType::Text, 0, "text/test.txt");
+
+    let mut search_on = SearchOn::default();
+    search_on.set_text(true);
+
+    let mut search = GlobalSearch::default();
+    search.set_pattern("(hel)lo".to_owned());
+    search.set_replace_text("${1}p".to_owned());
+    search.set_use_regex(true);
+
+    let mut files = vec![&mut file];
+    let matching_mode = search.matching_mode();
+    let pattern = search.pattern().to_owned();
+    search.matches_mut().find_matches(&pattern, false, &matching_mode, &search_on, &mut files, &Schema::default(), None, false, 0, false);
+
+    // Each match must report the full value before and after the replace, without touching the matches.
+    let path = ContainerPath::File("text/test.txt".to_owned());
+    assert_eq!(search.preview_replacements(), vec![
+        (path.clone(), 0, 0, "hello world".to_owned(), "help world".to_owned()),
+        (path, 1, 4, "say hello".to_owned(), "say help".to_owned()),
+    ]);
+    assert_eq!(search.matches().text()[0].matches().len(), 2);
+
+    // Outside the open Pack nothing can be replaced, so there's nothing to preview.
+    search.set_source(SearchSource::GameFiles);
+    assert!(search.preview_replacements().is_empty());
+}
+
+#[test]
+fn test_whole_word() {
+    let mut search = GlobalSearch::default();
+    search.set_pattern("unit".to_owned());
+    search.set_replace_text("$1".to_owned());
+    search.search_on_mut().set_text(true);
+    search.search_on_mut().set_whole_word(true);
+
+    let find_matches = |search: &mut GlobalSearch| {
+        let mut file = RFile::new_from_vec(b"unit unit_key Unit\nunits unit", FileType::Text, 0, "text/test.txt");
+        let mut files = vec![&mut file];
+        let matching_mode = search.matching_mode();
+        let pattern = search.pattern().to_owned();
+        let case_sensitive = *search.case_sensitive();
+        let search_on = search.search_on().clone();
+        *search.matches_mut() = Matches::default();
+        search.matches_mut().find_matches(&pattern, case_sensitive, &matching_mode, &search_on, &mut files, &Schema::default(), None, false, 0, false);
+        search.matches().text().first().map(|matches| matches.matches().len()).unwrap_or_default()
+    };
+
+    // Words that only contain the pattern must not match, and case sensitivity must still be respected.
+    assert_eq!(find_matches(&mut search), 3);
+    search.set_case_sensitive(true);
+    assert_eq!(find_matches(&mut search), 2);
+
+    // The replace text must not be treated as a regex replacement.
+    assert!(search.replace_preview().iter().all(|item| item.original() == "unit" && item.replacement() == "$1"));
+
+    // Whole word matching must also work when find_matches gets a plain pattern.
+    let mut file = RFile::new_from_vec(b"unit unit_key", FileType::Text, 0, "text/test.txt");
+    let mut matches = Matches::default();
+    matches.find_matches("unit", false, &MatchingMode::Pattern(None), search.search_on(), &mut vec![&mut file], &Schema::default(), None, false, 0, false);
+    assert_eq!(matches.text()[0].matches().len(), 1);
+
+    // Word boundaries are only checked on the sides of the pattern with word characters.
+    match MatchingMode::whole_word(".txt", false) {
+        MatchingMode::Regex(regex) => {
+            assert!(regex.is_match("file.TXT"));
+            assert!(!regex.is_match("file.txts"));
+        }
+        MatchingMode::Pattern(_) => panic!("expected a regex"),
+    }
+
+    // With regex, the user pattern is used as is.
+    search.set_use_regex(true);
+    search.set_pattern("unit.*".to_owned());
+    assert_eq!(find_matches(&mut search), 2);
+}
+
+#[test]
+fn test_replace_match_string_regex_captures() {
+    let matching_mode = |pattern: &str, case_sensitive: bool| MatchingMode::Regex(RegexBuilder::new(pattern).case_insensitive(!case_sensitive).build().unwrap());
+
+    // Capture groups, both numbered and named, are expanded.
+    let mut data = "unit_key_01".to_owned();
+    assert!(replace_match_string("", "${name}_$2", true, &matching_mode("(?P<name>unit)_key_(\\d+)", true), 0, 11, "unit_key_01", &mut data));
+    assert_eq!(data, "unit_01");
+
+    // The regex sees the full text, so assertions depending on the text around the match still work.
+    let mut data = "foo".to_owned();
+    assert!(replace_match_string("", "[$1]", true, &matching_mode("\\B(o)", true), 1, 2, "foo", &mut data));
+    assert_eq!(data, "f[o]o");
+
+    // Case insensitive regexes keep being case insensitive when replacing.
+    let mut data = "Hello".to_owned();
+    assert!(replace_match_string("", "${1}p", false, &matching_mode("(hel)lo", false), 0, 5, "Hello", &mut data));
+    assert_eq!(data, "Help");
+
+    // Outdated matches are not replaced.
+    let mut data = "hello".to_owned();
+    assert!(!replace_match_string("", "x", true, &matching_mode("l+", true), 1, 3, "hello", &mut data));
+    assert_eq!(data, "hello");
+}
+
+#[test]
+fn test_find_matches_esf() {
+    let data = std::fs::read("../test_files/test_decode_esf_caab.esf").unwrap();
+    let mut file = RFile::new_from_vec(&data, FileType::ESF, 0, "test.esf");
+
+    // Get the name of the root node to search for it.
+    file.decode(&None, true, false).unwrap();
+    let root_name = match file.decoded().unwrap() {
+        RFileDecoded::ESF(esf) => match esf.root_node() {
+            rpfm_lib::files::esf::NodeType::Record(record) => record.name().to_owned(),
+            _ => panic!("Root node is not a record."),
+        },
+        _ => panic!("File is not an ESF."),
+    };
+
+    let mut search_on = SearchOn::default();
+    search_on.set_esf(true);
+
+    let mut matches = Matches::default();
+    let mut files = vec![&mut file];
+    matches.find_matches(&root_name.to_lowercase(), false, &MatchingMode::Pattern(None), &search_on, &mut files, &Schema::default(), None, false, 0, false);
+
+    assert_eq!(matches.esf().len(), 1);
+    assert_eq!(matches.esf()[0].path(), "test.esf");
+    assert!(matches.esf()[0].matches().iter().any(|x| *x.name() && x.node_path() == &root_name && x.text() == &root_name));
+}
+
+#[test]
+fn test_search_anims_table() {
+    use rpfm_lib::files::anims_table::{AnimsTable, Entry, Fragment};
+
+    let mut fragment = Fragment::default();
+    fragment.set_name("animations/fragments/humanoid01.frg".to_owned());
+
+    let mut entry = Entry::default();
+    entry.set_table_name("humanoid01_tables".to_owned());
+    entry.set_skeleton_type("humanoid01".to_owned());
+    entry.set_fragments(vec![Fragment::default(), fragment]);
+
+    let mut table = AnimsTable::default();
+    table.set_entries(vec![Entry::default(), entry]);
+
+    // Entries are rows, and each text value of an entry is a column, fragments included.
+    let matches = table.search("test.bin", "humanoid01", false, &MatchingMode::Pattern(None));
+    let cells = matches.matches().iter().map(|x| (*x.row_number(), *x.column_number(), x.column_name().to_owned())).collect::<Vec<_>>();
+    assert_eq!(cells, vec![
+        (1, 0, "table_name".to_owned()),
+        (1, 1, "skeleton_type".to_owned()),
+        (1, 4, "fragment_name".to_owned()),
+    ]);
+
+    assert!(table.replace("humanoid01", "humanoid02", false, &MatchingMode::Pattern(None), &matches));
+    assert_eq!(table.entries()[1].table_name(), "humanoid02_tables");
+    assert_eq!(table.entries()[1].skeleton_type(), "humanoid02");
+    assert_eq!(table.entries()[1].fragments()[1].name(), "animations/fragments/humanoid02.frg");
+}
+
+#[test]
+fn test_table_matches_group_by_row() {
+    use rpfm_lib::files::anims_table::{AnimsTable, Entry, Fragment};
+
+    let mut fragment = Fragment::default();
+    fragment.set_name("animations/fragments/humanoid01.frg".to_owned());
+
+    let mut entry = Entry::default();
+    entry.set_table_name("humanoid01_tables".to_owned());
+    entry.set_skeleton_type("humanoid01_humanoid01".to_owned());
+    entry.set_fragments(vec![fragment]);
+
+    let mut table = AnimsTable::default();
+    table.set_entries(vec![Entry::default(), entry]);
+
+    let mut matches = table.search("test.bin", "humanoid01", false, &MatchingMode::Pattern(None));
+    assert_eq!(matches.matches().len(), 4);
+
+    // Grouping leaves the first match of the row, listing all the columns with matches. Grouping again changes nothing.
+    matches.group_by_row();
+    let grouped = matches.clone();
+    matches.group_by_row();
+    assert_eq!(matches, grouped);
+    assert_eq!(matches.matches().len(), 1);
+    assert!(matches.matches()[0].is_row_match());
+    assert_eq!(*matches.matches()[0].column_number(), 0);
+    assert_eq!(matches.matches()[0].columns_affected(), vec![0, 1, 3]);
+
+    // Replacing a row match replaces all the matches of the row, including multiple ones in the same cell.
+    assert!(table.replace("humanoid01", "humanoid02", false, &MatchingMode::Pattern(None), &matches));
+    assert_eq!(table.entries()[1].table_name(), "humanoid02_tables");
+    assert_eq!(table.entries()[1].skeleton_type(), "humanoid02_humanoid02");
+    assert_eq!(table.entries()[1].fragments()[0].name(), "animations/fragments/humanoid02.frg");
+}
+
+#[test]
+fn test_search_key_and_reference_columns() {
+    use rpfm_lib::files::{db::DB, table::DecodedData};
+    use rpfm_lib::schema::{Definition, FieldType};
+
+    let units_definition = Definition::new_for_tests(0, &[("key", FieldType::StringU8, true), ("category", FieldType::StringU8, false), ("name", FieldType::StringU8, false)], &[]);
+    let mut other_definition = Definition::new_for_tests(0, &[("unit_name", FieldType::StringU8, false)], &[]);
+    other_definition.fields_mut()[0].set_is_reference(Some(("units".to_owned(), "name".to_owned())));
+
+    let mut schema = Schema::default();
+    schema.add_definition("units_tables", &units_definition);
+    schema.add_definition("other_tables", &other_definition);
+
+    let reference_targets = schema.referenced_columns();
+    assert!(reference_targets.contains(&("units".to_owned(), "name".to_owned())));
+
+    let mut table = DB::new(&units_definition, None, "units_tables");
+    table.set_data(&[vec![
+        DecodedData::StringU8("foo_key".to_owned()),
+        DecodedData::StringU8("foo_category".to_owned()),
+        DecodedData::StringU8("foo_name".to_owned()),
+    ]]).unwrap();
+
+    // Only the key column and the referenced column must be searched.
+    let matches = TableMatches::search_key_and_reference_columns(&table, "db/units_tables/test", "foo", false, &MatchingMode::Pattern(None), &reference_targets);
+    assert_eq!(matches.matches().iter().map(|x| x.column_name().as_str()).collect::<Vec<_>>(), vec!["key", "name"]);
+    assert_eq!(table.search("db/units_tables/test", "foo", false, &MatchingMode::Pattern(None)).matches().len(), 3);
+
+    // Tables without key or referenced columns return no matches.
+    let mut table = DB::new(&other_definition, None, "other_tables");
+    table.set_data(&[vec![DecodedData::StringU8("foo_name".to_owned())]]).unwrap();
+    assert!(TableMatches::search_key_and_reference_columns(&table, "db/other_tables/test", "foo", false, &MatchingMode::Pattern(None), &reference_targets).matches().is_empty());
+}
+
+#[test]
+fn test_matches_to_grep_lines() {
+    let mut text_file = RFile::new_from_vec(b"first line\nsay hello\tthere", FileType::Text, 0, "text/test.txt");
+    let mut unknown_file = RFile::new_from_vec(b"xhello", FileType::Unknown, 0, "unknown/test.bin");
+
+    let mut search_on = SearchOn::default();
+    search_on.set_text(true);
+    search_on.set_unknown(true);
+
+    let mut matches = Matches::default();
+    assert!(matches.to_grep_lines().is_empty());
+
+    let mut files = vec![&mut text_file, &mut unknown_file];
+    matches.find_matches("hello", false, &MatchingMode::Pattern(None), &search_on, &mut files, &Schema::default(), None, false, 0, false);
+
+    // Text matches use 1-based line and column, and binary ones use byte offsets.
+    assert_eq!(matches.to_grep_lines(), vec![
+        "text/test.txt:2:5:hello".to_owned(),
+        "unknown/test.bin@1:5".to_owned(),
+    ]);
+}
+
+#[test]
+fn test_matches_iter_all() {
+    let mut text_file = RFile::new_from_vec(b"first line\nsay hello", FileType::Text, 0, "text/test.txt");
+    let mut unknown_file = RFile::new_from_vec(b"xhello", FileType::Unknown, 0, "unknown/test.bin");
+
+    let mut search_on = SearchOn::default();
+    search_on.set_text(true);
+    search_on.set_unknown(true);
+
+    let mut search = GlobalSearch::default();
+    assert_eq!(search.iter_all_matches().count(), 0);
+
+    let mut files = vec![&mut text_file, &mut unknown_file];
+    search.matches_mut().find_matches("hello", false, &MatchingMode::Pattern(None), &search_on, &mut files, &Schema::default(), None, false, 0, false);
+
+    let matches = search.iter_all_matches().collect::<Vec<_>>();
+    assert_eq!(matches.len(), 2);
+
+    assert_eq!(matches[0].file_type(), &Some(FileType::Text));
+    assert_eq!(matches[0].path(), "text/test.txt");
+    assert_eq!(matches[0].source(), &Some(SearchSource::Pack));
+    assert_eq!(matches[0].location(), &MatchLocation::Line(1));
+    assert_eq!(&matches[0].text()[*matches[0].start()..*matches[0].end()], "hello");
+
+    // Binary matches have no text, only a location.
+    assert_eq!(matches[1].file_type(), &Some(FileType::Unknown));
+    assert_eq!(matches[1].location(), &MatchLocation::Offset(1, 5));
+    assert!(matches[1].text().is_empty());
+}
+
+#[test]
+fn test_retain_table_names() {
+    let mut units = RFile::new_from_vec(b"", FileType::DB, 0, "db/units_tables/test");
+    let mut factions = RFile::new_from_vec(b"", FileType::DB, 0, "db/factions_tables/test");
+    let mut text = RFile::new_from_vec(b"", FileType::Text, 0, "text/test.txt");
+
+    // No list means no filtering.
+    let mut files = vec![&mut units, &mut factions, &mut text];
+    GlobalSearch::retain_table_names(&mut files, &None);
+    assert_eq!(files.len(), 3);
+
+    // Neither does an empty list.
+    GlobalSearch::retain_table_names(&mut files, &Some(BTreeSet::new()));
+    assert_eq!(files.len(), 3);
+
+    // Only DB tables must be filtered.
+    GlobalSearch::retain_table_names(&mut files, &Some(BTreeSet::from(["units_tables".to_owned()])));
+    assert_eq!(files.iter().map(|file| file.path_in_container_raw()).collect::<Vec<_>>(), vec!["db/units_tables/test", "text/test.txt"]);
+}
+
+#[test]
+fn test_favorite_tables() {
+    use super::favorites::FavoriteTables;
+
+    let mut favorites = FavoriteTables::default();
+    assert!(favorites.tables_for_game("warhammer_3").is_empty());
+
+    // Adding is per-game, and only reports new favorites.
+    assert!(favorites.add("warhammer_3", "units_tables"));
+    assert!(favorites.add("warhammer_3", "factions_tables"));
+    assert!(!favorites.add("warhammer_3", "units_tables"));
+    assert!(favorites.add("warhammer_2", "units_tables"));
+    assert!(favorites.is_favorite("warhammer_3", "factions_tables"));
+    assert!(!favorites.is_favorite("warhammer_2", "factions_tables"));
+
+    // Removing only reports removed favorites, and drops games left without favorites.
+    assert!(favorites.remove("warhammer_2", "units_tables"));
+    assert!(!favorites.remove("warhammer_2", "units_tables"));
+    assert!(!favorites.tables().contains_key("warhammer_2"));
+
+    // Favorites must survive a save/load roundtrip, and a missing file means no favorites.
+    let path = std::env::temp_dir().join("rpfm_test_favorite_tables.json");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(FavoriteTables::load(&path).unwrap(), FavoriteTables::default());
+
+    favorites.save(&path).unwrap();
+    let loaded = FavoriteTables::load(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(loaded, favorites);
+    assert_eq!(loaded.tables_for_game("warhammer_3"), BTreeSet::from(["factions_tables".to_owned(), "units_tables".to_owned()]));
+}
+
+#[test]
+fn test_search_registry() {
+    let mut registry = SearchRegistry::default();
+    assert!(registry.handler(FileType::DB).is_some());
+    assert!(registry.handler(FileType::Text).is_some());
+    assert!(registry.handler(FileType::Audio).is_none());
+
+    // Custom handlers must be called through the registry like the built-in ones.
+    registry.register(FileType::Audio, Box::new(|file: &mut RFile, _: &SearchContext| Some(MatchHolder::Audio(UnknownMatches::new(file.path_in_container_raw())))));
+
+    let mut file = RFile::new_from_vec(b"", FileType::Audio, 0, "audio/test.wem");
+    let timings = Timings::new("test_search_registry");
+    let context = SearchContext {
+        pattern: "hello",
+        case_sensitive: false,
+        matching_mode: &MatchingMode::Pattern(None),
+        extra_data: &None,
+        keep_decoded: false,
+        max_decode_bytes: 0,
+        key_and_reference_columns_only: false,
+        reference_targets: &HashSet::new(),
+        timings: &timings,
+    };
+
+    let handler = registry.handler(FileType::Audio).unwrap();
+    assert!(matches!(handler(&mut file, &context), Some(MatchHolder::Audio(matches)) if matches.path() == "audio/test.wem"));
+
+    assert!(registry.unregister(FileType::Audio).is_some());
+    assert!(registry.handler(FileType::Audio).is_none());
+    assert!(SearchRegistry::new().file_types().is_empty());
+}
+
+#[test]
+fn test_apply_recipe() {
+    use rpfm_lib::games::pfh_version::PFHVersion;
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+
+    use super::recipe::RecipeStep;
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let mut pack = Pack::new_with_version(PFHVersion::PFH6);
+    pack.insert(RFile::new_from_vec(b"hello world", FileType::Text, 0, "text/a.txt")).unwrap();
+    pack.insert(RFile::new_from_vec(b"goodbye world", FileType::Text, 0, "text/b.txt")).unwrap();
+    pack.insert(RFile::new_from_vec(b"unrelated", FileType::Text, 0, "text/c.txt")).unwrap();
+
+    let mut search_on = SearchOn::default();
+    search_on.set_text(true);
+
+    let mut recipe = Recipe::new("test");
+    recipe.steps_mut().push(RecipeStep::new("hello", "hi", false, false, search_on.clone()));
+    recipe.steps_mut().push(RecipeStep::new("w(or)ld", "m${1}e", false, true, search_on));
+
+    // Recipes must survive a save/load roundtrip.
+    let path = std::env::temp_dir().join("rpfm_test_search_recipe.json");
+    recipe.save(&path).unwrap();
+    let recipe = Recipe::load(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(recipe.name(), "test");
+    assert_eq!(recipe.steps().len(), 2);
+    assert!(*recipe.steps()[1].use_regex());
+    assert!(*recipe.steps()[1].search_on().text());
+
+    // Steps are applied in order, and each edited path is only reported once.
+    let mut search = GlobalSearch::default();
+    search.set_game_key(KEY_WARHAMMER_3.to_owned());
+    search.set_pattern("user pattern".to_owned());
+    search.set_replace_text("user replacement".to_owned());
+    search.set_use_regex(false);
+    search.set_source(SearchSource::ParentFiles);
+    let edited_paths = search.apply_recipe(&recipe, game, &Schema::default(), &mut pack, &mut Dependencies::default()).unwrap();
+
+    // The user's own search settings must survive the recipe.
+    assert_eq!(search.pattern(), "user pattern");
+    assert_eq!(search.replace_text(), "user replacement");
+    assert!(!*search.use_regex());
+    assert_eq!(search.source(), &SearchSource::ParentFiles);
+    assert!(!*search.search_on().text());
+    assert_eq!(edited_paths.len(), 2);
+    assert!(edited_paths.contains(&ContainerPath::File("text/a.txt".to_owned())));
+    assert!(edited_paths.contains(&ContainerPath::File("text/b.txt".to_owned())));
+
+    let text = |pack: &mut Pack, path: &str| match pack.file_mut(path, false).unwrap().decode(&None, false, true).unwrap() {
+        Some(RFileDecoded::Text(text)) => text.contents().to_owned(),
+        _ => unreachable!(),
+    };
+
+    assert_eq!(text(&mut pack, "text/a.txt"), "hi more");
+    assert_eq!(text(&mut pack, "text/b.txt"), "goodbye more");
+    assert_eq!(text(&mut pack, "text/c.txt"), "unrelated");
+}
+
+/// This function returns a schema with a units table definition, and an encoded units table with the provided names.
+fn units_table_for_dependencies(names: &[&str]) -> (Schema, RFile) {
+    use rpfm_lib::files::{db::DB, Encodeable, table::DecodedData};
+    use rpfm_lib::schema::{Definition, FieldType};
+
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("name", FieldType::StringU8, false)], &[]);
+
+    let mut schema = Schema::default();
+    schema.add_definition("units_tables", &definition);
+
+    let mut table = DB::new(&definition, None, "units_tables");
+    table.set_data(&names.iter()
+        .enumerate()
+        .map(|(index, name)| vec![DecodedData::StringU8(format!("key_{index}")), DecodedData::StringU8(name.to_string())])
+        .collect::<Vec<_>>()
+    ).unwrap();
+
+    let mut data = vec![];
+    table.encode(&mut data, &None).unwrap();
+
+    (schema, RFile::new_from_vec(&data, FileType::DB, 0, "db/units_tables/test"))
+}
+
+#[test]
+fn test_dependencies_decoded_tables() {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+
+    use crate::dependencies::DecodedTable;
+
+    let (mut schema, rfile) = units_table_for_dependencies(&["foo_unit", "bar_unit"]);
+    let mut dependencies = Dependencies::default();
+    dependencies.insert_db_as_vanilla_db(rfile);
+
+    let cached = |dependencies: &Dependencies, schema: &Schema| {
+        let mut tables = dependencies.decoded_tables(schema, &SearchSource::GameFiles, "units");
+        assert_eq!(tables.len(), 1);
+        match tables.remove(0) {
+            (path, DecodedTable::Cached(table)) if path == "db/units_tables/test" => table,
+            _ => unreachable!(),
+        }
+    };
+
+    // Undecoded tables are decoded once, and shared between calls.
+    let table = cached(&dependencies, &schema);
+    assert_eq!(table.data().len(), 2);
+    assert!(Arc::ptr_eq(&table, &cached(&dependencies, &schema)));
+
+    // Changing the schema or clearing the cache means decoding them again.
+    let mut patch = HashMap::new();
+    patch.insert("name".to_owned(), HashMap::from([("description".to_owned(), "patched".to_owned())]));
+    schema.patches_mut().insert("units_tables".to_owned(), patch);
+    let patched = cached(&dependencies, &schema);
+    assert!(!Arc::ptr_eq(&table, &patched));
+    assert!(Arc::ptr_eq(&patched, &cached(&dependencies, &schema)));
+
+    dependencies.clear_decoded_tables_cache();
+    assert!(!Arc::ptr_eq(&patched, &cached(&dependencies, &schema)));
+
+    // Other sources don't see the vanilla table.
+    assert!(dependencies.decoded_tables(&schema, &SearchSource::Pack, "units").is_empty());
+    assert!(dependencies.decoded_tables(&schema, &SearchSource::ParentFiles, "units").is_empty());
+    assert!(dependencies.decoded_tables(&schema, &SearchSource::AssKitFiles, "units").is_empty());
+
+    // Searching the game files must find matches in undecoded tables.
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let mut search_on = SearchOn::default();
+    search_on.set_db(true);
+
+    let mut search = GlobalSearch::default();
+    search.set_game_key(KEY_WARHAMMER_3.to_owned());
+    search.set_source(SearchSource::GameFiles);
+    search.set_search_on(search_on);
+    search.set_pattern("foo".to_owned());
+    search.search(game, &schema, &mut Pack::default(), &mut dependencies, &[]);
+
+    assert_eq!(search.matches().db().len(), 1);
+    assert_eq!(search.matches().db()[0].path(), "db/units_tables/test");
+    assert_eq!(search.matches().db()[0].matches().len(), 1);
+}
+
+#[test]
+#[ignore]
+fn bench_dependencies_decoded_tables() {
+    use rpfm_lib::games::supported_games::{KEY_WARHAMMER_3, SupportedGames};
+
+    let names = (0..100_000).map(|index| format!("unit_{index}")).collect::<Vec<_>>();
+    let (schema, rfile) = units_table_for_dependencies(&names.iter().map(|name| name.as_str()).collect::<Vec<_>>());
+    let mut dependencies = Dependencies::default();
+    dependencies.insert_db_as_vanilla_db(rfile);
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let mut search_on = SearchOn::default();
+    search_on.set_db(true);
+
+    let mut search = GlobalSearch::default();
+    search.set_game_key(KEY_WARHAMMER_3.to_owned());
+    search.set_source(SearchSource::GameFiles);
+    search.set_search_on(search_on);
+    search.set_pattern("unit_99999".to_owned());
+
+    for run in 0..3 {
+        let start = Instant::now();
+        search.search(game, &schema, &mut Pack::default(), &mut dependencies, &[]);
+        println!("search run {run}: {:?}, {} matches", start.elapsed(), search.matches().db().iter().map(|x| x.matches().len()).sum::<usize>());
+    }
+}
@@ -12,12 +12,16 @@
 Module with all the code related to the `TableMatches`.
 
 This module contains the code needed to get table matches from a `GlobalSearch`.
+
+Besides DB and Loc tables, table-like formats (AnimsTable and MatchedCombat) also use these matches,
+with one row per entry and one column per text value of the entry.
 !*/
 
 use getset::{Getters, MutGetters};
 
-use rpfm_lib::files::{db::DB, loc::Loc, table::DecodedData};
-use rpfm_lib::schema::Field;
+use std::collections::{HashMap, HashSet};
+
+use rpfm_lib::files::{anims_table::{AnimsTable, Entry as AnimsTableEntry}, db::DB, loc::Loc, matched_combat::{MatchedCombat, MatchedEntry}, table::DecodedData};
 
 use super::{find_in_string, MatchingMode, Replaceable, Searchable, replace_match_string};
 
@@ -59,6 +63,21 @@ pub struct TableMatch {
 
     // The contents of the matched cell.
     text: String,
+
+    /// Columns of the row with matches, as (name, logical index), when matches are grouped by row. Empty for cell matches.
+    row_columns: Vec<(String, u32)>,
+}
+
+/// This enum defines how matches on tables are reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableMatchGranularity {
+
+    /// One match per match within a cell.
+    #[default]
+    Cell,
+
+    /// One match per row with matches, pointing to the first match of the row and listing the columns with matches.
+    Row,
 }
 
 //-------------------------------------------------------------------------------//
@@ -75,7 +94,7 @@ impl Searchable for DB {
 
         for (row_number, row) in self.data().iter().enumerate() {
             for (column_number, cell) in row.iter().enumerate() {
-                matches.match_decoded_data(&cell.data_to_string(), pattern_to_search, case_sensitive, matching_mode, &fields_processed, column_number as u32, row_number as i64);
+                matches.match_decoded_data(&cell.data_to_string(), pattern_to_search, case_sensitive, matching_mode, fields_processed[column_number].name(), column_number as u32, row_number as i64);
             }
         }
 
@@ -93,7 +112,7 @@ impl Searchable for Loc {
 
         for (row_number, row) in self.data().iter().enumerate() {
             for (column_number, cell) in row.iter().enumerate() {
-                matches.match_decoded_data(&cell.data_to_string(), pattern_to_search, case_sensitive, matching_mode, &fields_processed, column_number as u32, row_number as i64);
+                matches.match_decoded_data(&cell.data_to_string(), pattern_to_search, case_sensitive, matching_mode, fields_processed[column_number].name(), column_number as u32, row_number as i64);
             }
         }
 
@@ -108,8 +127,10 @@ impl Replaceable for DB {
 
         for search_match in search_matches.matches() {
             if let Some(row) = self.data_mut().get_mut(search_match.row_number as usize) {
-                if let Some(data) = row.get_mut(search_match.column_number as usize) {
-                    edited |= search_match.replace(pattern, replace_pattern, case_sensitive, matching_mode, data);
+                for column_number in search_match.columns_affected() {
+                    if let Some(data) = row.get_mut(column_number as usize) {
+                        edited |= search_match.replace(pattern, replace_pattern, case_sensitive, matching_mode, data);
+                    }
                 }
             }
         }
@@ -125,8 +146,10 @@ impl Replaceable for Loc {
 
         for search_match in search_matches.matches() {
             if let Some(row) = self.data_mut().get_mut(search_match.row_number as usize) {
-                if let Some(data) = row.get_mut(search_match.column_number as usize) {
-                    edited |= search_match.replace(pattern, replace_pattern, case_sensitive, matching_mode, data);
+                for column_number in search_match.columns_affected() {
+                    if let Some(data) = row.get_mut(column_number as usize) {
+                        edited |= search_match.replace(pattern, replace_pattern, case_sensitive, matching_mode, data);
+                    }
                 }
             }
         }
@@ -146,6 +169,72 @@ impl TableMatches {
         }
     }
 
+    /// This function searches the provided DB table, but only on its key columns and on the columns referenced by other tables.
+    ///
+    /// `reference_targets` is the list of columns referenced by other tables, as returned by `Schema::referenced_columns`.
+    /// If the table has no key or referenced columns, no matches are returned.
+    pub fn search_key_and_reference_columns(table: &DB, file_path: &str, pattern_to_search: &str, case_sensitive: bool, matching_mode: &MatchingMode, reference_targets: &HashSet<(String, String)>) -> Self {
+        let mut matches = Self::new(file_path);
+
+        let definition = table.definition();
+        let patches = Some(definition.patches());
+        let fields_processed = definition.fields_processed();
+        let table_name = table.table_name().strip_suffix("_tables").unwrap_or(table.table_name());
+
+        let columns = fields_processed.iter()
+            .enumerate()
+            .filter(|(_, field)| field.is_key(patches) || reference_targets.contains(&(table_name.to_owned(), field.name().to_owned())))
+            .map(|(column_number, _)| column_number)
+            .collect::<Vec<_>>();
+
+        if columns.is_empty() {
+            return matches;
+        }
+
+        for (row_number, row) in table.data().iter().enumerate() {
+            for column_number in &columns {
+                if let Some(cell) = row.get(*column_number) {
+                    matches.match_decoded_data(&cell.data_to_string(), pattern_to_search, case_sensitive, matching_mode, fields_processed[*column_number].name(), *column_number as u32, row_number as i64);
+                }
+            }
+        }
+
+        matches
+    }
+
+    /// This function groups the matches by row, leaving only one match per row with matches.
+    ///
+    /// The match kept for each row is the first one of the row, and it lists all the columns of the row with matches. Already grouped matches are left as they are.
+    pub fn group_by_row(&mut self) {
+        let mut grouped: Vec<TableMatch> = Vec::with_capacity(self.matches.len());
+        let mut rows: HashMap<i64, usize> = HashMap::new();
+
+        for mut table_match in self.matches.drain(..) {
+            let columns = if table_match.row_columns.is_empty() {
+                vec![(table_match.column_name.to_owned(), table_match.column_number)]
+            } else {
+                std::mem::take(&mut table_match.row_columns)
+            };
+
+            let row_match = match rows.get(&table_match.row_number) {
+                Some(index) => &mut grouped[*index],
+                None => {
+                    rows.insert(table_match.row_number, grouped.len());
+                    grouped.push(table_match);
+                    grouped.last_mut().unwrap()
+                }
+            };
+
+            for column in columns {
+                if !row_match.row_columns.contains(&column) {
+                    row_match.row_columns.push(column);
+                }
+            }
+        }
+
+        self.matches = grouped;
+    }
+
     /// This function check if the provided `&str` matches our search.
     fn match_decoded_data(
         &mut self,
@@ -153,21 +242,19 @@ impl TableMatches {
         pattern: &str,
         case_sensitive: bool,
         matching_mode: &MatchingMode,
-        fields_processed: &[Field],
+        column_name: &str,
         column_number: u32,
         row_number: i64,
     ) {
         match matching_mode {
             MatchingMode::Regex(regex) => {
                 for entry_match in regex.find_iter(text) {
-                    let column_name = fields_processed[column_number as usize].name();
                     self.matches.push(TableMatch::new(column_name, column_number, row_number, entry_match.start(), entry_match.end(), text));
                 }
             }
 
             MatchingMode::Pattern(regex) => {
                 for (start, end, _) in &find_in_string(text, pattern, case_sensitive, regex) {
-                    let column_name = fields_processed[column_number as usize].name();
                     self.matches.push(TableMatch::new(column_name, column_number, row_number, *start, *end, text));
                 }
             }
@@ -187,13 +274,205 @@ impl TableMatch {
             start,
             end,
             text: text.to_owned(),
+            row_columns: vec![],
+        }
+    }
+
+    /// This function returns if this match represents a whole row, instead of a single match within a cell.
+    pub fn is_row_match(&self) -> bool {
+        !self.row_columns.is_empty()
+    }
+
+    /// This function returns the logical indexes of the columns affected by this match: the columns with matches of its row for row matches, or its own column for cell matches.
+    pub fn columns_affected(&self) -> Vec<u32> {
+        if self.is_row_match() {
+            self.row_columns.iter().map(|(_, column_number)| *column_number).collect()
+        } else {
+            vec![self.column_number]
         }
     }
 
     /// This function replaces all the matches in the provided text.
     fn replace(&self, pattern: &str, replace_pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode, data: &mut DecodedData) -> bool {
-        let (previous_data, mut current_data) = (data.data_to_string().to_string(), data.data_to_string().to_string());
-        let edited = replace_match_string(pattern, replace_pattern, case_sensitive, matching_mode, self.start, self.end, &previous_data, &mut current_data);
+        let mut current_data = data.data_to_string().to_string();
+        let edited = self.replace_string(pattern, replace_pattern, case_sensitive, matching_mode, &mut current_data);
         data.set_data(&current_data).is_ok() && edited
     }
+
+    /// This function replaces the match in the provided string.
+    ///
+    /// For row matches, as they only know the position of the first match of the row, all the matches in the string are searched again and replaced.
+    fn replace_string(&self, pattern: &str, replace_pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode, data: &mut String) -> bool {
+        let previous_data = data.to_owned();
+        if self.is_row_match() {
+            let pattern_to_search = if case_sensitive { pattern.to_owned() } else { pattern.to_lowercase() };
+            let mut cell_matches = TableMatches::new("");
+            cell_matches.match_decoded_data(&previous_data, &pattern_to_search, case_sensitive, matching_mode, &self.column_name, self.column_number, self.row_number);
+
+            // Replace them in reverse, so the positions of the pending ones are still valid after each replacement.
+            for cell_match in cell_matches.matches.iter().rev() {
+                let current_data = data.to_owned();
+                replace_match_string(pattern, replace_pattern, case_sensitive, matching_mode, cell_match.start, cell_match.end, &current_data, data);
+            }
+
+            previous_data != *data
+        } else {
+            replace_match_string(pattern, replace_pattern, case_sensitive, matching_mode, self.start, self.end, &previous_data, data)
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------//
+//                         Table-like files as tables
+//-------------------------------------------------------------------------------//
+
+/// This function returns the text values of an AnimsTable entry, as (column name, text) pairs.
+///
+/// The position of each value is its column number, so the order must be kept in sync with [anims_table_cell_mut].
+fn anims_table_cells(entry: &AnimsTableEntry) -> Vec<(&'static str, &str)> {
+    let mut cells = vec![
+        ("table_name", entry.table_name().as_str()),
+        ("skeleton_type", entry.skeleton_type().as_str()),
+        ("mount_table_name", entry.mount_table_name().as_str()),
+    ];
+
+    cells.extend(entry.fragments().iter().map(|fragment| ("fragment_name", fragment.name().as_str())));
+    cells
+}
+
+/// This function returns a mutable reference to the text value of an AnimsTable entry in the provided column, if any.
+fn anims_table_cell_mut(entry: &mut AnimsTableEntry, column_number: usize) -> Option<&mut String> {
+    match column_number {
+        0 => Some(entry.table_name_mut()),
+        1 => Some(entry.skeleton_type_mut()),
+        2 => Some(entry.mount_table_name_mut()),
+        _ => entry.fragments_mut().get_mut(column_number - 3).map(|fragment| fragment.name_mut()),
+    }
+}
+
+/// This function returns the text values of a MatchedCombat entry, as (column name, text) pairs.
+///
+/// The position of each value is its column number, so the order must be kept in sync with [matched_combat_cell_mut].
+fn matched_combat_cells(entry: &MatchedEntry) -> Vec<(&'static str, &str)> {
+    let mut cells = vec![("id", entry.id().as_str())];
+
+    for entity in entry.participants().iter()
+        .flat_map(|participant| participant.entity_info())
+        .flat_map(|bundle| bundle.entities()) {
+
+        cells.push(("animation_filename", entity.animation_filename().as_str()));
+        cells.extend(entity.metadata_filenames().iter().map(|filename| ("metadata_filename", filename.as_str())));
+        cells.extend(entity.filters().iter().map(|filter| ("filter_value", filter.value().as_str())));
+        cells.push(("mount_filename", entity.mount_filename().as_str()));
+    }
+
+    cells
+}
+
+/// This function returns a mutable reference to the text value of a MatchedCombat entry in the provided column, if any.
+fn matched_combat_cell_mut(entry: &mut MatchedEntry, column_number: usize) -> Option<&mut String> {
+    if column_number == 0 {
+        return Some(entry.id_mut());
+    }
+
+    let mut first_column = 1;
+    for entity in entry.participants_mut().iter_mut()
+        .flat_map(|participant| participant.entity_info_mut().iter_mut())
+        .flat_map(|bundle| bundle.entities_mut().iter_mut()) {
+
+        let metadata_len = entity.metadata_filenames().len();
+        let filters_len = entity.filters().len();
+        let columns = 2 + metadata_len + filters_len;
+
+        if column_number < first_column + columns {
+            let offset = column_number - first_column;
+            return if offset == 0 {
+                Some(entity.animation_filename_mut())
+            } else if offset <= metadata_len {
+                entity.metadata_filenames_mut().get_mut(offset - 1)
+            } else if offset <= metadata_len + filters_len {
+                entity.filters_mut().get_mut(offset - 1 - metadata_len).map(|filter| filter.value_mut())
+            } else {
+                Some(entity.mount_filename_mut())
+            };
+        }
+
+        first_column += columns;
+    }
+
+    None
+}
+
+impl Searchable for AnimsTable {
+    type SearchMatches = TableMatches;
+
+    fn search(&self, file_path: &str, pattern_to_search: &str, case_sensitive: bool, matching_mode: &MatchingMode) -> TableMatches {
+        let mut matches = TableMatches::new(file_path);
+
+        for (row_number, entry) in self.entries().iter().enumerate() {
+            for (column_number, (column_name, text)) in anims_table_cells(entry).into_iter().enumerate() {
+                matches.match_decoded_data(text, pattern_to_search, case_sensitive, matching_mode, column_name, column_number as u32, row_number as i64);
+            }
+        }
+
+        matches
+    }
+}
+
+impl Searchable for MatchedCombat {
+    type SearchMatches = TableMatches;
+
+    fn search(&self, file_path: &str, pattern_to_search: &str, case_sensitive: bool, matching_mode: &MatchingMode) -> TableMatches {
+        let mut matches = TableMatches::new(file_path);
+
+        for (row_number, entry) in self.entries().iter().enumerate() {
+            for (column_number, (column_name, text)) in matched_combat_cells(entry).into_iter().enumerate() {
+                matches.match_decoded_data(text, pattern_to_search, case_sensitive, matching_mode, column_name, column_number as u32, row_number as i64);
+            }
+        }
+
+        matches
+    }
+}
+
+impl Replaceable for AnimsTable {
+
+    fn replace(&mut self, pattern: &str, replace_pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode, search_matches: &TableMatches) -> bool {
+        let mut edited = false;
+
+        // NOTE: Due to changes in index positions, we need to do this in reverse.
+        // Otherwise we may cause one edit to generate invalid indexes for the next matches.
+        for search_match in search_matches.matches().iter().rev() {
+            if let Some(entry) = self.entries_mut().get_mut(search_match.row_number as usize) {
+                for column_number in search_match.columns_affected() {
+                    if let Some(data) = anims_table_cell_mut(entry, column_number as usize) {
+                        edited |= search_match.replace_string(pattern, replace_pattern, case_sensitive, matching_mode, data);
+                    }
+                }
+            }
+        }
+
+        edited
+    }
+}
+
+impl Replaceable for MatchedCombat {
+
+    fn replace(&mut self, pattern: &str, replace_pattern: &str, case_sensitive: bool, matching_mode: &MatchingMode, search_matches: &TableMatches) -> bool {
+        let mut edited = false;
+
+        // NOTE: Due to changes in index positions, we need to do this in reverse.
+        // Otherwise we may cause one edit to generate invalid indexes for the next matches.
+        for search_match in search_matches.matches().iter().rev() {
+            if let Some(entry) = self.entries_mut().get_mut(search_match.row_number as usize) {
+                for column_number in search_match.columns_affected() {
+                    if let Some(data) = matched_combat_cell_mut(entry, column_number as usize) {
+                        edited |= search_match.replace_string(pattern, replace_pattern, case_sensitive, matching_mode, data);
+                    }
+                }
+            }
+        }
+
+        edited
+    }
 }
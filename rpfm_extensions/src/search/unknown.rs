@@ -107,6 +107,21 @@ impl UnknownMatches {
             matches: vec![],
         }
     }
+
+    /// This function searches the provided byte pattern within the provided data, returning all the matches found.
+    pub fn search_bytes(path: &str, data: &[u8], pattern: &[u8]) -> Self {
+        let mut matches = Self::new(path);
+
+        if !pattern.is_empty() && data.len() >= pattern.len() {
+            matches.matches = data.windows(pattern.len())
+                .enumerate()
+                .filter(|(_, window)| *window == pattern)
+                .map(|(pos, _)| UnknownMatch::new(pos, pattern.len()))
+                .collect();
+        }
+
+        matches
+    }
 }
 
 impl UnknownMatch {
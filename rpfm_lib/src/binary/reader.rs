@@ -889,6 +889,29 @@ pub trait ReadBytes: Read + Seek {
         // REMEMBER, FORMAT ENCODED IS BBGGRR00.
         Ok(format!("{value:06X?}"))
     }
+
+    /// This function tries to read a Hex-Encoded RGBA Colour from `self`.
+    ///
+    /// Unlike [read_string_colour_rgb](Self::read_string_colour_rgb), the returned string is always 8 characters long, so the alpha channel is kept.
+    ///
+    /// It may fail if there are not enough bytes to read the value or `self` cannot be read.
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    ///
+    /// use rpfm_lib::binary::ReadBytes;
+    ///
+    /// let data = vec![0xFF, 0x04, 0x05, 0x00];
+    /// let mut cursor = Cursor::new(data);
+    /// let data = cursor.read_string_colour_rgba().unwrap();
+    ///
+    /// assert_eq!(data, "000504FF");
+    /// assert_eq!(cursor.read_string_colour_rgba().is_err(), true);
+    /// ```
+    fn read_string_colour_rgba(&mut self) -> Result<String> {
+        let value = self.read_u32()?;
+        Ok(format!("{value:08X?}"))
+    }
 }
 
 // Automatic implementation for everything that implements `Read + Seek`.
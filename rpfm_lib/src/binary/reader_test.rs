@@ -419,3 +419,15 @@ fn read_string_colour_rgb() {
     // Check the reader returns an error for a slice shorter than expected.
     assert!(ReadBytes::read_string_colour_rgb(&mut Cursor::new([0x87, 0x97])).is_err());
 }
+
+/// Test to `ReadBytes::read_string_colour_rgba()`.
+#[test]
+fn read_string_colour_rgba() {
+
+    // Check the reader works for a proper encoded string, keeping the leading zeros.
+    assert_eq!(ReadBytes::read_string_colour_rgba(&mut Cursor::new([0xFF, 0x04, 0x05, 0x00])).unwrap(), "000504FF");
+    assert_eq!(ReadBytes::read_string_colour_rgba(&mut Cursor::new([0xFF, 0x04, 0x05, 0x80])).unwrap(), "800504FF");
+
+    // Check the reader returns an error for a slice shorter than expected.
+    assert!(ReadBytes::read_string_colour_rgba(&mut Cursor::new([0x87, 0x97])).is_err());
+}
@@ -577,6 +577,24 @@ pub trait WriteBytes: Write {
         let value = u32::from_str_radix(value, 16)?;
         self.write_u32(value)
     }
+
+    /// This function tries to write an UTF-8 String representing a Hex-Encoded RGBA Colour to `self`.
+    ///
+    /// It may fail if `self` cannot be written to or if the string is not a valid Hex-Encoded RGBA Colour.
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    ///
+    /// use rpfm_lib::binary::WriteBytes;
+    ///
+    /// let mut data = vec![];
+    /// assert!(data.write_string_colour_rgba("800504FF").is_ok());
+    /// assert_eq!(data, vec![0xFF, 0x04, 0x05, 0x80]);
+    /// ```
+    fn write_string_colour_rgba(&mut self, value: &str) -> Result<()> {
+        let value = u32::from_str_radix(value, 16)?;
+        self.write_u32(value)
+    }
 }
 
 // Automatic implementation for everything that implements `Write`.
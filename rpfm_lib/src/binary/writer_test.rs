@@ -319,3 +319,17 @@ fn write_string_colour_rgb() {
     assert!(data.write_string_colour_rgb("0504FF").is_ok());
     assert_eq!(data, vec![0xFF, 0x04, 0x05, 0x00]);
 }
+
+/// Test for WriteBytes::write_string_colour_rgba().
+#[test]
+fn write_string_colour_rgba() {
+
+    // Check the writer works for a properly encoded string.
+    let mut data = vec![];
+    assert!(data.write_string_colour_rgba("800504FF").is_ok());
+    assert_eq!(data, vec![0xFF, 0x04, 0x05, 0x80]);
+
+    // Check the writer fails for an invalid string.
+    let mut data = vec![];
+    assert!(data.write_string_colour_rgba("not a colour").is_err());
+}
@@ -196,6 +196,18 @@ pub enum RLibError {
     #[error("Error while trying to save a row from a table: We expected a field of type \"{0}\", but we got a field of type \"{1}\".")]
     EncodingTableWrongFieldType(String, String),
 
+    #[error("Error while trying to decode a sequence: we expected a sequence field, but we got a field of type \"{0}\".")]
+    DecodedDataNotASequence(String),
+
+    #[error("Error while trying to convert a {0} into a {1}: the data of the sequence is not valid for the new definition: {2}.")]
+    ConvertingSequenceIncompatibleDefinition(String, String, String),
+
+    #[error("Error while trying to convert a {0} into a {1}: the sequence has {2} entries, which is more than the new type can hold.")]
+    ConvertingSequenceTooManyEntries(String, String, u32),
+
+    #[error("The hex pattern \"{0}\" is not valid. It must contain an even number of hexadecimal digits, optionally separated by spaces.")]
+    InvalidHexPattern(String),
+
     #[error("There are no definitions for this specific version of the table in the Schema and the table is empty. This means this table cannot be open nor decoded.")]
     DecodingDBNoDefinitionsFoundAndEmptyFile,
 
@@ -361,6 +373,33 @@ pub enum RLibError {
     #[error("You're trying to perform a Global Replace on a type that doesn't support Regex replacement and requires that both, pattern and replacement have the exact same byte lenght. To avoid breaking files this program doesn't allow you to do that. Either make sure both strings have the exact same byte lenght, don't use regex, or use a hexadecimal editor.")]
     GlobalSearchReplaceRequiresSameLenghtAndNotRegex,
 
+    #[error("You're trying to perform a Global Replace over the matches of a hexadecimal search. To avoid breaking files this program doesn't allow you to do that. Use a hexadecimal editor instead.")]
+    GlobalSearchReplaceHexNotSupported,
+
+    #[error("The column {0} doesn't exist in this table.")]
+    TableColumnNotFound(String),
+
+    #[error("This table has no key columns, so its rows cannot be matched by key.")]
+    TableHasNoKeyColumns,
+
+    #[error("The value provided for the column {0} is of type \"{1}\", but the column expects values of type \"{2}\".")]
+    TableColumnWrongFieldType(String, String, String),
+
+    #[error("The file \"{0}\" is not a Loc table.")]
+    FileNotALocTable(String),
+
+    #[error("Renaming \"{0}\" to \"{1}\" would overwrite an existing file.")]
+    RenameCollision(String, String),
+
+    #[error("Renaming \"{0}\" to \"{1}\" would move the table out of its table folder, making it invalid.")]
+    RenameBreaksDBTable(String, String),
+
+    #[error("The diagnostic doesn't have a report at position {0}.")]
+    DiagnosticReportNotFound(usize),
+
+    #[error("The path {0} is marked as read-only in this Pack, so it cannot be edited. You can unmark it in the Pack Settings.")]
+    PathIsReadOnly(String),
+
     #[error("Error in path: {1}. {0}")]
     IOErrorPath(Box<Self>, PathBuf),
 
@@ -65,3 +65,175 @@ fn test_encode_db_no_sqlite() {
 
     assert_eq!(before, after);
 }
+
+#[test]
+fn test_read_header_definition() {
+    let path = "../test_files/test_decode_db";
+    let mut reader = BufReader::new(File::open(path).unwrap());
+    let data_len = reader.len().unwrap();
+    let data = reader.read_slice(data_len as usize, false).unwrap();
+
+    let mut schema = Schema::default();
+    schema.add_definition("test_decode_db", &DB::test_definition());
+
+    let mut decodeable_extra_data = DecodeableExtraData::default();
+    decodeable_extra_data.file_name = Some("test_decode_db");
+    decodeable_extra_data.table_name = Some("test_decode_db");
+    decodeable_extra_data.schema = Some(&schema);
+
+    let db = DB::decode(&mut std::io::Cursor::new(&data), &Some(decodeable_extra_data)).unwrap();
+    let (definition, entry_count) = DB::read_header_definition(&data, "test_decode_db", &schema).unwrap();
+
+    assert_eq!(&definition, db.definition());
+    assert_eq!(entry_count as usize, db.data().len());
+
+    // Tables without definitions must fail.
+    assert!(DB::read_header_definition(&data, "missing_tables", &schema).is_err());
+}
+
+#[test]
+fn test_try_all_versions() {
+    use crate::schema::{Definition, FieldType};
+
+    let path = "../test_files/test_decode_db";
+    let mut reader = BufReader::new(File::open(path).unwrap());
+    let data_len = reader.len().unwrap();
+    let data = reader.read_slice(data_len as usize, false).unwrap();
+
+    let broken_definition = Definition::new_for_tests(5, &[("bool", FieldType::Boolean, false)], &[]);
+
+    let mut schema = Schema::default();
+    schema.add_definition("test_decode_db", &DB::test_definition());
+    schema.add_definition("test_decode_db", &broken_definition);
+
+    let mut decodeable_extra_data = DecodeableExtraData::default();
+    decodeable_extra_data.file_name = Some("test_decode_db");
+    decodeable_extra_data.table_name = Some("test_decode_db");
+    decodeable_extra_data.schema = Some(&schema);
+    let db = DB::decode(&mut std::io::Cursor::new(&data), &Some(decodeable_extra_data)).unwrap();
+
+    let results = DB::try_all_versions(&data, "test_decode_db", &schema);
+    assert_eq!(results.len(), 2);
+
+    let working = results.iter().find(|(version, _)| *version == -100).unwrap();
+    assert_eq!(*working.1.as_ref().unwrap(), db.data().len());
+
+    let broken = results.iter().find(|(version, _)| *version == 5).unwrap();
+    assert!(broken.1.is_err());
+
+    // Tables without definitions return nothing.
+    assert!(DB::try_all_versions(&data, "missing_tables", &schema).is_empty());
+}
+
+#[test]
+fn test_decode_db_undecoded_tail() {
+    use std::io::Cursor;
+
+    let mut data = std::fs::read("../test_files/test_decode_db").unwrap();
+    let data_len = data.len();
+    data.extend_from_slice(&[1, 2, 3]);
+
+    let mut schema = Schema::default();
+    schema.add_definition("test_decode_db", &DB::test_definition());
+
+    let mut decodeable_extra_data = DecodeableExtraData::default();
+    decodeable_extra_data.file_name = Some("test_decode_db");
+    decodeable_extra_data.table_name = Some("test_decode_db");
+    decodeable_extra_data.schema = Some(&schema);
+    decodeable_extra_data.return_incomplete = true;
+
+    // The bytes after the decoded data must be kept, along with the offset where they start.
+    match DB::decode(&mut Cursor::new(data), &Some(decodeable_extra_data)) {
+        Err(RLibError::DecodingTableIncomplete(_, table)) => assert_eq!(table.undecoded_tail(), Some((&[1u8, 2, 3][..], data_len))),
+        _ => panic!("The table must be returned as incomplete."),
+    }
+}
+
+#[test]
+fn test_remove_duplicated_combined_keys() {
+    use crate::files::table::DecodedData;
+    use crate::schema::{Definition, FieldType};
+
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("value", FieldType::I32, false)], &[]);
+    let rows = [("a", 1), ("b", 1), ("a", 2), ("a", 3)].iter()
+        .map(|(key, value)| vec![DecodedData::StringU8(key.to_string()), DecodedData::I32(*value)])
+        .collect::<Vec<_>>();
+
+    let mut table = DB::new(&definition, None, "test_tables");
+    table.set_data(&rows).unwrap();
+    assert_eq!(table.remove_duplicated_combined_keys(false, None), vec![2, 3]);
+    assert_eq!(table.data().to_vec(), vec![rows[0].clone(), rows[1].clone()]);
+
+    let mut table = DB::new(&definition, None, "test_tables");
+    table.set_data(&rows).unwrap();
+    assert_eq!(table.remove_duplicated_combined_keys(true, None), vec![0, 2]);
+    assert_eq!(table.data().to_vec(), vec![rows[1].clone(), rows[3].clone()]);
+
+    // Limited to some rows, the rest of the rows must be left untouched, even if they're duplicated.
+    let mut table = DB::new(&definition, None, "test_tables");
+    table.set_data(&rows).unwrap();
+    assert_eq!(table.remove_duplicated_combined_keys(true, Some(&[0, 2])), vec![0]);
+    assert_eq!(table.data().to_vec(), vec![rows[1].clone(), rows[2].clone(), rows[3].clone()]);
+}
+
+#[test]
+fn test_tsv_import_to_binary_path() {
+    use std::io::Cursor;
+
+    use crate::files::table::DecodedData;
+    use crate::schema::{Definition, FieldType};
+
+    let definition = Definition::new_for_tests(3, &[("key", FieldType::StringU8, true), ("value", FieldType::I32, false)], &[]);
+
+    let mut schema = Schema::default();
+    schema.add_definition("test_tsv_import_tables", &definition);
+
+    let rows = vec![
+        vec![DecodedData::StringU8("key_1".to_owned()), DecodedData::I32(1)],
+        vec![DecodedData::StringU8("key_2".to_owned()), DecodedData::I32(2)],
+    ];
+
+    let mut db = DB::new(&definition, None, "test_tsv_import_tables");
+    db.set_data(&rows).unwrap();
+
+    let mut rfile = RFile::new_from_decoded(&RFileDecoded::DB(db), 0, "db/test_tsv_import_tables/test");
+    let tsv_path = std::env::temp_dir().join("rpfm_test_db_tsv_import_to_binary_path.tsv");
+    let binary_path = std::env::temp_dir().join("rpfm_test_db_tsv_import_to_binary_path");
+    let temp_path = std::env::temp_dir().join("rpfm_test_db_tsv_import_to_binary_path.tmp");
+    rfile.tsv_export_to_path(&tsv_path, &schema, false, &None).unwrap();
+
+    let mut extra_data = EncodeableExtraData::default();
+    extra_data.set_table_has_guid(true);
+    let extra_data = Some(extra_data);
+
+    let path = RFile::tsv_import_to_binary_path(&tsv_path, &binary_path, &Some(schema.clone()), &extra_data).unwrap();
+    let data = std::fs::read(&binary_path).unwrap();
+    assert_eq!(path, "db/test_tsv_import_tables/test");
+    assert!(!temp_path.exists());
+
+    // The header must carry the GUID, the definition's version and the real entry count.
+    let (version, _, guid, entry_count) = DB::read_header(&mut Cursor::new(&data)).unwrap();
+    assert_eq!(version, 3);
+    assert!(!guid.is_empty());
+    assert_eq!(entry_count, 2);
+
+    let mut decodeable_extra_data = DecodeableExtraData::default();
+    decodeable_extra_data.file_name = Some("test");
+    decodeable_extra_data.table_name = Some("test_tsv_import_tables");
+    decodeable_extra_data.schema = Some(&schema);
+
+    let imported = DB::decode(&mut Cursor::new(&data), &Some(decodeable_extra_data)).unwrap();
+    assert_eq!(imported.data().to_vec(), rows);
+
+    // A failing import must leave the existing destination untouched, and no temporary file behind.
+    let mut tsv = std::fs::read_to_string(&tsv_path).unwrap();
+    tsv.push_str("\nkey_3\tnot_a_number");
+    std::fs::write(&tsv_path, tsv).unwrap();
+
+    assert!(RFile::tsv_import_to_binary_path(&tsv_path, &binary_path, &Some(schema), &extra_data).is_err());
+    assert_eq!(std::fs::read(&binary_path).unwrap(), data);
+    assert!(!temp_path.exists());
+
+    let _ = std::fs::remove_file(&tsv_path);
+    let _ = std::fs::remove_file(&binary_path);
+}
@@ -46,11 +46,11 @@ use std::borrow::Cow;
 #[cfg(test)] use std::collections::BTreeMap;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::SeekFrom;
+use std::io::{Cursor, Seek, SeekFrom};
 
 use crate::binary::{ReadBytes, WriteBytes};
 use crate::error::{RLibError, Result};
-use crate::files::{Container, ContainerPath, DecodeableExtraData, Decodeable, EncodeableExtraData, Encodeable, FileType, table::{DecodedData, Table}, pack::Pack, RFileDecoded};
+use crate::files::{Container, ContainerPath, DecodeableExtraData, Decodeable, EncodeableExtraData, Encodeable, FileType, table::{DecodedData, FormatOptions, Table}, pack::Pack, RFileDecoded};
 #[cfg(test)] use crate::schema::FieldType;
 use crate::schema::{Definition, DefinitionPatch, Field, Schema};
 use crate::utils::check_size_mismatch;
@@ -146,10 +146,8 @@ impl Decodeable for DB {
 
         // If we are not in the last byte, it means we didn't parse the entire file, which means this file is corrupt, or the decoding failed and we bailed early.
         //
-        // If we have return_incomplete enabled, we pass whatever we got decoded into this error.
-        check_size_mismatch(data.stream_position()? as usize, len as usize).map_err(|error| {
-            RLibError::DecodingTableIncomplete(error.to_string(), table.clone())
-        })?;
+        // If we have return_incomplete enabled, we pass whatever we got decoded into this error, along with the bytes we didn't decode.
+        let table = table.check_decoded_to_end(data)?;
 
         // If we've reached this, we've successfully decoded the table.
         Ok(Self {
@@ -241,6 +239,120 @@ impl DB {
         Ok((version, mysterious_byte, guid, entry_count))
     }
 
+    /// This function reads the header of a `DB` from its raw data, and returns the definition it needs to be decoded and its entry count.
+    ///
+    /// This only reads the header, so it's way faster than a full decode. The only exception are version 0 tables with more than one
+    /// candidate definition, as in those the only way to know the correct definition is to try to decode them with each one of them.
+    pub fn read_header_definition(data: &[u8], table_name: &str, schema: &Schema) -> Result<(Definition, u32)> {
+        let mut data = Cursor::new(data);
+        let (version, _, _, entry_count) = Self::read_header(&mut data)?;
+
+        let definitions = schema.definitions_by_table_name(table_name).ok_or({
+            if entry_count == 0 {
+                RLibError::DecodingDBNoDefinitionsFoundAndEmptyFile
+            } else {
+                RLibError::DecodingDBNoDefinitionsFound
+            }
+        })?;
+
+        let definition = if version == 0 {
+            let candidates = definitions.iter()
+                .filter(|definition| *definition.version() < 1)
+                .collect::<Vec<_>>();
+
+            if candidates.len() == 1 {
+                candidates[0]
+            } else {
+                let index_reset = data.stream_position()?;
+                let len = data.len()?;
+
+                let mut working_definition = Err(RLibError::DecodingDBNoDefinitionsFound);
+                for definition in candidates {
+                    data.seek(SeekFrom::Start(index_reset))?;
+                    let db = Table::decode_table(&mut data, definition, Some(entry_count), false);
+                    if db.is_ok() && data.stream_position()? == len {
+                        working_definition = Ok(definition);
+                        break;
+                    }
+                }
+
+                working_definition?
+            }
+        } else {
+            definitions.iter()
+                .find(|definition| *definition.version() == version)
+                .ok_or(RLibError::DecodingDBNoDefinitionsFound)?
+        };
+
+        Ok((definition.clone(), entry_count))
+    }
+
+    /// This function tries to decode the provided raw `DB` data with every definition available in the schema for the provided table,
+    /// ignoring the version in the table's header.
+    ///
+    /// It returns, for each version, the amount of rows decoded if the definition managed to decode the entire file,
+    /// or the error it hit if it didn't. If the data decodes but leaves bytes unread, the error contains how many bytes were consumed.
+    ///
+    /// If the header cannot be read or there are no definitions for the table, this returns an empty list.
+    pub fn try_all_versions(data: &[u8], table_name: &str, schema: &Schema) -> Vec<(i32, Result<usize>)> {
+        let mut data = Cursor::new(data);
+        let entry_count = match Self::read_header(&mut data) {
+            Ok((_, _, _, entry_count)) => entry_count,
+            Err(_) => return vec![],
+        };
+
+        let definitions = match schema.definitions_by_table_name(table_name) {
+            Some(definitions) => definitions,
+            None => return vec![],
+        };
+
+        let index_reset = data.position();
+        let len = data.get_ref().len();
+
+        definitions.iter()
+            .map(|definition| {
+                data.set_position(index_reset);
+                let result = Table::decode_table(&mut data, definition, Some(entry_count), false)
+                    .and_then(|rows| {
+                        check_size_mismatch(data.position() as usize, len)?;
+                        Ok(rows.len())
+                    });
+
+                (*definition.version(), result)
+            })
+            .collect()
+    }
+
+    /// This function returns the names of the tables in the schema that can decode the provided raw `DB` data, sorted.
+    ///
+    /// Only the definition matching the version in the table's header is tried for each table, and the data must be decoded entirely.
+    /// Useful to find out which table a file really is when it cannot be decoded as the table its path says it is.
+    pub fn table_names_for_data(data: &[u8], schema: &Schema) -> Vec<String> {
+        let mut data = Cursor::new(data);
+        let (version, entry_count) = match Self::read_header(&mut data) {
+            Ok((version, _, _, entry_count)) => (version, entry_count),
+            Err(_) => return vec![],
+        };
+
+        let index_reset = data.position();
+        let len = data.get_ref().len();
+
+        let mut table_names = schema.definitions()
+            .iter()
+            .filter(|(_, definitions)| definitions.iter()
+                .filter(|definition| if version == 0 { *definition.version() < 1 } else { *definition.version() == version })
+                .any(|definition| {
+                    data.set_position(index_reset);
+                    Table::decode_table(&mut data, definition, Some(entry_count), false).is_ok() && data.position() as usize == len
+                })
+            )
+            .map(|(table_name, _)| table_name.to_owned())
+            .collect::<Vec<_>>();
+
+        table_names.sort();
+        table_names
+    }
+
     /// This function returns a reference of the definition of this DB Table.
     pub fn definition(&self) -> &Definition {
         self.table.definition()
@@ -286,6 +398,13 @@ impl DB {
         self.table.set_data(data)
     }
 
+    /// This function removes the rows whose combined key is already used by another row, keeping only the first or the last row of each key.
+    ///
+    /// If `rows` is provided, only those rows are taken into account. Returns the positions the removed rows had before removing them, sorted.
+    pub fn remove_duplicated_combined_keys(&mut self, keep_last: bool, rows: Option<&[usize]>) -> Vec<usize> {
+        self.table.remove_duplicated_combined_keys(keep_last, rows)
+    }
+
     /// This function returns a valid empty (with default values if any) row for this table.
     pub fn new_row(&self) -> Vec<DecodedData> {
         Table::new_row(self.definition(), Some(self.patches()))
@@ -563,9 +682,16 @@ impl DB {
         Ok(db)
     }
 
-    /// This function imports a TSV file into a decoded table.
-    pub fn tsv_export(&self, writer: &mut Writer<File>, table_path: &str, keys_first: bool) -> Result<()> {
-        self.table.tsv_export(writer, table_path, keys_first)
+    /// This function exports a decoded table into a TSV file. If columns are provided, only them and the key columns are exported.
+    pub fn tsv_export(&self, writer: &mut Writer<File>, table_path: &str, keys_first: bool, format_options: &FormatOptions, columns: Option<&[String]>, trim_trailing_newline: bool) -> Result<()> {
+        self.table.tsv_export(writer, table_path, keys_first, format_options, columns, trim_trailing_newline)
+    }
+
+    /// This function overwrites the provided columns of this table with the ones of the rows of the source table with the same key.
+    ///
+    /// Check [Table::merge_columns_by_key] for more info.
+    pub fn merge_columns_by_key(&mut self, source: &Self, columns: &[String]) -> Result<usize> {
+        self.table.merge_columns_by_key(&source.table, columns)
     }
 }
 
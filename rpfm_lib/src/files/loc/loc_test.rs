@@ -38,3 +38,143 @@ fn test_encode_loc_no_sqlite() {
 
     assert_eq!(before, after);
 }
+
+#[test]
+fn test_tsv_merge_columns_from_path() {
+    use crate::files::table::DecodedData;
+    use crate::schema::Schema;
+
+    let row = |key: &str, text: &str, tooltip: bool| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(tooltip)];
+
+    let mut original = Loc::new();
+    original.set_data(&[row("key_1", "Text 1", false), row("key_2", "Text 2", false)]).unwrap();
+    let mut original = RFile::new_from_decoded(&RFileDecoded::Loc(original), 0, "text/test.loc");
+
+    // Someone else edits both columns, but only owns the text one, so only that one is exported.
+    let mut edited = Loc::new();
+    edited.set_data(&[row("key_2", "Text 2 edited", true), row("key_3", "Text 3", true)]).unwrap();
+    let mut edited = RFile::new_from_decoded(&RFileDecoded::Loc(edited), 0, "text/test.loc");
+
+    let mut extra_data = EncodeableExtraData::default();
+    extra_data.set_tsv_columns(Some(vec!["text".to_owned()]));
+
+    let path = std::env::temp_dir().join("rpfm_test_tsv_merge_columns.tsv");
+    edited.tsv_export_to_path(&path, &Schema::default(), true, &Some(extra_data)).unwrap();
+    let header = std::fs::read_to_string(&path).unwrap().lines().next().unwrap().to_owned();
+    assert_eq!(header, "key\ttext");
+
+    // Only the exported column of the matching rows must change.
+    let changed = original.tsv_merge_columns_from_path(&path, &None).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(changed, 1);
+
+    match original.decoded().unwrap() {
+        RFileDecoded::Loc(loc) => assert_eq!(loc.data().to_vec(), vec![row("key_1", "Text 1", false), row("key_2", "Text 2 edited", false)]),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_tsv_import_to_binary_path() {
+    use crate::files::table::DecodedData;
+    use crate::schema::Schema;
+
+    // More rows than fit in a batch, so the import has to encode more than one.
+    let rows = (0..TSV_IMPORT_BATCH_SIZE + 10)
+        .map(|index| vec![DecodedData::StringU16(format!("key_{index}")), DecodedData::StringU16(format!("Text {index}")), DecodedData::Boolean(index % 2 == 0)])
+        .collect::<Vec<_>>();
+
+    let mut loc = Loc::new();
+    loc.set_data(&rows).unwrap();
+    let mut expected = vec![];
+    loc.encode(&mut expected, &None).unwrap();
+
+    let mut rfile = RFile::new_from_decoded(&RFileDecoded::Loc(loc), 0, "text/test.loc");
+    let tsv_path = std::env::temp_dir().join("rpfm_test_tsv_import_to_binary_path.tsv");
+    let binary_path = std::env::temp_dir().join("rpfm_test_tsv_import_to_binary_path.loc");
+    rfile.tsv_export_to_path(&tsv_path, &Schema::default(), true, &None).unwrap();
+
+    // The streamed file must be exactly what encoding the whole table produces.
+    let path = RFile::tsv_import_to_binary_path(&tsv_path, &binary_path, &None, &None).unwrap();
+    let data = std::fs::read(&binary_path).unwrap();
+    let _ = std::fs::remove_file(&tsv_path);
+    let _ = std::fs::remove_file(&binary_path);
+
+    assert_eq!(path, "text/test.loc");
+    assert_eq!(data, expected);
+}
+
+#[test]
+fn test_tsv_export_trim_trailing_newline() {
+    use crate::files::table::DecodedData;
+    use crate::schema::Schema;
+
+    let tsv_path = std::env::temp_dir().join("rpfm_test_tsv_export_trim_trailing_newline.tsv");
+    let binary_path = std::env::temp_dir().join("rpfm_test_tsv_export_trim_trailing_newline.loc");
+
+    let mut extra_data = EncodeableExtraData::default();
+    extra_data.set_trim_trailing_newline(true);
+    let extra_data = Some(extra_data);
+
+    // Tables without rows end on their metadata line, so that's the one that must lose its newline.
+    for rows in [vec![], vec![vec![DecodedData::StringU16("key_1".to_owned()), DecodedData::StringU16("Text 1".to_owned()), DecodedData::Boolean(true)]]] {
+        let mut loc = Loc::new();
+        loc.set_data(&rows).unwrap();
+        let mut expected = vec![];
+        loc.encode(&mut expected, &None).unwrap();
+
+        let mut rfile = RFile::new_from_decoded(&RFileDecoded::Loc(loc), 0, "text/test.loc");
+        rfile.tsv_export_to_path(&tsv_path, &Schema::default(), true, &None).unwrap();
+        let with_newline = std::fs::read_to_string(&tsv_path).unwrap();
+
+        rfile.tsv_export_to_path(&tsv_path, &Schema::default(), true, &extra_data).unwrap();
+        let without_newline = std::fs::read_to_string(&tsv_path).unwrap();
+
+        assert!(with_newline.ends_with('\n'));
+        assert_eq!(without_newline, with_newline[..with_newline.len() - 1]);
+
+        // The file without the newline must still import to the same table.
+        RFile::tsv_import_to_binary_path(&tsv_path, &binary_path, &None, &None).unwrap();
+        assert_eq!(std::fs::read(&binary_path).unwrap(), expected);
+    }
+
+    let _ = std::fs::remove_file(&tsv_path);
+    let _ = std::fs::remove_file(&binary_path);
+}
+
+#[test]
+fn test_decode_loc_undecoded_tail() {
+    use std::io::Cursor;
+
+    use crate::error::RLibError;
+    use crate::files::table::DecodedData;
+
+    let row = |key: &str, text: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(false)];
+
+    let mut loc = Loc::new();
+    loc.set_data(&[row("key_1", "Text 1")]).unwrap();
+    let mut one_row = vec![];
+    loc.encode(&mut one_row, &None).unwrap();
+
+    loc.set_data(&[row("key_1", "Text 1"), row("key_2", "Text 2")]).unwrap();
+    let mut data = vec![];
+    loc.encode(&mut data, &None).unwrap();
+
+    // Break the last row, so it fails to decode.
+    data.pop();
+
+    let mut decodeable_extra_data = DecodeableExtraData::default();
+    decodeable_extra_data.return_incomplete = true;
+
+    // The failing row must be left undecoded, with the tail starting where it starts.
+    match Loc::decode(&mut Cursor::new(data.to_vec()), &Some(decodeable_extra_data)) {
+        Err(RLibError::DecodingTableIncomplete(_, table)) => {
+            assert_eq!(table.data().to_vec(), vec![row("key_1", "Text 1")]);
+            assert_eq!(table.undecoded_tail(), Some((&data[one_row.len()..], one_row.len())));
+        }
+        _ => panic!("The table must be returned as incomplete."),
+    }
+
+    // Without return_incomplete, it must just fail.
+    assert!(Loc::decode(&mut Cursor::new(data), &Some(DecodeableExtraData::default())).is_err());
+}
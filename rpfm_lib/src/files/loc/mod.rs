@@ -45,7 +45,7 @@ use std::fs::File;
 
 use crate::binary::{ReadBytes, WriteBytes};
 use crate::error::{RLibError, Result};
-use crate::files::{DecodeableExtraData, Decodeable, EncodeableExtraData, Encodeable, table::{DecodedData, Table}};
+use crate::files::{DecodeableExtraData, Decodeable, EncodeableExtraData, Encodeable, table::{DecodedData, FormatOptions, Table}};
 use crate::schema::*;
 use crate::utils::check_size_mismatch;
 
@@ -209,24 +209,39 @@ impl Loc {
         Ok(loc)
     }
 
-    /// This function exports a decoded Loc file into a TSV file.
-    pub fn tsv_export(&self, writer: &mut Writer<File>, table_path: &str) -> Result<()> {
-        self.table.tsv_export(writer, table_path, true)
+    /// This function exports a decoded Loc file into a TSV file. If columns are provided, only them and the key column are exported.
+    pub fn tsv_export(&self, writer: &mut Writer<File>, table_path: &str, format_options: &FormatOptions, columns: Option<&[String]>, trim_trailing_newline: bool) -> Result<()> {
+        self.table.tsv_export(writer, table_path, true, format_options, columns, trim_trailing_newline)
+    }
+
+    /// This function overwrites the provided columns of this Loc with the ones of the rows of the source Loc with the same key.
+    ///
+    /// Check [Table::merge_columns_by_key] for more info.
+    pub fn merge_columns_by_key(&mut self, source: &Self, columns: &[String]) -> Result<usize> {
+        self.table.merge_columns_by_key(&source.table, columns)
     }
 }
 
 impl Decodeable for Loc {
 
-    fn decode<R: ReadBytes>(data: &mut R, _extra_data: &Option<DecodeableExtraData>) -> Result<Self> {
+    fn decode<R: ReadBytes>(data: &mut R, extra_data: &Option<DecodeableExtraData>) -> Result<Self> {
+        let return_incomplete = extra_data.as_ref().map(|extra_data| extra_data.return_incomplete).unwrap_or(false);
 
         // Version is always 1, so we ignore it.
         let (_version, entry_count) = Self::read_header(data)?;
 
         let definition = Self::new_definition();
-        let table = Table::decode(data, &definition, &HashMap::new(), Some(entry_count), false, TSV_NAME_LOC)?;
+        let table = Table::decode(data, &definition, &HashMap::new(), Some(entry_count), return_incomplete, TSV_NAME_LOC)?;
 
         // If we are not in the last byte, it means we didn't parse the entire file, which means this file is corrupt.
-        check_size_mismatch(data.stream_position()? as usize, data.len()? as usize)?;
+        //
+        // If we have return_incomplete enabled, we pass whatever we got decoded into this error, along with the bytes we didn't decode.
+        let table = if return_incomplete {
+            table.check_decoded_to_end(data)?
+        } else {
+            check_size_mismatch(data.stream_position()? as usize, data.len()? as usize)?;
+            table
+        };
 
         Ok(Self {
             table,
@@ -78,7 +78,7 @@
 //! [`Video`]: crate::files::video::Video
 
 
-use csv::{QuoteStyle, ReaderBuilder, WriterBuilder};
+use csv::{QuoteStyle, ReaderBuilder, StringRecord, WriterBuilder};
 use getset::*;
 #[cfg(feature = "integration_log")] use log::warn;
 use rayon::prelude::*;
@@ -121,6 +121,7 @@ use self::pack::{Pack, RESERVED_NAME_SETTINGS, RESERVED_NAME_NOTES};
 use self::portrait_settings::PortraitSettings;
 use self::rigidmodel::RigidModel;
 use self::sound_bank::SoundBank;
+use self::table::{FormatOptions, Table};
 use self::text::Text;
 use self::uic::UIC;
 use self::unit_variant::UnitVariant;
@@ -160,6 +161,9 @@ pub mod video;
 
 #[cfg(test)] mod rfile_test;
 
+/// Amount of rows encoded at once by [RFile::tsv_import_to_binary_path].
+const TSV_IMPORT_BATCH_SIZE: usize = 1024;
+
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
 //---------------------------------------------------------------------------//
@@ -324,6 +328,20 @@ pub enum ContainerPath {
     Folder(String),
 }
 
+/// This struct contains the results of a batch TSV import or export over a folder.
+///
+/// Results are sorted by their path on disk.
+#[derive(Debug, Default, Getters)]
+#[getset(get = "pub")]
+pub struct TsvBatchReport {
+
+    /// Files that were imported/exported successfully, as their path on disk and their path in the container.
+    succeeded: Vec<(PathBuf, ContainerPath)>,
+
+    /// Files that failed to import/export, as their path on disk and the error that made them fail.
+    failed: Vec<(PathBuf, RLibError)>,
+}
+
 /// This is a generic struct to easily pass additional data to a [Decodeable::decode] method.
 ///
 /// To know what you need to provide to each file type, please check their documentation.
@@ -407,6 +425,22 @@ pub struct EncodeableExtraData<'a> {
     /// Only for tables. If we should regenerate the GUID of the table (if it even has one) or keep the current one.
     regenerate_table_guid: bool,
 
+    /// Only for text exports, like TSV. If we should remove the newline at the end of the exported file. By default, exports end with a single newline.
+    trim_trailing_newline: bool,
+
+    /// Only for text exports, like TSV. How to format the cells of tables.
+    tsv_format_options: FormatOptions,
+
+    /// Only for text exports, like TSV. If provided, only these columns (and the key columns) of tables are exported.
+    ///
+    /// The exported file can then be merged back into the table with [RFile::tsv_merge_columns_from_path], without touching the other columns.
+    tsv_columns: Option<Vec<String>>,
+
+    /// Only for Packs. If files with identical data should only be compressed once on save.
+    ///
+    /// Packs cannot share data between files, so this only makes saving faster. The saved Pack is the same.
+    deduplicate_compression: bool,
+
     //-----------------------//
     // Optional config data  //
     //-----------------------//
@@ -493,7 +527,7 @@ pub trait Container {
                             None => destination_path_tsv.set_extension("tsv"),
                         };
 
-                        let result = rfile.tsv_export_to_path(&destination_path_tsv, schema, keys_first);
+                        let result = rfile.tsv_export_to_path(&destination_path_tsv, schema, keys_first, extra_data);
 
                         // If it fails to extract as tsv, extract as binary.
                         if result.is_err() {
@@ -564,7 +598,7 @@ pub trait Container {
                                 None => destination_path_tsv.set_extension("tsv"),
                             };
 
-                            let result = rfile.tsv_export_to_path(&destination_path_tsv, schema, keys_first);
+                            let result = rfile.tsv_export_to_path(&destination_path_tsv, schema, keys_first, extra_data);
 
                             // If it fails to extract as tsv, extract as binary.
                             if result.is_err() {
@@ -609,6 +643,105 @@ pub trait Container {
         Ok(extracted_paths)
     }
 
+    /// This method exports all the DB and Loc files under the provided folder of the container to TSV files on disk,
+    /// replicating the folder structure they have within the container.
+    ///
+    /// If `parallel` is true, the files are exported in parallel. Otherwise, they're exported one by one.
+    ///
+    /// Unlike [Container::extract], files that fail to export are not extracted as binary, but reported in the returned [TsvBatchReport].
+    fn tsv_export_folder(&mut self, container_path_folder: &str, destination_path: &Path, schema: &Schema, keys_first: bool, extra_data: &Option<EncodeableExtraData>, parallel: bool) -> Result<TsvBatchReport> {
+        let mut container_path_folder = container_path_folder.replace('\\', "/");
+        if container_path_folder.starts_with('/') {
+            container_path_folder.remove(0);
+        }
+
+        let mut rfiles = self.files_by_path_mut(&ContainerPath::Folder(container_path_folder), false)
+            .into_iter()
+            .filter(|rfile| rfile.file_type() == FileType::DB || rfile.file_type() == FileType::Loc)
+            .collect::<Vec<_>>();
+
+        let export = |rfile: &mut &mut RFile| {
+            let container_path = rfile.path_in_container();
+
+            // Make sure to NOT replace the extension if there is one, only append to it.
+            let mut destination_path_tsv = destination_path.join(rfile.path_in_container_raw());
+            match destination_path_tsv.extension() {
+                Some(extension) => {
+                    let extension = format!("{}.tsv", extension.to_string_lossy());
+                    destination_path_tsv.set_extension(extension)
+                },
+                None => destination_path_tsv.set_extension("tsv"),
+            };
+
+            // Same as with extract, make sure files on disk are in memory before exporting them.
+            let result = match rfile.data {
+                RFileInnerData::OnDisk(_) => rfile.load(),
+                _ => Ok(()),
+            }.and_then(|_| rfile.tsv_export_to_path(&destination_path_tsv, schema, keys_first, extra_data));
+
+            (destination_path_tsv, container_path, result)
+        };
+
+        let mut results = if parallel {
+            rfiles.par_iter_mut().map(export).collect::<Vec<_>>()
+        } else {
+            rfiles.iter_mut().map(export).collect::<Vec<_>>()
+        };
+
+        results.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+        let mut report = TsvBatchReport::default();
+        for (path, container_path, result) in results {
+            match result {
+                Ok(_) => report.succeeded.push((path, container_path)),
+                Err(error) => report.failed.push((path, error)),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// This method imports all the TSV files within the provided folder (and its subfolders) on disk into the container.
+    ///
+    /// The path each file gets within the container is the one stored in its TSV metadata, like with [RFile::tsv_import_from_path].
+    ///
+    /// If `parallel` is true, the files are parsed in parallel. Otherwise, they're parsed one by one. Insertion into the container is always done
+    /// in order, so in case of path collisions the result is the same in both modes.
+    ///
+    /// Unlike [Container::insert_folder], files that fail to import are not imported as binary, but reported in the returned [TsvBatchReport].
+    fn tsv_import_folder(&mut self, source_path: &Path, schema: &Option<Schema>, parallel: bool) -> Result<TsvBatchReport> {
+        let mut file_paths = files_from_subdir(source_path, true)?
+            .into_iter()
+            .filter(|path| path.extension().map(|extension| extension.to_string_lossy() == "tsv").unwrap_or(false))
+            .collect::<Vec<_>>();
+
+        file_paths.sort();
+
+        let import = |path: &PathBuf| {
+            let result = RFile::tsv_import_from_path(path, schema)
+                .and_then(|mut rfile| rfile.guess_file_type().map(|_| rfile));
+
+            (path.to_owned(), result)
+        };
+
+        let results = if parallel {
+            file_paths.par_iter().map(import).collect::<Vec<_>>()
+        } else {
+            file_paths.iter().map(import).collect::<Vec<_>>()
+        };
+
+        let mut report = TsvBatchReport::default();
+        for (path, result) in results {
+            match result.and_then(|rfile| self.insert(rfile)) {
+                Ok(Some(container_path)) => report.succeeded.push((path, container_path)),
+                Ok(None) => {},
+                Err(error) => report.failed.push((path, error)),
+            }
+        }
+
+        Ok(report)
+    }
+
     /// This method allows us to extract the metadata associated to the provided container as `.json` files.
     ///
     /// Default implementation does nothing.
@@ -1405,6 +1538,17 @@ impl RFile {
         }
     }
 
+    /// This function returns the size in bytes of the raw data of an RFile, if said RFile has not been decoded yet. If it has, it returns None.
+    ///
+    /// Useful for checking how big a file is before decoding it. Note that for compressed files this is the compressed size.
+    pub fn undecoded_size(&self) -> Option<u64> {
+        match self.data {
+            RFileInnerData::Cached(ref data) => Some(data.len() as u64),
+            RFileInnerData::OnDisk(ref data) => Some(data.size),
+            RFileInnerData::Decoded(_) => None,
+        }
+    }
+
     /// This function returns a reference to the decoded data of an RFile, if said RFile has been decoded. If not, it returns an error.
     ///
     /// Useful for accessing preloaded data.
@@ -1982,6 +2126,24 @@ impl RFile {
     ///
     /// It requires the path on disk of the TSV file and the Schema to use. Schema is only needed for DB tables.
     pub fn tsv_import_from_path(path: &Path, schema: &Option<Schema>) -> Result<Self> {
+        Self::tsv_import_from_path_with_columns(path, schema).map(|(rfile, _)| rfile)
+    }
+
+    /// This function merges the columns present in a TSV file on the provided Path into this file, which must be an already decoded DB or Loc.
+    ///
+    /// Rows are matched by their key columns, and only the columns present in the TSV are overwritten. Rows of the TSV not in this file are ignored.
+    /// Useful to import back TSV files exported with only some columns. Returns the amount of rows changed.
+    pub fn tsv_merge_columns_from_path(&mut self, path: &Path, schema: &Option<Schema>) -> Result<usize> {
+        let (imported, columns) = Self::tsv_import_from_path_with_columns(path, schema)?;
+        match (self.decoded_mut()?, imported.decoded()?) {
+            (RFileDecoded::DB(table), RFileDecoded::DB(source)) if table.table_name() == source.table_name() => table.merge_columns_by_key(source, &columns),
+            (RFileDecoded::Loc(table), RFileDecoded::Loc(source)) => table.merge_columns_by_key(source, &columns),
+            _ => Err(RLibError::ImportTSVWrongTypeTable),
+        }
+    }
+
+    /// This function imports a TSV file on the provided Path, returning the file along with the names of the columns present in the TSV.
+    fn tsv_import_from_path_with_columns(path: &Path, schema: &Option<Schema>) -> Result<(Self, Vec<String>)> {
 
         // We want the reader to have no quotes, tab as delimiter and custom headers, because otherwise
         // Excel, Libreoffice and all the programs that edit this kind of files break them on save.
@@ -2001,39 +2163,7 @@ impl RFile {
 
         // Get the record iterator so we can check the metadata from the second row.
         let mut records = reader.records();
-        let (table_type, table_version, file_path) = match records.next() {
-            Some(Ok(record)) => {
-                let metadata = match record.get(0) {
-                    Some(metadata) => metadata.split(';').map(|x| x.to_owned()).collect::<Vec<String>>(),
-                    None => return Err(RLibError::ImportTSVWrongTypeTable),
-                };
-
-                let table_type = match metadata.get(0) {
-                    Some(table_type) => {
-                        let mut table_type = table_type.to_owned();
-                        if table_type.starts_with('#') {
-                            table_type.remove(0);
-                        }
-                        table_type
-                    },
-                    None => return Err(RLibError::ImportTSVWrongTypeTable),
-                };
-
-                let table_version = match metadata.get(1) {
-                    Some(table_version) => table_version.parse::<i32>().map_err(|_| RLibError::ImportTSVInvalidVersion)?,
-                    None => return Err(RLibError::ImportTSVInvalidVersion),
-                };
-
-                let file_path = match metadata.get(2) {
-                    Some(file_path) => file_path.replace('\\', "/"),
-                    None => return Err(RLibError::ImportTSVInvalidOrMissingPath),
-                };
-
-                (table_type, table_version, file_path)
-            }
-            Some(Err(_)) |
-            None => return Err(RLibError::ImportTSVIncorrectRow(1, 0)),
-        };
+        let (table_type, table_version, file_path) = Self::tsv_import_metadata(records.next())?;
 
         // Once we get the metadata, we know what kind of file we have. Create it and pass the records.
         let decoded = match &*table_type {
@@ -2054,14 +2184,149 @@ impl RFile {
             }
         };
 
+        let mut columns = field_order.into_iter().collect::<Vec<_>>();
+        columns.sort_by_key(|(position, _)| *position);
+
         let rfile = RFile::new_from_decoded(&decoded, 0, &file_path);
-        Ok(rfile)
+        Ok((rfile, columns.into_iter().map(|(_, column)| column).collect()))
+    }
+
+    /// This function parses the metadata row of a TSV file, returning the table type, the table version and the path of the file.
+    fn tsv_import_metadata(record: Option<std::result::Result<StringRecord, csv::Error>>) -> Result<(String, i32, String)> {
+        let record = match record {
+            Some(Ok(record)) => record,
+            Some(Err(_)) |
+            None => return Err(RLibError::ImportTSVIncorrectRow(1, 0)),
+        };
+
+        let metadata = match record.get(0) {
+            Some(metadata) => metadata.split(';').map(|x| x.to_owned()).collect::<Vec<String>>(),
+            None => return Err(RLibError::ImportTSVWrongTypeTable),
+        };
+
+        let table_type = match metadata.get(0) {
+            Some(table_type) => {
+                let mut table_type = table_type.to_owned();
+                if table_type.starts_with('#') {
+                    table_type.remove(0);
+                }
+                table_type
+            },
+            None => return Err(RLibError::ImportTSVWrongTypeTable),
+        };
+
+        let table_version = match metadata.get(1) {
+            Some(table_version) => table_version.parse::<i32>().map_err(|_| RLibError::ImportTSVInvalidVersion)?,
+            None => return Err(RLibError::ImportTSVInvalidVersion),
+        };
+
+        let file_path = match metadata.get(2) {
+            Some(file_path) => file_path.replace('\\', "/"),
+            None => return Err(RLibError::ImportTSVInvalidOrMissingPath),
+        };
+
+        Ok((table_type, table_version, file_path))
+    }
+
+    /// This function imports a TSV file on the provided Path directly into a binary DB or Loc file on the destination Path.
+    ///
+    /// Unlike [RFile::tsv_import_from_path], the table is never fully loaded in memory: rows are encoded in batches as they're read,
+    /// so it's meant for big tables. Schema is only needed for DB tables. Returns the path the file had in the container when it was exported.
+    pub fn tsv_import_to_binary_path(source_path: &Path, destination_path: &Path, schema: &Option<Schema>, extra_data: &Option<EncodeableExtraData>) -> Result<String> {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(b'\t')
+            .quoting(false)
+            .has_headers(true)
+            .flexible(true)
+            .from_path(source_path)?;
+
+        let field_order = reader.headers()?
+            .iter()
+            .enumerate()
+            .map(|(x, y)| (x as u32, y.to_owned()))
+            .collect::<HashMap<u32, String>>();
+
+        let mut records = reader.records();
+        let (table_type, table_version, file_path) = Self::tsv_import_metadata(records.next())?;
+
+        // Encode an empty file to get the header. Its last 4 bytes are the entry count, which we patch once we know it.
+        let mut header = vec![];
+        let (mut table, schema_patches) = match &*table_type {
+            loc::TSV_NAME_LOC | loc::TSV_NAME_LOC_OLD => {
+                let mut loc = Loc::new();
+                loc.encode(&mut header, extra_data)?;
+                (loc.table().clone(), None)
+            }
+
+            // Any other name is assumed to be a db table.
+            _ => {
+                let schema = schema.as_ref().ok_or(RLibError::SchemaNotProvided)?;
+                let definition = schema.definition_by_name_and_version(&table_type, table_version).ok_or(RLibError::DecodingDBNoDefinitionsFound)?;
+                let schema_patches = schema.patches_for_table(&table_type);
+                let mut db = DB::new(definition, schema_patches, &table_type);
+                db.encode(&mut header, extra_data)?;
+                (db.table().clone(), schema_patches)
+            }
+        };
+
+        let mut folder_path = destination_path.to_path_buf();
+        folder_path.pop();
+        DirBuilder::new().recursive(true).create(&folder_path)?;
+
+        // Write to a temporary file next to the destination, and only replace the destination once the import succeeded,
+        // so a failed import never leaves a broken or half-written file behind.
+        let mut temp_file_name = destination_path.file_name().map(|name| name.to_os_string()).unwrap_or_default();
+        temp_file_name.push(".tmp");
+        let temp_path = destination_path.with_file_name(temp_file_name);
+
+        let result = (|| -> Result<()> {
+            let mut writer = BufWriter::new(File::create(&temp_path)?);
+            writer.write_all(&header)?;
+
+            let definition = table.definition().clone();
+            let fields_processed = definition.fields_processed();
+            let mut entry_count = 0u32;
+            let mut batch = Vec::with_capacity(TSV_IMPORT_BATCH_SIZE);
+
+            for (row, record) in records.enumerate() {
+                let record = record.map_err(|_| RLibError::ImportTSVIncorrectRow(row, 0))?;
+                batch.push(Table::tsv_import_row(&record, row, &definition, &fields_processed, &field_order, schema_patches)?);
+
+                if batch.len() == TSV_IMPORT_BATCH_SIZE {
+                    table.set_data(&batch)?;
+                    table.encode(&mut writer, &None)?;
+                    entry_count += batch.len() as u32;
+                    batch.clear();
+                }
+            }
+
+            if !batch.is_empty() {
+                table.set_data(&batch)?;
+                table.encode(&mut writer, &None)?;
+                entry_count += batch.len() as u32;
+            }
+
+            writer.seek(SeekFrom::Start(header.len() as u64 - 4))?;
+            writer.write_u32(entry_count)?;
+            writer.flush()?;
+            Ok(())
+        })();
+
+        match result {
+            Ok(_) => std::fs::rename(&temp_path, destination_path)?,
+            Err(error) => {
+                let _ = std::fs::remove_file(&temp_path);
+                return Err(error);
+            }
+        }
+
+        Ok(file_path)
     }
 
     /// This function allows to export a RFile into a TSV file on disk.
     ///
     /// Only supported for DB and Loc files.
-    pub fn tsv_export_to_path(&mut self, path: &Path, schema: &Schema, keys_first: bool) -> Result<()> {
+    pub fn tsv_export_to_path(&mut self, path: &Path, schema: &Schema, keys_first: bool, extra_data: &Option<EncodeableExtraData>) -> Result<()> {
 
         // Make sure the folder actually exists.
         let mut folder_path = path.to_path_buf();
@@ -2077,21 +2342,24 @@ impl RFile {
             .flexible(true)
             .from_path(path)?;
 
-        let mut extra_data = DecodeableExtraData::default();
-        extra_data.set_schema(Some(schema));
+        let mut decode_extra_data = DecodeableExtraData::default();
+        decode_extra_data.set_schema(Some(schema));
 
-        let extra_data = Some(extra_data);
+        let decode_extra_data = Some(decode_extra_data);
 
         // If it fails in decoding, delete the tsv file.
-        let file = self.decode(&extra_data, false, true);
+        let file = self.decode(&decode_extra_data, false, true);
         if let Err(error) = file {
             let _ = std::fs::remove_file(path);
             return Err(error);
         }
 
+        let format_options = extra_data.as_ref().map(|extra_data| extra_data.tsv_format_options().clone()).unwrap_or_default();
+        let columns = extra_data.as_ref().and_then(|extra_data| extra_data.tsv_columns().as_deref());
+        let trim_trailing_newline = extra_data.as_ref().map(|extra_data| *extra_data.trim_trailing_newline()).unwrap_or(false);
         let file = match file?.unwrap() {
-            RFileDecoded::DB(table) => table.tsv_export(&mut writer, self.path_in_container_raw(), keys_first),
-            RFileDecoded::Loc(table) => table.tsv_export(&mut writer, self.path_in_container_raw()),
+            RFileDecoded::DB(table) => table.tsv_export(&mut writer, self.path_in_container_raw(), keys_first, &format_options, columns, trim_trailing_newline),
+            RFileDecoded::Loc(table) => table.tsv_export(&mut writer, self.path_in_container_raw(), &format_options, columns, trim_trailing_newline),
             _ => unimplemented!()
         };
 
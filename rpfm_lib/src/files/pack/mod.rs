@@ -15,21 +15,20 @@ use getset::*;
 use rayon::prelude::*;
 use serde_derive::{Serialize, Deserialize};
 use serde_json::{from_slice, to_string_pretty};
-use itertools::Itertools;
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Cursor, SeekFrom, Write};
+use std::io::{BufReader, BufWriter, Cursor, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use crate::binary::{ReadBytes, WriteBytes};
-use crate::compression::Compressible;
 use crate::error::{RLibError, Result};
 use crate::files::{Container, ContainerPath, Decodeable, DecodeableExtraData, Encodeable, EncodeableExtraData, FileType, Loc, RFile, RFileDecoded, table::DecodedData};
 use crate::games::{GameInfo, pfh_file_type::PFHFileType, pfh_version::PFHVersion};
 use crate::notes::Note;
-use crate::utils::{current_time, last_modified_time_from_file};
+use crate::schema::Schema;
+use crate::utils::{current_time, last_modified_time_from_file, starts_with_case_insensitive};
 
 #[cfg(test)]
 mod pack_test;
@@ -59,6 +58,9 @@ pub const RESERVED_NAME_SETTINGS_EXTRACTED: &str = "settings.rpfm_reserved.json"
 pub const RESERVED_NAME_NOTES: &str = "notes.rpfm_reserved";
 pub const RESERVED_NAME_NOTES_EXTRACTED: &str = "notes.rpfm_reserved.md";
 
+/// Key of the Pack Setting containing the paths that cannot be edited, one per line.
+pub const SETTING_KEY_READONLY_PATHS: &str = "readonly_paths";
+
 /// This is the list of ***Reserved File Names***. They're file names used by RPFM for special purposes.
 pub const RESERVED_RFILE_NAMES: [&str; 3] = [RESERVED_NAME_EXTRA_PACKFILE, RESERVED_NAME_SETTINGS, RESERVED_NAME_NOTES];
 
@@ -167,6 +169,11 @@ pub struct Pack {
 
     /// Settings stored in the Pack itself, to be able to share them between installations.
     settings: PackSettings,
+
+    /// Paths of the files added or edited since the Pack was last open or saved.
+    #[getset(skip)]
+    #[serde(skip)]
+    modified_paths: HashSet<String>,
 }
 
 /// Header of a Pack, containing all the header-related info of said Pack.
@@ -263,6 +270,22 @@ pub struct PackNotes {
     file_notes: HashMap<String, Vec<Note>>,
 }
 
+/// This struct represents a batch of edits over a Pack, created by [Pack::transaction].
+///
+/// Every file touched through it is snapshotted before its first edit, so all the edits can be undone if the batch fails.
+#[derive(Debug)]
+pub struct PackTransaction<'a> {
+
+    /// The Pack being edited.
+    pack: &'a mut Pack,
+
+    /// State of the touched files before their first edit. None means the file didn't exist.
+    snapshots: HashMap<String, Option<RFile>>,
+
+    /// Modified paths of the Pack before the transaction started.
+    modified_paths: HashSet<String>,
+}
+
 //---------------------------------------------------------------------------//
 //                           Structs Implementations
 //---------------------------------------------------------------------------//
@@ -310,9 +333,11 @@ impl Container for Pack {
             Ok(None)
         }
 
-        // If it's not filtered out, add it to the Pack.
+        // If it's not filtered out, add it to the Pack, unless its path is read-only.
         else {
+            self.check_path_not_readonly(path)?;
             self.paths_cache_insert_path(path);
+            self.modified_paths.insert(path.to_owned());
             self.files.insert(path.to_owned(), file);
 
             Ok(Some(path_container))
@@ -327,10 +352,49 @@ impl Container for Pack {
         &self.files
     }
 
+    /// [Pack] implementation doesn't track the files edited through the returned map, as it's also used to move and remove files.
+    ///
+    /// Use [Container::file_mut] or the `files_by_*_mut` methods to edit files, so they get marked as modified.
     fn files_mut(&mut self) -> &mut HashMap<String, RFile> {
         &mut self.files
     }
 
+    /// [Pack] implementation marks the returned file as modified.
+    fn file_mut(&mut self, path: &str, case_insensitive: bool) -> Option<&mut RFile> {
+        let path = if case_insensitive {
+            self.paths.get(&path.to_lowercase())?[0].to_owned()
+        } else {
+            path.to_owned()
+        };
+
+        let file = self.files.get_mut(&path)?;
+        self.modified_paths.insert(path);
+        Some(file)
+    }
+
+    /// [Pack] implementation marks the returned files as modified.
+    fn files_by_type_mut(&mut self, file_types: &[FileType]) -> Vec<&mut RFile> {
+        self.files_mut_filtered(|_, file| file_types.contains(&file.file_type()))
+    }
+
+    /// [Pack] implementation marks the returned files as modified.
+    fn files_by_path_mut(&mut self, path: &ContainerPath, case_insensitive: bool) -> Vec<&mut RFile> {
+        match path {
+            ContainerPath::File(path) => self.file_mut(path, case_insensitive).map(|file| vec![file]).unwrap_or_default(),
+            ContainerPath::Folder(_) => self.files_by_paths_mut(&[path.clone()], case_insensitive),
+        }
+    }
+
+    /// [Pack] implementation marks the returned files as modified.
+    fn files_by_paths_mut(&mut self, paths: &[ContainerPath], case_insensitive: bool) -> Vec<&mut RFile> {
+        self.files_mut_filtered(|file_path, _| Self::path_matches_any(file_path, paths, case_insensitive))
+    }
+
+    /// [Pack] implementation marks the returned files as modified.
+    fn files_by_type_and_paths_mut(&mut self, file_types: &[FileType], paths: &[ContainerPath], case_insensitive: bool) -> Vec<&mut RFile> {
+        self.files_mut_filtered(|file_path, file| file_types.contains(&file.file_type()) && Self::path_matches_any(file_path, paths, case_insensitive))
+    }
+
     fn disk_file_offset(&self) -> u64 {
        self.disk_file_offset
     }
@@ -366,6 +430,10 @@ impl Container for Pack {
                         return Err(RLibError::EmptyDestiny);
                     }
 
+                    // Check this before removing the file, so we don't lose it if it cannot be inserted back.
+                    self.check_path_not_readonly(source_path)?;
+                    self.check_path_not_readonly(destination_path)?;
+
                     self.paths_cache_remove_path(source_path);
                     let mut moved = self.files_mut()
                         .remove(source_path)
@@ -398,6 +466,12 @@ impl Container for Pack {
                         .filter_map(|(path, _)| if path.starts_with(&source_path_end) { Some(path.to_owned()) } else { None })
                         .collect::<Vec<_>>();
 
+                    // Check this before removing the files, so we don't lose them if they cannot be inserted back.
+                    self.check_container_path_not_readonly(&ContainerPath::Folder(source_path.to_owned()))?;
+                    for path in &moved_paths {
+                        self.check_path_not_readonly(&path.replacen(source_path, destination_path, 1))?;
+                    }
+
                     let moved = moved_paths.iter()
                         .filter_map(|x| {
                             self.paths_cache_remove_path(x);
@@ -426,7 +500,9 @@ impl Container for Pack {
 impl Decodeable for Pack {
 
     fn decode<R: ReadBytes>(data: &mut R, extra_data: &Option<DecodeableExtraData>) -> Result<Self> {
-        Self::read(data, extra_data)
+        let mut pack = Self::read(data, extra_data)?;
+        pack.mark_clean();
+        Ok(pack)
     }
 }
 
@@ -571,6 +647,48 @@ impl Pack {
 
     /// This function writes a `Pack` into the provided buffer.
     fn write<W: WriteBytes>(&mut self, buffer: &mut W, extra_data: &Option<EncodeableExtraData>) -> Result<()> {
+        self.add_reserved_files(extra_data)?;
+
+        match self.header.pfh_version {
+            PFHVersion::PFH6 => self.write_pfh6(buffer, extra_data)?,
+            PFHVersion::PFH5 => self.write_pfh5(buffer, extra_data)?,
+            PFHVersion::PFH4 => self.write_pfh4(buffer, extra_data)?,
+            PFHVersion::PFH3 => self.write_pfh3(buffer, extra_data)?,
+            PFHVersion::PFH2 => self.write_pfh2(buffer, extra_data)?,
+            PFHVersion::PFH0 => self.write_pfh0(buffer, extra_data)?,
+        }
+
+        self.remove_reserved_files();
+
+        // If nothing has failed, return success.
+        Ok(())
+    }
+
+    /// This function writes a `Pack` into the provided buffer, streaming the data of its files to the buffer in batches
+    /// instead of encoding all of them in memory before writing them. The result is the same as with `write`.
+    ///
+    /// Only PFH5 and PFH6 Packs support this, and only if compression deduplication is disabled, as it needs all the data at once.
+    /// In any other case, this falls back to `write`.
+    fn write_streamed<W: WriteBytes + Seek>(&mut self, buffer: &mut W, extra_data: &Option<EncodeableExtraData>) -> Result<()> {
+        let deduplicate = extra_data.as_ref().map(|extra_data| extra_data.deduplicate_compression).unwrap_or_default();
+        if deduplicate || (self.header.pfh_version != PFHVersion::PFH6 && self.header.pfh_version != PFHVersion::PFH5) {
+            return self.write(buffer, extra_data);
+        }
+
+        self.add_reserved_files(extra_data)?;
+
+        match self.header.pfh_version {
+            PFHVersion::PFH6 => self.write_pfh6_streamed(buffer, extra_data)?,
+            _ => self.write_pfh5_streamed(buffer, extra_data)?,
+        }
+
+        self.remove_reserved_files();
+
+        Ok(())
+    }
+
+    /// This function adds the reserved files with the notes and settings of the Pack, so they're saved with it.
+    fn add_reserved_files(&mut self, extra_data: &Option<EncodeableExtraData>) -> Result<()> {
         let test_mode = if let Some(extra_data) = extra_data {
             extra_data.test_mode
         } else {
@@ -596,21 +714,13 @@ impl Pack {
             }
         }
 
-        match self.header.pfh_version {
-            PFHVersion::PFH6 => self.write_pfh6(buffer, extra_data)?,
-            PFHVersion::PFH5 => self.write_pfh5(buffer, extra_data)?,
-            PFHVersion::PFH4 => self.write_pfh4(buffer, extra_data)?,
-            PFHVersion::PFH3 => self.write_pfh3(buffer, extra_data)?,
-            PFHVersion::PFH2 => self.write_pfh2(buffer, extra_data)?,
-            PFHVersion::PFH0 => self.write_pfh0(buffer, extra_data)?,
-        }
+        Ok(())
+    }
 
-        // Remove again the reserved Files.
+    /// This function removes the reserved files added by `add_reserved_files`.
+    fn remove_reserved_files(&mut self) {
         self.remove(&ContainerPath::File(RESERVED_NAME_NOTES.to_owned()));
         self.remove(&ContainerPath::File(RESERVED_NAME_SETTINGS.to_owned()));
-
-        // If nothing has failed, return success.
-        Ok(())
     }
 
     //-----------------------------------------------------------------------//
@@ -760,6 +870,143 @@ impl Pack {
         Ok(pack_new)
     }
 
+    /// This function builds a new Mod Pack for the provided game out of a folder on disk. It's the inverse of extracting an entire Pack.
+    ///
+    /// The path of each file within the Pack is its path relative to the provided folder. If a [Schema] is provided,
+    /// tsv files are imported as their corresponding tables. Extracted notes and settings files are loaded back as the Pack's metadata.
+    ///
+    /// Returns the new Pack, and the paths of the files within it that couldn't be classified as any known file type.
+    pub fn from_directory(root: &Path, game: &GameInfo, schema: &Option<Schema>) -> Result<(Self, Vec<ContainerPath>)> {
+        let name = root.file_name()
+            .ok_or_else(|| RLibError::PathMissingFileName(root.to_string_lossy().to_string()))?
+            .to_string_lossy().to_string() + EXTENSION;
+
+        let mut pack = Self::new_with_name_and_version(&name, game.pfh_version_by_file_type(PFHFileType::Mod));
+        pack.set_pfh_file_type(PFHFileType::Mod);
+
+        let mut unknown_paths = pack.insert_folder(root, "", &None, schema, false)?
+            .into_iter()
+            .filter(|path| pack.files().get(path.path_raw()).map(|rfile| rfile.file_type() == FileType::Unknown).unwrap_or_default())
+            .collect::<Vec<_>>();
+        unknown_paths.sort_by(|a, b| a.path_raw().cmp(b.path_raw()));
+
+        Ok((pack, unknown_paths))
+    }
+
+    /// This function splits the Pack into multiple Packs, one per provided path prefix, containing the files whose path starts with said prefix.
+    ///
+    /// Prefixes are compared case-insensitively. Files matching multiple prefixes go to the longest one, and files not matching any prefix
+    /// go to a catch-all Pack under the empty key. Only Packs with files are returned. The new Packs inherit the header,
+    /// compression and dependencies of this one, but not its notes or settings.
+    pub fn split_by_prefix(&self, prefixes: &[&str]) -> BTreeMap<String, Self> {
+        let mut prefixes = prefixes.iter()
+            .map(|prefix| (prefix.to_owned(), prefix.to_lowercase()))
+            .collect::<Vec<_>>();
+        prefixes.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+
+        let mut packs: BTreeMap<String, Self> = BTreeMap::new();
+        for (path, file) in &self.files {
+            let path_lower = path.to_lowercase();
+            let key = prefixes.iter()
+                .find(|(_, prefix)| path_lower.starts_with(prefix))
+                .map(|(prefix, _)| prefix.to_string())
+                .unwrap_or_default();
+
+            let pack = packs.entry(key).or_insert_with(|| Self {
+                header: self.header.clone(),
+                compress: self.compress,
+                dependencies: self.dependencies.clone(),
+                ..Default::default()
+            });
+
+            pack.files.insert(path.to_owned(), file.clone());
+        }
+
+        packs.values_mut().for_each(|pack| pack.paths_cache_generate());
+        packs
+    }
+
+    /// This function renames all files whose path starts with `old_prefix`, replacing said prefix with `new_prefix`.
+    ///
+    /// Returns the performed renames, as (old path, new path). All renames are checked before doing any of them, so if one would
+    /// overwrite a file that's not being renamed, or would move a DB table out of its table folder, nothing is renamed.
+    pub fn rename_by_prefix(&mut self, old_prefix: &str, new_prefix: &str) -> Result<Vec<(ContainerPath, ContainerPath)>> {
+        if old_prefix.is_empty() {
+            return Err(RLibError::EmptyDestiny);
+        }
+
+        let mut renames = self.files.iter()
+            .filter_map(|(path, file)| path.strip_prefix(old_prefix).map(|rest| (path.to_owned(), format!("{new_prefix}{rest}"), file.file_type())))
+            .collect::<Vec<_>>();
+        renames.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let old_paths = renames.iter().map(|(old_path, _, _)| old_path.as_str()).collect::<HashSet<_>>();
+        for (old_path, new_path, file_type) in &renames {
+            if new_path.is_empty() || new_path.ends_with('/') {
+                return Err(RLibError::EmptyDestiny);
+            }
+
+            self.check_path_not_readonly(old_path)?;
+            self.check_path_not_readonly(new_path)?;
+
+            if RESERVED_RFILE_NAMES.contains(&new_path.as_str()) {
+                return Err(RLibError::ReservedFiles);
+            }
+
+            if self.files.contains_key(new_path) && !old_paths.contains(new_path.as_str()) {
+                return Err(RLibError::RenameCollision(old_path.to_owned(), new_path.to_owned()));
+            }
+
+            // DB tables need to stay as direct children of their table folder, or they can no longer be decoded.
+            if *file_type == FileType::DB {
+                let old_folder = old_path.rsplit_once('/').map(|(folder, _)| folder);
+                let new_folder = new_path.rsplit_once('/').map(|(folder, _)| folder);
+                if old_folder != new_folder {
+                    return Err(RLibError::RenameBreaksDBTable(old_path.to_owned(), new_path.to_owned()));
+                }
+            }
+        }
+
+        // Remove all the files first, so renames into paths of other renamed files don't overwrite them.
+        let files = renames.iter()
+            .filter_map(|(old_path, new_path, _)| {
+                self.paths_cache_remove_path(old_path);
+                self.files.remove(old_path).map(|file| (old_path, new_path, file))
+            })
+            .collect::<Vec<_>>();
+
+        let mut renamed = Vec::with_capacity(files.len());
+        for (old_path, new_path, mut file) in files {
+            file.set_path_in_container_raw(new_path);
+            if let Some(new_path) = self.insert(file)? {
+                renamed.push((ContainerPath::File(old_path.to_owned()), new_path));
+            }
+        }
+
+        Ok(renamed)
+    }
+
+    /// This function runs a batch of edits over the Pack as a single transaction.
+    ///
+    /// All edits must be done through the provided [PackTransaction]. If the closure returns an error,
+    /// all the files it touched are restored to the state they had before the transaction, and the error is returned.
+    pub fn transaction<F>(&mut self, f: F) -> Result<()>
+        where F: FnOnce(&mut PackTransaction) -> Result<()> {
+        let modified_paths = self.modified_paths.clone();
+        let mut transaction = PackTransaction {
+            pack: self,
+            snapshots: HashMap::new(),
+            modified_paths,
+        };
+
+        let result = f(&mut transaction);
+        if result.is_err() {
+            transaction.rollback();
+        }
+
+        result
+    }
+
     /// Convenience function to easily save a Pack to disk.
     ///
     /// If a path is provided, the Pack will be saved to that path. Otherwise, it'll use whatever path it had set before.
@@ -778,7 +1025,77 @@ impl Pack {
             Some(EncodeableExtraData::new_from_game_info(game_info))
         };
 
-        self.encode(&mut file, &extra_data)
+        // Stream the data to disk, so we don't need a second copy of all the data in memory.
+        self.write_streamed(&mut file, &extra_data)?;
+        self.mark_clean();
+        Ok(())
+    }
+
+    /// This function returns the paths of the files added or edited since the Pack was last open or saved, sorted.
+    ///
+    /// Files added through [Container::insert] (or moved), and files returned by [Container::file_mut] or the `files_by_*_mut` methods
+    /// are tracked automatically. Files edited in place through [Container::files_mut] need to be marked with [Pack::mark_modified].
+    /// Files removed from the Pack are not returned.
+    pub fn modified_files(&self) -> Vec<ContainerPath> {
+        let mut paths = self.modified_paths.iter()
+            .filter(|path| self.files.contains_key(*path))
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths.into_iter()
+            .map(|path| ContainerPath::File(path.to_owned()))
+            .collect()
+    }
+
+    /// This function marks the files with the provided paths as modified, if they're in the Pack.
+    pub fn mark_modified(&mut self, paths: &[ContainerPath]) {
+        for path in paths {
+            match path {
+                ContainerPath::File(path) => {
+                    if self.files.contains_key(path) {
+                        self.modified_paths.insert(path.to_owned());
+                    }
+                },
+                ContainerPath::Folder(_) => {
+                    let files = self.files_by_path(path, false)
+                        .iter()
+                        .map(|file| file.path_in_container_raw().to_owned())
+                        .collect::<Vec<_>>();
+                    self.modified_paths.extend(files);
+                },
+            }
+        }
+    }
+
+    /// This function clears the list of modified files. Called when the Pack is open or saved.
+    pub fn mark_clean(&mut self) {
+        self.modified_paths.clear();
+    }
+
+    /// This function returns mutable references to the files matching the provided filter, marking them as modified.
+    fn files_mut_filtered<F: Fn(&str, &RFile) -> bool + Send + Sync>(&mut self, filter: F) -> Vec<&mut RFile> {
+        let files = self.files.par_iter_mut()
+            .filter(|(path, file)| filter(path, file))
+            .map(|(_, file)| file)
+            .collect::<Vec<_>>();
+
+        self.modified_paths.extend(files.iter().map(|file| file.path_in_container_raw().to_owned()));
+        files
+    }
+
+    /// This function checks if the provided file path matches any of the provided [ContainerPath].
+    fn path_matches_any(file_path: &str, paths: &[ContainerPath], case_insensitive: bool) -> bool {
+        paths.iter().any(|path| match path {
+            ContainerPath::File(path) => if case_insensitive {
+                caseless::canonical_caseless_match_str(file_path, path)
+            } else {
+                file_path == path
+            },
+            ContainerPath::Folder(path) => if case_insensitive {
+                starts_with_case_insensitive(file_path, path)
+            } else {
+                file_path.starts_with(path)
+            },
+        })
     }
 
     //-----------------------------------------------------------------------//
@@ -893,19 +1210,204 @@ impl Pack {
         }
     }
 
+    /// This function returns the Pack-specific notes, if any.
+    ///
+    /// Notes are stored in a reserved file within the Pack and loaded when opening it, so they can be read without decoding any other file.
+    pub fn pack_notes(&self) -> Option<&str> {
+        if self.notes.pack_notes().is_empty() {
+            None
+        } else {
+            Some(self.notes.pack_notes())
+        }
+    }
+
+    /// This function replaces the Pack-specific notes. They're written to the Pack on the next save.
+    pub fn set_pack_notes(&mut self, notes: &str) {
+        self.notes.set_pack_notes(notes.to_owned());
+    }
+
+    /// This function returns if the provided path is marked as read-only in the Pack Settings.
+    ///
+    /// A path is read-only if it, or any of the folders containing it, is marked as read-only. To mark a whole table, mark its folder (`db/xxx_tables`).
+    pub fn is_path_readonly(&self, path: &str) -> bool {
+        let path = path.to_lowercase();
+        self.settings.readonly_paths().iter().any(|readonly_path| {
+            let readonly_path = readonly_path.to_lowercase();
+            path == readonly_path || path.starts_with(&format!("{}/", readonly_path.trim_end_matches('/')))
+        })
+    }
+
+    /// This function marks or unmarks the provided path (file or folder) as read-only in the Pack Settings.
+    pub fn set_path_readonly(&mut self, path: &str, readonly: bool) {
+        let path = path.trim_end_matches('/');
+        let mut lines = self.settings.setting_text(SETTING_KEY_READONLY_PATHS)
+            .map(|paths| paths.lines().map(|line| line.to_owned()).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        lines.retain(|line| line.starts_with('#') || !line.trim().trim_end_matches('/').eq_ignore_ascii_case(path));
+        if readonly {
+            lines.push(path.to_owned());
+        }
+
+        self.settings.set_setting_text(SETTING_KEY_READONLY_PATHS, &lines.join("\n"));
+    }
+
+    /// This function returns an error if the provided path is marked as read-only.
+    pub fn check_path_not_readonly(&self, path: &str) -> Result<()> {
+        if self.is_path_readonly(path) {
+            Err(RLibError::PathIsReadOnly(path.to_owned()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// This function returns an error if the provided [ContainerPath] is marked as read-only or, if it's a folder, if it contains any read-only path.
+    ///
+    /// Use it before deleting or moving paths, as doing it to a folder affects everything inside it.
+    pub fn check_container_path_not_readonly(&self, path: &ContainerPath) -> Result<()> {
+        match path {
+            ContainerPath::File(path) => self.check_path_not_readonly(path.trim_start_matches('/')),
+            ContainerPath::Folder(path) => {
+                let folder = path.trim_start_matches('/').trim_end_matches('/');
+                let folder_lower = folder.to_lowercase();
+                let contained = self.settings.readonly_paths()
+                    .into_iter()
+                    .find(|readonly_path| folder_lower.is_empty() || readonly_path.to_lowercase().starts_with(&format!("{folder_lower}/")));
+
+                match contained {
+                    Some(readonly_path) => Err(RLibError::PathIsReadOnly(readonly_path)),
+                    None => self.check_path_not_readonly(folder),
+                }
+            }
+        }
+    }
+
     /// This function returns if the Pack is compressible or not.
     pub fn is_compressible(&self) -> bool {
         matches!(self.header.pfh_version, PFHVersion::PFH6 | PFHVersion::PFH5)
     }
 
-    /// This function is used to generate all loc entries missing from a Pack into a missing.loc file.
-    pub fn generate_missing_loc_data(&mut self) -> Result<Option<ContainerPath>> {
+    /// This function generates loc entries for all the loc keys expected by the DB tables of the Pack that are not in any of its Loc files.
+    ///
+    /// The new entries are added to the Loc file at `target_path` with `text` as their text, and the file is created if it doesn't exist.
+    /// Existing entries are never overwritten.
+    ///
+    /// Returns the amount of entries created.
+    pub fn generate_missing_loc_data(&mut self, target_path: &str, text: &str) -> Result<usize> {
+        let missing_keys = self.missing_loc_keys_by_table()
+            .into_values()
+            .flatten()
+            .collect::<BTreeSet<_>>();
+
+        self.add_loc_entries(target_path, &missing_keys, text)
+    }
+
+    /// This function generates loc entries for all the loc keys expected by the DB tables of the Pack that are not in any of its Loc files.
+    ///
+    /// Unlike [Pack::generate_missing_loc_data], the entries of each table are added to the Loc file the provided game
+    /// conventionally uses for said table (see [GameInfo::default_loc_path_for_table]), creating it if it doesn't exist.
+    ///
+    /// Returns the amount of entries created.
+    pub fn generate_missing_loc_data_for_game(&mut self, game_info: &GameInfo, text: &str) -> Result<usize> {
+        let mut missing_keys_by_path: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (table_name, missing_keys) in self.missing_loc_keys_by_table() {
+            missing_keys_by_path.entry(game_info.default_loc_path_for_table(&table_name)).or_default().extend(missing_keys);
+        }
+
+        // Check all the targets before editing any of them, so we don't leave the Pack half-edited.
+        for target_path in missing_keys_by_path.keys() {
+            self.check_path_not_readonly(target_path)?;
+
+            if let Some(file) = self.file(target_path, false) {
+                if file.file_type() != FileType::Loc {
+                    return Err(RLibError::FileNotALocTable(target_path.to_owned()));
+                }
+            }
+        }
+
+        let mut count = 0;
+        for (target_path, missing_keys) in &missing_keys_by_path {
+            count += self.add_loc_entries(target_path, missing_keys, text)?;
+        }
+
+        Ok(count)
+    }
+
+    /// This function returns, for each DB table of the Pack, the loc keys it expects that are not in any of the Loc files of the Pack.
+    ///
+    /// Tables without missing keys are not included.
+    fn missing_loc_keys_by_table(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let loc_keys = self.loc_keys();
+        let missing_keys = self.files_by_type(&[FileType::DB]).par_iter().filter_map(|rfile| {
+            if let Ok(RFileDecoded::DB(table)) = rfile.decoded() {
+                let definition = table.definition();
+                let missing_keys = table.data().iter()
+                    .flat_map(|row| definition.expected_loc_keys_for_row(row, table.table_name()))
+                    .filter(|loc_key| !loc_keys.contains(loc_key))
+                    .collect::<Vec<_>>();
+
+                Some((table.table_name().to_owned(), missing_keys))
+            } else { None }
+        }).collect::<Vec<_>>();
+
+        let mut missing_keys_by_table: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (table_name, missing_keys) in missing_keys {
+            if !missing_keys.is_empty() {
+                missing_keys_by_table.entry(table_name).or_default().extend(missing_keys);
+            }
+        }
+
+        missing_keys_by_table
+    }
+
+    /// This function adds entries with the provided keys and text to the Loc file at `target_path`, creating it if it doesn't exist.
+    ///
+    /// Returns the amount of entries added.
+    fn add_loc_entries(&mut self, target_path: &str, loc_keys: &BTreeSet<String>, text: &str) -> Result<usize> {
+        if loc_keys.is_empty() {
+            return Ok(0);
+        }
+
+        let new_rows = |loc: &Loc| loc_keys.iter().map(|loc_key| {
+            let mut row = loc.new_row();
+            row[0] = DecodedData::StringU16(loc_key.to_owned());
+            row[1] = DecodedData::StringU16(text.to_owned());
+            row
+        }).collect::<Vec<_>>();
+
+        self.check_path_not_readonly(target_path)?;
+
+        match self.file_mut(target_path, false) {
+            Some(file) => {
+                if file.file_type() != FileType::Loc {
+                    return Err(RLibError::FileNotALocTable(target_path.to_owned()));
+                }
+
+                file.decode(&None, true, false)?;
+                match file.decoded_mut() {
+                    Ok(RFileDecoded::Loc(loc)) => {
+                        let mut rows = new_rows(loc);
+                        loc.data_mut().append(&mut rows);
+                    }
+                    _ => return Err(RLibError::FileNotALocTable(target_path.to_owned())),
+                }
+
+                self.mark_modified(&[ContainerPath::File(target_path.to_owned())]);
+            }
+
+            None => {
+                let mut loc = Loc::new();
+                loc.set_data(&new_rows(&loc))?;
+                self.insert(RFile::new_from_decoded(&RFileDecoded::Loc(loc), 0, target_path))?;
+            }
+        }
 
-        let db_tables = self.files_by_type(&[FileType::DB]);
-        let loc_tables = self.files_by_type(&[FileType::Loc]);
-        let mut missing_trads_file = Loc::new();
+        Ok(loc_keys.len())
+    }
 
-        let loc_keys_from_memory = loc_tables.par_iter().filter_map(|rfile| {
+    /// This function returns all the loc keys in the Loc files of the Pack.
+    fn loc_keys(&self) -> HashSet<String> {
+        self.files_by_type(&[FileType::Loc]).par_iter().filter_map(|rfile| {
             if let Ok(RFileDecoded::Loc(table)) = rfile.decoded() {
                 Some(table.data().iter().filter_map(|x| {
                     if let DecodedData::StringU16(data) = &x[0] {
@@ -915,52 +1417,7 @@ impl Pack {
                     }
                 }).collect::<HashSet<String>>())
             } else { None }
-        }).flatten().collect::<HashSet<String>>();
-
-        let missing_trads_file_table_data = db_tables.par_iter().filter_map(|rfile| {
-            if let Ok(RFileDecoded::DB(table)) = rfile.decoded() {
-                let definition = table.definition();
-                let loc_fields = definition.localised_fields();
-                if !loc_fields.is_empty() {
-                    let table_data = table.data();
-                    let table_name = table.table_name_without_tables();
-
-                    // Get the keys, which may be concatenated. We get them IN THE ORDER THEY ARE IN THE BINARY FILE.
-                    let localised_order = definition.localised_key_order();
-                    let mut new_rows = vec![];
-
-                    for row in table_data.iter() {
-                        for loc_field in loc_fields {
-                            let key = localised_order.iter().map(|pos| row[*pos as usize].data_to_string()).join("");
-
-                            // Key can be empty due to incomplete schema. Ignore those.
-                            if !key.is_empty() {
-                                let loc_key = format!("{}_{}_{}", table_name, loc_field.name(), key);
-
-                                if loc_keys_from_memory.get(&*loc_key).is_none() {
-                                    let mut new_row = missing_trads_file.new_row();
-                                    new_row[0] = DecodedData::StringU16(loc_key);
-                                    new_row[1] = DecodedData::StringU16("PLACEHOLDER".to_owned());
-                                    new_rows.push(new_row);
-                                }
-                            }
-                        }
-                    }
-
-                    return Some(new_rows)
-                }
-            }
-            None
-        }).flatten().collect::<Vec<Vec<DecodedData>>>();
-
-        // Save the missing translations to a missing_locs.loc file.
-        let _ = missing_trads_file.set_data(&missing_trads_file_table_data);
-        if !missing_trads_file_table_data.is_empty() {
-            let packed_file = RFile::new_from_decoded(&RFileDecoded::Loc(missing_trads_file), 0,  "text/missing_locs.loc");
-            Ok(self.insert(packed_file)?)
-        } else {
-            Ok(None)
-        }
+        }).flatten().collect::<HashSet<String>>()
     }
 
     /// This function is used to patch Warhammer I & II Siege map packs so their AI actually works.
@@ -1059,6 +1516,80 @@ impl Pack {
     }
 }
 
+impl<'a> PackTransaction<'a> {
+
+    /// This function returns a reference to the Pack being edited.
+    pub fn pack(&self) -> &Pack {
+        self.pack
+    }
+
+    /// This function stores the current state of the file with the provided path, if it hasn't been stored already.
+    ///
+    /// Use it before editing files through means other than the ones provided by the transaction.
+    pub fn snapshot(&mut self, path: &str) {
+        if !self.snapshots.contains_key(path) {
+            let file = self.pack.files.get(path).cloned();
+            self.snapshots.insert(path.to_owned(), file);
+        }
+    }
+
+    /// This function returns a mutable reference to the file with the provided path, if exists, snapshotting it first.
+    pub fn file_mut(&mut self, path: &str) -> Option<&mut RFile> {
+        self.snapshot(path);
+        self.pack.file_mut(path, false)
+    }
+
+    /// This function inserts a file into the Pack, snapshotting whatever file was in its path first.
+    pub fn insert(&mut self, file: RFile) -> Result<Option<ContainerPath>> {
+        self.snapshot(file.path_in_container_raw());
+        self.pack.insert(file)
+    }
+
+    /// This function removes the files matching the provided [ContainerPath] from the Pack, snapshotting them first.
+    pub fn remove(&mut self, path: &ContainerPath) -> Vec<ContainerPath> {
+        let paths = self.pack.files_by_path(path, false)
+            .iter()
+            .map(|file| file.path_in_container_raw().to_owned())
+            .collect::<Vec<_>>();
+
+        paths.iter().for_each(|path| self.snapshot(path));
+        self.pack.remove(path)
+    }
+
+    /// This function renames all files whose path starts with `old_prefix`, snapshotting both, the renamed files and their destinations.
+    ///
+    /// See [Pack::rename_by_prefix] for more info.
+    pub fn rename_by_prefix(&mut self, old_prefix: &str, new_prefix: &str) -> Result<Vec<(ContainerPath, ContainerPath)>> {
+        let paths = self.pack.files.keys()
+            .filter_map(|path| path.strip_prefix(old_prefix).map(|rest| (path.to_owned(), format!("{new_prefix}{rest}"))))
+            .collect::<Vec<_>>();
+
+        for (old_path, new_path) in &paths {
+            self.snapshot(old_path);
+            self.snapshot(new_path);
+        }
+
+        self.pack.rename_by_prefix(old_prefix, new_prefix)
+    }
+
+    /// This function restores all touched files to their state before the transaction.
+    fn rollback(&mut self) {
+        for path in self.snapshots.keys() {
+            self.pack.paths_cache_remove_path(path);
+            self.pack.files.remove(path);
+        }
+
+        // Restore the files directly, so files in read-only paths are restored too.
+        for file in self.snapshots.drain().filter_map(|(_, file)| file) {
+            let path = file.path_in_container_raw().to_owned();
+            self.pack.paths_cache_insert_path(&path);
+            self.pack.files.insert(path, file);
+        }
+
+        self.pack.modified_paths = std::mem::take(&mut self.modified_paths);
+    }
+}
+
 impl PackNotes {
 
     /// This function tries to load the notes from the current Pack and return them.
@@ -1182,6 +1713,17 @@ impl PackSettings {
         self.settings_number.insert(key.to_owned(), value);
     }
 
+    /// This function returns the paths marked as read-only, skipping commented out lines.
+    pub fn readonly_paths(&self) -> Vec<String> {
+        self.settings_text.get(SETTING_KEY_READONLY_PATHS)
+            .map(|paths| paths.lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| line.to_owned())
+                .collect())
+            .unwrap_or_default()
+    }
+
     // TODO: Move this to rpfm_extensions.
     pub fn diagnostics_files_to_ignore(&self) -> Option<Vec<(String, Vec<String>, Vec<String>)>> {
         self.settings_text.get("diagnostics_files_to_ignore").map(|files_to_ignore| {
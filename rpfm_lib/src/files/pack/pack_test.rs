@@ -14,6 +14,7 @@ use std::io::{BufReader, BufWriter};
 use std::fs::File;
 
 use crate::files::*;
+use crate::games::pfh_version::PFHVersion;
 use super::Pack;
 
 #[test]
@@ -276,3 +277,413 @@ fn test_encode_pfh0() {
     assert_eq!(data_pack_1, data_pack_2);
 }
 
+
+#[test]
+fn test_modified_files() {
+    let path = "../test_files/PFH6_test.pack";
+    let mut reader = BufReader::new(File::open(path).unwrap());
+
+    let mut decodeable_extra_data = DecodeableExtraData::default();
+    decodeable_extra_data.disk_file_path = Some(path);
+    decodeable_extra_data.data_size = reader.len().unwrap();
+    decodeable_extra_data.timestamp = last_modified_time_from_file(reader.get_ref()).unwrap();
+
+    // Freshly open Packs have no modified files.
+    let mut pack = Pack::decode(&mut reader, &Some(decodeable_extra_data)).unwrap();
+    assert!(pack.modified_files().is_empty());
+
+    // Inserted files are tracked automatically.
+    let file = RFile::new_from_vec(b"test", FileType::Text, 0, "text/modified.txt");
+    pack.insert(file).unwrap();
+    assert_eq!(pack.modified_files(), vec![ContainerPath::File("text/modified.txt".to_owned())]);
+
+    // Files edited in place need to be marked manually. Marking files that are not in the Pack does nothing.
+    let existing = pack.files().keys().find(|path| *path != "text/modified.txt").cloned().unwrap();
+    pack.mark_modified(&[ContainerPath::File(existing.to_owned()), ContainerPath::File("not/in/the/pack.txt".to_owned())]);
+    assert_eq!(pack.modified_files().len(), 2);
+    assert!(pack.modified_files().contains(&ContainerPath::File(existing.to_owned())));
+
+    // Removed files are not returned.
+    pack.remove(&ContainerPath::File("text/modified.txt".to_owned()));
+    assert_eq!(pack.modified_files(), vec![ContainerPath::File(existing)]);
+
+    pack.mark_clean();
+    assert!(pack.modified_files().is_empty());
+
+    // Files decoded and edited through a mutable reference are tracked automatically.
+    pack.insert(RFile::new_from_vec(b"test", FileType::Text, 0, "text/edited.txt")).unwrap();
+    pack.mark_clean();
+
+    let file = pack.files_by_type_mut(&[FileType::Text]).into_iter().find(|file| file.path_in_container_raw() == "text/edited.txt").unwrap();
+    file.decode(&None, true, false).unwrap();
+    if let Ok(RFileDecoded::Text(text)) = file.decoded_mut() {
+        text.set_contents("edited".to_owned());
+    }
+
+    assert!(pack.modified_files().contains(&ContainerPath::File("text/edited.txt".to_owned())));
+}
+
+#[test]
+fn test_split_by_prefix() {
+    let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+    for path in ["db/a_tables/data", "db/b_tables/data", "UI/skins/test.png", "text/test.txt"] {
+        pack.insert(RFile::new_from_vec(path.as_bytes(), FileType::Unknown, 0, path)).unwrap();
+    }
+
+    let packs = pack.split_by_prefix(&["db/", "db/b_tables/", "ui/", "script/"]);
+
+    // Most specific prefix wins, unmatched files go to the catch-all, and empty Packs are not returned.
+    assert_eq!(packs.keys().collect::<Vec<_>>(), vec!["", "db/", "db/b_tables/", "ui/"]);
+    assert!(packs["db/"].files().contains_key("db/a_tables/data"));
+    assert!(packs["db/b_tables/"].files().contains_key("db/b_tables/data"));
+    assert!(packs["ui/"].files().contains_key("UI/skins/test.png"));
+    assert!(packs[""].files().contains_key("text/test.txt"));
+    assert!(packs.values().all(|pack| pack.files().len() == 1 && pack.pfh_version() == PFHVersion::PFH6));
+
+    // File data must be preserved.
+    assert_eq!(packs["ui/"].files()["UI/skins/test.png"], pack.files()["UI/skins/test.png"]);
+}
+
+#[test]
+fn test_generate_missing_loc_data() {
+    use crate::files::{db::DB, table::DecodedData};
+    use crate::schema::{Definition, FieldType};
+
+    let mut definition = Definition::new_for_tests(0, &[("key", FieldType::StringU8, true)], &[("name", FieldType::StringU8, false)]);
+    definition.set_localised_key_order(vec![0]);
+
+    let mut table = DB::new(&definition, None, "units_tables");
+    table.set_data(&[vec![DecodedData::StringU8("a".to_owned())], vec![DecodedData::StringU8("b".to_owned())]]).unwrap();
+
+    let mut loc = Loc::new();
+    loc.set_data(&[vec![DecodedData::StringU16("units_name_a".to_owned()), DecodedData::StringU16("hello".to_owned()), DecodedData::Boolean(false)]]).unwrap();
+
+    let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+    pack.insert(RFile::new_from_decoded(&RFileDecoded::DB(table), 0, "db/units_tables/test")).unwrap();
+    pack.insert(RFile::new_from_decoded(&RFileDecoded::Loc(loc), 0, "text/existing.loc")).unwrap();
+
+    // Targets that are not Locs are rejected.
+    assert!(pack.generate_missing_loc_data("db/units_tables/test", "").is_err());
+
+    // Only the missing key is added, without touching the existing entries.
+    assert_eq!(pack.generate_missing_loc_data("text/existing.loc", "").unwrap(), 1);
+    match pack.file("text/existing.loc", false).unwrap().decoded().unwrap() {
+        RFileDecoded::Loc(loc) => {
+            let data = loc.data();
+            assert_eq!(data.len(), 2);
+            assert_eq!(data[0][1], DecodedData::StringU16("hello".to_owned()));
+            assert_eq!(data[1][0], DecodedData::StringU16("units_name_b".to_owned()));
+            assert_eq!(data[1][1], DecodedData::StringU16(String::new()));
+        }
+        _ => panic!("File is not a Loc."),
+    }
+
+    // Once generated, there's nothing else missing, so no new file is created.
+    assert_eq!(pack.generate_missing_loc_data("text/new.loc", "PLACEHOLDER").unwrap(), 0);
+    assert!(pack.file("text/new.loc", false).is_none());
+}
+
+#[test]
+fn test_generate_missing_loc_data_for_game() {
+    use crate::files::{db::DB, table::DecodedData};
+    use crate::games::supported_games::{SupportedGames, KEY_EMPIRE, KEY_WARHAMMER_3};
+    use crate::schema::{Definition, FieldType};
+
+    let mut definition = Definition::new_for_tests(0, &[("key", FieldType::StringU8, true)], &[("name", FieldType::StringU8, false)]);
+    definition.set_localised_key_order(vec![0]);
+
+    let new_pack = || {
+        let mut units = DB::new(&definition, None, "units_tables");
+        units.set_data(&[vec![DecodedData::StringU8("a".to_owned())]]).unwrap();
+
+        let mut factions = DB::new(&definition, None, "factions_tables");
+        factions.set_data(&[vec![DecodedData::StringU8("b".to_owned())]]).unwrap();
+
+        let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+        pack.insert(RFile::new_from_decoded(&RFileDecoded::DB(units), 0, "db/units_tables/test")).unwrap();
+        pack.insert(RFile::new_from_decoded(&RFileDecoded::DB(factions), 0, "db/factions_tables/test")).unwrap();
+        pack
+    };
+
+    let loc_keys = |pack: &Pack, path: &str| match pack.file(path, false).unwrap().decoded().unwrap() {
+        RFileDecoded::Loc(loc) => loc.data().iter().map(|row| row[0].data_to_string().to_string()).collect::<Vec<_>>(),
+        _ => panic!("File is not a Loc."),
+    };
+
+    let games = SupportedGames::default();
+
+    // Games with a loc file per table get each table's entries in its own file.
+    let mut pack = new_pack();
+    assert_eq!(pack.generate_missing_loc_data_for_game(games.game(KEY_WARHAMMER_3).unwrap(), "").unwrap(), 2);
+    assert_eq!(loc_keys(&pack, "text/db/units__.loc"), vec!["units_name_a".to_owned()]);
+    assert_eq!(loc_keys(&pack, "text/db/factions__.loc"), vec!["factions_name_b".to_owned()]);
+
+    // Games with a single loc file get all of them there.
+    let mut pack = new_pack();
+    assert_eq!(pack.generate_missing_loc_data_for_game(games.game(KEY_EMPIRE).unwrap(), "").unwrap(), 2);
+    assert_eq!(loc_keys(&pack, "text/localisation.loc"), vec!["factions_name_b".to_owned(), "units_name_a".to_owned()]);
+}
+
+#[test]
+fn test_pack_notes() {
+    let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+    assert_eq!(pack.pack_notes(), None);
+
+    let notes = "Author: Frodo\nVersion: 1.0.0\n\nA test mod.";
+    pack.set_pack_notes(notes);
+
+    let mut data = vec![];
+    pack.encode(&mut data, &None).unwrap();
+
+    // Notes must survive a save, without being exposed as a file in the Pack.
+    let mut decodeable_extra_data = DecodeableExtraData::default();
+    decodeable_extra_data.data_size = data.len() as u64;
+    let pack = Pack::decode(&mut std::io::Cursor::new(data), &Some(decodeable_extra_data)).unwrap();
+    assert_eq!(pack.pack_notes(), Some(notes));
+    assert!(pack.files().is_empty());
+}
+
+#[test]
+fn test_rename_by_prefix() {
+    let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+    for (path, file_type) in [
+        ("text/old/a.txt", FileType::Text),
+        ("text/old/b.txt", FileType::Text),
+        ("text/new/c.txt", FileType::Text),
+        ("db/units_tables/old_data", FileType::DB),
+    ] {
+        pack.insert(RFile::new_from_vec(path.as_bytes(), file_type, 0, path)).unwrap();
+    }
+
+    // Collisions with files not being renamed must fail without renaming anything.
+    pack.insert(RFile::new_from_vec(b"x", FileType::Text, 0, "text/new/a.txt")).unwrap();
+    assert!(pack.rename_by_prefix("text/old/", "text/new/").is_err());
+    assert!(pack.has_file("text/old/a.txt"));
+    assert!(pack.has_file("text/old/b.txt"));
+    pack.remove(&ContainerPath::File("text/new/a.txt".to_owned()));
+
+    let renamed = pack.rename_by_prefix("text/old/", "text/new/").unwrap();
+    assert_eq!(renamed, vec![
+        (ContainerPath::File("text/old/a.txt".to_owned()), ContainerPath::File("text/new/a.txt".to_owned())),
+        (ContainerPath::File("text/old/b.txt".to_owned()), ContainerPath::File("text/new/b.txt".to_owned())),
+    ]);
+    assert!(!pack.has_file("text/old/a.txt"));
+    assert_eq!(pack.files()["text/new/a.txt"].path_in_container_raw(), "text/new/a.txt");
+
+    // Renamed files can take the paths of other renamed files.
+    let renamed = pack.rename_by_prefix("text/new/", "text/new/new/").unwrap();
+    assert_eq!(renamed.len(), 3);
+    assert!(pack.has_file("text/new/new/a.txt"));
+
+    // Tables can be renamed within their table folder, but not moved out of it.
+    assert!(pack.rename_by_prefix("db/units_tables/", "db/other_tables/").is_err());
+    assert!(pack.rename_by_prefix("db/", "mod/db/").is_err());
+    assert!(pack.rename_by_prefix("db/units_tables/old_", "db/units_tables/new_").is_ok());
+    assert!(pack.has_file("db/units_tables/new_data"));
+}
+
+#[test]
+fn test_tsv_export_import_folder() {
+    use crate::files::loc::Loc;
+    use crate::schema::Schema;
+
+    let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+    for path in ["text/db/a.loc", "text/db/b.loc", "text/db/c.loc"] {
+        pack.insert(RFile::new_from_decoded(&RFileDecoded::Loc(Loc::new()), 0, path)).unwrap();
+    }
+    pack.insert(RFile::new_from_vec(b"broken", FileType::Loc, 0, "text/db/broken.loc")).unwrap();
+    pack.insert(RFile::new_from_vec(b"text", FileType::Text, 0, "text/db/d.txt")).unwrap();
+
+    let schema = Schema::default();
+    for parallel in [false, true] {
+        let folder = std::env::temp_dir().join(format!("rpfm_test_tsv_folder_{parallel}"));
+        let _ = std::fs::remove_dir_all(&folder);
+
+        let report = pack.tsv_export_folder("text/", &folder, &schema, false, &None, parallel).unwrap();
+        assert_eq!(report.succeeded().len(), 3);
+        assert_eq!(report.failed().len(), 1);
+        assert_eq!(report.failed()[0].0, folder.join("text/db/broken.loc.tsv"));
+
+        let mut new_pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+        let report = new_pack.tsv_import_folder(&folder, &Some(schema.clone()), parallel).unwrap();
+        assert_eq!(report.succeeded().len(), 3);
+        assert!(report.failed().is_empty());
+        assert!(new_pack.has_file("text/db/a.loc"));
+        assert!(new_pack.has_file("text/db/c.loc"));
+
+        let _ = std::fs::remove_dir_all(&folder);
+    }
+}
+
+#[test]
+fn test_transaction() {
+    let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+    pack.insert(RFile::new_from_vec(b"a", FileType::Text, 0, "text/a.txt")).unwrap();
+    pack.insert(RFile::new_from_vec(b"b", FileType::Text, 0, "text/b.txt")).unwrap();
+    pack.mark_clean();
+
+    // A failure midway must roll back every edit done before it.
+    let result = pack.transaction(|transaction| {
+        assert!(transaction.file_mut("text/a.txt").is_some());
+        transaction.insert(RFile::new_from_vec(b"c", FileType::Text, 0, "text/c.txt"))?;
+        transaction.insert(RFile::new_from_vec(b"new_b", FileType::Text, 0, "text/b.txt"))?;
+        transaction.remove(&ContainerPath::File("text/a.txt".to_owned()));
+        transaction.rename_by_prefix("text/", "other/")?;
+        assert!(transaction.pack().has_file("other/b.txt"));
+
+        Err(RLibError::EmptyDestiny)
+    });
+
+    assert!(result.is_err());
+    assert_eq!(pack.files().len(), 2);
+    assert!(pack.has_file("text/a.txt"));
+    assert!(pack.has_file("text/b.txt"));
+    assert!(!pack.has_file("text/c.txt"));
+    assert!(!pack.has_file("other/b.txt"));
+    assert_eq!(pack.files_mut().get_mut("text/b.txt").unwrap().encode(&None, false, false, true).unwrap().unwrap(), b"b");
+    assert!(pack.modified_files().is_empty());
+
+    // Successful transactions keep their edits.
+    pack.transaction(|transaction| {
+        transaction.insert(RFile::new_from_vec(b"c", FileType::Text, 0, "text/c.txt"))?;
+        Ok(())
+    }).unwrap();
+
+    assert!(pack.has_file("text/c.txt"));
+}
+
+#[test]
+fn test_readonly_paths() {
+    let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+    pack.insert(RFile::new_from_vec(b"a", FileType::Text, 0, "text/a.loc")).unwrap();
+    assert!(!pack.is_path_readonly("text/a.loc"));
+
+    // Folders cover everything inside them, without matching similarly-named folders.
+    pack.set_path_readonly("db/units_tables/", true);
+    assert!(pack.is_path_readonly("db/units_tables/test"));
+    assert!(pack.is_path_readonly("DB/Units_Tables/test"));
+    assert!(!pack.is_path_readonly("db/units_tables_2/test"));
+
+    pack.set_path_readonly("text/a.loc", true);
+    assert!(pack.is_path_readonly("text/a.loc"));
+    assert!(matches!(pack.check_path_not_readonly("text/a.loc"), Err(RLibError::PathIsReadOnly(_))));
+
+    // Unmarking a path keeps the rest.
+    pack.set_path_readonly("db/units_tables", false);
+    assert!(!pack.is_path_readonly("db/units_tables/test"));
+    assert_eq!(pack.settings().readonly_paths(), vec!["text/a.loc".to_owned()]);
+}
+
+#[test]
+fn test_readonly_paths_block_add_delete_rename() {
+    let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+    pack.insert(RFile::new_from_vec(b"a", FileType::Text, 0, "text/a.txt")).unwrap();
+    pack.insert(RFile::new_from_vec(b"b", FileType::Text, 0, "text/b.txt")).unwrap();
+    pack.set_path_readonly("text/a.txt", true);
+
+    // Adding files to read-only paths, including replacing them, must fail.
+    assert!(matches!(pack.insert(RFile::new_from_vec(b"new_a", FileType::Text, 0, "text/a.txt")), Err(RLibError::PathIsReadOnly(_))));
+    pack.insert(RFile::new_from_vec(b"c", FileType::Text, 0, "text/c.txt")).unwrap();
+
+    // Deleting read-only files, or folders containing them, must fail.
+    assert!(pack.check_container_path_not_readonly(&ContainerPath::File("text/a.txt".to_owned())).is_err());
+    assert!(pack.check_container_path_not_readonly(&ContainerPath::Folder("text".to_owned())).is_err());
+    assert!(pack.check_container_path_not_readonly(&ContainerPath::Folder(String::new())).is_err());
+    assert!(pack.check_container_path_not_readonly(&ContainerPath::File("text/b.txt".to_owned())).is_ok());
+    assert!(pack.check_container_path_not_readonly(&ContainerPath::Folder("tex".to_owned())).is_ok());
+
+    // Renaming from or to read-only paths must fail without losing any file.
+    assert!(pack.move_path(&ContainerPath::File("text/a.txt".to_owned()), &ContainerPath::File("text/d.txt".to_owned())).is_err());
+    assert!(pack.move_path(&ContainerPath::File("text/b.txt".to_owned()), &ContainerPath::File("text/a.txt".to_owned())).is_err());
+    assert!(pack.move_path(&ContainerPath::Folder("text".to_owned()), &ContainerPath::Folder("other".to_owned())).is_err());
+    assert!(pack.rename_by_prefix("text/", "other/").is_err());
+    assert!(pack.has_file("text/a.txt"));
+    assert!(pack.has_file("text/b.txt"));
+    assert!(pack.has_file("text/c.txt"));
+
+    // Failed transactions must still restore read-only files.
+    let result = pack.transaction(|transaction| {
+        transaction.remove(&ContainerPath::File("text/a.txt".to_owned()));
+        Err(RLibError::EmptyDestiny)
+    });
+    assert!(result.is_err());
+    assert!(pack.has_file("text/a.txt"));
+}
+
+#[test]
+fn test_from_directory() {
+    use crate::games::{pfh_file_type::PFHFileType, supported_games::{SupportedGames, KEY_WARHAMMER_3}};
+
+    let folder = std::env::temp_dir().join("rpfm_test_from_directory");
+    let _ = std::fs::remove_dir_all(&folder);
+    std::fs::create_dir_all(folder.join("text/db")).unwrap();
+    std::fs::create_dir_all(folder.join("misc")).unwrap();
+    std::fs::write(folder.join("text/db/a.txt"), b"text").unwrap();
+    std::fs::write(folder.join("misc/b.unknown_ext"), b"unknown").unwrap();
+
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+    let (pack, unknown_paths) = Pack::from_directory(&folder, game, &None).unwrap();
+
+    assert_eq!(pack.disk_file_path(), "rpfm_test_from_directory.pack");
+    assert_eq!(pack.pfh_file_type(), PFHFileType::Mod);
+    assert!(pack.has_file("text/db/a.txt"));
+    assert!(pack.has_file("misc/b.unknown_ext"));
+    assert_eq!(unknown_paths, vec![ContainerPath::File("misc/b.unknown_ext".to_owned())]);
+
+    let _ = std::fs::remove_dir_all(&folder);
+}
+
+#[test]
+fn test_encode_deduplicate_compression() {
+    let mut pack = Pack::new_with_name_and_version("test.pack", PFHVersion::PFH6);
+    pack.set_compress(true);
+    for path in ["text/a.txt", "text/b.txt", "text/c.txt"] {
+        pack.insert(RFile::new_from_vec(&[b'a'; 1024], FileType::Text, 0, path)).unwrap();
+    }
+    pack.insert(RFile::new_from_vec(&[b'b'; 1024], FileType::Text, 0, "text/d.txt")).unwrap();
+
+    let mut encodeable_extra_data = EncodeableExtraData::default();
+    encodeable_extra_data.test_mode = true;
+    encodeable_extra_data.nullify_dates = true;
+
+    let mut data = vec![];
+    pack.encode(&mut data, &Some(encodeable_extra_data.clone())).unwrap();
+
+    // Deduplicating the compression must not change the resulting Pack.
+    encodeable_extra_data.deduplicate_compression = true;
+    let mut data_deduplicated = vec![];
+    pack.encode(&mut data_deduplicated, &Some(encodeable_extra_data)).unwrap();
+
+    assert_eq!(data, data_deduplicated);
+}
+
+#[test]
+fn test_write_streamed() {
+    use std::io::Cursor;
+
+    let mut encodeable_extra_data = EncodeableExtraData::default();
+    encodeable_extra_data.test_mode = true;
+    encodeable_extra_data.nullify_dates = true;
+    let extra_data = Some(encodeable_extra_data);
+
+    // Streaming the data must result in the exact same Pack as encoding it all in memory.
+    for pfh_version in [PFHVersion::PFH5, PFHVersion::PFH6] {
+        for compress in [false, true] {
+            let mut pack = Pack::new_with_name_and_version("test.pack", pfh_version);
+            pack.set_compress(compress);
+            for index in 0..2_000usize {
+                let data = (0..(index * 7) % 16_384).map(|byte| (byte * index) as u8).collect::<Vec<u8>>();
+                pack.insert(RFile::new_from_vec(&data, FileType::Text, 0, &format!("text/folder_{}/file_{index}.txt", index % 13))).unwrap();
+            }
+
+            let mut data = vec![];
+            pack.encode(&mut data, &extra_data).unwrap();
+
+            let mut data_streamed = Cursor::new(vec![]);
+            pack.write_streamed(&mut data_streamed, &extra_data).unwrap();
+
+            assert_eq!(data, data_streamed.into_inner());
+        }
+    }
+}
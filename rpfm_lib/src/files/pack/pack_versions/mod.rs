@@ -8,6 +8,18 @@
 // https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
 //---------------------------------------------------------------------------//
 
+use rayon::prelude::*;
+
+use std::collections::HashMap;
+use std::io::{Seek, SeekFrom};
+
+use crate::binary::WriteBytes;
+use crate::compression::Compressible;
+use crate::error::{RLibError, Result};
+use crate::files::{EncodeableExtraData, RFile};
+
+use super::Pack;
+
 // These consts are used for dealing with Time-related operations.
 const WINDOWS_TICK: u64 = 10_000_000;
 const SEC_TO_UNIX_EPOCH: u64 = 11_644_473_600;
@@ -15,9 +27,129 @@ const SEC_TO_UNIX_EPOCH: u64 = 11_644_473_600;
 const SUBHEADER_MARK: u32 = 0x12345678;
 const SUBHEADER_VERSION: u32 = 1;
 
+// Amount of files per thread encoded at once when streaming a Pack to a buffer.
+const STREAMING_BATCH_FILES_PER_THREAD: usize = 4;
+
 mod pfh6;
 mod pfh5;
 mod pfh4;
 mod pfh3;
 mod pfh2;
 mod pfh0;
+
+impl Pack {
+
+    /// This function encodes the provided files, compressing them if compression is enabled and the files support it.
+    ///
+    /// Returns the data of each file, in the same order, and if it has been compressed. If the extra data asks for it,
+    /// files with identical data are only compressed once, and share the compressed result.
+    pub(crate) fn encode_files_data(files: &mut [(String, &mut RFile)], compress: bool, extra_data: &Option<EncodeableExtraData>) -> Result<Vec<(Vec<u8>, bool)>> {
+        let deduplicate = extra_data.as_ref().map(|extra_data| extra_data.deduplicate_compression).unwrap_or_default();
+        if !compress || !deduplicate {
+            return files.par_iter_mut()
+                .map(|(_, file)| {
+
+                    // This unwrap is actually safe.
+                    let data = file.encode(extra_data, false, false, true)?.unwrap();
+                    if compress && file.is_compressible() {
+                        if let Ok(data_compressed) = data.compress() {
+                            return Ok((data_compressed, true));
+                        }
+                    }
+
+                    Ok((data, false))
+                }).collect();
+        }
+
+        let files_data = files.par_iter_mut()
+            .map(|(_, file)| Ok((file.encode(extra_data, false, false, true)?.unwrap(), file.is_compressible())))
+            .collect::<Result<Vec<(Vec<u8>, bool)>>>()?;
+
+        // Only the first file with each distinct data gets compressed. The rest reuse its compressed data.
+        let mut first_by_data: HashMap<&[u8], usize> = HashMap::new();
+        let sources = files_data.iter()
+            .enumerate()
+            .map(|(index, (data, is_compressible))| if *is_compressible {
+                Some(*first_by_data.entry(data.as_slice()).or_insert(index))
+            } else {
+                None
+            })
+            .collect::<Vec<_>>();
+
+        let compressed = first_by_data.into_par_iter()
+            .filter_map(|(data, index)| data.compress().ok().map(|data_compressed| (index, data_compressed)))
+            .collect::<HashMap<usize, Vec<u8>>>();
+
+        Ok(files_data.into_iter()
+            .zip(sources)
+            .map(|((data, _), source)| match source.and_then(|source| compressed.get(&source)) {
+                Some(data_compressed) => (data_compressed.to_vec(), true),
+                None => (data, false),
+            })
+            .collect())
+    }
+
+    /// This function returns the index entry of a file, for Packs with one index entry per file.
+    ///
+    /// The length of the entry only depends on the path and on the Pack having timestamps in its index.
+    pub(crate) fn file_index_entry(path: &str, file: &RFile, data_len: usize, has_been_compressed: bool, has_timestamps: bool, nullify_dates: bool) -> Result<Vec<u8>> {
+        let mut file_index_entry = Vec::with_capacity(Self::file_index_entry_len(path, has_timestamps));
+
+        // Error on files too big for the Pack.
+        if data_len > u32::MAX as usize {
+            return Err(RLibError::DataTooBigForContainer("Pack".to_owned(), u32::MAX as u64, data_len, path.to_owned()));
+        }
+
+        file_index_entry.write_u32(data_len as u32)?;
+
+        if has_timestamps {
+            let timestamp = if nullify_dates {
+                0
+            } else {
+                file.timestamp().unwrap_or(0) as u32
+            };
+            file_index_entry.write_u32(timestamp)?;
+        }
+
+        file_index_entry.write_bool(has_been_compressed)?;
+        file_index_entry.write_string_u8_0terminated(path)?;
+        Ok(file_index_entry)
+    }
+
+    /// This function returns the length of the index entry of a file.
+    pub(crate) fn file_index_entry_len(path: &str, has_timestamps: bool) -> usize {
+
+        // 6 because 4 (size) + 1 (compressed?) + 1 (null), 10 because + 4 (timestamp).
+        if has_timestamps {
+            10 + path.len()
+        } else {
+            6 + path.len()
+        }
+    }
+
+    /// This function writes the file index and the data of the provided files to the buffer, encoding them in batches.
+    ///
+    /// As we don't know the size of each file until we encode it, the space of the index is reserved first, and the index
+    /// is written once all the data has been written. This way, only one batch of files is encoded in memory at any time.
+    pub(crate) fn write_files_streamed<W: WriteBytes + Seek>(buffer: &mut W, files: &mut [(String, &mut RFile)], compress: bool, has_timestamps: bool, nullify_dates: bool, extra_data: &Option<EncodeableExtraData>) -> Result<()> {
+        let index_start = buffer.stream_position()?;
+        let index_len = files.iter().map(|(path, _)| Self::file_index_entry_len(path, has_timestamps)).sum::<usize>();
+        buffer.write_all(&vec![0; index_len])?;
+
+        let mut files_index = Vec::with_capacity(index_len);
+        for batch in files.chunks_mut(rayon::current_num_threads() * STREAMING_BATCH_FILES_PER_THREAD) {
+            let files_data = Self::encode_files_data(batch, compress, extra_data)?;
+            for ((path, file), (data, has_been_compressed)) in batch.iter().zip(files_data) {
+                files_index.write_all(&Self::file_index_entry(path, file, data.len(), has_been_compressed, has_timestamps, nullify_dates)?)?;
+                buffer.write_all(&data)?;
+            }
+        }
+
+        let data_end = buffer.stream_position()?;
+        buffer.seek(SeekFrom::Start(index_start))?;
+        buffer.write_all(&files_index)?;
+        buffer.seek(SeekFrom::Start(data_end))?;
+
+        Ok(())
+    }
+}
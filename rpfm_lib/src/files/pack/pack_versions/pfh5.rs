@@ -13,7 +13,7 @@
 //! All the functions here are internal, so they should be either private or
 //! public only within this crate.
 
-use std::io::{BufReader, Cursor};
+use std::io::{BufReader, Cursor, Seek};
 
 use crate::binary::{ReadBytes, WriteBytes};
 use crate::encryption::Decryptable;
@@ -132,6 +132,8 @@ impl Pack {
             (false, false)
         };
 
+        let has_timestamps = self.header.bitmask.contains(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS);
+
         // We need our files sorted before trying to write them. But we don't want to duplicate
         // them on memory. And we also need to load them to memory on the pack. So...  we do this.
         let mut sorted_files = self.files.iter_mut().map(|(key, file)| (key.replace('/', "\\"), file)).collect::<Vec<(String, &mut RFile)>>();
@@ -139,52 +141,63 @@ impl Pack {
 
         // Optimization: we process the sorted files in parallel, so we can speedup loading/compression.
         // Sadly, this requires us to make a double iterator to actually catch the errors.
-        let (files_index, files_data): (Vec<_>, Vec<_>) = sorted_files.par_iter_mut()
-            .map(|(path, file)| {
-
-                // This unwrap is actually safe.
-                let mut data = file.encode(extra_data, false, false, true)?.unwrap();
-
-                let mut has_been_compressed = false;
-                if self.compress && file.is_compressible() {
-                    if let Ok(data_compressed) = data.compress() {
-                        data = data_compressed;
-                        has_been_compressed = true;
-                    }
-                }
-
-                // 6 because 4 (size) + 1 (compressed?) + 1 (null), 10 because + 4 (timestamp).
-                let file_index_entry_len = if self.header.bitmask.contains(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS) {
-                    10 + path.len()
-                } else {
-                    6 + path.len()
-                };
-
-                let mut file_index_entry = Vec::with_capacity(file_index_entry_len);
-
-                // Error on files too big for the Pack.
-                if data.len() > u32::MAX as usize {
-                    return Err(RLibError::DataTooBigForContainer("Pack".to_owned(), u32::MAX as u64, data.len(), path.to_owned()));
-                }
-
-                file_index_entry.write_u32(data.len() as u32)?;
-
-                if self.header.bitmask.contains(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS) {
-                    let timestamp = if nullify_dates {
-                        0
-                    } else {
-                        file.timestamp().unwrap_or(0) as u32
-                    };
-                    file_index_entry.write_u32(timestamp)?;
-                }
-
-                file_index_entry.write_bool(has_been_compressed)?;
-                file_index_entry.write_string_u8_0terminated(path)?;
+        let files_data = Self::encode_files_data(&mut sorted_files, self.compress, extra_data)?;
+        let (files_index, files_data): (Vec<_>, Vec<_>) = sorted_files.par_iter()
+            .zip(files_data.into_par_iter())
+            .map(|((path, file), (data, has_been_compressed))| {
+                let file_index_entry = Self::file_index_entry(path, file, data.len(), has_been_compressed, has_timestamps, nullify_dates)?;
                 Ok((file_index_entry, data))
             }).collect::<Result<Vec<(Vec<u8>, Vec<u8>)>>>()?
             .into_par_iter()
             .unzip();
 
+        let files_count = sorted_files.len();
+        let files_index_len = files_index.par_iter().map(|x| x.len() as u32).sum();
+        let (header, dependencies_index) = self.pfh5_header(files_count, files_index_len, test_mode, nullify_dates)?;
+
+        // Finally, write everything in one go.
+        buffer.write_all(&header)?;
+        buffer.write_all(&dependencies_index)?;
+
+        for index in files_index {
+            buffer.write_all(&index)?;
+        }
+
+        for data in files_data {
+            buffer.write_all(&data)?;
+        }
+
+        Ok(())
+    }
+
+    /// This function writes a `Pack` of version 5 into the provided buffer, encoding and writing the data of its files in batches.
+    pub(crate) fn write_pfh5_streamed<W: WriteBytes + Seek>(&mut self, buffer: &mut W, extra_data: &Option<EncodeableExtraData>) -> Result<()> {
+        let (test_mode, nullify_dates) = if let Some(extra_data) = extra_data {
+            (extra_data.test_mode, extra_data.nullify_dates)
+        } else {
+            (false, false)
+        };
+
+        // The length of the index doesn't depend on the data, so we can write the header before encoding the files.
+        let has_timestamps = self.header.bitmask.contains(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS);
+        let files_count = self.files.len();
+        let files_index_len = self.files.keys().map(|path| Self::file_index_entry_len(path, has_timestamps) as u32).sum();
+        let (header, dependencies_index) = self.pfh5_header(files_count, files_index_len, test_mode, nullify_dates)?;
+
+        buffer.write_all(&header)?;
+        buffer.write_all(&dependencies_index)?;
+
+        let mut sorted_files = self.files.iter_mut().map(|(key, file)| (key.replace('/', "\\"), file)).collect::<Vec<(String, &mut RFile)>>();
+        sorted_files.sort_unstable_by_key(|(path, _)| path.to_lowercase());
+
+        Self::write_files_streamed(buffer, &mut sorted_files, self.compress, has_timestamps, nullify_dates, extra_data)
+    }
+
+    /// This function returns the header and the dependencies index of a `Pack` of version 5.
+    ///
+    /// Unless we're in test mode, this updates the internal timestamp of the `Pack`.
+    fn pfh5_header(&mut self, files_count: usize, files_index_len: u32, test_mode: bool, nullify_dates: bool) -> Result<(Vec<u8>, Vec<u8>)> {
+
         // Build the dependencies index on memory. This one is never big, so no need of par_iter.
         let mut dependencies_index = vec![];
         for dependency in &self.dependencies {
@@ -197,8 +210,8 @@ impl Pack {
         header.write_u32(self.header.bitmask.bits() | self.header.pfh_file_type.value())?;
         header.write_u32(self.dependencies.len() as u32)?;
         header.write_u32(dependencies_index.len() as u32)?;
-        header.write_u32(sorted_files.len() as u32)?;
-        header.write_u32(files_index.par_iter().map(|x| x.len() as u32).sum())?;
+        header.write_u32(files_count as u32)?;
+        header.write_u32(files_index_len)?;
 
         // If we're not in testing mode, update the header timestamp.
         if nullify_dates {
@@ -209,18 +222,6 @@ impl Pack {
 
         header.write_u32(self.header.internal_timestamp as u32)?;
 
-        // Finally, write everything in one go.
-        buffer.write_all(&header)?;
-        buffer.write_all(&dependencies_index)?;
-
-        for index in files_index {
-            buffer.write_all(&index)?;
-        }
-
-        for data in files_data {
-            buffer.write_all(&data)?;
-        }
-
-        Ok(())
+        Ok((header, dependencies_index))
     }
 }
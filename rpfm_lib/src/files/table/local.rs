@@ -37,6 +37,7 @@ impl Table {
             definition_patch: definition_patch.clone(),
             table_name: table_name.to_owned(),
             table_data,
+            undecoded_tail: None,
         };
 
         Ok(table)
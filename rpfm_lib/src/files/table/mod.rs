@@ -15,23 +15,36 @@ This module contains the struct `Table`, used to manage the decoded data of a ta
 !*/
 
 use base64::{Engine, engine::general_purpose::STANDARD};
-use csv::{StringRecordsIter, Writer};
+use csv::{StringRecord, StringRecordsIter, Writer};
 use float_eq::float_eq;
 use getset::*;
+use regex::RegexBuilder;
 use serde_derive::{Serialize, Deserialize};
 
 use std::borrow::Cow;
-use std::collections::{BTreeMap, HashMap};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Display;
 use std::fs::File;
-use std::io::SeekFrom;
+use std::hash::{Hash, Hasher};
+use std::io::{Cursor, SeekFrom};
 
 use crate::error::{RLibError, Result};
 use crate::binary::{ReadBytes, WriteBytes};
 use crate::schema::*;
-use crate::utils::parse_str_as_bool;
+use crate::utils::{check_size_mismatch, parse_str_as_bool};
 
 mod local;
 
+#[cfg(test)] mod table_test;
+
+/// Default amount of decimals used when formatting floats as text.
+pub const DEFAULT_FLOAT_PRECISION: usize = 4;
+
+/// Maximum amount of decimals that make sense when formatting floats as text. Past this, we're just printing float noise.
+pub const MAX_FLOAT_PRECISION: usize = 15;
+
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
 //---------------------------------------------------------------------------//
@@ -53,7 +66,14 @@ pub struct Table {
     definition_patch: DefinitionPatch,
 
     #[getset(skip)]
-    table_data: Vec<Vec<DecodedData>>
+    table_data: Vec<Vec<DecodedData>>,
+
+    /// Raw bytes left undecoded after decoding stopped, and the offset within the original data where decoding stopped.
+    ///
+    /// Only set on tables returned by incomplete decodes.
+    #[serde(skip)]
+    #[getset(skip)]
+    undecoded_tail: Option<(Vec<u8>, usize)>,
 }
 
 /// This enum is used to store different types of data in a unified way. Used, for example, to store the data from each field in a DB Table.
@@ -69,6 +89,7 @@ pub enum DecodedData {
     I32(i32),
     I64(i64),
     ColourRGB(String),
+    ColourRGBA(String),
     StringU8(String),
     StringU16(String),
     OptionalI16(i16),
@@ -80,6 +101,63 @@ pub enum DecodedData {
     SequenceU32(Vec<u8>)
 }
 
+/// This struct contains the options used to turn cell data into text, so the data is shown and exported the same way everywhere.
+#[derive(Clone, Debug, PartialEq, Eq, Getters, Setters)]
+#[getset(get = "pub", set = "pub")]
+pub struct FormatOptions {
+
+    /// Maximum amount of decimals floats are formatted with. Trailing zeroes are removed.
+    float_precision: usize,
+
+    /// Format used for colours.
+    colour_format: ColourFormat,
+}
+
+/// This enum represents the different ways a colour can be formatted as text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColourFormat {
+
+    /// Keep the colour exactly as it's stored.
+    #[default]
+    AsIs,
+
+    /// Hexadecimal digits in uppercase.
+    Uppercase,
+
+    /// Hexadecimal digits in lowercase.
+    Lowercase,
+}
+
+/// This enum represents the different ways [Table::search_cells] can match a pattern against a cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CellSearchMode {
+
+    /// The cell must contain the pattern.
+    #[default]
+    Contains,
+
+    /// The cell must be exactly the pattern.
+    Exact,
+
+    /// The cell must match the pattern as a regex. Invalid regexes fall back to `Contains`.
+    Regex,
+}
+
+/// This struct contains an index of the values of a column of a [Table], for fast repeated lookups.
+///
+/// It's a snapshot of the table at the moment it was built. If the table is edited afterwards, the index must be rebuilt.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct ColumnIndex {
+
+    /// Position of the indexed column in the table.
+    #[getset(get_copy = "pub")]
+    column: usize,
+
+    /// Rows containing each distinct value of the column, in row order. Values are indexed as text.
+    rows: HashMap<String, Vec<usize>>,
+}
+
 //----------------------------------------------------------------//
 // Implementations for `DecodedData`.
 //----------------------------------------------------------------//
@@ -96,6 +174,7 @@ impl PartialEq for DecodedData {
             (DecodedData::I32(x), DecodedData::I32(y)) => x == y,
             (DecodedData::I64(x), DecodedData::I64(y)) => x == y,
             (DecodedData::ColourRGB(x), DecodedData::ColourRGB(y)) => x == y,
+            (DecodedData::ColourRGBA(x), DecodedData::ColourRGBA(y)) => x == y,
             (DecodedData::StringU8(x), DecodedData::StringU8(y)) => x == y,
             (DecodedData::StringU16(x), DecodedData::StringU16(y)) => x == y,
             (DecodedData::OptionalI16(x), DecodedData::OptionalI16(y)) => x == y,
@@ -110,6 +189,15 @@ impl PartialEq for DecodedData {
     }
 }
 
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            float_precision: DEFAULT_FLOAT_PRECISION,
+            colour_format: ColourFormat::default(),
+        }
+    }
+}
+
 /// Implementation of `DecodedData`.
 impl DecodedData {
 
@@ -124,6 +212,7 @@ impl DecodedData {
                 FieldType::I32 => if let Ok(value) = default_value.parse::<i32>() { DecodedData::I32(value) } else { DecodedData::I32(0) },
                 FieldType::I64 => if let Ok(value) = default_value.parse::<i64>() { DecodedData::I64(value) } else { DecodedData::I64(0) },
                 FieldType::ColourRGB => DecodedData::ColourRGB(default_value.to_owned()),
+                FieldType::ColourRGBA => DecodedData::ColourRGBA(default_value.to_owned()),
                 FieldType::StringU8 => DecodedData::StringU8(default_value.to_owned()),
                 FieldType::StringU16 => DecodedData::StringU16(default_value.to_owned()),
                 FieldType::OptionalI16 => if let Ok(value) = default_value.parse::<i16>() { DecodedData::I16(value) } else { DecodedData::I16(0) },
@@ -144,6 +233,7 @@ impl DecodedData {
                 FieldType::I32 => DecodedData::I32(0),
                 FieldType::I64 => DecodedData::I64(0),
                 FieldType::ColourRGB => DecodedData::ColourRGB("".to_owned()),
+                FieldType::ColourRGBA => DecodedData::ColourRGBA("".to_owned()),
                 FieldType::StringU8 => DecodedData::StringU8("".to_owned()),
                 FieldType::StringU16 => DecodedData::StringU16("".to_owned()),
                 FieldType::OptionalI16 => DecodedData::OptionalI16(0),
@@ -169,6 +259,7 @@ impl DecodedData {
             FieldType::I32 => Self::I32(value.parse::<i32>()?),
             FieldType::I64 => Self::I64(value.parse::<i64>()?),
             FieldType::ColourRGB => Self::ColourRGB(value.to_string()),
+            FieldType::ColourRGBA => Self::ColourRGBA(value.to_string()),
             FieldType::StringU8 => Self::StringU8(value.to_string()),
             FieldType::StringU16 => Self::StringU16(value.to_string()),
             FieldType::OptionalI16 => Self::OptionalI16(value.parse::<i16>()?),
@@ -181,6 +272,11 @@ impl DecodedData {
         })
     }
 
+    /// This function checks if the provided text is a valid `ColourRGBA` value: exactly 8 hexadecimal characters.
+    pub fn is_valid_colour_rgba(text: &str) -> bool {
+        text.len() == 8 && text.chars().all(|character| character.is_ascii_hexdigit())
+    }
+
     /// This functions checks if the type of an specific `DecodedData` is the one it should have, according to the provided `FieldType`.
     pub fn is_field_type_correct(&self, field_type: &FieldType) -> bool {
         match self {
@@ -191,6 +287,7 @@ impl DecodedData {
             DecodedData::I32(_) => field_type == &FieldType::I32,
             DecodedData::I64(_) => field_type == &FieldType::I64,
             DecodedData::ColourRGB(_) => field_type == &FieldType::ColourRGB,
+            DecodedData::ColourRGBA(_) => field_type == &FieldType::ColourRGBA,
             DecodedData::StringU8(_) => field_type == &FieldType::StringU8,
             DecodedData::StringU16(_) => field_type == &FieldType::StringU16,
             DecodedData::OptionalI16(_) => field_type == &FieldType::OptionalI16,
@@ -206,6 +303,8 @@ impl DecodedData {
     /// This function tries to convert the provided data to the provided fieldtype. This can fail in so many ways you should always check the result.
     ///
     /// NOTE: If you pass the same type as it already has, this becomes an expensive way of cloning.
+    ///
+    /// Sequences can be converted between `SequenceU16` and `SequenceU32`, as long as their data is valid for the new definition.
     pub fn convert_between_types(&self, new_field_type: &FieldType) -> Result<Self> {
         Ok(match self {
             Self::Boolean(ref data) => match new_field_type {
@@ -216,6 +315,7 @@ impl DecodedData {
                 FieldType::I32 => Self::I32(i32::from(*data)),
                 FieldType::I64 => Self::I64(i64::from(*data)),
                 FieldType::ColourRGB => Self::ColourRGB(if *data { "FFFFFF" } else { "000000" }.to_owned()),
+                FieldType::ColourRGBA => Self::ColourRGBA(if *data { "FFFFFFFF" } else { "00000000" }.to_owned()),
                 FieldType::StringU8 => Self::StringU8(data.to_string()),
                 FieldType::StringU16 => Self::StringU16(data.to_string()),
                 FieldType::OptionalI16 => Self::OptionalI16(i16::from(*data)),
@@ -235,6 +335,7 @@ impl DecodedData {
                 FieldType::I32 => Self::I32(*data as i32),
                 FieldType::I64 => Self::I64(*data as i64),
                 FieldType::ColourRGB => Self::ColourRGB(data.to_string()),
+                FieldType::ColourRGBA => Self::ColourRGBA(data.to_string()),
                 FieldType::StringU8 => Self::StringU8(data.to_string()),
                 FieldType::StringU16 => Self::StringU16(data.to_string()),
                 FieldType::OptionalI16 => Self::OptionalI16(*data as i16),
@@ -254,6 +355,7 @@ impl DecodedData {
                 FieldType::I32 => Self::I32(*data as i32),
                 FieldType::I64 => Self::I64(*data as i64),
                 FieldType::ColourRGB => Self::ColourRGB(data.to_string()),
+                FieldType::ColourRGBA => Self::ColourRGBA(data.to_string()),
                 FieldType::StringU8 => Self::StringU8(data.to_string()),
                 FieldType::StringU16 => Self::StringU16(data.to_string()),
                 FieldType::OptionalI16 => Self::OptionalI16(*data as i16),
@@ -274,6 +376,7 @@ impl DecodedData {
                 FieldType::I32 => Self::I32(*data as i32),
                 FieldType::I64 => Self::I64(*data as i64),
                 FieldType::ColourRGB => Self::ColourRGB(data.to_string()),
+                FieldType::ColourRGBA => Self::ColourRGBA(data.to_string()),
                 FieldType::StringU8 => Self::StringU8(data.to_string()),
                 FieldType::StringU16 => Self::StringU16(data.to_string()),
                 FieldType::OptionalI16 => Self::OptionalI16(*data),
@@ -294,6 +397,7 @@ impl DecodedData {
                 FieldType::I32 => self.clone(),
                 FieldType::I64 => Self::I64(*data as i64),
                 FieldType::ColourRGB => Self::ColourRGB(data.to_string()),
+                FieldType::ColourRGBA => Self::ColourRGBA(data.to_string()),
                 FieldType::StringU8 => Self::StringU8(data.to_string()),
                 FieldType::StringU16 => Self::StringU16(data.to_string()),
                 FieldType::OptionalI16 => Self::OptionalI16(*data as i16),
@@ -314,6 +418,7 @@ impl DecodedData {
                 FieldType::I32 => Self::I32(*data as i32),
                 FieldType::I64 => self.clone(),
                 FieldType::ColourRGB => Self::ColourRGB(data.to_string()),
+                FieldType::ColourRGBA => Self::ColourRGBA(data.to_string()),
                 FieldType::StringU8 => Self::StringU8(data.to_string()),
                 FieldType::StringU16 => Self::StringU16(data.to_string()),
                 FieldType::OptionalI16 => Self::OptionalI16(*data as i16),
@@ -325,7 +430,12 @@ impl DecodedData {
                 FieldType::SequenceU32(_) => Self::SequenceU32(vec![0, 0, 0, 0]),
             }
 
+            // Colours are converted through their packed value, so the alpha channel is added or removed instead of breaking the hex.
+            Self::ColourRGB(ref data) if *new_field_type == FieldType::ColourRGBA => Self::ColourRGBA(format!("{:08X}", u32::from_str_radix(data, 16)?)),
+            Self::ColourRGBA(ref data) if *new_field_type == FieldType::ColourRGB => Self::ColourRGB(format!("{:06X}", u32::from_str_radix(data, 16)? & 0x00FF_FFFF)),
+
             Self::ColourRGB(ref data) |
+            Self::ColourRGBA(ref data) |
             Self::StringU8(ref data) |
             Self::StringU16(ref data) |
             Self::OptionalStringU8(ref data) |
@@ -337,6 +447,7 @@ impl DecodedData {
                 FieldType::I32 => Self::I32(data.parse::<i32>()?),
                 FieldType::I64 => Self::I64(data.parse::<i64>()?),
                 FieldType::ColourRGB => Self::ColourRGB(data.to_string()),
+                FieldType::ColourRGBA => Self::ColourRGBA(data.to_string()),
                 FieldType::StringU8 => Self::StringU8(data.to_string()),
                 FieldType::StringU16 => Self::StringU16(data.to_string()),
                 FieldType::OptionalI16 => Self::OptionalI16(data.parse::<i16>()?),
@@ -349,28 +460,104 @@ impl DecodedData {
             }
 
             Self::SequenceU16(data) => match new_field_type {
-                FieldType::SequenceU16(_) => Self::SequenceU16(data.to_vec()),
-                FieldType::SequenceU32(_) => Self::SequenceU32({
-                    let mut vec = vec![];
-                    vec.extend_from_slice(&data[0..2]);
-                    vec.extend_from_slice(&[0, 0]);
-                    vec.extend_from_slice(&data[2..]);
-                    vec
-                }),
+                FieldType::SequenceU16(_) => self.clone(),
+                FieldType::SequenceU32(definition) => Self::SequenceU32(Self::convert_sequence_blob(data, false, definition)?),
                 _ => Self::new_from_type_and_value(new_field_type, &None),
             }
             Self::SequenceU32(data) => match new_field_type {
-                FieldType::SequenceU16(_) => Self::SequenceU16({
-                    let mut vec = data[0..2].to_vec();
-                    vec.extend_from_slice(&data[4..]);
-                    vec
-                }),
-                FieldType::SequenceU32(_) => Self::SequenceU32(data.to_vec()),
+                FieldType::SequenceU16(definition) => Self::SequenceU16(Self::convert_sequence_blob(data, true, definition)?),
+                FieldType::SequenceU32(_) => self.clone(),
                 _ => Self::new_from_type_and_value(new_field_type, &None),
             }
         })
     }
 
+    /// This function tries to convert the provided data to the provided fieldtype, like `convert_between_types`, but taking into account if the source and destination fields are unsigned.
+    ///
+    /// Unsigned integers are converted through their unsigned value, and strings are parsed as unsigned when the destination field is unsigned.
+    /// Everything else is converted like in `convert_between_types`.
+    pub fn convert_between_types_with_sign(&self, new_field_type: &FieldType, is_unsigned: bool, new_is_unsigned: bool) -> Result<Self> {
+        let is_unsigned = is_unsigned && matches!(self,
+            Self::I16(_) | Self::I32(_) | Self::I64(_) | Self::OptionalI16(_) | Self::OptionalI32(_) | Self::OptionalI64(_));
+        let new_is_unsigned = new_is_unsigned && matches!(new_field_type,
+            FieldType::I16 | FieldType::I32 | FieldType::I64 | FieldType::OptionalI16 | FieldType::OptionalI32 | FieldType::OptionalI64);
+
+        let value = if is_unsigned {
+            self.data_to_string_as_unsigned()
+        } else if new_is_unsigned && matches!(self,
+            Self::StringU8(_) | Self::StringU16(_) | Self::OptionalStringU8(_) | Self::OptionalStringU16(_)) {
+            self.data_to_string()
+        } else {
+            return self.convert_between_types(new_field_type);
+        };
+
+        if new_is_unsigned {
+            let mut data = Self::new_from_type_and_value(new_field_type, &None);
+            data.set_data_as_unsigned(&value)?;
+            Ok(data)
+        } else {
+            Self::StringU8(value.to_string()).convert_between_types(new_field_type)
+        }
+    }
+
+    /// This function re-serializes the blob of a sequence with the entry counter of the other sequence type.
+    ///
+    /// If `is_u32` is true, the blob is from a `SequenceU32` and it's converted to a `SequenceU16`. Otherwise, the other way around.
+    /// The entries are checked against the definition of the new sequence, so incompatible data is reported instead of silently kept.
+    fn convert_sequence_blob(data: &[u8], is_u32: bool, definition: &Definition) -> Result<Vec<u8>> {
+        let (source_type, target_type, counter_size) = if is_u32 {
+            ("SequenceU32", "SequenceU16", 4)
+        } else {
+            ("SequenceU16", "SequenceU32", 2)
+        };
+
+        // Blobs without counter are encoded as empty sequences, so convert them as such.
+        if data.len() < counter_size {
+            return Ok(vec![0; if is_u32 { 2 } else { 4 }]);
+        }
+
+        let mut cursor = Cursor::new(data);
+        let entry_count = if is_u32 { cursor.read_u32()? } else { cursor.read_u16()? as u32 };
+
+        Table::decode_table(&mut cursor, definition, Some(entry_count), false)
+            .map_err(|error| RLibError::ConvertingSequenceIncompatibleDefinition(source_type.to_owned(), target_type.to_owned(), error.to_string()))?;
+
+        let remaining = data.len() - cursor.position() as usize;
+        if remaining != 0 {
+            return Err(RLibError::ConvertingSequenceIncompatibleDefinition(source_type.to_owned(), target_type.to_owned(), format!("{remaining} bytes left after the last entry")));
+        }
+
+        let mut blob = Vec::with_capacity(data.len() + 2);
+        if is_u32 {
+            let entry_count = u16::try_from(entry_count).map_err(|_| RLibError::ConvertingSequenceTooManyEntries(source_type.to_owned(), target_type.to_owned(), entry_count))?;
+            blob.write_u16(entry_count)?;
+        } else {
+            blob.write_u32(entry_count)?;
+        }
+
+        blob.extend_from_slice(&data[counter_size..]);
+        Ok(blob)
+    }
+
+    /// This function decodes the data of a sequence field into its rows, using the provided definition.
+    ///
+    /// The stored blob is not modified. It'll fail if this is not a sequence, or if the definition doesn't match the data.
+    pub fn decode_sequence(&self, definition: &Definition) -> Result<Vec<Vec<DecodedData>>> {
+        match self {
+            Self::SequenceU16(data) => {
+                let mut data = Cursor::new(data);
+                let entry_count = data.read_u16()?;
+                Table::decode_table(&mut data, definition, Some(entry_count as u32), false)
+            }
+            Self::SequenceU32(data) => {
+                let mut data = Cursor::new(data);
+                let entry_count = data.read_u32()?;
+                Table::decode_table(&mut data, definition, Some(entry_count), false)
+            }
+            _ => Err(RLibError::DecodedDataNotASequence(FieldType::from(self).to_string())),
+        }
+    }
+
     /// This function prints whatever you have in each variants to a String.
     pub fn data_to_string(&self) -> Cow<str> {
         match self {
@@ -384,6 +571,7 @@ impl DecodedData {
             DecodedData::OptionalI32(data) => Cow::from(data.to_string()),
             DecodedData::OptionalI64(data) => Cow::from(data.to_string()),
             DecodedData::ColourRGB(data) |
+            DecodedData::ColourRGBA(data) |
             DecodedData::StringU8(data) |
             DecodedData::StringU16(data) |
             DecodedData::OptionalStringU8(data) |
@@ -393,6 +581,71 @@ impl DecodedData {
         }
     }
 
+    /// This function prints whatever you have in each variant to a String, using the provided formatting options.
+    ///
+    /// This is what should be used for anything that shows or exports the data as text, so the text survives a round-trip through `set_data`.
+    pub fn to_export_string(&self, options: &FormatOptions) -> Cow<str> {
+        match self {
+            DecodedData::F32(data) => Cow::from(format_float(*data, *options.float_precision())),
+            DecodedData::F64(data) => Cow::from(format_float(*data, *options.float_precision())),
+            DecodedData::ColourRGB(data) |
+            DecodedData::ColourRGBA(data) => match options.colour_format() {
+                ColourFormat::AsIs => Cow::from(data),
+                ColourFormat::Uppercase => Cow::from(data.to_uppercase()),
+                ColourFormat::Lowercase => Cow::from(data.to_lowercase()),
+            },
+            _ => self.data_to_string(),
+        }
+    }
+
+    /// This function returns the float in this data as it's formatted with the provided options, if that's exactly the same value.
+    ///
+    /// The comparison is done bit by bit, so a float is never replaced with a different one, even if the difference is tiny.
+    /// Returns None for non-float data, or if the formatted value is not the same as the stored one.
+    pub fn normalized_float(&self, options: &FormatOptions) -> Option<Self> {
+        match self {
+            DecodedData::F32(data) => self.to_export_string(options).parse::<f32>().ok()
+                .filter(|normalized| normalized.to_bits() == data.to_bits())
+                .map(DecodedData::F32),
+            DecodedData::F64(data) => self.to_export_string(options).parse::<f64>().ok()
+                .filter(|normalized| normalized.to_bits() == data.to_bits())
+                .map(DecodedData::F64),
+            _ => None,
+        }
+    }
+
+    /// This function prints whatever you have in each variant to a String, like `data_to_string`, but with integers treated as unsigned.
+    ///
+    /// This is for fields flagged as unsigned in the schema, which store values bigger than their signed type can hold.
+    pub fn data_to_string_as_unsigned(&self) -> Cow<str> {
+        match self {
+            DecodedData::I16(data) |
+            DecodedData::OptionalI16(data) => Cow::from((*data as u16).to_string()),
+            DecodedData::I32(data) |
+            DecodedData::OptionalI32(data) => Cow::from((*data as u32).to_string()),
+            DecodedData::I64(data) |
+            DecodedData::OptionalI64(data) => Cow::from((*data as u64).to_string()),
+            _ => self.data_to_string(),
+        }
+    }
+
+    /// This function tries to change the current data with the new one provided, like `set_data`, but parsing integers as unsigned.
+    ///
+    /// This is for fields flagged as unsigned in the schema. The parsed value is stored with the same bits in the signed variant.
+    pub fn set_data_as_unsigned(&mut self, new_data: &str) -> Result<()> {
+        match self {
+            Self::I16(data) |
+            Self::OptionalI16(data) => *data = new_data.parse::<u16>()? as i16,
+            Self::I32(data) |
+            Self::OptionalI32(data) => *data = new_data.parse::<u32>()? as i32,
+            Self::I64(data) |
+            Self::OptionalI64(data) => *data = new_data.parse::<u64>()? as i64,
+            _ => return self.set_data(new_data),
+        };
+
+        Ok(())
+    }
+
     /// This function tries to change the current data with the new one provided.
     ///
     /// It may fail if the new data is not parseable to the type required of the current data.
@@ -405,6 +658,7 @@ impl DecodedData {
             Self::I32(data) => *data = new_data.parse::<i32>()?,
             Self::I64(data) => *data = new_data.parse::<i64>()?,
             Self::ColourRGB(data) => *data = new_data.to_string(),
+            Self::ColourRGBA(data) => *data = new_data.to_string(),
             Self::StringU8(data) => *data = new_data.to_string(),
             Self::StringU16(data) => *data = new_data.to_string(),
             Self::OptionalI16(data) => *data = new_data.parse::<i16>()?,
@@ -418,6 +672,40 @@ impl DecodedData {
 
         Ok(())
     }
+
+    /// This function checks if two values are equal, comparing strings case-insensitively.
+    ///
+    /// Only string-like variants (strings, optional strings and colours) ignore the case. The rest are compared like with `==`.
+    pub fn eq_ignore_case(&self, other: &Self) -> bool {
+        let eq_lowercase = |x: &str, y: &str| x.chars().flat_map(char::to_lowercase).eq(y.chars().flat_map(char::to_lowercase));
+        match (self, other) {
+            (DecodedData::ColourRGB(x), DecodedData::ColourRGB(y)) |
+            (DecodedData::ColourRGBA(x), DecodedData::ColourRGBA(y)) |
+            (DecodedData::StringU8(x), DecodedData::StringU8(y)) |
+            (DecodedData::StringU16(x), DecodedData::StringU16(y)) |
+            (DecodedData::OptionalStringU8(x), DecodedData::OptionalStringU8(y)) |
+            (DecodedData::OptionalStringU16(x), DecodedData::OptionalStringU16(y)) => eq_lowercase(x, y),
+            _ => self == other,
+        }
+    }
+
+    /// This function compares two values of the same type for sorting.
+    ///
+    /// Numbers and booleans are compared by value, and everything else by its string representation.
+    fn sort_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (DecodedData::Boolean(x), DecodedData::Boolean(y)) => x.cmp(y),
+            (DecodedData::F32(x), DecodedData::F32(y)) => x.total_cmp(y),
+            (DecodedData::F64(x), DecodedData::F64(y)) => x.total_cmp(y),
+            (DecodedData::I16(x), DecodedData::I16(y)) |
+            (DecodedData::OptionalI16(x), DecodedData::OptionalI16(y)) => x.cmp(y),
+            (DecodedData::I32(x), DecodedData::I32(y)) |
+            (DecodedData::OptionalI32(x), DecodedData::OptionalI32(y)) => x.cmp(y),
+            (DecodedData::I64(x), DecodedData::I64(y)) |
+            (DecodedData::OptionalI64(x), DecodedData::OptionalI64(y)) => x.cmp(y),
+            _ => self.data_to_string().cmp(&other.data_to_string()),
+        }
+    }
 }
 
 //----------------------------------------------------------------//
@@ -436,7 +724,8 @@ impl Table {
             definition: definition.clone(),
             definition_patch,
             table_name: table_name.to_owned(),
-            table_data
+            table_data,
+            undecoded_tail: None,
         }
     }
 
@@ -450,6 +739,34 @@ impl Table {
         &self.definition_patch
     }
 
+    /// This function returns the raw bytes left undecoded, and the offset within the original data where decoding stopped.
+    ///
+    /// This is only available on tables returned by an incomplete decode, so they can be inspected or decoded in other ways.
+    pub fn undecoded_tail(&self) -> Option<(&[u8], usize)> {
+        self.undecoded_tail.as_ref().map(|(data, offset)| (data.as_slice(), *offset))
+    }
+
+    /// This function sets the raw bytes left undecoded, and the offset within the original data where decoding stopped.
+    pub(crate) fn set_undecoded_tail(&mut self, data: Vec<u8>, offset: usize) {
+        self.undecoded_tail = Some((data, offset));
+    }
+
+    /// This function checks if the provided data has been decoded up to its end.
+    ///
+    /// If not, it returns this table in a `DecodingTableIncomplete` error, along with the bytes left undecoded and the offset where decoding stopped.
+    pub(crate) fn check_decoded_to_end<R: ReadBytes>(mut self, data: &mut R) -> Result<Self> {
+        let position = data.stream_position()?;
+        let len = data.len()?;
+        if let Err(error) = check_size_mismatch(position as usize, len as usize) {
+            let tail = data.read_slice(len.saturating_sub(position) as usize, false)?;
+            self.set_undecoded_tail(tail, position as usize);
+
+            return Err(RLibError::DecodingTableIncomplete(error.to_string(), self));
+        }
+
+        Ok(self)
+    }
+
     /// This function returns a mutable reference to the data of the table.
     ///
     /// Note that using this makes you responsible of keeping the structure of the table "valid".
@@ -505,7 +822,9 @@ impl Table {
 
                 // Otherwise, we got a moved column. Check here if it needs type conversion.
                 else if new_fields_processed[*new_pos as usize].field_type() != old_fields_processed[*old_pos as usize].field_type() {
-                    let converted_data = match row[*old_pos as usize].convert_between_types(new_fields_processed[*new_pos as usize].field_type()) {
+                    let old_is_unsigned = old_fields_processed[*old_pos as usize].is_unsigned(Some(&self.definition_patch));
+                    let new_is_unsigned = new_fields_processed[*new_pos as usize].is_unsigned(Some(&self.definition_patch));
+                    let converted_data = match row[*old_pos as usize].convert_between_types_with_sign(new_fields_processed[*new_pos as usize].field_type(), old_is_unsigned, new_is_unsigned) {
                         Ok(data) => data,
                         Err(_) => {
                             let field_type = new_fields_processed[*new_pos as usize].field_type();
@@ -574,29 +893,63 @@ impl Table {
         let mut table = if entry_count < 10_000 { Vec::with_capacity(entry_count as usize) } else { vec![] };
 
         for row in 0..entry_count {
-            table.push(Self::decode_row(data, fields, row, return_incomplete)?);
+            let row_start = data.stream_position()?;
+            match Self::decode_row(data, fields, row) {
+                Ok(row_data) => table.push(row_data),
+
+                // If we want incomplete tables, stop at the failing row and go back to its start, so it's kept as part of the undecoded data.
+                Err(_) if return_incomplete => {
+                    data.seek(SeekFrom::Start(row_start))?;
+                    break;
+                }
+                Err(error) => return Err(error),
+            }
         }
 
         Ok(table)
     }
 
-    fn decode_row<R: ReadBytes>(data: &mut R, fields: &[Field], row: u32, return_incomplete: bool) -> Result<Vec<DecodedData>> {
+    /// This function decodes a table like `decode_table`, but also returns the offset where each row starts.
+    ///
+    /// The offsets are relative to the start of the reader, so they can be used to locate each row in the original data.
+    pub fn decode_with_row_offsets<R: ReadBytes>(data: &mut R, definition: &Definition, entry_count: Option<u32>, return_incomplete: bool) -> Result<(Vec<Vec<DecodedData>>, Vec<usize>)> {
+        let entry_count = match entry_count {
+            Some(entry_count) => entry_count,
+            None => data.read_u32()?,
+        };
+
+        let fields = definition.fields();
+        let mut table = if entry_count < 10_000 { Vec::with_capacity(entry_count as usize) } else { vec![] };
+        let mut offsets = if entry_count < 10_000 { Vec::with_capacity(entry_count as usize) } else { vec![] };
+
+        for row in 0..entry_count {
+            let row_start = data.stream_position()?;
+            match Self::decode_row(data, fields, row) {
+                Ok(row_data) => {
+                    offsets.push(row_start as usize);
+                    table.push(row_data);
+                }
+
+                // Same as in `decode_table`, the failing row is left undecoded.
+                Err(_) if return_incomplete => {
+                    data.seek(SeekFrom::Start(row_start))?;
+                    break;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok((table, offsets))
+    }
+
+    fn decode_row<R: ReadBytes>(data: &mut R, fields: &[Field], row: u32) -> Result<Vec<DecodedData>> {
         let mut split_colours: BTreeMap<u8, HashMap<String, u8>> = BTreeMap::new();
         let mut row_data = Vec::with_capacity(fields.len());
         for (column, field) in fields.iter().enumerate() {
 
             // Decode the field, then apply any postprocess operation we need.
             let column = column as u32;
-            let field_data = match Self::decode_field(data, field, row, column) {
-                Ok(data) => data,
-                Err(error) => {
-                    if return_incomplete {
-                        return Ok(row_data);
-                    } else {
-                        return Err(error);
-                    }
-                }
-            };
+            let field_data = Self::decode_field(data, field, row, column)?;
             Self::decode_field_postprocess(&mut row_data, field_data, field, &mut split_colours)
         }
 
@@ -636,6 +989,10 @@ impl Table {
                 if let Ok(data) = data.read_string_colour_rgb() { Ok(DecodedData::ColourRGB(data)) }
                 else { Err(RLibError::DecodingTableFieldError(row + 1, column + 1, "Colour RGB".to_string())) }
             }
+            FieldType::ColourRGBA => {
+                if let Ok(data) = data.read_string_colour_rgba() { Ok(DecodedData::ColourRGBA(data)) }
+                else { Err(RLibError::DecodingTableFieldError(row + 1, column + 1, "Colour RGBA".to_string())) }
+            }
             FieldType::StringU8 => {
                 if let Ok(mut data) = data.read_sized_string_u8() {
                     Self::escape_special_chars(&mut data);
@@ -707,6 +1064,13 @@ impl Table {
     fn decode_row_postprocess(row_data: &mut Vec<DecodedData>, split_colours: &mut BTreeMap<u8, HashMap<String, u8>>) -> Result<()> {
         for split_colour in split_colours.values() {
             let mut colour_hex = "".to_owned();
+
+            // The alpha goes first, same as in the packed colours.
+            let alpha = split_colour.get("a").or_else(|| split_colour.get("alpha"));
+            if let Some(a) = alpha {
+                colour_hex.push_str(&format!("{a:02X?}"));
+            }
+
             if let Some(r) = split_colour.get("r") {
                 colour_hex.push_str(&format!("{r:02X?}"));
             }
@@ -732,7 +1096,11 @@ impl Table {
             }
 
             if u32::from_str_radix(&colour_hex, 16).is_ok() {
-                row_data.push(DecodedData::ColourRGB(colour_hex));
+                if alpha.is_some() {
+                    row_data.push(DecodedData::ColourRGBA(colour_hex));
+                } else {
+                    row_data.push(DecodedData::ColourRGB(colour_hex));
+                }
             } else {
                 return Err(RLibError::DecodingTableCombinedColour);
             }
@@ -743,13 +1111,16 @@ impl Table {
 
     fn decode_field_postprocess(row_data: &mut Vec<DecodedData>, data: DecodedData, field: &Field, split_colours: &mut BTreeMap<u8, HashMap<String, u8>>) {
 
-        // If the field is a bitwise, split it into multiple fields. This is currently limited to integer types.
+        // If the field is a bitwise, split it into multiple fields. This is currently limited to integer types, optional or not.
         if field.is_bitwise() > 1 {
-            if [FieldType::I16, FieldType::I32, FieldType::I64].contains(field.field_type()) {
+            if [FieldType::I16, FieldType::I32, FieldType::I64, FieldType::OptionalI16, FieldType::OptionalI32, FieldType::OptionalI64].contains(field.field_type()) {
                 let data = match data {
-                    DecodedData::I16(ref data) => *data as i64,
-                    DecodedData::I32(ref data) => *data as i64,
-                    DecodedData::I64(ref data) => *data,
+                    DecodedData::I16(ref data) |
+                    DecodedData::OptionalI16(ref data) => *data as i64,
+                    DecodedData::I32(ref data) |
+                    DecodedData::OptionalI32(ref data) => *data as i64,
+                    DecodedData::I64(ref data) |
+                    DecodedData::OptionalI64(ref data) => *data,
                     _ => unimplemented!()
                 };
 
@@ -775,19 +1146,23 @@ impl Table {
             }
         }
 
-        // If the field is part of an split colour field group, don't add it. We'll separate it from the rest, then merge them into a ColourRGB field.
+        // If the field is part of an split colour field group, don't add it. We'll separate it from the rest, then merge them into a ColourRGB field,
+        // or a ColourRGBA one if the group has an alpha channel.
         else if let Some(colour_index) = field.is_part_of_colour() {
-            if [FieldType::I16, FieldType::I32, FieldType::I64, FieldType::F32, FieldType::F64].contains(field.field_type()) {
+            if [FieldType::I16, FieldType::I32, FieldType::I64, FieldType::OptionalI16, FieldType::OptionalI32, FieldType::OptionalI64, FieldType::F32, FieldType::F64].contains(field.field_type()) {
                 let data = match data {
-                    DecodedData::I16(ref data) => *data as u8,
-                    DecodedData::I32(ref data) => *data as u8,
-                    DecodedData::I64(ref data) => *data as u8,
+                    DecodedData::I16(ref data) |
+                    DecodedData::OptionalI16(ref data) => *data as u8,
+                    DecodedData::I32(ref data) |
+                    DecodedData::OptionalI32(ref data) => *data as u8,
+                    DecodedData::I64(ref data) |
+                    DecodedData::OptionalI64(ref data) => *data as u8,
                     DecodedData::F32(ref data) => *data as u8,
                     DecodedData::F64(ref data) => *data as u8,
                     _ => unimplemented!()
                 };
 
-                // This can be r, g, b, red, green, blue.
+                // This can be r, g, b, a, red, green, blue, alpha.
                 let colour_split = field.name().rsplitn(2, '_').collect::<Vec<&str>>();
                 let colour_channel = colour_split[0].to_lowercase();
                 match split_colours.get_mut(&colour_index) {
@@ -850,16 +1225,18 @@ impl Table {
 
                     if let Some(data_column) = combined_colour_positions.get(&colour_field_name) {
                         match &row[*data_column] {
-                            DecodedData::ColourRGB(field_data) => {
+                            DecodedData::ColourRGB(field_data) |
+                            DecodedData::ColourRGBA(field_data) => {
 
                                 // Encode the full colour, then grab the byte of our field.
                                 let mut encoded = vec![];
-                                encoded.write_string_colour_rgb(field_data)?;
+                                encoded.write_string_colour_rgba(field_data)?;
 
                                 let field_data =
                                     if colour_channel == "r" || colour_channel == "red" { encoded[2] }
                                     else if colour_channel == "g" || colour_channel == "green" { encoded[1] }
                                     else if colour_channel == "b" || colour_channel == "blue" { encoded[0] }
+                                    else if colour_channel == "a" || colour_channel == "alpha" { encoded[3] }
                                 else { 0 };
 
                                 // Only these types can be split colours.
@@ -867,6 +1244,18 @@ impl Table {
                                     FieldType::I16 => data.write_i16(field_data as i16)?,
                                     FieldType::I32 => data.write_i32(field_data as i32)?,
                                     FieldType::I64 => data.write_i64(field_data as i64)?,
+                                    FieldType::OptionalI16 => {
+                                        data.write_bool(true)?;
+                                        data.write_i16(field_data as i16)?
+                                    },
+                                    FieldType::OptionalI32 => {
+                                        data.write_bool(true)?;
+                                        data.write_i32(field_data as i32)?
+                                    },
+                                    FieldType::OptionalI64 => {
+                                        data.write_bool(true)?;
+                                        data.write_i64(field_data as i64)?
+                                    },
                                     FieldType::F32 => data.write_f32(field_data as f32)?,
                                     FieldType::F64 => data.write_f64(field_data as f64)?,
                                     _ => return Err(RLibError::EncodingTableWrongFieldType(FieldType::from(&row[*data_column]).to_string(), field.field_type().to_string()))
@@ -903,6 +1292,18 @@ impl Table {
                         FieldType::I16 => data.write_i16(field_data as i16)?,
                         FieldType::I32 => data.write_i32(field_data as i32)?,
                         FieldType::I64 => data.write_i64(field_data)?,
+                        FieldType::OptionalI16 => {
+                            data.write_bool(true)?;
+                            data.write_i16(field_data as i16)?
+                        },
+                        FieldType::OptionalI32 => {
+                            data.write_bool(true)?;
+                            data.write_i32(field_data as i32)?
+                        },
+                        FieldType::OptionalI64 => {
+                            data.write_bool(true)?;
+                            data.write_i64(field_data)?
+                        },
                         _ => return Err(RLibError::EncodingTableWrongFieldType(FieldType::from(&row[data_column]).to_string(), field.field_type().to_string()))
                     }
                 }
@@ -918,6 +1319,7 @@ impl Table {
                         DecodedData::I32(field_data) => data.write_i32(*field_data)?,
                         DecodedData::I64(field_data) => data.write_i64(*field_data)?,
                         DecodedData::ColourRGB(field_data) => data.write_string_colour_rgb(field_data)?,
+                        DecodedData::ColourRGBA(field_data) => data.write_string_colour_rgba(field_data)?,
                         DecodedData::OptionalI16(field_data) => {
                             data.write_bool(true)?;
                             data.write_i16(*field_data)?
@@ -940,19 +1342,16 @@ impl Table {
                             // String files may be representations of enums (as integer => string) for ease of use.
                             // If so, we need to find the underlying integer key of our string and encode that.
                             if !field.enum_values().is_empty() {
-                                let field_data = match field.enum_values()
-                                    .iter()
-                                    .find_map(|(x, y)|
-                                        if y.to_lowercase() == field_data.to_lowercase() { Some(x) } else { None }) {
+                                let field_data = match field.enum_key(field_data) {
                                     Some(value) => {
                                         match field.field_type() {
-                                            FieldType::I16 => DecodedData::I16(*value as i16),
-                                            FieldType::I32 => DecodedData::I32(*value),
-                                            FieldType::I64 => DecodedData::I64(*value as i64),
+                                            FieldType::I16 => DecodedData::I16(value as i16),
+                                            FieldType::I32 => DecodedData::I32(value),
+                                            FieldType::I64 => DecodedData::I64(value as i64),
                                             _ => return Err(RLibError::EncodingTableWrongFieldType(field_data.to_string(), field.field_type().to_string()))
                                         }
                                     }
-                                    None => match row[data_column].convert_between_types(field.field_type()) {
+                                    None => match row[data_column].convert_between_types_with_sign(field.field_type(), false, field.is_unsigned(*schema_patches)) {
                                         Ok(data) => data,
                                         Err(_) => {
                                             let default_value = field.default_value(*schema_patches);
@@ -1095,6 +1494,17 @@ impl Table {
                             DecodedData::ColourRGB("000000".to_owned())
                         }
                     },
+                    FieldType::ColourRGBA => {
+                        if let Some(default_value) = field.default_value(schema_patches) {
+                            if u32::from_str_radix(&default_value, 16).is_ok() {
+                                DecodedData::ColourRGBA(default_value)
+                            } else {
+                                DecodedData::ColourRGBA("00000000".to_owned())
+                            }
+                        } else {
+                            DecodedData::ColourRGBA("00000000".to_owned())
+                        }
+                    },
                     FieldType::StringU8 => {
                         if let Some(default_value) = field.default_value(schema_patches) {
                             DecodedData::StringU8(default_value)
@@ -1168,52 +1578,15 @@ impl Table {
     /// This function returns the list of table/columns that reference the provided columns,
     /// and if there may be a loc entry that changing our column may need a change.
     ///
-    /// This supports more than one reference level, except for locs.
+    /// This supports more than one reference level, except for locs. The references are taken from the
+    /// schema's cached index of referencing columns, so the schema is only walked once.
     /// TODO: Make loc editions be as deep as needed.
     pub fn tables_and_columns_referencing_our_own(schema_option: &Option<Schema>, table_name: &str, column_name: &str, fields: &[Field], localised_fields: &[Field]) -> Option<(BTreeMap<String, Vec<String>>, bool)> {
         if let Some(ref schema) = *schema_option {
-
-            // Make sure the table name is correct.
-            let short_table_name = if table_name.ends_with("_tables") { table_name.split_at(table_name.len() - 7).0 } else { table_name };
+            let index = schema.referencing_columns_index();
             let mut tables: BTreeMap<String, Vec<String>> = BTreeMap::new();
-
-            // We get all the db definitions from the schema, then iterate all of them to find what tables/columns reference our own.
-            for (ref_table_name, ref_definition) in schema.definitions() {
-                let mut columns: Vec<String> = vec![];
-                for ref_version in ref_definition {
-                    let ref_fields = ref_version.fields_processed();
-                    let ref_patches = Some(ref_version.patches());
-                    let ref_fields_localised = ref_version.localised_fields();
-                    for ref_field in &ref_fields {
-                        if let Some((ref_ref_table, ref_ref_field)) = ref_field.is_reference(ref_patches) {
-
-                            // As this applies to all versions of a table, skip repeated fields.
-                            if ref_ref_table == short_table_name && ref_ref_field == column_name && !columns.iter().any(|x| x == ref_field.name()) {
-                                columns.push(ref_field.name().to_owned());
-
-                                // If we find a referencing column, get recursion working to check if there is any column referencing this one that needs to be edited.
-                                if let Some((ref_of_ref, _)) = Self::tables_and_columns_referencing_our_own(schema_option, ref_table_name, ref_field.name(), &ref_fields, ref_fields_localised) {
-                                    for refs in &ref_of_ref {
-                                        match tables.get_mut(refs.0) {
-                                            Some(columns) => for value in refs.1 {
-                                                if !columns.contains(value) {
-                                                    columns.push(value.to_owned());
-                                                }
-                                            }
-                                            None => { tables.insert(refs.0.to_owned(), refs.1.to_vec()); },
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-
-                // Only add them if we actually found columns.
-                if !columns.is_empty() {
-                    tables.insert(ref_table_name.to_owned(), columns);
-                }
-            }
+            let mut visited = HashSet::new();
+            Self::tables_and_columns_referencing_column(&index, table_name, column_name, &mut tables, &mut visited);
 
             // Also, check if we have to be careful about localised fields.
             let patches = schema.patches().get(table_name);
@@ -1227,6 +1600,117 @@ impl Table {
         }
     }
 
+    /// Recursive part of `tables_and_columns_referencing_our_own`. Each column is only checked once, so reference cycles don't loop forever.
+    fn tables_and_columns_referencing_column(index: &ReferencingColumnsIndex, table_name: &str, column_name: &str, tables: &mut BTreeMap<String, Vec<String>>, visited: &mut HashSet<(String, String)>) {
+
+        // Make sure the table name is correct.
+        let short_table_name = if table_name.ends_with("_tables") { table_name.split_at(table_name.len() - 7).0 } else { table_name };
+        if !visited.insert((short_table_name.to_owned(), column_name.to_owned())) {
+            return;
+        }
+
+        if let Some(referencing) = index.get(&(short_table_name.to_owned(), column_name.to_owned())) {
+            for (ref_table_name, ref_columns) in referencing {
+                let columns = tables.entry(ref_table_name.to_owned()).or_default();
+                for ref_column in ref_columns {
+                    if !columns.contains(ref_column) {
+                        columns.push(ref_column.to_owned());
+                    }
+                }
+
+                // Get recursion working to check if there is any column referencing these ones that needs to be edited.
+                for ref_column in ref_columns {
+                    Self::tables_and_columns_referencing_column(index, ref_table_name, ref_column, tables, visited);
+                }
+            }
+        }
+    }
+
+    /// This function returns the names of the columns of this table referenced by any other table in the provided schema, in column order.
+    ///
+    /// Editing the data of these columns may require editing the tables referencing them too.
+    pub fn referenced_columns(&self, schema: &Schema) -> Vec<String> {
+        let index = schema.referencing_columns_index();
+        let table_name = if self.table_name.ends_with("_tables") { self.table_name.split_at(self.table_name.len() - 7).0 } else { &self.table_name };
+
+        self.definition.fields_processed()
+            .iter()
+            .filter(|field| index.contains_key(&(table_name.to_owned(), field.name().to_owned())))
+            .map(|field| field.name().to_owned())
+            .collect()
+    }
+
+    /// This function removes the rows whose combined key is already used by another row, keeping only the first or the last row of each key.
+    ///
+    /// If `rows` is provided, only those rows are taken into account, and the rest of the table is left untouched.
+    ///
+    /// Returns the positions the removed rows had before removing them, sorted. Tables without key columns are left untouched.
+    pub fn remove_duplicated_combined_keys(&mut self, keep_last: bool, rows: Option<&[usize]>) -> Vec<usize> {
+        let patches = Some(self.definition.patches());
+        let key_columns = self.definition.fields_processed()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.is_key(patches))
+            .map(|(column, _)| column)
+            .collect::<Vec<_>>();
+
+        if key_columns.is_empty() {
+            return vec![];
+        }
+
+        let mut kept_rows: HashMap<Vec<String>, usize> = HashMap::new();
+        let mut removed_rows = vec![];
+        for (row_index, row) in self.table_data.iter().enumerate() {
+            if rows.map(|rows| !rows.contains(&row_index)).unwrap_or(false) {
+                continue;
+            }
+
+            let combined_keys = key_columns.iter()
+                .filter_map(|column| row.get(*column))
+                .map(|cell| cell.data_to_string().to_string())
+                .collect::<Vec<_>>();
+
+            match kept_rows.get_mut(&combined_keys) {
+                Some(kept_row) => if keep_last {
+                    removed_rows.push(*kept_row);
+                    *kept_row = row_index;
+                } else {
+                    removed_rows.push(row_index);
+                },
+                None => { kept_rows.insert(combined_keys, row_index); },
+            }
+        }
+
+        removed_rows.sort_unstable();
+
+        let mut row_index = 0;
+        self.table_data.retain(|_| {
+            let keep = removed_rows.binary_search(&row_index).is_err();
+            row_index += 1;
+            keep
+        });
+
+        removed_rows
+    }
+
+    /// This function returns the names of the columns of this table whose cells all contain their default value, in column order.
+    ///
+    /// Tables without rows have no empty columns. Note that a column being empty in this table doesn't mean it can be removed from the schema,
+    /// as the schema may require it (like key columns) or other tables of the same type may use it.
+    pub fn empty_columns(&self) -> Vec<String> {
+        if self.table_data.is_empty() {
+            return vec![];
+        }
+
+        let default_row = Self::new_row(&self.definition, Some(self.definition.patches()));
+        self.definition.fields_processed()
+            .iter()
+            .enumerate()
+            .filter(|(column, _)| self.table_data.iter().all(|row| row.get(*column) == default_row.get(*column)))
+            .map(|(_, field)| field.name().to_owned())
+            .collect()
+    }
+
     /// This function tries to find all rows with the provided data, if they exists in this table.
     pub fn rows_containing_data(&self, column_name: &str, data: &str) -> Option<(usize, Vec<usize>)> {
         let mut row_indexes = vec![];
@@ -1247,6 +1731,263 @@ impl Table {
         }
     }
 
+    /// This function builds an index of the values of the provided column, so rows containing a value can be found without scanning the table.
+    ///
+    /// Useful when doing a lot of lookups over the same table, as each [Table::rows_containing_data] call has to scan the whole table.
+    pub fn build_column_index(&self, column_name: &str) -> Result<ColumnIndex> {
+        let column = self.column_position_by_name(column_name).ok_or_else(|| RLibError::TableColumnNotFound(column_name.to_owned()))?;
+        let mut rows: HashMap<String, Vec<usize>> = HashMap::new();
+        for (row_index, row) in self.table_data.iter().enumerate() {
+            if let Some(cell_data) = row.get(column) {
+                rows.entry(cell_data.data_to_string().to_string()).or_default().push(row_index);
+            }
+        }
+
+        Ok(ColumnIndex {
+            column,
+            rows,
+        })
+    }
+
+    /// This function searches the cells of this table for the provided pattern, returning the (row, column) of each matching cell, in order.
+    ///
+    /// If a column name is provided, only that column is searched. If the column doesn't exist, nothing is returned.
+    pub fn search_cells(&self, pattern: &str, case_sensitive: bool, mode: CellSearchMode, column: Option<&str>) -> Vec<(usize, usize)> {
+        let column = match column {
+            Some(column_name) => match self.column_position_by_name(column_name) {
+                Some(column) => Some(column),
+                None => return vec![],
+            },
+            None => None,
+        };
+
+        let regex = if let CellSearchMode::Regex = mode {
+            RegexBuilder::new(pattern).case_insensitive(!case_sensitive).build().ok()
+        } else {
+            None
+        };
+
+        let pattern = if case_sensitive { pattern.to_owned() } else { pattern.to_lowercase() };
+        let is_match = |cell: &DecodedData| {
+            let text = cell.data_to_string();
+            match regex {
+                Some(ref regex) => regex.is_match(&text),
+                None => {
+                    let text = if case_sensitive { text } else { Cow::from(text.to_lowercase()) };
+                    match mode {
+                        CellSearchMode::Exact => text == pattern,
+                        CellSearchMode::Contains | CellSearchMode::Regex => text.contains(&pattern),
+                    }
+                }
+            }
+        };
+
+        self.table_data.iter()
+            .enumerate()
+            .flat_map(|(row_index, row)| row.iter()
+                .enumerate()
+                .filter(|(column_index, cell)| column.map_or(true, |column| column == *column_index) && is_match(cell))
+                .map(move |(column_index, _)| (row_index, column_index))
+            )
+            .collect()
+    }
+
+    /// This function applies the provided function to every cell of a column, replacing them with the values it returns.
+    ///
+    /// The returned values must be of the same type as the column. If any of them is not, or the function fails for any cell,
+    /// this returns an error and the table is left untouched. Returns the amount of cells changed.
+    pub fn apply_to_column(&mut self, column_name: &str, f: impl Fn(&DecodedData) -> Result<DecodedData>) -> Result<usize> {
+        let column = self.column_position_by_name(column_name).ok_or_else(|| RLibError::TableColumnNotFound(column_name.to_owned()))?;
+        let field_type = self.definition.fields_processed()[column].field_type().clone();
+
+        // Get all the new values first, so a failure doesn't leave the table half-edited.
+        let new_values = self.table_data.iter()
+            .map(|row| {
+                let value = f(&row[column])?;
+                if value.is_field_type_correct(&field_type) {
+                    Ok(value)
+                } else {
+                    Err(RLibError::TableColumnWrongFieldType(column_name.to_owned(), FieldType::from(&value).to_string(), field_type.to_string()))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut changed = 0;
+        for (row, value) in self.table_data.iter_mut().zip(new_values) {
+            if row[column] != value {
+                row[column] = value;
+                changed += 1;
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// This function sorts the rows of the table by the values of the provided column.
+    ///
+    /// The sort is stable, so rows with equal values keep their relative order.
+    pub fn sort_rows_by_column(&mut self, column_name: &str, ascending: bool) -> Result<()> {
+        let column = self.column_position_by_name(column_name).ok_or_else(|| RLibError::TableColumnNotFound(column_name.to_owned()))?;
+        self.table_data.sort_by(|a, b| {
+            let ordering = a[column].sort_cmp(&b[column]);
+            if ascending { ordering } else { ordering.reverse() }
+        });
+
+        Ok(())
+    }
+
+    /// This function overwrites the provided columns of the rows of this table with the ones of the rows of the source table with the same combined key.
+    ///
+    /// Key columns are only used to match the rows, so they're never overwritten. Rows of the source table not in this table are ignored,
+    /// and columns not provided are left untouched. Returns the amount of rows changed.
+    pub fn merge_columns_by_key(&mut self, source: &Table, columns: &[String]) -> Result<usize> {
+        let patches = Some(self.definition.patches());
+        let fields_processed = self.definition.fields_processed();
+
+        let key_columns = fields_processed.iter()
+            .enumerate()
+            .filter(|(_, field)| field.is_key(patches))
+            .map(|(column, field)| source.column_position_by_name(field.name())
+                .map(|source_column| (column, source_column))
+                .ok_or_else(|| RLibError::TableColumnNotFound(field.name().to_owned())))
+            .collect::<Result<Vec<_>>>()?;
+
+        if key_columns.is_empty() {
+            return Err(RLibError::TableHasNoKeyColumns);
+        }
+
+        let source_patches = Some(source.patches());
+        let source_fields_processed = source.definition.fields_processed();
+        let merged_columns = columns.iter()
+            .filter_map(|column_name| match fields_processed.iter().position(|field| field.name() == column_name) {
+                Some(column) if fields_processed[column].is_key(patches) => None,
+                Some(column) => Some(source.column_position_by_name(column_name)
+                    .map(|source_column| {
+                        let is_unsigned = fields_processed[column].is_unsigned(patches);
+                        let source_is_unsigned = source_fields_processed[source_column].is_unsigned(source_patches);
+                        (column, source_column, fields_processed[column].field_type().clone(), source_is_unsigned, is_unsigned)
+                    })
+                    .ok_or_else(|| RLibError::TableColumnNotFound(column_name.to_owned()))),
+                None => Some(Err(RLibError::TableColumnNotFound(column_name.to_owned()))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // If a key is repeated in the source, the last row with it wins.
+        let source_rows = source.table_data.iter()
+            .map(|row| (key_columns.iter().map(|(_, source_column)| row[*source_column].data_to_string().to_string()).collect::<Vec<_>>(), row))
+            .collect::<HashMap<_, _>>();
+
+        let mut changed = 0;
+        for row in self.table_data.iter_mut() {
+            let combined_keys = key_columns.iter().map(|(column, _)| row[*column].data_to_string().to_string()).collect::<Vec<_>>();
+            if let Some(source_row) = source_rows.get(&combined_keys) {
+                let mut row_changed = false;
+                for (column, source_column, field_type, source_is_unsigned, is_unsigned) in &merged_columns {
+                    let value = if source_row[*source_column].is_field_type_correct(field_type) {
+                        source_row[*source_column].clone()
+                    } else {
+                        source_row[*source_column].convert_between_types_with_sign(field_type, *source_is_unsigned, *is_unsigned)?
+                    };
+
+                    if row[*column] != value {
+                        row[*column] = value;
+                        row_changed = true;
+                    }
+                }
+
+                if row_changed {
+                    changed += 1;
+                }
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// This function returns the data of the table in column-major order, as (column name, values) pairs.
+    ///
+    /// Columns are in the same order as the processed fields of the definition, and the values of each column are in row order.
+    pub fn to_columns(&self) -> Vec<(String, Vec<DecodedData>)> {
+        self.definition.fields_processed()
+            .iter()
+            .enumerate()
+            .map(|(column, field)| (field.name().to_owned(), self.table_data.iter().map(|row| row[column].clone()).collect()))
+            .collect()
+    }
+
+    /// This function returns the localisable strings of this table, as (loc key, text) pairs, one per localised field and row.
+    ///
+    /// Loc keys are built with [Definition::expected_loc_keys_for_row], so rows without key are skipped. As the texts live in Loc files,
+    /// they're only filled if the table has a column named like the localised field (like tables imported from the Assembly Kit). Otherwise they're empty.
+    pub fn extract_localisable_strings(&self, table_name: &str) -> Vec<(String, String)> {
+        let text_columns = self.definition.localised_fields()
+            .iter()
+            .map(|field| self.column_position_by_name(field.name()))
+            .collect::<Vec<_>>();
+
+        self.table_data.iter()
+            .flat_map(|row| self.definition.expected_loc_keys_for_row(row, table_name)
+                .into_iter()
+                .zip(text_columns.iter())
+                .map(|(key, column)| {
+                    let text = column.and_then(|column| row.get(column))
+                        .map(|data| data.data_to_string().to_string())
+                        .unwrap_or_default();
+                    (key, text)
+                })
+                .collect::<Vec<_>>()
+            )
+            .collect()
+    }
+
+    /// This function returns a fingerprint of the table, computed from the version of its definition and all its data.
+    ///
+    /// Two tables with the same fingerprint can be considered equal, so this can be used to check if a table has actually changed
+    /// without comparing it cell by cell. Floats are hashed by their bits (with `-0.0` and `NaN` normalized), so, unlike `PartialEq`,
+    /// two floats that differ by a small amount produce different fingerprints.
+    ///
+    /// NOTE: The fingerprint is stable within the same build, but it's not guaranteed to be stable between different versions of this lib.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.definition.version().hash(&mut hasher);
+
+        let data = self.data();
+        data.len().hash(&mut hasher);
+        for row in data.iter() {
+            row.len().hash(&mut hasher);
+            for cell in row {
+                std::mem::discriminant(cell).hash(&mut hasher);
+                match cell {
+                    DecodedData::Boolean(data) => data.hash(&mut hasher),
+                    DecodedData::F32(data) => {
+                        let data = if *data == 0.0 { 0.0 } else if data.is_nan() { f32::NAN } else { *data };
+                        data.to_bits().hash(&mut hasher)
+                    },
+                    DecodedData::F64(data) => {
+                        let data = if *data == 0.0 { 0.0 } else if data.is_nan() { f64::NAN } else { *data };
+                        data.to_bits().hash(&mut hasher)
+                    },
+                    DecodedData::I16(data) |
+                    DecodedData::OptionalI16(data) => data.hash(&mut hasher),
+                    DecodedData::I32(data) |
+                    DecodedData::OptionalI32(data) => data.hash(&mut hasher),
+                    DecodedData::I64(data) |
+                    DecodedData::OptionalI64(data) => data.hash(&mut hasher),
+                    DecodedData::ColourRGB(data) |
+                    DecodedData::ColourRGBA(data) |
+                    DecodedData::StringU8(data) |
+                    DecodedData::StringU16(data) |
+                    DecodedData::OptionalStringU8(data) |
+                    DecodedData::OptionalStringU16(data) => data.hash(&mut hasher),
+                    DecodedData::SequenceU16(data) |
+                    DecodedData::SequenceU32(data) => data.hash(&mut hasher),
+                }
+            }
+        }
+
+        hasher.finish()
+    }
+
     //----------------------------------------------------------------//
     // TSV Functions for tables.
     //----------------------------------------------------------------//
@@ -1260,43 +2001,7 @@ impl Table {
 
         for (row, record) in records.enumerate() {
             match record {
-                Ok(record) => {
-                    let mut entry = Self::new_row(definition, schema_patches);
-                    for (column, field) in record.iter().enumerate() {
-
-                        // Get the column name from the header, and try to map it to a column in the table's.
-                        if let Some(column_name) = field_order.get(&(column as u32)) {
-                            if let Some(column_number) = fields_processed.iter().position(|x| x.name() == column_name) {
-
-                                entry[column_number] = match fields_processed[column_number].field_type() {
-                                    FieldType::Boolean => parse_str_as_bool(field).map(DecodedData::Boolean).map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?,
-                                    FieldType::F32 => DecodedData::F32(field.parse::<f32>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                    FieldType::F64 => DecodedData::F64(field.parse::<f64>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                    FieldType::I16 => DecodedData::I16(field.parse::<i16>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                    FieldType::I32 => DecodedData::I32(field.parse::<i32>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                    FieldType::I64 => DecodedData::I64(field.parse::<i64>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                    FieldType::OptionalI16 => DecodedData::OptionalI16(field.parse::<i16>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                    FieldType::OptionalI32 => DecodedData::OptionalI32(field.parse::<i32>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                    FieldType::OptionalI64 => DecodedData::OptionalI64(field.parse::<i64>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                    FieldType::ColourRGB => DecodedData::ColourRGB(if u32::from_str_radix(field, 16).is_ok() {
-                                        field.to_owned()
-                                    } else {
-                                        Err(RLibError::ImportTSVIncorrectRow(row, column))?
-                                    }),
-                                    FieldType::StringU8 => DecodedData::StringU8(field.to_owned()),
-                                    FieldType::StringU16 => DecodedData::StringU16(field.to_owned()),
-                                    FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(field.to_owned()),
-                                    FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(field.to_owned()),
-
-                                    // For now fail on Sequences. These are a bit special and I don't know if the're even possible in TSV.
-                                    FieldType::SequenceU16(_) => DecodedData::SequenceU16(STANDARD.decode(field).map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                    FieldType::SequenceU32(_) => DecodedData::SequenceU32(STANDARD.decode(field).map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
-                                }
-                            }
-                        }
-                    }
-                    entries.push(entry);
-                }
+                Ok(record) => entries.push(Self::tsv_import_row(&record, row, definition, &fields_processed, field_order, schema_patches)?),
                 Err(_) => return Err(RLibError::ImportTSVIncorrectRow(row, 0)),
             }
         }
@@ -1306,27 +2011,106 @@ impl Table {
         Ok(table)
     }
 
-    /// This function exports the provided data to a TSV file.
-    pub(crate) fn tsv_export(&self, writer: &mut Writer<File>, table_path: &str, keys_first: bool) -> Result<()> {
+    /// This function parses a record of a TSV file into a row of a table with the provided definition.
+    ///
+    /// Columns of the TSV not in the definition are ignored, and columns of the definition not in the TSV get their default value.
+    pub(crate) fn tsv_import_row(record: &StringRecord, row: usize, definition: &Definition, fields_processed: &[Field], field_order: &HashMap<u32, String>, schema_patches: Option<&DefinitionPatch>) -> Result<Vec<DecodedData>> {
+        let mut entry = Self::new_row(definition, schema_patches);
+        for (column, field) in record.iter().enumerate() {
+
+            // Get the column name from the header, and try to map it to a column in the table's.
+            if let Some(column_name) = field_order.get(&(column as u32)) {
+                if let Some(column_number) = fields_processed.iter().position(|x| x.name() == column_name) {
+
+                    // Unsigned integer fields need to be parsed as unsigned, then stored in their signed type.
+                    let is_unsigned_integer = fields_processed[column_number].is_unsigned(schema_patches) && matches!(fields_processed[column_number].field_type(),
+                        FieldType::I16 | FieldType::I32 | FieldType::I64 | FieldType::OptionalI16 | FieldType::OptionalI32 | FieldType::OptionalI64);
+
+                    entry[column_number] = if is_unsigned_integer {
+                        let mut data = DecodedData::new_from_type_and_value(fields_processed[column_number].field_type(), &None);
+                        data.set_data_as_unsigned(field).map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?;
+                        data
+                    } else {
+                        match fields_processed[column_number].field_type() {
+                            FieldType::Boolean => parse_str_as_bool(field).map(DecodedData::Boolean).map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?,
+                            FieldType::F32 => DecodedData::F32(field.parse::<f32>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                            FieldType::F64 => DecodedData::F64(field.parse::<f64>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                            FieldType::I16 => DecodedData::I16(field.parse::<i16>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                            FieldType::I32 => DecodedData::I32(field.parse::<i32>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                            FieldType::I64 => DecodedData::I64(field.parse::<i64>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                            FieldType::OptionalI16 => DecodedData::OptionalI16(field.parse::<i16>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                            FieldType::OptionalI32 => DecodedData::OptionalI32(field.parse::<i32>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                            FieldType::OptionalI64 => DecodedData::OptionalI64(field.parse::<i64>().map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                            FieldType::ColourRGB => DecodedData::ColourRGB(if u32::from_str_radix(field, 16).is_ok() {
+                                field.to_owned()
+                            } else {
+                                Err(RLibError::ImportTSVIncorrectRow(row, column))?
+                            }),
+                            FieldType::ColourRGBA => DecodedData::ColourRGBA(if DecodedData::is_valid_colour_rgba(field) {
+                                field.to_owned()
+                            } else {
+                                Err(RLibError::ImportTSVIncorrectRow(row, column))?
+                            }),
+                            FieldType::StringU8 => DecodedData::StringU8(field.to_owned()),
+                            FieldType::StringU16 => DecodedData::StringU16(field.to_owned()),
+                            FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(field.to_owned()),
+                            FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(field.to_owned()),
+
+                            // For now fail on Sequences. These are a bit special and I don't know if the're even possible in TSV.
+                            FieldType::SequenceU16(_) => DecodedData::SequenceU16(STANDARD.decode(field).map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                            FieldType::SequenceU32(_) => DecodedData::SequenceU32(STANDARD.decode(field).map_err(|_| RLibError::ImportTSVIncorrectRow(row, column))?),
+                        }
+                    };
+                }
+            }
+        }
 
+        Ok(entry)
+    }
+
+    /// This function exports the provided data to a TSV file.
+    ///
+    /// If columns are provided, only them and the key columns are exported. The header of the TSV records which columns were exported.
+    pub(crate) fn tsv_export(&self, writer: &mut Writer<File>, table_path: &str, keys_first: bool, format_options: &FormatOptions, columns: Option<&[String]>, trim_trailing_newline: bool) -> Result<()> {
+        let patches = Some(self.patches());
         let fields_processed = self.definition().fields_processed();
         let fields_sorted = self.definition().fields_processed_sorted(keys_first);
         let fields_sorted_properly = fields_sorted.iter()
+            .filter(|field| columns.map_or(true, |columns| field.is_key(patches) || columns.iter().any(|column| column == field.name())))
             .map(|field_sorted| (fields_processed.iter().position(|field| field == field_sorted).unwrap(), field_sorted))
             .collect::<Vec<(_,_)>>();
 
+        // Each line is a record. If we don't want a trailing newline, the last one is written field by field, without its terminator.
+        // A record with a single empty field is written as a pair of quotes, like the writer does, so it's not lost on import.
+        let write_line = |writer: &mut Writer<File>, fields: &[Cow<str>], is_last: bool| -> Result<()> {
+            if is_last && trim_trailing_newline {
+                if fields.len() == 1 && fields[0].is_empty() {
+                    writer.write_field(b"\"\"").map_err(From::from)
+                } else {
+                    fields.iter().try_for_each(|field| writer.write_field(field.as_bytes())).map_err(From::from)
+                }
+            } else {
+                writer.write_record(fields.iter().map(|field| field.as_bytes())).map_err(From::from)
+            }
+        };
+
         // We serialize the info of the table (name and version) in the first line, and the column names in the second one.
-        let metadata = (format!("#{};{};{}", self.table_name(), self.definition().version(), table_path), vec![String::new(); fields_sorted_properly.len() - 1]);
-        writer.serialize(fields_sorted_properly.iter().map(|(_, field)| field.name()).collect::<Vec<&str>>())?;
-        writer.serialize(metadata)?;
+        let entries = self.data();
+        let mut metadata = vec![Cow::from(String::new()); fields_sorted_properly.len().max(1)];
+        metadata[0] = Cow::from(format!("#{};{};{}", self.table_name(), self.definition().version(), table_path));
+        write_line(writer, &fields_sorted_properly.iter().map(|(_, field)| Cow::from(field.name())).collect::<Vec<Cow<str>>>(), false)?;
+        write_line(writer, &metadata, entries.is_empty())?;
 
         // Then we serialize each entry in the DB Table.
-        let entries = self.data();
-        for entry in &*entries {
+        for (row, entry) in entries.iter().enumerate() {
             let sorted_entry = fields_sorted_properly.iter()
-                .map(|(index, _)| entry[*index].data_to_string())
+                .map(|(index, field)| if field.is_unsigned(patches) {
+                    entry[*index].data_to_string_as_unsigned()
+                } else {
+                    entry[*index].to_export_string(format_options)
+                })
                 .collect::<Vec<Cow<str>>>();
-            writer.serialize(sorted_entry)?;
+            write_line(writer, &sorted_entry, row == entries.len() - 1)?;
         }
 
         writer.flush().map_err(From::from)
@@ -1359,3 +2143,33 @@ impl Table {
         data.replace("\\\\t", "\t").replace("\\\\n", "\n")
     }
 }
+
+//----------------------------------------------------------------//
+// Implementations for `ColumnIndex`.
+//----------------------------------------------------------------//
+
+/// Implementation of `ColumnIndex`.
+impl ColumnIndex {
+
+    /// This function returns the rows containing the provided value, if any of them contains it.
+    pub fn rows_containing_data(&self, data: &str) -> Option<&[usize]> {
+        self.rows.get(data).map(|rows| &rows[..])
+    }
+
+    /// This function returns if any row contains the provided value.
+    pub fn contains(&self, data: &str) -> bool {
+        self.rows.contains_key(data)
+    }
+}
+
+/// This function formats a float with up to the provided amount of decimals, removing trailing zeroes.
+///
+/// Rounding to the precision first gets rid of float noise, like 0.5000004 becoming 0.5.
+fn format_float<T: Display>(data: T, precision: usize) -> String {
+    let data_str = format!("{data:.precision$}");
+    if data_str.contains('.') {
+        data_str.trim_end_matches('0').trim_end_matches('.').to_owned()
+    } else {
+        data_str
+    }
+}
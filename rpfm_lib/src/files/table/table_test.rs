@@ -0,0 +1,472 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the `Table` and `DecodedData` functions.
+
+use crate::files::db::DB;
+
+use super::*;
+
+#[test]
+fn test_table_fingerprint() {
+    let definition = DB::test_definition();
+    let mut table = Table::new(&definition, None, "test_decode_db");
+    table.set_data(&[Table::new_row(&definition, None)]).unwrap();
+    let fingerprint = table.fingerprint();
+
+    // Same data, same fingerprint.
+    let mut copy = table.clone();
+    assert_eq!(fingerprint, copy.fingerprint());
+
+    // Any change in the data must change the fingerprint.
+    copy.data_mut().push(Table::new_row(&definition, None));
+    assert_ne!(fingerprint, copy.fingerprint());
+}
+
+#[test]
+fn test_table_apply_to_column() {
+    let definition = DB::test_definition();
+    let mut table = Table::new(&definition, None, "test_decode_db");
+    let row = Table::new_row(&definition, None);
+    table.set_data(&[row.clone(), row]).unwrap();
+
+    // Changed cells are counted, unchanged ones are not.
+    let doubled = table.apply_to_column("i32", |data| match data {
+        DecodedData::I32(value) => Ok(DecodedData::I32(value * 2)),
+        _ => unreachable!(),
+    }).unwrap();
+    assert_eq!(doubled, 2);
+    assert!(table.data().iter().all(|row| row[table.column_position_by_name("i32").unwrap()] == DecodedData::I32(8)));
+    assert_eq!(table.apply_to_column("i32", |data| Ok(data.clone())).unwrap(), 0);
+
+    // Wrong types and missing columns must fail without touching the data.
+    let fingerprint = table.fingerprint();
+    assert!(table.apply_to_column("i32", |_| Ok(DecodedData::StringU8("wrong".to_owned()))).is_err());
+    assert!(table.apply_to_column("not_a_column", |data| Ok(data.clone())).is_err());
+    assert_eq!(fingerprint, table.fingerprint());
+}
+
+#[test]
+fn test_table_sort_rows_by_column() {
+    let definition = DB::test_definition();
+    let mut table = Table::new(&definition, None, "test_decode_db");
+    let i32_column = table.column_position_by_name("i32").unwrap();
+    let string_column = table.column_position_by_name("stringu8").unwrap();
+
+    let rows = [(10, "b"), (2, "a"), (10, "a"), (-1, "c")].iter()
+        .map(|(number, text)| {
+            let mut row = Table::new_row(&definition, None);
+            row[i32_column] = DecodedData::I32(*number);
+            row[string_column] = DecodedData::StringU8(text.to_string());
+            row
+        })
+        .collect::<Vec<_>>();
+    table.set_data(&rows).unwrap();
+
+    // Numbers must be sorted by value, not lexically, and ties must keep their order.
+    table.sort_rows_by_column("i32", true).unwrap();
+    let sorted = table.data().iter().map(|row| (row[i32_column].data_to_string().to_string(), row[string_column].data_to_string().to_string())).collect::<Vec<_>>();
+    assert_eq!(sorted, vec![
+        ("-1".to_owned(), "c".to_owned()),
+        ("2".to_owned(), "a".to_owned()),
+        ("10".to_owned(), "b".to_owned()),
+        ("10".to_owned(), "a".to_owned()),
+    ]);
+
+    table.sort_rows_by_column("stringu8", false).unwrap();
+    let sorted = table.data().iter().map(|row| row[string_column].data_to_string().to_string()).collect::<Vec<_>>();
+    assert_eq!(sorted, vec!["c", "b", "a", "a"]);
+
+    assert!(table.sort_rows_by_column("not_a_column", true).is_err());
+}
+
+#[test]
+fn test_table_to_columns() {
+    let definition = DB::test_definition();
+    let mut table = Table::new(&definition, None, "test_decode_db");
+    let i32_column = table.column_position_by_name("i32").unwrap();
+
+    let rows = (0..3).map(|number| {
+            let mut row = Table::new_row(&definition, None);
+            row[i32_column] = DecodedData::I32(number);
+            row
+        })
+        .collect::<Vec<_>>();
+    table.set_data(&rows).unwrap();
+
+    let columns = table.to_columns();
+    assert_eq!(columns.len(), definition.fields_processed().len());
+    assert_eq!(columns[i32_column].0, "i32");
+    assert_eq!(columns[i32_column].1, vec![DecodedData::I32(0), DecodedData::I32(1), DecodedData::I32(2)]);
+
+    // Transposing the columns back must give us the original rows.
+    let rows_back = (0..rows.len())
+        .map(|row| columns.iter().map(|(_, values)| values[row].clone()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(rows_back, rows);
+
+    // Empty tables still return their columns, just without values.
+    let empty = Table::new(&definition, None, "test_decode_db");
+    assert!(empty.to_columns().iter().all(|(_, values)| values.is_empty()));
+}
+
+#[test]
+fn test_table_build_column_index() {
+    let definition = DB::test_definition();
+    let mut table = Table::new(&definition, None, "test_decode_db");
+    let i32_column = table.column_position_by_name("i32").unwrap();
+
+    let rows = [1, 2, 1, 3].iter().map(|number| {
+            let mut row = Table::new_row(&definition, None);
+            row[i32_column] = DecodedData::I32(*number);
+            row
+        })
+        .collect::<Vec<_>>();
+    table.set_data(&rows).unwrap();
+
+    let index = table.build_column_index("i32").unwrap();
+    assert_eq!(index.column(), i32_column);
+    assert_eq!(index.rows().len(), 3);
+
+    // The index must give the same rows as scanning the table.
+    for value in ["1", "2", "3", "4"] {
+        assert_eq!(index.rows_containing_data(value).map(|rows| rows.to_vec()), table.rows_containing_data("i32", value).map(|(_, rows)| rows));
+    }
+
+    assert!(index.contains("3"));
+    assert!(!index.contains("4"));
+    assert!(table.build_column_index("missing_column").is_err());
+}
+
+#[test]
+fn test_table_empty_columns() {
+    let mut definition = Definition::new_for_tests(0, &[("key", FieldType::StringU8, true), ("name", FieldType::OptionalStringU8, false), ("cost", FieldType::I32, false)], &[]);
+    definition.fields_mut()[2].set_default_value(Some("5".to_owned()));
+
+    // Tables without rows have no empty columns.
+    let mut table = Table::new(&definition, None, "units_tables");
+    assert!(table.empty_columns().is_empty());
+
+    // Columns are empty if all their cells have the default value of the column, not just zeroes.
+    let row = |key: &str, name: &str, cost: i32| vec![DecodedData::StringU8(key.to_owned()), DecodedData::OptionalStringU8(name.to_owned()), DecodedData::I32(cost)];
+    table.set_data(&[row("a", "", 5), row("b", "", 5)]).unwrap();
+    assert_eq!(table.empty_columns(), vec!["name".to_owned(), "cost".to_owned()]);
+
+    table.set_data(&[row("a", "", 0), row("b", "Unit B", 5)]).unwrap();
+    assert!(table.empty_columns().is_empty());
+}
+
+#[test]
+fn test_table_merge_columns_by_key() {
+    let definition = Definition::new_for_tests(0, &[("key", FieldType::StringU8, true), ("name", FieldType::StringU8, false), ("cost", FieldType::I32, false)], &[]);
+
+    let row = |key: &str, name: &str, cost: i32| vec![DecodedData::StringU8(key.to_owned()), DecodedData::StringU8(name.to_owned()), DecodedData::I32(cost)];
+
+    let mut table = Table::new(&definition, None, "units_tables");
+    table.set_data(&[row("a", "Unit A", 100), row("b", "Unit B", 200), row("c", "Unit C", 300)]).unwrap();
+
+    // The source has edits in both columns, and a row the table doesn't have.
+    let mut source = Table::new(&definition, None, "units_tables");
+    source.set_data(&[row("c", "Unit C", 350), row("b", "Unit B edited", 250), row("d", "Unit D", 400)]).unwrap();
+
+    // Only the provided column must be merged, and only on rows with the same key. Keys are never overwritten.
+    let changed = table.merge_columns_by_key(&source, &["cost".to_owned(), "key".to_owned()]).unwrap();
+    assert_eq!(changed, 2);
+    assert_eq!(table.data().to_vec(), vec![row("a", "Unit A", 100), row("b", "Unit B", 250), row("c", "Unit C", 350)]);
+
+    // Merging again changes nothing.
+    assert_eq!(table.merge_columns_by_key(&source, &["cost".to_owned()]).unwrap(), 0);
+
+    assert!(table.merge_columns_by_key(&source, &["not_a_column".to_owned()]).is_err());
+
+    // Without keys, there's no way to match the rows.
+    let no_keys = Definition::new_for_tests(0, &[("name", FieldType::StringU8, false)], &[]);
+    let mut table = Table::new(&no_keys, None, "units_tables");
+    assert!(table.merge_columns_by_key(&Table::new(&no_keys, None, "units_tables"), &["name".to_owned()]).is_err());
+}
+
+#[test]
+fn test_table_search_cells() {
+    let definition = DB::test_definition();
+    let mut table = Table::new(&definition, None, "test_decode_db");
+    let column = table.column_position_by_name("stringu8").unwrap();
+
+    let rows = ["Apple", "pineapple", "banana"].iter()
+        .map(|text| {
+            let mut row = Table::new_row(&definition, None);
+            row[column] = DecodedData::StringU8(text.to_string());
+            row
+        })
+        .collect::<Vec<_>>();
+    table.set_data(&rows).unwrap();
+
+    assert_eq!(table.search_cells("apple", false, CellSearchMode::Contains, Some("stringu8")), vec![(0, column), (1, column)]);
+    assert_eq!(table.search_cells("apple", true, CellSearchMode::Contains, Some("stringu8")), vec![(1, column)]);
+    assert_eq!(table.search_cells("apple", false, CellSearchMode::Exact, Some("stringu8")), vec![(0, column)]);
+    assert_eq!(table.search_cells("^B.n", false, CellSearchMode::Regex, Some("stringu8")), vec![(2, column)]);
+    assert_eq!(table.search_cells("banana", true, CellSearchMode::Contains, None), vec![(2, column)]);
+    assert!(table.search_cells("apple", false, CellSearchMode::Contains, Some("not_a_column")).is_empty());
+}
+
+#[test]
+fn test_decoded_data_unsigned() {
+    // Values over the signed max must roundtrip through their unsigned representation.
+    let mut data = DecodedData::I64(0);
+    data.set_data_as_unsigned("9223372036854775808").unwrap();
+    assert_eq!(data, DecodedData::I64(i64::MIN));
+    assert_eq!(data.data_to_string_as_unsigned(), "9223372036854775808");
+    assert_eq!(data.data_to_string(), "-9223372036854775808");
+
+    data.set_data_as_unsigned("18446744073709551615").unwrap();
+    assert_eq!(data, DecodedData::I64(-1));
+    assert_eq!(DecodedData::OptionalI64(i64::MAX).data_to_string_as_unsigned(), "9223372036854775807");
+    assert_eq!(DecodedData::I32(-1).data_to_string_as_unsigned(), "4294967295");
+    assert_eq!(DecodedData::I16(-1).data_to_string_as_unsigned(), "65535");
+
+    // Out of range and negative values are not valid unsigned values.
+    assert!(data.set_data_as_unsigned("18446744073709551616").is_err());
+    assert!(data.set_data_as_unsigned("-1").is_err());
+    assert_eq!(data, DecodedData::I64(-1));
+
+    // Non-integers are not affected.
+    assert_eq!(DecodedData::StringU8("-1".to_owned()).data_to_string_as_unsigned(), "-1");
+
+    // The flag can be set in the field, or through patches.
+    let mut field = Field::default();
+    field.set_field_type(FieldType::I64);
+    assert!(!field.is_unsigned(None));
+
+    let mut patches = HashMap::new();
+    patches.insert(field.name().to_owned(), HashMap::from([("is_unsigned".to_owned(), "true".to_owned())]));
+    assert!(field.is_unsigned(Some(&patches)));
+
+    field.set_is_unsigned(true);
+    assert!(field.is_unsigned(None));
+
+    // Conversions between types must keep the unsigned value, not the signed one.
+    assert_eq!(DecodedData::I32(-1).convert_between_types_with_sign(&FieldType::I64, true, false).unwrap(), DecodedData::I64(4294967295));
+    assert_eq!(DecodedData::I32(-1).convert_between_types_with_sign(&FieldType::StringU8, true, false).unwrap(), DecodedData::StringU8("4294967295".to_owned()));
+    assert_eq!(DecodedData::I32(-1).convert_between_types_with_sign(&FieldType::I64, false, false).unwrap(), DecodedData::I64(-1));
+    assert_eq!(DecodedData::StringU8("18446744073709551615".to_owned()).convert_between_types_with_sign(&FieldType::I64, false, true).unwrap(), DecodedData::I64(-1));
+    assert_eq!(DecodedData::I16(-1).convert_between_types_with_sign(&FieldType::OptionalI32, true, true).unwrap(), DecodedData::OptionalI32(65535));
+    assert!(DecodedData::I64(-1).convert_between_types_with_sign(&FieldType::I32, true, true).is_err());
+    assert!(DecodedData::StringU8("-1".to_owned()).convert_between_types_with_sign(&FieldType::I32, false, true).is_err());
+}
+
+#[test]
+fn test_decoded_data_export_string_round_trip() {
+    // Default precision removes float noise and trailing zeroes.
+    let mut options = FormatOptions::default();
+    assert_eq!(DecodedData::F32(0.5000004).to_export_string(&options), "0.5");
+    assert_eq!(DecodedData::F32(2.0).to_export_string(&options), "2");
+    assert_eq!(DecodedData::F64(1.23456789).to_export_string(&options), "1.2346");
+
+    // What gets exported must be exactly what we get after re-importing it and exporting it again.
+    for precision in [4, 8] {
+        options.set_float_precision(precision);
+        for value in [0.1, 1.23456789, -3.5, 1000.0001, 0.00001] {
+            for data in [DecodedData::F32(value as f32), DecodedData::F64(value)] {
+                let exported = data.to_export_string(&options).to_string();
+                let mut imported = data.clone();
+                imported.set_data(&exported).unwrap();
+                assert_eq!(imported.to_export_string(&options), exported);
+            }
+        }
+    }
+
+    assert_eq!(DecodedData::F64(1.23456789).to_export_string(&options), "1.23456789");
+    assert_eq!(DecodedData::F32(0.1).to_export_string(&options), "0.1");
+
+    let colour = DecodedData::ColourRGB("aaBB01".to_owned());
+    assert_eq!(colour.to_export_string(&options), "aaBB01");
+    options.set_colour_format(ColourFormat::Uppercase);
+    assert_eq!(colour.to_export_string(&options), "AABB01");
+    options.set_colour_format(ColourFormat::Lowercase);
+    assert_eq!(colour.to_export_string(&options), "aabb01");
+}
+
+#[test]
+fn test_decoded_data_normalized_float() {
+    let options = FormatOptions::default();
+    assert_eq!(DecodedData::F32(1.5).normalized_float(&options), Some(DecodedData::F32(1.5)));
+    assert_eq!(DecodedData::F64(2.0).normalized_float(&options), Some(DecodedData::F64(2.0)));
+
+    // Values that only look the same within the tolerance of the float comparisons must not be normalized.
+    assert_eq!(DecodedData::F32(1.00004).to_export_string(&options), "1");
+    assert_eq!(DecodedData::F32(1.00004).normalized_float(&options), None);
+    assert_eq!(DecodedData::F64(1.00004).normalized_float(&options), None);
+
+    assert_eq!(DecodedData::I32(1).normalized_float(&options), None);
+}
+
+#[test]
+fn test_decoded_data_eq_ignore_case() {
+    // Strings, optional strings and colours ignore the case, as long as both sides are of the same type.
+    assert!(DecodedData::StringU8("Wh_Main_Emp_Karl".to_owned()).eq_ignore_case(&DecodedData::StringU8("wh_main_emp_KARL".to_owned())));
+    assert!(DecodedData::StringU16("Ñandú".to_owned()).eq_ignore_case(&DecodedData::StringU16("ñANDÚ".to_owned())));
+    assert!(DecodedData::OptionalStringU8("ABC".to_owned()).eq_ignore_case(&DecodedData::OptionalStringU8("abc".to_owned())));
+    assert!(DecodedData::ColourRGB("AABB01".to_owned()).eq_ignore_case(&DecodedData::ColourRGB("aabb01".to_owned())));
+    assert!(!DecodedData::StringU8("abc".to_owned()).eq_ignore_case(&DecodedData::StringU8("abd".to_owned())));
+    assert!(!DecodedData::StringU8("abc".to_owned()).eq_ignore_case(&DecodedData::StringU16("ABC".to_owned())));
+
+    // Anything else is compared like with `==`.
+    assert!(DecodedData::I32(5).eq_ignore_case(&DecodedData::I32(5)));
+    assert!(!DecodedData::I32(5).eq_ignore_case(&DecodedData::I64(5)));
+    assert!(DecodedData::F32(0.5).eq_ignore_case(&DecodedData::F32(0.50001)));
+    assert!(!DecodedData::Boolean(true).eq_ignore_case(&DecodedData::Boolean(false)));
+}
+
+#[test]
+fn test_table_extract_localisable_strings() {
+    // Texts are taken from columns named like the localised fields, if there are any.
+    let mut definition = Definition::new_for_tests(0, &[("key", FieldType::StringU8, true), ("name", FieldType::StringU8, false)], &[("name", FieldType::StringU8, false), ("description", FieldType::StringU8, false)]);
+    definition.set_localised_key_order(vec![0]);
+
+    let mut table = Table::new(&definition, None, "units_tables");
+    table.set_data(&[
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::StringU8("Unit A".to_owned())],
+        vec![DecodedData::StringU8(String::new()), DecodedData::StringU8("No key".to_owned())],
+    ]).unwrap();
+
+    assert_eq!(table.extract_localisable_strings("units_tables"), vec![
+        ("units_name_a".to_owned(), "Unit A".to_owned()),
+        ("units_description_a".to_owned(), String::new()),
+    ]);
+}
+
+#[test]
+fn test_decode_with_row_offsets() {
+    let definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("value", FieldType::I32, false)], &[]);
+
+    let mut data = vec![];
+    data.write_string_u8("a").unwrap();
+    data.write_i32(1).unwrap();
+    data.write_string_u8("bcd").unwrap();
+    data.write_i32(2).unwrap();
+
+    let (rows, offsets) = Table::decode_with_row_offsets(&mut Cursor::new(data), &definition, Some(2), false).unwrap();
+    assert_eq!(rows, vec![
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(1)],
+        vec![DecodedData::StringU8("bcd".to_owned()), DecodedData::I32(2)],
+    ]);
+    assert_eq!(offsets, vec![0, 7]);
+}
+
+#[test]
+fn test_convert_between_sequence_types() {
+    let definition = Box::new(Definition::new_for_tests(0, &[("value", FieldType::I32, false)], &[]));
+    let other_definition = Box::new(Definition::new_for_tests(0, &[("name", FieldType::StringU8, false)], &[]));
+
+    // Two entries with a single i32 each.
+    let sequence_u16 = DecodedData::SequenceU16(vec![2, 0, 1, 0, 0, 0, 2, 0, 0, 0]);
+    let sequence_u32 = DecodedData::SequenceU32(vec![2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0]);
+
+    // Only the counter changes between both types, and the same type is just cloned.
+    assert_eq!(sequence_u16.convert_between_types(&FieldType::SequenceU32(definition.clone())).unwrap(), sequence_u32);
+    assert_eq!(sequence_u32.convert_between_types(&FieldType::SequenceU16(definition.clone())).unwrap(), sequence_u16);
+    assert_eq!(sequence_u16.convert_between_types(&FieldType::SequenceU16(other_definition.clone())).unwrap(), sequence_u16);
+
+    // Data not valid for the new definition must fail with a descriptive error.
+    assert!(matches!(sequence_u16.convert_between_types(&FieldType::SequenceU32(other_definition)), Err(RLibError::ConvertingSequenceIncompatibleDefinition(_, _, _))));
+
+    // Sequences with more entries than a u16 can hold cannot be converted to SequenceU16.
+    let mut data = vec![0, 0, 1, 0];
+    data.resize(4 + 0x10000 * 4, 0);
+    assert!(matches!(DecodedData::SequenceU32(data).convert_between_types(&FieldType::SequenceU16(definition)), Err(RLibError::ConvertingSequenceTooManyEntries(_, _, 0x10000))));
+}
+
+#[test]
+fn test_colour_rgba() {
+    let mut definition = Definition::new_for_tests(1, &[
+        ("colour", FieldType::ColourRGBA, false),
+        ("tint_r", FieldType::I32, false),
+        ("tint_g", FieldType::I32, false),
+        ("tint_b", FieldType::I32, false),
+        ("tint_a", FieldType::I32, false),
+    ], &[]);
+    definition.fields_mut()[1..].iter_mut().for_each(|field| { field.set_is_part_of_colour(Some(1)); });
+
+    // Packed colours keep the alpha and the leading zeros, and split colour groups with an alpha channel become RGBA colours.
+    let data = vec![0xFF, 0x04, 0x05, 0x00, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0x80, 0, 0, 0];
+    let rows = Table::decode_table(&mut Cursor::new(&data), &definition, Some(1), false).unwrap();
+    assert_eq!(rows, vec![vec![DecodedData::ColourRGBA("000504FF".to_owned()), DecodedData::ColourRGBA("80010203".to_owned())]]);
+
+    // Encoding them back must give the same bytes.
+    let mut table = Table::new(&definition, None, "test_tables");
+    table.set_data(&rows).unwrap();
+    let mut encoded = vec![];
+    table.encode(&mut encoded, &None).unwrap();
+    assert_eq!(encoded, data);
+
+    // Converting between RGB and RGBA adds or removes the alpha channel.
+    assert_eq!(DecodedData::ColourRGB("0504FF".to_owned()).convert_between_types(&FieldType::ColourRGBA).unwrap(), DecodedData::ColourRGBA("000504FF".to_owned()));
+    assert_eq!(DecodedData::ColourRGBA("800504FF".to_owned()).convert_between_types(&FieldType::ColourRGB).unwrap(), DecodedData::ColourRGB("0504FF".to_owned()));
+
+    // TSV imports only accept valid hex colours.
+    let fields_processed = definition.fields_processed();
+    let field_order = HashMap::from([(0, "colour".to_owned()), (1, "tint_hex".to_owned())]);
+    let record = StringRecord::from(vec!["800504FF", "80010203"]);
+    assert_eq!(Table::tsv_import_row(&record, 0, &definition, &fields_processed, &field_order, None).unwrap(), vec![DecodedData::ColourRGBA("800504FF".to_owned()), DecodedData::ColourRGBA("80010203".to_owned())]);
+
+    let record = StringRecord::from(vec!["not a colour", "80010203"]);
+    assert!(matches!(Table::tsv_import_row(&record, 0, &definition, &fields_processed, &field_order, None), Err(RLibError::ImportTSVIncorrectRow(0, 0))));
+
+    // Values parseable as hex, but not 8 hex chars long, must fail too.
+    for invalid in ["FFF", "+FFFFFFF", "0800504FF"] {
+        let record = StringRecord::from(vec!["800504FF", invalid]);
+        assert!(matches!(Table::tsv_import_row(&record, 0, &definition, &fields_processed, &field_order, None), Err(RLibError::ImportTSVIncorrectRow(0, 1))));
+    }
+
+    assert!(DecodedData::is_valid_colour_rgba("800504ff"));
+    assert!(!DecodedData::is_valid_colour_rgba(""));
+}
+
+#[test]
+fn test_encode_bitwise_and_split_colours() {
+    let mut definition = Definition::new_for_tests(1, &[
+        ("flags", FieldType::OptionalI32, false),
+        ("mask", FieldType::I16, false),
+        ("tint_r", FieldType::I32, false),
+        ("tint_g", FieldType::I32, false),
+        ("tint_b", FieldType::I32, false),
+        ("name", FieldType::StringU8, false),
+    ], &[]);
+    definition.fields_mut()[0].set_is_bitwise(3);
+    definition.fields_mut()[1].set_is_bitwise(2);
+    definition.fields_mut()[2..5].iter_mut().for_each(|field| { field.set_is_part_of_colour(Some(1)); });
+
+    let data = vec![
+        1, 5, 0, 0, 0,
+        2, 0,
+        0x11, 0, 0, 0,
+        0x22, 0, 0, 0,
+        0x33, 0, 0, 0,
+        2, 0, b'a', b'b',
+    ];
+
+    // Bitwise fields are split into booleans, and the split colours are merged at the end of the row.
+    let rows = Table::decode_table(&mut Cursor::new(&data), &definition, Some(1), false).unwrap();
+    assert_eq!(rows, vec![vec![
+        DecodedData::Boolean(true),
+        DecodedData::Boolean(false),
+        DecodedData::Boolean(true),
+        DecodedData::Boolean(false),
+        DecodedData::Boolean(true),
+        DecodedData::StringU8("ab".to_owned()),
+        DecodedData::ColourRGB("112233".to_owned()),
+    ]]);
+
+    // Encoding must reverse both, giving back the same bytes.
+    let mut table = Table::new(&definition, None, "test_tables");
+    table.set_data(&rows).unwrap();
+    let mut encoded = vec![];
+    table.encode(&mut encoded, &None).unwrap();
+    assert_eq!(encoded, data);
+}
@@ -0,0 +1,116 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the `GameInfo` checks.
+
+use super::*;
+
+#[test]
+fn test_is_valid_pack_name_ok() {
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    assert_eq!(game.is_valid_pack_name("my_mod.pack"), Ok(()));
+    assert_eq!(game.is_valid_pack_name("!!!my_mod_v2.pack"), Ok(()));
+    assert_eq!(game.is_valid_pack_name("data_my_mod.pack"), Ok(()));
+}
+
+#[test]
+fn test_is_valid_pack_name_empty() {
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    assert_eq!(game.is_valid_pack_name(""), Err(PackNameIssue::Empty));
+    assert_eq!(game.is_valid_pack_name(".pack"), Err(PackNameIssue::Empty));
+}
+
+#[test]
+fn test_is_valid_pack_name_wrong_extension() {
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    assert_eq!(game.is_valid_pack_name("my_mod"), Err(PackNameIssue::WrongExtension));
+    assert_eq!(game.is_valid_pack_name("my_mod.pak"), Err(PackNameIssue::WrongExtension));
+    assert_eq!(game.is_valid_pack_name("my_mod.pack.bak"), Err(PackNameIssue::WrongExtension));
+}
+
+#[test]
+fn test_is_valid_pack_name_illegal_characters() {
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    assert_eq!(game.is_valid_pack_name("my mod.pack"), Err(PackNameIssue::IllegalCharacters(vec![' '])));
+    assert_eq!(game.is_valid_pack_name("my\tmod?.pack"), Err(PackNameIssue::IllegalCharacters(vec!['\t', '?'])));
+    assert_eq!(game.is_valid_pack_name("my\"mod\".pack"), Err(PackNameIssue::IllegalCharacters(vec!['"'])));
+    assert_eq!(game.is_valid_pack_name("my mod?v 2.pack"), Err(PackNameIssue::IllegalCharacters(vec![' ', '?'])));
+}
+
+#[test]
+fn test_is_valid_pack_name_reserved() {
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    assert_eq!(game.is_valid_pack_name("data.pack"), Err(PackNameIssue::ReservedName("data.pack".to_owned())));
+    assert_eq!(game.is_valid_pack_name("Local_EN.pack"), Err(PackNameIssue::ReservedName("Local_EN.pack".to_owned())));
+
+    // Games without manifest use their hardcoded list of vanilla packs.
+    let game = games.game(KEY_EMPIRE).unwrap();
+    assert_eq!(game.is_valid_pack_name("battlepresets.pack"), Err(PackNameIssue::ReservedName("battlepresets.pack".to_owned())));
+}
+
+#[test]
+fn test_is_valid_pack_name_too_long() {
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let name = format!("{}.pack", "a".repeat(MAX_PACK_NAME_LENGTH));
+    assert_eq!(game.is_valid_pack_name(&name), Err(PackNameIssue::TooLong(MAX_PACK_NAME_LENGTH + 5)));
+}
+
+#[test]
+fn test_validate_install_path() {
+    let games = SupportedGames::default();
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+
+    let game_path = std::env::temp_dir().join("rpfm_test_validate_install_path");
+    if game_path.is_dir() {
+        std::fs::remove_dir_all(&game_path).unwrap();
+    }
+
+    assert_eq!(game.validate_install_path(&game_path), Err(GamePathIssue::PathNotFound(game_path.to_path_buf())));
+
+    std::fs::create_dir_all(&game_path).unwrap();
+    assert_eq!(game.validate_install_path(&game_path), Err(GamePathIssue::ExecutableNotFound(vec!["TotalWarhammer3.sh".to_owned(), "Warhammer3.exe".to_owned()])));
+
+    // Use a new path for each step, as the install type gets cached by path.
+    let game_path = std::env::temp_dir().join("rpfm_test_validate_install_path_exe");
+    if game_path.is_dir() {
+        std::fs::remove_dir_all(&game_path).unwrap();
+    }
+
+    std::fs::create_dir_all(&game_path).unwrap();
+    File::create(game_path.join("Warhammer3.exe")).unwrap();
+    assert_eq!(game.validate_install_path(&game_path), Err(GamePathIssue::DataFolderNotFound("data".to_owned())));
+
+    std::fs::create_dir_all(game_path.join("data")).unwrap();
+    assert_eq!(game.validate_install_path(&game_path), Ok(()));
+}
+
+#[test]
+fn test_default_loc_path_for_table() {
+    let games = SupportedGames::default();
+
+    let game = games.game(KEY_WARHAMMER_3).unwrap();
+    assert_eq!(game.default_loc_path_for_table("land_units_tables"), "text/db/land_units__.loc");
+    assert_eq!(game.default_loc_path_for_table("land_units"), "text/db/land_units__.loc");
+
+    let game = games.game(KEY_EMPIRE).unwrap();
+    assert_eq!(game.default_loc_path_for_table("land_units_tables"), "text/localisation.loc");
+}
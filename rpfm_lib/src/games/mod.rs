@@ -58,6 +58,19 @@ pub const OLD_AK_REPO: &str = "https://github.com/Frodo45127/total_war_ak_files_
 pub const OLD_AK_REMOTE: &str = "origin";
 pub const OLD_AK_BRANCH: &str = "master";
 
+/// Max length of a Pack's file name, extension included. Longer names tend to break the launchers and hit path length limits.
+pub const MAX_PACK_NAME_LENGTH: usize = 128;
+
+/// Characters that cannot be used in a Pack's file name. Whitespaces are checked separately.
+const PACK_NAME_ILLEGAL_CHARACTERS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Names (without extension) used by vanilla Packs across games. Mods using them will either override or be overridden by the vanilla ones.
+const PACK_NAME_RESERVED: [&str; 14] = [
+    "audio", "blood", "boot", "campaign_variants", "data", "models", "movies", "music", "patch", "shaders", "sound", "terrain", "variants", "warmachines"
+];
+
+#[cfg(test)] mod games_test;
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -111,6 +124,9 @@ pub struct GameInfo {
     /// Logic used to name vanilla tables.
     vanilla_db_table_name_logic: VanillaDBTableNameLogic,
 
+    /// Logic used to decide in which loc file the localised strings of a table go.
+    loc_path_logic: LocPathLogic,
+
     /// Installation-dependant data.
     #[getset(skip)]
     install_data: HashMap<InstallType, InstallData>,
@@ -140,6 +156,17 @@ pub enum VanillaDBTableNameLogic {
     DefaultName(String),
 }
 
+/// This enum holds the info about each game approach at naming the loc files for db tables.
+#[derive(Clone, Debug)]
+pub enum LocPathLogic {
+
+    /// This variant is for games where each table has its own loc file, named after the table without the `_tables` suffix.
+    PerTable,
+
+    /// This variant is for games where all the localised strings go in the same loc file.
+    SingleFile(String),
+}
+
 /// This enum represents the different installations of games the game support.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum InstallType {
@@ -157,6 +184,40 @@ pub enum InstallType {
     WinWargaming,
 }
 
+/// This enum represents the different problems a Pack's file name can have.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PackNameIssue {
+
+    /// The name is empty, or it's just the extension.
+    Empty,
+
+    /// The name doesn't end in `.pack`.
+    WrongExtension,
+
+    /// The name contains characters the game or the OS doesn't support. Contains the offending characters.
+    IllegalCharacters(Vec<char>),
+
+    /// The name is used by a vanilla Pack.
+    ReservedName(String),
+
+    /// The name is longer than the max length supported. Contains the length of the name.
+    TooLong(usize),
+}
+
+/// This enum represents the different problems a game's install path can have.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GamePathIssue {
+
+    /// The path doesn't exist, or it's not a folder.
+    PathNotFound(PathBuf),
+
+    /// The path doesn't contain the executable of the game. Contains the executable names expected.
+    ExecutableNotFound(Vec<String>),
+
+    /// The path doesn't contain the data folder of the game. Contains the data folder expected, relative to the game's path.
+    DataFolderNotFound(String),
+}
+
 /// This struct contains installation-dependant data about each game.
 ///
 /// NOTE: All PackFile paths contained in this struct are RELATIVE, either to the data folder, or to the game's folder.
@@ -211,6 +272,28 @@ impl Display for InstallType {
     }
 }
 
+impl Display for PackNameIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the name is empty"),
+            Self::WrongExtension => write!(f, "the name must end in \".pack\""),
+            Self::IllegalCharacters(chars) => write!(f, "the name contains invalid characters: {}", chars.iter().map(|c| format!("'{c}'")).collect::<Vec<_>>().join(", ")),
+            Self::ReservedName(name) => write!(f, "the name \"{name}\" is used by a vanilla Pack"),
+            Self::TooLong(len) => write!(f, "the name is too long ({len} characters, max is {MAX_PACK_NAME_LENGTH})"),
+        }
+    }
+}
+
+impl Display for GamePathIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::PathNotFound(path) => write!(f, "the folder \"{}\" doesn't exist", path.to_string_lossy()),
+            Self::ExecutableNotFound(executables) => write!(f, "the game executable is missing (expected {})", executables.iter().map(|exe| format!("\"{exe}\"")).collect::<Vec<_>>().join(" or ")),
+            Self::DataFolderNotFound(data_path) => write!(f, "the data folder \"{data_path}\" is missing"),
+        }
+    }
+}
+
 /// Implementation of GameInfo.
 impl GameInfo {
 
@@ -646,6 +729,86 @@ impl GameInfo {
         self.banned_packedfiles.iter().any(|x| path.starts_with(x))
     }
 
+    /// This function returns the path of the loc file where the localised strings of the provided table conventionally go for this game.
+    ///
+    /// The table name can be passed with or without the `_tables` suffix.
+    pub fn default_loc_path_for_table(&self, table_name: &str) -> String {
+        match self.loc_path_logic() {
+            LocPathLogic::PerTable => format!("text/db/{}__.loc", table_name.strip_suffix("_tables").unwrap_or(table_name)),
+            LocPathLogic::SingleFile(path) => path.to_owned(),
+        }
+    }
+
+    /// This function checks if the provided Pack file name is valid for this game, returning the first problem found if it's not.
+    ///
+    /// The name must include the extension, but not the folder.
+    pub fn is_valid_pack_name(&self, name: &str) -> std::result::Result<(), PackNameIssue> {
+        let stem = match name.strip_suffix(".pack") {
+            Some(stem) => stem,
+            None if name.is_empty() => return Err(PackNameIssue::Empty),
+            None => return Err(PackNameIssue::WrongExtension),
+        };
+
+        if stem.is_empty() {
+            return Err(PackNameIssue::Empty);
+        }
+
+        let len = name.chars().count();
+        if len > MAX_PACK_NAME_LENGTH {
+            return Err(PackNameIssue::TooLong(len));
+        }
+
+        let mut illegal_chars = vec![];
+        for c in name.chars() {
+            if (c.is_whitespace() || c.is_control() || PACK_NAME_ILLEGAL_CHARACTERS.contains(&c)) && !illegal_chars.contains(&c) {
+                illegal_chars.push(c);
+            }
+        }
+
+        if !illegal_chars.is_empty() {
+            return Err(PackNameIssue::IllegalCharacters(illegal_chars));
+        }
+
+        // Reserved names are the generic vanilla ones, the localisation ones, and the hardcoded vanilla ones for games without a manifest.
+        let stem_lower = stem.to_lowercase();
+        let is_reserved = PACK_NAME_RESERVED.contains(&&*stem_lower) ||
+            [BRAZILIAN, SIMPLIFIED_CHINESE, CZECH, ENGLISH, FRENCH, GERMAN, ITALIAN, KOREAN, POLISH, RUSSIAN, SPANISH, TURKISH, TRADITIONAL_CHINESE].iter().any(|lang| stem_lower == format!("local_{lang}")) ||
+            self.install_data.values()
+                .flat_map(|install_data| install_data.vanilla_packs())
+                .any(|vanilla_pack| vanilla_pack.rsplit('/').next().map(|vanilla_name| vanilla_name.to_lowercase() == name.to_lowercase()).unwrap_or(false));
+
+        if is_reserved {
+            return Err(PackNameIssue::ReservedName(name.to_owned()));
+        }
+
+        Ok(())
+    }
+
+    /// This function checks if the provided path is a valid install folder for this game, returning the first problem found if it's not.
+    pub fn validate_install_path(&self, path: &Path) -> std::result::Result<(), GamePathIssue> {
+        if !path.is_dir() {
+            return Err(GamePathIssue::PathNotFound(path.to_path_buf()));
+        }
+
+        // If no executable is found, the install type detection falls back to the first install type, so we need to check it here.
+        let install_data = self.install_data(path).map_err(|_| GamePathIssue::PathNotFound(path.to_path_buf()))?;
+        if !path.join(install_data.executable()).is_file() {
+            let mut executables = self.install_data.values()
+                .map(|install_data| install_data.executable().to_owned())
+                .collect::<Vec<_>>();
+            executables.sort();
+            executables.dedup();
+
+            return Err(GamePathIssue::ExecutableNotFound(executables));
+        }
+
+        if !path.join(install_data.data_path()).is_dir() {
+            return Err(GamePathIssue::DataFolderNotFound(install_data.data_path().to_owned()));
+        }
+
+        Ok(())
+    }
+
     /// Tries to retrieve a tool var for the game.
     pub fn tool_var(&self, var: &str) -> Option<&String> {
         self.tool_vars.get(var)
@@ -18,7 +18,7 @@ You should have no business here, except for supporting a new game.
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
-use super::{GameInfo, InstallData, InstallType, pfh_file_type::PFHFileType, pfh_version::PFHVersion, VanillaDBTableNameLogic};
+use super::{GameInfo, InstallData, InstallType, pfh_file_type::PFHFileType, pfh_version::PFHVersion, LocPathLogic, VanillaDBTableNameLogic};
 
 // Display Name for all the Supported Games.
 pub const DISPLAY_NAME_PHARAOH: &str = "Pharaoh";
@@ -96,6 +96,7 @@ impl Default for SupportedGames {
             icon_small: "gs_ph.png".to_owned(),
             icon_big: "gs_big_ph.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::DefaultName("data__".to_owned()),
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -305,6 +306,7 @@ impl Default for SupportedGames {
             icon_small: "gs_wh3.png".to_owned(),
             icon_big: "gs_big_wh3.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::DefaultName("data__".to_owned()),
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -500,6 +502,7 @@ impl Default for SupportedGames {
             icon_small: "gs_troy.png".to_owned(),
             icon_big: "gs_big_troy.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::DefaultName("data__".to_owned()),
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinEpic, InstallData {
@@ -693,6 +696,7 @@ impl Default for SupportedGames {
             icon_small: "gs_3k.png".to_owned(),
             icon_big: "gs_big_3k.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::DefaultName("data__".to_owned()),
+            loc_path_logic: LocPathLogic::PerTable,
 
             install_data: {
                 let mut data = HashMap::new();
@@ -973,6 +977,7 @@ impl Default for SupportedGames {
             icon_small: "gs_wh2.png".to_owned(),
             icon_big: "gs_big_wh2.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::DefaultName("data__".to_owned()),
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -1504,6 +1509,7 @@ impl Default for SupportedGames {
             icon_small: "gs_wh.png".to_owned(),
             icon_big: "gs_big_wh.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::FolderName,
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -1777,6 +1783,7 @@ impl Default for SupportedGames {
             icon_small: "gs_tob.png".to_owned(),
             icon_big: "gs_big_tob.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::FolderName,
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -2058,6 +2065,7 @@ impl Default for SupportedGames {
             icon_small: "gs_att.png".to_owned(),
             icon_big: "gs_big_att.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::FolderName,
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -2217,6 +2225,7 @@ impl Default for SupportedGames {
             icon_small: "gs_rom2.png".to_owned(),
             icon_big: "gs_big_rom2.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::FolderName,
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -2353,6 +2362,7 @@ impl Default for SupportedGames {
             icon_small: "gs_sho2.png".to_owned(),
             icon_big: "gs_big_sho2.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::FolderName,
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -2575,6 +2585,7 @@ impl Default for SupportedGames {
             icon_small: "gs_nap.png".to_owned(),
             icon_big: "gs_big_nap.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::FolderName,
+            loc_path_logic: LocPathLogic::SingleFile("text/localisation.loc".to_owned()),
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -2902,6 +2913,7 @@ impl Default for SupportedGames {
             icon_small: "gs_emp.png".to_owned(),
             icon_big: "gs_big_emp.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::FolderName,
+            loc_path_logic: LocPathLogic::SingleFile("text/localisation.loc".to_owned()),
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinSteam, InstallData {
@@ -3365,6 +3377,7 @@ impl Default for SupportedGames {
             icon_small: "gs_are.png".to_owned(),
             icon_big: "gs_big_are.png".to_owned(),
             vanilla_db_table_name_logic: VanillaDBTableNameLogic::FolderName,
+            loc_path_logic: LocPathLogic::PerTable,
             install_data: {
                 let mut data = HashMap::new();
                 data.insert(InstallType::WinWargaming, InstallData {
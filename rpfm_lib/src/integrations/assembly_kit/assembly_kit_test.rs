@@ -0,0 +1,54 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the Assembly Kit integration.
+
+use crate::files::table::{DecodedData, Table};
+
+use super::table_data::*;
+use super::table_definition::{RawDefinition, RawField};
+
+fn raw_table(values: &[&str]) -> RawTable {
+    let field = RawField {
+        primary_key: "1".to_owned(),
+        name: "key".to_owned(),
+        field_type: "text".to_owned(),
+        required: "1".to_owned(),
+        ..Default::default()
+    };
+
+    RawTable {
+        definition: Some(RawDefinition {
+            name: Some("test.xml".to_owned()),
+            fields: vec![field],
+        }),
+        rows: values.iter()
+            .map(|value| RawTableRow {
+                fields: vec![RawTableField {
+                    field_name: "key".to_owned(),
+                    field_data: value.to_string(),
+                    state: None,
+                }]
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_raw_table_empty_field_sentinel() {
+
+    // Only fields that are exactly the sentinel are turned into empty strings.
+    let table = Table::try_from(&raw_table(&[EMPTY_FIELD_SENTINEL, "abc", &format!("{EMPTY_FIELD_SENTINEL} abc")])).unwrap();
+    assert_eq!(table.data().to_vec(), vec![
+        vec![DecodedData::StringU8(String::new())],
+        vec![DecodedData::StringU8("abc".to_owned())],
+        vec![DecodedData::StringU8(format!("{EMPTY_FIELD_SENTINEL} abc"))],
+    ]);
+}
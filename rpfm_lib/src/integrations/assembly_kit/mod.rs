@@ -39,6 +39,8 @@ pub mod localisable_fields;
 pub mod table_data;
 pub mod table_definition;
 
+#[cfg(test)] mod assembly_kit_test;
+
 const LOCALISABLE_FILES_FILE_NAME_V2: &str = "TExc_LocalisableFields";
 
 const RAW_DEFINITION_NAME_PREFIX_V2: &str = "TWaD_";
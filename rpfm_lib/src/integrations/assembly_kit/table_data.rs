@@ -29,6 +29,11 @@ use crate::schema::FieldType;
 
 use super::table_definition::RawDefinition;
 
+/// Text used to mark empty fields while parsing the raw tables, as `serde_xml_rs` cannot deal with empty fields.
+///
+/// String fields containing only this text are turned back into empty strings when converting a `RawTable` into a `Table`.
+pub const EMPTY_FIELD_SENTINEL: &str = "Frodo Best Waifu";
+
 //---------------------------------------------------------------------------//
 // Types for parsing the Assembly Kit DB Files into.
 //---------------------------------------------------------------------------//
@@ -118,11 +123,10 @@ impl RawTable {
                 }
 
                 // Serde shits itself if it sees an empty field, so we have to work around that.
-                buffer = buffer.replace("\"></datafield>", "\">Frodo Best Waifu</datafield>");
-                buffer = buffer.replace("\"> </datafield>", "\"> Frodo Best Waifu</datafield>");
-                buffer = buffer.replace("\">  </datafield>", "\">  Frodo Best Waifu</datafield>");
-                buffer = buffer.replace("\">   </datafield>", "\">   Frodo Best Waifu</datafield>");
-                buffer = buffer.replace("\">    </datafield>", "\">    Frodo Best Waifu</datafield>");
+                for spaces in 0..5 {
+                    let padding = " ".repeat(spaces);
+                    buffer = buffer.replace(&format!("\">{padding}</datafield>"), &format!("\">{padding}{EMPTY_FIELD_SENTINEL}</datafield>"));
+                }
 
                 // Only if the table has data we deserialize it. If not, we just create an empty one.
                 let mut raw_table = if buffer.contains("</rows>\r\n</dataroot>") {
@@ -139,6 +143,19 @@ impl RawTable {
     }
 }
 
+/// Implementation of `RawTableField`.
+impl RawTableField {
+
+    /// This function returns the data of this field as a string, or an empty string if the data is [EMPTY_FIELD_SENTINEL].
+    pub fn data_or_empty(&self) -> String {
+        if self.field_data == EMPTY_FIELD_SENTINEL {
+            String::new()
+        } else {
+            self.field_data.to_string()
+        }
+    }
+}
+
 impl TryFrom<&RawTable> for DB {
     type Error = RLibError;
 
@@ -189,10 +206,11 @@ impl TryFrom<&RawTable> for Table {
                             FieldType::OptionalI32 => DecodedData::OptionalI32(if let Ok(data) = field.field_data.parse::<i32>() { data } else { 0 }),
                             FieldType::OptionalI64 => DecodedData::OptionalI64(if let Ok(data) = field.field_data.parse::<i64>() { data } else { 0 }),
                             FieldType::ColourRGB => DecodedData::ColourRGB(field.field_data.to_string()),
-                            FieldType::StringU8 => DecodedData::StringU8(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
-                            FieldType::StringU16 => DecodedData::StringU16(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
-                            FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
-                            FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
+                            FieldType::ColourRGBA => DecodedData::ColourRGBA(field.field_data.to_string()),
+                            FieldType::StringU8 => DecodedData::StringU8(field.data_or_empty()),
+                            FieldType::StringU16 => DecodedData::StringU16(field.data_or_empty()),
+                            FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(field.data_or_empty()),
+                            FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(field.data_or_empty()),
 
                             // This type is not used in the raw tables so, if we find it, we skip it.
                             FieldType::SequenceU16(_) | FieldType::SequenceU32(_) => continue,
@@ -214,6 +232,7 @@ impl TryFrom<&RawTable> for Table {
                         FieldType::OptionalI32 => DecodedData::OptionalI32(0),
                         FieldType::OptionalI64 => DecodedData::OptionalI64(0),
                         FieldType::ColourRGB => DecodedData::ColourRGB(String::new()),
+                        FieldType::ColourRGBA => DecodedData::ColourRGBA(String::new()),
                         FieldType::StringU8 => DecodedData::StringU8(String::new()),
                         FieldType::StringU16 => DecodedData::StringU16(String::new()),
                         FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(String::new()),
@@ -27,20 +27,35 @@ use serde_derive::Serialize;
 use simplelog::{ColorChoice, CombinedLogger, LevelFilter, SharedLogger, TermLogger, TerminalMode};
 
 use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Display;
 use std::fs::{DirBuilder, File};
 use std::io::{BufWriter, Write};
 use std::{panic, panic::PanicInfo};
 use std::path::Path;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use crate::error::Result;
 use crate::utils::current_time;
 
+#[cfg(test)] mod log_test;
+
 /// Current version of the crate.
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Log target used for the messages of `Timings`, so they can be told apart from (or filtered out of) the rest of the log.
+pub const TIMINGS_LOG_TARGET: &str = "rpfm_timings";
+
+/// If `Timings` should be recorded and logged or not.
+static TIMINGS_ENABLED: AtomicBool = AtomicBool::new(false);
+
 lazy_static! {
 
+    /// Log level the logger was initialized with, so it can be restored when disabling the timing logs.
+    static ref LOG_LEVEL: RwLock<LevelFilter> = RwLock::new(LevelFilter::Off);
+
     /// This is the DSN needed for Sentry reports to work. Don't change it.
     pub static ref SENTRY_DSN: Arc<RwLock<String>> = Arc::new(RwLock::new(String::new()));
 }
@@ -72,6 +87,30 @@ pub struct Logger {
     backtrace: String,
 }
 
+/// This struct accumulates the time spent on each phase of an operation, so it can be logged for profiling.
+///
+/// It does nothing unless timing logs are enabled with `Logger::set_timing_logs`. It's safe to share between threads.
+#[derive(Debug)]
+pub struct Timings {
+
+    /// Name of the operation being timed.
+    name: String,
+
+    /// If we're actually timing stuff or not.
+    enabled: bool,
+
+    /// Accumulated time and count for each phase and category (usually a file type) of the operation.
+    phases: Mutex<BTreeMap<(String, String), (Duration, usize)>>,
+}
+
+/// This struct adds the time passed since its creation to a phase of a `Timings` when dropped.
+pub struct TimingsGuard<'a> {
+    timings: &'a Timings,
+    phase: &'static str,
+    category: String,
+    start: Instant,
+}
+
 //-------------------------------------------------------------------------------//
 //                              Implementations
 //-------------------------------------------------------------------------------//
@@ -103,12 +142,20 @@ impl Logger {
             // Initialize the combined logger, with a term logger (for runtime logging) and a write logger (for storing on a log file).
             //
             // So, fun fact: this thing has a tendency to crash on boot for no reason. So instead of leaving it crashing, we'll make it optional.
-            let loggers: Vec<Box<dyn SharedLogger + 'static>> = vec![TermLogger::new(log_level, simplelog::Config::default(), TerminalMode::Mixed, ColorChoice::Auto)];
+            //
+            // Timings get their own logger, so they show up at debug level without letting through the debug messages of everything else.
+            let config = simplelog::ConfigBuilder::new().add_filter_ignore_str(TIMINGS_LOG_TARGET).build();
+            let timings_config = simplelog::ConfigBuilder::new().add_filter_allow_str(TIMINGS_LOG_TARGET).build();
+            let loggers: Vec<Box<dyn SharedLogger + 'static>> = vec![
+                TermLogger::new(log_level, config, TerminalMode::Mixed, ColorChoice::Auto),
+                TermLogger::new(LevelFilter::Debug, timings_config, TerminalMode::Mixed, ColorChoice::Auto),
+            ];
             let combined_logger = CombinedLogger::new(loggers);
 
             // Initialize Sentry's logger, so anything logged goes to the breadcrumbs too.
             let logger = SentryLogger::with_dest(combined_logger);
-            log::set_max_level(log_level);
+            *LOG_LEVEL.write().unwrap() = log_level;
+            log::set_max_level(Self::max_level());
             log::set_boxed_logger(Box::new(logger))?;
         }
 
@@ -149,6 +196,30 @@ impl Logger {
         Ok(sentry_guard)
     }
 
+    /// This function enables or disables the recording and logging of `Timings`.
+    ///
+    /// Timings are logged at debug level under the `TIMINGS_LOG_TARGET` target, so enabling them raises the max log level to debug.
+    /// The rest of the messages are still filtered by the level the logger was initialized with.
+    pub fn set_timing_logs(enabled: bool) {
+        TIMINGS_ENABLED.store(enabled, Ordering::Relaxed);
+        log::set_max_level(Self::max_level());
+    }
+
+    /// This function returns the max log level needed for the current log level and timing logs settings.
+    fn max_level() -> LevelFilter {
+        let log_level = *LOG_LEVEL.read().unwrap();
+        if Self::timing_logs() {
+            log_level.max(LevelFilter::Debug)
+        } else {
+            log_level
+        }
+    }
+
+    /// This function returns if `Timings` are being recorded and logged.
+    pub fn timing_logs() -> bool {
+        TIMINGS_ENABLED.load(Ordering::Relaxed)
+    }
+
     /// Create a new local Crash Report from a `Panic`.
     ///
     /// Remember that this creates the Crash Report in memory. If you want to save it to disk, you've to do it later.
@@ -210,3 +281,69 @@ impl Logger {
         Ok(())
     }
 }
+
+impl Timings {
+
+    /// This function creates a new `Timings` for the operation with the provided name.
+    ///
+    /// It only records anything if timing logs are enabled.
+    pub fn new(name: &str) -> Self {
+        Self::new_with_state(name, Logger::timing_logs())
+    }
+
+    /// This function creates a new `Timings` for the operation with the provided name, enabled or not regardless of the global setting.
+    fn new_with_state(name: &str, enabled: bool) -> Self {
+        Self {
+            name: name.to_owned(),
+            enabled,
+            phases: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// This function returns if the timings are being recorded.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// This function adds the provided time and count to a phase of the operation.
+    pub fn record<C: Display>(&self, phase: &str, category: C, elapsed: Duration, count: usize) {
+        if self.enabled {
+            let mut phases = self.phases.lock().unwrap();
+            let entry = phases.entry((phase.to_owned(), category.to_string())).or_default();
+            entry.0 += elapsed;
+            entry.1 += count;
+        }
+    }
+
+    /// This function starts timing an item of a phase of the operation. The time is recorded when the returned guard is dropped.
+    pub fn start<C: Display>(&self, phase: &'static str, category: C) -> TimingsGuard {
+        TimingsGuard {
+            timings: self,
+            phase,
+            category: if self.enabled { category.to_string() } else { String::new() },
+            start: Instant::now(),
+        }
+    }
+
+    /// This function writes all the recorded timings to the log at debug level, under the `TIMINGS_LOG_TARGET` target.
+    pub fn log(&self) {
+        if self.enabled {
+            for line in self.lines() {
+                log::debug!(target: TIMINGS_LOG_TARGET, "{}", line);
+            }
+        }
+    }
+
+    /// This function returns the recorded timings, one formatted line per phase and category.
+    fn lines(&self) -> Vec<String> {
+        self.phases.lock().unwrap().iter()
+            .map(|((phase, category), (elapsed, count))| format!("timings: operation={} phase={} category={} count={} elapsed_ms={:.3}", self.name, phase, category, count, elapsed.as_secs_f64() * 1000.0))
+            .collect()
+    }
+}
+
+impl Drop for TimingsGuard<'_> {
+    fn drop(&mut self) {
+        self.timings.record(self.phase, &self.category, self.start.elapsed(), 1);
+    }
+}
@@ -0,0 +1,61 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the `Timings` used for profiling.
+
+use std::time::Duration;
+
+use super::*;
+
+#[test]
+fn test_timings_disabled() {
+    let timings = Timings::new_with_state("test", false);
+    timings.record("decode", "DB", Duration::from_millis(5), 1);
+    {
+        let _timer = timings.start("search", "Loc");
+    }
+
+    assert!(!timings.enabled());
+    assert!(timings.phases.lock().unwrap().is_empty());
+    assert!(timings.lines().is_empty());
+}
+
+#[test]
+fn test_timings_enabled() {
+    let timings = Timings::new_with_state("test", true);
+    timings.record("decode", "DB", Duration::from_millis(5), 1);
+    timings.record("decode", "DB", Duration::from_millis(3), 2);
+    {
+        let _timer = timings.start("search", "Loc");
+    }
+
+    let phases = timings.phases.lock().unwrap().clone();
+    assert_eq!(phases.len(), 2);
+    assert_eq!(phases.get(&("decode".to_owned(), "DB".to_owned())), Some(&(Duration::from_millis(8), 3)));
+    assert_eq!(phases.get(&("search".to_owned(), "Loc".to_owned())).unwrap().1, 1);
+
+    let lines = timings.lines();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "timings: operation=test phase=decode category=DB count=3 elapsed_ms=8.000");
+}
+
+#[test]
+fn test_set_timing_logs() {
+    let max_level = log::max_level();
+
+    // Timings are logged at debug level, so enabling them must let debug messages through.
+    Logger::set_timing_logs(true);
+    assert!(Timings::new("test").enabled());
+    assert!(log::max_level() >= LevelFilter::Debug);
+
+    Logger::set_timing_logs(false);
+    assert!(!Timings::new("test").enabled());
+    assert_eq!(log::max_level(), max_level);
+}
@@ -71,11 +71,12 @@ use serde::{Serialize as SerdeSerialize, Serializer};
 use serde_derive::{Serialize, Deserialize};
 
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::{fmt, fmt::Display};
 use std::fs::{DirBuilder, File};
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
+use std::sync::{Arc, RwLock};
 
 #[cfg(feature = "integration_assembly_kit")]use crate::integrations::assembly_kit::localisable_fields::RawLocalisableField;
 #[cfg(feature = "integration_assembly_kit")]use crate::integrations::assembly_kit::table_definition::RawDefinition;
@@ -89,6 +90,8 @@ use crate::games::supported_games::SupportedGames;
 // Legacy Schemas, to keep backwards compatibility during updates.
 pub(crate) mod v4;
 
+#[cfg(test)] mod schema_test;
+
 /// Name of the folder containing all the schemas.
 pub const SCHEMA_FOLDER: &str = "schemas";
 
@@ -126,13 +129,39 @@ pub struct Schema {
 
     /// It stores the versioned files inside the Schema.
     #[serde(serialize_with = "ordered_map_definitions")]
+    #[getset(skip)]
     definitions: HashMap<String, Vec<Definition>>,
 
     /// It stores a list of per-table, per-column patches.
     #[serde(serialize_with = "ordered_map_patches")]
+    #[getset(skip)]
     patches: HashMap<String, DefinitionPatch>,
+
+    /// Index of the columns referencing each column, built on first use and cleared when the definitions or patches change.
+    #[serde(skip)]
+    #[getset(skip)]
+    referencing_columns_cache: SchemaCache<ReferencingColumnsIndex>,
+
+    /// Reference cycles between tables, found on first use and cleared when the definitions or patches change.
+    #[serde(skip)]
+    #[getset(skip)]
+    reference_cycles_cache: SchemaCache<Vec<Vec<String>>>,
+
+    /// Issues of the definitions of each table, found on first use and cleared when the definitions or patches change.
+    #[serde(skip)]
+    #[getset(skip)]
+    definition_issues_cache: SchemaCache<BTreeMap<String, Vec<DefinitionIssue>>>,
 }
 
+/// Index of the columns referencing each column of a [Schema], as (table_name without `_tables`, column_name) -> (referencing table_name, referencing column names).
+pub(crate) type ReferencingColumnsIndex = HashMap<(String, String), BTreeMap<String, Vec<String>>>;
+
+/// Lazily-built data derived from a [Schema], like the [ReferencingColumnsIndex], shared between clones of the same [Schema].
+///
+/// It's not part of the data of the schema, so it's ignored when comparing schemas.
+#[derive(Clone, Debug)]
+struct SchemaCache<T>(Arc<RwLock<Option<Arc<T>>>>);
+
 /// This struct contains all the data needed to decode a specific version of a versioned PackedFile.
 #[derive(Clone, PartialEq, Eq, Debug, Default, Getters, MutGetters, Setters, Serialize, Deserialize)]
 #[getset(get = "pub", get_mut = "pub", set = "pub")]
@@ -205,6 +234,86 @@ pub struct Field {
 
     /// If the field is part of a 3-part RGB column set, and which one (R, G or B) it is.
     is_part_of_colour: Option<u8>,
+
+    /// Regex the entire value of string cells of this field must match to be considered valid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    validation_regex: Option<String>,
+
+    /// If the integer values of this field must be treated as unsigned when converting them from/to text.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    is_unsigned: bool,
+}
+
+/// This enum represents the problems [Definition::validate] can find in a [Definition].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DefinitionIssue {
+
+    /// More than one field (or localised field) uses this name.
+    DuplicateFieldName(String),
+
+    /// The field in this position has no name.
+    EmptyFieldName(usize),
+
+    /// The localised key order points to a field that doesn't exist.
+    LocalisedKeyOrderOutOfBounds(u32),
+
+    /// There are patches for a field that doesn't exist in the definition.
+    PatchForMissingField(String),
+
+    /// This field references itself, instead of another column.
+    SelfReferencingField(String),
+
+    /// This field references a column of its own table that doesn't exist, as (field_name, referenced column).
+    ReferenceToMissingField(String, String),
+}
+
+/// This enum represents the conflicts [Definition::merge_conflicts] can find between two [Definition]s.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DefinitionMergeConflict {
+
+    /// A field with this name exists in both definitions, but with incompatible types, as (field_name, our_type, their_type).
+    IncompatibleFieldType(String, FieldType, FieldType),
+}
+
+/// This struct contains the list of changes done to a [Schema] when renaming a table with [Schema::rename_table].
+#[derive(Clone, PartialEq, Eq, Debug, Default, Getters)]
+#[getset(get = "pub")]
+pub struct RenameReport {
+
+    /// Versions of the table's definitions that were moved to the new name.
+    versions_renamed: Vec<i32>,
+
+    /// If the table had patches that were moved to the new name.
+    patches_renamed: bool,
+
+    /// References in fields updated to point to the new name, as (table_name, version, field_name).
+    ///
+    /// References within sequence fields are reported with the name of the sequence field that contains them.
+    references_updated: Vec<(String, i32, String)>,
+
+    /// References in patches updated to point to the new name, as (table_name, field_name).
+    patch_references_updated: Vec<(String, String)>,
+}
+
+/// This struct contains a compact description of a [Field], as returned by [Schema::table_versions].
+#[derive(Clone, PartialEq, Eq, Debug, Getters)]
+#[getset(get = "pub")]
+pub struct FieldSummary {
+
+    /// Name of the field.
+    name: String,
+
+    /// Type of the field.
+    field_type: FieldType,
+
+    /// If the field is part of the key of the table.
+    is_key: bool,
+
+    /// Table (without the `_tables` suffix) and column this field references, if any.
+    reference: Option<(String, String)>,
+
+    /// Default value of the field, if any.
+    default_value: Option<String>,
 }
 
 /// This enum defines every type of field the lib can encode/decode.
@@ -217,6 +326,7 @@ pub enum FieldType {
     I32,
     I64,
     ColourRGB,
+    ColourRGBA,
     StringU8,
     StringU16,
     OptionalI16,
@@ -235,6 +345,75 @@ pub enum FieldType {
 /// Implementation of `Schema`.
 impl Schema {
 
+    /// Getter for the `definitions` field.
+    pub fn definitions(&self) -> &HashMap<String, Vec<Definition>> {
+        &self.definitions
+    }
+
+    /// Mutable getter for the `definitions` field.
+    pub fn definitions_mut(&mut self) -> &mut HashMap<String, Vec<Definition>> {
+        self.caches_clear();
+        &mut self.definitions
+    }
+
+    /// Setter for the `definitions` field.
+    pub fn set_definitions(&mut self, definitions: HashMap<String, Vec<Definition>>) -> &mut Self {
+        self.caches_clear();
+        self.definitions = definitions;
+        self
+    }
+
+    /// Getter for the `patches` field.
+    pub fn patches(&self) -> &HashMap<String, DefinitionPatch> {
+        &self.patches
+    }
+
+    /// Mutable getter for the `patches` field.
+    pub fn patches_mut(&mut self) -> &mut HashMap<String, DefinitionPatch> {
+        self.caches_clear();
+        &mut self.patches
+    }
+
+    /// Setter for the `patches` field.
+    pub fn set_patches(&mut self, patches: HashMap<String, DefinitionPatch>) -> &mut Self {
+        self.caches_clear();
+        self.patches = patches;
+        self
+    }
+
+    /// This function returns the index of the columns referencing each column of the schema.
+    ///
+    /// The index is built the first time it's requested, and reused until the definitions or patches of the schema change.
+    pub(crate) fn referencing_columns_index(&self) -> Arc<ReferencingColumnsIndex> {
+        self.referencing_columns_cache.get_or_build(|| {
+            let mut index: ReferencingColumnsIndex = HashMap::new();
+            for (ref_table_name, ref_definitions) in &self.definitions {
+                for ref_definition in ref_definitions {
+                    let ref_patches = Some(ref_definition.patches());
+                    for ref_field in ref_definition.fields_processed() {
+                        if let Some(reference) = ref_field.is_reference(ref_patches) {
+
+                            // As this applies to all versions of a table, skip repeated fields.
+                            let columns = index.entry(reference).or_default().entry(ref_table_name.to_owned()).or_default();
+                            if !columns.iter().any(|x| x == ref_field.name()) {
+                                columns.push(ref_field.name().to_owned());
+                            }
+                        }
+                    }
+                }
+            }
+
+            index
+        })
+    }
+
+    /// This function drops the cached data derived from the schema. Clones of this schema keep theirs.
+    fn caches_clear(&mut self) {
+        self.referencing_columns_cache = SchemaCache::default();
+        self.reference_cycles_cache = SchemaCache::default();
+        self.definition_issues_cache = SchemaCache::default();
+    }
+
     /// This function will save a new patch to the local patches list.
     pub fn new_patch(&mut self, patches: &HashMap<String, DefinitionPatch>, path: &Path) -> Result<()> {
         let mut file = BufReader::new(File::open(path)?);
@@ -286,6 +465,7 @@ impl Schema {
 
     /// This function adds a definition for a table into the currently loaded schema.
     pub fn add_definition(&mut self, table_name: &str, definition: &Definition) {
+        self.caches_clear();
         match self.definitions.get_mut(table_name) {
             Some(definitions) => {
                 match definitions.iter_mut().find(|def| def.version() == definition.version()) {
@@ -299,6 +479,7 @@ impl Schema {
 
     /// This function removes a definition for a table from the currently loaded schema.
     pub fn remove_definition(&mut self, table_name: &str, version: i32) {
+        self.caches_clear();
         if let Some(definitions) = self.definitions.get_mut(table_name) {
             let mut index_to_delete = vec![];
             for (index, definition) in definitions.iter().enumerate() {
@@ -323,6 +504,7 @@ impl Schema {
 
     /// This function returns a mutable reference to a specific `VersionedFile` of DB Type from the provided `Schema`.
     pub fn definitions_by_table_name_mut(&mut self, table_name: &str) -> Option<&mut Vec<Definition>>  {
+        self.caches_clear();
         self.definitions.get_mut(table_name)
     }
 
@@ -345,11 +527,125 @@ impl Schema {
         }
     }
 
+    /// This function returns the names of all the tables this schema has definitions for, sorted.
+    ///
+    /// Names are returned with the `_tables` suffix, the same way they're used in the table folders of a Pack.
+    pub fn all_table_names(&self) -> Vec<String> {
+        let mut table_names = self.definitions.iter()
+            .filter(|(_, definitions)| !definitions.is_empty())
+            .map(|(table_name, _)| if table_name.ends_with("_tables") { table_name.to_owned() } else { format!("{table_name}_tables") })
+            .collect::<Vec<_>>();
+
+        table_names.sort();
+        table_names.dedup();
+        table_names
+    }
+
+    /// This function returns all the versions of a table, from newest to oldest, with a summary of the fields of each version, in their processed order.
+    ///
+    /// The table name can be provided with or without the `_tables` suffix. If the table is not in the schema, an empty list is returned.
+    pub fn table_versions(&self, table_name: &str) -> Vec<(i32, Vec<FieldSummary>)> {
+        let table_name = if table_name.ends_with("_tables") { table_name.to_owned() } else { format!("{table_name}_tables") };
+        let mut versions = match self.definitions.get(&table_name) {
+            Some(definitions) => definitions.iter()
+                .map(|definition| {
+                    let patches = Some(definition.patches());
+                    let fields = definition.fields_processed()
+                        .iter()
+                        .map(|field| FieldSummary {
+                            name: field.name().to_owned(),
+                            field_type: field.field_type().clone(),
+                            is_key: field.is_key(patches),
+                            reference: field.is_reference(patches),
+                            default_value: field.default_value(patches),
+                        })
+                        .collect();
+
+                    (*definition.version(), fields)
+                })
+                .collect::<Vec<_>>(),
+            None => vec![],
+        };
+
+        versions.sort_by(|a, b| b.0.cmp(&a.0));
+        versions
+    }
+
+    /// This function returns a Markdown reference of the schema, with a section per table and a table with the fields of each version.
+    ///
+    /// Tables are sorted by name, versions from newest to oldest, and fields are in their processed order, so the output is stable and can be diffed.
+    pub fn to_markdown(&self) -> String {
+        let escape = |text: &str| text.replace('|', "\\|").replace("\r\n", " ").replace('\n', " ");
+
+        let mut table_names = self.definitions.iter()
+            .filter(|(_, definitions)| !definitions.is_empty())
+            .map(|(table_name, _)| table_name)
+            .collect::<Vec<_>>();
+        table_names.sort();
+
+        let mut markdown = String::from("# Schema\n");
+        for table_name in table_names {
+            markdown.push_str(&format!("\n## {table_name}\n"));
+
+            let mut definitions = self.definitions[table_name].iter().collect::<Vec<_>>();
+            definitions.sort_by(|a, b| b.version().cmp(a.version()));
+
+            for definition in definitions {
+                let patches = Some(definition.patches());
+                markdown.push_str(&format!("\n### Version {}\n\n", definition.version()));
+                markdown.push_str("| Name | Type | Key | Reference | Default | Description |\n");
+                markdown.push_str("|------|------|-----|-----------|---------|-------------|\n");
+
+                for field in definition.fields_processed() {
+                    let reference = field.is_reference(patches).map(|(table, column)| format!("{table}.{column}")).unwrap_or_default();
+                    markdown.push_str(&format!("| {} | {} | {} | {} | {} | {} |\n",
+                        escape(field.name()),
+                        field.field_type(),
+                        if field.is_key(patches) { "Yes" } else { "" },
+                        escape(&reference),
+                        escape(&field.default_value(patches).unwrap_or_default()),
+                        escape(&field.description(patches)),
+                    ));
+                }
+
+                if !definition.localised_fields().is_empty() {
+                    let localised_fields = definition.localised_fields().iter().map(|field| field.name()).collect::<Vec<_>>();
+                    markdown.push_str(&format!("\nLocalised fields: {}\n", localised_fields.join(", ")));
+                }
+            }
+        }
+
+        markdown
+    }
+
+    /// This function returns how many fields of all the definitions in the schema use each field type.
+    ///
+    /// Sequences are counted by their kind (`SequenceU16` or `SequenceU32`), and the fields inside them are counted too.
+    pub fn field_type_histogram(&self) -> BTreeMap<String, usize> {
+        fn count_fields(fields: &[Field], histogram: &mut BTreeMap<String, usize>) {
+            for field in fields {
+                *histogram.entry(field.field_type().to_string()).or_default() += 1;
+
+                if let FieldType::SequenceU16(definition) | FieldType::SequenceU32(definition) = field.field_type() {
+                    count_fields(definition.fields(), histogram);
+                }
+            }
+        }
+
+        let mut histogram = BTreeMap::new();
+        for definition in self.definitions.values().flatten() {
+            count_fields(definition.fields(), &mut histogram);
+        }
+
+        histogram
+    }
+
     pub fn definition_by_name_and_version(&self, table_name: &str, table_version: i32) -> Option<&Definition>  {
         self.definitions.get(table_name)?.iter().find(|definition| *definition.version() == table_version)
     }
 
     pub fn definition_by_name_and_version_mut(&mut self, table_name: &str, table_version: i32) -> Option<&mut Definition>  {
+        self.caches_clear();
         self.definitions.get_mut(table_name)?.iter_mut().find(|definition| *definition.version() == table_version)
     }
 
@@ -385,6 +681,17 @@ impl Schema {
             }
         }
 
+        // Broken definitions still load, but they're worth a warning.
+        #[cfg(feature = "integration_log")] {
+            for (table_name, definitions) in schema.definitions() {
+                for definition in definitions {
+                    for issue in definition.validate(table_name) {
+                        warn!("Issue found in the definition of {}, version {}: {:?}", table_name, definition.version(), issue);
+                    }
+                }
+            }
+        }
+
         Ok(schema)
     }
 
@@ -502,6 +809,88 @@ impl Schema {
         v4::SchemaV4::update(schema_path, schema_patches_path, game_name)
     }
 
+    /// This function renames a table in the schema, moving all its definitions and patches to the new name,
+    /// and updating all references to it in the fields and patches of other tables.
+    ///
+    /// Table names can be provided with or without the `_tables` suffix: definitions are stored with it, while references use the name without it.
+    /// If the new name already has definitions, the ones with the same version are replaced with the ones of the renamed table.
+    pub fn rename_table(&mut self, old: &str, new: &str) -> RenameReport {
+        self.caches_clear();
+        let mut report = RenameReport::default();
+
+        let old_ref = old.strip_suffix("_tables").unwrap_or(old);
+        let new_ref = new.strip_suffix("_tables").unwrap_or(new);
+        let old_name = format!("{old_ref}_tables");
+        let new_name = format!("{new_ref}_tables");
+
+        if old_name == new_name {
+            return report;
+        }
+
+        // First, move the definitions and patches to the new name.
+        if let Some(definitions) = self.definitions.remove(&old_name) {
+            for definition in &definitions {
+                report.versions_renamed.push(*definition.version());
+                self.add_definition(&new_name, definition);
+            }
+        }
+
+        if let Some(patches) = self.patches.remove(&old_name) {
+            Self::add_patch_to_patch_set(&mut self.patches, &HashMap::from([(new_name.to_owned(), patches)]));
+            report.patches_renamed = true;
+        }
+
+        // Then, update the references in the fields of all definitions, including the ones within sequences.
+        for (table_name, definitions) in self.definitions.iter_mut() {
+            for definition in definitions.iter_mut() {
+                let version = *definition.version();
+                for field in definition.fields_mut().iter_mut() {
+                    if Self::rename_table_in_field(field, old_ref, new_ref) {
+                        report.references_updated.push((table_name.to_owned(), version, field.name().to_owned()));
+                    }
+                }
+            }
+        }
+
+        // And finally, update the references in the patches. Patched references use the `table;column` format.
+        for (table_name, patches) in self.patches.iter_mut() {
+            for (field_name, field_patches) in patches.iter_mut() {
+                if let Some(reference) = field_patches.get_mut("is_reference") {
+                    let split = reference.splitn(2, ';').collect::<Vec<_>>();
+                    if split.len() == 2 && split[0] == old_ref {
+                        *reference = format!("{};{}", new_ref, split[1]);
+                        report.patch_references_updated.push((table_name.to_owned(), field_name.to_owned()));
+                    }
+                }
+            }
+        }
+
+        report.references_updated.sort();
+        report.patch_references_updated.sort();
+        report
+    }
+
+    /// This function updates the reference of a field (and any field within it, if it's a sequence) from one table to another.
+    ///
+    /// Returns true if any reference was changed.
+    fn rename_table_in_field(field: &mut Field, old_ref: &str, new_ref: &str) -> bool {
+        let mut changed = false;
+        if let Some((ref mut ref_table, _)) = field.is_reference {
+            if *ref_table == old_ref {
+                *ref_table = new_ref.to_owned();
+                changed = true;
+            }
+        }
+
+        if let FieldType::SequenceU16(ref mut definition) | FieldType::SequenceU32(ref mut definition) = field.field_type {
+            for field in definition.fields_mut().iter_mut() {
+                changed |= Self::rename_table_in_field(field, old_ref, new_ref);
+            }
+        }
+
+        changed
+    }
+
     /// This function returns all columns that reference the columns on our specific table within the DB Tables of our Schema.
     ///
     /// Returns a list of (local_column_name, vec<(remote_table_name, remote_column_name)>).
@@ -546,6 +935,115 @@ impl Schema {
         }).collect()
     }
 
+    /// This function returns all the columns referenced by any column of any definition within our Schema.
+    ///
+    /// Returns a list of (remote_table_name, remote_column_name), with the table names without the `_tables` suffix.
+    pub fn referenced_columns(&self) -> HashSet<(String, String)> {
+        self.definitions().par_iter()
+            .flat_map(|(_, ver_definitions)| ver_definitions.par_iter()
+                .flat_map(|ver_definition| {
+                    let ver_patches = Some(ver_definition.patches());
+                    ver_definition.fields_processed()
+                        .iter()
+                        .filter_map(|ver_field| ver_field.is_reference(ver_patches))
+                        .collect::<Vec<_>>()
+                })
+            )
+            .collect()
+    }
+
+    /// This function returns the cycles found between the references of the tables within our Schema.
+    ///
+    /// Each cycle is a list of table names (with the `_tables` suffix) where each table references the next one, and the last one
+    /// references the first one. Tables only referencing themselves are not reported. At least one cycle is reported for every
+    /// group of tables referencing each other in a loop, but cycles sharing tables with an already reported one may be skipped.
+    ///
+    /// This goes through the entire schema. For repeated checks use [Schema::reference_cycles], which caches the result.
+    pub fn find_reference_cycles(&self) -> Vec<Vec<String>> {
+        let mut graph: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for (table_name, definitions) in self.definitions() {
+            let references = graph.entry(table_name).or_default();
+            for definition in definitions {
+                let patches = Some(definition.patches());
+                for field in definition.fields_processed() {
+                    if let Some((ref_table, _)) = field.is_reference(patches) {
+                        let ref_table = format!("{ref_table}_tables");
+                        if &ref_table != table_name && self.definitions().contains_key(&ref_table) {
+                            references.insert(ref_table);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut cycles = BTreeSet::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![];
+        for table_name in graph.keys() {
+            Self::find_reference_cycles_from(&graph, table_name, &mut visited, &mut stack, &mut cycles);
+        }
+
+        cycles.into_iter().collect()
+    }
+
+    /// This function returns the reference cycles of the schema, as returned by [Schema::find_reference_cycles].
+    ///
+    /// The cycles are searched the first time they're requested, and reused until the definitions or patches of the schema change.
+    pub fn reference_cycles(&self) -> Arc<Vec<Vec<String>>> {
+        self.reference_cycles_cache.get_or_build(|| self.find_reference_cycles())
+    }
+
+    /// This function returns the issues [Definition::validate] finds in the definitions of each table of the schema.
+    ///
+    /// Tables without issues are not included, and issues repeated between versions of the same table are only reported once.
+    /// The issues are searched the first time they're requested, and reused until the definitions or patches of the schema change.
+    pub fn definition_issues(&self) -> Arc<BTreeMap<String, Vec<DefinitionIssue>>> {
+        self.definition_issues_cache.get_or_build(|| {
+            let mut issues: BTreeMap<String, Vec<DefinitionIssue>> = BTreeMap::new();
+            for (table_name, definitions) in self.definitions() {
+                for definition in definitions {
+                    for issue in definition.validate(table_name) {
+                        let table_issues = issues.entry(table_name.to_owned()).or_default();
+                        if !table_issues.contains(&issue) {
+                            table_issues.push(issue);
+                        }
+                    }
+                }
+            }
+
+            issues
+        })
+    }
+
+    /// Recursive part of `find_reference_cycles`, doing a depth-first search from the provided table.
+    fn find_reference_cycles_from<'a>(graph: &'a BTreeMap<&'a str, BTreeSet<String>>, table_name: &'a str, visited: &mut HashSet<&'a str>, stack: &mut Vec<&'a str>, cycles: &mut BTreeSet<Vec<String>>) {
+
+        // If we reach a table we're already going through, we found a cycle.
+        if let Some(position) = stack.iter().position(|name| *name == table_name) {
+            let mut cycle = stack[position..].iter().map(|name| name.to_string()).collect::<Vec<_>>();
+
+            // Start cycles by their first table alphabetically, so the same cycle is not reported twice.
+            if let Some(first) = cycle.iter().enumerate().min_by_key(|(_, name)| *name).map(|(index, _)| index) {
+                cycle.rotate_left(first);
+            }
+
+            cycles.insert(cycle);
+            return;
+        }
+
+        if !visited.insert(table_name) {
+            return;
+        }
+
+        stack.push(table_name);
+        if let Some(references) = graph.get(table_name) {
+            for reference in references {
+                Self::find_reference_cycles_from(graph, reference, visited, stack, cycles);
+            }
+        }
+        stack.pop();
+    }
+
     /// This function tries to load multiple patches from a str.
     pub fn load_patches_from_str(patch: &str) -> Result<HashMap<String, DefinitionPatch>> {
         from_str(patch).map_err(From::from)
@@ -635,6 +1133,7 @@ impl Definition {
     /// This function returns the list of fields a table contains, after it has been expanded/changed due to the attributes of each field.
     pub fn fields_processed(&self) -> Vec<Field> {
         let mut split_colour_fields: BTreeMap<u8, Field> = BTreeMap::new();
+        let mut split_colour_alphas: BTreeMap<u8, String> = BTreeMap::new();
         let mut fields = self.fields().iter()
             .filter_map(|x|
                 if x.is_bitwise() > 1 {
@@ -653,6 +1152,14 @@ impl Definition {
                 }
 
                 else if let Some(colour_index) = x.is_part_of_colour() {
+
+                    // The alpha channel is kept apart, as it changes the type of the combined field.
+                    let is_alpha = x.name.ends_with("_a") || x.name.ends_with("_alpha") || x.name == "a" || x.name == "alpha";
+                    if is_alpha {
+                        let alpha = x.default_value(None).map(|default_value| default_value.parse::<i32>().unwrap_or(0)).unwrap_or(0);
+                        split_colour_alphas.insert(colour_index, format!("{alpha:02X}"));
+                    }
+
                     match split_colour_fields.get_mut(&colour_index) {
 
                         // If found, add the default value to the other previously known default value.
@@ -671,6 +1178,8 @@ impl Definition {
                                         field.default_value.clone().map(|df| {
                                             format!("{}{:X}", &df[..4], default_value.parse::<i32>().unwrap_or(0))
                                         })
+                                    } else if is_alpha {
+                                        field.default_value.clone()
                                     } else {
                                         Some("000000".to_owned())
                                     }
@@ -725,6 +1234,15 @@ impl Definition {
             .flatten()
             .collect::<Vec<Field>>();
 
+        // Groups with an alpha channel become ColourRGBA fields. Like in the packed colours, the alpha goes first.
+        for (colour_index, alpha) in &split_colour_alphas {
+            if let Some(field) = split_colour_fields.get_mut(colour_index) {
+                let default_value = field.default_value.clone().map(|df| format!("{alpha}{df}"));
+                field.set_field_type(FieldType::ColourRGBA);
+                field.set_default_value(default_value);
+            }
+        }
+
         // Second pass to add the combined colour fields.
         fields.append(&mut split_colour_fields.values().cloned().collect::<Vec<Field>>());
         fields
@@ -762,6 +1280,132 @@ impl Definition {
         fields
     }
 
+    /// This function checks the definition for problems that break name-based column mapping, like duplicated or empty field names,
+    /// or references to fields that don't exist in the definition.
+    ///
+    /// The table name (with or without the `_tables` suffix) is used to find references to the table's own columns.
+    ///
+    /// Returns the list of issues found, in the order they were found. Duplicated names are only reported once.
+    pub fn validate(&self, table_name: &str) -> Vec<DefinitionIssue> {
+        let mut issues = vec![];
+        let mut names = HashSet::new();
+        let mut duplicated = HashSet::new();
+
+        for (index, field) in self.fields().iter().chain(self.localised_fields().iter()).enumerate() {
+            if field.name().is_empty() {
+                issues.push(DefinitionIssue::EmptyFieldName(index));
+            } else if !names.insert(field.name()) && duplicated.insert(field.name()) {
+                issues.push(DefinitionIssue::DuplicateFieldName(field.name().to_owned()));
+            }
+        }
+
+        let fields_processed_len = self.fields_processed().len();
+        for position in self.localised_key_order() {
+            if *position as usize >= fields_processed_len {
+                issues.push(DefinitionIssue::LocalisedKeyOrderOutOfBounds(*position));
+            }
+        }
+
+        let mut patched_fields = self.patches().keys().collect::<Vec<_>>();
+        patched_fields.sort();
+        for field_name in patched_fields {
+            if !names.contains(&field_name) && self.column_position_by_name(field_name).is_none() {
+                issues.push(DefinitionIssue::PatchForMissingField(field_name.to_owned()));
+            }
+        }
+
+        // References to our own table must point to another column that exists.
+        let table_name = table_name.strip_suffix("_tables").unwrap_or(table_name);
+        let patches = Some(self.patches());
+        for field in self.fields_processed() {
+            if let Some((ref_table, ref_column)) = field.is_reference(patches) {
+                if ref_table == table_name {
+                    if ref_column == field.name() {
+                        issues.push(DefinitionIssue::SelfReferencingField(field.name().to_owned()));
+                    } else if self.column_position_by_name(&ref_column).is_none() {
+                        issues.push(DefinitionIssue::ReferenceToMissingField(field.name().to_owned(), ref_column));
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// This function merges another definition into this one, returning the merged definition.
+    ///
+    /// Fields are matched by name. For fields present in both definitions, the type, reference and default value of the other definition
+    /// are used, keeping the data of this one for anything the other one lacks. The other definition's field order is kept, with fields only
+    /// present in this definition inserted after the field they followed here. The resulting version is the highest of both.
+    ///
+    /// This doesn't check if the field types are compatible. Use [Definition::merge_conflicts] for that.
+    pub fn merge(&self, other: &Definition) -> Definition {
+        let mut merged = other.clone();
+        merged.version = self.version.max(other.version);
+        merged.fields = Self::merge_fields(&self.fields, &other.fields);
+        merged.localised_fields = Self::merge_fields(&self.localised_fields, &other.localised_fields);
+        merged.patches = self.patches.clone();
+        merged.patches.extend(other.patches.clone());
+
+        // The localised key order points to positions of processed fields, so it needs to be remapped to where those fields ended up.
+        // Positions of fields that no longer exist are dropped.
+        let source = if other.localised_key_order.is_empty() { self } else { other };
+        let source_fields = source.fields_processed();
+        let merged_fields = merged.fields_processed();
+        merged.localised_key_order = source.localised_key_order.iter()
+            .filter_map(|position| source_fields.get(*position as usize))
+            .filter_map(|field| merged_fields.iter().position(|merged_field| merged_field.name() == field.name()))
+            .map(|position| position as u32)
+            .collect();
+
+        merged
+    }
+
+    /// This function returns the fields that exist in both this and the other definition with incompatible types,
+    /// so they can be reviewed before using the result of [Definition::merge].
+    ///
+    /// Types are considered compatible if they only differ in size or optionality, like `I32` and `OptionalI64`.
+    pub fn merge_conflicts(&self, other: &Definition) -> Vec<DefinitionMergeConflict> {
+        self.fields.iter()
+            .chain(self.localised_fields.iter())
+            .filter_map(|field| {
+                let other_field = other.fields.iter()
+                    .chain(other.localised_fields.iter())
+                    .find(|other_field| other_field.name == field.name)?;
+
+                if field.field_type.is_compatible_with(&other_field.field_type) {
+                    None
+                } else {
+                    Some(DefinitionMergeConflict::IncompatibleFieldType(field.name.to_owned(), field.field_type.clone(), other_field.field_type.clone()))
+                }
+            })
+            .collect()
+    }
+
+    /// This function merges two lists of fields by name, following the order of the second one.
+    fn merge_fields(ours: &[Field], theirs: &[Field]) -> Vec<Field> {
+        let mut merged = theirs.iter()
+            .map(|their_field| match ours.iter().find(|our_field| our_field.name == their_field.name) {
+                Some(our_field) => our_field.merge(their_field),
+                None => their_field.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        for (index, our_field) in ours.iter().enumerate() {
+            if !merged.iter().any(|field| field.name == our_field.name) {
+                let position = ours[..index].iter()
+                    .rev()
+                    .find_map(|previous| merged.iter().position(|field| field.name == previous.name))
+                    .map(|position| position + 1)
+                    .unwrap_or(0);
+
+                merged.insert(position, our_field.clone());
+            }
+        }
+
+        merged
+    }
+
     /// This function returns the position of a column in a definition, or an error if the column is not found.
     pub fn column_position_by_name(&self, column_name: &str) -> Option<usize> {
         self.fields_processed()
@@ -769,6 +1413,30 @@ impl Definition {
             .position(|x| x.name() == column_name)
     }
 
+    /// This function returns the loc keys the game expects for the localised fields of the provided row.
+    ///
+    /// Loc keys are built as `{table_name}_{localised_field}_{key}`, with the key being the concatenation of the key fields
+    /// in the localised key order. The table name can be passed with or without the `_tables` suffix.
+    ///
+    /// If the row key is empty (usually due to an incomplete schema) no keys are returned.
+    pub fn expected_loc_keys_for_row(&self, row: &[DecodedData], table_name: &str) -> Vec<String> {
+        let table_name = table_name.strip_suffix("_tables").unwrap_or(table_name);
+        let key = self.localised_key_order()
+            .iter()
+            .filter_map(|position| row.get(*position as usize))
+            .map(|data| data.data_to_string())
+            .collect::<String>();
+
+        if key.is_empty() {
+            return vec![];
+        }
+
+        self.localised_fields()
+            .iter()
+            .map(|field| format!("{}_{}_{}", table_name, field.name(), key))
+            .collect()
+    }
+
     /// This function updates the fields in the provided definition with the data in the provided RawDefinition.
     ///
     /// Not all data is updated though, only:
@@ -930,7 +1598,9 @@ impl Field {
             ca_order,
             is_bitwise,
             enum_values,
-            is_part_of_colour
+            is_part_of_colour,
+            validation_regex: None,
+            is_unsigned: false,
         }
     }
 
@@ -1059,6 +1729,32 @@ impl Field {
         self.is_part_of_colour
     }
 
+    /// Getter for the `validation_regex` field.
+    pub fn validation_regex(&self, schema_patches: Option<&DefinitionPatch>) -> Option<String> {
+        if let Some(schema_patches) = schema_patches {
+            if let Some(patch) = schema_patches.get(self.name()) {
+                if let Some(field_patch) = patch.get("validation_regex") {
+                    return Some(field_patch.to_owned());
+                }
+            }
+        }
+
+        self.validation_regex.clone()
+    }
+
+    /// Getter for the `is_unsigned` field.
+    pub fn is_unsigned(&self, schema_patches: Option<&DefinitionPatch>) -> bool {
+        if let Some(schema_patches) = schema_patches {
+            if let Some(patch) = schema_patches.get(self.name()) {
+                if let Some(is_unsigned) = patch.get("is_unsigned") {
+                    return is_unsigned.parse::<bool>().unwrap_or(false);
+                }
+            }
+        }
+
+        self.is_unsigned
+    }
+
     /// Getter for the `cannot_be_empty` field.
     pub fn cannot_be_empty(&self, schema_patches: Option<&DefinitionPatch>) -> bool {
         if let Some(schema_patches) = schema_patches {
@@ -1071,6 +1767,83 @@ impl Field {
 
         false
     }
+
+    /// This function returns the integer key of the provided enum value of this field, if it's one of its enum values.
+    ///
+    /// Enum values are compared case-insensitively, the same way they're matched when encoding tables.
+    pub fn enum_key(&self, value: &str) -> Option<i32> {
+        let value = value.to_lowercase();
+        self.enum_values.iter().find_map(|(key, enum_value)| if enum_value.to_lowercase() == value { Some(*key) } else { None })
+    }
+
+    /// This function checks if the provided string value of an enum field can be encoded back to an integer without losing it.
+    ///
+    /// That's the case if it's one of the enum values of the field, or if it's directly a valid integer for the field's type.
+    /// Fields without enum values always return true.
+    pub fn is_enum_value_encodable(&self, value: &str) -> bool {
+        self.enum_values.is_empty() ||
+            self.enum_key(value).is_some() ||
+            DecodedData::StringU8(value.to_owned()).convert_between_types_with_sign(&self.field_type, false, self.is_unsigned).is_ok()
+    }
+
+    /// This function merges another field with the same name into this one, preferring the other's data when both have it.
+    fn merge(&self, other: &Field) -> Field {
+        let mut merged = other.clone();
+
+        if merged.default_value.is_none() {
+            merged.default_value = self.default_value.clone();
+        }
+
+        if merged.filename_relative_path.is_none() {
+            merged.filename_relative_path = self.filename_relative_path.clone();
+        }
+
+        if merged.is_reference.is_none() {
+            merged.is_reference = self.is_reference.clone();
+        }
+
+        if merged.lookup.is_none() {
+            merged.lookup = self.lookup.clone();
+        }
+
+        if merged.description.is_empty() {
+            merged.description = self.description.to_owned();
+        }
+
+        if merged.enum_values.is_empty() {
+            merged.enum_values = self.enum_values.clone();
+        }
+
+        if merged.is_part_of_colour.is_none() {
+            merged.is_part_of_colour = self.is_part_of_colour;
+        }
+
+        if merged.validation_regex.is_none() {
+            merged.validation_regex = self.validation_regex.clone();
+        }
+
+        merged
+    }
+}
+
+impl FieldType {
+
+    /// This function checks if data of this type can be converted to the other type without changing its meaning.
+    ///
+    /// Types are compatible if they're the same type, or if they only differ in size or optionality.
+    pub fn is_compatible_with(&self, other: &FieldType) -> bool {
+        matches!((self, other),
+            (FieldType::Boolean, FieldType::Boolean) |
+            (FieldType::ColourRGB, FieldType::ColourRGB) |
+            (FieldType::ColourRGBA, FieldType::ColourRGBA) |
+            (FieldType::F32 | FieldType::F64, FieldType::F32 | FieldType::F64) |
+            (FieldType::I16 | FieldType::I32 | FieldType::I64 | FieldType::OptionalI16 | FieldType::OptionalI32 | FieldType::OptionalI64,
+                FieldType::I16 | FieldType::I32 | FieldType::I64 | FieldType::OptionalI16 | FieldType::OptionalI32 | FieldType::OptionalI64) |
+            (FieldType::StringU8 | FieldType::StringU16 | FieldType::OptionalStringU8 | FieldType::OptionalStringU16,
+                FieldType::StringU8 | FieldType::StringU16 | FieldType::OptionalStringU8 | FieldType::OptionalStringU16) |
+            (FieldType::SequenceU16(_) | FieldType::SequenceU32(_), FieldType::SequenceU16(_) | FieldType::SequenceU32(_))
+        )
+    }
 }
 
 //---------------------------------------------------------------------------//
@@ -1083,12 +1856,75 @@ impl Default for Schema {
         Self {
             version: CURRENT_STRUCTURAL_VERSION,
             definitions: HashMap::new(),
-            patches: HashMap::new()
+            patches: HashMap::new(),
+            referencing_columns_cache: SchemaCache::default(),
+            reference_cycles_cache: SchemaCache::default(),
+            definition_issues_cache: SchemaCache::default(),
         }
     }
 }
 
+impl<T> SchemaCache<T> {
+
+    /// This function returns the cached data, building it with the provided function if it's not cached yet.
+    fn get_or_build<F: FnOnce() -> T>(&self, build: F) -> Arc<T> {
+        if let Some(data) = &*self.0.read().unwrap() {
+            return data.clone();
+        }
+
+        let data = Arc::new(build());
+        *self.0.write().unwrap() = Some(data.clone());
+        data
+    }
+}
+
+impl<T> Default for SchemaCache<T> {
+    fn default() -> Self {
+        Self(Arc::new(RwLock::new(None)))
+    }
+}
+
+/// The cache is not part of the schema's data, so it never makes two schemas different.
+impl<T> PartialEq for SchemaCache<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for SchemaCache<T> {}
+
 /// Default implementation of `FieldType`.
+/// Helpers to build definitions for tests.
+#[cfg(any(test, feature = "test_utils"))]
+impl Definition {
+
+    /// This function creates a new `Definition` for tests, with the fields provided as `(name, type, is_key)`.
+    ///
+    /// The rest of the data of the fields uses the default values. Use [Definition::fields_mut] and the setters of [Field] to change it.
+    pub fn new_for_tests(version: i32, fields: &[(&str, FieldType, bool)], loc_fields: &[(&str, FieldType, bool)]) -> Definition {
+        let build = |fields: &[(&str, FieldType, bool)]| fields.iter()
+            .map(|(name, field_type, is_key)| Field::new_for_tests(name, field_type.clone(), *is_key))
+            .collect::<Vec<_>>();
+
+        Definition::new_with_fields(version, &build(fields), &build(loc_fields), None)
+    }
+}
+
+/// Helpers to build fields for tests.
+#[cfg(any(test, feature = "test_utils"))]
+impl Field {
+
+    /// This function creates a new `Field` for tests, with default values for everything but its name, type and if it's a key.
+    pub fn new_for_tests(name: &str, field_type: FieldType, is_key: bool) -> Field {
+        Field {
+            name: name.to_owned(),
+            field_type,
+            is_key,
+            ..Default::default()
+        }
+    }
+}
+
 impl Default for Field {
     fn default() -> Self {
         Self {
@@ -1105,6 +1941,8 @@ impl Default for Field {
             is_bitwise: 0,
             enum_values: BTreeMap::new(),
             is_part_of_colour: None,
+            validation_regex: None,
+            is_unsigned: false,
         }
     }
 }
@@ -1120,6 +1958,7 @@ impl Display for FieldType {
             FieldType::I32 => write!(f, "I32"),
             FieldType::I64 => write!(f, "I64"),
             FieldType::ColourRGB => write!(f, "ColourRGB"),
+            FieldType::ColourRGBA => write!(f, "ColourRGBA"),
             FieldType::StringU8 => write!(f, "StringU8"),
             FieldType::StringU16 => write!(f, "StringU16"),
             FieldType::OptionalI16 => write!(f, "OptionalI16"),
@@ -1144,6 +1983,7 @@ impl From<&DecodedData> for FieldType {
             DecodedData::I32(_) => FieldType::I32,
             DecodedData::I64(_) => FieldType::I64,
             DecodedData::ColourRGB(_) => FieldType::ColourRGB,
+            DecodedData::ColourRGBA(_) => FieldType::ColourRGBA,
             DecodedData::StringU8(_) => FieldType::StringU8,
             DecodedData::StringU16(_) => FieldType::StringU16,
             DecodedData::OptionalI16(_) => FieldType::OptionalI16,
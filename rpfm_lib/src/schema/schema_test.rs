@@ -0,0 +1,308 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing tests for the `Schema`.
+
+use super::*;
+
+/// Returns a definition with a key column, and a column referencing the provided table and column, if any.
+fn test_definition(reference: Option<(&str, &str)>) -> Definition {
+    let mut definition = Definition::new_for_tests(0, &[("key", FieldType::StringU8, true)], &[]);
+    if let Some((table, column)) = reference {
+        let mut field = Field::new_for_tests("reference", FieldType::StringU8, false);
+        field.set_is_reference(Some((table.to_owned(), column.to_owned())));
+        definition.fields_mut().push(field);
+    }
+
+    definition
+}
+
+#[test]
+fn test_find_reference_cycles() {
+    let mut schema = Schema::default();
+    schema.add_definition("a_tables", &test_definition(Some(("b", "key"))));
+    schema.add_definition("b_tables", &test_definition(Some(("a", "key"))));
+    schema.add_definition("c_tables", &test_definition(Some(("a", "key"))));
+    schema.add_definition("d_tables", &test_definition(Some(("d", "key"))));
+    schema.add_definition("e_tables", &test_definition(None));
+
+    // Only the A -> B -> A cycle must be reported, as C only references it and D only references itself.
+    assert_eq!(schema.find_reference_cycles(), vec![vec!["a_tables".to_owned(), "b_tables".to_owned()]]);
+
+    // The cached cycles must be reused until the schema changes.
+    let cycles = schema.reference_cycles();
+    assert_eq!(*cycles, schema.find_reference_cycles());
+    assert!(Arc::ptr_eq(&cycles, &schema.reference_cycles()));
+
+    // Without the cycle there is nothing to report.
+    schema.add_definition("b_tables", &test_definition(Some(("e", "key"))));
+    assert!(schema.find_reference_cycles().is_empty());
+    assert!(schema.reference_cycles().is_empty());
+}
+
+#[test]
+fn test_tables_and_columns_referencing_our_own() {
+    use crate::files::table::Table;
+
+    let mut schema = Schema::default();
+    schema.add_definition("a_tables", &test_definition(None));
+    schema.add_definition("b_tables", &test_definition(Some(("a", "key"))));
+    schema.add_definition("c_tables", &test_definition(Some(("b", "reference"))));
+    let schema = Some(schema);
+
+    // References must be followed through multiple levels.
+    let (tables, _) = Table::tables_and_columns_referencing_our_own(&schema, "a_tables", "key", &[], &[]).unwrap();
+    assert_eq!(tables, BTreeMap::from([
+        ("b_tables".to_owned(), vec!["reference".to_owned()]),
+        ("c_tables".to_owned(), vec!["reference".to_owned()]),
+    ]));
+
+    // Changes to the schema must invalidate the cached references, but not the ones of its clones.
+    let mut schema = schema.unwrap();
+    let old_schema = Some(schema.clone());
+    schema.add_definition("d_tables", &test_definition(Some(("a", "key"))));
+    let schema = Some(schema);
+    let (tables, _) = Table::tables_and_columns_referencing_our_own(&schema, "a_tables", "key", &[], &[]).unwrap();
+    assert!(tables.contains_key("d_tables"));
+    let (tables, _) = Table::tables_and_columns_referencing_our_own(&old_schema, "a_tables", "key", &[], &[]).unwrap();
+    assert!(!tables.contains_key("d_tables"));
+
+    // Reference cycles must not loop forever.
+    let mut schema = schema.unwrap();
+    schema.add_definition("a_tables", &test_definition(Some(("c", "reference"))));
+    schema.add_definition("b_tables", &test_definition(Some(("a", "reference"))));
+    let schema = Some(schema);
+    let (tables, _) = Table::tables_and_columns_referencing_our_own(&schema, "a_tables", "reference", &[], &[]).unwrap();
+    assert_eq!(tables.keys().collect::<Vec<_>>(), vec!["a_tables", "b_tables", "c_tables"]);
+
+    assert!(Table::tables_and_columns_referencing_our_own(&None, "a_tables", "key", &[], &[]).is_none());
+}
+
+#[test]
+fn test_table_referenced_columns() {
+    use crate::files::table::Table;
+
+    let mut schema = Schema::default();
+    schema.add_definition("a_tables", &test_definition(Some(("b", "key"))));
+    schema.add_definition("b_tables", &test_definition(Some(("a", "key"))));
+    schema.add_definition("c_tables", &test_definition(Some(("b", "reference"))));
+
+    let table = Table::new(&test_definition(Some(("b", "key"))), None, "a_tables");
+    assert_eq!(table.referenced_columns(&schema), vec!["key".to_owned()]);
+
+    let table = Table::new(&test_definition(Some(("a", "key"))), None, "b_tables");
+    assert_eq!(table.referenced_columns(&schema), vec!["key".to_owned(), "reference".to_owned()]);
+
+    let table = Table::new(&test_definition(Some(("b", "reference"))), None, "c_tables");
+    assert!(table.referenced_columns(&schema).is_empty());
+}
+
+#[test]
+fn test_definition_validate() {
+    assert!(test_definition(Some(("a", "key"))).validate("b_tables").is_empty());
+
+    // References to the same table must point to another column that exists.
+    assert!(test_definition(Some(("a", "key"))).validate("a_tables").is_empty());
+    assert_eq!(test_definition(Some(("a", "reference"))).validate("a_tables"), vec![DefinitionIssue::SelfReferencingField("reference".to_owned())]);
+    assert_eq!(test_definition(Some(("a", "missing"))).validate("a"), vec![DefinitionIssue::ReferenceToMissingField("reference".to_owned(), "missing".to_owned())]);
+
+    let mut fields = test_definition(None).fields().to_vec();
+    fields.push(fields[0].clone());
+    fields.push(Field::new_for_tests("", FieldType::Boolean, false));
+    fields.push(fields[0].clone());
+
+    let mut definition = Definition::new_with_fields(0, &fields, &[], None);
+    definition.set_localised_key_order(vec![0, 4]);
+    definition.set_patches(HashMap::from([("missing".to_owned(), HashMap::new())]));
+
+    // Duplicates must only be reported once, no matter how many times they're repeated.
+    assert_eq!(definition.validate("a_tables"), vec![
+        DefinitionIssue::DuplicateFieldName("key".to_owned()),
+        DefinitionIssue::EmptyFieldName(2),
+        DefinitionIssue::LocalisedKeyOrderOutOfBounds(4),
+        DefinitionIssue::PatchForMissingField("missing".to_owned()),
+    ]);
+}
+
+#[test]
+fn test_definition_merge() {
+    let field = |name: &str, field_type: FieldType, default_value: Option<&str>, description: &str| {
+        let mut field = Field::new_for_tests(name, field_type, false);
+        field.set_default_value(default_value.map(|x| x.to_owned()));
+        field.set_description(description.to_owned());
+        field
+    };
+
+    let ours = Definition::new_with_fields(1, &[
+        field("key", FieldType::StringU8, None, "Our key."),
+        field("ours_only", FieldType::I32, None, ""),
+        field("value", FieldType::I32, Some("1"), ""),
+        field("flag", FieldType::Boolean, None, ""),
+    ], &[], None);
+
+    let theirs = Definition::new_with_fields(2, &[
+        field("key", FieldType::OptionalStringU8, None, ""),
+        field("value", FieldType::I64, Some("2"), ""),
+        field("theirs_only", FieldType::F32, None, ""),
+        field("flag", FieldType::StringU8, None, ""),
+    ], &[], None);
+
+    // Their data must be preferred, keeping our data where they have none, and our own fields after the ones they followed.
+    let merged = ours.merge(&theirs);
+    assert_eq!(*merged.version(), 2);
+    assert_eq!(merged.fields().iter().map(|x| x.name()).collect::<Vec<_>>(), vec!["key", "ours_only", "value", "theirs_only", "flag"]);
+    assert_eq!(merged.fields()[0].field_type(), &FieldType::OptionalStringU8);
+    assert_eq!(merged.fields()[0].description(None), "Our key.");
+    assert_eq!(merged.fields()[2].field_type(), &FieldType::I64);
+    assert_eq!(merged.fields()[2].default_value(None), Some("2".to_owned()));
+
+    // The localised key order must point to the same fields after the merge, whichever definition it comes from.
+    let mut ours_with_loc_keys = ours.clone();
+    ours_with_loc_keys.set_localised_key_order(vec![0, 2]);
+    let merged = ours_with_loc_keys.merge(&theirs);
+    assert_eq!(merged.localised_key_order(), &vec![0, 2]);
+
+    let mut theirs_with_loc_keys = theirs.clone();
+    theirs_with_loc_keys.set_localised_key_order(vec![2, 1]);
+    let merged = ours.merge(&theirs_with_loc_keys);
+    assert_eq!(merged.localised_key_order(), &vec![3, 2]);
+
+    // Fields that move in the merged definition must be followed.
+    ours_with_loc_keys.set_localised_key_order(vec![3, 1]);
+    let merged = ours_with_loc_keys.merge(&theirs);
+    assert_eq!(merged.localised_key_order(), &vec![4, 1]);
+
+    // Only changes of type that alter the meaning of the data are conflicts.
+    assert_eq!(ours.merge_conflicts(&theirs), vec![
+        DefinitionMergeConflict::IncompatibleFieldType("flag".to_owned(), FieldType::Boolean, FieldType::StringU8),
+    ]);
+    assert!(ours.merge_conflicts(&ours).is_empty());
+}
+
+#[test]
+fn test_field_enum_value_encodable() {
+    let mut field = Field::default();
+    field.set_field_type(FieldType::I16);
+    assert!(field.is_enum_value_encodable("not_an_enum"));
+
+    field.set_enum_values(BTreeMap::from([(0, "Infantry".to_owned()), (1, "Cavalry".to_owned())]));
+    assert_eq!(field.enum_key("cavalry"), Some(1));
+    assert!(field.is_enum_value_encodable("INFANTRY"));
+    assert!(field.is_enum_value_encodable("7"));
+    assert!(!field.is_enum_value_encodable("artillery"));
+    assert!(!field.is_enum_value_encodable("100000"));
+}
+
+#[test]
+fn test_table_versions() {
+    let mut schema = Schema::default();
+    schema.add_definition("a_tables", &Definition::new_with_fields(1, &[], &[], None));
+    schema.add_definition("a_tables", &Definition::new_with_fields(2, test_definition(Some(("b", "key"))).fields(), &[], None));
+
+    // Both the short and the full table name must work, and the newest version must be first.
+    let versions = schema.table_versions("a");
+    assert_eq!(versions, schema.table_versions("a_tables"));
+    assert_eq!(versions.iter().map(|(version, _)| *version).collect::<Vec<_>>(), vec![2, 1]);
+    assert!(versions[1].1.is_empty());
+
+    let fields = &versions[0].1;
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].name(), "key");
+    assert!(fields[0].is_key());
+    assert_eq!(fields[0].reference(), &None);
+    assert_eq!(fields[1].field_type(), &FieldType::StringU8);
+    assert!(!fields[1].is_key());
+    assert_eq!(fields[1].reference(), &Some(("b".to_owned(), "key".to_owned())));
+
+    assert!(schema.table_versions("missing").is_empty());
+}
+
+#[test]
+fn test_all_table_names() {
+    let mut schema = Schema::default();
+    schema.add_definition("units_tables", &Definition::new_with_fields(1, &[], &[], None));
+    schema.add_definition("units_tables", &Definition::new_with_fields(2, &[], &[], None));
+    schema.add_definition("factions_tables", &Definition::new_with_fields(1, &[], &[], None));
+    schema.definitions_mut().insert("empty_tables".to_owned(), vec![]);
+
+    // Tables are returned once, sorted, and only if they have definitions.
+    assert_eq!(schema.all_table_names(), vec!["factions_tables".to_owned(), "units_tables".to_owned()]);
+    assert!(Schema::default().all_table_names().is_empty());
+}
+
+#[test]
+fn test_to_markdown() {
+    let mut definition = Definition::new_for_tests(1, &[("key", FieldType::StringU8, true), ("value", FieldType::I32, false)], &[("name", FieldType::StringU16, false)]);
+    definition.fields_mut()[1].set_default_value(Some("1".to_owned()));
+    definition.fields_mut()[1].set_description("Cost | per turn.".to_owned());
+
+    let mut schema = Schema::default();
+    schema.add_definition("b_tables", &test_definition(None));
+    schema.add_definition("b_tables", &definition);
+    schema.add_definition("a_tables", &test_definition(Some(("b", "key"))));
+
+    // Tables must be sorted by name and versions from newest to oldest, with pipes in the texts escaped.
+    assert_eq!(schema.to_markdown(), "# Schema
+
+## a_tables
+
+### Version 0
+
+| Name | Type | Key | Reference | Default | Description |
+|------|------|-----|-----------|---------|-------------|
+| key | StringU8 | Yes |  |  |  |
+| reference | StringU8 |  | b.key |  |  |
+
+## b_tables
+
+### Version 1
+
+| Name | Type | Key | Reference | Default | Description |
+|------|------|-----|-----------|---------|-------------|
+| key | StringU8 | Yes |  |  |  |
+| value | I32 |  |  | 1 | Cost \\| per turn. |
+
+Localised fields: name
+
+### Version 0
+
+| Name | Type | Key | Reference | Default | Description |
+|------|------|-----|-----------|---------|-------------|
+| key | StringU8 | Yes |  |  |  |
+");
+}
+
+#[test]
+fn test_field_type_histogram() {
+    let mut schema = Schema::default();
+    schema.add_definition("a_tables", &test_definition(Some(("b", "key"))));
+    schema.add_definition("b_tables", &Definition::new_for_tests(0, &[("sequence", FieldType::SequenceU32(Box::new(test_definition(None))), false)], &[]));
+
+    // Fields inside sequences must be counted too.
+    let histogram = schema.field_type_histogram();
+    assert_eq!(histogram.len(), 2);
+    assert_eq!(histogram.get("StringU8"), Some(&3));
+    assert_eq!(histogram.get("SequenceU32"), Some(&1));
+}
+
+#[test]
+fn test_definition_issues() {
+    let mut schema = Schema::default();
+    schema.add_definition("a_tables", &test_definition(Some(("a", "reference"))));
+    schema.add_definition("b_tables", &test_definition(Some(("a", "key"))));
+
+    // Only tables with issues are reported, and the result is cached until the schema changes.
+    let issues = schema.definition_issues();
+    assert_eq!(*issues, BTreeMap::from([("a_tables".to_owned(), vec![DefinitionIssue::SelfReferencingField("reference".to_owned())])]));
+    assert!(Arc::ptr_eq(&issues, &schema.definition_issues()));
+
+    schema.add_definition("a_tables", &test_definition(None));
+    assert!(schema.definition_issues().is_empty());
+}
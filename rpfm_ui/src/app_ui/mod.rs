@@ -30,6 +30,7 @@ use qt_widgets::QLineEdit;
 use qt_widgets::QListView;
 use qt_widgets::QMainWindow;
 use qt_widgets::QMenu;
+use qt_widgets::QProgressBar;
 use qt_widgets::QSpinBox;
 use qt_widgets::{q_message_box, QMessageBox};
 use qt_widgets::QScrollArea;
@@ -81,11 +82,12 @@ use rpfm_ui_common::ASSETS_PATH;
 use rpfm_ui_common::clone;
 use rpfm_ui_common::FULL_DATE_FORMAT;
 use rpfm_ui_common::icons::IconType;
-use rpfm_ui_common::locale::{qtr, tre};
+use rpfm_ui_common::locale::{qtr, tr, tre};
 
 use crate::backend::*;
 use crate::CENTRAL_COMMAND;
 use crate::communications::{CentralCommand, Command, Response, THREADS_COMMUNICATION_ERROR};
+use crate::DEPENDENCIES_CACHE_GENERATION_RUNNING;
 use crate::dependencies_ui::DependenciesUI;
 use crate::diagnostics_ui::DiagnosticsUI;
 use crate::ffi::*;
@@ -1346,6 +1348,7 @@ impl AppUI {
 
                 // Close the Global Search stuff and reset the filter's history.
                 GlobalSearchUI::clear(global_search_ui);
+                UI_STATE.clear_diagnostics_snoozed();
 
                 // If it's a "MyMod" (game_folder_name is not empty), we choose the Game selected Depending on it.
                 if !game_folder.is_empty() && pack_file_paths.len() == 1 {
@@ -1530,6 +1533,7 @@ impl AppUI {
             if file_dialog.exec() == 1 {
                 let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
                 let file_name = path.file_name().unwrap().to_string_lossy().as_ref().to_owned();
+                let name_issue = GAME_SELECTED.read().unwrap().is_valid_pack_name(&file_name).err();
                 let receiver = CENTRAL_COMMAND.send_background(Command::SavePackFileAs(path));
                 let response = CENTRAL_COMMAND.recv_try(&receiver);
                 match response {
@@ -1541,6 +1545,10 @@ impl AppUI {
 
                         UI_STATE.set_operational_mode(app_ui, None);
                         UI_STATE.set_is_modified(false, app_ui, pack_file_contents_ui);
+
+                        if let Some(issue) = name_issue {
+                            show_message_warning(&app_ui.message_widget, tre("save_packfile_invalid_name", &[&issue.to_string()]));
+                        }
                     }
                     Response::Error(error) => result = Err(error),
 
@@ -2299,12 +2307,17 @@ impl AppUI {
                 tab.main_widget().set_context_menu_policy(ContextMenuPolicy::CustomContextMenu);
                 tab.set_path(path);
 
-                // Any table banned or from out of our PackFile should not be editable.
+                // Any table banned, marked as read-only or from out of our PackFile should not be editable.
                 if let DataSource::PackFile = data_source {
                     if GAME_SELECTED.read().unwrap().is_file_banned(path) {
                         tab.set_is_read_only(true);
                     } else {
-                        tab.set_is_read_only(false);
+                        let receiver = CENTRAL_COMMAND.send_background(Command::IsPathReadonly(path.to_owned()));
+                        let response = CentralCommand::recv(&receiver);
+                        match response {
+                            Response::Bool(readonly) => tab.set_is_read_only(readonly),
+                            _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+                        }
                     }
                 } else {
                     tab.set_is_read_only(true);
@@ -2957,6 +2970,114 @@ impl AppUI {
         Self::update_views_names(app_ui);
     }
 
+    /// This function generates the dependencies cache for the Game Selected, and reloads the dependencies panel with it.
+    ///
+    /// The cache is generated in the background, with its progress shown in the status bar, so the UI stays usable meanwhile.
+    /// Errors are reported through a dialog.
+    pub unsafe fn generate_dependencies_cache(app_ui: &Rc<Self>, dependencies_ui: &Rc<DependenciesUI>, show_success_dialog: bool) {
+
+        // Only one generation at a time.
+        if DEPENDENCIES_CACHE_GENERATION_RUNNING.swap(true, Ordering::SeqCst) {
+            show_message_warning(&app_ui.message_widget, tr("generate_dependencies_cache_already_running"));
+            return;
+        }
+
+        if (GAME_SELECTED.read().unwrap().raw_db_version() > &0 && !setting_path(&format!("{}_assembly_kit", GAME_SELECTED.read().unwrap().key())).is_dir()) ||
+            (*GAME_SELECTED.read().unwrap().raw_db_version() == 0 && !old_ak_files_path().unwrap_or(PathBuf::new()).join(GAME_SELECTED.read().unwrap().key()).is_dir()) {
+            show_dialog(&app_ui.main_window, tr("generate_dependencies_cache_warn"), false);
+        }
+
+        // If there is no problem, ere we go.
+        let status_bar = q_ptr_from_atomic(&STATUS_BAR);
+        let progress_bar = QProgressBar::new_1a(&status_bar);
+        progress_bar.set_tool_tip(&qtr("generate_dependencies_cache_in_progress_message"));
+        progress_bar.set_format(&qtr("generate_dependencies_cache_progress"));
+        progress_bar.set_maximum_width(300);
+        progress_bar.set_range(0, 0);
+        status_bar.add_widget_1a(&progress_bar);
+
+        // Poll the background thread for progress until we get the final response.
+        let receiver = CENTRAL_COMMAND.send_background(Command::GenerateDependenciesCache);
+        let timer = QTimer::new_1a(&app_ui.main_window);
+        let timer_ptr = timer.as_ptr();
+        let progress_bar = progress_bar.as_ptr();
+
+        let slot = SlotNoArgs::new(&timer, clone!(
+            app_ui,
+            dependencies_ui => move || {
+                loop {
+                    let response = match receiver.try_recv() {
+                        Ok(response) => response,
+                        Err(error) => if error.is_disconnected() {
+                            panic!("{THREADS_COMMUNICATION_ERROR}{error:?}")
+                        } else {
+                            break;
+                        }
+                    };
+
+                    match response {
+                        Response::I32I32(done, total) => {
+                            progress_bar.set_range(0, total);
+                            progress_bar.set_value(done);
+                            continue;
+                        }
+                        Response::DependenciesInfo(response) => {
+                            let mut parent_build_data = BuildData::new();
+                            parent_build_data.data = Some((ContainerInfo::default(), response.parent_packed_files().to_vec()));
+
+                            let mut game_build_data = BuildData::new();
+                            game_build_data.data = Some((ContainerInfo::default(), response.vanilla_packed_files().to_vec()));
+
+                            let mut asskit_build_data = BuildData::new();
+                            asskit_build_data.data = Some((ContainerInfo::default(), response.asskit_tables().to_vec()));
+
+                            dependencies_ui.dependencies_tree_view().update_treeview(true, TreeViewOperation::Build(parent_build_data), DataSource::ParentFiles);
+                            dependencies_ui.dependencies_tree_view().update_treeview(true, TreeViewOperation::Build(game_build_data), DataSource::GameFiles);
+                            dependencies_ui.dependencies_tree_view().update_treeview(true, TreeViewOperation::Build(asskit_build_data), DataSource::AssKitFiles);
+
+                            if show_success_dialog {
+                                show_dialog(&app_ui.main_window, tr("generate_dependency_cache_success"), true)
+                            } else {
+                                show_message_info(&app_ui.message_widget, tr("generate_dependency_cache_success"));
+                            }
+                        },
+                        Response::Error(error) => show_dialog(&app_ui.main_window, error, false),
+                        _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+                    }
+
+                    // If we reach this, the generation is done.
+                    timer_ptr.stop();
+                    timer_ptr.delete_later();
+                    progress_bar.delete_later();
+                    DEPENDENCIES_CACHE_GENERATION_RUNNING.store(false, Ordering::SeqCst);
+                    break;
+                }
+            }
+        ));
+
+        timer.timeout().connect(&slot);
+        timer.start_1a(100);
+    }
+
+    /// This function regenerates the dependencies cache if the `auto_refresh_dependency_cache` setting is enabled
+    /// and the cache is either missing or outdated.
+    ///
+    /// It does nothing if the game path is not correctly configured.
+    pub unsafe fn auto_refresh_dependencies_cache(app_ui: &Rc<Self>, dependencies_ui: &Rc<DependenciesUI>) {
+        if !setting_bool("auto_refresh_dependency_cache") {
+            return;
+        }
+
+        let receiver = CENTRAL_COMMAND.send_background(Command::IsDependencyCacheOutdated);
+        let response = CentralCommand::recv(&receiver);
+        let is_outdated = if let Response::Bool(is_outdated) = response { is_outdated } else { panic!("{THREADS_COMMUNICATION_ERROR}{response:?}") };
+
+        if is_outdated {
+            info!("Dependencies cache missing or outdated. Regenerating it automatically.");
+            Self::generate_dependencies_cache(app_ui, dependencies_ui, false);
+        }
+    }
+
     /// This function is the one that takes care of the creation of different Files, including making sure we can create them,
     /// and triggering the relevant dialogs for each file type.
     pub unsafe fn new_file(app_ui: &Rc<Self>, pack_file_contents_ui: &Rc<PackFileContentsUI>, file_type: FileType) {
@@ -3820,6 +3941,7 @@ impl AppUI {
     pub unsafe fn change_game_selected(
         app_ui: &Rc<Self>,
         pack_file_contents_ui: &Rc<PackFileContentsUI>,
+        diagnostics_ui: &Rc<DiagnosticsUI>,
         dependencies_ui: &Rc<DependenciesUI>,
         rebuild_dependencies: bool,
         force_full_dependency_reload: bool
@@ -3865,6 +3987,10 @@ impl AppUI {
             // Change the GameSelected Icon.
             GameSelectedIcons::set_game_selected_icon(app_ui);
 
+            // Diagnostic types ignored are saved per game, so reload them and refilter the results with them.
+            diagnostics_ui.load_diagnostics_ignored();
+            DiagnosticsUI::filter(app_ui, diagnostics_ui);
+
             // Set this at the end, because the backend need to check if it's our first initialization or not first.
             FIRST_GAME_CHANGE_DONE.store(true, Ordering::SeqCst);
             game_changed = true;
@@ -3933,6 +4059,11 @@ impl AppUI {
             AppUI::enable_packfile_actions(app_ui, &pack_path, true);
         }
 
+        // If the dependencies cache is missing or outdated and we have auto-refresh enabled, regenerate it.
+        if rebuild_dependencies {
+            Self::auto_refresh_dependencies_cache(app_ui, dependencies_ui);
+        }
+
         // If we have the setting enabled, ask the backend to generate the missing definition list.
         if setting_bool("check_for_missing_table_definitions") {
             let _ = CENTRAL_COMMAND.send_background(Command::GetMissingDefinitions);
@@ -3968,6 +4099,7 @@ impl AppUI {
         let _ = AppUI::purge_the_local_ones(app_ui, pack_file_contents_ui, false);
         GlobalSearchUI::clear(global_search_ui);
         diagnostics_ui.diagnostics_table_model().clear();
+        UI_STATE.clear_diagnostics_snoozed();
 
         // New PackFiles are always of Mod type.
         app_ui.change_packfile_type_mod.set_checked(true);
@@ -16,7 +16,7 @@ use qt_widgets::QApplication;
 use qt_widgets::QDialog;
 use qt_widgets::{QFileDialog, q_file_dialog::FileMode};
 use qt_widgets::QGridLayout;
-use qt_widgets::{QMessageBox, q_message_box};
+use qt_widgets::QMessageBox;
 use qt_widgets::QPushButton;
 use qt_widgets::QTextEdit;
 use qt_widgets::SlotOfQPoint;
@@ -36,7 +36,6 @@ use qt_core::QSignalBlocker;
 use qt_core::QString;
 use qt_core::QUrl;
 use qt_core::QVariant;
-use qt_core::WidgetAttribute;
 
 use std::collections::BTreeMap;
 use std::fs::{copy, remove_file, remove_dir_all};
@@ -291,9 +290,7 @@ impl AppUISlots {
                             return show_dialog(&app_ui.main_window, error, false);
                         }
 
-                        if setting_bool("diagnostics_trigger_on_open") {
-                            DiagnosticsUI::check(&app_ui, &diagnostics_ui);
-                        }
+                        DiagnosticsUI::check_on_open(&app_ui, &diagnostics_ui, &paths);
                     }
                 }
             }
@@ -599,7 +596,7 @@ impl AppUISlots {
                             // If we have changed the path of any of the games, and that game is the current `GameSelected`,
                             // re-select the current `GameSelected` to force it to reload the game's files.
                             if game_path_old != game_path_new || ak_path_old != ak_path_new || secondary_path_old != secondary_path_new {
-                                AppUI::change_game_selected(&app_ui, &pack_file_contents_ui, &dependencies_ui, true, true);
+                                AppUI::change_game_selected(&app_ui, &pack_file_contents_ui, &diagnostics_ui, &dependencies_ui, true, true);
                             }
 
                             // If we detect a change in theme, reload it.
@@ -971,9 +968,10 @@ impl AppUISlots {
         let change_game_selected = SlotOfBool::new(&app_ui.main_window, clone!(
             app_ui,
             pack_file_contents_ui,
+            diagnostics_ui,
             dependencies_ui => move |_| {
                 info!("Triggering `Change Game Selected` By Slot");
-                AppUI::change_game_selected(&app_ui, &pack_file_contents_ui, &dependencies_ui, true, false);
+                AppUI::change_game_selected(&app_ui, &pack_file_contents_ui, &diagnostics_ui, &dependencies_ui, true, false);
             }
         ));
 
@@ -987,57 +985,7 @@ impl AppUISlots {
             dependencies_ui => move |_| {
                 if AppUI::are_you_sure_edition(&app_ui, "generate_dependencies_cache_are_you_sure") {
                     info!("Triggering `Generate Dependencies Cache` By Slot");
-
-                    if (GAME_SELECTED.read().unwrap().raw_db_version() > &0 && !setting_path(&format!("{}_assembly_kit", GAME_SELECTED.read().unwrap().key())).is_dir()) ||
-                        (*GAME_SELECTED.read().unwrap().raw_db_version() == 0 && !old_ak_files_path().unwrap_or(PathBuf::new()).join(GAME_SELECTED.read().unwrap().key()).is_dir()) {
-                        show_dialog(&app_ui.main_window, tr("generate_dependencies_cache_warn"), false);
-                    }
-
-                    // If there is no problem, ere we go.
-                    app_ui.toggle_main_window(false);
-
-                    let wait_dialog = QMessageBox::from_icon2_q_string_q_flags_standard_button_q_widget(
-                        q_message_box::Icon::Information,
-                        &qtr("rpfm_title"),
-                        &qtr("generate_dependencies_cache_in_progress_message"),
-                        QFlags::from(0),
-                        &app_ui.main_window,
-                    );
-
-                    wait_dialog.set_attribute_1a(WidgetAttribute::WADeleteOnClose);
-                    wait_dialog.set_modal(true);
-                    wait_dialog.set_standard_buttons(QFlags::from(0));
-                    wait_dialog.show();
-
-                    let receiver = CENTRAL_COMMAND.send_background(Command::GenerateDependenciesCache);
-                    let response = CENTRAL_COMMAND.recv_try(&receiver);
-
-                    match response {
-                        Response::DependenciesInfo(response) => {
-                            let mut parent_build_data = BuildData::new();
-                            parent_build_data.data = Some((ContainerInfo::default(), response.parent_packed_files().to_vec()));
-
-                            let mut game_build_data = BuildData::new();
-                            game_build_data.data = Some((ContainerInfo::default(), response.vanilla_packed_files().to_vec()));
-
-                            let mut asskit_build_data = BuildData::new();
-                            asskit_build_data.data = Some((ContainerInfo::default(), response.asskit_tables().to_vec()));
-
-                            dependencies_ui.dependencies_tree_view().update_treeview(true, TreeViewOperation::Build(parent_build_data), DataSource::ParentFiles);
-                            dependencies_ui.dependencies_tree_view().update_treeview(true, TreeViewOperation::Build(game_build_data), DataSource::GameFiles);
-                            dependencies_ui.dependencies_tree_view().update_treeview(true, TreeViewOperation::Build(asskit_build_data), DataSource::AssKitFiles);
-
-                            wait_dialog.done(1);
-                            show_dialog(&app_ui.main_window, tr("generate_dependency_cache_success"), true)
-                        },
-                        Response::Error(error) => {
-                            wait_dialog.done(1);
-                            show_dialog(&app_ui.main_window, error, false);
-                        },
-                        _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
-                    }
-
-                    app_ui.toggle_main_window(true);
+                    AppUI::generate_dependencies_cache(&app_ui, &dependencies_ui, true);
                 }
             }
         ));
@@ -1899,9 +1847,7 @@ impl AppUISlots {
                     return show_dialog(&app_ui.main_window, error, false);
                 }
 
-                if setting_bool("diagnostics_trigger_on_open") {
-                    DiagnosticsUI::check(&app_ui, &diagnostics_ui);
-                }
+                DiagnosticsUI::check_on_open(&app_ui, &diagnostics_ui, &paths);
             }
         }));
 
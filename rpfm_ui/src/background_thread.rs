@@ -32,9 +32,10 @@ use std::sync::{Arc, atomic::Ordering, RwLock};
 use std::thread;
 use std::time::{Duration, SystemTime};
 
-use rpfm_extensions::dependencies::Dependencies;
-use rpfm_extensions::diagnostics::Diagnostics;
+use rpfm_extensions::dependencies::{Dependencies, ReferenceSource};
+use rpfm_extensions::diagnostics::{Diagnostics, text::TextDiagnostic};
 use rpfm_extensions::optimizer::OptimizableContainer;
+use rpfm_extensions::search::SearchSource;
 #[cfg(feature = "enable_tools")] use rpfm_extensions::translator::PackTranslation;
 
 use rpfm_lib::binary::WriteBytes;
@@ -267,6 +268,7 @@ pub fn background_loop() {
             // In case we want to launch a global search on a `PackFile`...
             Command::GlobalSearch(mut global_search) => {
                 let game_selected = GAME_SELECTED.read().unwrap();
+                global_search.set_max_decode_bytes(setting_int("max_decode_bytes") as u64);
                 match *SCHEMA.read().unwrap() {
                     Some(ref schema) => {
                         global_search.search(&game_selected, schema, &mut pack_file_decoded, &mut dependencies.write().unwrap(), &[]);
@@ -338,27 +340,44 @@ pub fn background_loop() {
 
             // In case we want to generate the dependencies cache for our Game Selected...
             Command::GenerateDependenciesCache => {
-                let game_selected = GAME_SELECTED.read().unwrap();
+                let game_selected = *GAME_SELECTED.read().unwrap();
                 let game_path = setting_path(game_selected.key());
                 let ignore_game_files_in_ak = setting_bool("ignore_game_files_in_ak");
                 let asskit_path = assembly_kit_path().ok();
 
                 if game_path.is_dir() {
-                    match Dependencies::generate_dependencies_cache(&game_selected, &game_path, &asskit_path, ignore_game_files_in_ak) {
-                        Ok(mut cache) => {
-                            let dependencies_path = dependencies_cache_path().unwrap().join(game_selected.dependencies_cache_file_name());
-                            match cache.save(&dependencies_path) {
-                                Ok(_) => {
-                                    let secondary_path = setting_path(SECONDARY_PATH);
-                                    let _ = dependencies.write().unwrap().rebuild(&SCHEMA.read().unwrap(), pack_file_decoded.dependencies(), Some(&dependencies_path), &game_selected, &game_path, &secondary_path);
-                                    let dependencies_info = DependenciesInfo::from(&*dependencies.read().unwrap());
-                                    CentralCommand::send_back(&sender, Response::DependenciesInfo(dependencies_info));
-                                },
-                                Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
+                    let pack_dependencies = pack_file_decoded.dependencies().to_vec();
+                    let dependencies = dependencies.clone();
+
+                    // Generate the cache in its own thread, so this thread can keep answering the UI while it's being generated.
+                    // Progress is reported as (steps done, total steps) before the final response.
+                    thread::spawn(move || {
+                        CentralCommand::send_back(&sender, Response::I32I32(0, 3));
+                        match Dependencies::generate_dependencies_cache(game_selected, &game_path, &asskit_path, ignore_game_files_in_ak) {
+                            Ok(mut cache) => {
+                                CentralCommand::send_back(&sender, Response::I32I32(1, 3));
+
+                                let dependencies_path = dependencies_cache_path().unwrap().join(game_selected.dependencies_cache_file_name());
+                                match cache.save(&dependencies_path) {
+                                    Ok(_) => {
+                                        CentralCommand::send_back(&sender, Response::I32I32(2, 3));
+
+                                        // Only reload the dependencies if the game selected didn't change while we were generating the cache.
+                                        if GAME_SELECTED.read().unwrap().key() == game_selected.key() {
+                                            let secondary_path = setting_path(SECONDARY_PATH);
+                                            let _ = dependencies.write().unwrap().rebuild(&SCHEMA.read().unwrap(), &pack_dependencies, Some(&dependencies_path), game_selected, &game_path, &secondary_path);
+                                        }
+
+                                        CentralCommand::send_back(&sender, Response::I32I32(3, 3));
+                                        let dependencies_info = DependenciesInfo::from(&*dependencies.read().unwrap());
+                                        CentralCommand::send_back(&sender, Response::DependenciesInfo(dependencies_info));
+                                    },
+                                    Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
+                                }
                             }
+                            Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
                         }
-                        Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
-                    }
+                    });
                 } else {
                     CentralCommand::send_back(&sender, Response::Error(anyhow!("Game Path not configured. Go to <i>'PackFile/Settings'</i> and configure it.")));
                 }
@@ -485,6 +504,20 @@ pub fn background_loop() {
                 CentralCommand::send_back(&sender, Response::Bool(are_dependencies_loaded))
             },
 
+            // In case we want to check if the dependencies cache needs to be regenerated...
+            Command::IsDependencyCacheOutdated => {
+                let game_selected = GAME_SELECTED.read().unwrap();
+                let game_path = setting_path(game_selected.key());
+                let is_game_path_valid = game_selected.validate_install_path(&game_path).is_ok();
+
+                let is_outdated = if is_game_path_valid {
+                    let dependencies = dependencies.read().unwrap();
+                    !dependencies.is_vanilla_data_loaded(false) || dependencies.needs_updating(&game_selected, &game_path).unwrap_or(true)
+                } else { false };
+
+                CentralCommand::send_back(&sender, Response::Bool(is_outdated))
+            },
+
             // In case we want to create a PackedFile from scratch...
             Command::NewPackedFile(path, new_packed_file) => {
                 let decoded = match new_packed_file {
@@ -662,7 +695,7 @@ pub fn background_loop() {
                     })
                     .collect::<Vec<RFile>>();
 
-                match pack_file_decoded.files_mut().get_mut(&anim_pack_path) {
+                match pack_file_decoded.file_mut(&anim_pack_path, false) {
                     Some(file) => {
 
                         // Try to decode it using lazy_load if enabled.
@@ -741,7 +774,7 @@ pub fn background_loop() {
 
             // In case we want to delete files from an Animpack...
             Command::DeleteFromAnimpack((anim_pack_path, paths)) => {
-                match pack_file_decoded.files_mut().get_mut(&anim_pack_path) {
+                match pack_file_decoded.file_mut(&anim_pack_path, false) {
                     Some(file) => {
 
                         // Try to decode it using lazy_load if enabled.
@@ -828,16 +861,28 @@ pub fn background_loop() {
                         pack_file_decoded.notes_mut().set_pack_notes(data.contents().to_owned());
                     }
                 }
+                else if let Err(error) = pack_file_decoded.check_path_not_readonly(&path) {
+                    CentralCommand::send_back(&sender, Response::Error(From::from(error)));
+                    continue;
+                }
                 else if let Some(file) = pack_file_decoded.files_mut().get_mut(&path) {
-                    if let Err(error) = file.set_decoded(file_decoded) {
-                        CentralCommand::send_back(&sender, Response::Error(From::from(error)));
+                    match file.set_decoded(file_decoded) {
+                        Ok(_) => pack_file_decoded.mark_modified(&[ContainerPath::File(path)]),
+                        Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
                     }
                 }
                 CentralCommand::send_back(&sender, Response::Success);
             }
 
             // In case we want to delete PackedFiles from a PackFile...
-            Command::DeletePackedFiles(paths) => CentralCommand::send_back(&sender, Response::VecContainerPath(paths.iter().flat_map(|path| pack_file_decoded.remove(path)).collect())),
+            Command::DeletePackedFiles(paths) => {
+
+                // Check all the paths before deleting any of them, so we don't leave the deletion half-done.
+                match paths.iter().try_for_each(|path| pack_file_decoded.check_container_path_not_readonly(path)) {
+                    Ok(_) => CentralCommand::send_back(&sender, Response::VecContainerPath(paths.iter().flat_map(|path| pack_file_decoded.remove(path)).collect())),
+                    Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
+                }
+            }
 
             // In case we want to extract PackedFiles from a PackFile...
             Command::ExtractPackedFiles(container_paths, path, extract_tables_to_tsv) => {
@@ -908,6 +953,18 @@ pub fn background_loop() {
 
             // In case we want to rename one or more files/folders...
             Command::RenamePackedFiles(renaming_data) => {
+
+                // Check all the paths before renaming any of them, so we don't leave the renaming half-done.
+                let readonly_check = renaming_data.iter().try_for_each(|(source_path, destination_path)| {
+                    pack_file_decoded.check_container_path_not_readonly(source_path)?;
+                    pack_file_decoded.check_path_not_readonly(destination_path.path_raw())
+                });
+
+                if let Err(error) = readonly_check {
+                    CentralCommand::send_back(&sender, Response::Error(From::from(error)));
+                    continue;
+                }
+
                 match pack_file_decoded.move_paths(&renaming_data) {
                     Ok(data) => CentralCommand::send_back(&sender, Response::VecContainerPathContainerPath(data)),
                     Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
@@ -931,7 +988,7 @@ pub fn background_loop() {
             },
             Command::GetCustomTableList => match &*SCHEMA.read().unwrap() {
                 Some(schema) => {
-                    let tables = schema.definitions().par_iter().filter(|(key, defintions)| !defintions.is_empty() && key.starts_with("start_pos_")).map(|(key, _)| key.to_owned()).collect::<Vec<_>>();
+                    let tables = schema.all_table_names().into_iter().filter(|table_name| table_name.starts_with("start_pos_")).collect::<Vec<_>>();
                     CentralCommand::send_back(&sender, Response::VecString(tables));
                 }
                 None => CentralCommand::send_back(&sender, Response::Error(anyhow!("There is no Schema for the Game Selected.")))
@@ -991,7 +1048,23 @@ pub fn background_loop() {
                 let files_to_merge = pack_file_decoded.files_by_paths(&paths, false);
                 match RFile::merge(&files_to_merge, &merged_path) {
                     Ok(file) => {
-                        let _ = pack_file_decoded.insert(file);
+
+                        // Check the source files can be deleted before adding the merged file, so we don't leave the merge half-done.
+                        if delete_source_files {
+                            let readonly_check = paths.iter()
+                                .filter(|path| merged_path != path.path_raw())
+                                .try_for_each(|path| pack_file_decoded.check_container_path_not_readonly(path));
+
+                            if let Err(error) = readonly_check {
+                                CentralCommand::send_back(&sender, Response::Error(From::from(error)));
+                                continue;
+                            }
+                        }
+
+                        if let Err(error) = pack_file_decoded.insert(file) {
+                            CentralCommand::send_back(&sender, Response::Error(From::from(error)));
+                            continue;
+                        }
 
                         // Make sure to only delete the files if they're not the destination file.
                         if delete_source_files {
@@ -1069,7 +1142,7 @@ pub fn background_loop() {
 
             // In case we want to change the format of a ca_vp8 video...
             Command::SetVideoFormat(path, format) => {
-                match pack_file_decoded.files_mut().get_mut(&path) {
+                match pack_file_decoded.file_mut(&path, false) {
                     Some(ref mut rfile) => {
                         match rfile.decoded_mut() {
                             Ok(data) => {
@@ -1125,8 +1198,9 @@ pub fn background_loop() {
                                 continue;
                             },
                         };
+                        let extra_data = Some(initialize_encodeable_extra_data(&GAME_SELECTED.read().unwrap()));
                         match file {
-                            Some(file) => match file.tsv_export_to_path(&external_path, schema, setting_bool("tables_use_old_column_order_for_tsv")) {
+                            Some(file) => match file.tsv_export_to_path(&external_path, schema, setting_bool("tables_use_old_column_order_for_tsv"), &extra_data) {
                                 Ok(_) => CentralCommand::send_back(&sender, Response::Success),
                                 Err(error) =>  CentralCommand::send_back(&sender, Response::Error(From::from(error))),
                             }
@@ -1140,6 +1214,11 @@ pub fn background_loop() {
             // In case we want to import a TSV as a PackedFile...
             // TODO: This is... unreliable at best, can break stuff at worst. Replace the set_decoded with proper type checking.
             Command::ImportTSV(internal_path, external_path) => {
+                if let Err(error) = pack_file_decoded.check_path_not_readonly(&internal_path) {
+                    CentralCommand::send_back(&sender, Response::Error(From::from(error)));
+                    continue;
+                }
+
                 match pack_file_decoded.file_mut(&internal_path, false) {
                     Some(file) => {
                         let schema = SCHEMA.read().unwrap();
@@ -1324,16 +1403,22 @@ pub fn background_loop() {
             }
 
             // In case we want to perform a diagnostics check...
-            Command::DiagnosticsCheck(diagnostics_ignored, check_ak_only_refs) => {
+            Command::DiagnosticsCheck(diagnostics_ignored, check_ak_only_refs, source) => {
 
                 let game_selected = GAME_SELECTED.read().unwrap();
                 let game_path = setting_path(game_selected.key());
 
                 let mut diagnostics = Diagnostics::default();
                 *diagnostics.diagnostics_ignored_mut() = diagnostics_ignored;
+                *diagnostics.max_decode_bytes_mut() = setting_int("max_decode_bytes") as u64;
+                diagnostics.loc_key_patterns_mut().insert(game_selected.key().to_owned(), setting_string(&format!("{}_loc_key_pattern", game_selected.key())));
+                diagnostics.loc_formatting_rules_mut().insert(game_selected.key().to_owned(), setting_string(&format!("{}_loc_formatting_rules", game_selected.key())));
+                *diagnostics.referenced_path_rules_mut() = TextDiagnostic::parse_path_rules(&setting_string("diagnostics_referenced_path_rules"));
 
                 if let Some(ref schema) = *SCHEMA.read().unwrap() {
-                    if pack_file_decoded.pfh_file_type() == PFHFileType::Mod ||
+                    if source != SearchSource::Pack {
+                        diagnostics.check_source(&source, &mut pack_file_decoded, &mut dependencies.write().unwrap(), &schema, &game_selected, &game_path, check_ak_only_refs);
+                    } else if pack_file_decoded.pfh_file_type() == PFHFileType::Mod ||
                         pack_file_decoded.pfh_file_type() == PFHFileType::Movie {
                         diagnostics.check(&mut pack_file_decoded, &mut dependencies.write().unwrap(), &schema, &game_selected, &game_path, &[], check_ak_only_refs);
                     }
@@ -1347,6 +1432,10 @@ pub fn background_loop() {
             Command::DiagnosticsUpdate(mut diagnostics, path_types, check_ak_only_refs) => {
                 let game_selected = GAME_SELECTED.read().unwrap();
                 let game_path = setting_path(game_selected.key());
+                *diagnostics.max_decode_bytes_mut() = setting_int("max_decode_bytes") as u64;
+                diagnostics.loc_key_patterns_mut().insert(game_selected.key().to_owned(), setting_string(&format!("{}_loc_key_pattern", game_selected.key())));
+                diagnostics.loc_formatting_rules_mut().insert(game_selected.key().to_owned(), setting_string(&format!("{}_loc_formatting_rules", game_selected.key())));
+                *diagnostics.referenced_path_rules_mut() = TextDiagnostic::parse_path_rules(&setting_string("diagnostics_referenced_path_rules"));
 
                 if let Some(ref schema) = *SCHEMA.read().unwrap() {
                     if pack_file_decoded.pfh_file_type() == PFHFileType::Mod ||
@@ -1360,9 +1449,57 @@ pub fn background_loop() {
                 CentralCommand::send_back(&sender, Response::Diagnostics(diagnostics));
             }
 
+            Command::ExportDiagnosticReproducer(diagnostic, report_index, path) => {
+                match *SCHEMA.read().unwrap() {
+                    Some(ref schema) => match Diagnostics::reproducer_bundle(&diagnostic, report_index, &pack_file_decoded, schema) {
+                        Ok(data) => match File::create(&path) {
+                            Ok(file) => {
+                                let mut file = BufWriter::new(file);
+                                match file.write_all(&data) {
+                                    Ok(_) => CentralCommand::send_back(&sender, Response::Success),
+                                    Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
+                                }
+                            }
+                            Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
+                        }
+                        Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
+                    }
+                    None => CentralCommand::send_back(&sender, Response::Error(anyhow!("There is no Schema for the Game Selected."))),
+                }
+            }
+
+            Command::RemoveDuplicatedCombinedKeys(path, rows, keep_last) => {
+                if let Err(error) = pack_file_decoded.check_path_not_readonly(&path) {
+                    CentralCommand::send_back(&sender, Response::Error(From::from(error)));
+                    continue;
+                }
+
+                let schema = SCHEMA.read().unwrap();
+                let mut decode_extra_data = DecodeableExtraData::default();
+                decode_extra_data.set_schema(schema.as_ref());
+                let extra_data = Some(decode_extra_data);
+
+                match pack_file_decoded.file_mut(&path, false) {
+                    Some(file) => {
+                        let _ = file.decode(&extra_data, true, false);
+                        match file.decoded_mut() {
+                            Ok(RFileDecoded::DB(table)) => {
+                                table.remove_duplicated_combined_keys(keep_last, Some(&rows));
+                                pack_file_decoded.mark_modified(&[ContainerPath::File(path)]);
+                                CentralCommand::send_back(&sender, Response::Success);
+                            }
+                            Ok(_) => CentralCommand::send_back(&sender, Response::Error(anyhow!("The file {} is not a DB table.", path))),
+                            Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
+                        }
+                    }
+                    None => CentralCommand::send_back(&sender, Response::Error(anyhow!("File with the following path not found in the Pack: {}", path))),
+                }
+            }
+
             // In case we want to get the open PackFile's Settings...
             Command::GetPackSettings => CentralCommand::send_back(&sender, Response::PackSettings(pack_file_decoded.settings().clone())),
             Command::SetPackSettings(settings) => { pack_file_decoded.set_settings(settings); }
+            Command::IsPathReadonly(path) => CentralCommand::send_back(&sender, Response::Bool(pack_file_decoded.is_path_readonly(&path))),
 
             Command::GetMissingDefinitions => {
 
@@ -1428,101 +1565,19 @@ pub fn background_loop() {
                 CentralCommand::send_back(&sender, Response::VecContainerPathVecRFileInfo(edited_paths, packed_files_info));
             }
 
-            Command::GoToDefinition(ref_table, mut ref_column, ref_data) => {
-                let table_name = format!("{ref_table}_tables");
-                let table_folder = format!("db/{table_name}");
-                let packed_files = pack_file_decoded.files_by_path(&ContainerPath::Folder(table_folder.to_owned()), true);
-                let mut found = false;
-                for packed_file in &packed_files {
-                    if let Ok(RFileDecoded::DB(data)) = packed_file.decoded() {
-
-                        // If the column is a loc column, we need to search in the first key column instead.
-                        if data.definition().localised_fields().iter().any(|x| x.name() == ref_column) {
-                            if let Some(first_key_index) = data.definition().localised_key_order().get(0) {
-                                if let Some(first_key_field) = data.definition().fields_processed().get(*first_key_index as usize) {
-                                    ref_column = first_key_field.name().to_owned();
-                                }
-                            }
-                        }
-
-                        if let Some((column_index, row_index)) = data.table().rows_containing_data(&ref_column, &ref_data[0]) {
-                            CentralCommand::send_back(&sender, Response::DataSourceStringUsizeUsize(DataSource::PackFile, packed_file.path_in_container_raw().to_owned(), column_index, row_index[0]));
-                            found = true;
-                            break;
-                        }
-                    }
-                }
-
-                if !found {
-                    if let Ok(packed_files) = dependencies.read().unwrap().db_data(&table_name, false, true) {
-                        for packed_file in &packed_files {
-                            if let Ok(RFileDecoded::DB(data)) = packed_file.decoded() {
-
-                                // If the column is a loc column, we need to search in the first key column instead.
-                                if data.definition().localised_fields().iter().any(|x| x.name() == ref_column) {
-                                    if let Some(first_key_index) = data.definition().localised_key_order().get(0) {
-                                        if let Some(first_key_field) = data.definition().fields_processed().get(*first_key_index as usize) {
-                                            ref_column = first_key_field.name().to_owned();
-                                        }
-                                    }
-                                }
-
-                                if let Some((column_index, row_index)) = data.table().rows_containing_data(&ref_column, &ref_data[0]) {
-                                    CentralCommand::send_back(&sender, Response::DataSourceStringUsizeUsize(DataSource::ParentFiles, packed_file.path_in_container_raw().to_owned(), column_index, row_index[0]));
-                                    found = true;
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-
-                if !found {
-                    if let Ok(packed_files) = dependencies.read().unwrap().db_data(&table_name, true, false) {
-                        for packed_file in &packed_files {
-                            if let Ok(RFileDecoded::DB(data)) = packed_file.decoded() {
-
-                                // If the column is a loc column, we need to search in the first key column instead.
-                                if data.definition().localised_fields().iter().any(|x| x.name() == ref_column) {
-                                    if let Some(first_key_index) = data.definition().localised_key_order().get(0) {
-                                        if let Some(first_key_field) = data.definition().fields_processed().get(*first_key_index as usize) {
-                                            ref_column = first_key_field.name().to_owned();
-                                        }
-                                    }
-                                }
-
-                                if let Some((column_index, row_index)) = data.table().rows_containing_data(&ref_column, &ref_data[0]) {
-                                    CentralCommand::send_back(&sender, Response::DataSourceStringUsizeUsize(DataSource::GameFiles, packed_file.path_in_container_raw().to_owned(), column_index, row_index[0]));
-                                    found = true;
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-
-                if !found {
-                    if let Some(data) = dependencies.read().unwrap().asskit_only_db_tables().get(&table_name) {
-
-                        // If the column is a loc column, we need to search in the first key column instead.
-                        if data.definition().localised_fields().iter().any(|x| x.name() == ref_column) {
-                            if let Some(first_key_index) = data.definition().localised_key_order().get(0) {
-                                if let Some(first_key_field) = data.definition().fields_processed().get(*first_key_index as usize) {
-                                    ref_column = first_key_field.name().to_owned();
-                                }
-                            }
-                        }
+            Command::GoToDefinition(ref_table, ref_column, ref_data) => {
+                match dependencies.read().unwrap().resolve_reference(Some(&pack_file_decoded), &ref_table, &ref_column, &ref_data[0]) {
+                    Some(location) => {
+                        let data_source = match location.source() {
+                            ReferenceSource::Pack => DataSource::PackFile,
+                            ReferenceSource::ParentFiles => DataSource::ParentFiles,
+                            ReferenceSource::GameFiles => DataSource::GameFiles,
+                            ReferenceSource::AssKitFiles => DataSource::AssKitFiles,
+                        };
 
-                        if let Some((column_index, row_index)) = data.table().rows_containing_data(&ref_column, &ref_data[0]) {
-                            let path = format!("{}/ak_data", &table_folder);
-                            CentralCommand::send_back(&sender, Response::DataSourceStringUsizeUsize(DataSource::AssKitFiles, path, column_index, row_index[0]));
-                            found = true;
-                        }
+                        CentralCommand::send_back(&sender, Response::DataSourceStringUsizeUsize(data_source, location.path().to_owned(), *location.column(), *location.row()));
                     }
-                }
-
-                if !found {
-                    CentralCommand::send_back(&sender, Response::Error(anyhow!(tr("source_data_for_field_not_found"))));
+                    None => CentralCommand::send_back(&sender, Response::Error(anyhow!(tr("source_data_for_field_not_found")))),
                 }
             },
 
@@ -1983,9 +2038,10 @@ pub fn background_loop() {
                 }
             }
 
-            Command::GenerateMissingLocData => {
-                match pack_file_decoded.generate_missing_loc_data() {
-                    Ok(path) => CentralCommand::send_back(&sender, Response::OptionContainerPath(path)),
+            Command::GenerateMissingLocData(path, text) => {
+                let is_new_file = pack_file_decoded.file(&path, false).is_none();
+                match pack_file_decoded.generate_missing_loc_data(&path, &text) {
+                    Ok(count) => CentralCommand::send_back(&sender, Response::UsizeBool(count, is_new_file)),
                     Err(error) => CentralCommand::send_back(&sender, Response::Error(From::from(error))),
                 }
             }
@@ -23,8 +23,8 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use rpfm_extensions::dependencies::TableReferences;
-use rpfm_extensions::diagnostics::Diagnostics;
-use rpfm_extensions::search::{GlobalSearch, MatchHolder};
+use rpfm_extensions::diagnostics::{Diagnostics, DiagnosticType};
+use rpfm_extensions::search::{GlobalSearch, MatchHolder, SearchSource};
 #[cfg(feature = "enable_tools")] use rpfm_extensions::translator::PackTranslation;
 
 use rpfm_lib::files::{anim_fragment_battle::AnimFragmentBattle, anims_table::AnimsTable, atlas::Atlas, audio::Audio, bmd::Bmd, ContainerPath, video::SupportedFormats, db::DB, esf::ESF, group_formations::GroupFormations, image::Image, loc::Loc, matched_combat::MatchedCombat, pack::PackSettings, RFile, RFileDecoded, portrait_settings::PortraitSettings, text::Text, uic::UIC, unit_variant::UnitVariant};
@@ -117,6 +117,9 @@ pub enum Command {
 
     /// This command is used when we want to generate the dependencies cache for a game. It contains the path of the
     /// source raw db files, the `Raw DB Version` of the currently selected game, and if we should has the files or not.
+    ///
+    /// The cache is generated in a separate thread. It answers with `I32I32` progress responses (steps done, total steps),
+    /// followed by either `DependenciesInfo` or `Error`.
     GenerateDependenciesCache,
 
     /// This command is used when we want to update the currently loaded Schema with data from the game selected's Assembly Kit.
@@ -155,6 +158,11 @@ pub enum Command {
     /// Pass true if you want to ensure the dependencies were built with the AssKit.
     IsThereADependencyDatabase(bool),
 
+    /// This command is used when we want to know if the dependencies cache is missing or outdated, and can be regenerated.
+    ///
+    /// It returns false if the game path is not correctly configured.
+    IsDependencyCacheOutdated,
+
     /// This command is used when we want to create a new `PackedFile` inside the currently open `PackFile`.
     ///
     /// It requires the path of the new PackedFile, and the `NewPackedFile` with the new PackedFile's info.
@@ -268,18 +276,27 @@ pub enum Command {
     /// This command is used to trigger an autosave to a backup from time to time.
     TriggerBackupAutosave,
 
-    /// This command is used to trigger a full diagnostics check over the open PackFile.
-    DiagnosticsCheck(Vec<String>, bool),
+    /// This command is used to trigger a full diagnostics check over the files of the provided source.
+    DiagnosticsCheck(Vec<String>, bool, SearchSource),
 
     // This command is used to trigger a partial diagnostics check over the open PackFile.
     DiagnosticsUpdate(Diagnostics, Vec<ContainerPath>, bool),
 
+    /// This command is used to save a Pack with the context of a single diagnostic report to the provided path, to reproduce it somewhere else.
+    ExportDiagnosticReproducer(DiagnosticType, usize, PathBuf),
+
+    /// This command is used to remove the rows with duplicated combined keys between the provided rows of the table in the provided path, keeping the first or the last row of each key.
+    RemoveDuplicatedCombinedKeys(String, Vec<usize>, bool),
+
     /// This command is used to get the settings of the currently open PackFile.
     GetPackSettings,
 
     // This command is used to set the settings of the currently open PackFile.
     SetPackSettings(PackSettings),
 
+    /// This command is used to check if a path is marked as read-only in the settings of the currently open PackFile.
+    IsPathReadonly(String),
+
     /// This command is used to trigger the debug missing table definition's code.
     GetMissingDefinitions,
 
@@ -327,8 +344,8 @@ pub enum Command {
     /// This command is used to import a schema patch in the local schema patches.
     ImportSchemaPatch(HashMap<String, DefinitionPatch>),
 
-    /// This command is used to generate all missing loc entries for the currently open PackFile.
-    GenerateMissingLocData,
+    /// This command is used to generate loc entries with the provided text for all the loc keys missing in the currently open PackFile, into the provided Loc file.
+    GenerateMissingLocData(String, String),
 
     /// This command is used to check for updates on the tw_autogen thing.
     CheckLuaAutogenUpdates,
@@ -374,12 +391,14 @@ pub enum Response {
     /// Response to return (i32).
     I32(i32),
 
+    /// Response to return (usize, bool).
+    UsizeBool(usize, bool),
+
     /// Response to return (PathBuf).
     PathBuf(PathBuf),
 
     /// Response to return (String)
     String(String),
-    OptionContainerPath(Option<ContainerPath>),
 
     // Response to return (ContainerInfo, Vec<RFileInfo>).
     ContainerInfoVecRFileInfo((ContainerInfo, Vec<RFileInfo>)),
@@ -25,11 +25,16 @@ pub unsafe fn set_connections(ui: &DiagnosticsUI, slots: &DiagnosticsUISlots) {
 
     ui.diagnostics_button_check_packfile.released().connect(slots.diagnostics_check_packfile());
     ui.diagnostics_button_check_current_packed_file.released().connect(slots.diagnostics_check_currently_open_packed_file());
+    ui.diagnostics_button_load_more.released().connect(slots.diagnostics_load_more());
+    ui.diagnostics_button_export.released().connect(slots.export_results());
 
     ui.diagnostics_button_info.toggled().connect(slots.toggle_filters());
     ui.diagnostics_button_warning.toggled().connect(slots.toggle_filters());
     ui.diagnostics_button_error.toggled().connect(slots.toggle_filters());
     ui.diagnostics_button_only_current_packed_file.toggled().connect(slots.toggle_filters());
+    ui.diagnostics_button_filter_case_sensitive.toggled().connect(slots.filter_change_case_sensitive());
+    ui.diagnostics_path_filter.text_changed().connect(slots.filter_change_text());
+    ui.diagnostics_message_filter.text_changed().connect(slots.filter_change_text());
 
     ui.diagnostics_button_show_more_filters.toggled().connect(slots.show_hide_extra_filters());
 
@@ -46,6 +51,11 @@ pub unsafe fn set_connections(ui: &DiagnosticsUI, slots: &DiagnosticsUISlots) {
     ui.ignore_diagnostic_for_file.triggered().connect(slots.ignore_diagnostic_for_file());
     ui.ignore_diagnostic_for_file_field.triggered().connect(slots.ignore_diagnostic_for_file_field());
     ui.ignore_diagnostic_for_pack.triggered().connect(slots.ignore_diagnostic_for_pack());
+    ui.snooze_diagnostic.triggered().connect(slots.snooze_diagnostic());
+    ui.export_reproducer.triggered().connect(slots.export_reproducer());
+    ui.copy_as_tsv.triggered().connect(slots.copy_as_tsv());
+    ui.fix_keep_first.triggered().connect(slots.fix_keep_first());
+    ui.fix_keep_last.triggered().connect(slots.fix_keep_last());
 
     ui.checkbox_all.toggled().connect(slots.toggle_filters_all());
     ui.checkbox_outdated_table.toggled().connect(slots.toggle_filters());
@@ -65,13 +75,27 @@ pub unsafe fn set_connections(ui: &DiagnosticsUI, slots: &DiagnosticsUISlots) {
     ui.checkbox_invalid_packfile_name.toggled().connect(slots.toggle_filters());
     ui.checkbox_table_name_ends_in_number.toggled().connect(slots.toggle_filters());
     ui.checkbox_table_name_has_space.toggled().connect(slots.toggle_filters());
+    ui.checkbox_table_name_folder_mismatch.toggled().connect(slots.toggle_filters());
     ui.checkbox_table_is_datacoring.toggled().connect(slots.toggle_filters());
     ui.checkbox_dependencies_cache_outdated.toggled().connect(slots.toggle_filters());
     ui.checkbox_dependencies_cache_could_not_be_loaded.toggled().connect(slots.toggle_filters());
     ui.checkbox_field_with_path_not_found.toggled().connect(slots.toggle_filters());
     ui.checkbox_incorrect_game_path.toggled().connect(slots.toggle_filters());
+    ui.checkbox_schema_reference_cycle.toggled().connect(slots.toggle_filters());
+    ui.checkbox_duplicate_field_name.toggled().connect(slots.toggle_filters());
+    ui.checkbox_invalid_schema_reference.toggled().connect(slots.toggle_filters());
     ui.checkbox_banned_table.toggled().connect(slots.toggle_filters());
     ui.checkbox_value_cannot_be_empty.toggled().connect(slots.toggle_filters());
+    ui.checkbox_unknown_column_in_data.toggled().connect(slots.toggle_filters());
+    ui.checkbox_field_fails_validation_pattern.toggled().connect(slots.toggle_filters());
+    ui.checkbox_invalid_validation_pattern.toggled().connect(slots.toggle_filters());
+    ui.checkbox_enum_value_not_encodable.toggled().connect(slots.toggle_filters());
+    ui.checkbox_duplicated_combined_keys_conflicting.toggled().connect(slots.toggle_filters());
+    ui.checkbox_malformed_loc_formatting.toggled().connect(slots.toggle_filters());
+    ui.checkbox_inconsistent_escaping.toggled().connect(slots.toggle_filters());
+    ui.checkbox_empty_column.toggled().connect(slots.toggle_filters());
+    ui.checkbox_self_reference.toggled().connect(slots.toggle_filters());
+    ui.checkbox_missing_loc_entry.toggled().connect(slots.toggle_filters());
     ui.checkbox_invalid_art_set_id.toggled().connect(slots.toggle_filters());
     ui.checkbox_invalid_variant_filename.toggled().connect(slots.toggle_filters());
     ui.checkbox_file_diffuse_not_found_for_variant.toggled().connect(slots.toggle_filters());
@@ -83,4 +107,6 @@ pub unsafe fn set_connections(ui: &DiagnosticsUI, slots: &DiagnosticsUISlots) {
     ui.checkbox_file_path_not_found.toggled().connect(slots.toggle_filters());
     ui.checkbox_meta_file_path_not_found.toggled().connect(slots.toggle_filters());
     ui.checkbox_snd_file_path_not_found.toggled().connect(slots.toggle_filters());
+    ui.checkbox_referenced_file_not_found.toggled().connect(slots.toggle_filters());
+    ui.checkbox_file_too_large_to_check.toggled().connect(slots.toggle_filters());
 }
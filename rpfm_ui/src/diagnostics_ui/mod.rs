@@ -15,9 +15,12 @@ Module with all the code related to the `DiagnosticsUI`.
 use qt_widgets::QAction;
 use qt_widgets::q_abstract_item_view::ScrollHint;
 use qt_widgets::{QCheckBox, QVBoxLayout};
+use qt_widgets::QComboBox;
 use qt_widgets::QDockWidget;
+use qt_widgets::{QFileDialog, q_file_dialog::AcceptMode};
 use qt_widgets::q_header_view::ResizeMode;
 use qt_widgets::QLabel;
+use qt_widgets::QLineEdit;
 use qt_widgets::QMenu;
 use qt_widgets::QScrollArea;
 use qt_widgets::QTableView;
@@ -26,6 +29,7 @@ use qt_widgets::QWidget;
 
 use qt_gui::QBrush;
 use qt_gui::QColor;
+use qt_gui::QGuiApplication;
 use qt_gui::QListOfQStandardItem;
 use qt_gui::QStandardItem;
 use qt_gui::QStandardItemModel;
@@ -49,9 +53,13 @@ use anyhow::Result;
 use getset::Getters;
 use rayon::prelude::*;
 
+use std::collections::BTreeMap;
+use std::fs::metadata;
+use std::path::PathBuf;
 use std::rc::Rc;
 
-use rpfm_extensions::diagnostics::{*, anim_fragment_battle::*, config::*, dependency::*, pack::*, portrait_settings::*, table::*};
+use rpfm_extensions::diagnostics::{*, anim_fragment_battle::*, config::*, dependency::*, pack::*, portrait_settings::*, table::*, text::*};
+use rpfm_extensions::search::SearchSource;
 
 use rpfm_lib::files::ContainerPath;
 use rpfm_lib::games::supported_games::*;
@@ -60,7 +68,7 @@ use rpfm_lib::integrations::log::info;
 use rpfm_ui_common::locale::{qtr, qtre};
 
 use crate::app_ui::AppUI;
-use crate::communications::{Command, Response, THREADS_COMMUNICATION_ERROR};
+use crate::communications::{CentralCommand, Command, Response, THREADS_COMMUNICATION_ERROR};
 use crate::CENTRAL_COMMAND;
 use crate::dependencies_ui::DependenciesUI;
 use crate::ffi::{new_tableview_filter_safe, trigger_tableview_filter_safe};
@@ -72,6 +80,7 @@ use crate::packfile_contents_ui::PackFileContentsUI;
 use crate::settings_ui::backend::*;
 use crate::UI_STATE;
 use crate::references_ui::ReferencesUI;
+use crate::SCHEMA;
 use crate::utils::*;
 use crate::views::table::{ITEM_HAS_ERROR, ITEM_HAS_WARNING, ITEM_HAS_INFO, utils::open_subtable};
 
@@ -81,6 +90,12 @@ pub mod slots;
 const VIEW_DEBUG: &str = "rpfm_ui/ui_templates/diagnostics_dock_widget.ui";
 const VIEW_RELEASE: &str = "ui/diagnostics_dock_widget.ui";
 
+/// Amount of results loaded into the table on each page.
+const DIAGNOSTICS_PAGE_SIZE: usize = 5_000;
+
+/// Role used to store, in the first item of each row, the position of its report in the full list of reports.
+const REPORT_POSITION: i32 = 40;
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -103,12 +118,18 @@ pub struct DiagnosticsUI {
     //-------------------------------------------------------------------------------//
     diagnostics_button_check_packfile: QPtr<QToolButton>,
     diagnostics_button_check_current_packed_file: QPtr<QToolButton>,
+    diagnostics_button_export: QPtr<QToolButton>,
     diagnostics_button_error: QPtr<QToolButton>,
     diagnostics_button_warning: QPtr<QToolButton>,
     diagnostics_button_info: QPtr<QToolButton>,
     diagnostics_button_only_current_packed_file: QPtr<QToolButton>,
     diagnostics_button_show_more_filters: QPtr<QToolButton>,
     diagnostics_button_check_ak_only_refs: QPtr<QToolButton>,
+    diagnostics_source_combobox: QPtr<QComboBox>,
+    diagnostics_button_load_more: QPtr<QToolButton>,
+    diagnostics_button_filter_case_sensitive: QPtr<QToolButton>,
+    diagnostics_path_filter: QPtr<QLineEdit>,
+    diagnostics_message_filter: QPtr<QLineEdit>,
 
     diagnostics_table_view_context_menu: QBox<QMenu>,
     ignore_parent_folder: QPtr<QAction>,
@@ -120,6 +141,11 @@ pub struct DiagnosticsUI {
     ignore_diagnostic_for_file: QPtr<QAction>,
     ignore_diagnostic_for_file_field: QPtr<QAction>,
     ignore_diagnostic_for_pack: QPtr<QAction>,
+    snooze_diagnostic: QPtr<QAction>,
+    export_reproducer: QPtr<QAction>,
+    copy_as_tsv: QPtr<QAction>,
+    fix_keep_first: QPtr<QAction>,
+    fix_keep_last: QPtr<QAction>,
 
     sidebar_scroll_area: QPtr<QScrollArea>,
     checkbox_all: QBox<QCheckBox>,
@@ -140,13 +166,27 @@ pub struct DiagnosticsUI {
     checkbox_invalid_packfile_name: QBox<QCheckBox>,
     checkbox_table_name_ends_in_number: QBox<QCheckBox>,
     checkbox_table_name_has_space: QBox<QCheckBox>,
+    checkbox_table_name_folder_mismatch: QBox<QCheckBox>,
     checkbox_table_is_datacoring: QBox<QCheckBox>,
     checkbox_dependencies_cache_outdated: QBox<QCheckBox>,
     checkbox_dependencies_cache_could_not_be_loaded: QBox<QCheckBox>,
     checkbox_field_with_path_not_found: QBox<QCheckBox>,
     checkbox_incorrect_game_path: QBox<QCheckBox>,
+    checkbox_schema_reference_cycle: QBox<QCheckBox>,
+    checkbox_duplicate_field_name: QBox<QCheckBox>,
+    checkbox_invalid_schema_reference: QBox<QCheckBox>,
     checkbox_banned_table: QBox<QCheckBox>,
     checkbox_value_cannot_be_empty: QBox<QCheckBox>,
+    checkbox_unknown_column_in_data: QBox<QCheckBox>,
+    checkbox_field_fails_validation_pattern: QBox<QCheckBox>,
+    checkbox_invalid_validation_pattern: QBox<QCheckBox>,
+    checkbox_enum_value_not_encodable: QBox<QCheckBox>,
+    checkbox_duplicated_combined_keys_conflicting: QBox<QCheckBox>,
+    checkbox_malformed_loc_formatting: QBox<QCheckBox>,
+    checkbox_inconsistent_escaping: QBox<QCheckBox>,
+    checkbox_empty_column: QBox<QCheckBox>,
+    checkbox_self_reference: QBox<QCheckBox>,
+    checkbox_missing_loc_entry: QBox<QCheckBox>,
     checkbox_invalid_art_set_id: QBox<QCheckBox>,
     checkbox_invalid_variant_filename: QBox<QCheckBox>,
     checkbox_file_diffuse_not_found_for_variant: QBox<QCheckBox>,
@@ -158,6 +198,11 @@ pub struct DiagnosticsUI {
     checkbox_file_path_not_found: QBox<QCheckBox>,
     checkbox_meta_file_path_not_found: QBox<QCheckBox>,
     checkbox_snd_file_path_not_found: QBox<QCheckBox>,
+    checkbox_referenced_file_not_found: QBox<QCheckBox>,
+    checkbox_file_too_large_to_check: QBox<QCheckBox>,
+
+    /// Diagnostic types checked by default, used for the ones without a saved state for the game selected.
+    diagnostics_checked_by_default: Vec<String>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -181,22 +226,37 @@ impl DiagnosticsUI {
         let diagnostics_label_hint: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "hint_label")?;
         let diagnostics_button_check_packfile: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "check_full_button")?;
         let diagnostics_button_check_current_packed_file: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "check_open_button")?;
+        let diagnostics_button_export: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "export_button")?;
         let diagnostics_button_error: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "error_button")?;
         let diagnostics_button_warning: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "warning_button")?;
         let diagnostics_button_info: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "info_button")?;
         let diagnostics_button_only_current_packed_file: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "only_open_button")?;
         let diagnostics_button_show_more_filters: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "more_filters_button")?;
         let diagnostics_button_check_ak_only_refs: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "check_ak_only_refs")?;
+        let diagnostics_source_combobox: QPtr<QComboBox> = find_widget(&main_widget.static_upcast(), "source_combobox")?;
+        let diagnostics_button_load_more: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "load_more_button")?;
+        let diagnostics_button_filter_case_sensitive: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "filter_case_sensitive_button")?;
+        let diagnostics_path_filter: QPtr<QLineEdit> = find_widget(&main_widget.static_upcast(), "path_filter_line_edit")?;
+        let diagnostics_message_filter: QPtr<QLineEdit> = find_widget(&main_widget.static_upcast(), "message_filter_line_edit")?;
 
         diagnostics_label_hint.set_text(&qtr("diagnostics_hint"));
         diagnostics_button_check_packfile.set_tool_tip(&qtr("diagnostics_button_check_packfile"));
         diagnostics_button_check_current_packed_file.set_tool_tip(&qtr("diagnostics_button_check_current_packed_file"));
+        diagnostics_button_export.set_tool_tip(&qtr("diagnostics_button_export"));
         diagnostics_button_error.set_tool_tip(&qtr("diagnostics_button_error"));
         diagnostics_button_warning.set_tool_tip(&qtr("diagnostics_button_warning"));
         diagnostics_button_info.set_tool_tip(&qtr("diagnostics_button_info"));
         diagnostics_button_only_current_packed_file.set_tool_tip(&qtr("diagnostics_button_only_current_packed_file"));
         diagnostics_button_show_more_filters.set_tool_tip(&qtr("diagnostics_button_show_more_filters"));
         diagnostics_button_check_ak_only_refs.set_tool_tip(&qtr("diagnostics_check_ak_only_refs"));
+        diagnostics_source_combobox.add_item_q_string(&qtr("diagnostics_source_packfile"));
+        diagnostics_source_combobox.add_item_q_string(&qtr("diagnostics_source_parent"));
+        diagnostics_source_combobox.add_item_q_string(&qtr("diagnostics_source_game"));
+        diagnostics_source_combobox.set_tool_tip(&qtr("diagnostics_source"));
+        diagnostics_button_load_more.set_visible(false);
+        diagnostics_button_filter_case_sensitive.set_tool_tip(&qtr("diagnostics_filter_case_sensitive"));
+        diagnostics_path_filter.set_placeholder_text(&qtr("diagnostics_path_filter"));
+        diagnostics_message_filter.set_placeholder_text(&qtr("diagnostics_message_filter"));
 
         diagnostics_button_error.set_tool_button_style(ToolButtonStyle::ToolButtonTextUnderIcon);
         diagnostics_button_warning.set_tool_button_style(ToolButtonStyle::ToolButtonTextUnderIcon);
@@ -213,6 +273,13 @@ impl DiagnosticsUI {
         let ignore_diagnostic_for_file = add_action_to_menu(&diagnostics_table_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "diagnostics_context_menu", "ignore_diagnostic_for_file", "ignore_diagnostic_for_file", Some(diagnostics_table_view.static_upcast::<qt_widgets::QWidget>()));
         let ignore_diagnostic_for_file_field = add_action_to_menu(&diagnostics_table_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "diagnostics_context_menu", "ignore_diagnostic_for_file_field", "ignore_diagnostic_for_file_field", Some(diagnostics_table_view.static_upcast::<qt_widgets::QWidget>()));
         let ignore_diagnostic_for_pack = add_action_to_menu(&diagnostics_table_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "diagnostics_context_menu", "ignore_diagnostic_for_pack", "ignore_diagnostic_for_pack", Some(diagnostics_table_view.static_upcast::<qt_widgets::QWidget>()));
+        diagnostics_table_view_context_menu.add_separator();
+        let snooze_diagnostic = add_action_to_menu(&diagnostics_table_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "diagnostics_context_menu", "snooze_diagnostic", "snooze_diagnostic", Some(diagnostics_table_view.static_upcast::<qt_widgets::QWidget>()));
+        let export_reproducer = add_action_to_menu(&diagnostics_table_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "diagnostics_context_menu", "export_reproducer", "diagnostics_export_reproducer", Some(diagnostics_table_view.static_upcast::<qt_widgets::QWidget>()));
+        let copy_as_tsv = add_action_to_menu(&diagnostics_table_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "diagnostics_context_menu", "copy_as_tsv", "diagnostics_copy_as_tsv", Some(diagnostics_table_view.static_upcast::<qt_widgets::QWidget>()));
+        diagnostics_table_view_context_menu.add_separator();
+        let fix_keep_first = add_action_to_menu(&diagnostics_table_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "diagnostics_context_menu", "fix_keep_first", "diagnostics_fix_keep_first", Some(diagnostics_table_view.static_upcast::<qt_widgets::QWidget>()));
+        let fix_keep_last = add_action_to_menu(&diagnostics_table_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "diagnostics_context_menu", "fix_keep_last", "diagnostics_fix_keep_last", Some(diagnostics_table_view.static_upcast::<qt_widgets::QWidget>()));
 
         let sidebar_scroll_area: QPtr<QScrollArea> = find_widget(&main_widget.static_upcast(), "more_filters_scroll")?;
         let header_column: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "diagnostics_label")?;
@@ -224,30 +291,6 @@ impl DiagnosticsUI {
         diagnostics_dock_widget.set_window_title(&qtr("gen_loc_diagnostics"));
         diagnostics_dock_widget.set_object_name(&QString::from_std_str("diagnostics_dock"));
 
-        diagnostics_button_info.set_style_sheet(&QString::from_std_str(format!("
-        QPushButton {{
-            background-color: {}
-        }}
-        QPushButton::checked {{
-            background-color: {}
-        }}", get_color_info(), get_color_info_pressed())));
-
-        diagnostics_button_warning.set_style_sheet(&QString::from_std_str(format!("
-        QPushButton {{
-            background-color: {}
-        }}
-        QPushButton::checked {{
-            background-color: {}
-        }}", get_color_warning(), get_color_warning_pressed())));
-
-        diagnostics_button_error.set_style_sheet(&QString::from_std_str(format!("
-        QPushButton {{
-            background-color: {}
-        }}
-        QPushButton::checked {{
-            background-color: {}
-        }}", get_color_error(), get_color_error_pressed())));
-
         let diagnostics_table_filter = new_tableview_filter_safe(diagnostics_dock_inner_widget.static_upcast());
         let diagnostics_table_model = QStandardItemModel::new_1a(&diagnostics_dock_inner_widget);
         diagnostics_table_filter.set_source_model(&diagnostics_table_model);
@@ -288,13 +331,27 @@ impl DiagnosticsUI {
         let checkbox_invalid_packfile_name = QCheckBox::from_q_string_q_widget(&qtr("label_invalid_packfile_name"), &sidebar_scroll_area);
         let checkbox_table_name_ends_in_number = QCheckBox::from_q_string_q_widget(&qtr("label_table_name_ends_in_number"), &sidebar_scroll_area);
         let checkbox_table_name_has_space = QCheckBox::from_q_string_q_widget(&qtr("label_table_name_has_space"), &sidebar_scroll_area);
+        let checkbox_table_name_folder_mismatch = QCheckBox::from_q_string_q_widget(&qtr("label_table_name_folder_mismatch"), &sidebar_scroll_area);
         let checkbox_table_is_datacoring = QCheckBox::from_q_string_q_widget(&qtr("label_table_is_datacoring"), &sidebar_scroll_area);
         let checkbox_dependencies_cache_outdated = QCheckBox::from_q_string_q_widget(&qtr("label_dependencies_cache_outdated"), &sidebar_scroll_area);
         let checkbox_dependencies_cache_could_not_be_loaded = QCheckBox::from_q_string_q_widget(&qtr("label_dependencies_cache_could_not_be_loaded"), &sidebar_scroll_area);
         let checkbox_field_with_path_not_found = QCheckBox::from_q_string_q_widget(&qtr("label_field_with_path_not_found"), &sidebar_scroll_area);
         let checkbox_incorrect_game_path = QCheckBox::from_q_string_q_widget(&qtr("label_incorrect_game_path"), &sidebar_scroll_area);
+        let checkbox_schema_reference_cycle = QCheckBox::from_q_string_q_widget(&qtr("label_schema_reference_cycle"), &sidebar_scroll_area);
+        let checkbox_duplicate_field_name = QCheckBox::from_q_string_q_widget(&qtr("label_duplicate_field_name"), &sidebar_scroll_area);
+        let checkbox_invalid_schema_reference = QCheckBox::from_q_string_q_widget(&qtr("label_invalid_schema_reference"), &sidebar_scroll_area);
         let checkbox_banned_table = QCheckBox::from_q_string_q_widget(&qtr("label_banned_table"), &sidebar_scroll_area);
         let checkbox_value_cannot_be_empty = QCheckBox::from_q_string_q_widget(&qtr("label_value_cannot_be_empty"), &sidebar_scroll_area);
+        let checkbox_unknown_column_in_data = QCheckBox::from_q_string_q_widget(&qtr("label_unknown_column_in_data"), &sidebar_scroll_area);
+        let checkbox_field_fails_validation_pattern = QCheckBox::from_q_string_q_widget(&qtr("label_field_fails_validation_pattern"), &sidebar_scroll_area);
+        let checkbox_invalid_validation_pattern = QCheckBox::from_q_string_q_widget(&qtr("label_invalid_validation_pattern"), &sidebar_scroll_area);
+        let checkbox_enum_value_not_encodable = QCheckBox::from_q_string_q_widget(&qtr("label_enum_value_not_encodable"), &sidebar_scroll_area);
+        let checkbox_duplicated_combined_keys_conflicting = QCheckBox::from_q_string_q_widget(&qtr("label_duplicated_combined_keys_conflicting"), &sidebar_scroll_area);
+        let checkbox_malformed_loc_formatting = QCheckBox::from_q_string_q_widget(&qtr("label_malformed_loc_formatting"), &sidebar_scroll_area);
+        let checkbox_inconsistent_escaping = QCheckBox::from_q_string_q_widget(&qtr("label_inconsistent_escaping"), &sidebar_scroll_area);
+        let checkbox_empty_column = QCheckBox::from_q_string_q_widget(&qtr("label_empty_column"), &sidebar_scroll_area);
+        let checkbox_self_reference = QCheckBox::from_q_string_q_widget(&qtr("label_self_reference"), &sidebar_scroll_area);
+        let checkbox_missing_loc_entry = QCheckBox::from_q_string_q_widget(&qtr("label_missing_loc_entry"), &sidebar_scroll_area);
         let checkbox_invalid_art_set_id = QCheckBox::from_q_string_q_widget(&qtr("label_invalid_art_set_id"), &sidebar_scroll_area);
         let checkbox_invalid_variant_filename = QCheckBox::from_q_string_q_widget(&qtr("label_invalid_variant_filename"), &sidebar_scroll_area);
         let checkbox_file_diffuse_not_found_for_variant = QCheckBox::from_q_string_q_widget(&qtr("label_file_diffuse_not_found_for_variant"), &sidebar_scroll_area);
@@ -306,6 +363,8 @@ impl DiagnosticsUI {
         let checkbox_file_path_not_found = QCheckBox::from_q_string_q_widget(&qtr("label_file_path_not_found"), &sidebar_scroll_area);
         let checkbox_meta_file_path_not_found = QCheckBox::from_q_string_q_widget(&qtr("label_meta_file_path_not_found"), &sidebar_scroll_area);
         let checkbox_snd_file_path_not_found = QCheckBox::from_q_string_q_widget(&qtr("label_snd_file_path_not_found"), &sidebar_scroll_area);
+        let checkbox_referenced_file_not_found = QCheckBox::from_q_string_q_widget(&qtr("label_referenced_file_not_found"), &sidebar_scroll_area);
+        let checkbox_file_too_large_to_check = QCheckBox::from_q_string_q_widget(&qtr("label_file_too_large_to_check"), &sidebar_scroll_area);
 
         checkbox_all.set_checked(false);
         checkbox_outdated_table.set_checked(true);
@@ -325,13 +384,28 @@ impl DiagnosticsUI {
         checkbox_invalid_packfile_name.set_checked(true);
         checkbox_table_name_ends_in_number.set_checked(true);
         checkbox_table_name_has_space.set_checked(true);
+        checkbox_table_name_folder_mismatch.set_checked(true);
         checkbox_table_is_datacoring.set_checked(true);
         checkbox_dependencies_cache_outdated.set_checked(true);
         checkbox_dependencies_cache_could_not_be_loaded.set_checked(true);
         checkbox_field_with_path_not_found.set_checked(false);
         checkbox_incorrect_game_path.set_checked(true);
+        checkbox_schema_reference_cycle.set_checked(true);
+        checkbox_duplicate_field_name.set_checked(true);
+        checkbox_invalid_schema_reference.set_checked(true);
         checkbox_banned_table.set_checked(true);
         checkbox_value_cannot_be_empty.set_checked(true);
+        checkbox_unknown_column_in_data.set_checked(true);
+        checkbox_field_fails_validation_pattern.set_checked(true);
+        checkbox_invalid_validation_pattern.set_checked(true);
+        checkbox_enum_value_not_encodable.set_checked(true);
+        checkbox_duplicated_combined_keys_conflicting.set_checked(true);
+        checkbox_malformed_loc_formatting.set_checked(true);
+        checkbox_inconsistent_escaping.set_checked(true);
+        checkbox_empty_column.set_checked(true);
+        checkbox_self_reference.set_checked(true);
+        checkbox_missing_loc_entry.set_checked(true);
+        checkbox_missing_loc_entry.set_tool_tip(&qtr("missing_loc_entry_explanation"));
         checkbox_invalid_art_set_id.set_checked(true);
         checkbox_invalid_variant_filename.set_checked(true);
         checkbox_file_diffuse_not_found_for_variant.set_checked(true);
@@ -343,6 +417,8 @@ impl DiagnosticsUI {
         checkbox_file_path_not_found.set_checked(true);
         checkbox_meta_file_path_not_found.set_checked(true);
         checkbox_snd_file_path_not_found.set_checked(true);
+        checkbox_referenced_file_not_found.set_checked(true);
+        checkbox_file_too_large_to_check.set_checked(true);
 
         sidebar_grid.add_widget_1a(&checkbox_all);
         sidebar_grid.add_widget_1a(&checkbox_outdated_table);
@@ -362,13 +438,27 @@ impl DiagnosticsUI {
         sidebar_grid.add_widget_1a(&checkbox_invalid_packfile_name);
         sidebar_grid.add_widget_1a(&checkbox_table_name_ends_in_number);
         sidebar_grid.add_widget_1a(&checkbox_table_name_has_space);
+        sidebar_grid.add_widget_1a(&checkbox_table_name_folder_mismatch);
         sidebar_grid.add_widget_1a(&checkbox_table_is_datacoring);
         sidebar_grid.add_widget_1a(&checkbox_dependencies_cache_outdated);
         sidebar_grid.add_widget_1a(&checkbox_dependencies_cache_could_not_be_loaded);
         sidebar_grid.add_widget_1a(&checkbox_field_with_path_not_found);
         sidebar_grid.add_widget_1a(&checkbox_incorrect_game_path);
+        sidebar_grid.add_widget_1a(&checkbox_schema_reference_cycle);
+        sidebar_grid.add_widget_1a(&checkbox_duplicate_field_name);
+        sidebar_grid.add_widget_1a(&checkbox_invalid_schema_reference);
         sidebar_grid.add_widget_1a(&checkbox_banned_table);
         sidebar_grid.add_widget_1a(&checkbox_value_cannot_be_empty);
+        sidebar_grid.add_widget_1a(&checkbox_unknown_column_in_data);
+        sidebar_grid.add_widget_1a(&checkbox_field_fails_validation_pattern);
+        sidebar_grid.add_widget_1a(&checkbox_invalid_validation_pattern);
+        sidebar_grid.add_widget_1a(&checkbox_enum_value_not_encodable);
+        sidebar_grid.add_widget_1a(&checkbox_duplicated_combined_keys_conflicting);
+        sidebar_grid.add_widget_1a(&checkbox_malformed_loc_formatting);
+        sidebar_grid.add_widget_1a(&checkbox_inconsistent_escaping);
+        sidebar_grid.add_widget_1a(&checkbox_empty_column);
+        sidebar_grid.add_widget_1a(&checkbox_self_reference);
+        sidebar_grid.add_widget_1a(&checkbox_missing_loc_entry);
         sidebar_grid.add_widget_1a(&checkbox_invalid_art_set_id);
         sidebar_grid.add_widget_1a(&checkbox_invalid_variant_filename);
         sidebar_grid.add_widget_1a(&checkbox_file_diffuse_not_found_for_variant);
@@ -380,8 +470,10 @@ impl DiagnosticsUI {
         sidebar_grid.add_widget_1a(&checkbox_file_path_not_found);
         sidebar_grid.add_widget_1a(&checkbox_meta_file_path_not_found);
         sidebar_grid.add_widget_1a(&checkbox_snd_file_path_not_found);
+        sidebar_grid.add_widget_1a(&checkbox_referenced_file_not_found);
+        sidebar_grid.add_widget_1a(&checkbox_file_too_large_to_check);
 
-        Ok(Self {
+        let diagnostics_ui = Self {
 
             //-------------------------------------------------------------------------------//
             // `Diagnostics` Dock Widget.
@@ -396,12 +488,18 @@ impl DiagnosticsUI {
             //-------------------------------------------------------------------------------//
             diagnostics_button_check_packfile,
             diagnostics_button_check_current_packed_file,
+            diagnostics_button_export,
             diagnostics_button_error,
             diagnostics_button_warning,
             diagnostics_button_info,
             diagnostics_button_only_current_packed_file,
             diagnostics_button_show_more_filters,
             diagnostics_button_check_ak_only_refs,
+            diagnostics_source_combobox,
+            diagnostics_button_load_more,
+            diagnostics_button_filter_case_sensitive,
+            diagnostics_path_filter,
+            diagnostics_message_filter,
 
             diagnostics_table_view_context_menu,
             ignore_parent_folder,
@@ -413,6 +511,11 @@ impl DiagnosticsUI {
             ignore_diagnostic_for_file,
             ignore_diagnostic_for_file_field,
             ignore_diagnostic_for_pack,
+            snooze_diagnostic,
+            export_reproducer,
+            copy_as_tsv,
+            fix_keep_first,
+            fix_keep_last,
 
             sidebar_scroll_area,
             checkbox_all,
@@ -433,13 +536,27 @@ impl DiagnosticsUI {
             checkbox_invalid_packfile_name,
             checkbox_table_name_ends_in_number,
             checkbox_table_name_has_space,
+            checkbox_table_name_folder_mismatch,
             checkbox_table_is_datacoring,
             checkbox_dependencies_cache_outdated,
             checkbox_dependencies_cache_could_not_be_loaded,
             checkbox_field_with_path_not_found,
             checkbox_incorrect_game_path,
+            checkbox_schema_reference_cycle,
+            checkbox_duplicate_field_name,
+            checkbox_invalid_schema_reference,
             checkbox_banned_table,
             checkbox_value_cannot_be_empty,
+            checkbox_unknown_column_in_data,
+            checkbox_field_fails_validation_pattern,
+            checkbox_invalid_validation_pattern,
+            checkbox_enum_value_not_encodable,
+            checkbox_duplicated_combined_keys_conflicting,
+            checkbox_malformed_loc_formatting,
+            checkbox_inconsistent_escaping,
+            checkbox_empty_column,
+            checkbox_self_reference,
+            checkbox_missing_loc_entry,
             checkbox_invalid_art_set_id,
             checkbox_invalid_variant_filename,
             checkbox_file_diffuse_not_found_for_variant,
@@ -451,7 +568,47 @@ impl DiagnosticsUI {
             checkbox_file_path_not_found,
             checkbox_meta_file_path_not_found,
             checkbox_snd_file_path_not_found,
-        })
+            checkbox_referenced_file_not_found,
+            checkbox_file_too_large_to_check,
+            diagnostics_checked_by_default: vec![],
+        };
+
+        diagnostics_ui.diagnostics_checked_by_default = diagnostics_ui.diagnostics_checkboxes()
+            .into_iter()
+            .filter(|(checkbox, _)| checkbox.is_checked())
+            .map(|(_, report_type)| report_type)
+            .collect();
+
+        diagnostics_ui.load_diagnostics_ignored();
+        diagnostics_ui.update_level_colours();
+        Ok(diagnostics_ui)
+    }
+
+    /// This function updates the colours of the level buttons with the ones in the settings.
+    pub unsafe fn update_level_colours(&self) {
+        self.diagnostics_button_info.set_style_sheet(&QString::from_std_str(format!("
+        QPushButton {{
+            background-color: {}
+        }}
+        QPushButton::checked {{
+            background-color: {}
+        }}", get_color_info(), get_color_info_pressed())));
+
+        self.diagnostics_button_warning.set_style_sheet(&QString::from_std_str(format!("
+        QPushButton {{
+            background-color: {}
+        }}
+        QPushButton::checked {{
+            background-color: {}
+        }}", get_color_warning(), get_color_warning_pressed())));
+
+        self.diagnostics_button_error.set_style_sheet(&QString::from_std_str(format!("
+        QPushButton {{
+            background-color: {}
+        }}
+        QPushButton::checked {{
+            background-color: {}
+        }}", get_color_error(), get_color_error_pressed())));
     }
 
     /// This function takes care of checking the entire PackFile for errors.
@@ -463,14 +620,15 @@ impl DiagnosticsUI {
         }
 
         app_ui.menu_bar_packfile().set_enabled(false);
+        diagnostics_ui.update_level_colours();
         let diagnostics_ignored = diagnostics_ui.diagnostics_ignored();
         info!("Triggering check.");
-        let receiver = CENTRAL_COMMAND.send_background(Command::DiagnosticsCheck(diagnostics_ignored, diagnostics_ui.diagnostics_button_check_ak_only_refs().is_checked()));
+        let receiver = CENTRAL_COMMAND.send_background(Command::DiagnosticsCheck(diagnostics_ignored, diagnostics_ui.diagnostics_button_check_ak_only_refs().is_checked(), diagnostics_ui.source()));
         let response = CENTRAL_COMMAND.recv_try(&receiver);
 
         match response {
             Response::Diagnostics(diagnostics) => {
-                Self::load_diagnostics_to_ui(app_ui, diagnostics_ui, diagnostics.results());
+                Self::load_diagnostics_to_ui(app_ui, diagnostics_ui, &diagnostics);
                 Self::filter(app_ui, diagnostics_ui);
                 Self::update_level_counts(diagnostics_ui, diagnostics.results());
                 UI_STATE.set_diagnostics(&diagnostics);
@@ -481,6 +639,37 @@ impl DiagnosticsUI {
         app_ui.menu_bar_packfile().set_enabled(true);
     }
 
+    /// This function takes care of triggering the diagnostics check, if enabled, after opening the provided Packs.
+    ///
+    /// If `run_diagnostics_on_open` is enabled, the panel is shown if hidden, unless the Packs are bigger than the configured limit.
+    pub unsafe fn check_on_open(app_ui: &Rc<AppUI>, diagnostics_ui: &Rc<Self>, pack_paths: &[PathBuf]) {
+        if setting_bool("run_diagnostics_on_open") {
+
+            // Without schema there's nothing we can check.
+            if SCHEMA.read().unwrap().is_none() {
+                return;
+            }
+
+            let max_size = setting_int("run_diagnostics_on_open_max_size") as u64 * 1024 * 1024;
+            let size = pack_paths.iter()
+                .filter_map(|path| metadata(path).ok())
+                .map(|metadata| metadata.len())
+                .sum::<u64>();
+
+            if max_size > 0 && size > max_size {
+                info!("Skipping diagnostics check on open: Pack too big ({size} bytes).");
+                return;
+            }
+
+            diagnostics_ui.diagnostics_dock_widget.show();
+            Self::check(app_ui, diagnostics_ui);
+        }
+
+        else if setting_bool("diagnostics_trigger_on_open") {
+            Self::check(app_ui, diagnostics_ui);
+        }
+    }
+
     /// This function takes care of updating the results of a diagnostics check for the provided paths.
     pub unsafe fn check_on_path(app_ui: &Rc<AppUI>, diagnostics_ui: &Rc<Self>, paths: Vec<ContainerPath>) {
 
@@ -489,9 +678,15 @@ impl DiagnosticsUI {
             return;
         }
 
+        // Edits in the Pack don't affect the results of checks over other sources.
+        let mut diagnostics = UI_STATE.get_diagnostics();
+        if diagnostics.source() != &SearchSource::Pack {
+            return;
+        }
+
         app_ui.menu_bar_packfile().set_enabled(false);
+        diagnostics_ui.update_level_colours();
 
-        let mut diagnostics = UI_STATE.get_diagnostics();
         *diagnostics.diagnostics_ignored_mut() = diagnostics_ui.diagnostics_ignored();
         info!("Triggering check update.");
         let receiver = CENTRAL_COMMAND.send_background(Command::DiagnosticsUpdate(diagnostics, paths, diagnostics_ui.diagnostics_button_check_ak_only_refs().is_checked()));
@@ -499,7 +694,7 @@ impl DiagnosticsUI {
 
         match response {
             Response::Diagnostics(diagnostics) => {
-                Self::load_diagnostics_to_ui(app_ui, diagnostics_ui, diagnostics.results());
+                Self::load_diagnostics_to_ui(app_ui, diagnostics_ui, &diagnostics);
                 Self::filter(app_ui, diagnostics_ui);
                 Self::update_level_counts(diagnostics_ui, diagnostics.results());
                 UI_STATE.set_diagnostics(&diagnostics);
@@ -511,14 +706,16 @@ impl DiagnosticsUI {
     }
 
     /// This function takes care of loading the results of a diagnostic check into the table.
-    unsafe fn load_diagnostics_to_ui(app_ui: &Rc<AppUI>, diagnostics_ui: &Rc<Self>, diagnostics: &[DiagnosticType]) {
+    ///
+    /// Only the first page of results is loaded. The rest can be loaded on demand with `load_diagnostics_page`.
+    unsafe fn load_diagnostics_to_ui(app_ui: &Rc<AppUI>, diagnostics_ui: &Rc<Self>, diagnostics: &Diagnostics) {
 
         // First, clean the current diagnostics.
         Self::clean_diagnostics_from_views(app_ui);
 
         // Build the table columns without data in them, because otherwise it becomes very slow.
         diagnostics_ui.diagnostics_table_model.clear();
-        diagnostics_ui.diagnostics_table_model.set_column_count(7);
+        diagnostics_ui.diagnostics_table_model.set_column_count(8);
 
         diagnostics_ui.diagnostics_table_model.set_header_data_3a(0, Orientation::Horizontal, &QVariant::from_q_string(&qtr("diagnostics_colum_level")));
         diagnostics_ui.diagnostics_table_model.set_header_data_3a(1, Orientation::Horizontal, &QVariant::from_q_string(&qtr("diagnostics_colum_diag")));
@@ -527,12 +724,14 @@ impl DiagnosticsUI {
         diagnostics_ui.diagnostics_table_model.set_header_data_3a(4, Orientation::Horizontal, &QVariant::from_q_string(&qtr("diagnostics_colum_message")));
         diagnostics_ui.diagnostics_table_model.set_header_data_3a(5, Orientation::Horizontal, &QVariant::from_q_string(&qtr("diagnostics_colum_report_type")));
         diagnostics_ui.diagnostics_table_model.set_header_data_3a(6, Orientation::Horizontal, &QVariant::from_q_string(&qtr("diagnostics_colum_column_names")));
+        diagnostics_ui.diagnostics_table_model.set_header_data_3a(7, Orientation::Horizontal, &QVariant::from_q_string(&qtr("diagnostics_colum_snoozed")));
 
         // Hide the column number column for tables.
         diagnostics_ui.diagnostics_table_view.hide_column(1);
         diagnostics_ui.diagnostics_table_view.hide_column(2);
         diagnostics_ui.diagnostics_table_view.hide_column(5);
         diagnostics_ui.diagnostics_table_view.hide_column(6);
+        diagnostics_ui.diagnostics_table_view.hide_column(7);
         diagnostics_ui.diagnostics_table_view.sort_by_column_2a(3, SortOrder::AscendingOrder);
 
         diagnostics_ui.diagnostics_table_view.horizontal_header().set_stretch_last_section(true);
@@ -540,7 +739,25 @@ impl DiagnosticsUI {
         diagnostics_ui.diagnostics_table_view.horizontal_header().set_default_section_size(70);
         diagnostics_ui.diagnostics_table_view.set_column_width(3, 600);
 
-        if !diagnostics.is_empty() {
+        Self::load_diagnostics_page(diagnostics_ui, diagnostics);
+
+        // After that, check if the table is open, and paint the results into it.
+        for diagnostic_type in diagnostics.results() {
+            Self::paint_diagnostics_to_table(app_ui, diagnostic_type);
+        }
+    }
+
+    /// This function loads the next page of results of a diagnostic check into the table.
+    ///
+    /// The page starts after the last result already in the table, so calling this repeatedly loads the results incrementally.
+    pub unsafe fn load_diagnostics_page(diagnostics_ui: &Rc<Self>, diagnostics: &Diagnostics) {
+        let loaded = diagnostics_ui.diagnostics_table_model.row_count_0a() as usize;
+        let reports = diagnostics.iter_results()
+            .skip(loaded)
+            .take(DIAGNOSTICS_PAGE_SIZE)
+            .collect::<Vec<_>>();
+
+        if !reports.is_empty() {
 
             // Microoptimization: block the model from triggering signals on each item added.
             diagnostics_ui.diagnostics_table_model.block_signals(true);
@@ -553,294 +770,135 @@ impl DiagnosticsUI {
             let color_warning = atomic_from_cpp_box(QBrush::from_q_color(&QColor::from_q_string(&QString::from_std_str(get_color_warning()))));
             let color_error = atomic_from_cpp_box(QBrush::from_q_color(&QColor::from_q_string(&QString::from_std_str(get_color_error()))));
 
-            let rows = diagnostics.par_iter()
-                .map(|diagnostic_type| {
-
-                    match diagnostic_type {
+            let rows = reports.par_iter()
+                .enumerate()
+                .map(|(position, (diagnostic_type, index))| {
+                    let qlist = QListOfQStandardItem::new();
+
+                    // Create an empty row.
+                    let level = Self::new_item();
+                    let diag_type = Self::new_item();
+                    let data_affected = Self::new_item();
+                    let path = Self::new_item();
+                    let message = Self::new_item();
+                    let report_type = Self::new_item();
+                    let extra_data_1 = Self::new_item();
+                    let snoozed = Self::new_item();
+
+                    diag_type.set_text(&QString::from_std_str(diagnostic_type.to_string()));
+                    level.set_data_2a(&QVariant::from_u64((loaded + position) as u64), REPORT_POSITION);
+
+                    let result_level = match diagnostic_type {
                         DiagnosticType::AnimFragmentBattle(ref diagnostic) => {
-                            let mut reports = Vec::with_capacity(diagnostic.results().len());
-
-                            for result in diagnostic.results() {
-                                let qlist = QListOfQStandardItem::new();
-
-                                // Create an empty row.
-                                let level = Self::new_item();
-                                let diag_type = Self::new_item();
-                                let data_affected = Self::new_item();
-                                let path = Self::new_item();
-                                let message = Self::new_item();
-                                let report_type = Self::new_item();
-                                let extra_data_1 = Self::new_item();
-
-                                let (result_type, color) = match result.level() {
-                                    DiagnosticLevel::Info => (ref_from_atomic(&result_type_info), ref_from_atomic(&color_info)),
-                                    DiagnosticLevel::Warning => (ref_from_atomic(&result_type_warning), ref_from_atomic(&color_warning)),
-                                    DiagnosticLevel::Error => (ref_from_atomic(&result_type_error), ref_from_atomic(&color_error)),
-                                };
-
-                                level.set_background(color);
-                                level.set_text(result_type);
-                                diag_type.set_text(&QString::from_std_str(diagnostic_type.to_string()));
-                                data_affected.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(serde_json::to_string(&result).unwrap())), 2);
-                                path.set_text(&QString::from_std_str(diagnostic.path()));
-                                message.set_text(&QString::from_std_str(result.message()));
-                                report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
-
-                                // Set the tooltips to the diag type and description columns.
-                                Self::set_tooltips_anim_fragment(&[&level, &path, &message], result.report_type());
-
-                                qlist.append_q_standard_item(&level.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&diag_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&data_affected.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&path.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&message.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&report_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&extra_data_1.into_ptr().as_mut_raw_ptr());
-
-                                reports.push(atomic_from_cpp_box(qlist));
-                            }
-
-                            reports
+                            let result = &diagnostic.results()[*index];
+                            data_affected.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(serde_json::to_string(&result).unwrap())), 2);
+                            path.set_text(&QString::from_std_str(diagnostic.path()));
+                            message.set_text(&QString::from_std_str(result.message()));
+                            report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
+
+                            // Set the tooltips to the diag type and description columns.
+                            Self::set_tooltips_anim_fragment(&[&level, &path, &message], result.report_type());
+                            result.level()
                         }
                         DiagnosticType::DB(ref diagnostic) |
                         DiagnosticType::Loc(ref diagnostic) => {
-                            let mut reports = Vec::with_capacity(diagnostic.results().len());
-
-                            for result in diagnostic.results() {
-                                let qlist = QListOfQStandardItem::new();
-
-                                // Create an empty row.
-                                let level = Self::new_item();
-                                let diag_type = Self::new_item();
-                                let data_affected = Self::new_item();
-                                let path = Self::new_item();
-                                let message = Self::new_item();
-                                let report_type = Self::new_item();
-                                let extra_data_1 = Self::new_item();
-
-                                let (result_type, color) = match result.level() {
-                                    DiagnosticLevel::Info => (ref_from_atomic(&result_type_info), ref_from_atomic(&color_info)),
-                                    DiagnosticLevel::Warning => (ref_from_atomic(&result_type_warning), ref_from_atomic(&color_warning)),
-                                    DiagnosticLevel::Error => (ref_from_atomic(&result_type_error), ref_from_atomic(&color_error)),
-                                };
-
-                                level.set_background(color);
-                                level.set_text(result_type);
-                                diag_type.set_text(&QString::from_std_str(diagnostic_type.to_string()));
-                                data_affected.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(serde_json::to_string(&result.cells_affected()).unwrap())), 2);
-                                path.set_text(&QString::from_std_str(diagnostic.path()));
-                                message.set_text(&QString::from_std_str(result.message()));
-                                report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
-                                extra_data_1.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(serde_json::to_string(&result.column_names()).unwrap())), 2);
-
-
-                                // Set the tooltips to the diag type and description columns.
-                                Self::set_tooltips_table(&[&level, &path, &message], result.report_type());
-
-                                qlist.append_q_standard_item(&level.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&diag_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&data_affected.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&path.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&message.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&report_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&extra_data_1.into_ptr().as_mut_raw_ptr());
-
-                                reports.push(atomic_from_cpp_box(qlist));
-                            }
-
-                            reports
+                            let result = &diagnostic.results()[*index];
+                            data_affected.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(serde_json::to_string(&result.cells_affected()).unwrap())), 2);
+                            path.set_text(&QString::from_std_str(diagnostic.path()));
+                            message.set_text(&QString::from_std_str(result.message()));
+                            report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
+                            extra_data_1.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(serde_json::to_string(&result.column_names()).unwrap())), 2);
+
+                            // Set the tooltips to the diag type and description columns.
+                            Self::set_tooltips_table(&[&level, &path, &message], result.report_type());
+                            result.level()
                         },
                         DiagnosticType::Pack(ref diagnostic) => {
-                            let mut reports = Vec::with_capacity(diagnostic.results().len());
-
-                            for result in diagnostic.results() {
-                                let qlist = QListOfQStandardItem::new();
-
-                                // Create an empty row.
-                                let level = Self::new_item();
-                                let diag_type = Self::new_item();
-                                let data_affected = Self::new_item();
-                                let path = Self::new_item();
-                                let message = Self::new_item();
-                                let report_type = Self::new_item();
-                                let extra_data_1 = Self::new_item();
-
-                                let (result_type, color) = match result.level() {
-                                    DiagnosticLevel::Info => (ref_from_atomic(&result_type_info), ref_from_atomic(&color_info)),
-                                    DiagnosticLevel::Warning => (ref_from_atomic(&result_type_warning), ref_from_atomic(&color_warning)),
-                                    DiagnosticLevel::Error => (ref_from_atomic(&result_type_error), ref_from_atomic(&color_error)),
-                                };
-
-                                level.set_background(color);
-                                level.set_text(result_type);
-                                diag_type.set_text(&QString::from_std_str(diagnostic_type.to_string()));
-                                message.set_text(&QString::from_std_str(result.message()));
-                                report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
-
-                                // Set the tooltips to the diag type and description columns.
-                                Self::set_tooltips_packfile(&[&level, &path, &message], result.report_type());
-
-                                qlist.append_q_standard_item(&level.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&diag_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&data_affected.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&path.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&message.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&report_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&extra_data_1.into_ptr().as_mut_raw_ptr());
+                            let result = &diagnostic.results()[*index];
+                            message.set_text(&QString::from_std_str(result.message()));
+                            report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
 
-                                reports.push(atomic_from_cpp_box(qlist));
-                            }
-
-                            reports
+                            // Set the tooltips to the diag type and description columns.
+                            Self::set_tooltips_packfile(&[&level, &path, &message], result.report_type());
+                            result.level()
                         }
                         DiagnosticType::PortraitSettings(ref diagnostic) => {
-                            let mut reports = Vec::with_capacity(diagnostic.results().len());
-
-                            for result in diagnostic.results() {
-                                let qlist = QListOfQStandardItem::new();
-
-                                // Create an empty row.
-                                let level = Self::new_item();
-                                let diag_type = Self::new_item();
-                                let data_affected = Self::new_item();
-                                let path = Self::new_item();
-                                let message = Self::new_item();
-                                let report_type = Self::new_item();
-                                let extra_data_1 = Self::new_item();
-
-                                let (result_type, color) = match result.level() {
-                                    DiagnosticLevel::Info => (ref_from_atomic(&result_type_info), ref_from_atomic(&color_info)),
-                                    DiagnosticLevel::Warning => (ref_from_atomic(&result_type_warning), ref_from_atomic(&color_warning)),
-                                    DiagnosticLevel::Error => (ref_from_atomic(&result_type_error), ref_from_atomic(&color_error)),
-                                };
-
-                                level.set_background(color);
-                                level.set_text(result_type);
-                                diag_type.set_text(&QString::from_std_str(diagnostic_type.to_string()));
-
-                                let data_affected_string = match result.report_type() {
-                                    PortraitSettingsDiagnosticReportType::DatacoredPortraitSettings => String::new(),
-                                    PortraitSettingsDiagnosticReportType::InvalidArtSetId(art_set_id) => art_set_id.to_owned(),
-                                    PortraitSettingsDiagnosticReportType::InvalidVariantFilename(art_set_id, variant_filename) => art_set_id.to_owned() + "|" + variant_filename,
-                                    PortraitSettingsDiagnosticReportType::FileDiffuseNotFoundForVariant(art_set_id, variant_filename, _) => art_set_id.to_owned() + "|" + variant_filename,
-                                    PortraitSettingsDiagnosticReportType::FileMask1NotFoundForVariant(art_set_id, variant_filename, _) => art_set_id.to_owned() + "|" + variant_filename,
-                                    PortraitSettingsDiagnosticReportType::FileMask2NotFoundForVariant(art_set_id, variant_filename, _) => art_set_id.to_owned() + "|" + variant_filename,
-                                    PortraitSettingsDiagnosticReportType::FileMask3NotFoundForVariant(art_set_id, variant_filename, _) => art_set_id.to_owned() + "|" + variant_filename,
-                                };
-
-                                data_affected.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(data_affected_string)), 2);
-                                path.set_text(&QString::from_std_str(diagnostic.path()));
-                                message.set_text(&QString::from_std_str(result.message()));
-                                report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
-
-                                // Set the tooltips to the diag type and description columns.
-                                Self::set_tooltips_portrait_settings(&[&level, &path, &message], result.report_type());
-
-                                qlist.append_q_standard_item(&level.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&diag_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&data_affected.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&path.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&message.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&report_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&extra_data_1.into_ptr().as_mut_raw_ptr());
-
-                                reports.push(atomic_from_cpp_box(qlist));
-                            }
-
-                            reports
+                            let result = &diagnostic.results()[*index];
+                            let data_affected_string = match result.report_type() {
+                                PortraitSettingsDiagnosticReportType::DatacoredPortraitSettings => String::new(),
+                                PortraitSettingsDiagnosticReportType::InvalidArtSetId(art_set_id) => art_set_id.to_owned(),
+                                PortraitSettingsDiagnosticReportType::InvalidVariantFilename(art_set_id, variant_filename) => art_set_id.to_owned() + "|" + variant_filename,
+                                PortraitSettingsDiagnosticReportType::FileDiffuseNotFoundForVariant(art_set_id, variant_filename, _) => art_set_id.to_owned() + "|" + variant_filename,
+                                PortraitSettingsDiagnosticReportType::FileMask1NotFoundForVariant(art_set_id, variant_filename, _) => art_set_id.to_owned() + "|" + variant_filename,
+                                PortraitSettingsDiagnosticReportType::FileMask2NotFoundForVariant(art_set_id, variant_filename, _) => art_set_id.to_owned() + "|" + variant_filename,
+                                PortraitSettingsDiagnosticReportType::FileMask3NotFoundForVariant(art_set_id, variant_filename, _) => art_set_id.to_owned() + "|" + variant_filename,
+                            };
+
+                            data_affected.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(data_affected_string)), 2);
+                            path.set_text(&QString::from_std_str(diagnostic.path()));
+                            message.set_text(&QString::from_std_str(result.message()));
+                            report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
+
+                            // Set the tooltips to the diag type and description columns.
+                            Self::set_tooltips_portrait_settings(&[&level, &path, &message], result.report_type());
+                            result.level()
+                        }
+                        DiagnosticType::Text(ref diagnostic) => {
+                            let result = &diagnostic.results()[*index];
+                            data_affected.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(result.line().to_string())), 2);
+                            path.set_text(&QString::from_std_str(diagnostic.path()));
+                            message.set_text(&QString::from_std_str(result.message()));
+                            report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
+
+                            // Set the tooltips to the diag type and description columns.
+                            Self::set_tooltips_text(&[&level, &path, &message], result.report_type());
+                            result.level()
                         }
                         DiagnosticType::Dependency(ref diagnostic) => {
-                            let mut reports = Vec::with_capacity(diagnostic.results().len());
-
-                            for result in diagnostic.results() {
-                                let qlist = QListOfQStandardItem::new();
-
-                                // Create an empty row.
-                                let level = Self::new_item();
-                                let diag_type = Self::new_item();
-                                let data_affected = Self::new_item();
-                                let path = Self::new_item();
-                                let message = Self::new_item();
-                                let report_type = Self::new_item();
-                                let extra_data_1 = Self::new_item();
-
-                                let (result_type, color) = match result.level() {
-                                    DiagnosticLevel::Info => (ref_from_atomic(&result_type_info), ref_from_atomic(&color_info)),
-                                    DiagnosticLevel::Warning => (ref_from_atomic(&result_type_warning), ref_from_atomic(&color_warning)),
-                                    DiagnosticLevel::Error => (ref_from_atomic(&result_type_error), ref_from_atomic(&color_error)),
-                                };
-
-                                level.set_background(color);
-                                level.set_text(result_type);
-                                diag_type.set_text(&QString::from_std_str(diagnostic_type.to_string()));
-                                data_affected.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(serde_json::to_string(&result.cells_affected()).unwrap())), 2);
-                                path.set_text(&QString::from_std_str(diagnostic.path()));
-                                message.set_text(&QString::from_std_str(result.message()));
-                                report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
-
-                                // Set the tooltips to the diag type and description columns.
-                                Self::set_tooltips_dependency_manager(&[&level, &path, &message], result.report_type());
-
-                                qlist.append_q_standard_item(&level.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&diag_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&data_affected.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&path.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&message.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&report_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&extra_data_1.into_ptr().as_mut_raw_ptr());
-
-                                reports.push(atomic_from_cpp_box(qlist));
-                            }
-
-                            reports
+                            let result = &diagnostic.results()[*index];
+                            data_affected.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(serde_json::to_string(&result.cells_affected()).unwrap())), 2);
+                            path.set_text(&QString::from_std_str(diagnostic.path()));
+                            message.set_text(&QString::from_std_str(result.message()));
+                            report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
+
+                            // Set the tooltips to the diag type and description columns.
+                            Self::set_tooltips_dependency_manager(&[&level, &path, &message], result.report_type());
+                            result.level()
                         }
-
                         DiagnosticType::Config(ref diagnostic) => {
-                            let mut reports = Vec::with_capacity(diagnostic.results().len());
-
-                            for result in diagnostic.results() {
-                                let qlist = QListOfQStandardItem::new();
-
-                                // Create an empty row.
-                                let level = Self::new_item();
-                                let diag_type = Self::new_item();
-                                let data_affected = Self::new_item();
-                                let path = Self::new_item();
-                                let message = Self::new_item();
-                                let report_type = Self::new_item();
-                                let extra_data_1 = Self::new_item();
-
-                                let (result_type, color) = match result.level() {
-                                    DiagnosticLevel::Info => (ref_from_atomic(&result_type_info), ref_from_atomic(&color_info)),
-                                    DiagnosticLevel::Warning => (ref_from_atomic(&result_type_warning), ref_from_atomic(&color_warning)),
-                                    DiagnosticLevel::Error => (ref_from_atomic(&result_type_error), ref_from_atomic(&color_error)),
-                                };
-
-                                level.set_background(color);
-                                level.set_text(result_type);
-                                diag_type.set_text(&QString::from_std_str(diagnostic_type.to_string()));
-                                message.set_text(&QString::from_std_str(result.message()));
-                                report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
-
-                                // Set the tooltips to the diag type and description columns.
-                                Self::set_tooltips_config(&[&level, &path, &message], result.report_type());
-
-                                qlist.append_q_standard_item(&level.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&diag_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&data_affected.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&path.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&message.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&report_type.into_ptr().as_mut_raw_ptr());
-                                qlist.append_q_standard_item(&extra_data_1.into_ptr().as_mut_raw_ptr());
+                            let result = &diagnostic.results()[*index];
+                            message.set_text(&QString::from_std_str(result.message()));
+                            report_type.set_text(&QString::from_std_str(result.report_type().to_string()));
 
-                                reports.push(atomic_from_cpp_box(qlist));
-                            }
-
-                            reports
+                            // Set the tooltips to the diag type and description columns.
+                            Self::set_tooltips_config(&[&level, &path, &message], result.report_type());
+                            result.level()
                         }
-                    }
+                    };
+
+                    let (result_type, color) = match result_level {
+                        DiagnosticLevel::Info => (ref_from_atomic(&result_type_info), ref_from_atomic(&color_info)),
+                        DiagnosticLevel::Warning => (ref_from_atomic(&result_type_warning), ref_from_atomic(&color_warning)),
+                        DiagnosticLevel::Error => (ref_from_atomic(&result_type_error), ref_from_atomic(&color_error)),
+                    };
+
+                    level.set_background(color);
+                    level.set_text(result_type);
+
+                    let snooze_key = Self::snooze_key(&report_type.text().to_std_string(), &path.text().to_std_string(), &data_affected.data_1a(2).to_string().to_std_string());
+                    snoozed.set_text(&QString::from_std_str(if UI_STATE.is_diagnostic_snoozed(&snooze_key) { "1" } else { "0" }));
+
+                    qlist.append_q_standard_item(&level.into_ptr().as_mut_raw_ptr());
+                    qlist.append_q_standard_item(&diag_type.into_ptr().as_mut_raw_ptr());
+                    qlist.append_q_standard_item(&data_affected.into_ptr().as_mut_raw_ptr());
+                    qlist.append_q_standard_item(&path.into_ptr().as_mut_raw_ptr());
+                    qlist.append_q_standard_item(&message.into_ptr().as_mut_raw_ptr());
+                    qlist.append_q_standard_item(&report_type.into_ptr().as_mut_raw_ptr());
+                    qlist.append_q_standard_item(&extra_data_1.into_ptr().as_mut_raw_ptr());
+                    qlist.append_q_standard_item(&snoozed.into_ptr().as_mut_raw_ptr());
+
+                    atomic_from_cpp_box(qlist)
                 })
-                .flatten()
                 .collect::<Vec<_>>();
 
             for (index, row) in rows.iter().enumerate() {
@@ -851,14 +909,14 @@ impl DiagnosticsUI {
                 }
 
                 diagnostics_ui.diagnostics_table_model.append_row_q_list_of_q_standard_item(ref_from_atomic(row));
-
-            }
-
-            // After that, check if the table is open, and paint the results into it.
-            for diagnostic_type in diagnostics {
-                Self::paint_diagnostics_to_table(app_ui, diagnostic_type);
             }
         }
+
+        // Only show the "Load More" button if there are results left to load.
+        let loaded = diagnostics_ui.diagnostics_table_model.row_count_0a() as usize;
+        let total = diagnostics.reports_count();
+        diagnostics_ui.diagnostics_button_load_more.set_visible(loaded < total);
+        diagnostics_ui.diagnostics_button_load_more.set_tool_tip(&qtre("diagnostics_button_load_more", &[&loaded.to_string(), &total.to_string()]));
     }
 
     /// This function tries to open the PackedFile where the selected match is.
@@ -1424,6 +1482,27 @@ impl DiagnosticsUI {
             sensitivity.push(CaseSensitivity::CaseSensitive);
         }
 
+        // Checks for the path and message text filters.
+        let text_sensitivity = if diagnostics_ui.diagnostics_button_filter_case_sensitive.is_checked() {
+            CaseSensitivity::CaseSensitive
+        } else {
+            CaseSensitivity::CaseInsensitive
+        };
+
+        let path_filter = diagnostics_ui.diagnostics_path_filter.text().to_std_string();
+        if !path_filter.is_empty() {
+            columns.push(3);
+            patterns.push(QString::from_std_str(format!("^{}", regex::escape(&path_filter))).into_ptr());
+            sensitivity.push(text_sensitivity);
+        }
+
+        let message_filter = diagnostics_ui.diagnostics_message_filter.text().to_std_string();
+        if !message_filter.is_empty() {
+            columns.push(4);
+            patterns.push(QString::from_std_str(regex::escape(&message_filter)).into_ptr());
+            sensitivity.push(text_sensitivity);
+        }
+
         // Checks for the diagnostic type filter.
         let mut diagnostic_type_pattern = String::new();
 
@@ -1469,6 +1548,9 @@ impl DiagnosticsUI {
         if diagnostics_ui.checkbox_table_name_has_space.is_checked() {
             diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::TableNameHasSpace));
         }
+        if diagnostics_ui.checkbox_table_name_folder_mismatch.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::TableNameFolderMismatch));
+        }
         if diagnostics_ui.checkbox_table_is_datacoring.is_checked() {
             diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::TableIsDataCoring));
         }
@@ -1481,6 +1563,36 @@ impl DiagnosticsUI {
         if diagnostics_ui.checkbox_value_cannot_be_empty.is_checked() {
             diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::ValueCannotBeEmpty(String::new())));
         }
+        if diagnostics_ui.checkbox_unknown_column_in_data.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::UnknownColumnInData(String::new())));
+        }
+        if diagnostics_ui.checkbox_field_fails_validation_pattern.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::FieldFailsValidationPattern(String::new())));
+        }
+        if diagnostics_ui.checkbox_invalid_validation_pattern.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::InvalidValidationPattern(String::new())));
+        }
+        if diagnostics_ui.checkbox_enum_value_not_encodable.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::EnumValueNotEncodable(String::new())));
+        }
+        if diagnostics_ui.checkbox_duplicated_combined_keys_conflicting.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::DuplicatedCombinedKeysConflicting(String::new())));
+        }
+        if diagnostics_ui.checkbox_malformed_loc_formatting.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::MalformedLocFormatting(String::new())));
+        }
+        if diagnostics_ui.checkbox_inconsistent_escaping.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::InconsistentEscaping(String::new())));
+        }
+        if diagnostics_ui.checkbox_empty_column.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::EmptyColumn(String::new())));
+        }
+        if diagnostics_ui.checkbox_self_reference.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::SelfReference(String::new())));
+        }
+        if diagnostics_ui.checkbox_missing_loc_entry.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TableDiagnosticReportType::MissingLocEntry(String::new())));
+        }
 
 
         if diagnostics_ui.checkbox_invalid_dependency_packfile.is_checked() {
@@ -1497,11 +1609,23 @@ impl DiagnosticsUI {
             diagnostic_type_pattern.push_str(&format!("{}|", ConfigDiagnosticReportType::DependenciesCacheCouldNotBeLoaded("".to_owned())));
         }
         if diagnostics_ui.checkbox_incorrect_game_path.is_checked() {
-            diagnostic_type_pattern.push_str(&format!("{}|", ConfigDiagnosticReportType::IncorrectGamePath));
+            diagnostic_type_pattern.push_str(&format!("{}|", ConfigDiagnosticReportType::IncorrectGamePath(String::new())));
+        }
+        if diagnostics_ui.checkbox_schema_reference_cycle.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", ConfigDiagnosticReportType::SchemaReferenceCycle(String::new())));
+        }
+        if diagnostics_ui.checkbox_duplicate_field_name.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", ConfigDiagnosticReportType::DuplicateFieldName(String::new(), String::new())));
+        }
+        if diagnostics_ui.checkbox_invalid_schema_reference.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", ConfigDiagnosticReportType::InvalidSchemaReference(String::new(), String::new())));
         }
 
         if diagnostics_ui.checkbox_invalid_packfile_name.is_checked() {
-            diagnostic_type_pattern.push_str(&format!("{}|", PackDiagnosticReportType::InvalidPackName(String::new())));
+            diagnostic_type_pattern.push_str(&format!("{}|", PackDiagnosticReportType::InvalidPackName(String::new(), String::new())));
+        }
+        if diagnostics_ui.checkbox_file_too_large_to_check.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", PackDiagnosticReportType::FileTooLargeToCheck(String::new())));
         }
 
         if diagnostics_ui.checkbox_datacored_portrait_settings.is_checked() {
@@ -1538,6 +1662,9 @@ impl DiagnosticsUI {
         if diagnostics_ui.checkbox_snd_file_path_not_found.is_checked() {
             diagnostic_type_pattern.push_str(&format!("{}|", AnimFragmentBattleDiagnosticReportType::SndFilePathNotFound(String::new())));
         }
+        if diagnostics_ui.checkbox_referenced_file_not_found.is_checked() {
+            diagnostic_type_pattern.push_str(&format!("{}|", TextDiagnosticReportType::ReferencedFileNotFound(String::new())));
+        }
 
         diagnostic_type_pattern.pop();
 
@@ -1548,6 +1675,11 @@ impl DiagnosticsUI {
         columns.push(5);
         patterns.push(QString::from_std_str(diagnostic_type_pattern).into_ptr());
         sensitivity.push(CaseSensitivity::CaseSensitive);
+
+        // Snoozed results are always hidden.
+        columns.push(7);
+        patterns.push(QString::from_std_str("^0$").into_ptr());
+        sensitivity.push(CaseSensitivity::CaseSensitive);
         let use_nott = vec![false; sensitivity.len()];
         let use_regex = vec![true; sensitivity.len()];
         let show_blank_lines = vec![false; sensitivity.len()];
@@ -1578,6 +1710,10 @@ impl DiagnosticsUI {
                     .iter()
                     .filter(|y| matches!(y.level(), DiagnosticLevel::Info))
                     .count(),
+                DiagnosticType::Text(ref diag) => diag.results()
+                    .iter()
+                    .filter(|y| matches!(y.level(), DiagnosticLevel::Info))
+                    .count(),
                 DiagnosticType::Dependency(ref diag) => diag.results()
                     .iter()
                     .filter(|y| matches!(y.level(), DiagnosticLevel::Info))
@@ -1607,6 +1743,10 @@ impl DiagnosticsUI {
                     .iter()
                     .filter(|y| matches!(y.level(), DiagnosticLevel::Warning))
                     .count(),
+                DiagnosticType::Text(ref diag) => diag.results()
+                    .iter()
+                    .filter(|y| matches!(y.level(), DiagnosticLevel::Warning))
+                    .count(),
                 DiagnosticType::Dependency(ref diag) => diag.results()
                     .iter()
                     .filter(|y| matches!(y.level(), DiagnosticLevel::Warning))
@@ -1637,6 +1777,10 @@ impl DiagnosticsUI {
                     .iter()
                     .filter(|y| matches!(y.level(), DiagnosticLevel::Error))
                     .count(),
+                DiagnosticType::Text(ref diag) => diag.results()
+                    .iter()
+                    .filter(|y| matches!(y.level(), DiagnosticLevel::Error))
+                    .count(),
                 DiagnosticType::Dependency(ref diag) => diag.results()
                     .iter()
                     .filter(|y| matches!(y.level(), DiagnosticLevel::Error))
@@ -1665,6 +1809,16 @@ impl DiagnosticsUI {
         }
     }
 
+    pub unsafe fn set_tooltips_text(items: &[&CppBox<QStandardItem>], report_type: &TextDiagnosticReportType) {
+        let tool_tip = match report_type {
+            TextDiagnosticReportType::ReferencedFileNotFound(_) => qtr("referenced_file_not_found_explanation"),
+        };
+
+        for item in items {
+            item.set_tool_tip(&tool_tip);
+        }
+    }
+
     pub unsafe fn set_tooltips_table(items: &[&CppBox<QStandardItem>], report_type: &TableDiagnosticReportType) {
         let tool_tip = match report_type {
             TableDiagnosticReportType::OutdatedTable => qtr("outdated_table_explanation"),
@@ -1681,10 +1835,21 @@ impl DiagnosticsUI {
             TableDiagnosticReportType::InvalidLocKey => qtr("invalid_loc_key_explanation"),
             TableDiagnosticReportType::TableNameEndsInNumber => qtr("table_name_ends_in_number_explanation"),
             TableDiagnosticReportType::TableNameHasSpace => qtr("table_name_has_space_explanation"),
+            TableDiagnosticReportType::TableNameFolderMismatch => qtr("table_name_folder_mismatch_explanation"),
             TableDiagnosticReportType::TableIsDataCoring => qtr("table_is_datacoring_explanation"),
             TableDiagnosticReportType::FieldWithPathNotFound(_) => qtr("field_with_path_not_found_explanation"),
             TableDiagnosticReportType::BannedTable => qtr("banned_table_explanation"),
             TableDiagnosticReportType::ValueCannotBeEmpty(_) => qtr("value_cannot_be_empty_explanation"),
+            TableDiagnosticReportType::UnknownColumnInData(_) => qtr("unknown_column_in_data_explanation"),
+            TableDiagnosticReportType::FieldFailsValidationPattern(_) => qtr("field_fails_validation_pattern_explanation"),
+            TableDiagnosticReportType::InvalidValidationPattern(_) => qtr("invalid_validation_pattern_explanation"),
+            TableDiagnosticReportType::EnumValueNotEncodable(_) => qtr("enum_value_not_encodable_explanation"),
+            TableDiagnosticReportType::DuplicatedCombinedKeysConflicting(_) => qtr("duplicated_combined_keys_conflicting_explanation"),
+            TableDiagnosticReportType::MalformedLocFormatting(_) => qtr("malformed_loc_formatting_explanation"),
+            TableDiagnosticReportType::InconsistentEscaping(_) => qtr("inconsistent_escaping_explanation"),
+            TableDiagnosticReportType::EmptyColumn(_) => qtr("empty_column_explanation"),
+            TableDiagnosticReportType::SelfReference(_) => qtr("self_reference_explanation"),
+            TableDiagnosticReportType::MissingLocEntry(_) => qtr("missing_loc_entry_explanation"),
         };
 
         for item in items {
@@ -1707,7 +1872,10 @@ impl DiagnosticsUI {
             ConfigDiagnosticReportType::DependenciesCacheNotGenerated => qtr("dependencies_cache_not_generated_explanation"),
             ConfigDiagnosticReportType::DependenciesCacheOutdated => qtr("dependencies_cache_outdated_explanation"),
             ConfigDiagnosticReportType::DependenciesCacheCouldNotBeLoaded(error) => qtre("dependencies_cache_could_not_be_loaded_explanation", &[error]),
-            ConfigDiagnosticReportType::IncorrectGamePath => qtr("incorrect_game_path_explanation"),
+            ConfigDiagnosticReportType::IncorrectGamePath(issue) => qtre("incorrect_game_path_explanation", &[issue]),
+            ConfigDiagnosticReportType::SchemaReferenceCycle(cycle) => qtre("schema_reference_cycle_explanation", &[cycle]),
+            ConfigDiagnosticReportType::DuplicateFieldName(_, _) => qtr("duplicate_field_name_explanation"),
+            ConfigDiagnosticReportType::InvalidSchemaReference(_, _) => qtr("invalid_schema_reference_explanation"),
         };
 
         for item in items {
@@ -1717,7 +1885,8 @@ impl DiagnosticsUI {
 
     pub unsafe fn set_tooltips_packfile(items: &[&CppBox<QStandardItem>], report_type: &PackDiagnosticReportType) {
         let tool_tip = match report_type {
-            PackDiagnosticReportType::InvalidPackName(_) => qtr("invalid_packfile_name_explanation"),
+            PackDiagnosticReportType::InvalidPackName(_, _) => qtr("invalid_packfile_name_explanation"),
+            PackDiagnosticReportType::FileTooLargeToCheck(_) => qtr("file_too_large_to_check_explanation"),
         };
 
         for item in items {
@@ -1741,122 +1910,277 @@ impl DiagnosticsUI {
         }
     }
 
+    /// This function returns the source of the files to check selected in the UI.
+    unsafe fn source(&self) -> SearchSource {
+        match self.diagnostics_source_combobox.current_index() {
+            1 => SearchSource::ParentFiles,
+            2 => SearchSource::GameFiles,
+            _ => SearchSource::Pack,
+        }
+    }
+
     unsafe fn diagnostics_ignored(&self) -> Vec<String> {
+        self.diagnostics_checkboxes()
+            .into_iter()
+            .filter(|(checkbox, _)| !checkbox.is_checked())
+            .map(|(_, report_type)| report_type)
+            .collect()
+    }
 
-        let mut diagnostics_ignored = vec![];
-        if !self.checkbox_outdated_table.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::OutdatedTable.to_string());
-        }
-        if !self.checkbox_invalid_reference.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::InvalidReference(String::new(), String::new()).to_string());
-        }
-        if !self.checkbox_empty_row.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::EmptyRow.to_string());
-        }
-        if !self.checkbox_empty_key_field.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::EmptyKeyField(String::new()).to_string());
-        }
-        if !self.checkbox_empty_key_fields.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::EmptyKeyFields.to_string());
-        }
-        if !self.checkbox_duplicated_combined_keys.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::DuplicatedCombinedKeys(String::new()).to_string());
-        }
-        if !self.checkbox_no_reference_table_found.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::NoReferenceTableFound(String::new()).to_string());
-        }
-        if !self.checkbox_no_reference_table_nor_column_found_pak.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::NoReferenceTableNorColumnFoundPak(String::new()).to_string());
-        }
-        if !self.checkbox_no_reference_table_nor_column_found_no_pak.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::NoReferenceTableNorColumnFoundNoPak(String::new()).to_string());
-        }
-        if !self.checkbox_invalid_escape.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::InvalidEscape.to_string());
-        }
-        if !self.checkbox_duplicated_row.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::DuplicatedRow(String::new()).to_string());
-        }
-        if !self.checkbox_invalid_loc_key.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::InvalidLocKey.to_string());
-        }
-        if !self.checkbox_table_name_ends_in_number.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::TableNameEndsInNumber.to_string());
-        }
-        if !self.checkbox_table_name_has_space.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::TableNameHasSpace.to_string());
-        }
-        if !self.checkbox_table_is_datacoring.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::TableIsDataCoring.to_string());
-        }
-        if !self.checkbox_field_with_path_not_found.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::FieldWithPathNotFound(vec![]).to_string());
-        }
-        if !self.checkbox_banned_table.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::BannedTable.to_string());
-        }
-        if !self.checkbox_value_cannot_be_empty.is_checked() {
-            diagnostics_ignored.push(TableDiagnosticReportType::ValueCannotBeEmpty(String::new()).to_string());
-        }
+    /// This function returns the diagnostic checkboxes, with the report type each one of them controls.
+    unsafe fn diagnostics_checkboxes(&self) -> Vec<(&QBox<QCheckBox>, String)> {
+        vec![
+            (&self.checkbox_outdated_table, TableDiagnosticReportType::OutdatedTable.to_string()),
+            (&self.checkbox_invalid_reference, TableDiagnosticReportType::InvalidReference(String::new(), String::new()).to_string()),
+            (&self.checkbox_empty_row, TableDiagnosticReportType::EmptyRow.to_string()),
+            (&self.checkbox_empty_key_field, TableDiagnosticReportType::EmptyKeyField(String::new()).to_string()),
+            (&self.checkbox_empty_key_fields, TableDiagnosticReportType::EmptyKeyFields.to_string()),
+            (&self.checkbox_duplicated_combined_keys, TableDiagnosticReportType::DuplicatedCombinedKeys(String::new()).to_string()),
+            (&self.checkbox_no_reference_table_found, TableDiagnosticReportType::NoReferenceTableFound(String::new()).to_string()),
+            (&self.checkbox_no_reference_table_nor_column_found_pak, TableDiagnosticReportType::NoReferenceTableNorColumnFoundPak(String::new()).to_string()),
+            (&self.checkbox_no_reference_table_nor_column_found_no_pak, TableDiagnosticReportType::NoReferenceTableNorColumnFoundNoPak(String::new()).to_string()),
+            (&self.checkbox_invalid_escape, TableDiagnosticReportType::InvalidEscape.to_string()),
+            (&self.checkbox_duplicated_row, TableDiagnosticReportType::DuplicatedRow(String::new()).to_string()),
+            (&self.checkbox_invalid_loc_key, TableDiagnosticReportType::InvalidLocKey.to_string()),
+            (&self.checkbox_table_name_ends_in_number, TableDiagnosticReportType::TableNameEndsInNumber.to_string()),
+            (&self.checkbox_table_name_has_space, TableDiagnosticReportType::TableNameHasSpace.to_string()),
+            (&self.checkbox_table_name_folder_mismatch, TableDiagnosticReportType::TableNameFolderMismatch.to_string()),
+            (&self.checkbox_table_is_datacoring, TableDiagnosticReportType::TableIsDataCoring.to_string()),
+            (&self.checkbox_field_with_path_not_found, TableDiagnosticReportType::FieldWithPathNotFound(vec![]).to_string()),
+            (&self.checkbox_banned_table, TableDiagnosticReportType::BannedTable.to_string()),
+            (&self.checkbox_value_cannot_be_empty, TableDiagnosticReportType::ValueCannotBeEmpty(String::new()).to_string()),
+            (&self.checkbox_unknown_column_in_data, TableDiagnosticReportType::UnknownColumnInData(String::new()).to_string()),
+            (&self.checkbox_field_fails_validation_pattern, TableDiagnosticReportType::FieldFailsValidationPattern(String::new()).to_string()),
+            (&self.checkbox_invalid_validation_pattern, TableDiagnosticReportType::InvalidValidationPattern(String::new()).to_string()),
+            (&self.checkbox_enum_value_not_encodable, TableDiagnosticReportType::EnumValueNotEncodable(String::new()).to_string()),
+            (&self.checkbox_duplicated_combined_keys_conflicting, TableDiagnosticReportType::DuplicatedCombinedKeysConflicting(String::new()).to_string()),
+            (&self.checkbox_malformed_loc_formatting, TableDiagnosticReportType::MalformedLocFormatting(String::new()).to_string()),
+            (&self.checkbox_inconsistent_escaping, TableDiagnosticReportType::InconsistentEscaping(String::new()).to_string()),
+            (&self.checkbox_empty_column, TableDiagnosticReportType::EmptyColumn(String::new()).to_string()),
+            (&self.checkbox_self_reference, TableDiagnosticReportType::SelfReference(String::new()).to_string()),
+            (&self.checkbox_missing_loc_entry, TableDiagnosticReportType::MissingLocEntry(String::new()).to_string()),
+
+            (&self.checkbox_invalid_dependency_packfile, DependencyDiagnosticReportType::InvalidDependencyPackName(String::new()).to_string()),
+
+            (&self.checkbox_dependencies_cache_not_generated, ConfigDiagnosticReportType::DependenciesCacheNotGenerated.to_string()),
+            (&self.checkbox_dependencies_cache_outdated, ConfigDiagnosticReportType::DependenciesCacheOutdated.to_string()),
+            (&self.checkbox_dependencies_cache_could_not_be_loaded, ConfigDiagnosticReportType::DependenciesCacheCouldNotBeLoaded(String::new()).to_string()),
+            (&self.checkbox_incorrect_game_path, ConfigDiagnosticReportType::IncorrectGamePath(String::new()).to_string()),
+            (&self.checkbox_schema_reference_cycle, ConfigDiagnosticReportType::SchemaReferenceCycle(String::new()).to_string()),
+            (&self.checkbox_duplicate_field_name, ConfigDiagnosticReportType::DuplicateFieldName(String::new(), String::new()).to_string()),
+            (&self.checkbox_invalid_schema_reference, ConfigDiagnosticReportType::InvalidSchemaReference(String::new(), String::new()).to_string()),
+
+            (&self.checkbox_invalid_packfile_name, PackDiagnosticReportType::InvalidPackName(String::new(), String::new()).to_string()),
+            (&self.checkbox_file_too_large_to_check, PackDiagnosticReportType::FileTooLargeToCheck(String::new()).to_string()),
+
+            (&self.checkbox_datacored_portrait_settings, PortraitSettingsDiagnosticReportType::DatacoredPortraitSettings.to_string()),
+            (&self.checkbox_invalid_art_set_id, PortraitSettingsDiagnosticReportType::InvalidArtSetId(String::new()).to_string()),
+            (&self.checkbox_invalid_variant_filename, PortraitSettingsDiagnosticReportType::InvalidVariantFilename(String::new(), String::new()).to_string()),
+            (&self.checkbox_file_diffuse_not_found_for_variant, PortraitSettingsDiagnosticReportType::FileDiffuseNotFoundForVariant(String::new(), String::new(), String::new()).to_string()),
+
+            (&self.checkbox_file_mask_1_not_found_for_variant, PortraitSettingsDiagnosticReportType::FileMask1NotFoundForVariant(String::new(), String::new(), String::new()).to_string()),
+            (&self.checkbox_file_mask_2_not_found_for_variant, PortraitSettingsDiagnosticReportType::FileMask2NotFoundForVariant(String::new(), String::new(), String::new()).to_string()),
+            (&self.checkbox_file_mask_3_not_found_for_variant, PortraitSettingsDiagnosticReportType::FileMask3NotFoundForVariant(String::new(), String::new(), String::new()).to_string()),
+
+            (&self.checkbox_loocomotion_graph_path_not_found, AnimFragmentBattleDiagnosticReportType::LocomotionGraphPathNotFound(String::new()).to_string()),
+            (&self.checkbox_file_path_not_found, AnimFragmentBattleDiagnosticReportType::FilePathNotFound(String::new()).to_string()),
+            (&self.checkbox_meta_file_path_not_found, AnimFragmentBattleDiagnosticReportType::MetaFilePathNotFound(String::new()).to_string()),
+            (&self.checkbox_snd_file_path_not_found, AnimFragmentBattleDiagnosticReportType::SndFilePathNotFound(String::new()).to_string()),
+            (&self.checkbox_referenced_file_not_found, TextDiagnosticReportType::ReferencedFileNotFound(String::new()).to_string()),
+        ]
+    }
 
-        if !self.checkbox_invalid_dependency_packfile.is_checked() {
-            diagnostics_ignored.push(DependencyDiagnosticReportType::InvalidDependencyPackName(String::new()).to_string());
+    /// This function applies the diagnostic types ignored for the current game, as saved in the settings, to the checkboxes.
+    ///
+    /// Diagnostic types that were not known when the list was saved use their default state. This must be called again when
+    /// the game selected changes, followed by a filter of the results, as the signals of the checkboxes are blocked while loading.
+    pub unsafe fn load_diagnostics_ignored(&self) {
+        let (ignored, known) = diagnostics_ignored(GAME_SELECTED.read().unwrap().key());
+        for (checkbox, report_type) in self.diagnostics_checkboxes() {
+
+            // Block the signals, so loading doesn't save the half-loaded state on each change.
+            let _blocker = QSignalBlocker::from_q_object(checkbox.static_upcast::<QObject>());
+            if ignored.contains(&report_type) {
+                checkbox.set_checked(false);
+            } else if known.contains(&report_type) {
+                checkbox.set_checked(true);
+            } else {
+                checkbox.set_checked(self.diagnostics_checked_by_default.contains(&report_type));
+            }
         }
 
-        if !self.checkbox_dependencies_cache_not_generated.is_checked() {
-            diagnostics_ignored.push(ConfigDiagnosticReportType::DependenciesCacheNotGenerated.to_string());
-        }
-        if !self.checkbox_dependencies_cache_outdated.is_checked() {
-            diagnostics_ignored.push(ConfigDiagnosticReportType::DependenciesCacheOutdated.to_string());
-        }
-        if !self.checkbox_dependencies_cache_could_not_be_loaded.is_checked() {
-            diagnostics_ignored.push(ConfigDiagnosticReportType::DependenciesCacheCouldNotBeLoaded(String::new()).to_string());
-        }
-        if !self.checkbox_incorrect_game_path.is_checked() {
-            diagnostics_ignored.push(ConfigDiagnosticReportType::IncorrectGamePath.to_string());
-        }
+        // Keep the "All" checkbox in sync with the loaded state.
+        let all_checked = self.diagnostics_checkboxes().iter().all(|(checkbox, _)| checkbox.is_checked());
+        let _blocker = QSignalBlocker::from_q_object(self.checkbox_all.static_upcast::<QObject>());
+        self.checkbox_all.set_checked(all_checked);
+    }
 
-        if !self.checkbox_invalid_packfile_name.is_checked() {
-            diagnostics_ignored.push(PackDiagnosticReportType::InvalidPackName(String::new()).to_string());
-        }
+    /// This function saves the diagnostic types ignored for the current game to the settings.
+    pub unsafe fn save_diagnostics_ignored(&self) {
+        let known = self.diagnostics_checkboxes()
+            .into_iter()
+            .map(|(_, report_type)| report_type)
+            .collect::<Vec<_>>();
 
-        if !self.checkbox_datacored_portrait_settings.is_checked() {
-            diagnostics_ignored.push(PortraitSettingsDiagnosticReportType::DatacoredPortraitSettings.to_string());
-        }
-        if !self.checkbox_invalid_art_set_id.is_checked() {
-            diagnostics_ignored.push(PortraitSettingsDiagnosticReportType::InvalidArtSetId(String::new()).to_string());
-        }
-        if !self.checkbox_invalid_variant_filename.is_checked() {
-            diagnostics_ignored.push(PortraitSettingsDiagnosticReportType::InvalidVariantFilename(String::new(), String::new()).to_string());
-        }
-        if !self.checkbox_file_diffuse_not_found_for_variant.is_checked() {
-            diagnostics_ignored.push(PortraitSettingsDiagnosticReportType::FileDiffuseNotFoundForVariant(String::new(), String::new(), String::new()).to_string());
-        }
+        set_diagnostics_ignored(GAME_SELECTED.read().unwrap().key(), &self.diagnostics_ignored(), &known);
+    }
 
-        if !self.checkbox_file_mask_1_not_found_for_variant.is_checked() {
-            diagnostics_ignored.push(PortraitSettingsDiagnosticReportType::FileMask1NotFoundForVariant(String::new(), String::new(), String::new()).to_string());
-        }
-        if !self.checkbox_file_mask_2_not_found_for_variant.is_checked() {
-            diagnostics_ignored.push(PortraitSettingsDiagnosticReportType::FileMask2NotFoundForVariant(String::new(), String::new(), String::new()).to_string());
+    /// This function snoozes the selected diagnostic results until the Pack is changed.
+    pub unsafe fn snooze_selection(app_ui: &Rc<AppUI>, diagnostics_ui: &Rc<Self>) {
+        let selection = diagnostics_ui.selection_sorted_and_deduped();
+        let mut keys = Vec::with_capacity(selection.len());
+
+        for index in &selection {
+            let model = index.model();
+            let report_type = model.index_2a(index.row(), 5).data_0a().to_string().to_std_string();
+            let path = model.index_2a(index.row(), 3).data_0a().to_string().to_std_string();
+            let cells_affected = model.index_2a(index.row(), 2).data_1a(2).to_string().to_std_string();
+            keys.push(Self::snooze_key(&report_type, &path, &cells_affected));
+
+            diagnostics_ui.diagnostics_table_model.item_2a(index.row(), 7).set_text(&QString::from_std_str("1"));
         }
-        if !self.checkbox_file_mask_3_not_found_for_variant.is_checked() {
-            diagnostics_ignored.push(PortraitSettingsDiagnosticReportType::FileMask3NotFoundForVariant(String::new(), String::new(), String::new()).to_string());
+
+        UI_STATE.snooze_diagnostics(&keys);
+        Self::filter(app_ui, diagnostics_ui);
+    }
+
+    /// This function saves a Pack with the context of the selected diagnostic report, so it can be attached to a bug report.
+    pub unsafe fn export_reproducer(diagnostics_ui: &Rc<Self>) {
+        let selection = diagnostics_ui.selection_sorted_and_deduped();
+        let index = match selection.first() {
+            Some(index) => index,
+            None => return,
+        };
+
+        let position = index.model().index_2a(index.row(), 0).data_1a(REPORT_POSITION).to_u_long_long_0a() as usize;
+        let diagnostics = UI_STATE.get_diagnostics();
+        let (diagnostic, report_index) = match diagnostics.iter_results().nth(position) {
+            Some((diagnostic, report_index)) => (diagnostic.clone(), report_index),
+            None => return,
+        };
+
+        let file_dialog = QFileDialog::from_q_widget_q_string(&diagnostics_ui.diagnostics_table_view, &qtr("diagnostics_export_reproducer"));
+        file_dialog.set_accept_mode(AcceptMode::AcceptSave);
+        file_dialog.set_confirm_overwrite(true);
+        file_dialog.set_name_filter(&QString::from_std_str("Packs (*.pack)"));
+        file_dialog.set_default_suffix(&QString::from_std_str("pack"));
+        file_dialog.select_file(&QString::from_std_str(REPRODUCER_PACK_NAME));
+
+        if file_dialog.exec() == 1 {
+            let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+            let receiver = CENTRAL_COMMAND.send_background(Command::ExportDiagnosticReproducer(diagnostic, report_index, path));
+            let response = CentralCommand::recv(&receiver);
+            match response {
+                Response::Success => {},
+                Response::Error(error) => show_dialog(&diagnostics_ui.diagnostics_table_view, error, false),
+                _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+            }
         }
+    }
 
-        if !self.checkbox_loocomotion_graph_path_not_found.is_checked() {
-            diagnostics_ignored.push(AnimFragmentBattleDiagnosticReportType::LocomotionGraphPathNotFound(String::new()).to_string());
+    /// This function exports the results of the last check to a JSON or CSV file, depending on the extension chosen.
+    ///
+    /// Only the reports visible with the current level buttons and diagnostic checkboxes are exported.
+    pub unsafe fn export_results(diagnostics_ui: &Rc<Self>) {
+        let info_state = diagnostics_ui.diagnostics_button_info.is_checked();
+        let warning_state = diagnostics_ui.diagnostics_button_warning.is_checked();
+        let error_state = diagnostics_ui.diagnostics_button_error.is_checked();
+        let diagnostics_ignored = diagnostics_ui.diagnostics_ignored();
+
+        let mut diagnostics = UI_STATE.get_diagnostics();
+        diagnostics.retain_reports(|level, report_type| {
+            let level_enabled = match level {
+                DiagnosticLevel::Info => info_state,
+                DiagnosticLevel::Warning => warning_state,
+                DiagnosticLevel::Error => error_state,
+            };
+
+            level_enabled && !diagnostics_ignored.iter().any(|ignored| ignored == report_type)
+        });
+
+        let file_dialog = QFileDialog::from_q_widget_q_string(&diagnostics_ui.diagnostics_table_view, &qtr("diagnostics_export_results"));
+        file_dialog.set_accept_mode(AcceptMode::AcceptSave);
+        file_dialog.set_confirm_overwrite(true);
+        file_dialog.set_name_filter(&QString::from_std_str("JSON (*.json);;CSV (*.csv)"));
+        file_dialog.set_default_suffix(&QString::from_std_str("json"));
+
+        if file_dialog.exec() == 1 {
+            let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
+            let is_csv = path.extension().map(|extension| extension.eq_ignore_ascii_case("csv")).unwrap_or(false);
+            let data = if is_csv {
+                Ok(diagnostics.to_csv())
+            } else {
+                diagnostics.json()
+            };
+
+            match data {
+                Ok(data) => if let Err(error) = std::fs::write(&path, data) {
+                    show_dialog(&diagnostics_ui.diagnostics_table_view, error, false);
+                },
+                Err(error) => show_dialog(&diagnostics_ui.diagnostics_table_view, error, false),
+            }
         }
-        if !self.checkbox_file_path_not_found.is_checked() {
-            diagnostics_ignored.push(AnimFragmentBattleDiagnosticReportType::FilePathNotFound(String::new()).to_string());
+    }
+
+    /// This function copies the selected diagnostic reports into the clipboard as TSV, one report per line, with their affected data in a readable form.
+    pub unsafe fn copy_selection_as_tsv(diagnostics_ui: &Rc<Self>) {
+        let mut positions = diagnostics_ui.selection_sorted_and_deduped()
+            .iter()
+            .map(|index| index.model().index_2a(index.row(), 0).data_1a(REPORT_POSITION).to_u_long_long_0a() as usize)
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+
+        // Positions are sorted, so we can get all the reports in a single pass over the results.
+        let diagnostics = UI_STATE.get_diagnostics();
+        let lines = diagnostics.iter_results()
+            .enumerate()
+            .filter(|(position, _)| positions.binary_search(position).is_ok())
+            .filter_map(|(_, (diagnostic, report_index))| diagnostic.report_to_tsv(report_index))
+            .collect::<Vec<_>>();
+
+        if !lines.is_empty() {
+            QGuiApplication::clipboard().set_text_1a(&QString::from_std_str(lines.join("\n")));
         }
-        if !self.checkbox_meta_file_path_not_found.is_checked() {
-            diagnostics_ignored.push(AnimFragmentBattleDiagnosticReportType::MetaFilePathNotFound(String::new()).to_string());
+    }
+
+    /// This function removes the conflicting duplicated combined keys of the rows in the selected reports, keeping either the first or the last row of each group.
+    ///
+    /// Rows not in the selected reports are left untouched, even if they're duplicated.
+    pub unsafe fn fix_duplicated_combined_keys(app_ui: &Rc<AppUI>, pack_file_contents_ui: &Rc<PackFileContentsUI>, diagnostics_ui: &Rc<Self>, keep_last: bool) {
+        let selection = diagnostics_ui.selection_sorted_and_deduped();
+        let mut rows_by_path: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for index in &selection {
+            let path = index.model().index_2a(index.row(), 3).data_0a().to_string().to_std_string();
+            let cells_affected: Vec<(i32, i32)> = serde_json::from_str(&index.model().index_2a(index.row(), 2).data_1a(2).to_string().to_std_string()).unwrap_or_default();
+            let rows = rows_by_path.entry(path).or_default();
+            rows.extend(cells_affected.iter().filter(|(row, _)| *row >= 0).map(|(row, _)| *row as usize));
+            rows.sort_unstable();
+            rows.dedup();
+        }
+
+        let mut modified = vec![];
+        for (path, rows) in rows_by_path {
+            AppUI::purge_that_one_specifically(app_ui, pack_file_contents_ui, &path, DataSource::PackFile, true);
+
+            let receiver = CENTRAL_COMMAND.send_background(Command::RemoveDuplicatedCombinedKeys(path.to_owned(), rows, keep_last));
+            let response = CentralCommand::recv(&receiver);
+            match response {
+                Response::Success => modified.push(ContainerPath::File(path)),
+                Response::Error(error) => show_dialog(&diagnostics_ui.diagnostics_table_view, error, false),
+                _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+            }
         }
-        if !self.checkbox_snd_file_path_not_found.is_checked() {
-            diagnostics_ignored.push(AnimFragmentBattleDiagnosticReportType::SndFilePathNotFound(String::new()).to_string());
+
+        if !modified.is_empty() {
+            pack_file_contents_ui.packfile_contents_tree_view.update_treeview(true, TreeViewOperation::Modify(modified.clone()), DataSource::PackFile);
+            UI_STATE.set_is_modified(true, app_ui, pack_file_contents_ui);
+            Self::check_on_path(app_ui, diagnostics_ui, modified);
         }
+    }
 
-        diagnostics_ignored
+    /// This function returns the key used to identify a diagnostic result when snoozing it.
+    fn snooze_key(report_type: &str, path: &str, cells_affected: &str) -> String {
+        format!("{report_type}|{path}|{cells_affected}")
     }
 
     pub unsafe fn selection_sorted_and_deduped(&self) -> Vec<CppBox<QModelIndex>> {
@@ -19,7 +19,7 @@ use qt_gui::QCursor;
 use qt_core::QBox;
 use qt_core::QObject;
 use qt_core::QSignalBlocker;
-use qt_core::{SlotNoArgs, SlotOfBool, SlotOfQModelIndex};
+use qt_core::{SlotNoArgs, SlotOfBool, SlotOfQModelIndex, SlotOfQString};
 
 use getset::Getters;
 
@@ -51,6 +51,8 @@ pub struct DiagnosticsUISlots {
     diagnostics_check_packfile: QBox<SlotNoArgs>,
     diagnostics_check_currently_open_packed_file: QBox<SlotNoArgs>,
     diagnostics_open_result: QBox<SlotOfQModelIndex>,
+    diagnostics_load_more: QBox<SlotNoArgs>,
+    export_results: QBox<SlotNoArgs>,
     contextual_menu: QBox<SlotOfQPoint>,
     contextual_menu_enabler: QBox<SlotNoArgs>,
     ignore_parent_folder: QBox<SlotNoArgs>,
@@ -62,9 +64,16 @@ pub struct DiagnosticsUISlots {
     ignore_diagnostic_for_file: QBox<SlotNoArgs>,
     ignore_diagnostic_for_file_field: QBox<SlotNoArgs>,
     ignore_diagnostic_for_pack: QBox<SlotNoArgs>,
+    snooze_diagnostic: QBox<SlotNoArgs>,
+    export_reproducer: QBox<SlotNoArgs>,
+    copy_as_tsv: QBox<SlotNoArgs>,
+    fix_keep_first: QBox<SlotNoArgs>,
+    fix_keep_last: QBox<SlotNoArgs>,
     show_hide_extra_filters: QBox<SlotOfBool>,
     toggle_filters: QBox<SlotOfBool>,
     toggle_filters_all: QBox<SlotOfBool>,
+    filter_change_text: QBox<SlotOfQString>,
+    filter_change_case_sensitive: QBox<SlotOfBool>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -121,6 +130,20 @@ impl DiagnosticsUISlots {
             }
         ));
 
+        let diagnostics_load_more = SlotNoArgs::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
+            diagnostics_ui => move || {
+                info!("Triggering `Load More Diagnostics` By Slot");
+                DiagnosticsUI::load_diagnostics_page(&diagnostics_ui, &UI_STATE.get_diagnostics());
+            }
+        ));
+
+        let export_results = SlotNoArgs::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
+            diagnostics_ui => move || {
+                info!("Triggering `Export Diagnostics Results` By Slot");
+                DiagnosticsUI::export_results(&diagnostics_ui);
+            }
+        ));
+
         let contextual_menu = SlotOfQPoint::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
             diagnostics_ui => move |_| {
             diagnostics_ui.diagnostics_table_view_context_menu.exec_1a_mut(&QCursor::pos_0a());
@@ -141,6 +164,9 @@ impl DiagnosticsUISlots {
                     "DependenciesCacheOutdated",
                     "DependenciesCacheCouldNotBeLoaded",
                     "IncorrectGamePath",
+                    "SchemaReferenceCycle",
+                    "DuplicateFieldName",
+                    "InvalidSchemaReference",
                     "InvalidPackName"
                 ];
 
@@ -160,6 +186,16 @@ impl DiagnosticsUISlots {
 
                 // This one is enabled as long as there is a selection.
                 diagnostics_ui.ignore_diagnostic_for_pack.set_enabled(!selection.is_empty() && can_be_ignored);
+                diagnostics_ui.snooze_diagnostic.set_enabled(!selection.is_empty());
+
+                // Reproducers are for a single report.
+                diagnostics_ui.export_reproducer.set_enabled(selection.len() == 1);
+                diagnostics_ui.copy_as_tsv.set_enabled(!selection.is_empty());
+
+                // Quick fixes are only available if all the selected reports can be fixed by them.
+                let can_fix_duplicated_keys = selection.iter().all(|index| index.model().index_2a(index.row(), 5).data_0a().to_string().to_std_string() == "DuplicatedCombinedKeysConflicting");
+                diagnostics_ui.fix_keep_first.set_enabled(!selection.is_empty() && can_fix_duplicated_keys);
+                diagnostics_ui.fix_keep_last.set_enabled(!selection.is_empty() && can_fix_duplicated_keys);
             }
         ));
 
@@ -371,6 +407,46 @@ impl DiagnosticsUISlots {
             }
         ));
 
+        let snooze_diagnostic = SlotNoArgs::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
+            app_ui,
+            diagnostics_ui => move || {
+                info!("Triggering `Snooze Diagnostic` By Slot");
+                DiagnosticsUI::snooze_selection(&app_ui, &diagnostics_ui);
+            }
+        ));
+
+        let export_reproducer = SlotNoArgs::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
+            diagnostics_ui => move || {
+                info!("Triggering `Export Reproducer` By Slot");
+                DiagnosticsUI::export_reproducer(&diagnostics_ui);
+            }
+        ));
+
+        let copy_as_tsv = SlotNoArgs::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
+            diagnostics_ui => move || {
+                info!("Triggering `Copy as TSV` By Slot");
+                DiagnosticsUI::copy_selection_as_tsv(&diagnostics_ui);
+            }
+        ));
+
+        let fix_keep_first = SlotNoArgs::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
+            app_ui,
+            pack_file_contents_ui,
+            diagnostics_ui => move || {
+                info!("Triggering `Fix Keep First` By Slot");
+                DiagnosticsUI::fix_duplicated_combined_keys(&app_ui, &pack_file_contents_ui, &diagnostics_ui, false);
+            }
+        ));
+
+        let fix_keep_last = SlotNoArgs::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
+            app_ui,
+            pack_file_contents_ui,
+            diagnostics_ui => move || {
+                info!("Triggering `Fix Keep Last` By Slot");
+                DiagnosticsUI::fix_duplicated_combined_keys(&app_ui, &pack_file_contents_ui, &diagnostics_ui, true);
+            }
+        ));
+
         let show_hide_extra_filters = SlotOfBool::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
             diagnostics_ui => move |state| {
                 if !state { diagnostics_ui.sidebar_scroll_area.hide(); }
@@ -389,6 +465,7 @@ impl DiagnosticsUISlots {
                 diagnostics_ui.checkbox_all.block_signals(false);
             }
 
+            diagnostics_ui.save_diagnostics_ignored();
             DiagnosticsUI::filter(&app_ui, &diagnostics_ui);
         }));
 
@@ -432,6 +509,22 @@ impl DiagnosticsUISlots {
                 let _blocker_32 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_file_path_not_found.static_upcast::<QObject>());
                 let _blocker_33 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_meta_file_path_not_found.static_upcast::<QObject>());
                 let _blocker_34 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_snd_file_path_not_found.static_upcast::<QObject>());
+                let _blocker_35 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_file_too_large_to_check.static_upcast::<QObject>());
+                let _blocker_36 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_unknown_column_in_data.static_upcast::<QObject>());
+                let _blocker_37 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_field_fails_validation_pattern.static_upcast::<QObject>());
+                let _blocker_38 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_invalid_validation_pattern.static_upcast::<QObject>());
+                let _blocker_39 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_schema_reference_cycle.static_upcast::<QObject>());
+                let _blocker_40 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_referenced_file_not_found.static_upcast::<QObject>());
+                let _blocker_41 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_duplicate_field_name.static_upcast::<QObject>());
+                let _blocker_42 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_table_name_folder_mismatch.static_upcast::<QObject>());
+                let _blocker_43 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_enum_value_not_encodable.static_upcast::<QObject>());
+                let _blocker_44 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_duplicated_combined_keys_conflicting.static_upcast::<QObject>());
+                let _blocker_45 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_malformed_loc_formatting.static_upcast::<QObject>());
+                let _blocker_46 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_inconsistent_escaping.static_upcast::<QObject>());
+                let _blocker_47 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_empty_column.static_upcast::<QObject>());
+                let _blocker_48 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_self_reference.static_upcast::<QObject>());
+                let _blocker_49 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_missing_loc_entry.static_upcast::<QObject>());
+                let _blocker_50 = QSignalBlocker::from_q_object(diagnostics_ui.checkbox_invalid_schema_reference.static_upcast::<QObject>());
 
                 if toggled {
                     diagnostics_ui.checkbox_outdated_table.set_checked(true);
@@ -469,8 +562,39 @@ impl DiagnosticsUISlots {
                     diagnostics_ui.checkbox_file_path_not_found.set_checked(true);
                     diagnostics_ui.checkbox_meta_file_path_not_found.set_checked(true);
                     diagnostics_ui.checkbox_snd_file_path_not_found.set_checked(true);
+                    diagnostics_ui.checkbox_file_too_large_to_check.set_checked(true);
+                    diagnostics_ui.checkbox_unknown_column_in_data.set_checked(true);
+                    diagnostics_ui.checkbox_field_fails_validation_pattern.set_checked(true);
+                    diagnostics_ui.checkbox_invalid_validation_pattern.set_checked(true);
+                    diagnostics_ui.checkbox_schema_reference_cycle.set_checked(true);
+                    diagnostics_ui.checkbox_referenced_file_not_found.set_checked(true);
+                    diagnostics_ui.checkbox_duplicate_field_name.set_checked(true);
+                    diagnostics_ui.checkbox_table_name_folder_mismatch.set_checked(true);
+                    diagnostics_ui.checkbox_enum_value_not_encodable.set_checked(true);
+                    diagnostics_ui.checkbox_duplicated_combined_keys_conflicting.set_checked(true);
+                    diagnostics_ui.checkbox_malformed_loc_formatting.set_checked(true);
+                    diagnostics_ui.checkbox_inconsistent_escaping.set_checked(true);
+                    diagnostics_ui.checkbox_empty_column.set_checked(true);
+                    diagnostics_ui.checkbox_self_reference.set_checked(true);
+                    diagnostics_ui.checkbox_missing_loc_entry.set_checked(true);
+                    diagnostics_ui.checkbox_invalid_schema_reference.set_checked(true);
                 }
 
+                diagnostics_ui.save_diagnostics_ignored();
+                DiagnosticsUI::filter(&app_ui, &diagnostics_ui);
+            }
+        ));
+
+        let filter_change_text = SlotOfQString::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
+            app_ui,
+            diagnostics_ui => move |_| {
+                DiagnosticsUI::filter(&app_ui, &diagnostics_ui);
+            }
+        ));
+
+        let filter_change_case_sensitive = SlotOfBool::new(&diagnostics_ui.diagnostics_dock_widget, clone!(
+            app_ui,
+            diagnostics_ui => move |_| {
                 DiagnosticsUI::filter(&app_ui, &diagnostics_ui);
             }
         ));
@@ -480,6 +604,8 @@ impl DiagnosticsUISlots {
             diagnostics_check_packfile,
             diagnostics_check_currently_open_packed_file,
             diagnostics_open_result,
+            diagnostics_load_more,
+            export_results,
             contextual_menu,
             contextual_menu_enabler,
             ignore_parent_folder,
@@ -491,9 +617,16 @@ impl DiagnosticsUISlots {
             ignore_diagnostic_for_file,
             ignore_diagnostic_for_file_field,
             ignore_diagnostic_for_pack,
+            snooze_diagnostic,
+            export_reproducer,
+            copy_as_tsv,
+            fix_keep_first,
+            fix_keep_last,
             show_hide_extra_filters,
             toggle_filters,
             toggle_filters_all,
+            filter_change_text,
+            filter_change_case_sensitive,
         }
     }
 }
@@ -90,11 +90,11 @@ pub fn new_combobox_item_delegate_safe(table_view: &Ptr<QObject>, column: i32, l
 }
 
 // This function changes the default editor widget for I32 cells on tables with a numeric one.
-extern "C" { fn new_spinbox_item_delegate(table_view: *mut QObject, column: i32, integer_type: i32, timer: *mut QTimer, is_dark_theme_enabled: bool, has_filter: bool, is_right_side_mark_enabled: bool); }
-pub fn new_spinbox_item_delegate_safe(table_view: &Ptr<QObject>, column: i32, integer_type: i32, timer: &Ptr<QTimer>, has_filter: bool) {
+extern "C" { fn new_spinbox_item_delegate(table_view: *mut QObject, column: i32, integer_type: i32, is_unsigned: bool, timer: *mut QTimer, is_dark_theme_enabled: bool, has_filter: bool, is_right_side_mark_enabled: bool); }
+pub fn new_spinbox_item_delegate_safe(table_view: &Ptr<QObject>, column: i32, integer_type: i32, is_unsigned: bool, timer: &Ptr<QTimer>, has_filter: bool) {
     let is_dark_theme_enabled = setting_bool("use_dark_theme");
     let is_right_side_mark_enabled = setting_bool("use_right_size_markers");
-    unsafe { new_spinbox_item_delegate(table_view.as_mut_raw_ptr(), column, integer_type, timer.as_mut_raw_ptr(), is_dark_theme_enabled, has_filter, is_right_side_mark_enabled) }
+    unsafe { new_spinbox_item_delegate(table_view.as_mut_raw_ptr(), column, integer_type, is_unsigned, timer.as_mut_raw_ptr(), is_dark_theme_enabled, has_filter, is_right_side_mark_enabled) }
 }
 
 // This function changes the default editor widget for F32 cells on tables with a numeric one.
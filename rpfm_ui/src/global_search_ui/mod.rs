@@ -20,6 +20,7 @@ use qt_widgets::QComboBox;
 use qt_widgets::QDockWidget;
 use qt_widgets::QGroupBox;
 use qt_widgets::QLineEdit;
+use qt_widgets::QListView;
 use qt_widgets::QMainWindow;
 use qt_widgets::QRadioButton;
 use qt_widgets::QTabWidget;
@@ -54,14 +55,15 @@ use rayon::prelude::*;
 
 use std::rc::Rc;
 
-use rpfm_extensions::search::{GlobalSearch, MatchHolder,
+use rpfm_extensions::search::{GlobalSearch, MatchHolder, parse_hex_pattern,
     anim_fragment_battle::{AnimFragmentBattleMatches, AnimFragmentBattleMatch},
     atlas::{AtlasMatches, AtlasMatch},
+    esf::EsfMatches,
     portrait_settings::{PortraitSettingsMatches, PortraitSettingsMatch},
     rigid_model::{RigidModelMatches, RigidModelMatch},
     SearchSource,
     schema::SchemaMatches,
-    table::{TableMatches, TableMatch},
+    table::{TableMatches, TableMatch, TableMatchGranularity},
     text::{TextMatches, TextMatch},
     unit_variant::{UnitVariantMatches, UnitVariantMatch},
     unknown::{UnknownMatches, UnknownMatch}
@@ -78,6 +80,7 @@ use crate::communications::{CentralCommand, Command, Response};
 use crate::dependencies_ui::DependenciesUI;
 use crate::diagnostics_ui::DiagnosticsUI;
 use crate::ffi::{kline_edit_configure_safe, new_treeview_filter_safe, scroll_to_row_safe, trigger_treeview_filter_safe};
+use crate::GAME_SELECTED;
 use crate::packfile_contents_ui::PackFileContentsUI;
 use crate::pack_tree::{PackTree, TreeViewOperation};
 use crate::packedfile_views::{DataSource, View, ViewType};
@@ -108,6 +111,8 @@ const UNIT_VARIANT_ENTRY_INDEX: i32 = 40;
 const UNIT_VARIANT_BOOL_DATA: i32 = 41;
 const UNIT_VARIANT_VARIANT_INDEX: i32 = 42;
 
+const TABLE_MATCH_ROW_COLUMNS: i32 = 40;
+
 //const MATCH_TEXT_START: i32 = 45;
 //const MATCH_TEXT_END: i32 = 46;
 
@@ -130,6 +135,11 @@ pub struct GlobalSearchUI {
     replace_button: QPtr<QToolButton>,
     replace_all_button: QPtr<QToolButton>,
     use_regex_checkbox: QPtr<QToolButton>,
+    use_hex_checkbox: QPtr<QToolButton>,
+    whole_word_checkbox: QPtr<QToolButton>,
+    key_columns_only_checkbox: QPtr<QToolButton>,
+    favorites_only_checkbox: QPtr<QToolButton>,
+    row_matches_checkbox: QPtr<QToolButton>,
 
     search_source_packfile: QPtr<QRadioButton>,
     search_source_parent: QPtr<QRadioButton>,
@@ -166,9 +176,11 @@ pub struct GlobalSearchUI {
 
     matches_table_and_text_tree_view: QPtr<QTreeView>,
     matches_schema_tree_view: QPtr<QTreeView>,
+    matches_skipped_list_view: QPtr<QListView>,
 
     matches_table_and_text_tree_model: QBox<QStandardItemModel>,
     matches_schema_tree_model: QBox<QStandardItemModel>,
+    matches_skipped_list_model: QBox<QStandardItemModel>,
 
     matches_filter_table_and_text_line_edit: QPtr<QLineEdit>,
     matches_filter_schema_line_edit: QPtr<QLineEdit>,
@@ -216,10 +228,20 @@ impl GlobalSearchUI {
         let replace_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "replace_button")?;
         let replace_all_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "replace_all_button")?;
         let use_regex_checkbox: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "regex_button")?;
+        let use_hex_checkbox: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "hex_button")?;
         replace_line_edit.set_placeholder_text(&qtr("global_search_replace_placeholder"));
         replace_button.set_tool_tip(&qtr("global_search_replace"));
         replace_all_button.set_tool_tip(&qtr("global_search_replace_all"));
         use_regex_checkbox.set_tool_tip(&qtr("global_search_use_regex"));
+        use_hex_checkbox.set_tool_tip(&qtr("global_search_use_hex"));
+        let whole_word_checkbox: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "whole_word_button")?;
+        whole_word_checkbox.set_tool_tip(&qtr("global_search_whole_word"));
+        let key_columns_only_checkbox: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "key_columns_only_button")?;
+        key_columns_only_checkbox.set_tool_tip(&qtr("global_search_key_columns_only"));
+        let favorites_only_checkbox: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "favorites_only_button")?;
+        favorites_only_checkbox.set_tool_tip(&qtr("global_search_favorites_only"));
+        let row_matches_checkbox: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "row_matches_button")?;
+        row_matches_checkbox.set_tool_tip(&qtr("global_search_row_matches"));
         kline_edit_configure_safe(&replace_line_edit.static_upcast::<QWidget>().as_ptr());
 
         let search_on_group_box: QPtr<QGroupBox> = find_widget(&main_widget.static_upcast(), "search_on_groupbox")?;
@@ -255,7 +277,7 @@ impl GlobalSearchUI {
         let search_on_anim_checkbox: QPtr<QCheckBox> = QCheckBox::from_q_widget(&main_widget).into_q_ptr();//find_widget(&main_widget.static_upcast(), "search_anim")?;
         let search_on_anim_fragment_battle_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "search_anim_fragment_battle")?;
         let search_on_anim_pack_checkbox: QPtr<QCheckBox> = QCheckBox::from_q_widget(&main_widget).into_q_ptr();//find_widget(&main_widget.static_upcast(), "search_anim_pack")?;
-        let search_on_anims_table_checkbox: QPtr<QCheckBox> = QCheckBox::from_q_widget(&main_widget).into_q_ptr();//find_widget(&main_widget.static_upcast(), "search_anims_table")?;
+        let search_on_anims_table_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "search_anims_table")?;
         let search_on_atlas_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "search_atlas")?;
         let search_on_audio_checkbox: QPtr<QCheckBox> = QCheckBox::from_q_widget(&main_widget).into_q_ptr();//find_widget(&main_widget.static_upcast(), "search_audio")?;
         let search_on_bmd_checkbox: QPtr<QCheckBox> = QCheckBox::from_q_widget(&main_widget).into_q_ptr();//find_widget(&main_widget.static_upcast(), "search_bmd")?;
@@ -264,7 +286,7 @@ impl GlobalSearchUI {
         let search_on_group_formations_checkbox: QPtr<QCheckBox> = QCheckBox::from_q_widget(&main_widget).into_q_ptr();//find_widget(&main_widget.static_upcast(), "search_group_formations")?;
         let search_on_image_checkbox: QPtr<QCheckBox> = QCheckBox::from_q_widget(&main_widget).into_q_ptr();//find_widget(&main_widget.static_upcast(), "search_image")?;
         let search_on_loc_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "search_loc")?;
-        let search_on_matched_combat_checkbox: QPtr<QCheckBox> = QCheckBox::from_q_widget(&main_widget).into_q_ptr();//find_widget(&main_widget.static_upcast(), "search_matched_combat")?;
+        let search_on_matched_combat_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "search_matched_combat")?;
         let search_on_pack_checkbox: QPtr<QCheckBox> = QCheckBox::from_q_widget(&main_widget).into_q_ptr();//find_widget(&main_widget.static_upcast(), "search_pack")?;
         let search_on_portrait_settings_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "search_portrait_settings")?;
         let search_on_rigid_model_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "search_rigid_model")?;
@@ -305,7 +327,7 @@ impl GlobalSearchUI {
         search_on_anim_checkbox.set_visible(false);
         search_on_anim_fragment_battle_checkbox.set_visible(true);
         search_on_anim_pack_checkbox.set_visible(false);
-        search_on_anims_table_checkbox.set_visible(false);
+        search_on_anims_table_checkbox.set_visible(true);
         search_on_atlas_checkbox.set_visible(true);
         search_on_audio_checkbox.set_visible(false);
         search_on_bmd_checkbox.set_visible(false);
@@ -314,7 +336,7 @@ impl GlobalSearchUI {
         search_on_group_formations_checkbox.set_visible(false);
         search_on_image_checkbox.set_visible(false);
         search_on_loc_checkbox.set_visible(true);
-        search_on_matched_combat_checkbox.set_visible(false);
+        search_on_matched_combat_checkbox.set_visible(true);
         search_on_pack_checkbox.set_visible(false);
         search_on_portrait_settings_checkbox.set_visible(true);
         search_on_rigid_model_checkbox.set_visible(true);
@@ -370,8 +392,14 @@ impl GlobalSearchUI {
         tree_view_matches_schema.set_model(&matches_schema_tree_filter);
         matches_schema_tree_filter.set_source_model(&matches_schema_tree_model);
 
+        // Skipped files. These are not matches, so they get their own list, out of the replaceable results.
+        let list_view_matches_skipped: QPtr<QListView> = find_widget(&main_widget.static_upcast(), "skipped_list_view")?;
+        let matches_skipped_list_model = QStandardItemModel::new_1a(&list_view_matches_skipped);
+        list_view_matches_skipped.set_model(&matches_skipped_list_model);
+
         matches_tab_widget.set_tab_text(0, &qtr("global_search_file_matches"));
         matches_tab_widget.set_tab_text(1, &qtr("global_search_schema_matches"));
+        matches_tab_widget.set_tab_text(2, &qtr("global_search_skipped_files"));
 
         // Hide this widget by default.
         dock_widget.hide();
@@ -389,6 +417,11 @@ impl GlobalSearchUI {
             clear_button,
             case_sensitive_checkbox,
             use_regex_checkbox,
+            use_hex_checkbox,
+            whole_word_checkbox,
+            key_columns_only_checkbox,
+            favorites_only_checkbox,
+            row_matches_checkbox,
 
             search_source_packfile,
             search_source_parent,
@@ -425,9 +458,11 @@ impl GlobalSearchUI {
 
             matches_table_and_text_tree_view: tree_view_matches_table_and_text,
             matches_schema_tree_view: tree_view_matches_schema,
+            matches_skipped_list_view: list_view_matches_skipped,
 
             matches_table_and_text_tree_model,
             matches_schema_tree_model,
+            matches_skipped_list_model,
 
             matches_filter_table_and_text_line_edit: filter_matches_table_and_text_line_edit,
             matches_filter_schema_line_edit: filter_matches_schema_line_edit,
@@ -465,15 +500,22 @@ impl GlobalSearchUI {
                     self.matches_tab_widget().set_current_index(1);
                 }
 
+                else if !global_search.matches().skipped().is_empty() {
+                    self.matches_tab_widget().set_current_index(2);
+                }
+
                 self.load_anim_fragment_battle_matches_to_ui(&global_search.matches().anim_fragment_battle(), FileType::AnimFragmentBattle);
                 self.load_atlas_matches_to_ui(&global_search.matches().atlas(), FileType::Atlas);
                 self.load_portrait_settings_matches_to_ui(&global_search.matches().portrait_settings(), FileType::PortraitSettings);
                 self.load_rigid_model_matches_to_ui(&global_search.matches().rigid_model(), FileType::RigidModel);
                 self.load_table_matches_to_ui(&global_search.matches().db(), FileType::DB);
                 self.load_table_matches_to_ui(&global_search.matches().loc(), FileType::Loc);
+                self.load_table_matches_to_ui(&global_search.matches().anims_table(), FileType::AnimsTable);
+                self.load_table_matches_to_ui(&global_search.matches().matched_combat(), FileType::MatchedCombat);
                 self.load_text_matches_to_ui(&global_search.matches().text(), FileType::Text);
                 self.load_unit_variant_matches_to_ui(&global_search.matches().unit_variant(), FileType::UnitVariant);
                 self.load_unknown_matches_to_ui(&global_search.matches().unknown(), FileType::Unknown);
+                self.load_skipped_files_to_ui(global_search.matches().skipped());
                 self.load_schema_matches_to_ui(&global_search.matches().schema());
 
                 UI_STATE.set_global_search(&global_search);
@@ -490,6 +532,7 @@ impl GlobalSearchUI {
 
         self.matches_table_and_text_tree_model.clear();
         self.matches_schema_tree_model.clear();
+        self.matches_skipped_list_model.clear();
     }
 
     /// This function replace the currently selected match with the provided text.
@@ -1066,12 +1109,21 @@ impl GlobalSearchUI {
                         let end = Self::new_item();
 
                         text.set_text(&QString::from_std_str(Self::format_search_match(match_row.text(), *match_row.start(), *match_row.end())));
-                        column_name.set_text(&QString::from_std_str(match_row.column_name()));
                         row.set_data_2a(&QVariant::from_i64(match_row.row_number() + 1), 2);
                         column_number.set_data_2a(&QVariant::from_uint(*match_row.column_number()), 2);
                         start.set_data_2a(&QVariant::from_uint(*match_row.start() as u32), 2);
                         end.set_data_2a(&QVariant::from_uint(*match_row.end() as u32), 2);
 
+                        // Row matches show all the columns of the row with matches, and keep their numbers so they can be rebuilt for replacing.
+                        if match_row.is_row_match() {
+                            let names = match_row.row_columns().iter().map(|(name, _)| name.to_owned()).collect::<Vec<_>>();
+                            let numbers = match_row.columns_affected().iter().map(|number| number.to_string()).collect::<Vec<_>>();
+                            column_name.set_text(&QString::from_std_str(names.join(", ")));
+                            column_name.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(numbers.join(","))), TABLE_MATCH_ROW_COLUMNS);
+                        } else {
+                            column_name.set_text(&QString::from_std_str(match_row.column_name()));
+                        }
+
                         // Add an empty row to the list.
                         qlist_boi.append_q_standard_item(&text.into_ptr().as_mut_raw_ptr());
                         qlist_boi.append_q_standard_item(&column_name.into_ptr().as_mut_raw_ptr());
@@ -1635,6 +1687,21 @@ impl GlobalSearchUI {
         }
     }
 
+    /// This function takes care of loading the files skipped by a global search (due to their size) into their own list.
+    ///
+    /// They're kept out of the matches tree so they're never picked up by a replace.
+    unsafe fn load_skipped_files_to_ui(&self, paths: &[String]) {
+        let model = &self.matches_skipped_list_model;
+
+        for path in paths {
+            let file = Self::new_item();
+            file.set_text(&QString::from_std_str(path));
+            file.set_tool_tip(&qtr("global_search_skipped_too_large"));
+
+            model.append_row_q_standard_item(file.into_ptr());
+        }
+    }
+
     /// This function takes care of loading the results of a global search of `SchemaMatches` into a model.
     unsafe fn load_schema_matches_to_ui(&self, matches: &SchemaMatches) {
         let model = &self.matches_schema_tree_model;
@@ -1708,16 +1775,16 @@ impl GlobalSearchUI {
         let anim_matches: Vec<UnknownMatches> = vec![];
         let mut anim_fragment_battle_matches: Vec<AnimFragmentBattleMatches> = vec![];
         let anim_pack_matches: Vec<UnknownMatches> = vec![];
-        let anims_table_matches: Vec<UnknownMatches> = vec![];
+        let mut anims_table_matches: Vec<TableMatches> = vec![];
         let mut atlas_matches: Vec<AtlasMatches> = vec![];
         let audio_matches: Vec<UnknownMatches> = vec![];
         let bmd_matches: Vec<UnknownMatches> = vec![];
         let mut db_matches: Vec<TableMatches> = vec![];
-        let esf_matches: Vec<UnknownMatches> = vec![];
+        let esf_matches: Vec<EsfMatches> = vec![];
         let group_formations_matches: Vec<UnknownMatches> = vec![];
         let image_matches: Vec<UnknownMatches> = vec![];
         let mut loc_matches: Vec<TableMatches> = vec![];
-        let matched_combat_matches: Vec<UnknownMatches> = vec![];
+        let mut matched_combat_matches: Vec<TableMatches> = vec![];
         let pack_matches: Vec<UnknownMatches> = vec![];
         let mut portrait_settings_matches: Vec<PortraitSettingsMatches> = vec![];
         let mut rigid_model_matches: Vec<RigidModelMatches> = vec![];
@@ -1802,7 +1869,29 @@ impl GlobalSearchUI {
                             }
                         },
                         FileType::AnimPack => todo!(),
-                        FileType::AnimsTable => todo!(),
+                        FileType::AnimsTable => {
+                            let (column_name, row_columns) = Self::table_match_columns(parent.child_2a(item.row(), 1));
+                            let column_number = parent.child_2a(item.row(), 3).text().to_std_string().parse().unwrap();
+                            let row_number = parent.child_2a(item.row(), 2).text().to_std_string().parse::<i64>().unwrap() - 1;
+                            let start = parent.child_2a(item.row(), 4).text().to_std_string().parse::<usize>().unwrap();
+                            let end = parent.child_2a(item.row(), 5).text().to_std_string().parse::<usize>().unwrap();
+                            let text = parent.child_2a(item.row(), 0).text().to_std_string();
+                            let match_file = match anims_table_matches.iter_mut().find(|x| x.path() == &path) {
+                                Some(match_file) => match_file,
+                                None => {
+                                    let table = TableMatches::new(&path);
+                                    anims_table_matches.push(table);
+                                    anims_table_matches.last_mut().unwrap()
+                                }
+                            };
+
+                            let mut match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                            *match_entry.row_columns_mut() = row_columns;
+
+                            if !match_file.matches_mut().contains(&match_entry) {
+                                match_file.matches_mut().push(match_entry);
+                            }
+                        },
                         FileType::Atlas => {
                             let column_name = parent.child_2a(item.row(), 1).text().to_std_string();
                             let column_number = parent.child_2a(item.row(), 3).text().to_std_string().parse().unwrap();
@@ -1830,7 +1919,7 @@ impl GlobalSearchUI {
                         FileType::BMDVegetation => todo!(),
                         FileType::Dat => todo!(),
                         FileType::DB => {
-                            let column_name = parent.child_2a(item.row(), 1).text().to_std_string();
+                            let (column_name, row_columns) = Self::table_match_columns(parent.child_2a(item.row(), 1));
                             let column_number = parent.child_2a(item.row(), 3).text().to_std_string().parse().unwrap();
                             let row_number = parent.child_2a(item.row(), 2).text().to_std_string().parse::<i64>().unwrap() - 1;
                             let start = parent.child_2a(item.row(), 4).text().to_std_string().parse::<usize>().unwrap();
@@ -1845,7 +1934,8 @@ impl GlobalSearchUI {
                                 }
                             };
 
-                            let match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                            let mut match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                            *match_entry.row_columns_mut() = row_columns;
 
                             if !match_file.matches_mut().contains(&match_entry) {
                                 match_file.matches_mut().push(match_entry);
@@ -1857,7 +1947,7 @@ impl GlobalSearchUI {
                         FileType::HlslCompiled => todo!(),
                         FileType::Image => todo!(),
                         FileType::Loc => {
-                            let column_name = parent.child_2a(item.row(), 1).text().to_std_string();
+                            let (column_name, row_columns) = Self::table_match_columns(parent.child_2a(item.row(), 1));
                             let column_number = parent.child_2a(item.row(), 3).text().to_std_string().parse().unwrap();
                             let row_number = parent.child_2a(item.row(), 2).text().to_std_string().parse::<i64>().unwrap() - 1;
                             let start = parent.child_2a(item.row(), 4).text().to_std_string().parse::<usize>().unwrap();
@@ -1872,13 +1962,36 @@ impl GlobalSearchUI {
                                 }
                             };
 
-                            let match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                            let mut match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                            *match_entry.row_columns_mut() = row_columns;
 
                             if !match_file.matches_mut().contains(&match_entry) {
                                 match_file.matches_mut().push(match_entry);
                             }
                         }
-                        FileType::MatchedCombat => todo!(),
+                        FileType::MatchedCombat => {
+                            let (column_name, row_columns) = Self::table_match_columns(parent.child_2a(item.row(), 1));
+                            let column_number = parent.child_2a(item.row(), 3).text().to_std_string().parse().unwrap();
+                            let row_number = parent.child_2a(item.row(), 2).text().to_std_string().parse::<i64>().unwrap() - 1;
+                            let start = parent.child_2a(item.row(), 4).text().to_std_string().parse::<usize>().unwrap();
+                            let end = parent.child_2a(item.row(), 5).text().to_std_string().parse::<usize>().unwrap();
+                            let text = parent.child_2a(item.row(), 0).text().to_std_string();
+                            let match_file = match matched_combat_matches.iter_mut().find(|x| x.path() == &path) {
+                                Some(match_file) => match_file,
+                                None => {
+                                    let table = TableMatches::new(&path);
+                                    matched_combat_matches.push(table);
+                                    matched_combat_matches.last_mut().unwrap()
+                                }
+                            };
+
+                            let mut match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                            *match_entry.row_columns_mut() = row_columns;
+
+                            if !match_file.matches_mut().contains(&match_entry) {
+                                match_file.matches_mut().push(match_entry);
+                            }
+                        },
                         FileType::Pack => todo!(),
                         FileType::PortraitSettings => {
                             let item = parent.child_2a(item.row(), 0);
@@ -2091,7 +2204,28 @@ impl GlobalSearchUI {
                             }
                         },
                         FileType::AnimPack => todo!(),
-                        FileType::AnimsTable => todo!(),
+                        FileType::AnimsTable => {
+                            if let Some(position) = anims_table_matches.iter().position(|x| x.path() == &path) {
+                                anims_table_matches.remove(position);
+                            }
+
+                            let table = TableMatches::new(&path);
+                            anims_table_matches.push(table);
+                            let match_file = anims_table_matches.last_mut().unwrap();
+
+                            // For the individual matches, we have to get them from the view, so the filtered out items are not added.
+                            for row in 0..item.row_count() {
+                                let (column_name, row_columns) = Self::table_match_columns(item.child_2a(row, 1));
+                                let column_number = item.child_2a(row, 3).text().to_std_string().parse().unwrap();
+                                let row_number = item.child_2a(row, 2).text().to_std_string().parse::<i64>().unwrap() - 1;
+                                let start = item.child_2a(row, 4).text().to_std_string().parse::<usize>().unwrap();
+                                let end = item.child_2a(row, 5).text().to_std_string().parse::<usize>().unwrap();
+                                let text = item.child_2a(row, 0).text().to_std_string();
+                                let mut match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                                *match_entry.row_columns_mut() = row_columns;
+                                match_file.matches_mut().push(match_entry);
+                            }
+                        }
                         FileType::Atlas => {
                             if let Some(position) = atlas_matches.iter().position(|x| x.path() == &path) {
                                 atlas_matches.remove(position);
@@ -2128,13 +2262,14 @@ impl GlobalSearchUI {
 
                             // For the individual matches, we have to get them from the view, so the filtered out items are not added.
                             for row in 0..item.row_count() {
-                                let column_name = item.child_2a(row, 1).text().to_std_string();
+                                let (column_name, row_columns) = Self::table_match_columns(item.child_2a(row, 1));
                                 let column_number = item.child_2a(row, 3).text().to_std_string().parse().unwrap();
                                 let row_number = item.child_2a(row, 2).text().to_std_string().parse::<i64>().unwrap() - 1;
                                 let start = item.child_2a(row, 4).text().to_std_string().parse::<usize>().unwrap();
                                 let end = item.child_2a(row, 5).text().to_std_string().parse::<usize>().unwrap();
                                 let text = item.child_2a(row, 0).text().to_std_string();
-                                let match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                                let mut match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                                *match_entry.row_columns_mut() = row_columns;
                                 match_file.matches_mut().push(match_entry);
                             }
                         }
@@ -2154,17 +2289,39 @@ impl GlobalSearchUI {
 
                             // For the individual matches, we have to get them from the view, so the filtered out items are not added.
                             for row in 0..item.row_count() {
-                                let column_name = item.child_2a(row, 1).text().to_std_string();
+                                let (column_name, row_columns) = Self::table_match_columns(item.child_2a(row, 1));
                                 let column_number = item.child_2a(row, 3).text().to_std_string().parse().unwrap();
                                 let row_number = item.child_2a(row, 2).text().to_std_string().parse::<i64>().unwrap() - 1;
                                 let start = item.child_2a(row, 4).text().to_std_string().parse::<usize>().unwrap();
                                 let end = item.child_2a(row, 5).text().to_std_string().parse::<usize>().unwrap();
                                 let text = item.child_2a(row, 0).text().to_std_string();
-                                let match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                                let mut match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                                *match_entry.row_columns_mut() = row_columns;
+                                match_file.matches_mut().push(match_entry);
+                            }
+                        }
+                        FileType::MatchedCombat => {
+                            if let Some(position) = matched_combat_matches.iter().position(|x| x.path() == &path) {
+                                matched_combat_matches.remove(position);
+                            }
+
+                            let table = TableMatches::new(&path);
+                            matched_combat_matches.push(table);
+                            let match_file = matched_combat_matches.last_mut().unwrap();
+
+                            // For the individual matches, we have to get them from the view, so the filtered out items are not added.
+                            for row in 0..item.row_count() {
+                                let (column_name, row_columns) = Self::table_match_columns(item.child_2a(row, 1));
+                                let column_number = item.child_2a(row, 3).text().to_std_string().parse().unwrap();
+                                let row_number = item.child_2a(row, 2).text().to_std_string().parse::<i64>().unwrap() - 1;
+                                let start = item.child_2a(row, 4).text().to_std_string().parse::<usize>().unwrap();
+                                let end = item.child_2a(row, 5).text().to_std_string().parse::<usize>().unwrap();
+                                let text = item.child_2a(row, 0).text().to_std_string();
+                                let mut match_entry = TableMatch::new(&column_name, column_number, row_number, start, end, &text);
+                                *match_entry.row_columns_mut() = row_columns;
                                 match_file.matches_mut().push(match_entry);
                             }
                         }
-                        FileType::MatchedCombat => todo!(),
                         FileType::Pack => todo!(),
                         FileType::PortraitSettings => {
                             if let Some(position) = portrait_settings_matches.iter().position(|x| x.path() == &path) {
@@ -2349,6 +2506,15 @@ impl GlobalSearchUI {
         global_search.set_pattern(self.search_line_edit.text().to_std_string());
         global_search.set_case_sensitive(self.case_sensitive_checkbox.is_checked());
         global_search.set_use_regex(self.use_regex_checkbox.is_checked());
+        global_search.set_key_and_reference_columns_only(self.key_columns_only_checkbox.is_checked());
+        global_search.set_table_match_granularity(if self.row_matches_checkbox.is_checked() { TableMatchGranularity::Row } else { TableMatchGranularity::Cell });
+
+        // Favorites only affect DB tables, and only of the game selected.
+        if self.favorites_only_checkbox.is_checked() {
+            global_search.set_table_names(Some(favorite_tables(GAME_SELECTED.read().unwrap().key())));
+        } else {
+            global_search.set_table_names(None);
+        }
 
         if is_replace {
             global_search.set_replace_text(self.replace_line_edit.text().to_std_string());
@@ -2359,6 +2525,15 @@ impl GlobalSearchUI {
             return None;
         }
 
+        // Hex patterns need to be valid before searching.
+        global_search.set_use_hex(self.use_hex_checkbox.is_checked());
+        if *global_search.use_hex() {
+            if let Err(error) = parse_hex_pattern(global_search.pattern()) {
+                show_dialog(&self.dock_widget, error, false);
+                return None;
+            }
+        }
+
         if self.search_source_packfile.is_checked() {
             global_search.set_source(SearchSource::Pack);
         } else if self.search_source_parent.is_checked() {
@@ -2446,6 +2621,8 @@ impl GlobalSearchUI {
             global_search.search_on_mut().set_video(self.search_on_video_checkbox.is_checked());
         }
 
+        global_search.search_on_mut().set_whole_word(self.whole_word_checkbox.is_checked());
+
         Some(global_search)
     }
 
@@ -2454,6 +2631,7 @@ impl GlobalSearchUI {
         // Clear the current results panels.
         self.matches_table_and_text_tree_model.clear();
         self.matches_schema_tree_model.clear();
+        self.matches_skipped_list_model.clear();
 
         // Optimisation: Setting the column counts allows us to configure the columns before loading the data.
         self.matches_table_and_text_tree_model.set_column_count(6);
@@ -2504,6 +2682,25 @@ impl GlobalSearchUI {
         item
     }
 
+    /// This function returns the column name and the columns with matches of a table match, from its column name item.
+    ///
+    /// For cell matches, the list of columns is empty. For row matches, the column name is the one of the first column with matches.
+    unsafe fn table_match_columns(item: Ptr<QStandardItem>) -> (String, Vec<(String, u32)>) {
+        let text = item.text().to_std_string();
+        let numbers = item.data_1a(TABLE_MATCH_ROW_COLUMNS).to_string().to_std_string();
+        if numbers.is_empty() {
+            return (text, vec![]);
+        }
+
+        let row_columns = text.split(", ")
+            .zip(numbers.split(','))
+            .filter_map(|(name, number)| number.parse::<u32>().ok().map(|number| (name.to_owned(), number)))
+            .collect::<Vec<_>>();
+
+        let column_name = row_columns.first().map(|(name, _)| name.to_owned()).unwrap_or(text);
+        (column_name, row_columns)
+    }
+
     unsafe fn format_search_match(text: &str, start: usize, end: usize) -> String {
 
         // Trim the text so only the part with the match shows up.
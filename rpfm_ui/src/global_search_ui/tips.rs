@@ -25,6 +25,11 @@ pub unsafe fn set_tips(global_search_ui: &Rc<GlobalSearchUI>) {
     // Global Search panel tips.
     //---------------------------------------------------//
     global_search_ui.use_regex_checkbox.set_status_tip(&qtr("tt_global_search_use_regex_checkbox"));
+    global_search_ui.use_hex_checkbox.set_status_tip(&qtr("tt_global_search_use_hex_checkbox"));
+    global_search_ui.whole_word_checkbox.set_status_tip(&qtr("tt_global_search_whole_word_checkbox"));
+    global_search_ui.key_columns_only_checkbox.set_status_tip(&qtr("tt_global_search_key_columns_only_checkbox"));
+    global_search_ui.favorites_only_checkbox.set_status_tip(&qtr("tt_global_search_favorites_only_checkbox"));
+    global_search_ui.row_matches_checkbox.set_status_tip(&qtr("tt_global_search_row_matches_checkbox"));
     global_search_ui.case_sensitive_checkbox.set_status_tip(&qtr("tt_global_search_case_sensitive_checkbox"));
     global_search_ui.search_on_all_checkbox.set_status_tip(&qtr("tt_global_search_search_on_all_checkbox"));
     global_search_ui.search_on_db_checkbox.set_status_tip(&qtr("tt_global_search_search_on_dbs_checkbox"));
@@ -184,6 +184,9 @@ lazy_static! {
     /// This one is for detecting when a file is open for the first time, so we can skip some costly slots.
     static ref NEW_FILE_VIEW_CREATED: AtomicBool = AtomicBool::new(false);
 
+    /// Atomic to control if there is a dependencies cache generation running in the background.
+    static ref DEPENDENCIES_CACHE_GENERATION_RUNNING: AtomicBool = AtomicBool::new(false);
+
     /// Variable to keep the background thread for the startpos generation working.
     static ref START_POS_WORKAROUND_THREAD: Arc<RwLock<Option<Vec<(Sender<bool>, JoinHandle<()>)>>>> = Arc::new(RwLock::new(None));
 }
@@ -14,6 +14,7 @@ This module contains code to make our live easier when dealing with `TreeViews`.
 
 use qt_widgets::QTreeView;
 
+use qt_gui::{QColor, q_color::NameFormat};
 use qt_gui::QStandardItem;
 use qt_gui::QStandardItemModel;
 use qt_gui::QListOfQStandardItem;
@@ -1963,49 +1964,41 @@ pub unsafe fn get_color_clean() -> String {
 }
 
 pub unsafe fn get_color_info() -> String {
-    if setting_bool("use_dark_theme") {
-        INFO_UNPRESSED_DARK.to_owned()
-    } else {
-        INFO_UNPRESSED_LIGHT.to_owned()
-    }
+    get_color_diagnostic("info", false)
 }
 
 pub unsafe fn get_color_warning() -> String {
-    if setting_bool("use_dark_theme") {
-        WARNING_UNPRESSED_DARK.to_owned()
-    } else {
-        WARNING_UNPRESSED_LIGHT.to_owned()
-    }
+    get_color_diagnostic("warning", false)
 }
 
 pub unsafe fn get_color_error() -> String {
-    if setting_bool("use_dark_theme") {
-        ERROR_UNPRESSED_DARK.to_owned()
-    } else {
-        ERROR_UNPRESSED_LIGHT.to_owned()
-    }
+    get_color_diagnostic("error", false)
 }
 
 pub unsafe fn get_color_info_pressed() -> String {
-    if setting_bool("use_dark_theme") {
-        INFO_PRESSED_DARK.to_owned()
-    } else {
-        INFO_PRESSED_LIGHT.to_owned()
-    }
+    get_color_diagnostic("info", true)
 }
 
 pub unsafe fn get_color_warning_pressed() -> String {
-    if setting_bool("use_dark_theme") {
-        WARNING_PRESSED_DARK.to_owned()
-    } else {
-        WARNING_PRESSED_LIGHT.to_owned()
-    }
+    get_color_diagnostic("warning", true)
 }
 
 pub unsafe fn get_color_error_pressed() -> String {
-    if setting_bool("use_dark_theme") {
-        ERROR_PRESSED_DARK.to_owned()
-    } else {
-        ERROR_PRESSED_LIGHT.to_owned()
-    }
+    get_color_diagnostic("error", true)
+}
+
+/// This function returns the colour configured for the provided diagnostic level in the current theme.
+///
+/// The pressed variant is derived from it: lighter on the dark theme, darker on the light one.
+unsafe fn get_color_diagnostic(level: &str, pressed: bool) -> String {
+    let dark_theme = setting_bool("use_dark_theme");
+    let theme = if dark_theme { "dark" } else { "light" };
+    let colour = QColor::from_q_string(&QString::from_std_str(setting_string(&format!("colour_{theme}_diagnostics_panel_{level}"))));
+    let colour = match (pressed, dark_theme) {
+        (false, _) => colour,
+        (true, true) => colour.lighter_1a(130),
+        (true, false) => colour.darker_1a(120),
+    };
+
+    colour.name_1a(NameFormat::HexArgb).to_std_string()
 }
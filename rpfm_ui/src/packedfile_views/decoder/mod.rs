@@ -1059,6 +1059,12 @@ impl PackedFileDecoderView {
                     Err(_) => "Error".to_owned(),
                 }
             },
+            FieldType::ColourRGBA => {
+                match data.read_string_colour_rgba() {
+                    Ok(result) => result,
+                    Err(_) => "Error".to_owned(),
+                }
+            },
             FieldType::StringU8 => {
                 match data.read_sized_string_u8() {
                     Ok(result) => result,
@@ -1145,6 +1151,7 @@ impl PackedFileDecoderView {
                         "OptionalI32" => FieldType::OptionalI32,
                         "OptionalI64" => FieldType::OptionalI64,
                         "ColourRGB" => FieldType::ColourRGB,
+                        "ColourRGBA" => FieldType::ColourRGBA,
                         "StringU8" => FieldType::StringU8,
                         "StringU16" => FieldType::StringU16,
                         "OptionalStringU8" => FieldType::OptionalStringU8,
@@ -1270,6 +1277,7 @@ impl PackedFileDecoderView {
                     "OptionalI32" => FieldType::OptionalI32,
                     "OptionalI64" => FieldType::OptionalI64,
                     "ColourRGB" => FieldType::ColourRGB,
+                    "ColourRGBA" => FieldType::ColourRGBA,
                     "StringU8" => FieldType::StringU8,
                     "StringU16" => FieldType::StringU16,
                     "OptionalStringU8" => FieldType::OptionalStringU8,
@@ -1684,6 +1692,7 @@ unsafe fn configure_table_view(table_view: &QBox<QTreeView>) {
     list.append_q_string(&QString::from_std_str("OptionalI32"));
     list.append_q_string(&QString::from_std_str("OptionalI64"));
     list.append_q_string(&QString::from_std_str("ColourRGB"));
+    list.append_q_string(&QString::from_std_str("ColourRGBA"));
     list.append_q_string(&QString::from_std_str("StringU8"));
     list.append_q_string(&QString::from_std_str("StringU16"));
     list.append_q_string(&QString::from_std_str("OptionalStringU8"));
@@ -1692,7 +1701,7 @@ unsafe fn configure_table_view(table_view: &QBox<QTreeView>) {
     new_combobox_item_delegate_safe(&table_view.static_upcast::<QObject>().as_ptr(), 2, list.into_ptr(), QStringList::new().into_ptr(), false, &QTimer::new_0a().into_ptr(), false);
 
     // Fields that need special code.
-    new_spinbox_item_delegate_safe(&table_view.static_upcast::<QObject>().as_ptr(), 11, 16, &QTimer::new_0a().into_ptr(), false);
+    new_spinbox_item_delegate_safe(&table_view.static_upcast::<QObject>().as_ptr(), 11, 16, false, &QTimer::new_0a().into_ptr(), false);
     new_qstring_item_delegate_safe(&table_view.static_upcast::<QObject>().as_ptr(), 14, &QTimer::new_0a().into_ptr(), false);
-    new_spinbox_item_delegate_safe(&table_view.static_upcast::<QObject>().as_ptr(), 15, 32, &QTimer::new_0a().into_ptr(), false);
+    new_spinbox_item_delegate_safe(&table_view.static_upcast::<QObject>().as_ptr(), 15, 32, false, &QTimer::new_0a().into_ptr(), false);
 }
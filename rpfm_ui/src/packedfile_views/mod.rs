@@ -444,6 +444,8 @@ impl FileView {
                                 Ok(())
                             }
 
+                            Response::Error(error) => Err(error),
+
                             // In ANY other situation, it's a message problem.
                             _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
                         }
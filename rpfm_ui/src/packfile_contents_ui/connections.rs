@@ -69,6 +69,9 @@ pub unsafe fn set_connections(ui: &PackFileContentsUI, slots: &PackFileContentsS
     ui.context_menu_merge_tables.triggered().connect(&slots.contextual_menu_tables_merge_tables);
     ui.context_menu_update_table.triggered().connect(&slots.contextual_menu_tables_update_table);
     ui.context_menu_generate_missing_loc_data.triggered().connect(&slots.contextual_menu_generate_missing_loc_data);
+    ui.context_menu_generate_missing_loc_entries.triggered().connect(&slots.contextual_menu_generate_missing_loc_entries);
+    ui.context_menu_add_to_favorites.triggered().connect(&slots.contextual_menu_add_to_favorites);
+    ui.context_menu_remove_from_favorites.triggered().connect(&slots.contextual_menu_remove_from_favorites);
 
     ui.packfile_contents_tree_view_expand_all.triggered().connect(&slots.packfile_contents_tree_view_expand_all);
     ui.packfile_contents_tree_view_collapse_all.triggered().connect(&slots.packfile_contents_tree_view_collapse_all);
@@ -22,6 +22,7 @@ use qt_widgets::QGridLayout;
 use qt_widgets::QLabel;
 use qt_widgets::QLineEdit;
 use qt_widgets::QMenu;
+use qt_widgets::QPushButton;
 use qt_widgets::QToolButton;
 use qt_widgets::QTreeView;
 use qt_widgets::QWidget;
@@ -29,6 +30,8 @@ use qt_widgets::QWidget;
 use qt_gui::QStandardItemModel;
 
 use qt_core::QBox;
+use qt_core::QFlags;
+use qt_core::q_item_selection_model::SelectionFlag;
 use qt_core::CaseSensitivity;
 use qt_core::DockWidgetArea;
 use qt_core::QObject;
@@ -37,23 +40,26 @@ use qt_core::QRegExp;
 use qt_core::QSortFilterProxyModel;
 use qt_core::QString;
 use qt_core::QTimer;
+use qt_core::SlotOfBool;
 
 use anyhow::Result;
 use getset::Getters;
 
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 use std::rc::Rc;
 
 use rpfm_lib::files::{ContainerPath, pack::RESERVED_NAME_NOTES};
 
+use rpfm_ui_common::clone;
 use rpfm_ui_common::locale::qtr;
 
 use crate::app_ui::AppUI;
 use crate::CENTRAL_COMMAND;
 use crate::communications::{CentralCommand, Command, Response, THREADS_COMMUNICATION_ERROR};
 use crate::ffi::*;
+use crate::GAME_SELECTED;
 use crate::packedfile_views::DataSource;
 use crate::pack_tree::{PackTree, TreeViewOperation};
 use crate::settings_ui::backend::*;
@@ -120,6 +126,10 @@ pub struct PackFileContentsUI {
     context_menu_merge_tables: QPtr<QAction>,
     context_menu_update_table: QPtr<QAction>,
     context_menu_generate_missing_loc_data: QPtr<QAction>,
+    context_menu_generate_missing_loc_entries: QPtr<QAction>,
+    context_menu_add_to_favorites: QPtr<QAction>,
+    context_menu_remove_from_favorites: QPtr<QAction>,
+    menu_favorite_tables: QPtr<QMenu>,
 
     //-------------------------------------------------------------------------------//
     // Actions not in the UI.
@@ -221,6 +231,10 @@ impl PackFileContentsUI {
         let context_menu_merge_tables = add_action_to_menu(&packfile_contents_tree_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "pack_tree_context_menu", "merge_files", "context_menu_merge_tables", Some(packfile_contents_tree_view.static_upcast::<qt_widgets::QWidget>()));
         let context_menu_update_table = add_action_to_menu(&packfile_contents_tree_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "pack_tree_context_menu", "update_files", "context_menu_update_table", Some(packfile_contents_tree_view.static_upcast::<qt_widgets::QWidget>()));
         let context_menu_generate_missing_loc_data = add_action_to_menu(&packfile_contents_tree_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "pack_tree_context_menu", "generate_missing_loc_data", "context_menu_generate_missing_loc_data", Some(packfile_contents_tree_view.static_upcast::<qt_widgets::QWidget>()));
+        let context_menu_generate_missing_loc_entries = add_action_to_menu(&packfile_contents_tree_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "pack_tree_context_menu", "generate_missing_loc_entries", "context_menu_generate_missing_loc_entries", Some(packfile_contents_tree_view.static_upcast::<qt_widgets::QWidget>()));
+        let context_menu_add_to_favorites = add_action_to_menu(&packfile_contents_tree_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "pack_tree_context_menu", "add_to_favorites", "context_menu_add_to_favorites", Some(packfile_contents_tree_view.static_upcast::<qt_widgets::QWidget>()));
+        let context_menu_remove_from_favorites = add_action_to_menu(&packfile_contents_tree_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "pack_tree_context_menu", "remove_from_favorites", "context_menu_remove_from_favorites", Some(packfile_contents_tree_view.static_upcast::<qt_widgets::QWidget>()));
+        let menu_favorite_tables = packfile_contents_tree_view_context_menu.add_menu_q_string(&qtr("context_menu_favorite_tables"));
 
         let packfile_contents_tree_view_expand_all = add_action_to_menu(&packfile_contents_tree_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "pack_tree_context_menu", "expand_all", "treeview_expand_all", Some(packfile_contents_tree_view.static_upcast::<qt_widgets::QWidget>()));
         let packfile_contents_tree_view_collapse_all = add_action_to_menu(&packfile_contents_tree_view_context_menu.static_upcast(), app_ui.shortcuts().as_ref(), "pack_tree_context_menu", "collapse_all", "treeview_collapse_all", Some(packfile_contents_tree_view.static_upcast::<qt_widgets::QWidget>()));
@@ -229,6 +243,7 @@ impl PackFileContentsUI {
         packfile_contents_tree_view_context_menu.insert_separator(&menu_open.menu_action());
         packfile_contents_tree_view_context_menu.insert_separator(&context_menu_rename);
         packfile_contents_tree_view_context_menu.insert_separator(&context_menu_merge_tables);
+        packfile_contents_tree_view_context_menu.insert_separator(&context_menu_add_to_favorites);
 
         // Disable all the Contextual Menu actions by default.
         context_menu_add_file.set_enabled(false);
@@ -251,6 +266,8 @@ impl PackFileContentsUI {
         context_menu_open_packfile_settings.set_enabled(false);
         context_menu_open_with_external_program.set_enabled(false);
         context_menu_open_notes.set_enabled(false);
+        context_menu_add_to_favorites.set_enabled(false);
+        context_menu_remove_from_favorites.set_enabled(false);
 
         // Create ***Da monsta***.
         Ok(Self {
@@ -300,6 +317,10 @@ impl PackFileContentsUI {
             context_menu_merge_tables,
             context_menu_update_table,
             context_menu_generate_missing_loc_data,
+            context_menu_generate_missing_loc_entries,
+            context_menu_add_to_favorites,
+            context_menu_remove_from_favorites,
+            menu_favorite_tables,
 
             //-------------------------------------------------------------------------------//
             // "Special" Actions for the TreeView.
@@ -380,6 +401,68 @@ impl PackFileContentsUI {
         }
     }
 
+    /// This function creates the "Generate Missing Loc Entries" dialog.
+    ///
+    /// It returns the path of the Loc file where the entries should be generated, or `None` if the dialog is canceled or closed.
+    pub unsafe fn generate_missing_loc_entries_dialog(app_ui: &Rc<AppUI>, default_path: &str) -> Option<String> {
+        let dialog = QDialog::new_1a(app_ui.main_window());
+        dialog.set_window_title(&qtr("generate_missing_loc_entries"));
+        dialog.set_modal(true);
+        dialog.resize_2a(600, 20);
+
+        let main_grid = create_grid_layout(dialog.static_upcast());
+        let path_line_edit = QLineEdit::new();
+        path_line_edit.set_text(&QString::from_std_str(default_path));
+        path_line_edit.set_placeholder_text(&qtr("generate_missing_loc_entries_placeholder"));
+        let accept_button = QPushButton::from_q_string(&qtr("gen_loc_accept"));
+
+        main_grid.add_widget_5a(&path_line_edit, 0, 0, 1, 1);
+        main_grid.add_widget_5a(&accept_button, 0, 1, 1, 1);
+        accept_button.released().connect(dialog.slot_accept());
+
+        if dialog.exec() == 1 {
+            let path = path_line_edit.text().to_std_string();
+            if path.is_empty() { None } else { Some(path) }
+        } else { None }
+    }
+
+    /// This function generates the loc entries missing in the open Pack into the Loc file at the provided path, updating the UI accordingly.
+    ///
+    /// It returns the amount of entries generated, or `None` if there was an error, which is reported in a dialog.
+    pub unsafe fn generate_missing_loc_data(app_ui: &Rc<AppUI>, pack_file_contents_ui: &Rc<Self>, path: &str, text: &str) -> Option<usize> {
+
+        // If the target file is open, save and close it first so we don't lose its changes.
+        if let Err(error) = AppUI::purge_that_one_specifically(app_ui, pack_file_contents_ui, path, DataSource::PackFile, true) {
+            show_dialog(app_ui.main_window(), error, false);
+            return None;
+        }
+
+        let receiver = CENTRAL_COMMAND.send_background(Command::GenerateMissingLocData(path.to_owned(), text.to_owned()));
+        let response = CentralCommand::recv(&receiver);
+        match response {
+            Response::UsizeBool(count, is_new_file) => {
+                if count > 0 {
+                    let paths = vec![ContainerPath::File(path.to_owned())];
+                    if is_new_file {
+                        pack_file_contents_ui.packfile_contents_tree_view.update_treeview(true, TreeViewOperation::Add(paths), DataSource::PackFile);
+                    } else {
+                        pack_file_contents_ui.packfile_contents_tree_view.update_treeview(true, TreeViewOperation::Modify(paths), DataSource::PackFile);
+                    }
+
+                    UI_STATE.set_is_modified(true, app_ui, pack_file_contents_ui);
+                }
+
+                Some(count)
+            }
+
+            Response::Error(error) => {
+                show_dialog(app_ui.main_window(), error, false);
+                None
+            }
+            _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+        }
+    }
+
     /// This function creates the entire "Rename" dialog.
     ///
     ///It returns the new name of the Item, or `None` if the dialog is canceled or closed.
@@ -538,6 +621,46 @@ impl PackFileContentsUI {
         }
     }
 
+    /// This function returns the names of the DB tables selected in the TreeView, either by selecting their files or their folders.
+    pub unsafe fn selected_table_names(&self) -> BTreeSet<String> {
+        self.packfile_contents_tree_view.get_path_from_selection()
+            .iter()
+            .filter_map(|path| {
+                let mut split = path.split('/');
+                match (split.next(), split.next()) {
+                    (Some(folder), Some(table_name)) if folder.to_lowercase() == "db" && !table_name.is_empty() => Some(table_name.to_owned()),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// This function rebuilds the quick-access menu with the favorite tables of the game selected.
+    ///
+    /// Triggering one of its entries selects the folder of said table in the TreeView.
+    pub unsafe fn update_favorite_tables_menu(pack_file_contents_ui: &Rc<Self>) {
+        pack_file_contents_ui.menu_favorite_tables.clear();
+
+        let favorite_tables = favorite_tables(GAME_SELECTED.read().unwrap().key());
+        pack_file_contents_ui.menu_favorite_tables.set_enabled(!favorite_tables.is_empty());
+
+        for table_name in favorite_tables {
+            let action = pack_file_contents_ui.menu_favorite_tables.add_action_q_string(&QString::from_std_str(&table_name));
+            let slot = SlotOfBool::new(&action, clone!(
+                pack_file_contents_ui => move |_| {
+                let tree_view = pack_file_contents_ui.packfile_contents_tree_view();
+                if let Some(tree_index) = tree_view.expand_treeview_to_item(&format!("db/{table_name}"), DataSource::PackFile) {
+                    if tree_index.is_valid() {
+                        tree_view.scroll_to_1a(tree_index.as_ref().unwrap());
+                        tree_view.selection_model().select_q_model_index_q_flags_selection_flag(tree_index.as_ref().unwrap(), QFlags::from(SelectionFlag::ClearAndSelect));
+                    }
+                }
+            }));
+
+            action.triggered().connect(&slot);
+        }
+    }
+
     pub unsafe fn start_delayed_updates_timer(pack_file_contents_ui: &Rc<Self>,) {
         pack_file_contents_ui.filter_timer_delayed_updates.set_interval(500);
         pack_file_contents_ui.filter_timer_delayed_updates.start_0a();
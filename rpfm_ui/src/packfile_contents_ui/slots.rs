@@ -41,6 +41,7 @@ use rpfm_ui_common::locale::{qtr, tre};
 use crate::app_ui::AppUI;
 use crate::CENTRAL_COMMAND;
 use crate::dependencies_ui::DependenciesUI;
+use crate::GAME_SELECTED;
 use crate::diagnostics_ui::DiagnosticsUI;
 use crate::communications::{CentralCommand, Command, Response, THREADS_COMMUNICATION_ERROR};
 use crate::global_search_ui::GlobalSearchUI;
@@ -100,6 +101,9 @@ pub struct PackFileContentsSlots {
     pub contextual_menu_tables_merge_tables: QBox<SlotOfBool>,
     pub contextual_menu_tables_update_table: QBox<SlotOfBool>,
     pub contextual_menu_generate_missing_loc_data: QBox<SlotOfBool>,
+    pub contextual_menu_generate_missing_loc_entries: QBox<SlotOfBool>,
+    pub contextual_menu_add_to_favorites: QBox<SlotOfBool>,
+    pub contextual_menu_remove_from_favorites: QBox<SlotOfBool>,
 
     pub packfile_contents_tree_view_expand_all: QBox<SlotNoArgs>,
     pub packfile_contents_tree_view_collapse_all: QBox<SlotNoArgs>,
@@ -297,6 +301,7 @@ impl PackFileContentsSlots {
         // Slot to show the Contextual Menu for the TreeView.
         let contextual_menu = SlotOfQPoint::new(&pack_file_contents_ui.packfile_contents_dock_widget, clone!(
             pack_file_contents_ui => move |_| {
+            PackFileContentsUI::update_favorite_tables_menu(&pack_file_contents_ui);
             pack_file_contents_ui.packfile_contents_tree_view_context_menu.exec_1a_mut(&QCursor::pos_0a());
         }));
 
@@ -530,10 +535,18 @@ impl PackFileContentsSlots {
                 // If there is anything selected, we can generate missing loc data.
                 if files > 0 || folders > 0 {
                     pack_file_contents_ui.context_menu_generate_missing_loc_data.set_enabled(true);
+                    pack_file_contents_ui.context_menu_generate_missing_loc_entries.set_enabled(true);
                 } else {
                     pack_file_contents_ui.context_menu_generate_missing_loc_data.set_enabled(false);
+                    pack_file_contents_ui.context_menu_generate_missing_loc_entries.set_enabled(false);
                 }
 
+                // Favorites can be added or removed if we have tables selected that are (or are not) favorites already.
+                let selected_table_names = pack_file_contents_ui.selected_table_names();
+                let favorite_tables = favorite_tables(GAME_SELECTED.read().unwrap().key());
+                pack_file_contents_ui.context_menu_add_to_favorites.set_enabled(selected_table_names.iter().any(|table_name| !favorite_tables.contains(table_name)));
+                pack_file_contents_ui.context_menu_remove_from_favorites.set_enabled(selected_table_names.iter().any(|table_name| favorite_tables.contains(table_name)));
+
                 // Ask the other thread if there is a Dependency Database and a Schema loaded.
                 let receiver = CENTRAL_COMMAND.send_background(Command::IsThereADependencyDatabase(false));
                 let response = CentralCommand::recv(&receiver);
@@ -892,6 +905,7 @@ impl PackFileContentsSlots {
                                 }
                             }
                         },
+                        Response::Error(error) => show_dialog(app_ui.main_window(), error, false),
                         _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
                     };
                 }
@@ -1341,19 +1355,59 @@ impl PackFileContentsSlots {
             pack_file_contents_ui => move |_| {
             info!("Triggering `Generate Loc Data` By Slot");
 
-            let receiver = CENTRAL_COMMAND.send_background(Command::GenerateMissingLocData);
-            let response = CentralCommand::recv(&receiver);
-            match response {
-                Response::OptionContainerPath(path_to_add) => {
-                    if let Some(path_to_add) = path_to_add {
-                        pack_file_contents_ui.packfile_contents_tree_view.update_treeview(true, TreeViewOperation::Add(vec![path_to_add; 1]), DataSource::PackFile);
-                        UI_STATE.set_is_modified(true, &app_ui, &pack_file_contents_ui);
-                    }
+            PackFileContentsUI::generate_missing_loc_data(&app_ui, &pack_file_contents_ui, "text/missing_locs.loc", "PLACEHOLDER");
+        }));
+
+        // What happens when we trigger the "Generate Missing Loc Entries" action in the Contextual Menu.
+        let contextual_menu_generate_missing_loc_entries = SlotOfBool::new(&pack_file_contents_ui.packfile_contents_dock_widget, clone!(
+            app_ui,
+            pack_file_contents_ui => move |_| {
+            info!("Triggering `Generate Missing Loc Entries` By Slot");
+
+            // If we have a Loc file selected, use it as default target. If we have a table selected, use the game's loc file for it.
+            let selected_paths = pack_file_contents_ui.packfile_contents_tree_view.get_path_from_selection();
+            let selected_table_names = pack_file_contents_ui.selected_table_names();
+            let default_path = match selected_paths.first() {
+                Some(path) if selected_paths.len() == 1 && path.to_lowercase().ends_with(".loc") => path.to_owned(),
+                _ if selected_table_names.len() == 1 => GAME_SELECTED.read().unwrap().default_loc_path_for_table(selected_table_names.first().unwrap()),
+                _ => "text/missing_locs.loc".to_owned(),
+            };
+
+            if let Some(path) = PackFileContentsUI::generate_missing_loc_entries_dialog(&app_ui, &default_path) {
+                if let Some(count) = PackFileContentsUI::generate_missing_loc_data(&app_ui, &pack_file_contents_ui, &path, "") {
+                    show_dialog(app_ui.main_window(), tre("generate_missing_loc_entries_success", &[&count.to_string()]), true);
                 }
+            }
+        }));
 
-                Response::Error(error) => show_dialog(app_ui.main_window(), error, false),
-                _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+        // What happens when we trigger the "Add to Favorites" action in the Contextual Menu.
+        let contextual_menu_add_to_favorites = SlotOfBool::new(&pack_file_contents_ui.packfile_contents_dock_widget, clone!(
+            pack_file_contents_ui => move |_| {
+            info!("Triggering `Add to Favorites` By Slot");
+
+            let game_key = GAME_SELECTED.read().unwrap().key().to_owned();
+            let table_names = pack_file_contents_ui.selected_table_names().into_iter().collect::<Vec<_>>();
+            if let Err(error) = add_favorite_tables(&game_key, &table_names) {
+                return show_dialog(&pack_file_contents_ui.packfile_contents_dock_widget, error, false);
+            }
+
+            pack_file_contents_ui.context_menu_add_to_favorites.set_enabled(false);
+            pack_file_contents_ui.context_menu_remove_from_favorites.set_enabled(true);
+        }));
+
+        // What happens when we trigger the "Remove from Favorites" action in the Contextual Menu.
+        let contextual_menu_remove_from_favorites = SlotOfBool::new(&pack_file_contents_ui.packfile_contents_dock_widget, clone!(
+            pack_file_contents_ui => move |_| {
+            info!("Triggering `Remove from Favorites` By Slot");
+
+            let game_key = GAME_SELECTED.read().unwrap().key().to_owned();
+            let table_names = pack_file_contents_ui.selected_table_names().into_iter().collect::<Vec<_>>();
+            if let Err(error) = remove_favorite_tables(&game_key, &table_names) {
+                return show_dialog(&pack_file_contents_ui.packfile_contents_dock_widget, error, false);
             }
+
+            pack_file_contents_ui.context_menu_add_to_favorites.set_enabled(true);
+            pack_file_contents_ui.context_menu_remove_from_favorites.set_enabled(false);
         }));
 
         let packfile_contents_tree_view_expand_all = SlotNoArgs::new(&pack_file_contents_ui.packfile_contents_dock_widget, clone!(
@@ -1418,6 +1472,9 @@ impl PackFileContentsSlots {
             contextual_menu_tables_merge_tables,
             contextual_menu_tables_update_table,
             contextual_menu_generate_missing_loc_data,
+            contextual_menu_generate_missing_loc_entries,
+            contextual_menu_add_to_favorites,
+            contextual_menu_remove_from_favorites,
 
             packfile_contents_tree_view_expand_all,
             packfile_contents_tree_view_collapse_all,
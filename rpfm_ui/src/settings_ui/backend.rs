@@ -24,12 +24,15 @@ use qt_core::QPtr;
 use anyhow::{anyhow, Result};
 use ron::ser::{PrettyConfig, to_string_pretty};
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs::{DirBuilder, File};
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 
+use rpfm_extensions::search::favorites::FavoriteTables;
+
 use rpfm_lib::error::RLibError;
+use rpfm_lib::files::table::DEFAULT_FLOAT_PRECISION;
 use rpfm_lib::games::{*, supported_games::*};
 use rpfm_lib::schema::{SCHEMA_FOLDER, DefinitionPatch};
 
@@ -38,11 +41,21 @@ pub use rpfm_ui_common::settings::*;
 use crate::GAME_SELECTED;
 use crate::SUPPORTED_GAMES;
 use crate::updater_ui::STABLE;
+use crate::utils::{ERROR_UNPRESSED_DARK, ERROR_UNPRESSED_LIGHT, INFO_UNPRESSED_DARK, INFO_UNPRESSED_LIGHT, WARNING_UNPRESSED_DARK, WARNING_UNPRESSED_LIGHT};
 
 /// Key of the MyMod path in the settings";
 pub const MYMOD_BASE_PATH: &str = "mymods_base_path";
 pub const SECONDARY_PATH: &str = "secondary_path";
 
+/// Name of the file containing the favorite tables of all games.
+const FAVORITE_TABLES_FILE: &str = "favorite_tables.json";
+
+/// Suffix of the per-game setting containing the diagnostic types unchecked in the diagnostics panel for said game.
+const DIAGNOSTICS_IGNORED_SUFFIX: &str = "_diagnostics_ignored";
+
+/// Suffix of the per-game setting containing all the diagnostic types known when the ignored ones were saved.
+const DIAGNOSTICS_KNOWN_SUFFIX: &str = "_diagnostics_known";
+
 const DEPENDENCIES_FOLDER: &str = "dependencies";
 const TABLE_PATCHES_FOLDER: &str = "table_patches";
 const TABLE_PROFILES_FOLDER: &str = "table_profiles";
@@ -98,6 +111,12 @@ pub unsafe fn init_settings(main_window: &QPtr<QMainWindow>) {
                 set_setting_if_new_string(&q_settings, &ak_key, &ak_path);
             }
         }
+
+        // Empty means the built-in loc key validation rule.
+        set_setting_if_new_string(&q_settings, &(game_key.to_owned() + "_loc_key_pattern"), "");
+
+        // Empty means the built-in loc formatting rules.
+        set_setting_if_new_string(&q_settings, &(game_key.to_owned() + "_loc_formatting_rules"), "");
     }
 
     // General Settings.
@@ -106,6 +125,8 @@ pub unsafe fn init_settings(main_window: &QPtr<QMainWindow>) {
     set_setting_if_new_string(&q_settings, "update_channel", STABLE);
     set_setting_if_new_int(&q_settings, "autosave_amount", 10);
     set_setting_if_new_int(&q_settings, "autosave_interval", 5);
+    set_setting_if_new_int(&q_settings, "max_decode_bytes", 0);
+    set_setting_if_new_string(&q_settings, "diagnostics_referenced_path_rules", "xml:path,xml:image");
 
     let font = QApplication::font();
     let font_name = font.family().to_std_string();
@@ -130,12 +151,20 @@ pub unsafe fn init_settings(main_window: &QPtr<QMainWindow>) {
     set_setting_if_new_bool(&q_settings, "packfile_treeview_resize_to_fit", false);
     set_setting_if_new_bool(&q_settings, "expand_treeview_when_adding_items", true);
     set_setting_if_new_bool(&q_settings, "use_right_size_markers", false);
+    set_setting_if_new_bool(&q_settings, "table_mark_tsv_import_changes", true);
+    set_setting_if_new_bool(&q_settings, "tsv_trim_trailing_newline", false);
+    set_setting_if_new_bool(&q_settings, "normalize_floats_on_edit", false);
+    set_setting_if_new_int(&q_settings, "normalize_floats_precision", DEFAULT_FLOAT_PRECISION as i32);
+    set_setting_if_new_int(&q_settings, "tsv_float_precision", DEFAULT_FLOAT_PRECISION as i32);
+    set_setting_if_new_int(&q_settings, "tsv_colour_format", 0);
     set_setting_if_new_bool(&q_settings, "disable_file_previews", false);
+    set_setting_if_new_bool(&q_settings, "deduplicate_compression", false);
     set_setting_if_new_bool(&q_settings, "include_base_folder_on_add_from_folder", true);
     set_setting_if_new_bool(&q_settings, "delete_empty_folders_on_delete", true);
     set_setting_if_new_bool(&q_settings, "autosave_folder_size_warning_triggered", false);
     set_setting_if_new_bool(&q_settings, "ignore_game_files_in_ak", false);
     set_setting_if_new_bool(&q_settings, "enable_multifolder_filepicker", false);
+    set_setting_if_new_bool(&q_settings, "auto_refresh_dependency_cache", false);
 
     // Table Settings.
     set_setting_if_new_bool(&q_settings, "adjust_columns_to_content", true);
@@ -155,10 +184,14 @@ pub unsafe fn init_settings(main_window: &QPtr<QMainWindow>) {
     set_setting_if_new_bool(&q_settings, "enable_rigidmodel_editor", true);
     set_setting_if_new_bool(&q_settings, "enable_unit_editor", false);
     set_setting_if_new_bool(&q_settings, "enable_esf_editor", false);
+    set_setting_if_new_bool(&q_settings, "enable_timing_logs", false);
 
     // Diagnostics Settings
     set_setting_if_new_bool(&q_settings, "diagnostics_trigger_on_open", true);
     set_setting_if_new_bool(&q_settings, "diagnostics_trigger_on_table_edit", true);
+    set_setting_if_new_int(&q_settings, "diagnostics_trigger_on_table_edit_delay", 500);
+    set_setting_if_new_bool(&q_settings, "run_diagnostics_on_open", false);
+    set_setting_if_new_int(&q_settings, "run_diagnostics_on_open_max_size", 512);
 
     // Colours.
     set_setting_if_new_string(&q_settings, "colour_light_table_added", "#87ca00");
@@ -171,6 +204,12 @@ pub unsafe fn init_settings(main_window: &QPtr<QMainWindow>) {
     set_setting_if_new_string(&q_settings, "colour_dark_diagnostic_error", "#ff0000");
     set_setting_if_new_string(&q_settings, "colour_dark_diagnostic_warning", "#cece67");
     set_setting_if_new_string(&q_settings, "colour_dark_diagnostic_info", "#55aaff");
+    set_setting_if_new_string(&q_settings, "colour_light_diagnostics_panel_error", ERROR_UNPRESSED_LIGHT);
+    set_setting_if_new_string(&q_settings, "colour_light_diagnostics_panel_warning", WARNING_UNPRESSED_LIGHT);
+    set_setting_if_new_string(&q_settings, "colour_light_diagnostics_panel_info", INFO_UNPRESSED_LIGHT);
+    set_setting_if_new_string(&q_settings, "colour_dark_diagnostics_panel_error", ERROR_UNPRESSED_DARK);
+    set_setting_if_new_string(&q_settings, "colour_dark_diagnostics_panel_warning", WARNING_UNPRESSED_DARK);
+    set_setting_if_new_string(&q_settings, "colour_dark_diagnostics_panel_info", INFO_UNPRESSED_DARK);
 
     q_settings.sync();
 }
@@ -288,3 +327,69 @@ pub fn assembly_kit_path() -> Result<PathBuf> {
         _ => Err(RLibError::AssemblyKitUnsupportedVersion(version).into())
     }
 }
+
+//-------------------------------------------------------------------------------//
+//                            Favorite tables
+//-------------------------------------------------------------------------------//
+
+/// This function returns the path of the file with the favorite tables.
+pub fn favorite_tables_path() -> Result<PathBuf> {
+    Ok(config_path()?.join(FAVORITE_TABLES_FILE))
+}
+
+/// This function loads the favorite tables of all games. If they cannot be loaded, it returns an empty list.
+fn load_favorite_tables() -> FavoriteTables {
+    favorite_tables_path().ok()
+        .and_then(|path| FavoriteTables::load(&path).ok())
+        .unwrap_or_default()
+}
+
+/// This function returns the names of the favorite tables of the provided game.
+pub fn favorite_tables(game_key: &str) -> BTreeSet<String> {
+    load_favorite_tables().tables_for_game(game_key)
+}
+
+/// This function adds the provided tables to the favorite tables of the provided game, and saves them to disk.
+pub fn add_favorite_tables(game_key: &str, table_names: &[String]) -> Result<()> {
+    let mut favorites = load_favorite_tables();
+    for table_name in table_names {
+        favorites.add(game_key, table_name);
+    }
+
+    favorites.save(&favorite_tables_path()?).map_err(From::from)
+}
+
+/// This function removes the provided tables from the favorite tables of the provided game, and saves them to disk.
+pub fn remove_favorite_tables(game_key: &str, table_names: &[String]) -> Result<()> {
+    let mut favorites = load_favorite_tables();
+    for table_name in table_names {
+        favorites.remove(game_key, table_name);
+    }
+
+    favorites.save(&favorite_tables_path()?).map_err(From::from)
+}
+
+//-------------------------------------------------------------------------------//
+//                            Ignored diagnostics
+//-------------------------------------------------------------------------------//
+
+/// This function returns the diagnostic types ignored for the provided game, and all the diagnostic types known when they were saved.
+pub fn diagnostics_ignored(game_key: &str) -> (BTreeSet<String>, BTreeSet<String>) {
+    let split = |value: String| value.split(',')
+        .map(|report_type| report_type.trim())
+        .filter(|report_type| !report_type.is_empty())
+        .map(|report_type| report_type.to_owned())
+        .collect::<BTreeSet<_>>();
+
+    let ignored = split(setting_string(&(game_key.to_owned() + DIAGNOSTICS_IGNORED_SUFFIX)));
+    let known = split(setting_string(&(game_key.to_owned() + DIAGNOSTICS_KNOWN_SUFFIX)));
+    (ignored, known)
+}
+
+/// This function replaces the diagnostic types ignored for the provided game.
+///
+/// `known` must contain all the diagnostic types the UI knows about, so types added in future versions can be told apart from checked ones.
+pub fn set_diagnostics_ignored(game_key: &str, ignored: &[String], known: &[String]) {
+    set_setting_string(&(game_key.to_owned() + DIAGNOSTICS_IGNORED_SUFFIX), &ignored.join(","));
+    set_setting_string(&(game_key.to_owned() + DIAGNOSTICS_KNOWN_SUFFIX), &known.join(","));
+}
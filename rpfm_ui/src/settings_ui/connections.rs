@@ -55,4 +55,10 @@ pub unsafe fn set_connections(settings_ui: &SettingsUI, slots: &SettingsUISlots)
     settings_ui.ui_table_colour_dark_diagnostic_error_button.released().connect(&slots.select_colour_dark_diagnostic_error);
     settings_ui.ui_table_colour_dark_diagnostic_warning_button.released().connect(&slots.select_colour_dark_diagnostic_warning);
     settings_ui.ui_table_colour_dark_diagnostic_info_button.released().connect(&slots.select_colour_dark_diagnostic_info);
+    settings_ui.ui_table_colour_light_diagnostics_panel_error_button.released().connect(&slots.select_colour_light_diagnostics_panel_error);
+    settings_ui.ui_table_colour_light_diagnostics_panel_warning_button.released().connect(&slots.select_colour_light_diagnostics_panel_warning);
+    settings_ui.ui_table_colour_light_diagnostics_panel_info_button.released().connect(&slots.select_colour_light_diagnostics_panel_info);
+    settings_ui.ui_table_colour_dark_diagnostics_panel_error_button.released().connect(&slots.select_colour_dark_diagnostics_panel_error);
+    settings_ui.ui_table_colour_dark_diagnostics_panel_warning_button.released().connect(&slots.select_colour_dark_diagnostics_panel_warning);
+    settings_ui.ui_table_colour_dark_diagnostics_panel_info_button.released().connect(&slots.select_colour_dark_diagnostics_panel_info);
 }
@@ -45,9 +45,13 @@ use std::collections::BTreeMap;
 use std::path::Path;
 use std::rc::Rc;
 
+use rpfm_extensions::diagnostics::table::DEFAULT_LOC_FORMATTING_RULES;
+use rpfm_lib::files::table::{DEFAULT_FLOAT_PRECISION, MAX_FLOAT_PRECISION};
 use rpfm_lib::games::supported_games::*;
+use rpfm_lib::integrations::log::Logger;
 
-use rpfm_ui_common::locale::{Locale, qtr, qtre};
+use rpfm_ui_common::locale::{Locale, qtr, qtre, tre};
+use rpfm_ui_common::utils::show_dialog;
 use rpfm_ui_common::tools::*;
 
 use crate::app_ui::AppUI;
@@ -94,6 +98,9 @@ pub struct SettingsUI {
     paths_asskit_line_edits: BTreeMap<String, QBox<QLineEdit>>,
     paths_asskit_buttons: BTreeMap<String, QBox<QPushButton>>,
 
+    paths_loc_key_pattern_line_edits: BTreeMap<String, QBox<QLineEdit>>,
+    paths_loc_formatting_rules_line_edits: BTreeMap<String, QBox<QLineEdit>>,
+
     //-------------------------------------------------------------------------------//
     // `General` section of the `Settings` dialog.
     //-------------------------------------------------------------------------------//
@@ -105,6 +112,7 @@ pub struct SettingsUI {
     extra_packfile_use_lazy_loading_label: QBox<QLabel>,
     extra_packfile_disable_uuid_regeneration_on_db_tables_label: QBox<QLabel>,
     extra_packfile_disable_file_previews_label: QBox<QLabel>,
+    extra_packfile_deduplicate_compression_label: QBox<QLabel>,
     ui_global_use_dark_theme_label: QBox<QLabel>,
     ui_window_start_maximized_label: QBox<QLabel>,
     general_packfile_treeview_expand_treeview_when_adding_items_label: QBox<QLabel>,
@@ -112,6 +120,9 @@ pub struct SettingsUI {
     delete_empty_folders_on_delete_label: QBox<QLabel>,
     ignore_game_files_in_ak_label: QBox<QLabel>,
     enable_multifolder_filepicker_label: QBox<QLabel>,
+    auto_refresh_dependency_cache_label: QBox<QLabel>,
+    max_decode_bytes_label: QBox<QLabel>,
+    diagnostics_referenced_path_rules_label: QBox<QLabel>,
 
     general_language_combobox: QBox<QComboBox>,
     extra_global_default_game_combobox: QBox<QComboBox>,
@@ -127,6 +138,7 @@ pub struct SettingsUI {
     extra_packfile_use_lazy_loading_checkbox: QBox<QCheckBox>,
     extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox: QBox<QCheckBox>,
     extra_packfile_disable_file_previews_checkbox: QBox<QCheckBox>,
+    extra_packfile_deduplicate_compression_checkbox: QBox<QCheckBox>,
     ui_global_use_dark_theme_checkbox: QBox<QCheckBox>,
     ui_window_start_maximized_checkbox: QBox<QCheckBox>,
     ui_window_hide_background_icon_checkbox: QBox<QCheckBox>,
@@ -136,6 +148,9 @@ pub struct SettingsUI {
     delete_empty_folders_on_delete_checkbox: QBox<QCheckBox>,
     ignore_game_files_in_ak_checkbox: QBox<QCheckBox>,
     enable_multifolder_filepicker_checkbox: QBox<QCheckBox>,
+    auto_refresh_dependency_cache_checkbox: QBox<QCheckBox>,
+    max_decode_bytes_spinbox: QBox<QSpinBox>,
+    diagnostics_referenced_path_rules_line_edit: QBox<QLineEdit>,
 
     font_data: Rc<RefCell<(String, i32)>>,
 
@@ -147,6 +162,12 @@ pub struct SettingsUI {
     ui_table_extend_last_column_label: QBox<QLabel>,
     ui_table_tight_table_mode_label: QBox<QLabel>,
     ui_table_use_right_size_markers_label: QBox<QLabel>,
+    ui_table_mark_tsv_import_changes_label: QBox<QLabel>,
+    ui_table_tsv_trim_trailing_newline_label: QBox<QLabel>,
+    ui_table_normalize_floats_on_edit_label: QBox<QLabel>,
+    ui_table_normalize_floats_precision_label: QBox<QLabel>,
+    ui_table_tsv_float_precision_label: QBox<QLabel>,
+    ui_table_tsv_colour_format_label: QBox<QLabel>,
 
     ui_table_adjust_columns_to_content_checkbox: QBox<QCheckBox>,
     ui_table_disable_combos_checkbox: QBox<QCheckBox>,
@@ -158,6 +179,12 @@ pub struct SettingsUI {
     ui_table_use_right_size_markers_checkbox: QBox<QCheckBox>,
     ui_table_enable_lookups_checkbox: QBox<QCheckBox>,
     ui_table_enable_icons_checkbox: QBox<QCheckBox>,
+    ui_table_mark_tsv_import_changes_checkbox: QBox<QCheckBox>,
+    ui_table_tsv_trim_trailing_newline_checkbox: QBox<QCheckBox>,
+    ui_table_normalize_floats_on_edit_checkbox: QBox<QCheckBox>,
+    ui_table_normalize_floats_precision_spinbox: QBox<QSpinBox>,
+    ui_table_tsv_float_precision_spinbox: QBox<QSpinBox>,
+    ui_table_tsv_colour_format_combobox: QBox<QComboBox>,
 
     ui_table_colour_light_table_added_button: QBox<QPushButton>,
     ui_table_colour_light_table_modified_button: QBox<QPushButton>,
@@ -169,6 +196,12 @@ pub struct SettingsUI {
     ui_table_colour_dark_diagnostic_error_button: QBox<QPushButton>,
     ui_table_colour_dark_diagnostic_warning_button: QBox<QPushButton>,
     ui_table_colour_dark_diagnostic_info_button: QBox<QPushButton>,
+    ui_table_colour_light_diagnostics_panel_error_button: QBox<QPushButton>,
+    ui_table_colour_light_diagnostics_panel_warning_button: QBox<QPushButton>,
+    ui_table_colour_light_diagnostics_panel_info_button: QBox<QPushButton>,
+    ui_table_colour_dark_diagnostics_panel_error_button: QBox<QPushButton>,
+    ui_table_colour_dark_diagnostics_panel_warning_button: QBox<QPushButton>,
+    ui_table_colour_dark_diagnostics_panel_info_button: QBox<QPushButton>,
 
     //-------------------------------------------------------------------------------//
     // `Debug` section of the `Settings` dialog.
@@ -181,6 +214,8 @@ pub struct SettingsUI {
     debug_enable_unit_editor_label: QBox<QLabel>,
     debug_enable_unit_editor_checkbox: QBox<QCheckBox>,
     debug_enable_esf_editor_checkbox: QBox<QCheckBox>,
+    debug_enable_timing_logs_label: QBox<QLabel>,
+    debug_enable_timing_logs_checkbox: QBox<QCheckBox>,
 
     debug_clear_dependencies_cache_folder_button: QBox<QPushButton>,
     debug_clear_autosave_folder_button: QBox<QPushButton>,
@@ -195,9 +230,15 @@ pub struct SettingsUI {
     //-------------------------------------------------------------------------------//
     diagnostics_diagnostics_trigger_on_open_label: QBox<QLabel>,
     diagnostics_diagnostics_trigger_on_table_edit_label: QBox<QLabel>,
+    diagnostics_run_diagnostics_on_open_label: QBox<QLabel>,
+    diagnostics_run_diagnostics_on_open_max_size_label: QBox<QLabel>,
+    diagnostics_diagnostics_trigger_on_table_edit_delay_label: QBox<QLabel>,
 
     diagnostics_diagnostics_trigger_on_open_checkbox: QBox<QCheckBox>,
     diagnostics_diagnostics_trigger_on_table_edit_checkbox: QBox<QCheckBox>,
+    diagnostics_run_diagnostics_on_open_checkbox: QBox<QCheckBox>,
+    diagnostics_run_diagnostics_on_open_max_size_spinbox: QBox<QSpinBox>,
+    diagnostics_diagnostics_trigger_on_table_edit_delay_spinbox: QBox<QSpinBox>,
 
     //-------------------------------------------------------------------------------//
     // `ButtonBox` section of the `Settings` dialog.
@@ -283,6 +324,9 @@ impl SettingsUI {
         let mut paths_asskit_line_edits = BTreeMap::new();
         let mut paths_asskit_buttons = BTreeMap::new();
 
+        let mut paths_loc_key_pattern_line_edits = BTreeMap::new();
+        let mut paths_loc_formatting_rules_line_edits = BTreeMap::new();
+
         for (index, game_supported) in SUPPORTED_GAMES.games_sorted().iter().enumerate() {
             let spoiler = new_spoiler_safe(&QString::from_std_str(game_supported.display_name()).as_ptr(), 200, &paths_frame.as_ptr().static_upcast());
 
@@ -321,6 +365,26 @@ impl SettingsUI {
                 paths_asskit_buttons.insert(game_key.to_owned(), asskit_button);
             }
 
+            let loc_key_pattern_label = QLabel::from_q_string_q_widget(&qtr("settings_loc_key_pattern_label"), &spoiler);
+            let loc_key_pattern_line_edit = QLineEdit::from_q_widget(&spoiler);
+            loc_key_pattern_line_edit.set_placeholder_text(&qtr("settings_loc_key_pattern_line_ph"));
+            loc_key_pattern_line_edit.set_tool_tip(&qtr("tt_settings_loc_key_pattern"));
+
+            game_path_layout.add_widget_5a(&loc_key_pattern_label, 2, 0, 1, 1);
+            game_path_layout.add_widget_5a(&loc_key_pattern_line_edit, 2, 1, 1, 2);
+
+            paths_loc_key_pattern_line_edits.insert(game_key.to_owned(), loc_key_pattern_line_edit);
+
+            let loc_formatting_rules_label = QLabel::from_q_string_q_widget(&qtr("settings_loc_formatting_rules_label"), &spoiler);
+            let loc_formatting_rules_line_edit = QLineEdit::from_q_widget(&spoiler);
+            loc_formatting_rules_line_edit.set_placeholder_text(&QString::from_std_str(DEFAULT_LOC_FORMATTING_RULES));
+            loc_formatting_rules_line_edit.set_tool_tip(&qtr("tt_settings_loc_formatting_rules"));
+
+            game_path_layout.add_widget_5a(&loc_formatting_rules_label, 3, 0, 1, 1);
+            game_path_layout.add_widget_5a(&loc_formatting_rules_line_edit, 3, 1, 1, 2);
+
+            paths_loc_formatting_rules_line_edits.insert(game_key.to_owned(), loc_formatting_rules_line_edit);
+
             set_spoiler_layout_safe(&spoiler.as_ptr(), &game_path_layout.as_ptr().static_upcast());
             main_paths_grid.add_widget_5a(&spoiler, index as i32 + 1, 0, 1, 1);
             paths_spoilers.insert(game_key.to_owned(), spoiler);
@@ -419,6 +483,9 @@ impl SettingsUI {
         let extra_packfile_disable_file_previews_label = QLabel::from_q_string_q_widget(&qtr("settings_disable_file_previews"), &general_frame);
         let extra_packfile_disable_file_previews_checkbox = QCheckBox::from_q_widget(&general_frame);
 
+        let extra_packfile_deduplicate_compression_label = QLabel::from_q_string_q_widget(&qtr("settings_deduplicate_compression"), &general_frame);
+        let extra_packfile_deduplicate_compression_checkbox = QCheckBox::from_q_widget(&general_frame);
+
         let ui_global_use_dark_theme_label = QLabel::from_q_string_q_widget(&qtr("settings_ui_dark_theme"), &general_frame);
         let ui_global_use_dark_theme_checkbox = QCheckBox::from_q_widget(&general_frame);
 
@@ -447,6 +514,14 @@ impl SettingsUI {
 
         let enable_multifolder_filepicker_label = QLabel::from_q_string_q_widget(&qtr("enable_multifolder_filepicker"), &general_frame);
         let enable_multifolder_filepicker_checkbox = QCheckBox::from_q_widget(&general_frame);
+        let auto_refresh_dependency_cache_label = QLabel::from_q_string_q_widget(&qtr("auto_refresh_dependency_cache"), &general_frame);
+        let auto_refresh_dependency_cache_checkbox = QCheckBox::from_q_widget(&general_frame);
+        let max_decode_bytes_label = QLabel::from_q_string_q_widget(&qtr("settings_max_decode_bytes"), &general_frame);
+        let max_decode_bytes_spinbox = QSpinBox::new_1a(&general_frame);
+        max_decode_bytes_spinbox.set_maximum(i32::MAX);
+        let diagnostics_referenced_path_rules_label = QLabel::from_q_string_q_widget(&qtr("settings_diagnostics_referenced_path_rules"), &general_frame);
+        let diagnostics_referenced_path_rules_line_edit = QLineEdit::from_q_widget(&general_frame);
+        diagnostics_referenced_path_rules_line_edit.set_placeholder_text(&qtr("settings_diagnostics_referenced_path_rules_ph"));
 
         // Adding to the grid.
         general_grid.add_widget_5a(&general_language_label, 0, 0, 1, 1);
@@ -485,6 +560,9 @@ impl SettingsUI {
         general_grid.add_widget_5a(&extra_packfile_disable_file_previews_label, 12, 0, 1, 1);
         general_grid.add_widget_5a(&extra_packfile_disable_file_previews_checkbox, 12, 1, 1, 1);
 
+        general_grid.add_widget_5a(&extra_packfile_deduplicate_compression_label, 13, 0, 1, 1);
+        general_grid.add_widget_5a(&extra_packfile_deduplicate_compression_checkbox, 13, 1, 1, 1);
+
         general_grid.add_widget_5a(&ui_global_use_dark_theme_label, 14, 0, 1, 1);
         general_grid.add_widget_5a(&ui_global_use_dark_theme_checkbox, 14, 1, 1, 1);
 
@@ -512,6 +590,15 @@ impl SettingsUI {
         general_grid.add_widget_5a(&enable_multifolder_filepicker_label, 21, 0, 1, 1);
         general_grid.add_widget_5a(&enable_multifolder_filepicker_checkbox, 21, 1, 1, 1);
 
+        general_grid.add_widget_5a(&auto_refresh_dependency_cache_label, 22, 0, 1, 1);
+        general_grid.add_widget_5a(&auto_refresh_dependency_cache_checkbox, 22, 1, 1, 1);
+
+        general_grid.add_widget_5a(&max_decode_bytes_label, 23, 0, 1, 1);
+        general_grid.add_widget_5a(&max_decode_bytes_spinbox, 23, 1, 1, 1);
+
+        general_grid.add_widget_5a(&diagnostics_referenced_path_rules_label, 24, 0, 1, 1);
+        general_grid.add_widget_5a(&diagnostics_referenced_path_rules_line_edit, 24, 1, 1, 1);
+
         settings_grid.add_widget_5a(&general_frame, 2, 0, 2, 1);
 
         //-----------------------------------------------//
@@ -556,6 +643,27 @@ impl SettingsUI {
 
         let ui_table_enable_icons_label = QLabel::from_q_string_q_widget(&qtr("settings_enable_icons"), &ui_table_view_frame);
         let ui_table_enable_icons_checkbox = QCheckBox::from_q_widget(&ui_table_view_frame);
+        let ui_table_mark_tsv_import_changes_label = QLabel::from_q_string_q_widget(&qtr("settings_mark_tsv_import_changes"), &ui_table_view_frame);
+        let ui_table_mark_tsv_import_changes_checkbox = QCheckBox::from_q_widget(&ui_table_view_frame);
+        let ui_table_tsv_trim_trailing_newline_label = QLabel::from_q_string_q_widget(&qtr("settings_tsv_trim_trailing_newline"), &ui_table_view_frame);
+        let ui_table_tsv_trim_trailing_newline_checkbox = QCheckBox::from_q_widget(&ui_table_view_frame);
+        let ui_table_normalize_floats_on_edit_label = QLabel::from_q_string_q_widget(&qtr("settings_normalize_floats_on_edit"), &ui_table_view_frame);
+        let ui_table_normalize_floats_on_edit_checkbox = QCheckBox::from_q_widget(&ui_table_view_frame);
+        let ui_table_normalize_floats_precision_label = QLabel::from_q_string_q_widget(&qtr("settings_normalize_floats_precision"), &ui_table_view_frame);
+        let ui_table_normalize_floats_precision_spinbox = QSpinBox::new_1a(&ui_table_view_frame);
+        ui_table_normalize_floats_precision_spinbox.set_minimum(DEFAULT_FLOAT_PRECISION as i32);
+        ui_table_normalize_floats_precision_spinbox.set_maximum(MAX_FLOAT_PRECISION as i32);
+        let ui_table_tsv_float_precision_label = QLabel::from_q_string_q_widget(&qtr("settings_tsv_float_precision"), &ui_table_view_frame);
+        let ui_table_tsv_float_precision_spinbox = QSpinBox::new_1a(&ui_table_view_frame);
+        ui_table_tsv_float_precision_spinbox.set_minimum(DEFAULT_FLOAT_PRECISION as i32);
+        ui_table_tsv_float_precision_spinbox.set_maximum(MAX_FLOAT_PRECISION as i32);
+        let ui_table_tsv_colour_format_label = QLabel::from_q_string_q_widget(&qtr("settings_tsv_colour_format"), &ui_table_view_frame);
+        let ui_table_tsv_colour_format_combobox = QComboBox::new_1a(&ui_table_view_frame);
+
+        // Same order as the `tsv_colour_format` setting values.
+        ui_table_tsv_colour_format_combobox.add_item_q_string(&qtr("settings_tsv_colour_format_as_is"));
+        ui_table_tsv_colour_format_combobox.add_item_q_string(&qtr("settings_tsv_colour_format_uppercase"));
+        ui_table_tsv_colour_format_combobox.add_item_q_string(&qtr("settings_tsv_colour_format_lowercase"));
 
         ui_table_view_grid.add_widget_5a(&ui_table_adjust_columns_to_content_label, 0, 0, 1, 2);
         ui_table_view_grid.add_widget_5a(&ui_table_adjust_columns_to_content_checkbox, 0, 2, 1, 1);
@@ -590,6 +698,24 @@ impl SettingsUI {
         ui_table_view_grid.add_widget_5a(&ui_table_enable_icons_label, 10, 0, 1, 2);
         ui_table_view_grid.add_widget_5a(&ui_table_enable_icons_checkbox, 10, 2, 1, 1);
 
+        ui_table_view_grid.add_widget_5a(&ui_table_mark_tsv_import_changes_label, 11, 0, 1, 2);
+        ui_table_view_grid.add_widget_5a(&ui_table_mark_tsv_import_changes_checkbox, 11, 2, 1, 1);
+
+        ui_table_view_grid.add_widget_5a(&ui_table_tsv_trim_trailing_newline_label, 12, 0, 1, 2);
+        ui_table_view_grid.add_widget_5a(&ui_table_tsv_trim_trailing_newline_checkbox, 12, 2, 1, 1);
+
+        ui_table_view_grid.add_widget_5a(&ui_table_normalize_floats_on_edit_label, 13, 0, 1, 2);
+        ui_table_view_grid.add_widget_5a(&ui_table_normalize_floats_on_edit_checkbox, 13, 2, 1, 1);
+
+        ui_table_view_grid.add_widget_5a(&ui_table_normalize_floats_precision_label, 14, 0, 1, 2);
+        ui_table_view_grid.add_widget_5a(&ui_table_normalize_floats_precision_spinbox, 14, 2, 1, 1);
+
+        ui_table_view_grid.add_widget_5a(&ui_table_tsv_float_precision_label, 15, 0, 1, 2);
+        ui_table_view_grid.add_widget_5a(&ui_table_tsv_float_precision_spinbox, 15, 2, 1, 1);
+
+        ui_table_view_grid.add_widget_5a(&ui_table_tsv_colour_format_label, 16, 0, 1, 2);
+        ui_table_view_grid.add_widget_5a(&ui_table_tsv_colour_format_combobox, 16, 2, 1, 1);
+
         let settings_ui_table_colour_light_label = QLabel::from_q_string_q_widget(&qtr("settings_ui_table_colour_light_label"), &ui_table_view_frame);
         let settings_ui_table_colour_dark_label = QLabel::from_q_string_q_widget(&qtr("settings_ui_table_colour_dark_label"), &ui_table_view_frame);
 
@@ -603,6 +729,12 @@ impl SettingsUI {
         ui_table_colour_diagnostic_error_label.set_alignment(QFlags::from(AlignmentFlag::AlignCenter));
         ui_table_colour_diagnostic_warning_label.set_alignment(QFlags::from(AlignmentFlag::AlignCenter));
         ui_table_colour_diagnostic_info_label.set_alignment(QFlags::from(AlignmentFlag::AlignCenter));
+        let ui_table_colour_diagnostics_panel_error_label = QLabel::from_q_string_q_widget(&qtr("settings_ui_table_colour_diagnostics_panel_error_label"), &ui_table_view_frame);
+        let ui_table_colour_diagnostics_panel_warning_label = QLabel::from_q_string_q_widget(&qtr("settings_ui_table_colour_diagnostics_panel_warning_label"), &ui_table_view_frame);
+        let ui_table_colour_diagnostics_panel_info_label = QLabel::from_q_string_q_widget(&qtr("settings_ui_table_colour_diagnostics_panel_info_label"), &ui_table_view_frame);
+        ui_table_colour_diagnostics_panel_error_label.set_alignment(QFlags::from(AlignmentFlag::AlignCenter));
+        ui_table_colour_diagnostics_panel_warning_label.set_alignment(QFlags::from(AlignmentFlag::AlignCenter));
+        ui_table_colour_diagnostics_panel_info_label.set_alignment(QFlags::from(AlignmentFlag::AlignCenter));
 
         let ui_table_colour_light_table_added_button = QPushButton::from_q_widget(&ui_table_view_frame);
         let ui_table_colour_light_table_modified_button = QPushButton::from_q_widget(&ui_table_view_frame);
@@ -614,6 +746,12 @@ impl SettingsUI {
         let ui_table_colour_dark_diagnostic_error_button = QPushButton::from_q_widget(&ui_table_view_frame);
         let ui_table_colour_dark_diagnostic_warning_button = QPushButton::from_q_widget(&ui_table_view_frame);
         let ui_table_colour_dark_diagnostic_info_button = QPushButton::from_q_widget(&ui_table_view_frame);
+        let ui_table_colour_light_diagnostics_panel_error_button = QPushButton::from_q_widget(&ui_table_view_frame);
+        let ui_table_colour_light_diagnostics_panel_warning_button = QPushButton::from_q_widget(&ui_table_view_frame);
+        let ui_table_colour_light_diagnostics_panel_info_button = QPushButton::from_q_widget(&ui_table_view_frame);
+        let ui_table_colour_dark_diagnostics_panel_error_button = QPushButton::from_q_widget(&ui_table_view_frame);
+        let ui_table_colour_dark_diagnostics_panel_warning_button = QPushButton::from_q_widget(&ui_table_view_frame);
+        let ui_table_colour_dark_diagnostics_panel_info_button = QPushButton::from_q_widget(&ui_table_view_frame);
 
         ui_table_colour_light_table_added_button.set_auto_fill_background(true);
         ui_table_colour_light_table_modified_button.set_auto_fill_background(true);
@@ -625,6 +763,12 @@ impl SettingsUI {
         ui_table_colour_dark_diagnostic_error_button.set_auto_fill_background(true);
         ui_table_colour_dark_diagnostic_warning_button.set_auto_fill_background(true);
         ui_table_colour_dark_diagnostic_info_button.set_auto_fill_background(true);
+        ui_table_colour_light_diagnostics_panel_error_button.set_auto_fill_background(true);
+        ui_table_colour_light_diagnostics_panel_warning_button.set_auto_fill_background(true);
+        ui_table_colour_light_diagnostics_panel_info_button.set_auto_fill_background(true);
+        ui_table_colour_dark_diagnostics_panel_error_button.set_auto_fill_background(true);
+        ui_table_colour_dark_diagnostics_panel_warning_button.set_auto_fill_background(true);
+        ui_table_colour_dark_diagnostics_panel_info_button.set_auto_fill_background(true);
 
         ui_table_view_grid.add_widget_5a(&settings_ui_table_colour_light_label, 90, 0, 1, 1);
         ui_table_view_grid.add_widget_5a(&settings_ui_table_colour_dark_label, 90, 2, 1, 1);
@@ -646,6 +790,16 @@ impl SettingsUI {
         ui_table_view_grid.add_widget_5a(&ui_table_colour_dark_diagnostic_warning_button, 96, 2, 1, 1);
         ui_table_view_grid.add_widget_5a(&ui_table_colour_dark_diagnostic_info_button, 97, 2, 1, 1);
 
+        ui_table_view_grid.add_widget_5a(&ui_table_colour_diagnostics_panel_error_label, 99, 1, 1, 1);
+        ui_table_view_grid.add_widget_5a(&ui_table_colour_diagnostics_panel_warning_label, 100, 1, 1, 1);
+        ui_table_view_grid.add_widget_5a(&ui_table_colour_diagnostics_panel_info_label, 101, 1, 1, 1);
+        ui_table_view_grid.add_widget_5a(&ui_table_colour_light_diagnostics_panel_error_button, 99, 0, 1, 1);
+        ui_table_view_grid.add_widget_5a(&ui_table_colour_light_diagnostics_panel_warning_button, 100, 0, 1, 1);
+        ui_table_view_grid.add_widget_5a(&ui_table_colour_light_diagnostics_panel_info_button, 101, 0, 1, 1);
+        ui_table_view_grid.add_widget_5a(&ui_table_colour_dark_diagnostics_panel_error_button, 99, 2, 1, 1);
+        ui_table_view_grid.add_widget_5a(&ui_table_colour_dark_diagnostics_panel_warning_button, 100, 2, 1, 1);
+        ui_table_view_grid.add_widget_5a(&ui_table_colour_dark_diagnostics_panel_info_button, 101, 2, 1, 1);
+
         settings_grid.add_widget_5a(&ui_table_view_frame, 2, 1, 1, 1);
 
         //-----------------------------------------------//
@@ -668,6 +822,8 @@ impl SettingsUI {
         let debug_enable_rigidmodel_editor_checkbox = QCheckBox::from_q_widget(&debug_frame);
         let debug_enable_unit_editor_checkbox = QCheckBox::from_q_widget(&debug_frame);
         let debug_enable_esf_editor_checkbox = QCheckBox::from_q_widget(&debug_frame);
+        let debug_enable_timing_logs_label = QLabel::from_q_string_q_widget(&qtr("settings_debug_enable_timing_logs"), &debug_frame);
+        let debug_enable_timing_logs_checkbox = QCheckBox::from_q_widget(&debug_frame);
 
         let extra_packfile_use_lazy_loading_label = QLabel::from_q_string_q_widget(&qtr("settings_use_lazy_loading"), &debug_frame);
         let extra_packfile_use_lazy_loading_checkbox = QCheckBox::from_q_widget(&debug_frame);
@@ -693,6 +849,9 @@ impl SettingsUI {
         debug_grid.add_widget_5a(&debug_enable_esf_editor_label, 6, 0, 1, 2);
         debug_grid.add_widget_5a(&debug_enable_esf_editor_checkbox, 6, 2, 1, 1);
 
+        debug_grid.add_widget_5a(&debug_enable_timing_logs_label, 7, 0, 1, 2);
+        debug_grid.add_widget_5a(&debug_enable_timing_logs_checkbox, 7, 2, 1, 1);
+
         debug_grid.add_widget_5a(&extra_packfile_use_lazy_loading_label, 11, 0, 1, 2);
         debug_grid.add_widget_5a(&extra_packfile_use_lazy_loading_checkbox, 11, 2, 1, 1);
 
@@ -715,9 +874,18 @@ impl SettingsUI {
 
         let diagnostics_diagnostics_trigger_on_open_label = QLabel::from_q_string_q_widget(&qtr("settings_diagnostics_trigger_on_open"), &diagnostics_frame);
         let diagnostics_diagnostics_trigger_on_table_edit_label = QLabel::from_q_string_q_widget(&qtr("settings_diagnostics_trigger_on_edit"), &diagnostics_frame);
+        let diagnostics_run_diagnostics_on_open_label = QLabel::from_q_string_q_widget(&qtr("settings_run_diagnostics_on_open"), &diagnostics_frame);
+        let diagnostics_run_diagnostics_on_open_max_size_label = QLabel::from_q_string_q_widget(&qtr("settings_run_diagnostics_on_open_max_size"), &diagnostics_frame);
+        let diagnostics_diagnostics_trigger_on_table_edit_delay_label = QLabel::from_q_string_q_widget(&qtr("settings_diagnostics_trigger_on_edit_delay"), &diagnostics_frame);
 
         let diagnostics_diagnostics_trigger_on_open_checkbox = QCheckBox::from_q_widget(&diagnostics_frame);
         let diagnostics_diagnostics_trigger_on_table_edit_checkbox = QCheckBox::from_q_widget(&diagnostics_frame);
+        let diagnostics_run_diagnostics_on_open_checkbox = QCheckBox::from_q_widget(&diagnostics_frame);
+        let diagnostics_run_diagnostics_on_open_max_size_spinbox = QSpinBox::new_1a(&diagnostics_frame);
+        diagnostics_run_diagnostics_on_open_max_size_spinbox.set_maximum(i32::MAX);
+        let diagnostics_diagnostics_trigger_on_table_edit_delay_spinbox = QSpinBox::new_1a(&diagnostics_frame);
+        diagnostics_diagnostics_trigger_on_table_edit_delay_spinbox.set_minimum(100);
+        diagnostics_diagnostics_trigger_on_table_edit_delay_spinbox.set_maximum(60_000);
 
         diagnostics_grid.add_widget_5a(&diagnostics_diagnostics_trigger_on_open_label, 1, 0, 1, 1);
         diagnostics_grid.add_widget_5a(&diagnostics_diagnostics_trigger_on_open_checkbox, 1, 1, 1, 1);
@@ -725,6 +893,15 @@ impl SettingsUI {
         diagnostics_grid.add_widget_5a(&diagnostics_diagnostics_trigger_on_table_edit_label, 2, 0, 1, 1);
         diagnostics_grid.add_widget_5a(&diagnostics_diagnostics_trigger_on_table_edit_checkbox, 2, 1, 1, 1);
 
+        diagnostics_grid.add_widget_5a(&diagnostics_run_diagnostics_on_open_label, 3, 0, 1, 1);
+        diagnostics_grid.add_widget_5a(&diagnostics_run_diagnostics_on_open_checkbox, 3, 1, 1, 1);
+
+        diagnostics_grid.add_widget_5a(&diagnostics_run_diagnostics_on_open_max_size_label, 4, 0, 1, 1);
+        diagnostics_grid.add_widget_5a(&diagnostics_run_diagnostics_on_open_max_size_spinbox, 4, 1, 1, 1);
+
+        diagnostics_grid.add_widget_5a(&diagnostics_diagnostics_trigger_on_table_edit_delay_label, 5, 0, 1, 1);
+        diagnostics_grid.add_widget_5a(&diagnostics_diagnostics_trigger_on_table_edit_delay_spinbox, 5, 1, 1, 1);
+
         settings_grid.add_widget_5a(&diagnostics_frame, 3, 2, 1, 1);
 
         //-----------------------------------------------//
@@ -776,6 +953,8 @@ impl SettingsUI {
             paths_games_buttons,
             paths_asskit_line_edits,
             paths_asskit_buttons,
+            paths_loc_key_pattern_line_edits,
+            paths_loc_formatting_rules_line_edits,
 
             //-------------------------------------------------------------------------------//
             // `General` section of the `Settings` dialog.
@@ -788,6 +967,7 @@ impl SettingsUI {
             extra_packfile_use_lazy_loading_label,
             extra_packfile_disable_uuid_regeneration_on_db_tables_label,
             extra_packfile_disable_file_previews_label,
+            extra_packfile_deduplicate_compression_label,
             ui_global_use_dark_theme_label,
             ui_window_start_maximized_label,
             general_packfile_treeview_expand_treeview_when_adding_items_label,
@@ -795,6 +975,9 @@ impl SettingsUI {
             delete_empty_folders_on_delete_label,
             ignore_game_files_in_ak_label,
             enable_multifolder_filepicker_label,
+            auto_refresh_dependency_cache_label,
+            max_decode_bytes_label,
+            diagnostics_referenced_path_rules_label,
 
             general_language_combobox,
             extra_global_default_game_combobox,
@@ -810,6 +993,7 @@ impl SettingsUI {
             extra_packfile_use_lazy_loading_checkbox,
             extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox,
             extra_packfile_disable_file_previews_checkbox,
+            extra_packfile_deduplicate_compression_checkbox,
             ui_global_use_dark_theme_checkbox,
             ui_window_start_maximized_checkbox,
             ui_window_hide_background_icon_checkbox,
@@ -819,6 +1003,9 @@ impl SettingsUI {
             delete_empty_folders_on_delete_checkbox,
             ignore_game_files_in_ak_checkbox,
             enable_multifolder_filepicker_checkbox,
+            auto_refresh_dependency_cache_checkbox,
+            max_decode_bytes_spinbox,
+            diagnostics_referenced_path_rules_line_edit,
 
             font_data: Rc::new(RefCell::new((String::new(), -1))),
 
@@ -830,6 +1017,12 @@ impl SettingsUI {
             ui_table_extend_last_column_label,
             ui_table_tight_table_mode_label,
             ui_table_use_right_size_markers_label,
+            ui_table_mark_tsv_import_changes_label,
+            ui_table_tsv_trim_trailing_newline_label,
+            ui_table_normalize_floats_on_edit_label,
+            ui_table_normalize_floats_precision_label,
+            ui_table_tsv_float_precision_label,
+            ui_table_tsv_colour_format_label,
 
             ui_table_adjust_columns_to_content_checkbox,
             ui_table_disable_combos_checkbox,
@@ -841,6 +1034,12 @@ impl SettingsUI {
             ui_table_use_right_size_markers_checkbox,
             ui_table_enable_lookups_checkbox,
             ui_table_enable_icons_checkbox,
+            ui_table_mark_tsv_import_changes_checkbox,
+            ui_table_tsv_trim_trailing_newline_checkbox,
+            ui_table_normalize_floats_on_edit_checkbox,
+            ui_table_normalize_floats_precision_spinbox,
+            ui_table_tsv_float_precision_spinbox,
+            ui_table_tsv_colour_format_combobox,
 
             ui_table_colour_light_table_added_button,
             ui_table_colour_light_table_modified_button,
@@ -852,6 +1051,12 @@ impl SettingsUI {
             ui_table_colour_dark_diagnostic_error_button,
             ui_table_colour_dark_diagnostic_warning_button,
             ui_table_colour_dark_diagnostic_info_button,
+            ui_table_colour_light_diagnostics_panel_error_button,
+            ui_table_colour_light_diagnostics_panel_warning_button,
+            ui_table_colour_light_diagnostics_panel_info_button,
+            ui_table_colour_dark_diagnostics_panel_error_button,
+            ui_table_colour_dark_diagnostics_panel_warning_button,
+            ui_table_colour_dark_diagnostics_panel_info_button,
 
             //-------------------------------------------------------------------------------//
             // `Debug` section of the `Settings` dialog.
@@ -864,6 +1069,8 @@ impl SettingsUI {
             debug_enable_unit_editor_label,
             debug_enable_unit_editor_checkbox,
             debug_enable_esf_editor_checkbox,
+            debug_enable_timing_logs_label,
+            debug_enable_timing_logs_checkbox,
 
             debug_clear_dependencies_cache_folder_button,
             debug_clear_autosave_folder_button,
@@ -876,9 +1083,15 @@ impl SettingsUI {
             //-------------------------------------------------------------------------------//
             diagnostics_diagnostics_trigger_on_open_label,
             diagnostics_diagnostics_trigger_on_table_edit_label,
+            diagnostics_run_diagnostics_on_open_label,
+            diagnostics_run_diagnostics_on_open_max_size_label,
+            diagnostics_diagnostics_trigger_on_table_edit_delay_label,
 
             diagnostics_diagnostics_trigger_on_open_checkbox,
             diagnostics_diagnostics_trigger_on_table_edit_checkbox,
+            diagnostics_run_diagnostics_on_open_checkbox,
+            diagnostics_run_diagnostics_on_open_max_size_spinbox,
+            diagnostics_diagnostics_trigger_on_table_edit_delay_spinbox,
 
             //-------------------------------------------------------------------------------//
             // `ButtonBox` section of the `Settings` dialog.
@@ -917,6 +1130,14 @@ impl SettingsUI {
             path.set_text(&QString::from_std_str(setting_string_from_q_setting(&q_settings, &(key.to_owned() + "_assembly_kit"))));
         }
 
+        for (key, pattern) in self.paths_loc_key_pattern_line_edits.iter() {
+            pattern.set_text(&QString::from_std_str(setting_string_from_q_setting(&q_settings, &(key.to_owned() + "_loc_key_pattern"))));
+        }
+
+        for (key, rules) in self.paths_loc_formatting_rules_line_edits.iter() {
+            rules.set_text(&QString::from_std_str(setting_string_from_q_setting(&q_settings, &(key.to_owned() + "_loc_formatting_rules"))));
+        }
+
         // Get the default game.
         for (index, game) in SUPPORTED_GAMES.games_sorted().iter().enumerate() {
             if game.key() == setting_string_from_q_setting(&q_settings, "default_game") {
@@ -958,12 +1179,16 @@ impl SettingsUI {
         self.extra_packfile_use_lazy_loading_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "use_lazy_loading"));
         self.extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "disable_uuid_regeneration_on_db_tables"));
         self.extra_packfile_disable_file_previews_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "disable_file_previews"));
+        self.extra_packfile_deduplicate_compression_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "deduplicate_compression"));
         self.general_packfile_treeview_resize_to_fit_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "packfile_treeview_resize_to_fit"));
         self.general_packfile_treeview_expand_treeview_when_adding_items_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "expand_treeview_when_adding_items"));
         self.include_base_folder_on_add_from_folder_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "include_base_folder_on_add_from_folder"));
         self.delete_empty_folders_on_delete_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "delete_empty_folders_on_delete"));
         self.ignore_game_files_in_ak_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "ignore_game_files_in_ak"));
         self.enable_multifolder_filepicker_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "enable_multifolder_filepicker"));
+        self.auto_refresh_dependency_cache_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "auto_refresh_dependency_cache"));
+        self.max_decode_bytes_spinbox.set_value(setting_int_from_q_setting(&q_settings, "max_decode_bytes"));
+        self.diagnostics_referenced_path_rules_line_edit.set_text(&QString::from_std_str(setting_string_from_q_setting(&q_settings, "diagnostics_referenced_path_rules")));
 
         // Load the Table Stuff.
         self.ui_table_adjust_columns_to_content_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "adjust_columns_to_content"));
@@ -976,6 +1201,12 @@ impl SettingsUI {
         self.ui_table_use_right_size_markers_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "use_right_size_markers"));
         self.ui_table_enable_lookups_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "enable_lookups"));
         self.ui_table_enable_icons_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "enable_icons"));
+        self.ui_table_mark_tsv_import_changes_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "table_mark_tsv_import_changes"));
+        self.ui_table_tsv_trim_trailing_newline_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "tsv_trim_trailing_newline"));
+        self.ui_table_normalize_floats_on_edit_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "normalize_floats_on_edit"));
+        self.ui_table_normalize_floats_precision_spinbox.set_value(setting_int_from_q_setting(&q_settings, "normalize_floats_precision"));
+        self.ui_table_tsv_float_precision_spinbox.set_value(setting_int_from_q_setting(&q_settings, "tsv_float_precision"));
+        self.ui_table_tsv_colour_format_combobox.set_current_index(setting_int_from_q_setting(&q_settings, "tsv_colour_format"));
 
         // Load colours.
         let colour_light_table_added = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_light_table_added")).to_string());
@@ -988,6 +1219,12 @@ impl SettingsUI {
         let colour_dark_diagnostic_error = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_dark_diagnostic_error")).to_string());
         let colour_dark_diagnostic_warning = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_dark_diagnostic_warning")).to_string());
         let colour_dark_diagnostic_info = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_dark_diagnostic_info")).to_string());
+        let colour_light_diagnostics_panel_error = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_light_diagnostics_panel_error")).to_string());
+        let colour_light_diagnostics_panel_warning = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_light_diagnostics_panel_warning")).to_string());
+        let colour_light_diagnostics_panel_info = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_light_diagnostics_panel_info")).to_string());
+        let colour_dark_diagnostics_panel_error = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_dark_diagnostics_panel_error")).to_string());
+        let colour_dark_diagnostics_panel_warning = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_dark_diagnostics_panel_warning")).to_string());
+        let colour_dark_diagnostics_panel_info = QColor::from_q_string(&q_settings.value_1a(&QString::from_std_str("colour_dark_diagnostics_panel_info")).to_string());
 
         self.ui_table_colour_light_table_added_button.set_palette(&QPalette::from_q_color(&colour_light_table_added));
         self.ui_table_colour_light_table_modified_button.set_palette(&QPalette::from_q_color(&colour_light_table_modified));
@@ -999,6 +1236,12 @@ impl SettingsUI {
         self.ui_table_colour_dark_diagnostic_error_button.set_palette(&QPalette::from_q_color(&colour_dark_diagnostic_error));
         self.ui_table_colour_dark_diagnostic_warning_button.set_palette(&QPalette::from_q_color(&colour_dark_diagnostic_warning));
         self.ui_table_colour_dark_diagnostic_info_button.set_palette(&QPalette::from_q_color(&colour_dark_diagnostic_info));
+        self.ui_table_colour_light_diagnostics_panel_error_button.set_palette(&QPalette::from_q_color(&colour_light_diagnostics_panel_error));
+        self.ui_table_colour_light_diagnostics_panel_warning_button.set_palette(&QPalette::from_q_color(&colour_light_diagnostics_panel_warning));
+        self.ui_table_colour_light_diagnostics_panel_info_button.set_palette(&QPalette::from_q_color(&colour_light_diagnostics_panel_info));
+        self.ui_table_colour_dark_diagnostics_panel_error_button.set_palette(&QPalette::from_q_color(&colour_dark_diagnostics_panel_error));
+        self.ui_table_colour_dark_diagnostics_panel_warning_button.set_palette(&QPalette::from_q_color(&colour_dark_diagnostics_panel_warning));
+        self.ui_table_colour_dark_diagnostics_panel_info_button.set_palette(&QPalette::from_q_color(&colour_dark_diagnostics_panel_info));
 
         self.ui_table_colour_light_table_added_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_light_table_added.name_1a(NameFormat::HexArgb).to_std_string())));
         self.ui_table_colour_light_table_modified_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_light_table_modified.name_1a(NameFormat::HexArgb).to_std_string())));
@@ -1010,6 +1253,12 @@ impl SettingsUI {
         self.ui_table_colour_dark_diagnostic_error_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_dark_diagnostic_error.name_1a(NameFormat::HexArgb).to_std_string())));
         self.ui_table_colour_dark_diagnostic_warning_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_dark_diagnostic_warning.name_1a(NameFormat::HexArgb).to_std_string())));
         self.ui_table_colour_dark_diagnostic_info_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_dark_diagnostic_info.name_1a(NameFormat::HexArgb).to_std_string())));
+        self.ui_table_colour_light_diagnostics_panel_error_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_light_diagnostics_panel_error.name_1a(NameFormat::HexArgb).to_std_string())));
+        self.ui_table_colour_light_diagnostics_panel_warning_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_light_diagnostics_panel_warning.name_1a(NameFormat::HexArgb).to_std_string())));
+        self.ui_table_colour_light_diagnostics_panel_info_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_light_diagnostics_panel_info.name_1a(NameFormat::HexArgb).to_std_string())));
+        self.ui_table_colour_dark_diagnostics_panel_error_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_dark_diagnostics_panel_error.name_1a(NameFormat::HexArgb).to_std_string())));
+        self.ui_table_colour_dark_diagnostics_panel_warning_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_dark_diagnostics_panel_warning.name_1a(NameFormat::HexArgb).to_std_string())));
+        self.ui_table_colour_dark_diagnostics_panel_info_button.set_style_sheet(&QString::from_std_str(format!("background-color: {}", colour_dark_diagnostics_panel_info.name_1a(NameFormat::HexArgb).to_std_string())));
 
         // Load the Debug Stuff.
         self.debug_check_for_missing_table_definitions_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "check_for_missing_table_definitions"));
@@ -1017,10 +1266,14 @@ impl SettingsUI {
         self.debug_enable_rigidmodel_editor_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "enable_rigidmodel_editor"));
         self.debug_enable_unit_editor_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "enable_unit_editor"));
         self.debug_enable_esf_editor_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "enable_esf_editor"));
+        self.debug_enable_timing_logs_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "enable_timing_logs"));
 
         // Load the Diagnostics Stuff.
         self.diagnostics_diagnostics_trigger_on_open_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "diagnostics_trigger_on_open"));
         self.diagnostics_diagnostics_trigger_on_table_edit_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "diagnostics_trigger_on_table_edit"));
+        self.diagnostics_run_diagnostics_on_open_checkbox.set_checked(setting_bool_from_q_setting(&q_settings, "run_diagnostics_on_open"));
+        self.diagnostics_run_diagnostics_on_open_max_size_spinbox.set_value(setting_int_from_q_setting(&q_settings, "run_diagnostics_on_open_max_size"));
+        self.diagnostics_diagnostics_trigger_on_table_edit_delay_spinbox.set_value(setting_int_from_q_setting(&q_settings, "diagnostics_trigger_on_table_edit_delay"));
 
         Ok(())
     }
@@ -1041,6 +1294,14 @@ impl SettingsUI {
             set_setting_string_to_q_setting(&q_settings, &(key.to_owned() + "_assembly_kit"), &line_edit.text().to_std_string());
         }
 
+        for (key, line_edit) in self.paths_loc_key_pattern_line_edits.iter() {
+            set_setting_string_to_q_setting(&q_settings, &(key.to_owned() + "_loc_key_pattern"), &line_edit.text().to_std_string());
+        }
+
+        for (key, line_edit) in self.paths_loc_formatting_rules_line_edits.iter() {
+            set_setting_string_to_q_setting(&q_settings, &(key.to_owned() + "_loc_formatting_rules"), &line_edit.text().to_std_string());
+        }
+
         // We get his game's folder, depending on the selected game.
         let mut game = self.extra_global_default_game_combobox.current_text().to_std_string();
         if let Some(index) = game.find('&') { game.remove(index); }
@@ -1075,12 +1336,16 @@ impl SettingsUI {
         set_setting_bool_to_q_setting(&q_settings, "use_lazy_loading", self.extra_packfile_use_lazy_loading_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "disable_uuid_regeneration_on_db_tables", self.extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "disable_file_previews", self.extra_packfile_disable_file_previews_checkbox.is_checked());
+        set_setting_bool_to_q_setting(&q_settings, "deduplicate_compression", self.extra_packfile_deduplicate_compression_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "packfile_treeview_resize_to_fit", self.general_packfile_treeview_resize_to_fit_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "expand_treeview_when_adding_items", self.general_packfile_treeview_expand_treeview_when_adding_items_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "include_base_folder_on_add_from_folder", self.include_base_folder_on_add_from_folder_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "delete_empty_folders_on_delete", self.delete_empty_folders_on_delete_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "ignore_game_files_in_ak", self.ignore_game_files_in_ak_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "enable_multifolder_filepicker", self.enable_multifolder_filepicker_checkbox.is_checked());
+        set_setting_bool_to_q_setting(&q_settings, "auto_refresh_dependency_cache", self.auto_refresh_dependency_cache_checkbox.is_checked());
+        set_setting_int_to_q_setting(&q_settings, "max_decode_bytes", self.max_decode_bytes_spinbox.value());
+        set_setting_string_to_q_setting(&q_settings, "diagnostics_referenced_path_rules", &self.diagnostics_referenced_path_rules_line_edit.text().to_std_string());
 
         // Get the Table Settings.
         set_setting_bool_to_q_setting(&q_settings, "adjust_columns_to_content", self.ui_table_adjust_columns_to_content_checkbox.is_checked());
@@ -1093,6 +1358,12 @@ impl SettingsUI {
         set_setting_bool_to_q_setting(&q_settings, "use_right_size_markers", self.ui_table_use_right_size_markers_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "enable_lookups", self.ui_table_enable_lookups_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "enable_icons", self.ui_table_enable_icons_checkbox.is_checked());
+        set_setting_bool_to_q_setting(&q_settings, "table_mark_tsv_import_changes", self.ui_table_mark_tsv_import_changes_checkbox.is_checked());
+        set_setting_bool_to_q_setting(&q_settings, "tsv_trim_trailing_newline", self.ui_table_tsv_trim_trailing_newline_checkbox.is_checked());
+        set_setting_bool_to_q_setting(&q_settings, "normalize_floats_on_edit", self.ui_table_normalize_floats_on_edit_checkbox.is_checked());
+        set_setting_int_to_q_setting(&q_settings, "normalize_floats_precision", self.ui_table_normalize_floats_precision_spinbox.value());
+        set_setting_int_to_q_setting(&q_settings, "tsv_float_precision", self.ui_table_tsv_float_precision_spinbox.value());
+        set_setting_int_to_q_setting(&q_settings, "tsv_colour_format", self.ui_table_tsv_colour_format_combobox.current_index());
 
         // Get the colours high.
         q_settings.set_value(&QString::from_std_str("colour_light_table_added"), &QVariant::from_q_string(&self.ui_table_colour_light_table_added_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
@@ -1105,6 +1376,12 @@ impl SettingsUI {
         q_settings.set_value(&QString::from_std_str("colour_dark_diagnostic_error"), &QVariant::from_q_string(&self.ui_table_colour_dark_diagnostic_error_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
         q_settings.set_value(&QString::from_std_str("colour_dark_diagnostic_warning"), &QVariant::from_q_string(&self.ui_table_colour_dark_diagnostic_warning_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
         q_settings.set_value(&QString::from_std_str("colour_dark_diagnostic_info"), &QVariant::from_q_string(&self.ui_table_colour_dark_diagnostic_info_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
+        q_settings.set_value(&QString::from_std_str("colour_light_diagnostics_panel_error"), &QVariant::from_q_string(&self.ui_table_colour_light_diagnostics_panel_error_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
+        q_settings.set_value(&QString::from_std_str("colour_light_diagnostics_panel_warning"), &QVariant::from_q_string(&self.ui_table_colour_light_diagnostics_panel_warning_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
+        q_settings.set_value(&QString::from_std_str("colour_light_diagnostics_panel_info"), &QVariant::from_q_string(&self.ui_table_colour_light_diagnostics_panel_info_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
+        q_settings.set_value(&QString::from_std_str("colour_dark_diagnostics_panel_error"), &QVariant::from_q_string(&self.ui_table_colour_dark_diagnostics_panel_error_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
+        q_settings.set_value(&QString::from_std_str("colour_dark_diagnostics_panel_warning"), &QVariant::from_q_string(&self.ui_table_colour_dark_diagnostics_panel_warning_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
+        q_settings.set_value(&QString::from_std_str("colour_dark_diagnostics_panel_info"), &QVariant::from_q_string(&self.ui_table_colour_dark_diagnostics_panel_info_button.palette().color_1a(ColorRole::Background).name_1a(NameFormat::HexArgb)));
 
         // Get the Debug Settings.
         set_setting_bool_to_q_setting(&q_settings, "check_for_missing_table_definitions", self.debug_check_for_missing_table_definitions_checkbox.is_checked());
@@ -1112,10 +1389,15 @@ impl SettingsUI {
         set_setting_bool_to_q_setting(&q_settings, "enable_rigidmodel_editor", self.debug_enable_rigidmodel_editor_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "enable_unit_editor", self.debug_enable_unit_editor_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "enable_esf_editor", self.debug_enable_esf_editor_checkbox.is_checked());
+        set_setting_bool_to_q_setting(&q_settings, "enable_timing_logs", self.debug_enable_timing_logs_checkbox.is_checked());
+        Logger::set_timing_logs(self.debug_enable_timing_logs_checkbox.is_checked());
 
         // Get the Diagnostics Settings.
         set_setting_bool_to_q_setting(&q_settings, "diagnostics_trigger_on_open", self.diagnostics_diagnostics_trigger_on_open_checkbox.is_checked());
         set_setting_bool_to_q_setting(&q_settings, "diagnostics_trigger_on_table_edit", self.diagnostics_diagnostics_trigger_on_table_edit_checkbox.is_checked());
+        set_setting_bool_to_q_setting(&q_settings, "run_diagnostics_on_open", self.diagnostics_run_diagnostics_on_open_checkbox.is_checked());
+        set_setting_int_to_q_setting(&q_settings, "run_diagnostics_on_open_max_size", self.diagnostics_run_diagnostics_on_open_max_size_spinbox.value());
+        set_setting_int_to_q_setting(&q_settings, "diagnostics_trigger_on_table_edit_delay", self.diagnostics_diagnostics_trigger_on_table_edit_delay_spinbox.value());
 
         // Save the settings.
         q_settings.sync();
@@ -1188,6 +1470,15 @@ impl SettingsUI {
 
             // Add the Path to the LineEdit.
             line_edit.set_text(path);
+
+            // If it's a game path, tell the user what's wrong with it, if anything.
+            if !is_asskit_path {
+                if let Some(game_info) = SUPPORTED_GAMES.game(game) {
+                    if let Err(issue) = game_info.validate_install_path(Path::new(&path.to_std_string())) {
+                        show_dialog(&self.dialog, tre("settings_game_path_invalid", &[game_info.display_name(), &issue.to_string()]), false);
+                    }
+                }
+            }
         }
     }
 
@@ -70,6 +70,12 @@ pub struct SettingsUISlots {
     pub select_colour_dark_diagnostic_error: QBox<SlotNoArgs>,
     pub select_colour_dark_diagnostic_warning: QBox<SlotNoArgs>,
     pub select_colour_dark_diagnostic_info: QBox<SlotNoArgs>,
+    pub select_colour_light_diagnostics_panel_error: QBox<SlotNoArgs>,
+    pub select_colour_light_diagnostics_panel_warning: QBox<SlotNoArgs>,
+    pub select_colour_light_diagnostics_panel_info: QBox<SlotNoArgs>,
+    pub select_colour_dark_diagnostics_panel_error: QBox<SlotNoArgs>,
+    pub select_colour_dark_diagnostics_panel_warning: QBox<SlotNoArgs>,
+    pub select_colour_dark_diagnostics_panel_info: QBox<SlotNoArgs>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -305,6 +311,36 @@ impl SettingsUISlots {
                 change_colour(&ui.ui_table_colour_dark_diagnostic_info_button);
         }));
 
+        let select_colour_light_diagnostics_panel_error = SlotNoArgs::new(&ui.dialog, clone!(
+            ui => move || {
+                change_colour(&ui.ui_table_colour_light_diagnostics_panel_error_button);
+        }));
+
+        let select_colour_light_diagnostics_panel_warning = SlotNoArgs::new(&ui.dialog, clone!(
+            ui => move || {
+                change_colour(&ui.ui_table_colour_light_diagnostics_panel_warning_button);
+        }));
+
+        let select_colour_light_diagnostics_panel_info = SlotNoArgs::new(&ui.dialog, clone!(
+            ui => move || {
+                change_colour(&ui.ui_table_colour_light_diagnostics_panel_info_button);
+        }));
+
+        let select_colour_dark_diagnostics_panel_error = SlotNoArgs::new(&ui.dialog, clone!(
+            ui => move || {
+                change_colour(&ui.ui_table_colour_dark_diagnostics_panel_error_button);
+        }));
+
+        let select_colour_dark_diagnostics_panel_warning = SlotNoArgs::new(&ui.dialog, clone!(
+            ui => move || {
+                change_colour(&ui.ui_table_colour_dark_diagnostics_panel_warning_button);
+        }));
+
+        let select_colour_dark_diagnostics_panel_info = SlotNoArgs::new(&ui.dialog, clone!(
+            ui => move || {
+                change_colour(&ui.ui_table_colour_dark_diagnostics_panel_info_button);
+        }));
+
         // And here... we return all the slots.
 		Self {
             restore_default,
@@ -330,6 +366,12 @@ impl SettingsUISlots {
             select_colour_dark_diagnostic_error,
             select_colour_dark_diagnostic_warning,
             select_colour_dark_diagnostic_info,
+            select_colour_light_diagnostics_panel_error,
+            select_colour_light_diagnostics_panel_warning,
+            select_colour_light_diagnostics_panel_info,
+            select_colour_dark_diagnostics_panel_error,
+            select_colour_dark_diagnostics_panel_warning,
+            select_colour_dark_diagnostics_panel_info,
 		}
 	}
 }
@@ -31,6 +31,12 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     let ui_table_extend_last_column_tip = qtr("tt_ui_table_extend_last_column_tip");
     let ui_table_tight_table_mode_tip = qtr("tt_ui_table_tight_table_mode_tip");
     let ui_table_use_right_size_markers_tip = qtr("tt_ui_table_use_right_side_markers_tip");
+    let ui_table_mark_tsv_import_changes_tip = qtr("tt_ui_table_mark_tsv_import_changes_tip");
+    let ui_table_tsv_trim_trailing_newline_tip = qtr("tt_ui_table_tsv_trim_trailing_newline_tip");
+    let ui_table_normalize_floats_on_edit_tip = qtr("tt_ui_table_normalize_floats_on_edit_tip");
+    let ui_table_normalize_floats_precision_tip = qtr("tt_ui_table_normalize_floats_precision_tip");
+    let ui_table_tsv_float_precision_tip = qtr("tt_ui_table_tsv_float_precision_tip");
+    let ui_table_tsv_colour_format_tip = qtr("tt_ui_table_tsv_colour_format_tip");
 
     let ui_window_start_maximized_tip = qtr("tt_ui_window_start_maximized_tip");
     let settings_expand_treeview_when_adding_items_tip = qtr("settings_expand_treeview_when_adding_items_tip");
@@ -38,6 +44,7 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     let delete_empty_folders_on_delete = qtr("settings_delete_empty_folders_on_delete");
     let ignore_game_files_in_ak = qtr("settings_ignore_game_files_in_ak");
     let enable_multifolder_filepicker = qtr("settings_enable_multifolder_filepicker");
+    let auto_refresh_dependency_cache = qtr("settings_auto_refresh_dependency_cache");
 
     settings_ui.ui_global_use_dark_theme_label.set_tool_tip(&ui_global_use_dark_theme_tip);
     settings_ui.ui_global_use_dark_theme_checkbox.set_tool_tip(&ui_global_use_dark_theme_tip);
@@ -51,6 +58,18 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     settings_ui.ui_table_tight_table_mode_checkbox.set_tool_tip(&ui_table_tight_table_mode_tip);
     settings_ui.ui_table_use_right_size_markers_label.set_tool_tip(&ui_table_use_right_size_markers_tip);
     settings_ui.ui_table_use_right_size_markers_checkbox.set_tool_tip(&ui_table_use_right_size_markers_tip);
+    settings_ui.ui_table_mark_tsv_import_changes_label.set_tool_tip(&ui_table_mark_tsv_import_changes_tip);
+    settings_ui.ui_table_mark_tsv_import_changes_checkbox.set_tool_tip(&ui_table_mark_tsv_import_changes_tip);
+    settings_ui.ui_table_tsv_trim_trailing_newline_label.set_tool_tip(&ui_table_tsv_trim_trailing_newline_tip);
+    settings_ui.ui_table_tsv_trim_trailing_newline_checkbox.set_tool_tip(&ui_table_tsv_trim_trailing_newline_tip);
+    settings_ui.ui_table_normalize_floats_on_edit_label.set_tool_tip(&ui_table_normalize_floats_on_edit_tip);
+    settings_ui.ui_table_normalize_floats_on_edit_checkbox.set_tool_tip(&ui_table_normalize_floats_on_edit_tip);
+    settings_ui.ui_table_normalize_floats_precision_label.set_tool_tip(&ui_table_normalize_floats_precision_tip);
+    settings_ui.ui_table_normalize_floats_precision_spinbox.set_tool_tip(&ui_table_normalize_floats_precision_tip);
+    settings_ui.ui_table_tsv_float_precision_label.set_tool_tip(&ui_table_tsv_float_precision_tip);
+    settings_ui.ui_table_tsv_float_precision_spinbox.set_tool_tip(&ui_table_tsv_float_precision_tip);
+    settings_ui.ui_table_tsv_colour_format_label.set_tool_tip(&ui_table_tsv_colour_format_tip);
+    settings_ui.ui_table_tsv_colour_format_combobox.set_tool_tip(&ui_table_tsv_colour_format_tip);
     settings_ui.ui_window_start_maximized_label.set_tool_tip(&ui_window_start_maximized_tip);
     settings_ui.ui_window_start_maximized_checkbox.set_tool_tip(&ui_window_start_maximized_tip);
     settings_ui.general_packfile_treeview_expand_treeview_when_adding_items_label.set_tool_tip(&settings_expand_treeview_when_adding_items_tip);
@@ -63,6 +82,16 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     settings_ui.ignore_game_files_in_ak_checkbox.set_tool_tip(&ignore_game_files_in_ak);
     settings_ui.enable_multifolder_filepicker_label.set_tool_tip(&enable_multifolder_filepicker);
     settings_ui.enable_multifolder_filepicker_checkbox.set_tool_tip(&enable_multifolder_filepicker);
+    settings_ui.auto_refresh_dependency_cache_label.set_tool_tip(&auto_refresh_dependency_cache);
+    settings_ui.auto_refresh_dependency_cache_checkbox.set_tool_tip(&auto_refresh_dependency_cache);
+
+    let max_decode_bytes = qtr("tt_settings_max_decode_bytes");
+    settings_ui.max_decode_bytes_label.set_tool_tip(&max_decode_bytes);
+    settings_ui.max_decode_bytes_spinbox.set_tool_tip(&max_decode_bytes);
+
+    let diagnostics_referenced_path_rules = qtr("tt_settings_diagnostics_referenced_path_rules");
+    settings_ui.diagnostics_referenced_path_rules_label.set_tool_tip(&diagnostics_referenced_path_rules);
+    settings_ui.diagnostics_referenced_path_rules_line_edit.set_tool_tip(&diagnostics_referenced_path_rules);
 
     //-----------------------------------------------//
     // `Extra` tips.
@@ -76,6 +105,7 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     let extra_packfile_use_lazy_loading_tip = qtr("tt_extra_packfile_use_lazy_loading_tip");
     let extra_disable_uuid_regeneration_on_db_tables_label_tip = qtr("tt_extra_disable_uuid_regeneration_on_db_tables_label_tip");
     let extra_packfile_disable_file_previews_tip = qtr("tt_settings_disable_file_previews_tip");
+    let extra_packfile_deduplicate_compression_tip = qtr("tt_settings_deduplicate_compression_tip");
 
     settings_ui.extra_packfile_autosave_amount_label.set_tool_tip(&autosave_amount_tip);
     settings_ui.extra_packfile_autosave_amount_spinbox.set_tool_tip(&autosave_amount_tip);
@@ -94,6 +124,8 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     settings_ui.extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox.set_tool_tip(&extra_disable_uuid_regeneration_on_db_tables_label_tip);
     settings_ui.extra_packfile_disable_file_previews_label.set_tool_tip(&extra_packfile_disable_file_previews_tip);
     settings_ui.extra_packfile_disable_file_previews_checkbox.set_tool_tip(&extra_packfile_disable_file_previews_tip);
+    settings_ui.extra_packfile_deduplicate_compression_label.set_tool_tip(&extra_packfile_deduplicate_compression_tip);
+    settings_ui.extra_packfile_deduplicate_compression_checkbox.set_tool_tip(&extra_packfile_deduplicate_compression_tip);
 
     //-----------------------------------------------//
     // `Debug` tips.
@@ -101,6 +133,7 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     let debug_check_for_missing_table_definitions_tip = qtr("tt_debug_check_for_missing_table_definitions_tip");
     let debug_enable_rigidmodel_editor = qtr("tt_settings_debug_enable_rigidmodel_editor");
     let debug_enable_unit_editor = qtr("tt_settings_debug_enable_unit_editor");
+    let debug_enable_timing_logs = qtr("tt_settings_debug_enable_timing_logs");
 
     let debug_clear_dependencies_cache = qtr("tt_settings_debug_clear_dependencies_cache_folder");
     let debug_clear_autosaves = qtr("tt_settings_debug_clear_autosave_folder");
@@ -113,6 +146,8 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     settings_ui.debug_enable_rigidmodel_editor_checkbox.set_tool_tip(&debug_enable_rigidmodel_editor);
     settings_ui.debug_enable_unit_editor_label.set_tool_tip(&debug_enable_unit_editor);
     settings_ui.debug_enable_unit_editor_checkbox.set_tool_tip(&debug_enable_unit_editor);
+    settings_ui.debug_enable_timing_logs_label.set_tool_tip(&debug_enable_timing_logs);
+    settings_ui.debug_enable_timing_logs_checkbox.set_tool_tip(&debug_enable_timing_logs);
 
     settings_ui.debug_clear_dependencies_cache_folder_button.set_tool_tip(&debug_clear_dependencies_cache);
     settings_ui.debug_clear_autosave_folder_button.set_tool_tip(&debug_clear_autosaves);
@@ -124,9 +159,18 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     //-----------------------------------------------//
     let diagnostics_trigger_diagnostics_on_open_tip = qtr("tt_diagnostics_trigger_diagnostics_on_open_tip");
     let diagnostics_trigger_diagnostics_on_table_edit_tip = qtr("tt_diagnostics_trigger_diagnostics_on_table_edit_tip");
+    let diagnostics_run_diagnostics_on_open_tip = qtr("tt_diagnostics_run_diagnostics_on_open_tip");
+    let diagnostics_run_diagnostics_on_open_max_size_tip = qtr("tt_diagnostics_run_diagnostics_on_open_max_size_tip");
+    let diagnostics_trigger_diagnostics_on_table_edit_delay_tip = qtr("tt_diagnostics_trigger_diagnostics_on_table_edit_delay_tip");
 
     settings_ui.diagnostics_diagnostics_trigger_on_open_label.set_tool_tip(&diagnostics_trigger_diagnostics_on_open_tip);
     settings_ui.diagnostics_diagnostics_trigger_on_open_checkbox.set_tool_tip(&diagnostics_trigger_diagnostics_on_open_tip);
     settings_ui.diagnostics_diagnostics_trigger_on_table_edit_label.set_tool_tip(&diagnostics_trigger_diagnostics_on_table_edit_tip);
     settings_ui.diagnostics_diagnostics_trigger_on_table_edit_checkbox.set_tool_tip(&diagnostics_trigger_diagnostics_on_table_edit_tip);
+    settings_ui.diagnostics_run_diagnostics_on_open_label.set_tool_tip(&diagnostics_run_diagnostics_on_open_tip);
+    settings_ui.diagnostics_run_diagnostics_on_open_checkbox.set_tool_tip(&diagnostics_run_diagnostics_on_open_tip);
+    settings_ui.diagnostics_run_diagnostics_on_open_max_size_label.set_tool_tip(&diagnostics_run_diagnostics_on_open_max_size_tip);
+    settings_ui.diagnostics_run_diagnostics_on_open_max_size_spinbox.set_tool_tip(&diagnostics_run_diagnostics_on_open_max_size_tip);
+    settings_ui.diagnostics_diagnostics_trigger_on_table_edit_delay_label.set_tool_tip(&diagnostics_trigger_diagnostics_on_table_edit_delay_tip);
+    settings_ui.diagnostics_diagnostics_trigger_on_table_edit_delay_spinbox.set_tool_tip(&diagnostics_trigger_diagnostics_on_table_edit_delay_tip);
 }
@@ -143,6 +143,7 @@ impl UI {
 
         // Initialize settings.
         init_settings(&app_ui.main_window().static_upcast());
+        Logger::set_timing_logs(setting_bool("enable_timing_logs"));
 
         // Apply last ui state.
         app_ui.main_window().restore_geometry(&setting_byte_array("geometry"));
@@ -191,7 +192,7 @@ impl UI {
             _ => app_ui.game_selected_warhammer_3().set_checked(true),
         }
 
-        AppUI::change_game_selected(&app_ui, &pack_file_contents_ui, &dependencies_ui, true, false);
+        AppUI::change_game_selected(&app_ui, &pack_file_contents_ui, &diagnostics_ui, &dependencies_ui, true, false);
         info!("Initial Game Selected set to {}.", setting_string("default_game"));
 
         // We get all the Arguments provided when starting RPFM, just in case we passed it a path,
@@ -234,9 +235,7 @@ impl UI {
                         }
                     }
 
-                    if setting_bool("diagnostics_trigger_on_open") {
-                        DiagnosticsUI::check(&app_ui, &diagnostics_ui);
-                    }
+                    DiagnosticsUI::check_on_open(&app_ui, &diagnostics_ui, &paths);
                 }
             }
         }
@@ -16,6 +16,7 @@ This module contains the code needed to keep track of the current state of the U
 
 use qt_core::QEventLoop;
 
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -52,6 +53,9 @@ pub struct UIState {
 
     /// This stores the current `Diagnostics`.
     diagnostics: Arc<RwLock<Diagnostics>>,
+
+    /// This stores the keys of the diagnostic results snoozed during this session. Cleared when the Pack is changed.
+    diagnostics_snoozed: Arc<RwLock<HashSet<String>>>,
 }
 
 /// This enum represent the current ***Operational Mode*** for RPFM.
@@ -81,6 +85,7 @@ impl Default for UIState {
             operational_mode: Arc::new(RwLock::new(OperationalMode::Normal)),
             global_search: Arc::new(RwLock::new(GlobalSearch::default())),
             diagnostics: Arc::new(RwLock::new(Diagnostics::default())),
+            diagnostics_snoozed: Arc::new(RwLock::new(HashSet::new())),
         }
     }
 }
@@ -192,4 +197,19 @@ impl UIState {
     pub fn set_diagnostics(&self, diagnostics: &Diagnostics) {
         *self.diagnostics.write().unwrap() = diagnostics.clone();
     }
+
+    /// This function checks if the diagnostic result with the provided key has been snoozed.
+    pub fn is_diagnostic_snoozed(&self, key: &str) -> bool {
+        self.diagnostics_snoozed.read().unwrap().contains(key)
+    }
+
+    /// This function snoozes the diagnostic results with the provided keys until the Pack is changed.
+    pub fn snooze_diagnostics(&self, keys: &[String]) {
+        self.diagnostics_snoozed.write().unwrap().extend(keys.iter().cloned());
+    }
+
+    /// This function removes all the snoozed diagnostic results.
+    pub fn clear_diagnostics_snoozed(&self) {
+        self.diagnostics_snoozed.write().unwrap().clear();
+    }
 }
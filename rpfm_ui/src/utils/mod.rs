@@ -43,12 +43,13 @@ use std::fmt::Display;
 use std::fs::File;
 use std::io::Read;
 
-use rpfm_lib::files::{EncodeableExtraData, pack::PackSettings};
+use rpfm_lib::files::{EncodeableExtraData, pack::{PackSettings, SETTING_KEY_READONLY_PATHS}, table::{ColourFormat, DEFAULT_FLOAT_PRECISION, FormatOptions, MAX_FLOAT_PRECISION}};
 use rpfm_lib::games::GameInfo;
 use rpfm_lib::integrations::log::*;
 
 use rpfm_ui_common::ASSETS_PATH;
 use rpfm_ui_common::locale::{qtr, qtre};
+use rpfm_ui_common::settings::setting_int;
 pub use rpfm_ui_common::utils::*;
 
 use crate::app_ui::AppUI;
@@ -67,16 +68,10 @@ pub const RED_DARK: &str = "#8F6E6E";
 pub const TRANSPARENT_BRIGHT: &str = "#00000000";
 pub const ERROR_UNPRESSED_DARK: &str = "#b30000";
 pub const ERROR_UNPRESSED_LIGHT: &str = "#ffcccc";
-pub const ERROR_PRESSED_DARK: &str = "#e60000";
-pub const ERROR_PRESSED_LIGHT: &str = "#ff9999";
 pub const WARNING_UNPRESSED_DARK: &str = "#4d4d00";
 pub const WARNING_UNPRESSED_LIGHT: &str = "#ffffcc";
-pub const WARNING_PRESSED_DARK: &str = "#808000";
-pub const WARNING_PRESSED_LIGHT: &str = "#ffff99";
 pub const INFO_UNPRESSED_DARK: &str = "#0059b3";
 pub const INFO_UNPRESSED_LIGHT: &str = "#cce6ff";
-pub const INFO_PRESSED_DARK: &str = "#0073e6";
-pub const INFO_PRESSED_LIGHT: &str = "#99ccff";
 
 //----------------------------------------------------------------------------//
 //              Utility functions (helpers and stuff like that)
@@ -414,6 +409,7 @@ pub fn initialize_pack_settings() -> PackSettings {
     let mut pack_settings = PackSettings::default();
     pack_settings.settings_text_mut().insert("diagnostics_files_to_ignore".to_owned(), "".to_owned());
     pack_settings.settings_text_mut().insert("import_files_to_ignore".to_owned(), "".to_owned());
+    pack_settings.settings_text_mut().insert(SETTING_KEY_READONLY_PATHS.to_owned(), "".to_owned());
     pack_settings.settings_bool_mut().insert("disable_autosaves".to_owned(), false);
     pack_settings
 }
@@ -421,5 +417,17 @@ pub fn initialize_pack_settings() -> PackSettings {
 pub fn initialize_encodeable_extra_data(game_info: &GameInfo) -> EncodeableExtraData {
     let mut extra_data = EncodeableExtraData::new_from_game_info(game_info);
     extra_data.set_regenerate_table_guid(!setting_bool("disable_uuid_regeneration_on_db_tables"));
+    extra_data.set_trim_trailing_newline(setting_bool("tsv_trim_trailing_newline"));
+    extra_data.set_deduplicate_compression(setting_bool("deduplicate_compression"));
+
+    let mut format_options = FormatOptions::default();
+    format_options.set_float_precision(setting_int("tsv_float_precision").clamp(DEFAULT_FLOAT_PRECISION as i32, MAX_FLOAT_PRECISION as i32) as usize);
+    format_options.set_colour_format(match setting_int("tsv_colour_format") {
+        1 => ColourFormat::Uppercase,
+        2 => ColourFormat::Lowercase,
+        _ => ColourFormat::AsIs,
+    });
+
+    extra_data.set_tsv_format_options(format_options);
     extra_data
 }
@@ -77,6 +77,7 @@ pub unsafe fn set_connections(ui: &Arc<TableView>, slots: &TableViewSlots) {
     ui.table_view_ptr().double_clicked().connect(&slots.open_subtable);
 
     ui.timer_delayed_updates.timeout().connect(&slots.delayed_updates);
+    ui.timer_delayed_diagnostics.timeout().connect(&slots.delayed_diagnostics);
 
     ui.signal_mapper_profile_apply.mapped2().connect(&slots.profile_apply);
     ui.signal_mapper_profile_delete.mapped2().connect(&slots.profile_delete);
@@ -12,6 +12,7 @@
 Module with all the code for managing the view for Tables.
 !*/
 
+use qt_widgets::q_abstract_item_view::EditTrigger;
 use qt_widgets::q_abstract_item_view::ScrollHint;
 use qt_widgets::QActionGroup;
 use qt_widgets::QAction;
@@ -259,6 +260,7 @@ pub struct TableView {
     dependency_data: Arc<RwLock<HashMap<i32, TableReferences>>>,
 
     banned_table: bool,
+    readonly_table: bool,
 
     #[getset(skip)]
     reference_map: Arc<HashMap<String, HashMap<String, Vec<String>>>>,
@@ -278,6 +280,7 @@ pub struct TableView {
     history_redo: Arc<RwLock<Vec<TableOperations>>>,
 
     timer_delayed_updates: QBox<QTimer>,
+    timer_delayed_diagnostics: QBox<QTimer>,
 }
 
 /// This struct contains data to load a specific status of a view.
@@ -372,6 +375,27 @@ impl TableView {
             }
         }
 
+        // Tables marked as read-only in the Pack Settings can be seen, but not edited.
+        let readonly_table = match (&*data_source.read().unwrap(), &packed_file_path) {
+            (DataSource::PackFile, Some(path)) => {
+                let receiver = CENTRAL_COMMAND.send_background(Command::IsPathReadonly(path.read().unwrap().to_owned()));
+                let response = CENTRAL_COMMAND.recv_try(&receiver);
+                match response {
+                    Response::Bool(readonly) => readonly,
+                    _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+                }
+            }
+            _ => false,
+        };
+
+        if readonly_table {
+            table_view.set_edit_triggers(QFlags::from(EditTrigger::NoEditTriggers));
+            if !banned_table {
+                let warning_message = QLabel::from_q_string_q_widget(&qtr("readonly_table_warning"), parent);
+                layout.add_widget_5a(&warning_message, 0, 0, 1, 4);
+            }
+        }
+
         let table_status_bar = QWidget::new_1a(parent);
         let table_status_bar_grid = create_grid_layout(table_status_bar.static_upcast());
         let table_status_bar_line_counter_label = QLabel::from_q_string_q_widget(&qtre("line_counter", &["0", "0"]), &table_status_bar);
@@ -532,7 +556,6 @@ impl TableView {
         let label_all = QLabel::from_q_string_q_widget(&qtr("all"), &sidebar_widget);
         let sidebar_hide_checkboxes_all = QCheckBox::from_q_widget(&sidebar_widget);
         let sidebar_freeze_checkboxes_all = QCheckBox::from_q_widget(&sidebar_widget);
-        sidebar_freeze_checkboxes_all.set_enabled(false);
 
         sidebar_grid.set_alignment_q_widget_q_flags_alignment_flag(&sidebar_hide_checkboxes_all, QFlags::from(AlignmentFlag::AlignHCenter));
         sidebar_grid.set_alignment_q_widget_q_flags_alignment_flag(&sidebar_freeze_checkboxes_all, QFlags::from(AlignmentFlag::AlignHCenter));
@@ -547,7 +570,6 @@ impl TableView {
             let column_name = QLabel::from_q_string_q_widget(&QString::from_std_str(utils::clean_column_names(column.name())), &sidebar_widget);
             let hide_show_checkbox = QCheckBox::from_q_widget(&sidebar_widget);
             let freeze_unfreeze_checkbox = QCheckBox::from_q_widget(&sidebar_widget);
-            freeze_unfreeze_checkbox.set_enabled(false);
 
             sidebar_grid.set_alignment_q_widget_q_flags_alignment_flag(&hide_show_checkbox, QFlags::from(AlignmentFlag::AlignHCenter));
             sidebar_grid.set_alignment_q_widget_q_flags_alignment_flag(&freeze_unfreeze_checkbox, QFlags::from(AlignmentFlag::AlignHCenter));
@@ -567,6 +589,10 @@ impl TableView {
 
         let timer_delayed_updates = QTimer::new_1a(parent);
         timer_delayed_updates.set_single_shot(true);
+        timer_delayed_updates.set_interval(1500);
+
+        let timer_delayed_diagnostics = QTimer::new_1a(parent);
+        timer_delayed_diagnostics.set_single_shot(true);
 
         // Get the reference data for this table, to speedup reference searching.
         let reference_map = if let TableType::NormalTable(_) = table_data {
@@ -649,6 +675,7 @@ impl TableView {
             packed_file_path: packed_file_path.clone(),
             packed_file_type: Arc::new(packed_file_type),
             banned_table,
+            readonly_table,
             reference_map: Arc::new(reference_map),
             profile_default: Arc::new(RwLock::new(String::new())),
             profiles: Arc::new(RwLock::new(HashMap::new())),
@@ -661,6 +688,7 @@ impl TableView {
             history_redo: Arc::new(RwLock::new(vec![])),
 
             timer_delayed_updates,
+            timer_delayed_diagnostics,
         });
 
         let packed_file_table_view_slots = TableViewSlots::new(
@@ -703,6 +731,14 @@ impl TableView {
         // Set the connections and return success.
         connections::set_connections(&packed_file_table_view, &packed_file_table_view_slots);
 
+        // Restore the frozen columns we had for this table, if any.
+        if let Some(ref table_name) = packed_file_table_view.table_name {
+            let frozen_columns = setting_int(&format!("frozen_columns_{table_name}"));
+            if frozen_columns > 0 {
+                packed_file_table_view.set_frozen_columns(frozen_columns as usize);
+            }
+        }
+
         // Update the line counter.
         packed_file_table_view.update_line_counter();
 
@@ -712,6 +748,25 @@ impl TableView {
         Ok(packed_file_table_view)
     }
 
+    /// This function freezes the first `count` columns of the table, in the order they're shown in the sidebar, and unfreezes the rest.
+    ///
+    /// The amount of frozen columns is remembered per table name.
+    pub unsafe fn set_frozen_columns(&self, count: usize) {
+        for (index, checkbox) in self.sidebar_freeze_checkboxes.iter().enumerate() {
+            checkbox.set_checked(index < count);
+        }
+
+        self.save_frozen_columns();
+    }
+
+    /// This function stores the amount of leading frozen columns of this table, so they're restored the next time we open it.
+    pub unsafe fn save_frozen_columns(&self) {
+        if let Some(ref table_name) = self.table_name {
+            let count = self.sidebar_freeze_checkboxes.iter().take_while(|checkbox| checkbox.is_checked()).count();
+            set_setting_int(&format!("frozen_columns_{table_name}"), count as i32);
+        }
+    }
+
     pub unsafe fn apply_table_view_profile(&self, key: &str) {
         let profiles = self.profiles.read().unwrap();
         if let Some(profile) = profiles.get(key) {
@@ -979,9 +1034,22 @@ impl TableView {
         self.data_source.read().unwrap().clone()
     }
 
+    /// This function (re)starts the timers of the delayed updates, so multiple edits in a row only trigger one save and diagnostics check.
+    ///
+    /// Saving always waits the default delay. If diagnostics are triggered on table edits, they use their own configured delay, so they keep up with the edits.
     pub unsafe fn start_delayed_updates_timer(&self) {
-        self.timer_delayed_updates.set_interval(1500);
         self.timer_delayed_updates.start_0a();
+
+        if setting_bool("diagnostics_trigger_on_table_edit") {
+            self.timer_delayed_diagnostics.set_interval(setting_int("diagnostics_trigger_on_table_edit_delay").max(100));
+            self.timer_delayed_diagnostics.start_0a();
+        }
+    }
+
+    /// This function stops both timers of the delayed updates.
+    pub unsafe fn stop_delayed_updates_timer(&self) {
+        self.timer_delayed_updates.stop();
+        self.timer_delayed_diagnostics.stop();
     }
 
     pub unsafe fn update_line_counter(&self) {
@@ -1046,9 +1114,9 @@ impl TableView {
             self.context_menu_go_to_loc.iter().for_each(|x| x.set_enabled(false));
         }
 
-        // Only enable editing if the table is ours and not banned.
+        // Only enable editing if the table is ours, not banned and not marked as read-only.
         if let DataSource::PackFile = self.get_data_source() {
-            if !self.banned_table && !self.is_translator {
+            if !self.banned_table && !self.readonly_table && !self.is_translator {
 
                 // These ones are always enabled if the table is editable.
                 self.context_menu_add_rows.set_enabled(true);
@@ -1596,26 +1664,32 @@ impl TableView {
                 if let Some(field) = fields_processed.get(real_column as usize) {
 
                     // Check if, according to the definition, we have a valid value for the type.
-                    let is_valid_data = match field.field_type() {
-                        FieldType::Boolean => !(text.to_lowercase() != "true" && text.to_lowercase() != "false" && text != &"1" && text != &"0"),
-                        FieldType::F32 => text.parse::<f32>().is_ok(),
-                        FieldType::F64 => text.parse::<f64>().is_ok(),
-                        FieldType::I16 => text.parse::<i16>().is_ok() || text.parse::<f32>().is_ok(),
-                        FieldType::I32 => text.parse::<i32>().is_ok() || text.parse::<f32>().is_ok(),
-                        FieldType::I64 => text.parse::<i64>().is_ok() || text.parse::<f32>().is_ok(),
-                        FieldType::OptionalI16 => text.parse::<i16>().is_ok() || text.parse::<f32>().is_ok(),
-                        FieldType::OptionalI32 => text.parse::<i32>().is_ok() || text.parse::<f32>().is_ok(),
-                        FieldType::OptionalI64 => text.parse::<i64>().is_ok() || text.parse::<f32>().is_ok(),
-                        FieldType::ColourRGB => u32::from_str_radix(text, 16).is_ok(),
-
-                        // All these are Strings, so we can skip their checks....
-                        FieldType::StringU8 |
-                        FieldType::StringU16 |
-                        FieldType::OptionalStringU8 |
-                        FieldType::OptionalStringU16 => true,
-
-                        // Ignore sequences.
-                        FieldType::SequenceU16(_) | FieldType::SequenceU32(_) => false,
+                    let mut unsigned_data = DecodedData::new_from_type_and_value(field.field_type(), &None);
+                    let is_valid_data = if field.is_unsigned(Some(definition.patches())) && unsigned_integer_to_qvariant(&unsigned_data).is_some() {
+                        unsigned_data.set_data_as_unsigned(text).is_ok()
+                    } else {
+                        match field.field_type() {
+                            FieldType::Boolean => !(text.to_lowercase() != "true" && text.to_lowercase() != "false" && text != &"1" && text != &"0"),
+                            FieldType::F32 => text.parse::<f32>().is_ok(),
+                            FieldType::F64 => text.parse::<f64>().is_ok(),
+                            FieldType::I16 => text.parse::<i16>().is_ok() || text.parse::<f32>().is_ok(),
+                            FieldType::I32 => text.parse::<i32>().is_ok() || text.parse::<f32>().is_ok(),
+                            FieldType::I64 => text.parse::<i64>().is_ok() || text.parse::<f32>().is_ok(),
+                            FieldType::OptionalI16 => text.parse::<i16>().is_ok() || text.parse::<f32>().is_ok(),
+                            FieldType::OptionalI32 => text.parse::<i32>().is_ok() || text.parse::<f32>().is_ok(),
+                            FieldType::OptionalI64 => text.parse::<i64>().is_ok() || text.parse::<f32>().is_ok(),
+                            FieldType::ColourRGB => u32::from_str_radix(text, 16).is_ok(),
+                            FieldType::ColourRGBA => DecodedData::is_valid_colour_rgba(text),
+
+                            // All these are Strings, so we can skip their checks....
+                            FieldType::StringU8 |
+                            FieldType::StringU16 |
+                            FieldType::OptionalStringU8 |
+                            FieldType::OptionalStringU16 => true,
+
+                            // Ignore sequences.
+                            FieldType::SequenceU16(_) | FieldType::SequenceU32(_) => false,
+                        }
                     };
 
                     // If it's valid, add it to the real_cells list.
@@ -1929,31 +2003,9 @@ impl TableView {
         match fields_processed[index.column() as usize].field_type() {
             FieldType::Boolean => if let CheckState::Checked = item.check_state() { "true".to_owned() } else { "false".to_owned() },
 
-            // Floats need to be tweaked to fix trailing zeroes and precision issues, like turning 0.5000004 into 0.5.
-            FieldType::F32 => {
-                let data_str = format!("{}", item.data_1a(2).to_float_0a());
-
-                // If we have more than 3 decimals, we limit it to three, then do magic to remove trailing zeroes.
-                if let Some(position) = data_str.find('.') {
-                    let decimals = &data_str[position..].len();
-                    if *decimals > 4 { format!("{}", format!("{:.4}", item.data_1a(2).to_float_0a()).parse::<f32>().unwrap()) }
-                    else { data_str }
-                }
-                else { data_str }
-            },
-
-            // Floats need to be tweaked to fix trailing zeroes and precision issues, like turning 0.5000004 into 0.5.
-            FieldType::F64 => {
-                let data_str = format!("{}", item.data_1a(2).to_float_0a());
-
-                // If we have more than 3 decimals, we limit it to three, then do magic to remove trailing zeroes.
-                if let Some(position) = data_str.find('.') {
-                    let decimals = &data_str[position..].len();
-                    if *decimals > 4 { format!("{}", format!("{:.4}", item.data_1a(2).to_double_0a()).parse::<f64>().unwrap()) }
-                    else { data_str }
-                }
-                else { data_str }
-            },
+            // Floats are formatted the same way they're exported, which fixes trailing zeroes and precision issues, like turning 0.5000004 into 0.5.
+            FieldType::F32 => DecodedData::F32(item.data_1a(2).to_float_0a()).to_export_string(&FormatOptions::default()).to_string(),
+            FieldType::F64 => DecodedData::F64(item.data_1a(2).to_double_0a()).to_export_string(&FormatOptions::default()).to_string(),
             FieldType::I16 |
             FieldType::I32 |
             FieldType::I64 |
@@ -1963,6 +2015,7 @@ impl TableView {
 
             // All these are Strings, so they need to escape certain chars and include commas in Lua.
             FieldType::ColourRGB |
+            FieldType::ColourRGBA |
             FieldType::StringU8 |
             FieldType::StringU16 |
             FieldType::OptionalStringU8 |
@@ -2277,6 +2330,7 @@ impl TableView {
             let default_i32 = "0".to_owned();
             let default_bool = "false".to_owned();
             let default_colour_rgb = "000000".to_owned();
+            let default_colour_rgba = "00000000".to_owned();
 
             let mut real_cells = vec![];
             let mut values = vec![];
@@ -2295,6 +2349,7 @@ impl TableView {
                             FieldType::OptionalI32 |
                             FieldType::OptionalI64 => values.push(&*default_i32),
                             FieldType::ColourRGB => values.push(&*default_colour_rgb),
+                            FieldType::ColourRGBA => values.push(&*default_colour_rgba),
                             FieldType::StringU8 |
                             FieldType::StringU16 |
                             FieldType::OptionalStringU8 |
@@ -2333,13 +2388,32 @@ impl TableView {
         let blocker = QSignalBlocker::from_q_object(&self.table_model);
         let blocker_undo = QSignalBlocker::from_q_object(&self.undo_model);
         let mut changed_cells = 0;
+        let definition = self.table_definition();
+        let patches = Some(definition.patches());
 
         for (real_cell, text) in real_cells {
             if real_cell.is_valid() {
 
                 // Depending on the column, we try to encode the data in one format or another.
                 let current_value = self.table_model.data_1a(real_cell).to_string().to_std_string();
-                match fields[real_cell.column() as usize].field_type() {
+                let field = &fields[real_cell.column() as usize];
+
+                // Unsigned integers are parsed and stored as unsigned values.
+                if field.is_unsigned(patches) {
+                    let mut new_value = DecodedData::new_from_type_and_value(field.field_type(), &None);
+                    if unsigned_integer_to_qvariant(&new_value).is_some() {
+                        if new_value.set_data_as_unsigned(text).is_ok() && current_value != new_value.data_to_string_as_unsigned() {
+                            if let Some(qdata) = unsigned_integer_to_qvariant(&new_value) {
+                                self.table_model.set_data_3a(real_cell, &qdata, 2);
+                                changed_cells += 1;
+                                self.process_edition(self.table_model.item_from_index(real_cell));
+                            }
+                        }
+                        continue;
+                    }
+                }
+
+                match field.field_type() {
 
                     FieldType::Boolean => {
                         let current_value = self.table_model.item_from_index(real_cell).check_state();
@@ -2429,6 +2503,14 @@ impl TableView {
                             self.process_edition(self.table_model.item_from_index(real_cell));
                         }
                     }
+
+                    FieldType::ColourRGBA => {
+                        if DecodedData::is_valid_colour_rgba(text) && current_value != *text {
+                            self.table_model.set_data_3a(real_cell, &QVariant::from_q_string(&QString::from_std_str(text)), 2);
+                            changed_cells += 1;
+                            self.process_edition(self.table_model.item_from_index(real_cell));
+                        }
+                    }
                     FieldType::StringU8 |
                     FieldType::StringU16 |
                     FieldType::OptionalStringU8 |
@@ -2570,7 +2652,7 @@ impl TableView {
             if setting_bool("enable_icons") {
                 if field.is_filename(patches) {
                     let mut icons = BTreeMap::new();
-                    let data = vec![vec![get_field_from_view(&self.table_model.static_upcast(), field, item.row(), item.column())]];
+                    let data = vec![vec![get_field_from_view(&self.table_model.static_upcast(), field, patches, item.row(), item.column())]];
 
                     if request_backend_files(&data, 0, &field, patches, &mut icons).is_ok() {
                         if let Some(column_data) = icons.get(&0) {
@@ -2631,7 +2713,7 @@ impl TableView {
 
         // This feature has some... interesting lockups when running alongside a diagnostics check. So, while this runs,
         // we have to avoid triggering the diagnostics check.
-        self.timer_delayed_updates.stop();
+        self.stop_delayed_updates_timer();
 
         // We only want to do this for tables we can identify.
         let table_name = if let Some(table_name) = self.table_name() { table_name.to_lowercase() } else { return };
@@ -2652,7 +2734,7 @@ impl TableView {
             self.set_data_on_cells(&real_cells, 0, &[], &fields_processed, app_ui, pack_file_contents_ui);
 
             // Stop the timer again.
-            self.timer_delayed_updates.stop();
+            self.stop_delayed_updates_timer();
 
             // Now that we know what to edit, save all views of referencing files, so we only have to deal with them in the background.
             let _ = AppUI::back_to_back_end_all(app_ui, pack_file_contents_ui);
@@ -3182,6 +3264,58 @@ impl TableView {
         else { Some(error_message) }
     }
 
+    /// This function marks the rows added and the cells modified by an import, comparing the current data of the view with the provided one.
+    ///
+    /// Rows are matched by their key columns. These marks live only in the view, so they're never saved to the file.
+    pub unsafe fn mark_imported_changes(&self, old_data: &[Vec<DecodedData>]) {
+        let definition = self.table_definition();
+        let patches = Some(definition.patches());
+        let key_columns = definition.fields_processed().iter()
+            .enumerate()
+            .filter(|(_, field)| field.is_key(patches))
+            .map(|(column, _)| column)
+            .collect::<Vec<_>>();
+
+        // Without keys we cannot know which rows correspond to which, so don't mark anything.
+        if key_columns.is_empty() {
+            return;
+        }
+
+        let row_key = |row: &[DecodedData]| key_columns.iter()
+            .map(|column| row[*column].data_to_string().to_string())
+            .collect::<Vec<_>>();
+
+        let old_rows = old_data.iter()
+            .map(|row| (row_key(row), row))
+            .collect::<HashMap<_, _>>();
+
+        let table_model = self.table_model_ptr();
+        let new_data = match get_table_from_view(&table_model, &definition) {
+            Ok(table) => table.data().to_vec(),
+            Err(_) => return,
+        };
+
+        let blocker = QSignalBlocker::from_q_object(table_model.static_upcast::<QObject>());
+        for (row, new_row) in new_data.iter().enumerate() {
+            match old_rows.get(&row_key(new_row)) {
+                Some(old_row) => {
+                    for (column, cell) in new_row.iter().enumerate() {
+                        if old_row.get(column) != Some(cell) {
+                            table_model.item_2a(row as i32, column as i32).set_data_2a(&QVariant::from_bool(true), ITEM_IS_MODIFIED);
+                        }
+                    }
+                }
+                None => {
+                    for column in 0..new_row.len() {
+                        table_model.item_2a(row as i32, column as i32).set_data_2a(&QVariant::from_bool(true), ITEM_IS_ADDED);
+                    }
+                }
+            }
+        }
+        blocker.unblock();
+        self.table_view.viewport().repaint();
+    }
+
     /// This function clears the markings for added/modified cells.
     pub unsafe fn clear_markings(&self) {
         let table_view = self.table_view_ptr();
@@ -56,6 +56,7 @@ use super::*;
 /// This struct contains the slots of the view of a Table PackedFile.
 pub struct TableViewSlots {
     pub delayed_updates: QBox<SlotNoArgs>,
+    pub delayed_diagnostics: QBox<SlotNoArgs>,
     pub sort_order_column_changed: QBox<SlotOfIntSortOrder>,
     pub show_context_menu: QBox<SlotOfQPoint>,
     pub context_menu_enabler: QBox<SlotOfQItemSelectionQItemSelection>,
@@ -118,30 +119,19 @@ impl TableViewSlots {
         packed_file_path: Option<Arc<RwLock<String>>>,
     ) -> Self {
 
-        // When we want to update the diagnostic/global search data of this table.
+        // When we want to update the global search data of this table.
         let delayed_updates = SlotNoArgs::new(&view.table_view, clone!(
             app_ui,
             pack_file_contents_ui,
-            diagnostics_ui,
             view => move || {
             info!("Triggering `Delayed Table Updates` By Slot");
 
             // Only save to the backend if both, the save and undo locks are disabled. Otherwise this will cause locks.
             if view.get_data_source() == DataSource::PackFile && !view.save_lock.load(Ordering::SeqCst) && !view.undo_lock.load(Ordering::SeqCst) {
                 if let Some(ref packed_file_path) = view.packed_file_path {
-                    let mut paths_to_check = vec![];
                     if let Some(packed_file) = UI_STATE.get_open_packedfiles().iter().find(|x| *x.path_read() == *packed_file_path.read().unwrap() && x.data_source() == DataSource::PackFile) {
                         if let Err(error) = packed_file.save(&app_ui, &pack_file_contents_ui) {
                             show_dialog(&view.table_view, error, false);
-                        } else if let Some(path) = view.get_packed_file_path() {
-                            paths_to_check.push(path);
-                        }
-                    }
-
-                    if setting_bool("diagnostics_trigger_on_table_edit") && diagnostics_ui.diagnostics_dock_widget().is_visible() {
-                        for path in &paths_to_check {
-                            let path_types = vec![ContainerPath::File(path.to_owned())];
-                            DiagnosticsUI::check_on_path(&app_ui, &diagnostics_ui, path_types);
                         }
                     }
                 }
@@ -151,6 +141,36 @@ impl TableViewSlots {
             view.update_line_counter();
         }));
 
+        // When we want to update the diagnostic data of this table. This runs on its own delay, so it can keep up with the edits.
+        let delayed_diagnostics = SlotNoArgs::new(&view.table_view, clone!(
+            app_ui,
+            pack_file_contents_ui,
+            diagnostics_ui,
+            view => move || {
+            info!("Triggering `Delayed Table Diagnostics` By Slot");
+
+            if !setting_bool("diagnostics_trigger_on_table_edit") || !diagnostics_ui.diagnostics_dock_widget().is_visible() {
+                return;
+            }
+
+            // Same locks as when saving. The check runs on the backend data, so the table has to be sent there first.
+            if view.get_data_source() == DataSource::PackFile && !view.save_lock.load(Ordering::SeqCst) && !view.undo_lock.load(Ordering::SeqCst) {
+                if let Some(ref packed_file_path) = view.packed_file_path {
+                    if let Some(packed_file) = UI_STATE.get_open_packedfiles().iter().find(|x| *x.path_read() == *packed_file_path.read().unwrap() && x.data_source() == DataSource::PackFile) {
+                        if let Err(error) = packed_file.save(&app_ui, &pack_file_contents_ui) {
+                            show_dialog(&view.table_view, error, false);
+                            return;
+                        }
+                    }
+
+                    if let Some(path) = view.get_packed_file_path() {
+                        let path_types = vec![ContainerPath::File(path)];
+                        DiagnosticsUI::check_on_path(&app_ui, &diagnostics_ui, path_types);
+                    }
+                }
+            }
+        }));
+
         let sort_order_column_changed = SlotOfIntSortOrder::new(&view.table_view, clone!(
             view => move |column, _| {
                 info!("Triggering `Sort Order` By Slot");
@@ -182,6 +202,17 @@ impl TableViewSlots {
                 if !view.undo_lock.load(Ordering::SeqCst) {
                     let item_old = view.undo_model.item_2a(item.row(), item.column());
 
+                    // RGBA colours are edited as text, so reject anything that's not a valid one before it reaches the table.
+                    let is_invalid_colour_rgba = view.table_definition().fields_processed().get(item.column() as usize)
+                        .map(|field| field.field_type() == &FieldType::ColourRGBA && !DecodedData::is_valid_colour_rgba(&item.text().to_std_string()))
+                        .unwrap_or(false);
+
+                    if is_invalid_colour_rgba {
+                        let _blocker = QSignalBlocker::from_q_object(&view.table_model);
+                        item.set_text(&item_old.text());
+                        return;
+                    }
+
                     // Only trigger this if the values are actually different. Checkable cells are tricky. Nested cells an go to hell.
                     if (item_old.text().compare_q_string(item.text().as_ref()) != 0 || item_old.check_state() != item.check_state()) ||
                         item_old.data_1a(ITEM_IS_SEQUENCE).to_bool() && 0 != item_old.data_1a(ITEM_SEQUENCE_DATA).to_string().compare_q_string(&item.data_1a(ITEM_SEQUENCE_DATA).to_string()) {
@@ -200,6 +231,11 @@ impl TableViewSlots {
                             let fields_processed = definition.fields_processed();
                             let field = &fields_processed[item.column() as usize];
 
+                            // Normalize edited floats while the model is blocked, so they don't retrigger this.
+                            if setting_bool("normalize_floats_on_edit") {
+                                normalize_float_item(item, field);
+                            }
+
                             // Update the lookup data while the model is blocked.
                             if setting_bool("enable_lookups") {
                                 let dependency_data = view.dependency_data.read().unwrap();
@@ -246,7 +282,7 @@ impl TableViewSlots {
                             if setting_bool("enable_icons") {
                                 if field.is_filename(patches) {
                                     let mut icons = BTreeMap::new();
-                                    let data = vec![vec![get_field_from_view(&view.table_model.static_upcast(), field, item.row(), item.column())]];
+                                    let data = vec![vec![get_field_from_view(&view.table_model.static_upcast(), field, patches, item.row(), item.column())]];
 
                                     if request_backend_files(&data, 0, &field, patches, &mut icons).is_ok() {
                                         if let Some(column_data) = icons.get(&0) {
@@ -522,6 +558,11 @@ impl TableViewSlots {
                                     _ => unimplemented!(),
                                 };
                                 let old_data = view.get_copy_of_table();
+                                let old_table = if setting_bool("table_mark_tsv_import_changes") {
+                                    get_table_from_view(&view.table_model_ptr(), &view.table_definition()).ok()
+                                } else {
+                                    None
+                                };
 
                                 view.undo_lock.store(true, Ordering::SeqCst);
 
@@ -540,6 +581,11 @@ impl TableViewSlots {
                                     view.get_data_source(),
                                 );
 
+                                // Mark what the import added or changed, so it can be reviewed before saving.
+                                if let Some(old_table) = old_table {
+                                    view.mark_imported_changes(&old_table.data());
+                                }
+
                                 // Prepare the diagnostic pass.
                                 view.start_delayed_updates_timer();
 
@@ -589,7 +635,8 @@ impl TableViewSlots {
                         let path = PathBuf::from(file_dialog.selected_files().at(0).to_std_string());
                         if let Some(packed_file) = UI_STATE.get_open_packedfiles().iter().find(|x| *x.path_read() == *packed_file_path.read().unwrap() && x.data_source() == DataSource::PackFile) {
                             if let Err(error) = packed_file.save(&app_ui, &pack_file_contents_ui) {
-                                return show_dialog(&view.table_view, error, false);
+                                show_dialog(&view.table_view, error, false);
+                            return;
                             }
                         }
 
@@ -754,6 +801,7 @@ impl TableViewSlots {
                 let freeze_slot = SlotOfInt::new(&view.table_view, clone!(
                     mut view => move |_| {
                         toggle_freezer_safe(&view.table_view, index as i32);
+                        view.save_frozen_columns();
                     }
                 ));
 
@@ -843,6 +891,7 @@ impl TableViewSlots {
         // Return the slots, so we can keep them alive for the duration of the view.
         Self {
             delayed_updates,
+            delayed_diagnostics,
             sort_order_column_changed,
             show_context_menu,
             context_menu_enabler,
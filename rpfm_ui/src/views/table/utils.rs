@@ -49,7 +49,7 @@ use std::sync::{atomic::AtomicPtr, RwLock};
 use rpfm_extensions::dependencies::TableReferences;
 
 use rpfm_lib::binary::WriteBytes;
-use rpfm_lib::files::{ContainerPath, RFileDecoded, table::Table};
+use rpfm_lib::files::{ContainerPath, RFileDecoded, table::{DEFAULT_FLOAT_PRECISION, FormatOptions, MAX_FLOAT_PRECISION, Table}};
 use rpfm_lib::integrations::log::*;
 use rpfm_lib::schema::{Definition, DefinitionPatch, Field, FieldType};
 
@@ -238,6 +238,30 @@ pub unsafe fn get_new_row(table_definition: &Definition) -> CppBox<QListOfQStand
 
 /// This function generates a *Default* StandardItem for the provided field.
 pub unsafe fn get_default_item_from_field(field: &Field, patches: Option<&DefinitionPatch>) -> CppBox<QStandardItem> {
+
+    // Unsigned integers need their default value parsed as unsigned.
+    if field.is_unsigned(patches) {
+        let mut data = DecodedData::new_from_type_and_value(field.field_type(), &None);
+        if let Some(default_value) = field.default_value(patches) {
+            let _ = data.set_data_as_unsigned(&default_value);
+        }
+
+        if let Some(qdata) = unsigned_integer_to_qvariant(&data) {
+            let item = QStandardItem::new();
+            item.set_tool_tip(&QString::from_std_str(tre("original_data", &[&data.data_to_string_as_unsigned()])));
+            item.set_data_2a(&QVariant::from_bool(true), ITEM_HAS_SOURCE_VALUE);
+            item.set_data_2a(&QVariant::from_bool(false), ITEM_IS_SEQUENCE);
+            item.set_data_2a(&qdata, ITEM_SOURCE_VALUE);
+            item.set_data_2a(&qdata, 2);
+
+            if field.is_key(patches) {
+                item.set_data_2a(&QVariant::from_bool(true), ITEM_IS_KEY);
+            }
+
+            return item;
+        }
+    }
+
     let item = match field.field_type() {
         FieldType::Boolean => {
             let item = QStandardItem::new();
@@ -357,15 +381,17 @@ pub unsafe fn get_default_item_from_field(field: &Field, patches: Option<&Defini
             item.set_data_2a(&QVariant::from_i64(data), 2);
             item
         },
-        FieldType::ColourRGB => {
+        FieldType::ColourRGB |
+        FieldType::ColourRGBA => {
+            let empty_colour = if field.field_type() == &FieldType::ColourRGBA { "00000000" } else { "000000" };
             let text = if let Some(default_value) = field.default_value(patches) {
                 if u32::from_str_radix(&default_value, 16).is_ok() {
                     default_value
                 } else {
-                    "000000".to_owned()
+                    empty_colour.to_owned()
                 }
             } else {
-                "000000".to_owned()
+                empty_colour.to_owned()
             };
             let item = QStandardItem::from_q_string(&QString::from_std_str(&text));
             item.set_tool_tip(&QString::from_std_str(tre("original_data", &[&text])));
@@ -474,6 +500,7 @@ pub unsafe fn load_data(
         let fields_processed = definition.fields_processed();
         let patches = Some(definition.patches());
         let keys = fields_processed.iter().enumerate().filter_map(|(x, y)| if y.is_key(patches) { Some(x as i32) } else { None }).collect::<Vec<i32>>();
+        let unsigned_columns = fields_processed.iter().map(|x| x.is_unsigned(patches)).collect::<Vec<bool>>();
         let enable_lookups = setting_bool("enable_lookups");
         let enable_icons = setting_bool("enable_icons");
 
@@ -495,7 +522,7 @@ pub unsafe fn load_data(
             qlist.reserve(entry.len() as i32);
 
             for (column, field) in entry.iter().enumerate() {
-                let item = get_item_from_decoded_data(field, &keys, column);
+                let item = get_item_from_decoded_data(field, &keys, column, unsigned_columns.get(column).copied().unwrap_or(false));
 
                 if data_source != DataSource::PackFile || (is_translator && qlist.count_0a() < 4) {
                     item.set_editable(false);
@@ -566,8 +593,23 @@ pub unsafe fn load_data(
     );
 }
 
+/// This function returns the QVariant used to store the provided integer in the items of unsigned fields, or `None` if it's not an integer.
+pub unsafe fn unsigned_integer_to_qvariant(data: &DecodedData) -> Option<CppBox<QVariant>> {
+    match data {
+        DecodedData::I16(data) |
+        DecodedData::OptionalI16(data) => Some(QVariant::from_uint(*data as u16 as u32)),
+        DecodedData::I32(data) |
+        DecodedData::OptionalI32(data) => Some(QVariant::from_uint(*data as u32)),
+        DecodedData::I64(data) |
+        DecodedData::OptionalI64(data) => Some(QVariant::from_u64(*data as u64)),
+        _ => None,
+    }
+}
+
 /// This function generates a StandardItem for the provided DecodedData.
-pub unsafe fn get_item_from_decoded_data(data: &DecodedData, keys: &[i32], column: usize) -> CppBox<QStandardItem> {
+///
+/// If `is_unsigned` is true, integers are stored in the item as unsigned values.
+pub unsafe fn get_item_from_decoded_data(data: &DecodedData, keys: &[i32], column: usize, is_unsigned: bool) -> CppBox<QStandardItem> {
     let item = match *data {
 
         // This one needs a couple of changes before turning it into an item in the table.
@@ -582,22 +624,12 @@ pub unsafe fn get_item_from_decoded_data(data: &DecodedData, keys: &[i32], colum
             item
         }
 
-        // Floats need to be tweaked to fix trailing zeroes and precision issues, like turning 0.5000004 into 0.5.
-        // Also, they should be limited to 3 decimals.
-        DecodedData::F32(ref data) => {
-            let (data, string) = {
-                let data_str = format!("{data}");
-                if let Some(position) = data_str.find('.') {
-                    let decimals = &data_str[position..].len();
-                    if *decimals > 4 {
-                        let data_str = format!("{data:.4}");
-                        (data_str.parse::<f32>().unwrap(), data_str) }
-                    else { (*data, data_str) }
-                }
-                else { (*data, data_str) }
-            };
+        // Floats are shown the same way they're exported, which fixes trailing zeroes and precision issues, like turning 0.5000004 into 0.5.
+        DecodedData::F32(ref value) => {
+            let string = data.to_export_string(&FormatOptions::default()).to_string();
+            let value = string.parse::<f32>().unwrap_or(*value);
 
-            let qdata = QVariant::from_float(data);
+            let qdata = QVariant::from_float(value);
             let item = QStandardItem::new();
             item.set_data_2a(ref_from_atomic(&QVARIANT_TRUE), ITEM_HAS_SOURCE_VALUE);
             item.set_data_2a(ref_from_atomic(&QVARIANT_FALSE), ITEM_IS_SEQUENCE);
@@ -606,20 +638,11 @@ pub unsafe fn get_item_from_decoded_data(data: &DecodedData, keys: &[i32], colum
             item
         },
 
-        DecodedData::F64(ref data) => {
-            let (data, string) = {
-                let data_str = format!("{data}");
-                if let Some(position) = data_str.find('.') {
-                    let decimals = &data_str[position..].len();
-                    if *decimals > 4 {
-                        let data_str = format!("{data:.4}");
-                        (data_str.parse::<f64>().unwrap(), data_str) }
-                    else { (*data, data_str) }
-                }
-                else { (*data, data_str) }
-            };
+        DecodedData::F64(ref value) => {
+            let string = data.to_export_string(&FormatOptions::default()).to_string();
+            let value = string.parse::<f64>().unwrap_or(*value);
 
-            let qdata = QVariant::from_double(data);
+            let qdata = QVariant::from_double(value);
             let item = QStandardItem::new();
             item.set_data_2a(ref_from_atomic(&QVARIANT_TRUE), ITEM_HAS_SOURCE_VALUE);
             item.set_data_2a(ref_from_atomic(&QVARIANT_FALSE), ITEM_IS_SEQUENCE);
@@ -630,7 +653,7 @@ pub unsafe fn get_item_from_decoded_data(data: &DecodedData, keys: &[i32], colum
         DecodedData::I16(ref data) |
         DecodedData::OptionalI16(ref data) => {
             let item = QStandardItem::new();
-            let qdata = QVariant::from_int(*data as i32);
+            let qdata = if is_unsigned { QVariant::from_uint(*data as u16 as u32) } else { QVariant::from_int(*data as i32) };
             item.set_data_2a(ref_from_atomic(&QVARIANT_TRUE), ITEM_HAS_SOURCE_VALUE);
             item.set_data_2a(ref_from_atomic(&QVARIANT_FALSE), ITEM_IS_SEQUENCE);
             item.set_data_2a(&qdata, ITEM_SOURCE_VALUE);
@@ -640,7 +663,7 @@ pub unsafe fn get_item_from_decoded_data(data: &DecodedData, keys: &[i32], colum
         DecodedData::I32(ref data) |
         DecodedData::OptionalI32(ref data) => {
             let item = QStandardItem::new();
-            let qdata = QVariant::from_int(*data);
+            let qdata = if is_unsigned { QVariant::from_uint(*data as u32) } else { QVariant::from_int(*data) };
             item.set_data_2a(ref_from_atomic(&QVARIANT_TRUE), ITEM_HAS_SOURCE_VALUE);
             item.set_data_2a(ref_from_atomic(&QVARIANT_FALSE), ITEM_IS_SEQUENCE);
             item.set_data_2a(&qdata, ITEM_SOURCE_VALUE);
@@ -650,7 +673,7 @@ pub unsafe fn get_item_from_decoded_data(data: &DecodedData, keys: &[i32], colum
         DecodedData::I64(ref data) |
         DecodedData::OptionalI64(ref data) => {
             let item = QStandardItem::new();
-            let qdata = QVariant::from_i64(*data);
+            let qdata = if is_unsigned { QVariant::from_u64(*data as u64) } else { QVariant::from_i64(*data) };
             item.set_data_2a(ref_from_atomic(&QVARIANT_TRUE), ITEM_HAS_SOURCE_VALUE);
             item.set_data_2a(ref_from_atomic(&QVARIANT_FALSE), ITEM_IS_SEQUENCE);
             item.set_data_2a(&qdata, ITEM_SOURCE_VALUE);
@@ -660,6 +683,7 @@ pub unsafe fn get_item_from_decoded_data(data: &DecodedData, keys: &[i32], colum
 
         // All these are Strings, so it can be together,
         DecodedData::ColourRGB(ref data) |
+        DecodedData::ColourRGBA(ref data) |
         DecodedData::StringU8(ref data) |
         DecodedData::StringU16(ref data) |
         DecodedData::OptionalStringU8(ref data) |
@@ -746,6 +770,7 @@ pub unsafe fn build_columns(
                 FieldType::OptionalI32 => table_view.set_column_width(index as i32, COLUMN_SIZE_NUMBER),
                 FieldType::OptionalI64 => table_view.set_column_width(index as i32, COLUMN_SIZE_NUMBER),
                 FieldType::ColourRGB => table_view.set_column_width(index as i32, COLUMN_SIZE_NUMBER),
+                FieldType::ColourRGBA => table_view.set_column_width(index as i32, COLUMN_SIZE_NUMBER),
                 FieldType::StringU8 => table_view.set_column_width(index as i32, COLUMN_SIZE_STRING),
                 FieldType::StringU16 => table_view.set_column_width(index as i32, COLUMN_SIZE_STRING),
                 FieldType::OptionalStringU8 => table_view.set_column_width(index as i32, COLUMN_SIZE_STRING),
@@ -780,7 +805,8 @@ pub unsafe fn build_columns(
                         FieldType::OptionalI16 |
                         FieldType::OptionalI32 |
                         FieldType::OptionalI64 |
-                        FieldType::ColourRGB => {
+                        FieldType::ColourRGB |
+                        FieldType::ColourRGBA => {
                             let mut size = model.horizontal_header_item(index as i32).text().length() * 6 + 40;
 
                             // Fix some columns getting their title eaten by description icon.
@@ -911,7 +937,7 @@ pub unsafe fn get_column_tooltips(
     let mut tooltips = vec![];
 
     // If we passed it a table name, build the tooltip based on it. The logic is simple:
-    // - If we have a description, we add it to the tooltip.
+    // - If we have a description, we add it to the tooltip. If not, we add the type of the field instead.
     // - If the column references another column, we add it to the tooltip.
     // - If the column is referenced by another column, we add it to the tooltip.
     if let Some(table_name) = table_name {
@@ -922,6 +948,8 @@ pub unsafe fn get_column_tooltips(
                 let mut tooltip_text = String::new();
                 if !field.description(patches).is_empty() {
                     tooltip_text.push_str(&format!("<p>{}</p>", field.description(patches)));
+                } else {
+                    tooltip_text.push_str(&format!("<p>{}</p>", tre("column_tooltip_field_type", &[&field.field_type().to_string()])));
                 }
 
                 if field.is_filename(patches) {
@@ -1054,9 +1082,11 @@ pub unsafe fn setup_item_delegates(
 ) {
     let table_object = table_view.static_upcast::<QObject>().as_ptr();
     let enable_lookups = setting_bool("enable_lookups");
+    let patches = Some(definition.patches());
 
     for (column, field) in definition.fields_processed().iter().enumerate() {
         let references = table_references.get(&(column as i32));
+        let is_unsigned = field.is_unsigned(patches);
 
         // Combos are a bit special, as they may or may not replace other delegates. If we disable them, use the normal delegates.
         if !setting_bool("disable_combos_on_tables") && references.is_some() || !field.enum_values().is_empty() {
@@ -1088,17 +1118,20 @@ pub unsafe fn setup_item_delegates(
                 FieldType::Boolean => new_generic_item_delegate_safe(&table_object, column as i32, &timer.as_ptr(), true),
                 FieldType::F32 => new_doublespinbox_item_delegate_safe(&table_object, column as i32, &timer.as_ptr(), true),
                 FieldType::F64 => new_doublespinbox_item_delegate_safe(&table_object, column as i32, &timer.as_ptr(), true),
-                FieldType::I16 => new_spinbox_item_delegate_safe(&table_object, column as i32, 16, &timer.as_ptr(), true),
-                FieldType::I32 => new_spinbox_item_delegate_safe(&table_object, column as i32, 32, &timer.as_ptr(), true),
+                FieldType::I16 => new_spinbox_item_delegate_safe(&table_object, column as i32, 16, is_unsigned, &timer.as_ptr(), true),
+                FieldType::I32 => new_spinbox_item_delegate_safe(&table_object, column as i32, 32, is_unsigned, &timer.as_ptr(), true),
 
                 // LongInteger uses normal string controls due to QSpinBox being limited to i32.
-                FieldType::I64 => new_spinbox_item_delegate_safe(&table_object, column as i32, 64, &timer.as_ptr(), true),
-                FieldType::OptionalI16 => new_spinbox_item_delegate_safe(&table_object, column as i32, 16, &timer.as_ptr(), true),
-                FieldType::OptionalI32 => new_spinbox_item_delegate_safe(&table_object, column as i32, 32, &timer.as_ptr(), true),
+                FieldType::I64 => new_spinbox_item_delegate_safe(&table_object, column as i32, 64, is_unsigned, &timer.as_ptr(), true),
+                FieldType::OptionalI16 => new_spinbox_item_delegate_safe(&table_object, column as i32, 16, is_unsigned, &timer.as_ptr(), true),
+                FieldType::OptionalI32 => new_spinbox_item_delegate_safe(&table_object, column as i32, 32, is_unsigned, &timer.as_ptr(), true),
 
                 // LongInteger uses normal string controls due to QSpinBox being limited to i32.
-                FieldType::OptionalI64 => new_spinbox_item_delegate_safe(&table_object, column as i32, 64, &timer.as_ptr(), true),
+                FieldType::OptionalI64 => new_spinbox_item_delegate_safe(&table_object, column as i32, 64, is_unsigned, &timer.as_ptr(), true),
                 FieldType::ColourRGB => new_colour_item_delegate_safe(&table_object, column as i32, &timer.as_ptr(), true),
+
+                // The colour delegate doesn't support alpha, so RGBA colours are edited as text.
+                FieldType::ColourRGBA => new_qstring_item_delegate_safe(&table_object, column as i32, &timer.as_ptr(), true),
                 FieldType::StringU8 |
                 FieldType::StringU16 |
                 FieldType::OptionalStringU8 |
@@ -1143,13 +1176,14 @@ pub unsafe fn get_table_from_view(
     definition: &Definition
 ) -> Result<Table> {
     let mut entries = vec![];
+    let patches = Some(definition.patches());
 
     for row in 0..model.row_count_0a() {
         let mut new_row: Vec<DecodedData> = vec![];
 
         // Bitwise columns can span across multiple columns. That means we have to keep track of the column ourselves.
         for (column, field) in definition.fields_processed().iter().enumerate() {
-            let item = get_field_from_view(model, field, row, column as i32);
+            let item = get_field_from_view(model, field, patches, row, column as i32);
             new_row.push(item);
         }
         entries.push(new_row);
@@ -1160,7 +1194,44 @@ pub unsafe fn get_table_from_view(
     Ok(table)
 }
 
-pub unsafe fn get_field_from_view(model: &QPtr<QStandardItemModel>, field: &Field, row: i32, column: i32) -> DecodedData {
+/// This function normalizes the value of an edited float item, so it's stored the same way it's shown and exported.
+///
+/// The normalized value is only applied if it's exactly the original one, so this never changes the actual value of the cell.
+pub unsafe fn normalize_float_item(item: Ptr<QStandardItem>, field: &Field) {
+    let mut format_options = FormatOptions::default();
+    let precision = setting_int("normalize_floats_precision").clamp(DEFAULT_FLOAT_PRECISION as i32, MAX_FLOAT_PRECISION as i32);
+    format_options.set_float_precision(precision as usize);
+
+    match field.field_type() {
+        FieldType::F32 => {
+            if let Some(DecodedData::F32(normalized)) = DecodedData::F32(item.data_1a(2).to_float_0a()).normalized_float(&format_options) {
+                item.set_data_2a(&QVariant::from_float(normalized), 2);
+            }
+        }
+        FieldType::F64 => {
+            if let Some(DecodedData::F64(normalized)) = DecodedData::F64(item.data_1a(2).to_double_0a()).normalized_float(&format_options) {
+                item.set_data_2a(&QVariant::from_double(normalized), 2);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub unsafe fn get_field_from_view(model: &QPtr<QStandardItemModel>, field: &Field, patches: Option<&DefinitionPatch>, row: i32, column: i32) -> DecodedData {
+
+    // Unsigned integers are stored in the items as unsigned values, so they need to be read as such.
+    if field.is_unsigned(patches) {
+        match field.field_type() {
+            FieldType::I16 => return DecodedData::I16(model.item_2a(row, column).data_1a(2).to_u_int_0a() as u16 as i16),
+            FieldType::I32 => return DecodedData::I32(model.item_2a(row, column).data_1a(2).to_u_int_0a() as i32),
+            FieldType::I64 => return DecodedData::I64(model.item_2a(row, column).data_1a(2).to_u_long_long_0a() as i64),
+            FieldType::OptionalI16 => return DecodedData::OptionalI16(model.item_2a(row, column).data_1a(2).to_u_int_0a() as u16 as i16),
+            FieldType::OptionalI32 => return DecodedData::OptionalI32(model.item_2a(row, column).data_1a(2).to_u_int_0a() as i32),
+            FieldType::OptionalI64 => return DecodedData::OptionalI64(model.item_2a(row, column).data_1a(2).to_u_long_long_0a() as i64),
+            _ => {}
+        }
+    }
+
     match field.field_type() {
 
         // This one needs a couple of changes before turning it into an item in the table.
@@ -1178,6 +1249,7 @@ pub unsafe fn get_field_from_view(model: &QPtr<QStandardItemModel>, field: &Fiel
 
         // Colours need parsing to turn them into integers.
         FieldType::ColourRGB => DecodedData::ColourRGB(QString::to_std_string(&model.item_2a(row, column as i32).text())),
+        FieldType::ColourRGBA => DecodedData::ColourRGBA(QString::to_std_string(&model.item_2a(row, column as i32).text())),
 
         // All these are just normal Strings.
         FieldType::StringU8 => DecodedData::StringU8(QString::to_std_string(&model.item_2a(row, column as i32).text())),